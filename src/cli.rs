@@ -0,0 +1,178 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+use crate::domain::routing::CaptureMode;
+use crate::domain::visuals::{VisualId, VisualKind};
+use crate::persistence::settings::{RenderBackend, UiSettings};
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: openmeters [OPTIONS]
+
+Options:
+  --profile <NAME>        load and save settings from profiles/<NAME>.json
+  --enable <VISUALS>      comma-separated visuals to enable
+  --disable <VISUALS>     comma-separated visuals to disable
+  --capture <MODE>        capture mode: applications | device
+  --device <NAME>         capture from the named device (implies --capture device)
+  --hidden                start with the main window minimized (not with the bar)
+  --renderer <BACKEND>    renderer: auto | gpu | gl | software
+  --doctor                check PipeWire, session, GPU and config, then exit
+  --screenshots <DIR>     render every visual from a test signal into DIR, then exit
+  -h, --help              print this help
+
+Visuals: loudness, oscilloscope, waveform, spectrogram, spectrum, stereometer, tuner";
+
+#[derive(Debug, Default)]
+pub struct CliOptions {
+    pub profile: Option<String>,
+    pub visuals: Vec<(VisualKind, bool)>,
+    pub capture_mode: Option<CaptureMode>,
+    pub device: Option<String>,
+    pub start_hidden: bool,
+    pub renderer: Option<RenderBackend>,
+    pub doctor: bool,
+    pub screenshots: Option<PathBuf>,
+    pub help: bool,
+}
+
+impl CliOptions {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut out = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_owned())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("missing value for {flag}"))
+            };
+            match flag {
+                "--profile" => out.profile = Some(value()?),
+                "--enable" | "--disable" => {
+                    let enabled = flag == "--enable";
+                    for name in value()?.split(',').filter(|n| !n.trim().is_empty()) {
+                        out.visuals.push((parse_visual(name)?, enabled));
+                    }
+                }
+                "--capture" => out.capture_mode = Some(parse_capture_mode(&value()?)?),
+                "--device" => out.device = Some(value()?),
+                "--hidden" => out.start_hidden = true,
+                "--renderer" => out.renderer = Some(parse_renderer(&value()?)?),
+                "--doctor" => out.doctor = true,
+                "--screenshots" => out.screenshots = Some(value()?.into()),
+                "-h" | "--help" => out.help = true,
+                _ => return Err(format!("unknown argument: {arg}")),
+            }
+        }
+        Ok(out)
+    }
+
+    /// Overrides applied on top of the loaded settings for this session.
+    pub fn apply(&self, settings: &mut UiSettings) {
+        for &(kind, enabled) in &self.visuals {
+            settings
+                .visuals
+                .modules
+                .entry(VisualId::primary(kind))
+                .or_default()
+                .enabled = Some(enabled);
+        }
+        if let Some(mode) = self.capture_mode {
+            settings.capture_mode = mode;
+        }
+        if let Some(device) = &self.device {
+            settings.capture_mode = CaptureMode::Device;
+            settings.last_device_name = Some(device.clone());
+        }
+        if let Some(renderer) = self.renderer {
+            settings.render_backend = renderer;
+        }
+    }
+}
+
+fn parse_visual(name: &str) -> Result<VisualKind, String> {
+    serde_json::from_value(name.trim().to_ascii_lowercase().into())
+        .map_err(|_| format!("unknown visual: {name}"))
+}
+
+fn parse_capture_mode(mode: &str) -> Result<CaptureMode, String> {
+    match mode.to_ascii_lowercase().as_str() {
+        "applications" | "apps" => Ok(CaptureMode::Applications),
+        "device" | "devices" => Ok(CaptureMode::Device),
+        _ => Err(format!("unknown capture mode: {mode}")),
+    }
+}
+
+fn parse_renderer(backend: &str) -> Result<RenderBackend, String> {
+    match backend.to_ascii_lowercase().as_str() {
+        "auto" => Ok(RenderBackend::Auto),
+        "gpu" | "wgpu" | "vulkan" => Ok(RenderBackend::Gpu),
+        "gl" | "opengl" => Ok(RenderBackend::Gl),
+        "software" | "cpu" => Ok(RenderBackend::Software),
+        _ => Err(format!("unknown renderer: {backend}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<CliOptions, String> {
+        CliOptions::parse(args.iter().map(|s| (*s).to_owned()))
+    }
+
+    #[test]
+    fn parses_flags_and_inline_values() {
+        let cli = parse(&[
+            "--profile=stream",
+            "--enable",
+            "spectrum,Loudness",
+            "--disable=waveform",
+            "--device",
+            "alsa_input.usb",
+            "--hidden",
+            "--renderer=software",
+            "--doctor",
+            "--screenshots",
+            "docs/shots",
+        ])
+        .unwrap();
+
+        assert_eq!(cli.profile.as_deref(), Some("stream"));
+        assert_eq!(
+            cli.visuals,
+            [
+                (VisualKind::Spectrum, true),
+                (VisualKind::Loudness, true),
+                (VisualKind::Waveform, false),
+            ]
+        );
+        assert!(cli.start_hidden);
+        assert!(cli.doctor);
+        assert_eq!(cli.screenshots, Some(PathBuf::from("docs/shots")));
+
+        let mut settings = UiSettings::default();
+        cli.apply(&mut settings);
+        assert_eq!(settings.capture_mode, CaptureMode::Device);
+        assert_eq!(settings.last_device_name.as_deref(), Some("alsa_input.usb"));
+        assert_eq!(settings.render_backend, RenderBackend::Software);
+        assert_eq!(
+            settings.visuals.modules[&VisualId::primary(VisualKind::Waveform)].enabled,
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_input() {
+        assert!(parse(&["--enable", "theremin"]).is_err());
+        assert!(parse(&["--capture", "loopback"]).is_err());
+        assert!(parse(&["--renderer", "directx"]).is_err());
+        assert!(parse(&["--profile"]).is_err());
+        assert!(parse(&["--frobnicate"]).is_err());
+    }
+}
@@ -0,0 +1,226 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// `openmeters --doctor`: probes what the app needs at startup and prints one
+// line per check, with a hint whenever something is off.
+
+use crate::infra::graphics;
+use crate::persistence::settings::settings_file;
+use pipewire as pw;
+use std::cell::RefCell;
+use std::process::ExitCode;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+const PIPEWIRE_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Ok,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug)]
+struct Check {
+    name: &'static str,
+    status: Status,
+    detail: String,
+    hint: Option<&'static str>,
+}
+
+impl Check {
+    fn new(name: &'static str, status: Status, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn hint(mut self, hint: &'static str) -> Self {
+        self.hint = Some(hint);
+        self
+    }
+}
+
+/// Runs every check and prints the results; fails if any check failed.
+pub fn run(profile: Option<&str>) -> ExitCode {
+    println!("OpenMeters {}", env!("CARGO_PKG_VERSION"));
+    let checks = [pipewire(), session(), gpu(), config(profile)];
+    for check in &checks {
+        let tag = match check.status {
+            Status::Ok => "ok",
+            Status::Warn => "warn",
+            Status::Fail => "FAIL",
+        };
+        println!("[{tag:>4}] {}: {}", check.name, check.detail);
+        if let Some(hint) = check.hint {
+            println!("       -> {hint}");
+        }
+    }
+    if checks.iter().any(|check| check.status == Status::Fail) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn pipewire() -> Check {
+    const HINT: &str = "start the daemon (systemctl --user start pipewire pipewire-pulse \
+                        wireplumber) and make sure XDG_RUNTIME_DIR is set";
+    match pipewire_server() {
+        Ok(Some((name, version))) => {
+            Check::new("PipeWire", Status::Ok, format!("{name} {version}"))
+        }
+        Ok(None) => Check::new(
+            "PipeWire",
+            Status::Fail,
+            "connected but the daemon never replied",
+        )
+        .hint(HINT),
+        Err(err) => {
+            Check::new("PipeWire", Status::Fail, format!("cannot connect: {err}")).hint(HINT)
+        }
+    }
+}
+
+// The core announces its name and version right after the connection handshake.
+fn pipewire_server() -> Result<Option<(String, String)>, pw::Error> {
+    pw::init();
+    let mainloop = pw::main_loop::MainLoopRc::new(None)?;
+    let context = pw::context::ContextRc::new(&mainloop, None)?;
+    let core = context.connect_rc(None)?;
+
+    let server: Rc<RefCell<Option<(String, String)>>> = Rc::default();
+    let _listener = {
+        let server = Rc::clone(&server);
+        core.add_listener_local()
+            .info(move |info| {
+                *server.borrow_mut() = Some((info.name().to_owned(), info.version().to_owned()));
+            })
+            .register()
+    };
+
+    let deadline = Instant::now() + PIPEWIRE_TIMEOUT;
+    let loop_ref = mainloop.loop_();
+    while server.borrow().is_none() && Instant::now() < deadline {
+        loop_ref.iterate(pw::loop_::Timeout::Finite(Duration::from_millis(50)));
+    }
+    Ok(server.take())
+}
+
+fn session() -> Check {
+    let var = |key| std::env::var(key).ok().filter(|value| !value.is_empty());
+    let mut check = session_check(
+        var("XDG_SESSION_TYPE").as_deref(),
+        var("WAYLAND_DISPLAY").as_deref(),
+        var("DISPLAY").as_deref(),
+    );
+    if check.status == Status::Ok && !crate::ui::layershell_available() {
+        check.status = Status::Warn;
+        check
+            .detail
+            .push_str("; no zwlr_layer_shell_v1, bar mode is unavailable");
+        check = check.hint("windowed mode works; bar mode needs a wlroots-style compositor");
+    }
+    check
+}
+
+fn session_check(kind: Option<&str>, wayland: Option<&str>, x11: Option<&str>) -> Check {
+    let kind = kind.unwrap_or("unknown");
+    match (wayland, x11) {
+        (Some(wayland), _) => Check::new(
+            "Session",
+            Status::Ok,
+            format!("{kind}, WAYLAND_DISPLAY={wayland}"),
+        ),
+        (None, Some(x11)) => Check::new("Session", Status::Warn, format!("{kind}, DISPLAY={x11}"))
+            .hint("windows work on X11; bar mode needs a Wayland compositor with layer-shell"),
+        (None, None) => Check::new(
+            "Session",
+            Status::Fail,
+            format!("{kind}, neither WAYLAND_DISPLAY nor DISPLAY is set"),
+        )
+        .hint("run from a graphical session, or export WAYLAND_DISPLAY/DISPLAY"),
+    }
+}
+
+fn gpu() -> Check {
+    let adapters = graphics::adapters();
+    let hardware = adapters
+        .iter()
+        .find(|adapter| adapter.device_type != wgpu::DeviceType::Cpu);
+    match (hardware, adapters.first()) {
+        (Some(adapter), _) => Check::new(
+            "GPU",
+            Status::Ok,
+            format!(
+                "{} ({:?}, {:?}, driver {} {})",
+                adapter.name,
+                adapter.device_type,
+                adapter.backend,
+                adapter.driver,
+                adapter.driver_info
+            ),
+        ),
+        (None, Some(adapter)) => Check::new(
+            "GPU",
+            Status::Warn,
+            format!("only a software adapter: {}", adapter.name),
+        )
+        .hint("visuals render on the CPU and may be slow; install your GPU's Vulkan driver"),
+        (None, None) => Check::new("GPU", Status::Warn, "no Vulkan adapter found")
+            .hint("install a Vulkan driver (e.g. mesa-vulkan-drivers); software rendering is used"),
+    }
+}
+
+fn config(profile: Option<&str>) -> Check {
+    let path = settings_file(profile);
+    let shown = path.display();
+    match std::fs::read_to_string(&path) {
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Check::new(
+            "Config",
+            Status::Ok,
+            format!("{shown} does not exist yet; defaults apply"),
+        ),
+        Err(err) => Check::new(
+            "Config",
+            Status::Fail,
+            format!("cannot read {shown}: {err}"),
+        )
+        .hint("check the file's permissions"),
+        Ok(text) => match serde_json::from_str::<serde_json::Value>(&text) {
+            Ok(value) if value.is_object() => Check::new("Config", Status::Ok, shown.to_string()),
+            Ok(_) => Check::new(
+                "Config",
+                Status::Fail,
+                format!("{shown} is not a JSON object"),
+            )
+            .hint("delete the file to start from defaults"),
+            Err(err) => Check::new("Config", Status::Fail, format!("{shown}: {err}"))
+                .hint("fix the syntax, or delete the file to start from defaults"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_prefers_wayland_and_flags_missing_displays() {
+        let wayland = session_check(Some("wayland"), Some("wayland-1"), Some(":0"));
+        assert_eq!(wayland.status, Status::Ok);
+        assert!(wayland.detail.contains("wayland-1"));
+
+        let x11 = session_check(Some("x11"), None, Some(":0"));
+        assert_eq!(x11.status, Status::Warn);
+        assert!(x11.hint.is_some());
+
+        let none = session_check(None, None, None);
+        assert_eq!(none.status, Status::Fail);
+        assert!(none.detail.starts_with("unknown"));
+    }
+}
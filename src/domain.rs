@@ -8,6 +8,11 @@ pub mod routing {
     pub enum RoutingCommand {
         SetApplicationEnabled { node_id: u32, enabled: bool },
         SetCaptureState(CaptureMode, DeviceSelection),
+        SetLoopbackSink(DeviceSelection),
+        SetLoopbackDelay(f32),
+        SetOutputGain(f32),
+        SetApplicationTrim { node_id: u32, gain: f32 },
+        ForceQuantum(u32),
     }
 
     crate::macros::choice_enum!(all pub enum CaptureMode { #[default] Applications => "Applications", Device => "Devices" });
@@ -35,15 +40,83 @@ pub mod routing {
         }
     }
 
+    /// Capture channels that can be routed; one bit each.
+    pub const MATRIX_INPUTS: usize = 32;
+
+    /// Which capture channels feed each analysis channel, one bitmask per
+    /// analysis channel. Several bits average those channels; an empty matrix
+    /// passes the capture through untouched.
+    #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(transparent)]
+    pub struct ChannelMatrix(pub Vec<u32>);
+
+    impl ChannelMatrix {
+        /// Capture 1/2 as L/R, the starting point when routing is switched on.
+        pub fn stereo() -> Self {
+            Self(vec![0b01, 0b10])
+        }
+
+        pub fn is_passthrough(&self) -> bool {
+            self.0.is_empty()
+        }
+
+        pub fn routes(&self, output: usize, input: usize) -> bool {
+            let mask = self.0.get(output).copied().unwrap_or(0);
+            input < MATRIX_INPUTS && (mask >> input) & 1 == 1
+        }
+
+        pub fn toggle(&mut self, output: usize, input: usize) {
+            if let Some(mask) = self.0.get_mut(output)
+                && input < MATRIX_INPUTS
+            {
+                *mask ^= 1 << input;
+            }
+        }
+
+        /// Capture channels the matrix reads, i.e. one past the highest routed.
+        pub fn inputs(&self) -> usize {
+            let all = self.0.iter().fold(0, |all, mask| all | mask);
+            (u32::BITS - all.leading_zeros()) as usize
+        }
+    }
+
+    /// Analysis channels that get their own mixdown weight.
+    pub const MIXDOWN_CHANNELS: usize = 8;
+
+    crate::macros::default_struct! {
+        /// How frames collapse to the mono (Mid) signal. Weights are relative
+        /// and missing ones count as 1, so the default is a plain average.
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        #[serde(default)]
+        pub struct Mixdown {
+            pub weights: Vec<f32> = Vec::new(),
+            /// Drops the fourth channel of 5.1 and wider layouts.
+            pub exclude_lfe: bool = false,
+        }
+    }
+
+    impl Mixdown {
+        pub fn is_average(&self) -> bool {
+            !self.exclude_lfe && self.weights.is_empty()
+        }
+    }
+
     #[derive(Debug, Clone)]
     pub struct RoutingConfig {
         pub capture_mode: CaptureMode,
         pub preferred_device: DeviceSelection,
+        /// Where the loopback plays in application mode.
+        pub loopback_sink: DeviceSelection,
+        /// Milliseconds the loopback lags the meters by.
+        pub loopback_delay_ms: f32,
     }
 }
 
 pub mod visuals {
-    crate::macros::choice_enum!(no_default
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::fmt;
+
+    crate::macros::choice_enum!(no_default all
         #[derive(PartialOrd, Ord)]
         pub enum VisualKind {
             Loudness => "Loudness",
@@ -52,6 +125,257 @@ pub mod visuals {
             Spectrogram => "Spectrogram",
             Spectrum => "Spectrum analyzer",
             Stereometer => "Stereometer",
+            Tuner => "Tuner",
         }
     );
+
+    impl VisualKind {
+        /// Serialized name, as used for settings keys.
+        pub const fn key(self) -> &'static str {
+            match self {
+                Self::Loudness => "loudness",
+                Self::Oscilloscope => "oscilloscope",
+                Self::Waveform => "waveform",
+                Self::Spectrogram => "spectrogram",
+                Self::Spectrum => "spectrum",
+                Self::Stereometer => "stereometer",
+                Self::Tuner => "tuner",
+            }
+        }
+    }
+
+    /// Instances of one kind, the built-in one included.
+    pub const MAX_INSTANCES: u8 = 4;
+
+    /// One visual in the layout. Instance 0 is the built-in one and keeps the
+    /// plain kind as its settings key; extras are keyed `spectrum#2` and so on.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct VisualId {
+        pub kind: VisualKind,
+        pub instance: u8,
+    }
+
+    impl VisualId {
+        pub const fn primary(kind: VisualKind) -> Self {
+            Self { kind, instance: 0 }
+        }
+
+        pub const fn is_primary(self) -> bool {
+            self.instance == 0
+        }
+
+        /// Distinct for every id, for keyed widget state.
+        pub const fn index(self) -> usize {
+            self.kind as usize * MAX_INSTANCES as usize + self.instance as usize
+        }
+
+        pub fn key(self) -> String {
+            if self.is_primary() {
+                self.kind.key().to_owned()
+            } else {
+                format!("{}#{}", self.kind.key(), self.instance + 1)
+            }
+        }
+
+        pub fn parse(key: &str) -> Option<Self> {
+            let (name, number) = match key.split_once('#') {
+                Some((name, number)) => (name, number.parse::<u8>().ok().filter(|&n| n > 1)?),
+                None => (key, 1),
+            };
+            let kind = VisualKind::ALL
+                .iter()
+                .copied()
+                .find(|kind| kind.key() == name)?;
+            (1..=MAX_INSTANCES).contains(&number).then_some(Self {
+                kind,
+                instance: number - 1,
+            })
+        }
+    }
+
+    impl From<VisualKind> for VisualId {
+        fn from(kind: VisualKind) -> Self {
+            Self::primary(kind)
+        }
+    }
+
+    impl fmt::Display for VisualId {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            if self.is_primary() {
+                f.write_str(self.kind.label())
+            } else {
+                write!(f, "{} {}", self.kind.label(), self.instance + 1)
+            }
+        }
+    }
+
+    impl Serialize for VisualId {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(&self.key())
+        }
+    }
+
+    impl<'de> Deserialize<'de> for VisualId {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let key = String::deserialize(deserializer)?;
+            Self::parse(&key)
+                .ok_or_else(|| serde::de::Error::custom(format!("unknown visual `{key}`")))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn ids_round_trip_and_primary_keys_match_the_kind() {
+            for &kind in VisualKind::ALL {
+                let name = serde_json::to_value(kind).unwrap();
+                assert_eq!(name, kind.key());
+                let id = VisualId::primary(kind);
+                assert_eq!(serde_json::to_value(id).unwrap(), name);
+                let extra = VisualId { kind, instance: 2 };
+                assert_eq!(VisualId::parse(&extra.key()), Some(extra));
+            }
+            let second = VisualId::parse("spectrum#2").unwrap();
+            assert_eq!(second.to_string(), "Spectrum analyzer 2");
+            for bad in ["spectrum#1", "spectrum#9", "spectrum#", "nope"] {
+                assert_eq!(VisualId::parse(bad), None, "{bad}");
+            }
+        }
+    }
+}
+
+pub mod automation {
+    use serde::{Deserialize, Serialize};
+
+    crate::macros::choice_enum!(all pub enum RuleMetric {
+        #[default] TruePeak => "True peak",
+        ShortTerm => "Short-term LUFS",
+        Momentary => "Momentary LUFS",
+    });
+    crate::macros::choice_enum!(all pub enum RuleCondition { #[default] Above => "Above", Below => "Below" });
+
+    impl RuleCondition {
+        pub fn holds(self, value: f32, threshold: f32) -> bool {
+            match self {
+                Self::Above => value > threshold,
+                Self::Below => value < threshold,
+            }
+        }
+    }
+    crate::macros::choice_enum!(all pub enum RuleAction {
+        #[default] SnapshotAndLog => "Spectrogram snapshot + log",
+        Log => "Log only",
+        Freeze => "Freeze a visual + save the lead-up",
+        RunCommand => "Run the alert command",
+    });
+
+    crate::macros::default_struct! {
+        #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+        #[serde(default)]
+        pub struct AutomationRule {
+            pub enabled: bool = true,
+            pub metric: RuleMetric = RuleMetric::TruePeak,
+            pub condition: RuleCondition = RuleCondition::Above,
+            pub threshold_db: f32 = -1.0,
+            pub action: RuleAction = RuleAction::SnapshotAndLog,
+            /// Visual held still by [`RuleAction::Freeze`].
+            pub freeze: super::visuals::VisualId =
+                super::visuals::VisualId::primary(super::visuals::VisualKind::Spectrogram),
+            /// Seconds of audio before the event saved with a freeze.
+            pub pre_seconds: f32 = 3.0,
+        }
+    }
+
+    crate::macros::default_struct! {
+        /// Short-term loudness alert, raised as a desktop notification.
+        #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+        #[serde(default)]
+        pub struct LoudnessAlert {
+            pub enabled: bool = false,
+            pub condition: RuleCondition = RuleCondition::Above,
+            pub target_lufs: f32 = -14.0,
+            pub hold_secs: f32 = 10.0,
+            pub cooldown_secs: f32 = 120.0,
+        }
+    }
+
+    crate::macros::default_struct! {
+        /// Shell command run by [`RuleAction::RunCommand`], with the measurement
+        /// in its environment. Nothing runs until `enabled` is set by hand;
+        /// it isn't saved, so every launch starts with it off.
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        #[serde(default)]
+        pub struct RuleCommand {
+            #[serde(skip)]
+            pub enabled: bool = false,
+            pub command: String = String::new(),
+            /// Shortest gap between two runs, across all rules.
+            pub min_interval_secs: f32 = 60.0,
+        }
+    }
+
+    pub const MIN_COMMAND_INTERVAL_SECS: f32 = 10.0;
+    pub const MAX_COMMAND_INTERVAL_SECS: f32 = 900.0;
+
+    impl RuleCommand {
+        /// `min_interval_secs` held to the range the settings page offers.
+        pub fn interval_secs(&self) -> f32 {
+            if self.min_interval_secs.is_finite() {
+                self.min_interval_secs
+                    .clamp(MIN_COMMAND_INTERVAL_SECS, MAX_COMMAND_INTERVAL_SECS)
+            } else {
+                Self::default().min_interval_secs
+            }
+        }
+    }
+
+    crate::macros::default_struct! {
+        /// Stops analysis once the watched application has been silent for
+        /// `silence_secs`, until audio returns.
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        #[serde(default)]
+        pub struct IdlePause {
+            pub enabled: bool = false,
+            /// Application name as shown in the capture list; empty watches
+            /// the whole capture.
+            pub application: String = String::new(),
+            pub silence_secs: f32 = 30.0,
+            /// Hides the main window's visuals while paused.
+            pub collapse: bool = false,
+        }
+    }
+
+    crate::macros::default_struct! {
+        /// Raises an alarm once the capture has been silent for `silence_secs`
+        /// while captured applications are playing.
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        #[serde(default)]
+        pub struct SilenceWatch {
+            pub enabled: bool = false,
+            pub silence_secs: f32 = 10.0,
+            pub notify: bool = true,
+            /// `host:port` for an OSC message; empty sends none.
+            pub osc_target: String = String::new(),
+        }
+    }
+
+    impl AutomationRule {
+        pub fn is_met(&self, value: f32) -> bool {
+            self.condition.holds(value, self.threshold_db)
+        }
+
+        pub fn describe(&self) -> String {
+            let unit = match self.metric {
+                RuleMetric::TruePeak => "dBTP",
+                RuleMetric::ShortTerm | RuleMetric::Momentary => "LUFS",
+            };
+            let condition = self.condition.label().to_ascii_lowercase();
+            format!(
+                "{} {condition} {:.1} {unit}",
+                self.metric, self.threshold_db
+            )
+        }
+    }
 }
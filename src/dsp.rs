@@ -1,7 +1,22 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // Copyright (C) 2026 Maika Namuo
 
+pub mod band_mute;
+pub mod calibration;
+pub mod clicks;
+pub mod delay;
+pub mod dynamic_range;
+pub mod gated_loudness;
+pub mod noise_floor;
+pub mod onset;
+pub mod pitch;
+pub mod replay;
+pub mod steady_tones;
+pub mod test_signal;
+
 use crate::util::audio::{flush_denormal_f32, sanitize_sample_rate};
+use realfft::RealToComplex;
+use rustfft::num_complex::Complex32;
 
 #[derive(Debug, Clone, Copy)]
 pub struct AudioBlock<'a> {
@@ -28,6 +43,24 @@ impl<'a> AudioBlock<'a> {
     }
 }
 
+/// Writes `|X[k]|^2` of the real FFT of `input` into `power`, clobbering
+/// `input`. Returns false if the FFT rejected the buffers.
+pub fn power_spectrum(
+    fft: &dyn RealToComplex<f32>,
+    input: &mut [f32],
+    spectrum: &mut [Complex32],
+    scratch: &mut [Complex32],
+    power: &mut [f32],
+) -> bool {
+    if fft.process_with_scratch(input, spectrum, scratch).is_err() {
+        return false;
+    }
+    for (power, bin) in power.iter_mut().zip(spectrum.iter()) {
+        *power = bin.norm_sqr();
+    }
+    true
+}
+
 /// Running means for several values over one or more independently sized windows.
 /// All windows share the ring sized for the longest duration.
 #[derive(Debug)]
@@ -123,6 +156,21 @@ impl Biquad {
         }
     }
 
+    /// Notch centred on the band's geometric mean, as wide as the band at -3 dB.
+    pub fn notch(sample_rate: f32, low: f32, high: f32) -> Self {
+        let centre = (low.max(0.0) * high.max(0.0)).sqrt();
+        let ratio = (centre / sample_rate).clamp(1.0e-6, 0.49);
+        let (sin, cos) = (core::f32::consts::TAU * ratio).sin_cos();
+        let q = (centre / (high - low).max(1.0e-3)).max(0.1);
+        let alpha = sin / (2.0 * q);
+        let inv_a0 = 1.0 / (1.0 + alpha);
+        Self {
+            b: [inv_a0, -2.0 * cos * inv_a0, inv_a0],
+            a: [-2.0 * cos * inv_a0, (1.0 - alpha) * inv_a0],
+            z: [0.0; 2],
+        }
+    }
+
     pub fn process(&mut self, sample: f32) -> f32 {
         let output = self.b[0].mul_add(sample, self.z[0]);
         self.z[0] = self.b[1] * sample - self.a[0] * output + self.z[1];
@@ -0,0 +1,97 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+use super::Biquad;
+
+/// Notches bands out of interleaved audio, each with a pair of notch filters
+/// per channel centred on the band and as wide as it.
+#[derive(Debug, Default)]
+pub struct BandMute {
+    bands: Vec<(f32, f32)>,
+    channels: usize,
+    sample_rate: f32,
+    // `channels` filter pairs per band, band by band.
+    filters: Vec<[Biquad; 2]>,
+    out: Vec<f32>,
+}
+
+impl BandMute {
+    /// Bands as (low, high) Hz; none passes audio through untouched.
+    pub fn set_bands(&mut self, bands: Vec<(f32, f32)>) {
+        if self.bands != bands {
+            self.bands = bands;
+            self.filters.clear();
+        }
+    }
+
+    pub fn process<'a>(
+        &'a mut self,
+        samples: &'a [f32],
+        channels: usize,
+        sample_rate: f32,
+    ) -> &'a [f32] {
+        if self.bands.is_empty() || channels == 0 {
+            return samples;
+        }
+        if self.filters.is_empty() || (channels, sample_rate) != (self.channels, self.sample_rate) {
+            (self.channels, self.sample_rate) = (channels, sample_rate);
+            self.filters = self
+                .bands
+                .iter()
+                .flat_map(|&(low, high)| {
+                    std::iter::repeat_n([Biquad::notch(sample_rate, low, high); 2], channels)
+                })
+                .collect();
+        }
+        self.out.clear();
+        self.out.extend_from_slice(samples);
+        for frame in self.out.chunks_exact_mut(channels) {
+            for band in self.filters.chunks_exact_mut(channels) {
+                for (sample, pair) in frame.iter_mut().zip(band) {
+                    *sample = pair
+                        .iter_mut()
+                        .fold(*sample, |value, notch| notch.process(value));
+                }
+            }
+        }
+        for pair in &mut self.filters {
+            pair.iter_mut().for_each(Biquad::flush_denormals);
+        }
+        &self.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rms_of(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn removes_the_band_and_passes_the_rest() {
+        const RATE: f32 = 48_000.0;
+        let tone = |hz: f32| -> Vec<f32> {
+            (0..RATE as usize)
+                .flat_map(|n| {
+                    let s = (std::f32::consts::TAU * hz * n as f32 / RATE).sin();
+                    [s, s]
+                })
+                .collect()
+        };
+        let (inside, outside) = (tone(1_000.0), tone(5_000.0));
+        let mut mute = BandMute::default();
+        mute.set_bands(vec![(900.0, 1_100.0)]);
+        let muted = mute.process(&inside, 2, RATE);
+        assert!(rms_of(&muted[RATE as usize..]) < 0.01);
+
+        let mut mute = BandMute::default();
+        mute.set_bands(vec![(900.0, 1_100.0)]);
+        let passed = mute.process(&outside, 2, RATE);
+        assert!(rms_of(&passed[RATE as usize..]) > 0.69);
+
+        mute.set_bands(Vec::new());
+        assert_eq!(mute.process(&inside, 2, RATE), inside.as_slice());
+    }
+}
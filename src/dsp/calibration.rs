@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Measurement-microphone calibration. A `.txt`/`.cal` file lists the mic's
+// response as `frequency dB [phase]` rows, after any header; the spectrum and
+// spectrogram divide it out of each bin's power while it is active.
+
+use crate::util::audio::db_to_power;
+
+// Corrections beyond this are a bad file or a dead band, not a mic.
+const MAX_CORRECTION_DB: f32 = 40.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalibrationCurve {
+    // (Hz, dB), ascending and unique in frequency.
+    points: Vec<(f32, f32)>,
+}
+
+impl CalibrationCurve {
+    /// `None` unless at least two rows have a positive frequency.
+    pub fn new(points: &[[f32; 2]]) -> Option<Self> {
+        let mut points: Vec<(f32, f32)> = points
+            .iter()
+            .filter(|[hz, db]| hz.is_finite() && *hz > 0.0 && db.is_finite())
+            .map(|&[hz, db]| (hz, db.clamp(-MAX_CORRECTION_DB, MAX_CORRECTION_DB)))
+            .collect();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        points.dedup_by(|b, a| a.0 == b.0);
+        (points.len() >= 2).then_some(Self { points })
+    }
+
+    /// Rows of a calibration file; headers, comments and the sensitivity
+    /// line are skipped since only the shape of the response is used.
+    pub fn parse(text: &str) -> Option<Vec<[f32; 2]>> {
+        let rows: Vec<[f32; 2]> = text
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line
+                    .split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+                    .filter(|field| !field.is_empty());
+                let hz = fields.next()?.parse().ok()?;
+                let db = fields.next()?.parse().ok()?;
+                Some([hz, db])
+            })
+            .collect();
+        Self::new(&rows).map(|_| rows)
+    }
+
+    /// The mic's response at `hz`, interpolated on a log-frequency axis and
+    /// held flat beyond the measured range.
+    pub fn response_db(&self, hz: f32) -> f32 {
+        let (first, last) = (self.points[0], self.points[self.points.len() - 1]);
+        if hz.is_nan() || hz <= first.0 {
+            return first.1;
+        }
+        if hz >= last.0 {
+            return last.1;
+        }
+        let upper = self.points.partition_point(|&(f, _)| f <= hz);
+        let ((f0, db0), (f1, db1)) = (self.points[upper - 1], self.points[upper]);
+        let t = (hz / f0).ln() / (f1 / f0).ln();
+        db0 + (db1 - db0) * t
+    }
+
+    /// Power gain that flattens the mic's response at `hz`.
+    pub fn correction(&self, hz: f32) -> f32 {
+        db_to_power(-self.response_db(hz))
+    }
+}
+
+/// Per-bin power scale for bins `bin_hz` apart, with `curve` folded into
+/// `base`; `true` when a curve was applied.
+pub fn apply_to_bins(
+    curve: Option<&CalibrationCurve>,
+    base: &[f32],
+    bin_hz: f32,
+    out: &mut Vec<f32>,
+) -> bool {
+    out.clear();
+    out.extend_from_slice(base);
+    let Some(curve) = curve else {
+        return false;
+    };
+    for (i, scale) in out.iter_mut().enumerate() {
+        *scale *= curve.correction(i as f32 * bin_hz);
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_umik_style_files_and_interpolates_on_log_frequency() {
+        let text = "\"Sens Factor =-0.7dB, AGain =18dB, SERNO: 7000000\"\n\
+                    * comment\n\
+                    Freq(Hz)\tSPL(dB)\tPhase(degrees)\n\
+                    100\t-2.0\t0\n\
+                    20.0 , 1.0\n\
+                    1000\t0.0\t0\n\
+                    1000\t5.0\t0\n";
+        let rows = CalibrationCurve::parse(text).unwrap();
+        assert_eq!(rows.len(), 4);
+        let curve = CalibrationCurve::new(&rows).unwrap();
+        assert_eq!(curve.response_db(10.0), 1.0);
+        assert_eq!(curve.response_db(20_000.0), 0.0);
+        // Halfway between 100 Hz and 1 kHz in log frequency.
+        assert!((curve.response_db(316.227_77) + 1.0).abs() < 1e-3);
+        assert!((curve.correction(100.0) - db_to_power(2.0)).abs() < 1e-4);
+
+        assert!(CalibrationCurve::parse("Freq SPL\n1000 0.0\n").is_none());
+        assert!(CalibrationCurve::new(&[[0.0, 1.0], [f32::NAN, 2.0], [50.0, 0.0]]).is_none());
+    }
+}
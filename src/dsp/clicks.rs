@@ -0,0 +1,100 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Clicks and pops: sample-to-sample jumps the signal's recent bandwidth
+// can't explain, as left by dropped or repeated buffers. The second
+// difference (the error of a straight-line prediction from the last two
+// samples) stays small for band-limited audio, scaling with its level and
+// high-frequency content; a discontinuity lands far above its running RMS.
+// The ringing after a click is held off so one event counts once, and so is
+// the start, until the RMS has settled.
+
+const RMS_SECONDS: f32 = 0.01;
+const HOLDOFF_SECONDS: f32 = 0.05;
+// Smaller residuals never count, which keeps dither and near silence quiet.
+const MIN_JUMP: f32 = 0.01;
+pub const MIN_CLICK_THRESHOLD: f32 = 6.0;
+pub const MAX_CLICK_THRESHOLD: f32 = 40.0;
+pub const DEFAULT_CLICK_THRESHOLD: f32 = 10.0;
+
+#[derive(Debug, Clone)]
+pub struct ClickDetector {
+    history: [f32; 2],
+    primed: u8,
+    mean_square: f32,
+    smoothing: f32,
+    holdoff: usize,
+    held: usize,
+    threshold: f32,
+}
+
+impl ClickDetector {
+    /// `threshold` is how far above the running RMS of the second difference
+    /// a residual has to land to count.
+    pub fn new(sample_rate: f32, threshold: f32) -> Self {
+        let sample_rate = sample_rate.max(1.0);
+        let holdoff = (HOLDOFF_SECONDS * sample_rate).round() as usize;
+        Self {
+            history: [0.0; 2],
+            primed: 0,
+            mean_square: 0.0,
+            smoothing: 1.0 - (-1.0 / (RMS_SECONDS * sample_rate)).exp(),
+            holdoff,
+            held: holdoff,
+            threshold: threshold.clamp(MIN_CLICK_THRESHOLD, MAX_CLICK_THRESHOLD),
+        }
+    }
+
+    /// Feeds one sample; true on the first sample of a click.
+    pub fn push(&mut self, sample: f32) -> bool {
+        if !sample.is_finite() {
+            self.primed = 0;
+            self.held = self.holdoff;
+            return false;
+        }
+        let [before, last] = self.history;
+        self.history = [last, sample];
+        if self.primed < 2 {
+            self.primed += 1;
+            return false;
+        }
+        let residual = (sample - 2.0 * last + before).abs();
+        let click = residual > MIN_JUMP && residual > self.threshold * self.mean_square.sqrt();
+        self.mean_square += self.smoothing * (residual * residual - self.mean_square);
+        if click {
+            let first = self.held == 0;
+            self.held = self.holdoff;
+            return first;
+        }
+        self.held = self.held.saturating_sub(1);
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::TAU;
+
+    #[test]
+    fn flags_a_skipped_buffer_once_but_not_loud_tones_or_noise() {
+        const RATE: f32 = 48_000.0;
+        let tone = |n: usize| 0.8 * (TAU * 1_000.0 * n as f32 / RATE).sin();
+        let mut detector = ClickDetector::new(RATE, DEFAULT_CLICK_THRESHOLD);
+        // Half a second of a loud tone, then 37 samples go missing.
+        let clicks: Vec<usize> = (0..24_000)
+            .chain(24_037..48_000)
+            .enumerate()
+            .filter_map(|(index, n)| detector.push(tone(n)).then_some(index))
+            .collect();
+        assert_eq!(clicks, [24_000]);
+
+        let mut detector = ClickDetector::new(RATE, DEFAULT_CLICK_THRESHOLD);
+        let mut seed = 1_u32;
+        let mut noise = || {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (seed >> 8) as f32 / (1 << 24) as f32 * 0.4 - 0.2
+        };
+        assert!((0..48_000).all(|_| !detector.push(noise())));
+    }
+}
@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+use std::collections::VecDeque;
+
+/// Delays interleaved audio by a whole number of frames. Changing the delay
+/// takes effect immediately: shrinking it releases the backlog in one go,
+/// growing it holds output back until enough input has arrived.
+#[derive(Debug, Default)]
+pub struct FrameDelay {
+    buffer: VecDeque<f32>,
+    channels: usize,
+}
+
+impl FrameDelay {
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    pub fn process(&mut self, samples: &[f32], channels: usize, frames: usize) -> Vec<f32> {
+        if channels != self.channels {
+            self.buffer.clear();
+            self.channels = channels;
+        }
+        self.buffer.extend(samples);
+        let ready = self.buffer.len().saturating_sub(frames * channels);
+        self.buffer.drain(..ready).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn holds_back_the_requested_frames() {
+        let mut delay = FrameDelay::default();
+        assert!(delay.process(&[1.0, -1.0], 2, 2).is_empty());
+        assert!(delay.process(&[2.0, -2.0], 2, 2).is_empty());
+        assert_eq!(delay.process(&[3.0, -3.0], 2, 2), [1.0, -1.0]);
+        assert_eq!(
+            delay.process(&[4.0, -4.0], 2, 0),
+            [2.0, -2.0, 3.0, -3.0, 4.0, -4.0]
+        );
+        assert!(delay.is_empty());
+
+        delay.process(&[5.0, -5.0], 2, 4);
+        assert_eq!(delay.process(&[6.0], 1, 0), [6.0]);
+    }
+}
@@ -0,0 +1,138 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Dynamic range the way DR meters report it: each channel is cut into 3 s
+// blocks, and its DR is the second-highest block peak over the RMS of the
+// loudest 20 % of blocks. Block RMS is scaled by sqrt(2) so a steady sine
+// reads DR 0. Channels are averaged. Only whole blocks count, so nothing is
+// reported for the first 3 s.
+
+const BLOCK_SECONDS: f64 = 3.0;
+const TOP_FRACTION: f64 = 0.2;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Block {
+    sum_squares: f64,
+    peak: f32,
+}
+
+#[derive(Debug, Default)]
+pub struct DynamicRange {
+    // Finished blocks per channel, as (scaled mean square, peak).
+    blocks: Vec<Vec<(f64, f32)>>,
+    current: Vec<Block>,
+    filled: usize,
+    seconds: f64,
+}
+
+impl DynamicRange {
+    /// Returns whether a block finished, i.e. whether `value` may have moved.
+    /// A change in channel count starts over.
+    pub fn push(&mut self, samples: &[f32], channels: usize, sample_rate: f32) -> bool {
+        let channels = channels.max(1);
+        if sample_rate <= 0.0 {
+            return false;
+        }
+        if self.current.len() != channels {
+            *self = Self {
+                blocks: vec![Vec::new(); channels],
+                current: vec![Block::default(); channels],
+                ..Self::default()
+            };
+        }
+        let block_frames = (BLOCK_SECONDS * f64::from(sample_rate)).round() as usize;
+        let mut finished = false;
+        for frame in samples.chunks_exact(channels) {
+            for (block, &sample) in self.current.iter_mut().zip(frame) {
+                block.sum_squares += f64::from(sample) * f64::from(sample);
+                block.peak = block.peak.max(sample.abs());
+            }
+            self.filled += 1;
+            if self.filled >= block_frames {
+                self.finish_block();
+                finished = true;
+            }
+        }
+        self.seconds += (samples.len() / channels) as f64 / f64::from(sample_rate);
+        finished
+    }
+
+    fn finish_block(&mut self) {
+        let frames = self.filled as f64;
+        for (blocks, block) in self.blocks.iter_mut().zip(&mut self.current) {
+            let block = std::mem::take(block);
+            blocks.push((2.0 * block.sum_squares / frames, block.peak));
+        }
+        self.filled = 0;
+    }
+
+    /// Seconds of audio measured so far.
+    pub fn seconds(&self) -> f64 {
+        self.seconds
+    }
+
+    /// DR in dB, or `None` until a whole block with signal has been seen.
+    pub fn value(&self) -> Option<f32> {
+        let channels: Vec<f64> = self
+            .blocks
+            .iter()
+            .map(Vec::as_slice)
+            .filter_map(channel_dr)
+            .collect();
+        (!channels.is_empty())
+            .then(|| (channels.iter().sum::<f64>() / channels.len() as f64) as f32)
+    }
+}
+
+fn channel_dr(blocks: &[(f64, f32)]) -> Option<f64> {
+    let mut squares: Vec<f64> = blocks.iter().map(|&(square, _)| square).collect();
+    squares.sort_unstable_by(|a, b| b.total_cmp(a));
+    let top = ((squares.len() as f64 * TOP_FRACTION).round() as usize).max(1);
+    let rms = (squares.get(..top)?.iter().sum::<f64>() / top as f64).sqrt();
+    let mut peaks: Vec<f32> = blocks.iter().map(|&(_, peak)| peak).collect();
+    peaks.sort_unstable_by(|a, b| b.total_cmp(a));
+    // The second-highest peak, so one stray click doesn't set the result.
+    let peak = f64::from(*peaks.get(1).or(peaks.first())?);
+    (rms > 0.0 && peak > 0.0).then(|| 20.0 * (peak / rms).log10())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: f32 = 8_000.0;
+
+    // 250 Hz: 32 samples a cycle, a whole number of cycles a block, and a
+    // sample on the crest.
+    fn tone(amplitude: f32, seconds: f32) -> Vec<f32> {
+        (0..(RATE * seconds) as usize)
+            .map(|n| amplitude * (std::f32::consts::TAU * (n % 32) as f32 / 32.0).sin())
+            .collect()
+    }
+
+    #[test]
+    fn sine_reads_zero_and_two_spikes_set_the_peak() {
+        let mut dr = DynamicRange::default();
+        assert!(!dr.push(&tone(0.5, 2.0), 1, RATE));
+        assert_eq!(dr.value(), None);
+        assert!(dr.push(&tone(0.5, 4.0), 1, RATE));
+        assert!(dr.value().unwrap().abs() < 0.01);
+        assert!((dr.seconds() - 6.0).abs() < 1e-9);
+
+        // One spike is ignored as the stray maximum; a second one counts.
+        let mut samples = tone(0.5, 30.0);
+        samples[1_000] = 1.0;
+        let mut dr = DynamicRange::default();
+        dr.push(&samples, 1, RATE);
+        assert!(dr.value().unwrap().abs() < 0.01);
+        samples[30_000] = 1.0;
+        let mut dr = DynamicRange::default();
+        dr.push(&samples, 1, RATE);
+        let expected = 20.0 * 2.0_f32.log10();
+        assert!((dr.value().unwrap() - expected).abs() < 0.01);
+
+        let mut dr = DynamicRange::default();
+        dr.push(&[0.0; 48_000], 2, RATE);
+        assert_eq!(dr.value(), None);
+    }
+}
@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Gated integrated loudness (ITU-R BS.1770-4) and loudness range (EBU Tech
+// 3342), built from momentary and short-term readings taken every 100 ms,
+// i.e. the standard's 75 % block overlap.
+
+const ABSOLUTE_GATE_LUFS: f32 = -70.0;
+const RELATIVE_GATE_LU: f32 = -10.0;
+const RANGE_RELATIVE_GATE_LU: f32 = -20.0;
+const RANGE_PERCENTILES: (f32, f32) = (0.10, 0.95);
+
+#[derive(Debug, Default, Clone)]
+pub struct GatedLoudness {
+    momentary: Vec<f32>,
+    short_term: Vec<f32>,
+}
+
+impl GatedLoudness {
+    /// Resumes from blocks saved by `blocks`.
+    pub fn from_blocks([momentary, short_term]: [Vec<f32>; 2]) -> Self {
+        let gate = |blocks: Vec<f32>| gated(&blocks, ABSOLUTE_GATE_LUFS);
+        Self {
+            momentary: gate(momentary),
+            short_term: gate(short_term),
+        }
+    }
+
+    /// Momentary and short-term blocks that passed the absolute gate.
+    pub fn blocks(&self) -> [&[f32]; 2] {
+        [&self.momentary, &self.short_term]
+    }
+
+    pub fn push_momentary(&mut self, lufs: f32) {
+        if lufs > ABSOLUTE_GATE_LUFS {
+            self.momentary.push(lufs);
+        }
+    }
+
+    pub fn push_short_term(&mut self, lufs: f32) {
+        if lufs > ABSOLUTE_GATE_LUFS {
+            self.short_term.push(lufs);
+        }
+    }
+
+    /// LUFS-I, or `None` until a block has passed the absolute gate.
+    pub fn integrated(&self) -> Option<f32> {
+        let gate = power_mean(&self.momentary)? + RELATIVE_GATE_LU;
+        power_mean(&gated(&self.momentary, gate))
+    }
+
+    /// LRA in LU, or `None` until two short-term values pass both gates.
+    pub fn range(&self) -> Option<f32> {
+        let gate = power_mean(&self.short_term)? + RANGE_RELATIVE_GATE_LU;
+        let mut values = gated(&self.short_term, gate);
+        if values.len() < 2 {
+            return None;
+        }
+        values.sort_unstable_by(f32::total_cmp);
+        let at = |p: f32| values[((values.len() - 1) as f32 * p).round() as usize];
+        Some(at(RANGE_PERCENTILES.1) - at(RANGE_PERCENTILES.0))
+    }
+}
+
+fn gated(values: &[f32], gate: f32) -> Vec<f32> {
+    values.iter().copied().filter(|&lufs| lufs > gate).collect()
+}
+
+// The K-weighting offset cancels out of a mean taken in the power domain.
+fn power_mean(values: &[f32]) -> Option<f32> {
+    if values.is_empty() {
+        return None;
+    }
+    let sum: f64 = values
+        .iter()
+        .map(|&lufs| 10.0_f64.powf(f64::from(lufs) / 10.0))
+        .sum();
+    Some((10.0 * (sum / values.len() as f64).log10()) as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gates_drop_silence_and_quiet_passages() {
+        let mut loudness = GatedLoudness::default();
+        assert_eq!(loudness.integrated(), None);
+        // 20 s at -20 LUFS, 10 s at -40 LUFS (below the relative gate), 10 s of silence.
+        for lufs in [[-20.0; 200].as_slice(), &[-40.0; 100], &[-99.9; 100]].concat() {
+            loudness.push_momentary(lufs);
+            loudness.push_short_term(lufs);
+        }
+        let integrated = loudness.integrated().unwrap();
+        assert!((integrated + 20.0).abs() < 1e-3, "{integrated}");
+        // -40 sits inside the LRA's 20 LU window, so the range spans both levels.
+        assert!((loudness.range().unwrap() - 20.0).abs() < 1e-3);
+
+        let mut steady = GatedLoudness::default();
+        for _ in 0..50 {
+            steady.push_short_term(-23.0);
+        }
+        assert_eq!(steady.range(), Some(0.0));
+    }
+}
@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Minimum-statistics noise floor (after Martin, 2001). Each bin's power is
+// smoothed over time and its minimum tracked across a sliding window. The
+// window is split into subwindows so the minimum can rise again once a loud
+// passage has scrolled out, while speech or music rarely holds a bin above the
+// noise for the whole window.
+
+use std::collections::VecDeque;
+
+const SMOOTHING_SECONDS: f32 = 0.05;
+const WINDOW_SECONDS: f32 = 3.0;
+const SUBWINDOWS: usize = 6;
+// The minimum of a smoothed noise periodogram sits below its mean.
+const BIAS: f32 = 1.5;
+
+#[derive(Debug, Default)]
+pub struct NoiseFloor {
+    smoothed: Vec<f32>,
+    running_min: Vec<f32>,
+    // Minima of completed subwindows, oldest first.
+    history: VecDeque<Vec<f32>>,
+    elapsed: f32,
+    estimate: Vec<f32>,
+}
+
+impl NoiseFloor {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Feeds one power spectrum `dt` seconds after the last and returns the
+    /// per-bin noise power estimate.
+    pub fn update(&mut self, power: &[f32], dt: f32) -> &[f32] {
+        if power.len() != self.smoothed.len() {
+            self.reset();
+            self.smoothed.extend_from_slice(power);
+            self.running_min.extend_from_slice(power);
+            self.estimate.resize(power.len(), 0.0);
+        } else {
+            let alpha = (-dt.max(0.0) / SMOOTHING_SECONDS).exp();
+            for ((smoothed, min), &power) in self
+                .smoothed
+                .iter_mut()
+                .zip(&mut self.running_min)
+                .zip(power)
+            {
+                *smoothed = alpha * *smoothed + (1.0 - alpha) * power;
+                *min = min.min(*smoothed);
+            }
+        }
+
+        self.elapsed += dt.max(0.0);
+        if self.elapsed >= WINDOW_SECONDS / SUBWINDOWS as f32 {
+            self.elapsed = 0.0;
+            let mut done = if self.history.len() >= SUBWINDOWS {
+                self.history.pop_front().unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+            done.clone_from(&self.running_min);
+            self.history.push_back(done);
+            self.running_min.copy_from_slice(&self.smoothed);
+        }
+
+        for (bin, estimate) in self.estimate.iter_mut().enumerate() {
+            let min = self
+                .history
+                .iter()
+                .fold(self.running_min[bin], |min, window| min.min(window[bin]));
+            *estimate = BIAS * min;
+        }
+        &self.estimate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignores_loud_passages_until_they_outlast_the_window() {
+        const DT: f32 = 0.01;
+        let mut floor = NoiseFloor::default();
+        let mut run = |power: [f32; 2], seconds: f32| {
+            let mut last = [0.0; 2];
+            for _ in 0..(seconds / DT) as usize {
+                last.copy_from_slice(floor.update(&power, DT));
+            }
+            last
+        };
+
+        let quiet = run([1e-4, 1e-4], 2.0);
+        assert!(quiet.iter().all(|&e| (e - BIAS * 1e-4).abs() < 1e-6));
+
+        // A second of signal in bin 1 leaves its floor where it was.
+        let loud = run([1e-4, 1.0], 1.0);
+        assert!((loud[1] - quiet[1]).abs() < 1e-6);
+
+        // Held past the window it becomes the new floor.
+        let held = run([1e-4, 1.0], WINDOW_SECONDS + 1.0);
+        assert!((held[1] - BIAS).abs() < 0.01, "{}", held[1]);
+        assert!((held[0] - quiet[0]).abs() < 1e-6);
+
+        // A drop in level shows up as soon as the smoothing settles.
+        let after = run([1e-4, 1e-4], 3.0);
+        assert!((after[1] - quiet[1]).abs() < 1e-5);
+    }
+}
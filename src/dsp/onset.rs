@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Beat onsets from the low end, where kick drums and bass notes carry the
+// beat in most music. The level of a low-passed mono mix is read every 10 ms,
+// and a reading that is still climbing and lands well above the recent
+// average counts as an onset. Decaying tails never climb, and the holdoff
+// caps the rate at 400 a minute.
+
+use crate::util::audio::power_to_db;
+
+const CUTOFF_HZ: f32 = 150.0;
+const FRAME_SECONDS: f32 = 0.01;
+const AVERAGE_SECONDS: f32 = 0.5;
+const HOLDOFF_SECONDS: f32 = 0.15;
+const RISE_DB: f32 = 6.0;
+// The climb from one reading to the next that marks an attack.
+const ATTACK_DB: f32 = 2.0;
+// Quieter frames never count, which keeps noise and fade-outs still.
+const MIN_DB: f32 = -50.0;
+
+#[derive(Debug, Clone)]
+pub struct OnsetDetector {
+    sample_rate: f32,
+    lowpass: [f32; 2],
+    coefficient: f32,
+    frame_len: usize,
+    filled: usize,
+    // Mean square over about one frame.
+    envelope: f32,
+    last_db: f32,
+    average_db: f32,
+    smoothing: f32,
+    holdoff: usize,
+    held: usize,
+}
+
+impl OnsetDetector {
+    pub fn new(sample_rate: f32) -> Self {
+        let sample_rate = sample_rate.max(1.0);
+        let frame_len = ((FRAME_SECONDS * sample_rate).round() as usize).max(1);
+        let frames_per_second = sample_rate / frame_len as f32;
+        Self {
+            sample_rate,
+            lowpass: [0.0; 2],
+            coefficient: 1.0 - (-std::f32::consts::TAU * CUTOFF_HZ / sample_rate).exp(),
+            frame_len,
+            filled: 0,
+            envelope: 0.0,
+            last_db: MIN_DB,
+            average_db: MIN_DB,
+            smoothing: 1.0 - (-1.0 / (AVERAGE_SECONDS * frames_per_second)).exp(),
+            holdoff: (HOLDOFF_SECONDS * frames_per_second).round() as usize,
+            held: 0,
+        }
+    }
+
+    pub fn sample_rate(&self) -> f32 {
+        self.sample_rate
+    }
+
+    /// Feeds interleaved samples; true if an onset started among them.
+    pub fn push(&mut self, samples: &[f32], channels: usize) -> bool {
+        let channels = channels.max(1);
+        let mut onset = false;
+        for frame in samples.chunks_exact(channels) {
+            let mono = frame.iter().sum::<f32>() / channels as f32;
+            if !mono.is_finite() {
+                continue;
+            }
+            // Two one-pole stages roll off the mids at 12 dB per octave.
+            let [first, second] = &mut self.lowpass;
+            *first += self.coefficient * (mono - *first);
+            *second += self.coefficient * (*first - *second);
+            self.envelope += (*second * *second - self.envelope) / self.frame_len as f32;
+            self.filled += 1;
+            if self.filled == self.frame_len {
+                onset |= self.frame(power_to_db(self.envelope, MIN_DB));
+                self.filled = 0;
+            }
+        }
+        onset
+    }
+
+    fn frame(&mut self, db: f32) -> bool {
+        let onset = self.held == 0
+            && db > MIN_DB
+            && db > self.last_db + ATTACK_DB
+            && db > self.average_db + RISE_DB;
+        self.last_db = db;
+        self.average_db += self.smoothing * (db - self.average_db);
+        if onset {
+            self.held = self.holdoff;
+        } else {
+            self.held = self.held.saturating_sub(1);
+        }
+        onset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::TAU;
+
+    #[test]
+    fn counts_kicks_once_each_but_not_a_steady_bassline() {
+        const RATE: f32 = 48_000.0;
+        let kick = |n: usize| {
+            // 120 BPM: a decaying 60 Hz thump every half second.
+            let t = (n % 24_000) as f32 / RATE;
+            0.8 * (TAU * 60.0 * t).sin() * (-t / 0.08).exp()
+        };
+        let bass = |n: usize| 0.2 * (TAU * 55.0 * n as f32 / RATE).sin();
+        let count = |signal: &dyn Fn(usize) -> f32| {
+            let mut detector = OnsetDetector::new(RATE);
+            let samples: Vec<f32> = (0..4 * RATE as usize)
+                .flat_map(|n| [signal(n); 2])
+                .collect();
+            samples
+                .chunks(960)
+                .filter(|chunk| detector.push(chunk, 2))
+                .count()
+        };
+        assert_eq!(count(&|n| kick(n) + bass(n) * 0.3), 8);
+        // Only the bassline's first note stands out.
+        assert_eq!(count(&bass), 1);
+    }
+}
@@ -0,0 +1,193 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Monophonic pitch from spectral peaks, e.g. reassigned spectrogram points,
+// which already sit on their instantaneous frequencies. Each frame the peaks
+// vote for every fundamental they could be a harmonic of, and the candidate
+// explaining the most power wins. Half (or a third of) the true pitch
+// explains the same harmonics, so the highest candidate that keeps nearly all
+// of the best score is taken instead. A median over the last few frames
+// drops the octave slips that still get through.
+
+use std::collections::VecDeque;
+
+const MIN_HZ: f32 = 27.5;
+const MAX_HZ: f32 = 4_200.0;
+const MAX_PEAKS: usize = 8;
+const MAX_DIVISOR: u32 = 4;
+const MAX_HARMONIC: f32 = 16.0;
+// Points more than 40 dB under the loudest don't vote.
+const PEAK_RANGE: f32 = 1.0e-4;
+const MERGE_SEMITONES: f32 = 0.25;
+const MATCH_SEMITONES: f32 = 0.3;
+const OCTAVE_KEEP: f32 = 0.9;
+// Share of the peak power a pitch has to explain for the frame to be voiced.
+const MIN_CLARITY: f32 = 0.6;
+const MEDIAN_FRAMES: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pitch {
+    pub hz: f32,
+    /// Share of the frame's peak power the pitch explains, `0..=1`.
+    pub clarity: f32,
+}
+
+#[derive(Debug, Default)]
+pub struct PitchTracker {
+    points: Vec<(f32, f32)>,
+    peaks: Vec<(f32, f32)>,
+    recent: VecDeque<Option<Pitch>>,
+}
+
+fn semitones(ratio: f32) -> f32 {
+    12.0 * ratio.log2()
+}
+
+impl PitchTracker {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Feeds one frame of `(hz, power)` points and returns the smoothed pitch.
+    pub fn push(&mut self, points: impl IntoIterator<Item = (f32, f32)>) -> Option<Pitch> {
+        let frame = self.estimate(points);
+        if self.recent.len() == MEDIAN_FRAMES {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(frame);
+        self.current()
+    }
+
+    /// Median of the voiced recent frames, while most of them are voiced.
+    pub fn current(&self) -> Option<Pitch> {
+        let mut voiced: Vec<Pitch> = self.recent.iter().flatten().copied().collect();
+        if voiced.len() * 2 <= self.recent.len() {
+            return None;
+        }
+        voiced.sort_by(|a, b| a.hz.total_cmp(&b.hz));
+        Some(voiced[voiced.len() / 2])
+    }
+
+    fn estimate(&mut self, points: impl IntoIterator<Item = (f32, f32)>) -> Option<Pitch> {
+        self.points.clear();
+        self.points
+            .extend(points.into_iter().filter(|&(hz, power)| {
+                hz.is_finite() && hz >= MIN_HZ && power.is_finite() && power > 0.0
+            }));
+        let loudest = self
+            .points
+            .iter()
+            .map(|&(_, power)| power)
+            .fold(0.0, f32::max);
+        self.points
+            .retain(|&(_, power)| power >= loudest * PEAK_RANGE);
+        self.points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        // Neighbouring points merge into power-weighted peaks.
+        self.peaks.clear();
+        for &(hz, power) in &self.points {
+            match self.peaks.last_mut() {
+                Some((sum_hz, sum_power))
+                    if semitones(hz * *sum_power / *sum_hz) < MERGE_SEMITONES =>
+                {
+                    *sum_hz += hz * power;
+                    *sum_power += power;
+                }
+                _ => self.peaks.push((hz * power, power)),
+            }
+        }
+        for (sum_hz, sum_power) in &mut self.peaks {
+            *sum_hz /= *sum_power;
+        }
+        self.peaks.sort_by(|a, b| b.1.total_cmp(&a.1));
+        self.peaks.truncate(MAX_PEAKS);
+        let total: f32 = self.peaks.iter().map(|&(_, power)| power).sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let peaks = &self.peaks;
+        let harmonic = |f0: f32, hz: f32| {
+            let n = (hz / f0).round();
+            (1.0..=MAX_HARMONIC).contains(&n) && semitones(hz / (f0 * n)).abs() < MATCH_SEMITONES
+        };
+        let score = |f0: f32| -> f32 {
+            peaks
+                .iter()
+                .filter(|&&(hz, _)| harmonic(f0, hz))
+                .map(|&(_, power)| power)
+                .sum()
+        };
+        let candidates = peaks
+            .iter()
+            .flat_map(|&(hz, _)| (1..=MAX_DIVISOR).map(move |d| hz / d as f32))
+            .filter(|f0| (MIN_HZ..=MAX_HZ).contains(f0))
+            .map(|f0| (f0, score(f0)))
+            .collect::<Vec<_>>();
+        let best = candidates.iter().map(|&(_, s)| s).fold(0.0, f32::max);
+        let (f0, explained) = candidates
+            .into_iter()
+            .filter(|&(_, s)| s >= best * OCTAVE_KEEP)
+            .max_by(|a, b| a.0.total_cmp(&b.0))?;
+        let clarity = explained / total;
+        if clarity < MIN_CLARITY {
+            return None;
+        }
+
+        // The matched peaks, each divided by its harmonic number, pin the
+        // pitch down finer than the candidate they voted for.
+        let (sum, weight) = peaks
+            .iter()
+            .filter(|&&(hz, _)| harmonic(f0, hz))
+            .fold((0.0, 0.0), |(sum, weight), &(hz, power)| {
+                (sum + power * hz / (hz / f0).round(), weight + power)
+            });
+        Some(Pitch {
+            hz: sum / weight,
+            clarity,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn harmonics(f0: f32, numbers: &[u32]) -> Vec<(f32, f32)> {
+        numbers
+            .iter()
+            .map(|&n| (f0 * n as f32, 1.0 / n as f32))
+            .collect()
+    }
+
+    #[test]
+    fn finds_missing_fundamentals_and_rides_out_octave_slips() {
+        let mut tracker = PitchTracker::default();
+        let hz = |pitch: Option<Pitch>| pitch.map(|p| p.hz);
+        // A4 without its fundamental still reads as A4, not as A5 or A3.
+        for _ in 0..3 {
+            tracker.push(harmonics(440.0, &[2, 3, 4, 5, 6]));
+        }
+        assert!((hz(tracker.current()).unwrap() - 440.0).abs() < 0.01);
+
+        // Two frames of a lone second harmonic are outvoted.
+        tracker.reset();
+        for points in [
+            harmonics(110.0, &[1, 2, 3, 4]),
+            harmonics(220.0, &[1]),
+            harmonics(110.0, &[1, 2, 3, 4]),
+            harmonics(220.0, &[1]),
+            harmonics(110.0, &[1, 2, 3, 4]),
+        ] {
+            tracker.push(points);
+        }
+        assert!((hz(tracker.current()).unwrap() - 110.0).abs() < 0.01);
+
+        // Inharmonic clutter is unvoiced.
+        tracker.reset();
+        for _ in 0..3 {
+            tracker.push([(100.0, 1.0), (137.0, 1.0), (291.0, 1.0), (533.0, 1.0)]);
+        }
+        assert_eq!(tracker.current(), None);
+    }
+}
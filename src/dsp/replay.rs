@@ -0,0 +1,144 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Rolling capture of recent input, replayed slowed down by linear resampling.
+
+use crate::util::lerp;
+use std::collections::VecDeque;
+
+pub const REPLAY_SECONDS: f32 = 5.0;
+pub const REPLAY_SPEED: f64 = 0.25;
+
+#[derive(Debug, Default)]
+pub struct CaptureRing {
+    samples: VecDeque<f32>,
+    channels: usize,
+    sample_rate: f32,
+}
+
+impl CaptureRing {
+    pub fn push(&mut self, samples: &[f32], channels: usize, sample_rate: f32) {
+        let channels = channels.max(1);
+        if channels != self.channels || sample_rate != self.sample_rate {
+            self.samples.clear();
+            self.channels = channels;
+            self.sample_rate = sample_rate;
+        }
+        let capacity = (REPLAY_SECONDS * sample_rate) as usize * channels;
+        self.samples
+            .extend(&samples[..samples.len() / channels * channels]);
+        let excess = self.samples.len().saturating_sub(capacity);
+        self.samples.drain(..excess);
+    }
+
+    /// The newest `seconds` of capture, with its channel count and rate.
+    pub fn recent(&self, seconds: f32) -> Option<(Vec<f32>, usize, f32)> {
+        let frames = (seconds.max(0.0) * self.sample_rate) as usize;
+        let len = (frames * self.channels).min(self.samples.len());
+        (len > 0).then(|| {
+            let start = self.samples.len() - len;
+            (
+                self.samples.range(start..).copied().collect(),
+                self.channels,
+                self.sample_rate,
+            )
+        })
+    }
+
+    pub fn replay(&self, speed: f64) -> Option<SlowReplay> {
+        (self.samples.len() >= 2 * self.channels && speed > 0.0).then(|| SlowReplay {
+            samples: self.samples.iter().copied().collect(),
+            channels: self.channels,
+            sample_rate: self.sample_rate,
+            step: speed,
+            position: 0.0,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct SlowReplay {
+    samples: Vec<f32>,
+    channels: usize,
+    sample_rate: f32,
+    step: f64,
+    position: f64,
+}
+
+impl SlowReplay {
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    pub fn sample_rate(&self) -> f32 {
+        self.sample_rate
+    }
+
+    fn frame_count(&self) -> usize {
+        self.samples.len() / self.channels
+    }
+
+    /// Up to `frames` output frames; `None` once the capture has been played out.
+    pub fn next_chunk(&mut self, frames: usize) -> Option<Vec<f32>> {
+        let last = self.frame_count().checked_sub(1)?;
+        if self.position >= last as f64 {
+            return None;
+        }
+        let mut out = Vec::with_capacity(frames * self.channels);
+        for _ in 0..frames {
+            let index = self.position as usize;
+            if index >= last {
+                break;
+            }
+            let t = (self.position - index as f64) as f32;
+            let (a, b) = (index * self.channels, (index + 1) * self.channels);
+            for channel in 0..self.channels {
+                out.push(lerp(
+                    self.samples[a + channel],
+                    self.samples[b + channel],
+                    t,
+                ));
+            }
+            self.position += self.step;
+        }
+        Some(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replays_the_newest_capture_slowed_down() {
+        let mut ring = CaptureRing::default();
+        let rate = 4.0;
+        let ramp: Vec<f32> = (0..40).flat_map(|i| [i as f32, -(i as f32)]).collect();
+        ring.push(&ramp, 2, rate);
+        // Only the last REPLAY_SECONDS (20 frames) are kept.
+        assert_eq!(ring.samples.len(), 40);
+        assert_eq!(ring.samples[0], 20.0);
+
+        let mut replay = ring.replay(REPLAY_SPEED).unwrap();
+        let chunk = replay.next_chunk(5).unwrap();
+        assert_eq!(
+            chunk,
+            [
+                20.0, -20.0, 20.25, -20.25, 20.5, -20.5, 20.75, -20.75, 21.0, -21.0
+            ]
+        );
+
+        let mut frames = 5;
+        while let Some(chunk) = replay.next_chunk(7) {
+            frames += chunk.len() / 2;
+        }
+        assert_eq!(frames, 19 * 4);
+
+        let (recent, channels, _) = ring.recent(1.0).unwrap();
+        assert_eq!((recent.len(), recent[0], channels), (8, 36.0, 2));
+        assert_eq!(ring.recent(60.0).unwrap().0.len(), 40);
+
+        ring.push(&[1.0], 1, rate);
+        assert!(ring.replay(REPLAY_SPEED).is_none());
+    }
+}
@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Long-lived narrowband components (mains hum, whistles, coil whine). A bin
+// counts as tonal when it peaks above the mean level of its neighbourhood;
+// a tone is reported once that has held for long enough. Held time carries
+// over from adjacent bins so slow drift doesn't restart the count.
+
+const GUARD_BINS: usize = 3;
+const SPAN_BINS: usize = 16;
+// A missed frame costs this many seconds of held time per second, so
+// brief dropouts don't restart the count but a finished tone is forgotten.
+const DROPOUT_RATE: f32 = 4.0;
+pub const MAX_TONES: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tone {
+    pub hz: f32,
+    pub seconds: f32,
+    pub prominence_db: f32,
+}
+
+#[derive(Debug, Default)]
+pub struct SteadyTones {
+    held: Vec<f32>,
+    next: Vec<f32>,
+    sums: Vec<f64>,
+    tones: Vec<Tone>,
+}
+
+impl SteadyTones {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn tones(&self) -> &[Tone] {
+        &self.tones
+    }
+
+    /// Feeds one dB spectrum with bins `bin_hz` apart, `dt` seconds after the
+    /// last. Bins at least `threshold_db` above their surroundings for
+    /// `min_seconds` are reported, most prominent first.
+    pub fn update(
+        &mut self,
+        db: &[f32],
+        bin_hz: f32,
+        dt: f32,
+        threshold_db: f32,
+        min_seconds: f32,
+    ) -> &[Tone] {
+        let n = db.len();
+        if n != self.held.len() {
+            self.reset();
+            self.held.resize(n, 0.0);
+        }
+        self.next.clear();
+        self.next.resize(n, 0.0);
+        self.tones.clear();
+        if n < 3 {
+            return &self.tones;
+        }
+        self.sums.clear();
+        self.sums.push(0.0);
+        for &level in db {
+            let last = self.sums[self.sums.len() - 1];
+            self.sums.push(last + f64::from(level));
+        }
+        let range_mean = |lo: usize, hi: usize| {
+            (hi > lo).then(|| (self.sums[hi] - self.sums[lo]) / (hi - lo) as f64)
+        };
+        let dt = dt.max(0.0);
+
+        // DC and Nyquist only ever have one neighbour.
+        for k in 1..n - 1 {
+            let (below, here, above) = (db[k - 1], db[k], db[k + 1]);
+            let left = range_mean(k.saturating_sub(SPAN_BINS), k.saturating_sub(GUARD_BINS));
+            let right = range_mean((k + GUARD_BINS + 1).min(n), (k + SPAN_BINS + 1).min(n));
+            let baseline = match (left, right) {
+                (Some(l), Some(r)) => ((l + r) * 0.5) as f32,
+                (Some(side), None) | (None, Some(side)) => side as f32,
+                (None, None) => continue,
+            };
+            let prominence = here - baseline;
+            let peak = here >= below && here > above && prominence >= threshold_db;
+            if !peak {
+                self.next[k] = (self.held[k] - dt * DROPOUT_RATE).max(0.0);
+                continue;
+            }
+            let held = self.held[k - 1].max(self.held[k]).max(self.held[k + 1]) + dt;
+            self.next[k] = held;
+            if held >= min_seconds {
+                // Parabolic interpolation over the peak and its neighbours.
+                let curve = below - 2.0 * here + above;
+                let offset = if curve < 0.0 {
+                    (0.5 * (below - above) / curve).clamp(-0.5, 0.5)
+                } else {
+                    0.0
+                };
+                self.tones.push(Tone {
+                    hz: (k as f32 + offset) * bin_hz,
+                    seconds: held,
+                    prominence_db: prominence,
+                });
+            }
+        }
+        std::mem::swap(&mut self.held, &mut self.next);
+        self.tones
+            .sort_unstable_by(|a, b| b.prominence_db.total_cmp(&a.prominence_db));
+        self.tones.truncate(MAX_TONES);
+        &self.tones
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_tones_that_outlast_the_minimum_and_follow_drift() {
+        const DT: f32 = 0.1;
+        let mut tracker = SteadyTones::default();
+        let frame = |peaks: &[(usize, f32)]| {
+            let mut db = vec![-90.0; 256];
+            for &(bin, level) in peaks {
+                db[bin] = level;
+                db[bin - 1] = level - 6.0;
+                db[bin + 1] = level - 6.0;
+            }
+            db
+        };
+
+        // A 3 s hum at bin 10 and a 0.5 s blip at bin 100.
+        for step in 0..30 {
+            let blip = if step < 5 { &[(100, -40.0)][..] } else { &[] };
+            let db = frame(&[&[(10, -50.0)][..], blip].concat());
+            tracker.update(&db, 5.0, DT, 12.0, 2.0);
+        }
+        let tones = tracker.tones();
+        assert_eq!(tones.len(), 1);
+        assert!((tones[0].hz - 50.0).abs() < 0.1, "{}", tones[0].hz);
+        assert!((tones[0].seconds - 3.0).abs() < 1e-3);
+        assert!((tones[0].prominence_db - 40.0).abs() < 1.0);
+
+        // Drifting one bin keeps the held time; a quiet peak is never tonal.
+        let tones = tracker.update(&frame(&[(11, -50.0), (50, -85.0)]), 5.0, DT, 12.0, 2.0);
+        assert_eq!(tones.len(), 1);
+        assert!(tones[0].seconds > 3.0);
+
+        // Once it stops the label goes at once.
+        assert!(tracker.update(&frame(&[]), 5.0, DT, 12.0, 2.0).is_empty());
+    }
+}
@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Known signals fed to the visuals in place of capture, so levels and analyser
+// behaviour can be checked with nothing playing. Every channel carries the
+// same waveform.
+
+use crate::util::audio::db_to_gain;
+use std::f64::consts::TAU;
+
+pub const SWEEP_START_HZ: f32 = 20.0;
+pub const SWEEP_END_HZ: f32 = 20_000.0;
+pub const SWEEP_SECONDS: f32 = 10.0;
+
+crate::macros::choice_enum!(all pub enum TestSignalKind {
+    #[default] Off => "Off",
+    Tone => "Sine tone",
+    Sweep => "Log sweep, 20 Hz to 20 kHz",
+});
+
+crate::macros::default_struct! {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct TestSignal {
+        pub kind: TestSignalKind = TestSignalKind::Off,
+        pub frequency_hz: f32 = 1_000.0,
+        /// Peak level; a full-scale sine is 0 dBFS.
+        pub level_db: f32 = -18.0,
+    }
+}
+
+impl TestSignal {
+    pub fn is_active(self) -> bool {
+        self.kind != TestSignalKind::Off
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct TestSignalGenerator {
+    // In cycles, so frequency changes and sweep restarts stay continuous.
+    phase: f64,
+    sweep_seconds: f64,
+}
+
+impl TestSignalGenerator {
+    /// Appends `frames` interleaved frames of `signal` to `out`.
+    pub fn render(
+        &mut self,
+        signal: TestSignal,
+        sample_rate: f32,
+        channels: usize,
+        frames: usize,
+        out: &mut Vec<f32>,
+    ) {
+        let rate = f64::from(sample_rate.max(1.0));
+        let gain = if signal.is_active() {
+            db_to_gain(signal.level_db)
+        } else {
+            0.0
+        };
+        let channels = channels.max(1);
+        out.reserve(frames * channels);
+        for _ in 0..frames {
+            let hz = match signal.kind {
+                TestSignalKind::Off => 0.0,
+                TestSignalKind::Tone => f64::from(signal.frequency_hz),
+                TestSignalKind::Sweep => self.sweep_frequency(1.0 / rate),
+            };
+            let sample = gain * (TAU * self.phase).sin() as f32;
+            self.phase = (self.phase + hz.min(rate / 2.0) / rate).fract();
+            out.extend(std::iter::repeat_n(sample, channels));
+        }
+    }
+
+    // Exponential from SWEEP_START_HZ to SWEEP_END_HZ, then again from the top.
+    fn sweep_frequency(&mut self, dt: f64) -> f64 {
+        let t = self.sweep_seconds / f64::from(SWEEP_SECONDS);
+        self.sweep_seconds = (self.sweep_seconds + dt) % f64::from(SWEEP_SECONDS);
+        let ratio = f64::from(SWEEP_END_HZ / SWEEP_START_HZ);
+        f64::from(SWEEP_START_HZ) * ratio.powf(t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tone_hits_its_peak_and_sweep_climbs_from_the_bottom() {
+        let tone = TestSignal {
+            kind: TestSignalKind::Tone,
+            frequency_hz: 1_000.0,
+            level_db: -6.0,
+        };
+        let mut generator = TestSignalGenerator::default();
+        let mut out = Vec::new();
+        generator.render(tone, 48_000.0, 2, 480, &mut out);
+        assert_eq!(out.len(), 960);
+        assert!(out.chunks_exact(2).all(|frame| frame[0] == frame[1]));
+        let peak = out.iter().fold(0.0_f32, |peak, s| peak.max(s.abs()));
+        assert!((peak - db_to_gain(-6.0)).abs() < 1e-3, "peak {peak}");
+        // A quarter cycle in is the crest.
+        assert!((out[24] - db_to_gain(-6.0)).abs() < 1e-3);
+
+        let mut sweep = TestSignalGenerator::default();
+        assert!((sweep.sweep_frequency(1.0) - 20.0).abs() < 1e-9);
+        let at_one_second = sweep.sweep_frequency(f64::from(SWEEP_SECONDS) / 2.0 - 1.0);
+        assert!((at_one_second - 20.0 * 1_000_f64.powf(0.1)).abs() < 1e-6);
+        // Halfway through, the geometric mean of the ends.
+        assert!((sweep.sweep_frequency(0.0) - 20.0 * 1_000_f64.sqrt()).abs() < 1e-6);
+
+        out.clear();
+        generator.render(TestSignal::default(), 48_000.0, 1, 64, &mut out);
+        assert!(out.iter().all(|&s| s == 0.0));
+    }
+}
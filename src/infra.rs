@@ -1,9 +1,17 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // Copyright (C) 2026 Maika Namuo
 
+pub mod graphics;
+pub mod hook;
+pub mod ipc;
+pub mod mpris;
+pub mod notify;
+pub mod osc;
+
 pub mod pipewire {
     pub mod meter_tap;
     pub mod monitor;
+    pub mod pool_audit;
     pub mod registry;
     pub mod virtual_sink;
 }
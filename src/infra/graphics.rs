@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// iced picks its compositor from ICED_BACKEND when the first window opens:
+// "wgpu" runs the shader visuals, "tiny-skia" draws on the CPU, where only
+// the loudness meter, waveform and spectrogram have a quad-based fallback.
+// wgpu in turn picks its API from WGPU_BACKEND.
+
+use crate::persistence::settings::RenderBackend;
+use crate::visuals::render::common::set_software_rendering;
+use tracing::{info, warn};
+
+const ICED_BACKEND: &str = "ICED_BACKEND";
+const WGPU_BACKEND: &str = "WGPU_BACKEND";
+// iced tries these in order, so wgpu falls back to tiny-skia by itself.
+const WITH_FALLBACK: &str = "wgpu,tiny-skia";
+
+/// Exports `choice` for iced and wgpu unless the environment already names a
+/// backend. Must run before any thread starts or any adapter is created.
+pub fn export(choice: RenderBackend) {
+    if let Ok(value) = std::env::var(ICED_BACKEND) {
+        info!("[graphics] using {ICED_BACKEND}={value} from the environment");
+        return;
+    }
+    let (iced, wgpu) = match choice {
+        RenderBackend::Auto => (WITH_FALLBACK, None),
+        RenderBackend::Gpu => ("wgpu", Some("vulkan")),
+        RenderBackend::Gl => ("wgpu", Some("gl")),
+        RenderBackend::Software => ("tiny-skia", None),
+    };
+    // SAFETY: main calls this before the settings saver, PipeWire, IPC and UI
+    // threads start and before wgpu loads a driver, so nothing reads the
+    // environment concurrently.
+    unsafe {
+        std::env::set_var(ICED_BACKEND, iced);
+        if let Some(wgpu) = wgpu
+            && std::env::var_os(WGPU_BACKEND).is_none()
+        {
+            std::env::set_var(WGPU_BACKEND, wgpu);
+        }
+    }
+}
+
+/// Works out whether iced will draw on the CPU, once [`export`] has run.
+/// Software Vulkan (lavapipe) is still wgpu and keeps the shader visuals.
+pub fn detect() {
+    let value = std::env::var(ICED_BACKEND).unwrap_or_default();
+    let software = match value.trim() {
+        backend if backend.starts_with("tiny-skia") => true,
+        backend if backend.contains("tiny-skia") => !has_adapter(),
+        _ => false,
+    };
+    if software {
+        info!("[graphics] software rendering; shader visuals are disabled");
+    }
+    set_software_rendering(software);
+}
+
+/// Adapters wgpu can see. WGPU_BACKEND narrows the search the same way it
+/// narrows iced's.
+pub fn adapters() -> Vec<wgpu::AdapterInfo> {
+    let backends = wgpu::Backends::from_env().unwrap_or(wgpu::Backends::VULKAN);
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+        backends,
+        ..Default::default()
+    });
+    instance
+        .enumerate_adapters(backends)
+        .iter()
+        .map(wgpu::Adapter::get_info)
+        .collect()
+}
+
+fn has_adapter() -> bool {
+    let adapters = adapters();
+    for adapter in &adapters {
+        info!(
+            "[graphics] adapter: {} ({:?}, {:?})",
+            adapter.name, adapter.device_type, adapter.backend
+        );
+    }
+    if adapters.is_empty() {
+        warn!("[graphics] no adapter found; falling back to software rendering");
+    }
+    !adapters.is_empty()
+}
@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// User alert commands, run through `sh -c` so pipes and arguments work as
+// typed. Values are passed in the environment rather than spliced into the
+// command line, so a measurement can never be read as shell syntax.
+
+use std::process::{Command, Stdio};
+use std::thread;
+use tracing::{info, warn};
+
+/// Fire and forget; the command's output is discarded and failures are only
+/// logged.
+pub fn run(command: &str, env: Vec<(&'static str, String)>) {
+    let mut child = Command::new("sh");
+    child
+        .arg("-c")
+        .arg(command)
+        .envs(env)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    let command = command.to_owned();
+    let spawned = thread::Builder::new()
+        .name("openmeters-hook".into())
+        .spawn(move || match child.status() {
+            Ok(status) if status.success() => info!("[hook] ran `{command}`"),
+            Ok(status) => warn!("[hook] `{command}` exited with {status}"),
+            Err(err) => warn!("[hook] failed to run `{command}`: {err}"),
+        });
+    if let Err(err) = spawned {
+        warn!("[hook] failed to spawn hook thread: {err}");
+    }
+}
@@ -0,0 +1,223 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Line-delimited JSON over a user-only UNIX socket, e.g.
+// {"cmd":"set_visual","visual":"spectrum","enabled":true}
+
+use crate::domain::visuals::VisualKind;
+use async_channel::{Receiver as AsyncReceiver, Sender as AsyncSender};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, mpsc};
+use std::time::Duration;
+use std::{fs, thread};
+use tracing::{info, warn};
+
+const CHANNEL_CAPACITY: usize = 16;
+const REPLY_TIMEOUT: Duration = Duration::from_secs(2);
+const CLIENT_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum IpcCommand {
+    Pause { paused: Option<bool> },
+    SetVisual { visual: VisualKind, enabled: bool },
+    SetProfile { profile: Option<String> },
+    GetLevels,
+}
+
+#[derive(Debug, Clone)]
+pub struct IpcRequest {
+    pub command: IpcCommand,
+    reply: mpsc::Sender<Value>,
+}
+
+impl IpcRequest {
+    pub fn reply(&self, result: Result<Value, String>) {
+        let response = match result {
+            Ok(Value::Null) => json!({ "ok": true }),
+            Ok(value) => json!({ "ok": true, "result": value }),
+            Err(error) => error_response(&error),
+        };
+        let _ = self.reply.send(response);
+    }
+}
+
+fn error_response(error: &str) -> Value {
+    json!({ "ok": false, "error": error })
+}
+
+pub fn socket_path() -> PathBuf {
+    match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) => PathBuf::from(dir).join("openmeters.sock"),
+        None => {
+            let user = std::env::var("USER").unwrap_or_default();
+            std::env::temp_dir().join(format!("openmeters-{user}.sock"))
+        }
+    }
+}
+
+/// Removes the socket file when dropped.
+pub struct IpcServer {
+    path: PathBuf,
+    pub requests: Arc<AsyncReceiver<IpcRequest>>,
+}
+
+impl Drop for IpcServer {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+pub fn spawn() -> Option<IpcServer> {
+    let path = socket_path();
+    let listener = bind(&path)
+        .inspect_err(|err| warn!("[ipc] not listening on {path:?}: {err}"))
+        .ok()?;
+    let (sender, receiver) = async_channel::bounded(CHANNEL_CAPACITY);
+    thread::Builder::new()
+        .name("openmeters-ipc".into())
+        .spawn(move || accept_loop(&listener, &sender))
+        .inspect_err(|err| warn!("[ipc] failed to spawn listener thread: {err}"))
+        .ok()?;
+    info!("[ipc] listening on {path:?}");
+    Some(IpcServer {
+        path,
+        requests: Arc::new(receiver),
+    })
+}
+
+fn bind(path: &Path) -> io::Result<UnixListener> {
+    if path.exists() {
+        if UnixStream::connect(path).is_ok() {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrInUse,
+                "another instance is listening",
+            ));
+        }
+        fs::remove_file(path)?;
+    }
+    // Bind under a user-only umask so the socket is never reachable by
+    // others, not even between the bind and the chmod.
+    let previous = set_umask(0o177);
+    let listener = UnixListener::bind(path);
+    set_umask(previous);
+    let listener = listener?;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+fn set_umask(mask: u32) -> u32 {
+    unsafe extern "C" {
+        fn umask(mask: u32) -> u32;
+    }
+    // SAFETY: umask only swaps the process file-mode mask and cannot fail.
+    unsafe { umask(mask) }
+}
+
+// Each client gets its own thread, so an idle one can't hold up the rest.
+fn accept_loop(listener: &UnixListener, sender: &AsyncSender<IpcRequest>) {
+    for stream in listener.incoming() {
+        if sender.is_closed() {
+            break;
+        }
+        match stream {
+            Ok(stream) => {
+                let sender = sender.clone();
+                let spawned = thread::Builder::new()
+                    .name("openmeters-ipc-client".into())
+                    .spawn(move || serve_client(stream, &sender));
+                if let Err(err) = spawned {
+                    warn!("[ipc] failed to spawn client thread: {err}");
+                }
+            }
+            Err(err) => warn!("[ipc] accept failed: {err}"),
+        }
+    }
+}
+
+// Returns once the client hangs up, idles out, or the UI side goes away.
+fn serve_client(stream: UnixStream, sender: &AsyncSender<IpcRequest>) {
+    let _ = stream.set_read_timeout(Some(CLIENT_IDLE_TIMEOUT));
+    let Ok(mut writer) = stream.try_clone() else {
+        return;
+    };
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<IpcCommand>(&line) {
+            Ok(command) => {
+                let (reply, replies) = mpsc::channel();
+                if sender.send_blocking(IpcRequest { command, reply }).is_err() {
+                    return;
+                }
+                replies
+                    .recv_timeout(REPLY_TIMEOUT)
+                    .unwrap_or_else(|_| error_response("no reply"))
+            }
+            Err(err) => error_response(&err.to_string()),
+        };
+        if writeln!(writer, "{response}").is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_commands_through_the_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ipc.sock");
+        let listener = bind(&path).unwrap();
+        assert_eq!(
+            fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+            0o600
+        );
+        assert!(bind(&path).is_err());
+
+        let (sender, receiver) = async_channel::bounded(1);
+        thread::spawn(move || accept_loop(&listener, &sender));
+        thread::spawn(move || {
+            while let Ok(request) = receiver.recv_blocking() {
+                match &request.command {
+                    IpcCommand::SetVisual {
+                        visual: VisualKind::Spectrum,
+                        enabled: true,
+                    } => request.reply(Ok(Value::Null)),
+                    IpcCommand::GetLevels => request.reply(Ok(json!({ "momentary": -14.0 }))),
+                    _ => request.reply(Err("unexpected".into())),
+                }
+            }
+        });
+
+        // A connected client that never speaks doesn't hold up the next one.
+        let _idle = UnixStream::connect(&path).unwrap();
+        let mut client = UnixStream::connect(&path).unwrap();
+        let mut lines = BufReader::new(client.try_clone().unwrap()).lines();
+        let mut call = |line: &str| -> Value {
+            writeln!(client, "{line}").unwrap();
+            serde_json::from_str(&lines.next().unwrap().unwrap()).unwrap()
+        };
+
+        assert_eq!(
+            call(r#"{"cmd":"set_visual","visual":"spectrum","enabled":true}"#),
+            json!({ "ok": true })
+        );
+        assert_eq!(
+            call(r#"{"cmd":"get_levels"}"#)["result"]["momentary"],
+            -14.0
+        );
+        assert_eq!(call(r#"{"cmd":"reboot"}"#)["ok"], false);
+    }
+}
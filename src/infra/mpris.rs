@@ -0,0 +1,118 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Track changes from MPRIS players, followed through `playerctl` so no session
+// bus client is needed in-process.
+
+use async_channel::Receiver as AsyncReceiver;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, ChildStdout, Command, Stdio};
+use std::sync::Arc;
+use std::thread;
+use tracing::{info, warn};
+
+const CHANNEL_CAPACITY: usize = 8;
+const FORMAT: &str = "{{playerName}}\t{{artist}}\t{{title}}";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrackInfo {
+    pub player: String,
+    pub artist: String,
+    pub title: String,
+}
+
+impl TrackInfo {
+    /// Parses one `FORMAT` line; `None` when nothing with a title is playing.
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.trim_end_matches(['\r', '\n']).splitn(3, '\t');
+        let player = fields.next()?.trim().to_owned();
+        let artist = fields.next()?.trim().to_owned();
+        let title = fields.next()?.trim().to_owned();
+        (!title.is_empty()).then_some(Self {
+            player,
+            artist,
+            title,
+        })
+    }
+
+    pub fn label(&self) -> String {
+        if self.artist.is_empty() {
+            self.title.clone()
+        } else {
+            format!("{} - {}", self.artist, self.title)
+        }
+    }
+}
+
+/// Kills `playerctl` when dropped.
+pub struct TrackFollower {
+    child: Child,
+    pub changes: Arc<AsyncReceiver<Option<TrackInfo>>>,
+}
+
+impl Drop for TrackFollower {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+pub fn follow() -> Option<TrackFollower> {
+    let mut child = Command::new("playerctl")
+        .args(["--follow", "metadata", "--format", FORMAT])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .inspect_err(|err| warn!("[mpris] failed to run playerctl: {err}"))
+        .ok()?;
+    let stdout = child.stdout.take()?;
+    let (sender, receiver) = async_channel::bounded(CHANNEL_CAPACITY);
+    let spawned = thread::Builder::new()
+        .name("openmeters-mpris".into())
+        .spawn(move || read_changes(stdout, &sender));
+    if let Err(err) = spawned {
+        warn!("[mpris] failed to spawn follower thread: {err}");
+        let _ = child.kill();
+        let _ = child.wait();
+        return None;
+    }
+    info!("[mpris] following track changes");
+    Some(TrackFollower {
+        child,
+        changes: Arc::new(receiver),
+    })
+}
+
+// Players republish metadata for artwork and the like; only title changes count.
+fn read_changes(stdout: ChildStdout, sender: &async_channel::Sender<Option<TrackInfo>>) {
+    let mut last = None;
+    for line in BufReader::new(stdout).lines() {
+        let Ok(line) = line else { break };
+        let track = TrackInfo::parse(&line);
+        if track != last {
+            last.clone_from(&track);
+            if sender.send_blocking(track).is_err() {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_metadata_lines_and_treats_untitled_as_stopped() {
+        let track = TrackInfo::parse("spotify\tBoards of Canada\tRoygbiv\n").unwrap();
+        assert_eq!(track.player, "spotify");
+        assert_eq!(track.label(), "Boards of Canada - Roygbiv");
+
+        let untitled = TrackInfo::parse("mpv\t\tfile.flac").unwrap();
+        assert_eq!(untitled.label(), "file.flac");
+
+        assert_eq!(TrackInfo::parse("mpv\t\t"), None);
+        assert_eq!(TrackInfo::parse(""), None);
+    }
+}
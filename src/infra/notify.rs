@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Desktop notifications through libnotify's `notify-send`, so no session bus
+// client is needed in-process.
+
+use std::process::{Command, Stdio};
+use std::thread;
+use tracing::warn;
+
+const APP_NAME: &str = "OpenMeters";
+
+/// Fire and forget; failures (e.g. no notification daemon) are only logged.
+pub fn send(summary: &str, body: &str) {
+    let mut command = Command::new("notify-send");
+    command
+        .arg(format!("--app-name={APP_NAME}"))
+        .arg(summary)
+        .arg(body)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    let spawned = thread::Builder::new()
+        .name("openmeters-notify".into())
+        .spawn(move || match command.status() {
+            Ok(status) if status.success() => {}
+            Ok(status) => warn!("[notify] notify-send exited with {status}"),
+            Err(err) => warn!("[notify] failed to run notify-send: {err}"),
+        });
+    if let Err(err) = spawned {
+        warn!("[notify] failed to spawn notifier thread: {err}");
+    }
+}
@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Minimal OSC 1.0 sender: single float messages over UDP, enough to poke a
+// streaming or show-control setup.
+
+use std::net::UdpSocket;
+use std::thread;
+use tracing::warn;
+
+/// Fire and forget to `target` (`host:port`); failures are only logged.
+pub fn send(target: &str, address: &str, value: f32) {
+    let (target, packet) = (target.to_owned(), encode(address, value));
+    let spawned = thread::Builder::new()
+        .name("openmeters-osc".into())
+        .spawn(move || {
+            let sent = UdpSocket::bind(("0.0.0.0", 0))
+                .and_then(|socket| socket.send_to(&packet, target.as_str()));
+            if let Err(err) = sent {
+                warn!("[osc] failed to send to {target}: {err}");
+            }
+        });
+    if let Err(err) = spawned {
+        warn!("[osc] failed to spawn sender thread: {err}");
+    }
+}
+
+fn encode(address: &str, value: f32) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(address.len() + 12);
+    push_padded(&mut packet, address.as_bytes());
+    push_padded(&mut packet, b",f");
+    packet.extend_from_slice(&value.to_be_bytes());
+    packet
+}
+
+// OSC strings end in at least one NUL and are padded to four bytes.
+fn push_padded(packet: &mut Vec<u8>, bytes: &[u8]) {
+    packet.extend_from_slice(bytes);
+    packet.push(0);
+    packet.resize(packet.len().next_multiple_of(4), 0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_a_padded_float_message() {
+        let packet = encode("/om/silence", 12.5);
+        assert_eq!(&packet[..12], b"/om/silence\0");
+        assert_eq!(&packet[12..16], b",f\0\0");
+        assert_eq!(&packet[16..], 12.5_f32.to_be_bytes());
+        assert_eq!(encode("/abc", 0.0).len(), 16);
+    }
+}
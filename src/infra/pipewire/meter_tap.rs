@@ -2,9 +2,12 @@
 // Copyright (C) 2026 Maika Namuo
 
 use super::virtual_sink::{self, CaptureBuffer};
+use crate::domain::routing::{ChannelMatrix, MATRIX_INPUTS};
+use crate::dsp::test_signal::{TestSignal, TestSignalGenerator};
 use crate::util::audio::{DEFAULT_SAMPLE_RATE, sanitize_sample_rate};
 use async_channel::{Receiver as AsyncReceiver, Sender as AsyncSender};
-use std::sync::{Arc, LazyLock};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, PoisonError};
 use std::thread;
 use std::time::{Duration, Instant};
 use tracing::{info, warn};
@@ -14,17 +17,59 @@ const POLL_BACKOFF: Duration = Duration::from_millis(50);
 const TARGET_BATCH_FRAMES_AT_48K: usize = 1_024;
 const MAX_BATCH_LATENCY: Duration = Duration::from_millis(25);
 const DROP_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+// Beyond this the meters visibly step rather than move.
+const SLUGGISH_QUANTUM_MS: f32 = 40.0;
+const TEST_SIGNAL_CHANNELS: usize = 2;
+const TEST_SIGNAL_MIN_FRAMES: u64 = 256;
 
 static AUDIO_STREAM: LazyLock<Arc<AsyncReceiver<AudioBatch>>> = LazyLock::new(|| {
     let (sender, receiver) = async_channel::bounded(CHANNEL_CAPACITY);
     spawn_forwarder(sender, virtual_sink::capture_buffer_handle());
     Arc::new(receiver)
 });
+static CHANNEL_MATRIX: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+static INPUT_CHANNELS: AtomicUsize = AtomicUsize::new(0);
+static GRAPH_CLOCK: AtomicU64 = AtomicU64::new(0);
+static TEST_SIGNAL: Mutex<Option<TestSignal>> = Mutex::new(None);
 
 #[derive(Debug, Clone)]
 pub struct AudioBatch {
     pub samples: Vec<f32>,
     pub format: MeterFormat,
+    /// When the newest audio in the batch was captured.
+    pub captured: Instant,
+}
+
+/// The graph cycle as seen by the capture: each packet is one quantum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GraphClock {
+    pub quantum: u32,
+    pub rate: u32,
+}
+
+impl GraphClock {
+    pub fn millis(self) -> f32 {
+        self.quantum as f32 * 1_000.0 / self.rate.max(1) as f32
+    }
+
+    pub fn is_sluggish(self) -> bool {
+        self.millis() > SLUGGISH_QUANTUM_MS
+    }
+}
+
+/// Capture glitches since start: gaps between the sink's callbacks long
+/// enough to mean missed cycles, and packets the capture ring dropped before
+/// the meters read them. Neither is PipeWire's own xrun count.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GlitchCounts {
+    pub gaps: u64,
+    pub dropped: u64,
+}
+
+impl GlitchCounts {
+    pub fn total(self) -> u64 {
+        self.gaps + self.dropped
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -37,6 +82,7 @@ struct SampleBatcher {
     target_frames_at_48k: usize,
     samples: Vec<f32>,
     format: Option<MeterFormat>,
+    captured: Option<Instant>,
 }
 
 impl SampleBatcher {
@@ -45,6 +91,7 @@ impl SampleBatcher {
             target_frames_at_48k,
             samples: Vec::with_capacity(target_frames_at_48k.saturating_mul(2)),
             format: None,
+            captured: None,
         }
     }
 
@@ -56,7 +103,7 @@ impl SampleBatcher {
         frames.saturating_mul(format.channels.max(1))
     }
 
-    fn push(&mut self, samples: &[f32], format: MeterFormat) {
+    fn push(&mut self, samples: &[f32], format: MeterFormat, captured: Instant) {
         if samples.is_empty() {
             return;
         }
@@ -64,6 +111,7 @@ impl SampleBatcher {
             self.format = Some(format);
         }
         self.samples.extend_from_slice(samples);
+        self.captured = Some(captured);
     }
 
     fn is_empty(&self) -> bool {
@@ -84,11 +132,47 @@ impl SampleBatcher {
             return None;
         }
         let format = self.format.take()?;
+        let captured = self.captured.take()?;
         let target_samples = self.target_samples(format);
         let max_capacity = target_samples.saturating_mul(4);
         let next_capacity = self.samples.len().clamp(target_samples, max_capacity);
         let samples = std::mem::replace(&mut self.samples, Vec::with_capacity(next_capacity));
-        Some(AudioBatch { samples, format })
+        Some(AudioBatch {
+            samples,
+            format,
+            captured,
+        })
+    }
+}
+
+// Paces a test signal on the wall clock, since no graph cycles drive it.
+struct TestSignalClock {
+    started: Instant,
+    sample_rate: f32,
+    sent: u64,
+}
+
+impl TestSignalClock {
+    fn new(sample_rate: f32) -> Self {
+        Self {
+            started: Instant::now(),
+            sample_rate,
+            sent: 0,
+        }
+    }
+
+    // Frames owed since the last batch, once there are enough to send.
+    fn due_frames(&mut self, sample_rate: f32) -> usize {
+        if sample_rate != self.sample_rate {
+            *self = Self::new(sample_rate);
+        }
+        let elapsed = self.started.elapsed().as_secs_f64() * f64::from(sample_rate);
+        let owed = (elapsed as u64).saturating_sub(self.sent);
+        if owed < TEST_SIGNAL_MIN_FRAMES {
+            return 0;
+        }
+        self.sent += owed;
+        owed as usize
     }
 }
 
@@ -96,6 +180,63 @@ pub fn audio_sample_stream() -> Arc<AsyncReceiver<AudioBatch>> {
     Arc::clone(&AUDIO_STREAM)
 }
 
+pub fn set_channel_matrix(matrix: &ChannelMatrix) {
+    CHANNEL_MATRIX
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone_from(&matrix.0);
+}
+
+/// Channel count of the most recent capture packet, before routing.
+pub fn input_channels() -> usize {
+    INPUT_CHANNELS.load(Ordering::Relaxed)
+}
+
+/// Quantum and rate of the most recent capture packet, once audio has flowed.
+pub fn graph_clock() -> Option<GraphClock> {
+    let packed = GRAPH_CLOCK.load(Ordering::Relaxed);
+    let clock = GraphClock {
+        quantum: (packed >> 32) as u32,
+        rate: packed as u32,
+    };
+    (clock.quantum > 0 && clock.rate > 0).then_some(clock)
+}
+
+/// Feeds the visuals `signal` instead of capture until it is switched off.
+pub fn set_test_signal(signal: TestSignal) {
+    *TEST_SIGNAL.lock().unwrap_or_else(PoisonError::into_inner) =
+        signal.is_active().then_some(signal);
+}
+
+pub fn test_signal() -> Option<TestSignal> {
+    *TEST_SIGNAL.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+pub fn glitch_counts() -> GlitchCounts {
+    GlitchCounts {
+        gaps: virtual_sink::cycle_gaps(),
+        dropped: virtual_sink::capture_buffer_handle().dropped_frames(),
+    }
+}
+
+// Averages the capture channels routed to each analysis channel.
+fn remix(samples: &[f32], channels: usize, matrix: &[u32], out: &mut Vec<f32>) {
+    out.clear();
+    for frame in samples.chunks_exact(channels) {
+        for &mask in matrix {
+            let (sum, count) = frame
+                .iter()
+                .take(MATRIX_INPUTS)
+                .enumerate()
+                .filter(|&(input, _)| (mask >> input) & 1 == 1)
+                .fold((0.0, 0), |(sum, count), (_, &sample)| {
+                    (sum + sample, count + 1)
+                });
+            out.push(if count > 0 { sum / count as f32 } else { 0.0 });
+        }
+    }
+}
+
 fn spawn_forwarder(sender: AsyncSender<AudioBatch>, buffer: Arc<CaptureBuffer>) {
     if let Err(err) = thread::Builder::new()
         .name("openmeters-audio-meter-tap".into())
@@ -110,6 +251,10 @@ fn forward_loop(sender: AsyncSender<AudioBatch>, buffer: Arc<CaptureBuffer>) {
     let mut batch_started_at = Instant::now();
     let mut last_drop_check = Instant::now();
     let mut drop_baseline = buffer.dropped_frames();
+    let mut matrix = Vec::new();
+    let mut routed = Vec::new();
+    let mut generator = TestSignalGenerator::default();
+    let mut test_clock: Option<TestSignalClock> = None;
 
     let flush = |batcher: &mut SampleBatcher, batch_started_at: &mut Instant| -> bool {
         let Some(batch) = batcher.take() else {
@@ -133,9 +278,54 @@ fn forward_loop(sender: AsyncSender<AudioBatch>, buffer: Arc<CaptureBuffer>) {
                 );
                 drop_baseline = dropped;
             }
+            if let Some(report) = buffer.audit_report() {
+                if report.is_clean() {
+                    info!("[meter-tap] capture pool audit: {report:?}");
+                } else {
+                    warn!("[meter-tap] capture pool audit found problems: {report:?}");
+                }
+            }
             last_drop_check = Instant::now();
         }
 
+        // Capture is still drained so its pool keeps recycling, but none of
+        // it reaches the visuals while a test signal plays.
+        if let Some(signal) = test_signal() {
+            if flush(&mut batcher, &mut batch_started_at) {
+                break;
+            }
+            if let Some(packet) = buffer.pop_wait_timeout(MAX_BATCH_LATENCY) {
+                buffer.recycle_samples_blocking(packet.samples);
+            }
+            let sample_rate = graph_clock().map_or(DEFAULT_SAMPLE_RATE, |clock| clock.rate as f32);
+            let clock = test_clock.get_or_insert_with(|| TestSignalClock::new(sample_rate));
+            let frames = clock.due_frames(sample_rate);
+            if frames > 0 {
+                let mut samples = Vec::new();
+                generator.render(
+                    signal,
+                    sample_rate,
+                    TEST_SIGNAL_CHANNELS,
+                    frames,
+                    &mut samples,
+                );
+                let format = MeterFormat {
+                    channels: TEST_SIGNAL_CHANNELS,
+                    sample_rate,
+                };
+                let batch = AudioBatch {
+                    samples,
+                    format,
+                    captured: Instant::now(),
+                };
+                if sender.send_blocking(batch).is_err() {
+                    break;
+                }
+            }
+            continue;
+        }
+        test_clock = None;
+
         let timeout = if batcher.is_empty() {
             POLL_BACKOFF
         } else {
@@ -146,10 +336,28 @@ fn forward_loop(sender: AsyncSender<AudioBatch>, buffer: Arc<CaptureBuffer>) {
 
         match buffer.pop_wait_timeout(timeout) {
             Some(packet) => {
-                let format = MeterFormat {
+                let mut format = MeterFormat {
                     channels: packet.channels.max(1) as usize,
                     sample_rate: sanitize_sample_rate(packet.sample_rate as f32),
                 };
+                INPUT_CHANNELS.store(format.channels, Ordering::Relaxed);
+                let quantum = (packet.samples.len() / format.channels) as u64;
+                GRAPH_CLOCK.store(
+                    (quantum << 32) | u64::from(packet.sample_rate),
+                    Ordering::Relaxed,
+                );
+                matrix.clone_from(
+                    &CHANNEL_MATRIX
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner),
+                );
+                let samples = if matrix.is_empty() {
+                    &packet.samples
+                } else {
+                    remix(&packet.samples, format.channels, &matrix, &mut routed);
+                    format.channels = matrix.len();
+                    &routed
+                };
 
                 let batch_expired =
                     !batcher.is_empty() && batch_started_at.elapsed() >= MAX_BATCH_LATENCY;
@@ -161,7 +369,7 @@ fn forward_loop(sender: AsyncSender<AudioBatch>, buffer: Arc<CaptureBuffer>) {
                 }
 
                 let starts_batch = batcher.is_empty();
-                batcher.push(&packet.samples, format);
+                batcher.push(samples, format, packet.captured);
                 if starts_batch {
                     batch_started_at = Instant::now();
                 }
@@ -195,7 +403,8 @@ fn forward_loop(sender: AsyncSender<AudioBatch>, buffer: Arc<CaptureBuffer>) {
 
 #[cfg(test)]
 mod tests {
-    use super::{MeterFormat, SampleBatcher};
+    use super::{MeterFormat, SampleBatcher, remix};
+    use std::time::{Duration, Instant};
 
     const STEREO_48K: MeterFormat = MeterFormat {
         channels: 2,
@@ -209,23 +418,27 @@ mod tests {
     #[test]
     fn batches_chunks() {
         let mut batcher = SampleBatcher::new(2);
+        let now = Instant::now();
+        let later = now + Duration::from_millis(5);
         let high_rate = MeterFormat {
             sample_rate: 96_000.0,
             ..STEREO_48K
         };
         assert_eq!(batcher.target_samples(high_rate), 8);
-        batcher.push(&[0.0, 1.0], STEREO_48K);
+        batcher.push(&[0.0, 1.0], STEREO_48K, now);
         assert!(!batcher.should_flush());
-        batcher.push(&[2.0, 3.0], STEREO_48K);
+        batcher.push(&[2.0, 3.0], STEREO_48K, later);
         assert!(batcher.should_flush());
 
         let batch = batcher.take().expect("batch should be available");
         assert_eq!(batch.samples, vec![0.0, 1.0, 2.0, 3.0]);
         assert_eq!(batch.format, STEREO_48K);
+        // Stamped with its newest packet.
+        assert_eq!(batch.captured, later);
         assert!(batcher.take().is_none());
 
-        batcher.push(&[4.0, 5.0], STEREO_48K);
-        batcher.push(&[6.0, 7.0], STEREO_48K);
+        batcher.push(&[4.0, 5.0], STEREO_48K, now);
+        batcher.push(&[6.0, 7.0], STEREO_48K, now);
         let second = batcher.take().expect("second batch available");
         assert_eq!(second.samples, vec![4.0, 5.0, 6.0, 7.0]);
         assert_eq!(second.format, STEREO_48K);
@@ -234,8 +447,9 @@ mod tests {
     #[test]
     fn format_changes_flush_without_mixing_batches() {
         let mut batcher = SampleBatcher::new(8);
+        let now = Instant::now();
 
-        batcher.push(&[0.0, 1.0], STEREO_48K);
+        batcher.push(&[0.0, 1.0], STEREO_48K, now);
         assert!(!batcher.has_different_format(STEREO_48K));
         assert!(batcher.has_different_format(MONO_44K));
 
@@ -243,11 +457,23 @@ mod tests {
         assert_eq!(first.samples, vec![0.0, 1.0]);
         assert_eq!(first.format, STEREO_48K);
 
-        batcher.push(&[2.0, 3.0], MONO_44K);
+        batcher.push(&[2.0, 3.0], MONO_44K, now);
         let second = batcher
             .take()
             .expect("new-format batch should remain separate");
         assert_eq!(second.samples, vec![2.0, 3.0]);
         assert_eq!(second.format, MONO_44K);
     }
+
+    #[test]
+    fn remix_picks_and_averages_routed_channels() {
+        let quad = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        let mut out = Vec::new();
+        // Inputs 3/4 as L/R.
+        remix(&quad, 4, &[0b0100, 0b1000], &mut out);
+        assert_eq!(out, [3.0, 4.0, 7.0, 8.0]);
+        // Inputs 1+2 averaged to mono; a channel the device lacks reads silence.
+        remix(&quad, 4, &[0b0011, 1 << 9], &mut out);
+        assert_eq!(out, [1.5, 0.0, 5.5, 0.0]);
+    }
 }
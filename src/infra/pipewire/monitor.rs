@@ -106,6 +106,16 @@ fn run_monitor_loop(
 }
 
 fn restore_all_routes(routing: &mut RoutingManager, snapshot: Option<&registry::RegistrySnapshot>) {
+    // A forced quantum holds the whole graph, so it goes with us.
+    if routing.forced_quantum {
+        routing.handle.force_quantum(0);
+        if !routing.handle.sync() {
+            warn!("[registry-monitor] failed to sync with registry thread");
+        }
+    }
+    for (node, _) in routing.applied_trims.drain() {
+        virtual_sink::set_app_trim(node, None);
+    }
     let Some(snapshot) = snapshot else { return };
 
     let routed_nodes: Vec<_> = routing.routed_to.keys().copied().collect();
@@ -145,12 +155,20 @@ struct RoutingManager {
     commands: mpsc::Receiver<RoutingCommand>,
     disabled_nodes: HashSet<u32>,
     routed_to: HashMap<u32, u32>,
+    trims: HashMap<u32, f32>,
+    // Trim sinks as last requested, so each is only sent on change.
+    applied_trims: HashMap<u32, f32>,
+    // Whether we set `clock.force-quantum` and still owe its release.
+    forced_quantum: bool,
     capture_mode: CaptureMode,
     device_target: DeviceSelection,
+    loopback_target: DeviceSelection,
+    loopback_delayed: bool,
     hw_sink_cache: Option<(u32, String)>,
     current_links: Vec<registry::LinkSpec>,
     warned_sink_missing: bool,
     warned_device_missing: bool,
+    warned_loopback_missing: bool,
 }
 
 impl RoutingManager {
@@ -164,12 +182,18 @@ impl RoutingManager {
             commands,
             disabled_nodes: HashSet::default(),
             routed_to: HashMap::default(),
+            trims: HashMap::default(),
+            applied_trims: HashMap::default(),
+            forced_quantum: false,
             capture_mode: routing_config.capture_mode,
             device_target: routing_config.preferred_device,
+            loopback_target: routing_config.loopback_sink,
+            loopback_delayed: set_loopback_delay(routing_config.loopback_delay_ms),
             hw_sink_cache: None,
             current_links: Vec::new(),
             warned_sink_missing: false,
             warned_device_missing: false,
+            warned_loopback_missing: false,
         }
     }
 
@@ -190,6 +214,35 @@ impl RoutingManager {
                     self.device_target = device;
                     changed
                 }
+                RoutingCommand::SetLoopbackSink(sink) => {
+                    let changed = self.loopback_target != sink;
+                    self.loopback_target = sink;
+                    changed
+                }
+                RoutingCommand::SetLoopbackDelay(ms) => {
+                    let delayed = set_loopback_delay(ms);
+                    let changed = self.loopback_delayed != delayed;
+                    self.loopback_delayed = delayed;
+                    changed
+                }
+                RoutingCommand::SetOutputGain(gain) => {
+                    virtual_sink::set_monitor_gain(gain);
+                    false
+                }
+                RoutingCommand::SetApplicationTrim { node_id, gain } => {
+                    let previous = if gain == 1.0 {
+                        self.trims.remove(&node_id)
+                    } else {
+                        self.trims.insert(node_id, gain)
+                    };
+                    previous.unwrap_or(1.0) != gain
+                }
+                RoutingCommand::ForceQuantum(frames) => {
+                    if self.handle.force_quantum(frames) {
+                        self.forced_quantum = frames > 0;
+                    }
+                    false
+                }
             };
         }
         changed
@@ -199,6 +252,16 @@ impl RoutingManager {
         let node_exists = |id| snapshot.nodes.iter().any(|n| n.id == id);
         self.disabled_nodes.retain(|&id| node_exists(id));
         self.routed_to.retain(|&id, _| node_exists(id));
+        self.trims.retain(|&id, _| node_exists(id));
+        let gone: Vec<u32> = self
+            .applied_trims
+            .keys()
+            .copied()
+            .filter(|&id| !node_exists(id))
+            .collect();
+        for node_id in gone {
+            self.set_trim(node_id, None);
+        }
         if self
             .hw_sink_cache
             .as_ref()
@@ -214,8 +277,26 @@ impl RoutingManager {
         self.update_routes(snapshot);
     }
 
+    // A trimmed stream plays into a sink of its own, which scales it for the
+    // meters only; `None` drops that sink.
+    fn set_trim(&mut self, node_id: u32, gain: Option<f32>) {
+        if self.applied_trims.get(&node_id).copied() == gain
+            || !virtual_sink::set_app_trim(node_id, gain)
+        {
+            return;
+        }
+        match gain {
+            Some(gain) => self.applied_trims.insert(node_id, gain),
+            None => self.applied_trims.remove(&node_id),
+        };
+    }
+
     fn update_routes(&mut self, snapshot: &registry::RegistrySnapshot) {
         if self.capture_mode == CaptureMode::Device {
+            let trimmed: Vec<u32> = self.applied_trims.keys().copied().collect();
+            for node_id in trimmed {
+                self.set_trim(node_id, None);
+            }
             let (handle, nodes) = (&self.handle, &snapshot.nodes);
             self.routed_to
                 .retain(|&id, _| nodes.iter().any(|n| n.id == id && !handle.reset_route(n)));
@@ -237,7 +318,11 @@ impl RoutingManager {
 
         for node in snapshot.route_candidates(sink) {
             let enabled = !self.disabled_nodes.contains(&node.id);
-            let target = enabled.then_some(sink).or(hw_sink);
+            let trim = self.trims.get(&node.id).copied().filter(|_| enabled);
+            self.set_trim(node.id, trim);
+            // Until its trim sink shows up, a trimmed stream stays on the main one.
+            let own_sink = trim.and_then(|_| snapshot.trim_sink(node.id));
+            let target = enabled.then(|| own_sink.unwrap_or(sink)).or(hw_sink);
 
             if let Some(target) = target {
                 if self.handle.route_node(node, target)
@@ -275,6 +360,22 @@ impl RoutingManager {
         node
     }
 
+    // The chosen sink while it is present, else the default one.
+    fn loopback_sink<'a>(
+        &mut self,
+        snapshot: &'a registry::RegistrySnapshot,
+    ) -> Option<&'a registry::NodeInfo> {
+        let DeviceSelection::Device(token) = &self.loopback_target else {
+            return self.hw_sink(snapshot);
+        };
+        let sink = snapshot.find_playback_sink_by_token(token);
+        if sink.is_none() && !self.warned_loopback_missing {
+            warn!("[router] loopback sink '{token}' unavailable; playing to the default sink");
+        }
+        self.warned_loopback_missing = sink.is_none();
+        sink.or_else(|| self.hw_sink(snapshot))
+    }
+
     fn compute_links(
         &mut self,
         snapshot: &registry::RegistrySnapshot,
@@ -282,36 +383,32 @@ impl RoutingManager {
         let om_sink = snapshot.virtual_sink()?;
 
         let (source, target) = match self.capture_mode {
-            CaptureMode::Applications => (om_sink, self.hw_sink(snapshot)?),
+            // The delayed loopback plays in place of the sink's monitor ports.
+            CaptureMode::Applications => (
+                snapshot
+                    .loopback()
+                    .filter(|_| self.loopback_delayed)
+                    .unwrap_or(om_sink),
+                self.loopback_sink(snapshot)?,
+            ),
             CaptureMode::Device => (self.device_source(snapshot)?, om_sink),
         };
 
-        let (src_ports, tgt_ports) = (
-            source.output_ports_for_loopback(),
-            target.input_ports_for_loopback(),
-        );
-        if src_ports.is_empty() {
-            let name = source.capture_device_token();
-            debug!("[loopback] no output ports on '{name}'");
-            return None;
-        }
-        if tgt_ports.is_empty() {
-            let name = target.capture_device_token();
-            debug!("[loopback] no input ports on '{name}'");
-            return None;
-        }
+        let mut links = link_ports(source, target)?;
 
-        Some(
-            registry::pair_ports_by_channel(src_ports, tgt_ports)
+        // Trim sinks carry their streams to the loopback unscaled; a delayed
+        // loopback already has them mixed in.
+        if source.id == om_sink.id && self.capture_mode == CaptureMode::Applications {
+            let mut trimmed: Vec<u32> = self.applied_trims.keys().copied().collect();
+            trimmed.sort_unstable();
+            for trim_sink in trimmed
                 .into_iter()
-                .map(|(out, inp)| registry::LinkSpec {
-                    output_node: source.id,
-                    output_port: out.port_id,
-                    input_node: target.id,
-                    input_port: inp.port_id,
-                })
-                .collect(),
-        )
+                .filter_map(|app| snapshot.trim_sink(app))
+            {
+                links.extend(link_ports(trim_sink, target).unwrap_or_default());
+            }
+        }
+        Some(links)
     }
 
     fn device_source<'a>(
@@ -335,6 +432,44 @@ impl RoutingManager {
     }
 }
 
+// Whether the loopback now runs through the delay line.
+fn set_loopback_delay(ms: f32) -> bool {
+    virtual_sink::set_loopback_delay(ms);
+    ms > 0.0
+}
+
+fn link_ports(
+    source: &registry::NodeInfo,
+    target: &registry::NodeInfo,
+) -> Option<Vec<registry::LinkSpec>> {
+    let (src_ports, tgt_ports) = (
+        source.output_ports_for_loopback(),
+        target.input_ports_for_loopback(),
+    );
+    if src_ports.is_empty() {
+        let name = source.capture_device_token();
+        debug!("[loopback] no output ports on '{name}'");
+        return None;
+    }
+    if tgt_ports.is_empty() {
+        let name = target.capture_device_token();
+        debug!("[loopback] no input ports on '{name}'");
+        return None;
+    }
+
+    Some(
+        registry::pair_ports_by_channel(src_ports, tgt_ports)
+            .into_iter()
+            .map(|(out, inp)| registry::LinkSpec {
+                output_node: source.id,
+                output_port: out.port_id,
+                input_node: target.id,
+                input_port: inp.port_id,
+            })
+            .collect(),
+    )
+}
+
 fn log_registry_snapshot(snapshot: &registry::RegistrySnapshot) {
     let sink = snapshot.describe_default_target(snapshot.defaults.audio_sink.as_ref());
     let source = snapshot.describe_default_target(snapshot.defaults.audio_source.as_ref());
@@ -0,0 +1,158 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Lifetime ledger for the capture pool's sample buffers, keyed by allocation,
+// for chasing memory growth: buffers handed out and never returned, buffers
+// that come back from another allocation (grown on the audio thread, or not
+// from the pool at all), buffers pooled twice (two owners writing the same
+// memory) and buffers dropped instead of pooled. It takes a lock on the audio
+// thread, so it only runs in builds with the `pool-audit` feature.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+// Out longer than this, a buffer counts as leaked.
+const LEAK_AGE: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AuditReport {
+    pub outstanding: usize,
+    pub leaked: usize,
+    pub foreign: u64,
+    pub duplicate: u64,
+    pub discarded: u64,
+}
+
+impl AuditReport {
+    pub fn is_clean(&self) -> bool {
+        self.leaked == 0 && self.foreign == 0 && self.duplicate == 0
+    }
+}
+
+#[derive(Debug, Default)]
+struct Ledger {
+    out: HashMap<usize, Instant>,
+    pooled: HashSet<usize>,
+    foreign: u64,
+    duplicate: u64,
+    discarded: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct PoolAudit {
+    ledger: Option<Mutex<Ledger>>,
+}
+
+fn address(buffer: &[f32]) -> usize {
+    buffer.as_ptr() as usize
+}
+
+impl PoolAudit {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            ledger: enabled.then(Mutex::default),
+        }
+    }
+
+    fn with(&self, edit: impl FnOnce(&mut Ledger)) {
+        if let Some(ledger) = &self.ledger {
+            edit(&mut ledger.lock().unwrap_or_else(PoisonError::into_inner));
+        }
+    }
+
+    /// Takes the pool as it stands, e.g. after its buffers were regrown.
+    pub fn pooled(&self, pool: &[Vec<f32>]) {
+        self.with(|ledger| ledger.pooled = pool.iter().map(Vec::as_slice).map(address).collect());
+    }
+
+    pub fn acquired(&self, buffer: &[f32], now: Instant) {
+        self.with(|ledger| {
+            let key = address(buffer);
+            ledger.pooled.remove(&key);
+            ledger.out.insert(key, now);
+        });
+    }
+
+    pub fn returned(&self, buffer: &[f32]) {
+        self.with(|ledger| {
+            let key = address(buffer);
+            if !ledger.pooled.insert(key) {
+                ledger.duplicate += 1;
+            } else if ledger.out.remove(&key).is_none() {
+                ledger.foreign += 1;
+            }
+        });
+    }
+
+    pub fn discarded(&self, buffer: &[f32]) {
+        self.with(|ledger| {
+            if ledger.out.remove(&address(buffer)).is_none() {
+                ledger.foreign += 1;
+            }
+            ledger.discarded += 1;
+        });
+    }
+
+    /// `None` unless auditing.
+    pub fn report(&self, now: Instant) -> Option<AuditReport> {
+        let ledger = self.ledger.as_ref()?;
+        let ledger = ledger.lock().unwrap_or_else(PoisonError::into_inner);
+        Some(AuditReport {
+            outstanding: ledger.out.len(),
+            leaked: ledger
+                .out
+                .values()
+                .filter(|&&since| now.duration_since(since) > LEAK_AGE)
+                .count(),
+            foreign: ledger.foreign,
+            duplicate: ledger.duplicate,
+            discarded: ledger.discarded,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ledger_flags_leaks_reallocations_and_double_returns() {
+        let audit = PoolAudit::new(true);
+        let mut pool: Vec<Vec<f32>> = (0..3).map(|_| Vec::with_capacity(4)).collect();
+        audit.pooled(&pool);
+        let t0 = Instant::now();
+
+        // A clean round trip.
+        let buffer = pool.pop().unwrap();
+        audit.acquired(&buffer, t0);
+        audit.returned(&buffer);
+        pool.push(buffer);
+
+        // One grows past its capacity before coming back; one never does.
+        let mut grown = pool.pop().unwrap();
+        audit.acquired(&grown, t0);
+        grown.extend_from_slice(&[0.0; 64]);
+        audit.returned(&grown);
+        let kept = pool.pop().unwrap();
+        audit.acquired(&kept, t0);
+        audit.returned(&pool[0]);
+
+        let report = audit.report(t0 + Duration::from_secs(10)).unwrap();
+        assert_eq!(
+            report,
+            AuditReport {
+                outstanding: 2,
+                leaked: 2,
+                foreign: 1,
+                duplicate: 1,
+                discarded: 0,
+            }
+        );
+        assert!(!report.is_clean());
+
+        audit.discarded(&kept);
+        assert_eq!(audit.report(t0).unwrap().discarded, 1);
+        assert_eq!(PoolAudit::new(false).report(t0), None);
+    }
+}
@@ -149,6 +149,12 @@ mod tests {
         assert_eq!(id("Desk speakers"), Some(8));
         assert_eq!(id("NODE#9"), Some(9));
         assert_eq!(id("missing"), None);
+
+        // Only sinks can take the loopback.
+        let sink = |token| snapshot.find_playback_sink_by_token(token).map(|n| n.id);
+        assert_eq!(sink("Desk speakers"), Some(8));
+        assert_eq!(sink("NODE#9"), None);
+        assert_eq!(snapshot.playback_sinks().count(), 2);
     }
 
     #[test]
@@ -163,6 +169,7 @@ mod tests {
                 type_hint: None,
             }),
             audio_source: None,
+            forced_quantum: None,
         };
 
         let mut nodes = HashMap::new();
@@ -181,4 +188,19 @@ mod tests {
             Some(42)
         );
     }
+
+    #[test]
+    fn forced_quantum_tracks_the_settings_key() {
+        let mut defaults = MetadataDefaults::default();
+        let force = |defaults: &mut MetadataDefaults, value| {
+            defaults.apply_update(3, 0, "clock.force-quantum", None, value)
+        };
+        assert!(force(&mut defaults, Some("256")));
+        assert_eq!(defaults.forced_quantum, Some(256));
+        assert!(!force(&mut defaults, Some(" 256")));
+        // Zero is how PipeWire spells "not forced".
+        assert!(force(&mut defaults, Some("0")));
+        assert_eq!(defaults.forced_quantum, None);
+        assert!(!force(&mut defaults, None));
+    }
 }
@@ -3,10 +3,12 @@
 
 use super::state::RegistryState;
 use super::types::{
-    GraphPort, LinkSpec, NodeInfo, RegistryCommand, RegistrySnapshot, format_target_metadata,
+    FORCE_QUANTUM_KEY, GraphPort, LinkSpec, NodeInfo, RegistryCommand, RegistrySnapshot,
+    format_target_metadata,
 };
 use pipewire as pw;
 use pw::metadata::{Metadata, MetadataListener};
+use pw::node::{Node, NodeListener, NodeState};
 use pw::properties::properties;
 use pw::registry::{GlobalObject, RegistryRc};
 use pw::spa::utils::dict::DictRef;
@@ -25,6 +27,7 @@ const TARGET_OBJECT_KEY: &str = "target.object";
 const TARGET_NODE_KEY: &str = "target.node";
 const LINK_FACTORY_NAME: &str = "link-factory";
 const PREFERRED_METADATA_NAMES: &[&str] = &["settings", "default"];
+const SETTINGS_METADATA_NAME: &str = "settings";
 
 type PendingSyncs = Vec<(AsyncSeq, mpsc::Sender<()>)>;
 
@@ -109,6 +112,12 @@ impl AudioRegistryHandle {
         })
     }
 
+    /// Forces the whole graph to `frames` per cycle through the settings
+    /// metadata; zero releases it. PipeWire has no per-stream equivalent.
+    pub fn force_quantum(&self, frames: u32) -> bool {
+        self.send_command(RegistryCommand::ForceQuantum(frames))
+    }
+
     pub fn sync(&self) -> bool {
         let (tx, rx) = mpsc::channel();
         self.send_command(RegistryCommand::Sync(tx)) && rx.recv().is_ok()
@@ -217,6 +226,7 @@ fn registry_thread_main(
         registry: registry.clone(),
         runtime,
         metadata_bindings: Rc::default(),
+        node_bindings: Rc::default(),
         routing_metadata_id: Rc::new(RefCell::new(None)),
     };
     let metadata_bindings = Rc::clone(&registry_context.metadata_bindings);
@@ -405,6 +415,25 @@ fn apply_route(
     }
 }
 
+fn apply_forced_quantum(
+    metadata_bindings: &Rc<RefCell<HashMap<u32, MetadataBinding>>>,
+    frames: u32,
+) {
+    let bindings = metadata_bindings.borrow();
+    let Some(binding) = bindings
+        .values()
+        .find(|binding| binding.name.as_deref() == Some(SETTINGS_METADATA_NAME))
+    else {
+        warn!("[registry] cannot force the quantum; no settings metadata bound");
+        return;
+    };
+    let value = frames.to_string();
+    binding
+        .proxy
+        .set_property(0, FORCE_QUANTUM_KEY, None, Some(value.as_str()));
+    info!("[registry] set {FORCE_QUANTUM_KEY} to {frames}");
+}
+
 fn handle_command(
     command: RegistryCommand,
     link_state: &mut LinkState,
@@ -432,6 +461,7 @@ fn handle_command(
                 .as_ref()
                 .map(|(object, node)| (object.as_str(), node.as_str())),
         ),
+        RegistryCommand::ForceQuantum(frames) => apply_forced_quantum(metadata_bindings, frames),
         RegistryCommand::Shutdown => {
             info!("[registry] shutting down...");
             mainloop.quit();
@@ -446,6 +476,7 @@ struct RegistryContext {
     registry: RegistryRc,
     runtime: RegistryRuntime,
     metadata_bindings: Rc<RefCell<HashMap<u32, MetadataBinding>>>,
+    node_bindings: Rc<RefCell<HashMap<u32, NodeBinding>>>,
     routing_metadata_id: Rc<RefCell<Option<u32>>>,
 }
 
@@ -453,8 +484,12 @@ impl RegistryContext {
     fn handle_global_added(&self, global: &GlobalObject<&DictRef>) {
         match global.type_ {
             ObjectType::Node => {
-                self.runtime
-                    .mutate(|s| s.upsert_node(NodeInfo::from_global(global)));
+                let info = NodeInfo::from_global(global);
+                let stream = info.is_audio_application_output();
+                self.runtime.mutate(|s| s.upsert_node(info));
+                if stream {
+                    self.watch_node_state(global);
+                }
             }
             ObjectType::Device => {
                 let id = global.id;
@@ -471,6 +506,7 @@ impl RegistryContext {
     }
 
     fn handle_global_removed(&self, id: u32) {
+        self.node_bindings.borrow_mut().remove(&id);
         if self
             .runtime
             .mutate(|s| s.remove_port(id) || s.remove_node(id) || s.remove_device(id))
@@ -486,6 +522,33 @@ impl RegistryContext {
         }
     }
 
+    // Node state needs a bound proxy; the global only carries properties.
+    fn watch_node_state(&self, global: &GlobalObject<&DictRef>) {
+        let node_id = global.id;
+        if self.node_bindings.borrow().contains_key(&node_id) {
+            return;
+        }
+        let Ok(node) = self.registry.bind::<Node, _>(global) else {
+            warn!("[registry] failed to bind node {node_id}");
+            return;
+        };
+        let runtime = self.runtime.clone();
+        let listener = node
+            .add_listener_local()
+            .info(move |info| {
+                let running = matches!(info.state(), NodeState::Running);
+                runtime.mutate(|s| s.set_node_running(node_id, running));
+            })
+            .register();
+        self.node_bindings.borrow_mut().insert(
+            node_id,
+            NodeBinding {
+                _proxy: node,
+                _listener: listener,
+            },
+        );
+    }
+
     fn process_metadata_added(&self, global: &GlobalObject<&DictRef>) {
         let metadata_id = global.id;
         if self.metadata_bindings.borrow().contains_key(&metadata_id) {
@@ -573,6 +636,11 @@ impl RegistryContext {
     }
 }
 
+struct NodeBinding {
+    _proxy: Node,
+    _listener: NodeListener,
+}
+
 struct MetadataBinding {
     proxy: Metadata,
     _listener: MetadataListener,
@@ -26,7 +26,10 @@ impl RegistryState {
         }
     }
 
-    pub(super) fn upsert_node(&mut self, info: NodeInfo) -> bool {
+    pub(super) fn upsert_node(&mut self, mut info: NodeInfo) -> bool {
+        if let Some(existing) = self.nodes.get(&info.id) {
+            info.running = existing.running;
+        }
         if self.nodes.get(&info.id) == Some(&info) {
             return false;
         }
@@ -52,6 +55,17 @@ impl RegistryState {
         true
     }
 
+    pub(super) fn set_node_running(&mut self, id: u32, running: bool) -> bool {
+        match self.nodes.get_mut(&id) {
+            Some(node) if node.running != running => {
+                node.running = running;
+                self.bump_serial();
+                true
+            }
+            _ => false,
+        }
+    }
+
     pub(super) fn add_device(&mut self, id: u32) -> bool {
         if !self.device_ids.insert(id) {
             return false;
@@ -109,6 +109,7 @@ fn derive_node_direction(media_class: Option<&str>, port_direction: Option<&str>
 
 pub(super) const DEFAULT_AUDIO_SINK_KEY: &str = "default.audio.sink";
 pub(super) const DEFAULT_AUDIO_SOURCE_KEY: &str = "default.audio.source";
+pub(super) const FORCE_QUANTUM_KEY: &str = "clock.force-quantum";
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct DefaultTarget {
@@ -171,6 +172,8 @@ pub enum RegistryCommand {
         subject: u32,
         target: Option<(String, String)>,
     },
+    /// Zero hands the quantum back to the graph.
+    ForceQuantum(u32),
     Sync(std::sync::mpsc::Sender<()>),
     Shutdown,
 }
@@ -223,18 +226,42 @@ impl RegistrySnapshot {
             .find(|n| n.name.as_deref() == Some(virtual_sink::NODE_NAME))
     }
 
+    /// The virtual sink's delayed loopback stream.
+    pub fn loopback(&self) -> Option<&NodeInfo> {
+        self.nodes
+            .iter()
+            .find(|n| n.name.as_deref() == Some(virtual_sink::LOOPBACK_NODE_NAME))
+    }
+
     pub fn find_capture_device_by_token(&self, token: &str) -> Option<&NodeInfo> {
+        self.find_by_token(token, NodeInfo::is_capture_device_candidate)
+    }
+
+    /// The sink a trimmed application plays into in place of the virtual sink.
+    pub fn trim_sink(&self, app: u32) -> Option<&NodeInfo> {
+        let name = virtual_sink::trim_node_name(app);
+        self.nodes
+            .iter()
+            .find(|n| n.name.as_deref() == Some(name.as_str()))
+    }
+
+    /// Hardware and other sinks the loopback can play into.
+    pub fn playback_sinks(&self) -> impl Iterator<Item = &NodeInfo> {
+        self.nodes.iter().filter(|n| n.is_playback_sink())
+    }
+
+    pub fn find_playback_sink_by_token(&self, token: &str) -> Option<&NodeInfo> {
+        self.find_by_token(token, NodeInfo::is_playback_sink)
+    }
+
+    fn find_by_token(&self, token: &str, candidate: fn(&NodeInfo) -> bool) -> Option<&NodeInfo> {
         let node_token_id = token
             .get(..5)
             .filter(|prefix| prefix.eq_ignore_ascii_case("node#"))
             .and_then(|_| token.get(5..))
             .and_then(|id| id.parse::<u32>().ok())
             .filter(|id| format!("node#{id}").eq_ignore_ascii_case(token));
-        let candidates = || {
-            self.nodes
-                .iter()
-                .filter(|n| n.is_capture_device_candidate())
-        };
+        let candidates = || self.nodes.iter().filter(|n| candidate(n));
         candidates()
             .find(|n| {
                 n.name
@@ -269,6 +296,8 @@ pub struct NodeInfo {
     pub(super) app_name: Option<Arc<str>>,
     pub(super) object_serial: Option<Arc<str>>,
     pub ports: Vec<GraphPort>,
+    /// Only tracked for application streams; a paused player goes idle.
+    pub running: bool,
 }
 
 impl NodeInfo {
@@ -304,6 +333,7 @@ impl NodeInfo {
             app_name: app_name.map(Arc::from),
             object_serial: object_serial.map(Arc::from),
             ports: Vec::new(),
+            running: false,
         }
     }
 
@@ -340,11 +370,20 @@ impl NodeInfo {
                 || contains(self.description.as_deref(), "monitor"))
     }
 
+    pub fn is_playback_sink(&self) -> bool {
+        self.media_class.as_deref() == Some("Audio/Sink")
+            && self.name.as_deref().is_none_or(|name| {
+                name != virtual_sink::NODE_NAME && !name.starts_with(virtual_sink::TRIM_NODE_PREFIX)
+            })
+    }
+
     pub fn should_route_to(&self, sink: &Self) -> bool {
-        self.id != sink.id && self.is_audio_application_output()
+        self.id != sink.id
+            && self.is_audio_application_output()
+            && self.name.as_deref() != Some(virtual_sink::LOOPBACK_NODE_NAME)
     }
 
-    fn is_audio_application_output(&self) -> bool {
+    pub(super) fn is_audio_application_output(&self) -> bool {
         self.direction == Direction::Output
             && self
                 .media_class
@@ -380,6 +419,8 @@ impl NodeInfo {
 pub struct MetadataDefaults {
     pub audio_sink: Option<DefaultTarget>,
     pub audio_source: Option<DefaultTarget>,
+    /// `clock.force-quantum` from the settings metadata, unless unset or zero.
+    pub forced_quantum: Option<u32>,
 }
 
 impl MetadataDefaults {
@@ -391,6 +432,10 @@ impl MetadataDefaults {
         type_hint: Option<&str>,
         value: Option<&str>,
     ) -> bool {
+        if key == FORCE_QUANTUM_KEY {
+            let forced = value.and_then(|v| v.trim().parse().ok()).filter(|&q| q > 0);
+            return std::mem::replace(&mut self.forced_quantum, forced) != forced;
+        }
         let slot = match key {
             DEFAULT_AUDIO_SINK_KEY => &mut self.audio_sink,
             DEFAULT_AUDIO_SOURCE_KEY => &mut self.audio_source,
@@ -1,31 +1,63 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // Copyright (C) 2026 Maika Namuo
 
+use super::pool_audit::{AuditReport, PoolAudit};
 use crate::util::audio::DEFAULT_SAMPLE_RATE;
 use pipewire as pw;
 use pw::{properties::properties, spa};
 use spa::pod::Pod;
-use std::collections::VecDeque;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
 use std::error::Error;
 use std::io::{self, Cursor};
 use std::mem::size_of;
-use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Condvar, LazyLock, Mutex, PoisonError};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tracing::{error, info, warn};
 
 pub const NODE_NAME: &str = "openmeters.sink";
+/// The delayed loopback, linked in place of the sink's monitor ports while a
+/// delay is set.
+pub const LOOPBACK_NODE_NAME: &str = "openmeters.loopback";
+pub const MAX_LOOPBACK_DELAY_MS: f32 = 500.0;
+/// A trimmed application plays into a sink of its own, named this and its node id.
+pub const TRIM_NODE_PREFIX: &str = "openmeters.trim.";
 
 const DESCRIPTION: &str = "OpenMeters Sink";
+const LOOPBACK_DESCRIPTION: &str = "OpenMeters Loopback";
+const LOOPBACK_CHANNELS: u32 = 2;
+// Queued audio past the delay that a stalled reader may leave behind before
+// the oldest is dropped.
+const LOOPBACK_SLACK_FRAMES: usize = 8_192;
 const VIRTUAL_SINK_SAMPLE_RATE: u32 = DEFAULT_SAMPLE_RATE as u32;
 const CAPTURE_BUFFER_CAPACITY: usize = 64;
 const CAPTURE_POOL_INITIAL_SAMPLES: usize = 4_096;
 const CAPTURE_POOL_MAX_SAMPLES: usize = 65_536;
 const CAPTURE_POOL_SPARE_BUFFERS: usize = 8;
-const DESIRED_LATENCY_FRAMES: u32 = 256;
+pub const DESIRED_LATENCY_FRAMES: u32 = 256;
+// A cycle arriving this many periods after the last means the graph skipped
+// some; longer gaps are the node suspending. These are inferred from callback
+// timing, not PipeWire's own xrun reports.
+const GAP_PERIODS: f64 = 1.75;
+const MAX_CYCLE_GAP: Duration = Duration::from_secs(1);
+// Span the data callbacks' CPU time is averaged over before it is published.
+const LOAD_WINDOW: Duration = Duration::from_secs(1);
 
 static SINK_THREAD: Mutex<Option<thread::JoinHandle<()>>> = Mutex::new(None);
+// Gain on the monitor ports feeding the loopback; the captured signal is unaffected.
+static MONITOR_GAIN: AtomicU32 = AtomicU32::new(1.0_f32.to_bits());
+static MONITOR_CONTROL: Mutex<Option<pw::channel::Sender<()>>> = Mutex::new(None);
+// Sends (application node, trim gain) to the sink thread; `None` drops its sink.
+static TRIM_CONTROL: Mutex<Option<pw::channel::Sender<(u32, Option<f32>)>>> = Mutex::new(None);
+static LOOPBACK_DELAY_MS: AtomicU32 = AtomicU32::new(0.0_f32.to_bits());
+static SINK_CYCLE_GAPS: AtomicU64 = AtomicU64::new(0);
+// Share of real time spent in the sink and loopback callbacks, as f32 bits.
+static CALLBACK_LOAD: AtomicU32 = AtomicU32::new(0);
+static SINK_FORMAT: Mutex<Option<NegotiatedFormat>> = Mutex::new(None);
+static LOOPBACK_FORMAT: Mutex<Option<NegotiatedFormat>> = Mutex::new(None);
 static CAPTURE_BUFFER: LazyLock<Arc<CaptureBuffer>> =
     LazyLock::new(|| Arc::new(CaptureBuffer::new(CAPTURE_BUFFER_CAPACITY)));
 
@@ -34,6 +66,8 @@ pub struct CapturedAudio {
     pub samples: Vec<f32>,
     pub channels: u32,
     pub sample_rate: u32,
+    /// When the capture callback received it.
+    pub captured: Instant,
 }
 
 pub struct CaptureBuffer {
@@ -44,23 +78,26 @@ pub struct CaptureBuffer {
     available: Condvar,
     dropped_frames: AtomicU64,
     requested_pool_samples: AtomicUsize,
+    audit: PoolAudit,
 }
 
 impl CaptureBuffer {
     fn new(capacity: usize) -> Self {
         let pool_capacity = capacity.saturating_add(CAPTURE_POOL_SPARE_BUFFERS);
+        let pool: Vec<_> = (0..pool_capacity)
+            .map(|_| Vec::with_capacity(CAPTURE_POOL_INITIAL_SAMPLES))
+            .collect();
+        let audit = PoolAudit::new(cfg!(feature = "pool-audit"));
+        audit.pooled(&pool);
         Self {
             inner: Mutex::new(VecDeque::with_capacity(capacity)),
-            recycled: Mutex::new(
-                (0..pool_capacity)
-                    .map(|_| Vec::with_capacity(CAPTURE_POOL_INITIAL_SAMPLES))
-                    .collect(),
-            ),
+            recycled: Mutex::new(pool),
             capacity,
             pool_capacity,
             available: Condvar::new(),
             dropped_frames: AtomicU64::new(0),
             requested_pool_samples: AtomicUsize::new(0),
+            audit,
         }
     }
 
@@ -96,6 +133,11 @@ impl CaptureBuffer {
         self.dropped_frames.load(Ordering::Relaxed)
     }
 
+    /// The pool ledger, in builds with the `pool-audit` feature.
+    pub fn audit_report(&self) -> Option<AuditReport> {
+        self.audit.report(Instant::now())
+    }
+
     pub fn try_acquire_samples(&self, needed: usize) -> Option<Vec<f32>> {
         if !(1..=CAPTURE_POOL_MAX_SAMPLES).contains(&needed) {
             self.note_dropped_frame();
@@ -113,12 +155,14 @@ impl CaptureBuffer {
         };
         let mut samples = recycled.swap_remove(index);
         samples.clear();
+        self.audit.acquired(&samples, Instant::now());
         Some(samples)
     }
 
     pub fn recycle_samples(&self, samples: Vec<f32>) {
-        if let Ok(mut recycled) = self.recycled.try_lock() {
-            self.recycle_samples_locked(samples, &mut recycled);
+        match self.recycled.try_lock() {
+            Ok(mut recycled) => self.recycle_samples_locked(samples, &mut recycled),
+            Err(_) => self.audit.discarded(&samples),
         }
     }
 
@@ -130,7 +174,10 @@ impl CaptureBuffer {
     fn recycle_samples_locked(&self, mut samples: Vec<f32>, recycled: &mut Vec<Vec<f32>>) {
         if samples.capacity() <= CAPTURE_POOL_MAX_SAMPLES && recycled.len() < self.pool_capacity {
             samples.clear();
+            self.audit.returned(&samples);
             recycled.push(samples);
+        } else {
+            self.audit.discarded(&samples);
         }
     }
 
@@ -145,9 +192,11 @@ impl CaptureBuffer {
         for buffer in recycled.iter_mut().filter(|b| b.capacity() < requested) {
             if let Err(err) = buffer.try_reserve_exact(requested) {
                 warn!("[virtual-sink] failed to grow capture buffer pool: {err}");
+                self.audit.pooled(&recycled);
                 return;
             }
         }
+        self.audit.pooled(&recycled);
         drop(recycled);
         info!("[virtual-sink] grew capture buffer pool to {requested} samples per packet");
     }
@@ -190,6 +239,144 @@ fn bytes_per_sample(format: spa::param::audio::AudioFormat) -> Option<usize> {
     }
 }
 
+fn format_name(format: spa::param::audio::AudioFormat) -> &'static str {
+    use spa::param::audio::AudioFormat as Fmt;
+
+    match format {
+        Fmt::F32LE => "F32LE",
+        Fmt::F32BE => "F32BE",
+        Fmt::F64LE => "F64LE",
+        Fmt::F64BE => "F64BE",
+        Fmt::S8 => "S8",
+        Fmt::U8 => "U8",
+        Fmt::S16LE => "S16LE",
+        Fmt::S16BE => "S16BE",
+        Fmt::U16LE => "U16LE",
+        Fmt::U16BE => "U16BE",
+        Fmt::S24_32LE => "S24_32LE",
+        Fmt::S24_32BE => "S24_32BE",
+        Fmt::S24LE => "S24LE",
+        Fmt::S24BE => "S24BE",
+        Fmt::S32LE => "S32LE",
+        Fmt::S32BE => "S32BE",
+        Fmt::U32LE => "U32LE",
+        Fmt::U32BE => "U32BE",
+        _ => "unknown",
+    }
+}
+
+fn channel_name(position: u32) -> String {
+    use spa::sys::{
+        SPA_AUDIO_CHANNEL_AUX0, SPA_AUDIO_CHANNEL_FC, SPA_AUDIO_CHANNEL_FL, SPA_AUDIO_CHANNEL_FLC,
+        SPA_AUDIO_CHANNEL_FR, SPA_AUDIO_CHANNEL_FRC, SPA_AUDIO_CHANNEL_LFE, SPA_AUDIO_CHANNEL_MONO,
+        SPA_AUDIO_CHANNEL_RC, SPA_AUDIO_CHANNEL_RL, SPA_AUDIO_CHANNEL_RR, SPA_AUDIO_CHANNEL_SL,
+        SPA_AUDIO_CHANNEL_SR, SPA_AUDIO_CHANNEL_UNKNOWN,
+    };
+    // SPA reserves 0x1000 positions for aux channels.
+    const AUX_CHANNELS: u32 = 0x1000;
+
+    let name = match position {
+        SPA_AUDIO_CHANNEL_MONO => "MONO",
+        SPA_AUDIO_CHANNEL_FL => "FL",
+        SPA_AUDIO_CHANNEL_FR => "FR",
+        SPA_AUDIO_CHANNEL_FC => "FC",
+        SPA_AUDIO_CHANNEL_LFE => "LFE",
+        SPA_AUDIO_CHANNEL_SL => "SL",
+        SPA_AUDIO_CHANNEL_SR => "SR",
+        SPA_AUDIO_CHANNEL_FLC => "FLC",
+        SPA_AUDIO_CHANNEL_FRC => "FRC",
+        SPA_AUDIO_CHANNEL_RC => "RC",
+        SPA_AUDIO_CHANNEL_RL => "RL",
+        SPA_AUDIO_CHANNEL_RR => "RR",
+        SPA_AUDIO_CHANNEL_UNKNOWN => "UNK",
+        other => {
+            return match other.checked_sub(SPA_AUDIO_CHANNEL_AUX0) {
+                Some(aux) if aux < AUX_CHANNELS => format!("AUX{aux}"),
+                _ => format!("#{other}"),
+            };
+        }
+    };
+    name.to_owned()
+}
+
+/// What a stream settled on with its peer, for the diagnostics readout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegotiatedFormat {
+    pub format: &'static str,
+    /// Container bits per sample; `None` for formats the sink cannot read.
+    pub bits: Option<usize>,
+    pub rate: u32,
+    pub channel_map: Vec<String>,
+}
+
+impl NegotiatedFormat {
+    fn from_info(info: &spa::param::audio::AudioInfoRaw) -> Self {
+        let channels = (info.channels() as usize).min(spa::param::audio::MAX_CHANNELS);
+        Self {
+            format: format_name(info.format()),
+            bits: bytes_per_sample(info.format()).map(|bytes| bytes * 8),
+            rate: info.rate(),
+            channel_map: info.position()[..channels]
+                .iter()
+                .map(|&position| channel_name(position))
+                .collect(),
+        }
+    }
+
+    /// Meters stay silent on a format the sink cannot convert.
+    pub fn is_supported(&self) -> bool {
+        self.bits.is_some()
+    }
+
+    pub fn describe(&self) -> String {
+        let bits = self
+            .bits
+            .map_or_else(String::new, |bits| format!(" ({bits}-bit)"));
+        format!(
+            "{}{bits}, {} Hz, {} ch [{}]",
+            self.format,
+            self.rate,
+            self.channel_map.len(),
+            self.channel_map.join(" ")
+        )
+    }
+}
+
+// Records what a stream settled on. A format param without a pod means the
+// stream was unlinked, so the stored format goes with it.
+fn track_format(
+    slot: &Mutex<Option<NegotiatedFormat>>,
+    id: u32,
+    param: Option<&Pod>,
+) -> Option<spa::param::audio::AudioInfoRaw> {
+    if id != spa::param::ParamType::Format.as_raw() {
+        return None;
+    }
+    let info = param.and_then(|param| {
+        let mut info = spa::param::audio::AudioInfoRaw::new();
+        info.parse(param).ok().map(|_| info)
+    });
+    *slot.lock().unwrap_or_else(PoisonError::into_inner) =
+        info.as_ref().map(NegotiatedFormat::from_info);
+    info
+}
+
+/// The virtual sink's negotiated format, once a peer has connected.
+pub fn sink_format() -> Option<NegotiatedFormat> {
+    SINK_FORMAT
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// The delayed loopback's negotiated format, once it has been linked.
+pub fn loopback_format() -> Option<NegotiatedFormat> {
+    LOOPBACK_FORMAT
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
 fn audio_chunk(bytes: &[u8], offset: u32, size: u32, frame_bytes: usize) -> Option<&[u8]> {
     let frame_bytes = frame_bytes.max(1);
     let start = usize::try_from(offset).ok()?;
@@ -293,12 +480,192 @@ pub fn capture_buffer_handle() -> Arc<CaptureBuffer> {
     Arc::clone(&CAPTURE_BUFFER)
 }
 
+/// Graph cycles the sink's callback missed since start, judged by the gaps
+/// between callbacks. The loopback hangs off the sink's monitor ports in the
+/// same cycle, so its gaps land here too.
+pub fn cycle_gaps() -> u64 {
+    SINK_CYCLE_GAPS.load(Ordering::Relaxed)
+}
+
+/// Share of real time the sink and loopback data callbacks take, averaged
+/// over the last second.
+pub fn callback_load() -> f32 {
+    f32::from_bits(CALLBACK_LOAD.load(Ordering::Relaxed))
+}
+
+fn skipped_cycles(gap: Duration, period: Duration) -> u64 {
+    if period.is_zero() || gap > MAX_CYCLE_GAP {
+        return 0;
+    }
+    let periods = gap.as_secs_f64() / period.as_secs_f64();
+    if periods < GAP_PERIODS {
+        return 0;
+    }
+    periods.round() as u64 - 1
+}
+
+pub fn set_monitor_gain(gain: f32) {
+    let gain = if gain.is_finite() {
+        gain.clamp(0.0, 1.0)
+    } else {
+        1.0
+    };
+    if MONITOR_GAIN.swap(gain.to_bits(), Ordering::Relaxed) == gain.to_bits() {
+        return;
+    }
+    if let Some(control) = MONITOR_CONTROL
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()
+    {
+        let _ = control.send(());
+    }
+}
+
+fn apply_monitor_gain(stream: &pw::stream::Stream, channels: u32) {
+    let gain = f32::from_bits(MONITOR_GAIN.load(Ordering::Relaxed));
+    let volumes = vec![gain; channels.max(1) as usize];
+    if let Err(err) = stream.set_control(spa::sys::SPA_PROP_monitorVolumes, &volumes) {
+        warn!("[virtual-sink] failed to set monitor volume: {err}");
+    }
+}
+
+pub fn trim_node_name(app: u32) -> String {
+    format!("{TRIM_NODE_PREFIX}{app}")
+}
+
+/// Gives application `app` a sink of its own. What plays into it reaches the
+/// meters scaled by `gain` and the loopback unchanged; `None` removes it.
+pub fn set_app_trim(app: u32, gain: Option<f32>) -> bool {
+    TRIM_CONTROL
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()
+        .is_some_and(|control| control.send((app, gain)).is_ok())
+}
+
+/// Delays the loopback to the hardware sink; the meters keep the live signal.
+pub fn set_loopback_delay(ms: f32) {
+    let ms = if ms.is_finite() {
+        ms.clamp(0.0, MAX_LOOPBACK_DELAY_MS)
+    } else {
+        0.0
+    };
+    LOOPBACK_DELAY_MS.store(ms.to_bits(), Ordering::Relaxed);
+}
+
+fn loopback_delay_frames(sample_rate: u32) -> usize {
+    let ms = f32::from_bits(LOOPBACK_DELAY_MS.load(Ordering::Relaxed));
+    (ms * sample_rate as f32 / 1_000.0).round() as usize
+}
+
+// Interleaved samples on their way from the sink to the delayed loopback,
+// primed with silence as long as the delay.
+#[derive(Default)]
+struct DelayLine {
+    samples: VecDeque<f32>,
+    delay: usize,
+    scratch: Vec<f32>,
+}
+
+impl DelayLine {
+    fn set_delay(&mut self, frames: usize, channels: usize) {
+        let delay = frames * channels;
+        if delay != self.delay {
+            self.delay = delay;
+            self.samples.clear();
+            self.samples.resize(delay, 0.0);
+        }
+    }
+
+    fn write(&mut self, samples: impl IntoIterator<Item = f32>) {
+        self.samples.extend(samples);
+        let limit = self.delay + LOOPBACK_SLACK_FRAMES * LOOPBACK_CHANNELS as usize;
+        if self.samples.len() > limit {
+            self.samples.drain(..self.samples.len() - limit);
+        }
+    }
+
+    // `extra` is added sample by sample, as far as it goes, before the
+    // sink's `channels` are folded onto the stereo loopback.
+    fn write_bytes(
+        &mut self,
+        bytes: &[u8],
+        format: spa::param::audio::AudioFormat,
+        channels: usize,
+        extra: &[f32],
+    ) {
+        let Some(count) = converted_sample_count(bytes, format) else {
+            return;
+        };
+        let mut scratch = std::mem::take(&mut self.scratch);
+        scratch.reserve(count);
+        if convert_samples_to_f32_into(bytes, format, &mut scratch).is_some() {
+            for (sample, extra) in scratch.iter_mut().zip(extra) {
+                *sample += extra;
+            }
+            self.write(fold_to_stereo(&scratch, channels));
+        }
+        self.scratch = scratch;
+    }
+
+    // Silence while the reader has caught up with the writer.
+    fn pop(&mut self) -> f32 {
+        self.samples.pop_front().unwrap_or(0.0)
+    }
+}
+
+// Even channels go left and odd ones right, each side scaled back to one
+// channel's level; mono plays on both.
+fn fold_to_stereo(samples: &[f32], channels: usize) -> impl Iterator<Item = f32> + '_ {
+    let channels = channels.max(1);
+    let left = channels.div_ceil(2) as f32;
+    let right = (channels / 2).max(1) as f32;
+    samples.chunks_exact(channels).flat_map(move |frame| {
+        let mut sides = [0.0; 2];
+        for (channel, sample) in frame.iter().enumerate() {
+            sides[channel % 2] += sample;
+        }
+        if channels == 1 {
+            sides[1] = sides[0];
+        }
+        [sides[0] / left, sides[1] / right]
+    })
+}
+
+fn play_delayed(stream: &pw::stream::Stream, delay: &mut DelayLine) {
+    let Some(mut buffer) = stream.dequeue_buffer() else {
+        return;
+    };
+    let requested = buffer.requested() as usize;
+    let Some(data) = buffer.datas_mut().first_mut() else {
+        return;
+    };
+    let stride = LOOPBACK_CHANNELS as usize * size_of::<f32>();
+    let gain = f32::from_bits(MONITOR_GAIN.load(Ordering::Relaxed));
+    let Some(bytes) = data.data() else {
+        return;
+    };
+    let mut frames = bytes.len() / stride;
+    if requested > 0 {
+        frames = frames.min(requested);
+    }
+    for sample in bytes[..frames * stride].chunks_exact_mut(size_of::<f32>()) {
+        sample.copy_from_slice(&(delay.pop() * gain).to_le_bytes());
+    }
+    let chunk = data.chunk_mut();
+    *chunk.offset_mut() = 0;
+    *chunk.stride_mut() = stride as i32;
+    *chunk.size_mut() = (frames * stride) as u32;
+}
+
 crate::macros::default_struct! {
     struct VirtualSinkState {
         frame_bytes: usize = 2 * size_of::<f32>(),
         channels: u32 = 2,
         sample_rate: u32 = VIRTUAL_SINK_SAMPLE_RATE,
         format: spa::param::audio::AudioFormat = spa::param::audio::AudioFormat::F32LE,
+        last_cycle: Option<Instant> = None,
     }
 }
 
@@ -322,9 +689,193 @@ impl VirtualSinkState {
             self.channels
         );
     }
+
+    fn note_cycle(&mut self, frames: usize, now: Instant) {
+        let Some(last) = self.last_cycle.replace(now) else {
+            return;
+        };
+        let period = Duration::from_secs_f64(frames as f64 / f64::from(self.sample_rate.max(1)));
+        let skipped = skipped_cycles(now.duration_since(last), period);
+        if skipped > 0 {
+            SINK_CYCLE_GAPS.fetch_add(skipped, Ordering::Relaxed);
+        }
+    }
 }
 
-fn capture_audio_chunk(capture_buffer: &CaptureBuffer, bytes: &[u8], state: &VirtualSinkState) {
+/// Time the data callbacks spend, published once per `LOAD_WINDOW`.
+#[derive(Default)]
+struct CallbackLoad {
+    busy: Duration,
+    since: Option<Instant>,
+}
+
+impl CallbackLoad {
+    fn record(&mut self, started: Instant) {
+        let now = Instant::now();
+        self.busy += now.duration_since(started);
+        let since = *self.since.get_or_insert(started);
+        let elapsed = now.duration_since(since);
+        if elapsed >= LOAD_WINDOW {
+            let share = self.busy.as_secs_f64() / elapsed.as_secs_f64();
+            CALLBACK_LOAD.store((share as f32).to_bits(), Ordering::Relaxed);
+            *self = Self {
+                busy: Duration::ZERO,
+                since: Some(now),
+            };
+        }
+    }
+
+    // A suspended node runs no callbacks, so nothing would clear the last value.
+    fn reset(&mut self) {
+        *self = Self::default();
+        CALLBACK_LOAD.store(0, Ordering::Relaxed);
+    }
+}
+
+// A trimmed application's own sink; what it receives waits here for the main
+// sink's next cycle.
+struct TrimSink {
+    gain: f32,
+    queue: Rc<RefCell<VecDeque<f32>>>,
+    stream: pw::stream::StreamRc,
+    _listener: pw::stream::StreamListener<Rc<RefCell<VecDeque<f32>>>>,
+}
+
+#[derive(Default)]
+struct TrimMix {
+    sinks: HashMap<u32, TrimSink>,
+    // Trimmed audio for this cycle: scaled for the meters, as is for the loopback.
+    scaled: Vec<f32>,
+    plain: Vec<f32>,
+}
+
+impl TrimMix {
+    fn take(&mut self, frames: usize, channels: usize) {
+        self.scaled.clear();
+        self.plain.clear();
+        if self.sinks.is_empty() {
+            return;
+        }
+        self.scaled.resize(frames * channels, 0.0);
+        self.plain.resize(frames * channels, 0.0);
+        let trim_channels = LOOPBACK_CHANNELS as usize;
+        for sink in self.sinks.values() {
+            let mut queue = sink.queue.borrow_mut();
+            let available = (queue.len() / trim_channels).min(frames);
+            for frame in 0..available {
+                for channel in 0..channels {
+                    let sample = queue[frame * trim_channels + channel % trim_channels];
+                    self.plain[frame * channels + channel] += sample;
+                    self.scaled[frame * channels + channel] += sample * sink.gain;
+                }
+            }
+            queue.drain(..available * trim_channels);
+        }
+    }
+}
+
+fn queue_trimmed(stream: &pw::stream::Stream, queue: &mut VecDeque<f32>) {
+    let Some(mut buffer) = stream.dequeue_buffer() else {
+        return;
+    };
+    let frame_bytes = LOOPBACK_CHANNELS as usize * size_of::<f32>();
+    for data in buffer.datas_mut() {
+        let chunk = data.chunk();
+        let (offset, size) = (chunk.offset(), chunk.size());
+        if size == 0 {
+            continue;
+        }
+        if let Some(bytes) = data
+            .data()
+            .and_then(|bytes| audio_chunk(bytes, offset, size, frame_bytes))
+        {
+            queue.extend(
+                bytes
+                    .chunks_exact(size_of::<f32>())
+                    .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+            );
+        }
+        let limit = LOOPBACK_SLACK_FRAMES * LOOPBACK_CHANNELS as usize;
+        if queue.len() > limit {
+            queue.drain(..queue.len() - limit);
+        }
+        let chunk_mut = data.chunk_mut();
+        *chunk_mut.offset_mut() = 0;
+        *chunk_mut.size_mut() = size;
+        *chunk_mut.stride_mut() = frame_bytes as i32;
+    }
+}
+
+fn create_trim_sink(
+    core: &pw::core::CoreRc,
+    app: u32,
+    gain: f32,
+) -> Result<TrimSink, Box<dyn Error + Send + Sync>> {
+    let name = trim_node_name(app);
+    let description = format!("{DESCRIPTION} (trimmed app {app})");
+    let stream = pw::stream::StreamRc::new(
+        core.clone(),
+        &description,
+        properties! {
+            *pw::keys::MEDIA_CLASS => "Audio/Sink",
+            *pw::keys::MEDIA_TYPE => "Audio",
+            *pw::keys::MEDIA_ROLE => "Playback",
+            *pw::keys::MEDIA_CATEGORY => "Playback",
+            *pw::keys::NODE_DESCRIPTION => description.as_str(),
+            *pw::keys::NODE_NAME => name.as_str(),
+            *pw::keys::APP_NAME => "OpenMeters",
+            *pw::keys::NODE_LATENCY => format!("{}/{}", DESIRED_LATENCY_FRAMES, VIRTUAL_SINK_SAMPLE_RATE),
+        },
+    )?;
+    let queue = Rc::new(RefCell::new(VecDeque::new()));
+    let listener = stream
+        .add_local_listener_with_user_data(Rc::clone(&queue))
+        .process(|stream, queue| queue_trimmed(stream, &mut queue.borrow_mut()))
+        .register()?;
+    let format_bytes = build_format_pod(VIRTUAL_SINK_SAMPLE_RATE, true)?;
+    let mut params = [Pod::from_bytes(&format_bytes)
+        .ok_or_else(|| io::Error::other("serialized PipeWire format pod was invalid"))?];
+    stream.connect(
+        spa::utils::Direction::Input,
+        None,
+        pw::stream::StreamFlags::AUTOCONNECT | pw::stream::StreamFlags::MAP_BUFFERS,
+        &mut params,
+    )?;
+    stream.set_active(true)?;
+    apply_monitor_gain(&stream, LOOPBACK_CHANNELS);
+    info!("[virtual-sink] trim sink for node {app} at gain {gain}");
+    Ok(TrimSink {
+        gain,
+        queue,
+        stream,
+        _listener: listener,
+    })
+}
+
+fn update_trim(core: &pw::core::CoreRc, mix: &mut TrimMix, app: u32, gain: Option<f32>) {
+    match (gain, mix.sinks.get_mut(&app)) {
+        (Some(gain), Some(sink)) => sink.gain = gain,
+        (Some(gain), None) => match create_trim_sink(core, app, gain) {
+            Ok(sink) => {
+                mix.sinks.insert(app, sink);
+            }
+            Err(err) => error!("[virtual-sink] failed to create trim sink for node {app}: {err}"),
+        },
+        (None, _) => {
+            if mix.sinks.remove(&app).is_some() {
+                info!("[virtual-sink] removed trim sink for node {app}");
+            }
+        }
+    }
+}
+
+// `trimmed` is added to the converted samples, as far as it goes.
+fn capture_audio_chunk(
+    capture_buffer: &CaptureBuffer,
+    bytes: &[u8],
+    state: &VirtualSinkState,
+    trimmed: &[f32],
+) {
     let Some(sample_count) = converted_sample_count(bytes, state.format) else {
         capture_buffer.note_dropped_frame();
         return;
@@ -337,10 +888,14 @@ fn capture_audio_chunk(capture_buffer: &CaptureBuffer, bytes: &[u8], state: &Vir
         capture_buffer.note_dropped_frame();
         return;
     }
+    for (sample, trimmed) in samples.iter_mut().zip(trimmed) {
+        *sample += trimmed;
+    }
     capture_buffer.try_push(CapturedAudio {
         samples,
         channels: state.channels,
         sample_rate: state.sample_rate,
+        captured: Instant::now(),
     });
 }
 
@@ -351,8 +906,8 @@ fn run_virtual_sink() -> Result<(), Box<dyn Error + Send + Sync>> {
     let context = pw::context::ContextRc::new(&mainloop, None)?;
     let core = context.connect_rc(None)?;
 
-    let stream = pw::stream::StreamBox::new(
-        &core,
+    let stream = pw::stream::StreamRc::new(
+        core.clone(),
         DESCRIPTION,
         properties! {
             *pw::keys::MEDIA_CLASS => "Audio/Sink",
@@ -368,29 +923,87 @@ fn run_virtual_sink() -> Result<(), Box<dyn Error + Send + Sync>> {
 
     let audio_state = VirtualSinkState::default();
     let capture_buffer = capture_buffer_handle();
+    let channels = Rc::new(Cell::new(audio_state.channels));
+    let delay = Rc::new(RefCell::new(DelayLine::default()));
+    let load = Rc::new(RefCell::new(CallbackLoad::default()));
+    let trims = Rc::new(RefCell::new(TrimMix::default()));
+
+    // Not autoconnected: the router links it to the loopback sink while a
+    // delay is set. Both streams run on this loop, so they share the line.
+    let loopback = pw::stream::StreamRc::new(
+        core.clone(),
+        LOOPBACK_DESCRIPTION,
+        properties! {
+            *pw::keys::MEDIA_TYPE => "Audio",
+            *pw::keys::MEDIA_ROLE => "Playback",
+            *pw::keys::MEDIA_CATEGORY => "Playback",
+            *pw::keys::NODE_DESCRIPTION => LOOPBACK_DESCRIPTION,
+            *pw::keys::NODE_NAME => LOOPBACK_NODE_NAME,
+            *pw::keys::NODE_LATENCY => format!("{}/{}", DESIRED_LATENCY_FRAMES, VIRTUAL_SINK_SAMPLE_RATE),
+        },
+    )?;
+    let _loopback_listener = loopback
+        .add_local_listener_with_user_data(Rc::clone(&delay))
+        .param_changed(|_, _, id, param| {
+            track_format(&LOOPBACK_FORMAT, id, param);
+        })
+        .process({
+            let load = Rc::clone(&load);
+            move |stream, delay| {
+                let started = Instant::now();
+                play_delayed(stream, &mut delay.borrow_mut());
+                load.borrow_mut().record(started);
+            }
+        })
+        .register()?;
 
+    let (control_tx, control_rx) = pw::channel::channel();
+    let _control = control_rx.attach(mainloop.loop_(), {
+        let (stream, channels) = (stream.clone(), Rc::clone(&channels));
+        let trims = Rc::clone(&trims);
+        move |()| {
+            apply_monitor_gain(&stream, channels.get());
+            for sink in trims.borrow().sinks.values() {
+                apply_monitor_gain(&sink.stream, LOOPBACK_CHANNELS);
+            }
+        }
+    });
+    *MONITOR_CONTROL
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = Some(control_tx);
+
+    let (trim_tx, trim_rx) = pw::channel::channel();
+    let _trim_control = trim_rx.attach(mainloop.loop_(), {
+        let (core, trims) = (core.clone(), Rc::clone(&trims));
+        move |(app, gain)| update_trim(&core, &mut trims.borrow_mut(), app, gain)
+    });
+    *TRIM_CONTROL.lock().unwrap_or_else(PoisonError::into_inner) = Some(trim_tx);
+
+    let sink_trims = Rc::clone(&trims);
     let _listener = stream
         .add_local_listener_with_user_data(audio_state)
-        .state_changed(|_, _, previous, current| {
-            info!("[virtual-sink] state {previous:?} -> {current:?}");
-        })
-        .param_changed(|_, state, id, param| {
-            if id != spa::param::ParamType::Format.as_raw() {
-                return;
+        .state_changed({
+            let load = Rc::clone(&load);
+            move |_, state, previous, current| {
+                info!("[virtual-sink] state {previous:?} -> {current:?}");
+                state.last_cycle = None;
+                load.borrow_mut().reset();
             }
-
-            if let Some(pod) = param {
-                let mut info = spa::param::audio::AudioInfoRaw::new();
-                if info.parse(pod).is_ok() {
-                    state.update_from_info(&info);
-                }
+        })
+        .param_changed(move |stream, state, id, param| {
+            if let Some(info) = track_format(&SINK_FORMAT, id, param) {
+                state.update_from_info(&info);
+                channels.set(state.channels);
+                apply_monitor_gain(stream, state.channels);
             }
         })
         .process(move |stream, state| {
+            let now = Instant::now();
             let Some(mut buffer) = stream.dequeue_buffer() else {
                 return;
             };
 
+            let mut frames = 0;
             for data in buffer.datas_mut() {
                 let chunk = data.chunk();
                 let (offset, size) = (chunk.offset(), chunk.size());
@@ -398,12 +1011,22 @@ fn run_virtual_sink() -> Result<(), Box<dyn Error + Send + Sync>> {
                 if size == 0 {
                     continue;
                 }
+                frames = frames.max(size as usize / state.frame_bytes.max(1));
 
                 if let Some(bytes) = data
                     .data()
                     .and_then(|bytes| audio_chunk(bytes, offset, size, state.frame_bytes))
                 {
-                    capture_audio_chunk(&capture_buffer, bytes, state);
+                    let mut trims = sink_trims.borrow_mut();
+                    let channels = state.channels as usize;
+                    trims.take(bytes.len() / state.frame_bytes.max(1), channels);
+                    capture_audio_chunk(&capture_buffer, bytes, state, &trims.scaled);
+                    let frames = loopback_delay_frames(state.sample_rate);
+                    let mut delay = delay.borrow_mut();
+                    delay.set_delay(frames, LOOPBACK_CHANNELS as usize);
+                    if frames > 0 {
+                        delay.write_bytes(bytes, state.format, channels, &trims.plain);
+                    }
                 }
 
                 let chunk_mut = data.chunk_mut();
@@ -411,12 +1034,17 @@ fn run_virtual_sink() -> Result<(), Box<dyn Error + Send + Sync>> {
                 *chunk_mut.size_mut() = size;
                 *chunk_mut.stride_mut() = state.frame_bytes as i32;
             }
+            state.note_cycle(frames, now);
+            load.borrow_mut().record(now);
         })
         .register()?;
 
-    let format_bytes = build_format_pod(VIRTUAL_SINK_SAMPLE_RATE)?;
+    let format_bytes = build_format_pod(VIRTUAL_SINK_SAMPLE_RATE, false)?;
     let mut params = [Pod::from_bytes(&format_bytes)
         .ok_or_else(|| io::Error::other("serialized PipeWire format pod was invalid"))?];
+    let loopback_bytes = build_format_pod(VIRTUAL_SINK_SAMPLE_RATE, true)?;
+    let mut loopback_params = [Pod::from_bytes(&loopback_bytes)
+        .ok_or_else(|| io::Error::other("serialized PipeWire format pod was invalid"))?];
 
     // With RT_PROCESS, `process` may run concurrently with
     // `param_changed`, but pipewire-rs gives both callbacks mutable
@@ -429,18 +1057,40 @@ fn run_virtual_sink() -> Result<(), Box<dyn Error + Send + Sync>> {
     )?;
 
     stream.set_active(true)?;
+    loopback.connect(
+        spa::utils::Direction::Output,
+        None,
+        pw::stream::StreamFlags::MAP_BUFFERS,
+        &mut loopback_params,
+    )?;
 
     info!("[virtual-sink] PipeWire sink active");
     mainloop.run();
+    MONITOR_CONTROL
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    TRIM_CONTROL
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    trims.borrow_mut().sinks.clear();
     info!("[virtual-sink] main loop exited");
 
     Ok(())
 }
 
-fn build_format_pod(rate: u32) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+fn build_format_pod(rate: u32, stereo: bool) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
     let mut info = spa::param::audio::AudioInfoRaw::new();
     info.set_format(spa::param::audio::AudioFormat::F32LE);
     info.set_rate(rate);
+    if stereo {
+        info.set_channels(LOOPBACK_CHANNELS);
+        let mut position = [0; spa::param::audio::MAX_CHANNELS];
+        position[0] = spa::sys::SPA_AUDIO_CHANNEL_FL;
+        position[1] = spa::sys::SPA_AUDIO_CHANNEL_FR;
+        info.set_position(position);
+    }
 
     let (cursor, _) = pw::spa::pod::serialize::PodSerializer::serialize(
         Cursor::new(Vec::new()),
@@ -487,6 +1137,18 @@ mod tests {
         assert_eq!(audio_chunk(&bytes, 20, 4, 4), None);
     }
 
+    #[test]
+    fn cycle_gaps_count_skipped_cycles_but_not_suspends() {
+        let period = Duration::from_millis(5);
+        let gap = |ms| skipped_cycles(Duration::from_millis(ms), period);
+        assert_eq!(gap(5), 0);
+        assert_eq!(gap(8), 0);
+        assert_eq!(gap(10), 1);
+        assert_eq!(gap(21), 3);
+        assert_eq!(gap(2_000), 0);
+        assert_eq!(skipped_cycles(Duration::from_millis(10), Duration::ZERO), 0);
+    }
+
     #[test]
     fn capture_buffer_recycles_and_grows_sample_storage() {
         let buffer = CaptureBuffer::new(1);
@@ -498,6 +1160,7 @@ mod tests {
             samples,
             channels: 2,
             sample_rate: 48_000,
+            captured: Instant::now(),
         });
         let packet = buffer.pop_wait_timeout(Duration::ZERO).expect("packet");
         assert_eq!(packet.samples, [0.0, 1.0, 2.0, 3.0]);
@@ -582,12 +1245,74 @@ mod tests {
         assert!(converted_sample_count(&[0u8; 3], Fmt::S16LE).is_none());
     }
 
+    #[test]
+    fn negotiated_format_describes_depth_rate_and_channel_map() {
+        let mut info = spa::param::audio::AudioInfoRaw::new();
+        info.set_format(Fmt::S24_32LE);
+        info.set_rate(44_100);
+        info.set_channels(3);
+        let mut position = [0; spa::param::audio::MAX_CHANNELS];
+        position[..3].copy_from_slice(&[
+            spa::sys::SPA_AUDIO_CHANNEL_FL,
+            spa::sys::SPA_AUDIO_CHANNEL_FR,
+            spa::sys::SPA_AUDIO_CHANNEL_AUX0 + 2,
+        ]);
+        info.set_position(position);
+        let format = NegotiatedFormat::from_info(&info);
+        assert!(format.is_supported());
+        assert_eq!(
+            format.describe(),
+            "S24_32LE (32-bit), 44100 Hz, 3 ch [FL FR AUX2]"
+        );
+
+        info.set_format(Fmt::S24LE);
+        let format = NegotiatedFormat::from_info(&info);
+        assert!(!format.is_supported());
+        assert!(format.describe().starts_with("S24LE, "));
+    }
+
+    #[test]
+    fn delay_line_plays_silence_for_the_delay_then_the_signal() {
+        let mut line = DelayLine::default();
+        line.set_delay(2, 2);
+        line.write([1.0, 2.0, 3.0, 4.0]);
+        let played: Vec<f32> = (0..10).map(|_| line.pop()).collect();
+        assert_eq!(played, [0.0, 0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 4.0, 0.0, 0.0]);
+
+        // A stalled reader drops the oldest audio beyond the slack.
+        line.set_delay(0, 2);
+        let slack = LOOPBACK_SLACK_FRAMES * LOOPBACK_CHANNELS as usize;
+        line.write((0..slack + 4).map(|n| n as f32));
+        assert_eq!(line.samples.len(), slack);
+        assert_eq!(line.pop(), 4.0);
+    }
+
+    #[test]
+    fn delay_line_folds_any_channel_count_onto_stereo() {
+        let mono: Vec<f32> = fold_to_stereo(&[0.5, 0.25], 1).collect();
+        assert_eq!(mono, [0.5, 0.5, 0.25, 0.25]);
+        let stereo: Vec<f32> = fold_to_stereo(&[0.5, 0.25], 2).collect();
+        assert_eq!(stereo, [0.5, 0.25]);
+        let surround: Vec<f32> = fold_to_stereo(&[0.5, 0.75, 0.25, 0.0, 0.75, 0.0], 6).collect();
+        assert_eq!(surround, [0.5, 0.25]);
+
+        let mut line = DelayLine::default();
+        line.set_delay(1, LOOPBACK_CHANNELS as usize);
+        let bytes: Vec<u8> = [1.0f32, 0.0, 1.0]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        line.write_bytes(&bytes, Fmt::F32LE, 3, &[]);
+        let played: Vec<f32> = (0..4).map(|_| line.pop()).collect();
+        assert_eq!(played, [0.0, 0.0, 1.0, 0.0]);
+    }
+
     #[test]
     fn capture_sanitizes_non_finite_float_samples() {
         let buffer = CaptureBuffer::new(1);
         let state = VirtualSinkState::default();
 
-        capture_audio_chunk(&buffer, &f32::NAN.to_le_bytes(), &state);
+        capture_audio_chunk(&buffer, &f32::NAN.to_le_bytes(), &state, &[]);
 
         let packet = buffer.pop_wait_timeout(Duration::ZERO).expect("packet");
         assert_eq!(packet.samples, [0.0]);
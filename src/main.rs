@@ -1,6 +1,8 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // Copyright (C) 2026 Maika Namuo
 
+mod cli;
+mod doctor;
 mod domain;
 mod dsp;
 mod infra;
@@ -9,8 +11,10 @@ mod persistence;
 mod ui;
 mod util;
 mod visuals;
+use cli::CliOptions;
 use domain::routing::{DeviceSelection, RoutingCommand, RoutingConfig};
 use infra::pipewire::{meter_tap, monitor, registry, virtual_sink};
+use infra::{graphics, ipc};
 use persistence::settings::SettingsHandle;
 use std::{
     process::ExitCode,
@@ -22,31 +26,64 @@ use util::telemetry;
 use tracing::{error, info};
 
 fn main() -> ExitCode {
+    let cli = match CliOptions::parse(std::env::args().skip(1)) {
+        Ok(cli) if cli.help => {
+            println!("{}", cli::USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Ok(cli) if cli.doctor => return doctor::run(cli.profile.as_deref()),
+        Ok(CliOptions {
+            screenshots: Some(dir),
+            ..
+        }) => return visuals::snapshot::run(&dir),
+        Ok(cli) => cli,
+        Err(err) => {
+            eprintln!("openmeters: {err}\n\n{}", cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
     telemetry::init();
     info!("OpenMeters starting up");
 
     let (routing_tx, routing_rx) = mpsc::channel::<RoutingCommand>();
     let (snapshot_tx, snapshot_rx) = async_channel::bounded::<registry::RegistrySnapshot>(64);
 
-    let settings_handle = SettingsHandle::load_or_default();
+    let settings_handle = SettingsHandle::load_or_default(cli.profile.as_deref());
+    settings_handle.apply_overrides(|settings| cli.apply(settings));
+    // Before any thread or graphics adapter exists; see `graphics::export`.
+    graphics::export(settings_handle.borrow().data.render_backend);
+    graphics::detect();
+    // A layer-shell bar can't be minimized, so --hidden would be ignored.
+    if cli.start_hidden && settings_handle.borrow().data.bar.enabled && ui::layershell_available() {
+        eprintln!("openmeters: --hidden can't hide the bar; turn the bar off or drop --hidden");
+        return ExitCode::from(2);
+    }
     let routing_config = {
         let guard = settings_handle.borrow();
         let settings = &guard.data;
         RoutingConfig {
             capture_mode: settings.capture_mode,
             preferred_device: DeviceSelection::from_token(settings.last_device_name.clone()),
+            loopback_sink: DeviceSelection::from_token(settings.loopback_sink.clone()),
+            loopback_delay_ms: settings.loopback_delay_ms,
         }
     };
 
     let registry_thread = monitor::init_registry_monitor(routing_rx, snapshot_tx, routing_config);
 
     virtual_sink::run();
+    let ipc_server = ipc::spawn();
 
     let ui_config = UiConfig {
         routing_sender: routing_tx,
         registry_updates: registry_thread.is_some().then(|| Arc::new(snapshot_rx)),
         audio_frames: meter_tap::audio_sample_stream(),
         settings_handle: settings_handle.clone(),
+        start_hidden: cli.start_hidden,
+        ipc_requests: ipc_server
+            .as_ref()
+            .map(|server| Arc::clone(&server.requests)),
     };
 
     let exit_code = match ui::run(ui_config) {
@@ -57,6 +94,7 @@ fn main() -> ExitCode {
         }
     };
     settings_handle.flush();
+    drop(ipc_server);
 
     if let Some(handle) = registry_thread {
         info!("[main] shutdown requested; waiting for registry monitor to exit...");
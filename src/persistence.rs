@@ -4,8 +4,10 @@
 mod lossy;
 mod palette;
 mod schema;
+pub mod session;
 mod store;
 mod theme;
+pub mod tracks;
 mod visuals;
 
 use std::{fs, io, path::Path};
@@ -22,16 +24,19 @@ fn write_json_atomic(path: &Path, json: &str) -> io::Result<()> {
 pub mod settings {
     pub use super::palette::{HasPalette, PaletteSettings};
     pub use super::schema::{
-        BAR_MAX_HEIGHT, BAR_MIN_HEIGHT, BarAlignment, BarSettings, MainWindowSettings,
-        clamp_bar_height,
+        APPLICATION_TRIM_MIN_DB, BAR_MAX_HEIGHT, BAR_MIN_HEIGHT, BarAlignment, BarSettings,
+        BeatPulseSettings, BeatPulseTarget, LevelRanges, MainWindowSettings, MicCalibration,
+        OUTPUT_DIM_MIN_DB, OUTPUT_DIM_MUTE_DB, OutputDimSettings, RenderBackend, TuningSettings,
+        UiSettings, WINDOW_OPACITY_MIN, WindowOpacitySettings, clamp_bar_height,
     };
-    pub use super::store::SettingsHandle;
+    pub use super::store::{SettingsHandle, data_dir, settings_file};
     pub(crate) use super::theme::canonical_theme_name;
     pub use super::theme::{BUILTIN_THEME, ThemeChoice, ThemeFile, ThemeOrigin};
     pub(crate) use super::visuals::SettingsConfig;
     pub use super::visuals::{
-        LoudnessSettings, ModuleSettings, OscilloscopeSettings, PopoutWindowSettings,
-        SpectrogramSettings, SpectrumSettings, StereometerSettings, VisualSettings,
+        DisplayOverride, FrequencyMarker, LoudnessSettings, MemoryTrace, ModuleSettings,
+        OscilloscopeSettings, PopoutWindowSettings, SPECTRUM_MEMORY_SLOTS, SpectrogramPreset,
+        SpectrogramSettings, SpectrumSettings, StereometerSettings, TunerSettings, VisualSettings,
         WaveformSettings,
     };
 }
@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // Copyright (C) 2026 Maika Namuo
 
-use crate::util::color::{EPSILON, palettes_equal, sanitize_stop_spreads};
+use crate::util::color::{ColorBlend, EPSILON, palettes_equal, sanitize_stop_spreads};
 use iced::Color;
 use serde::de::{self, Deserializer};
 use serde::ser::Serializer;
@@ -50,23 +50,40 @@ pub struct PaletteSettings {
     pub stop_positions: Option<Vec<f32>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub stop_spreads: Option<Vec<f32>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blend: Option<ColorBlend>,
 }
 
 impl PaletteSettings {
     pub fn if_differs_from(colors: &[Color], defaults: &[Color]) -> Option<Self> {
         color_stops_if_differ(colors, defaults).map(|stops| Self {
             stops,
-            stop_positions: None,
-            stop_spreads: None,
+            ..Self::default()
+        })
+    }
+
+    // A non-default blend is worth storing even when the colors are stock.
+    pub fn with_blend(palette: Option<Self>, blend: ColorBlend) -> Option<Self> {
+        if blend == ColorBlend::default() {
+            return palette;
+        }
+        Some(Self {
+            blend: Some(blend),
+            ..palette.unwrap_or_default()
         })
     }
 
+    pub fn blend_or_default(palette: Option<&Self>) -> ColorBlend {
+        palette.and_then(|p| p.blend).unwrap_or_default()
+    }
+
     pub fn from_state(
         colors: &[Color],
         defaults: &[Color],
         positions: &[f32],
         default_positions: &[f32],
         spreads: &[f32],
+        blend: ColorBlend,
     ) -> Option<Self> {
         let count = defaults.len();
         debug_assert_eq!(positions.len(), default_positions.len());
@@ -78,12 +95,14 @@ impl PaletteSettings {
         let sanitized_spreads = sanitize_stop_spreads(Some(spreads), count);
         let spreads_differ = sanitized_spreads.iter().any(|s| (*s - 1.0).abs() > EPSILON);
 
-        (stops.is_some() || positions_differ || spreads_differ).then_some(Self {
+        let palette = (stops.is_some() || positions_differ || spreads_differ).then_some(Self {
             stops: stops.unwrap_or_default(),
             stop_positions: (positions_differ && count > 2)
                 .then(|| positions[1..count - 1].to_vec()),
             stop_spreads: spreads_differ.then_some(sanitized_spreads),
-        })
+            blend: None,
+        });
+        Self::with_blend(palette, blend)
     }
 }
 
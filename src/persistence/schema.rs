@@ -1,8 +1,12 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // Copyright (C) 2026 Maika Namuo
 use super::{lossy, palette::ColorSetting, visuals::VisualSettings};
-use crate::domain::routing::CaptureMode;
+use crate::domain::automation::{AutomationRule, IdlePause, RuleCommand, SilenceWatch};
+use crate::domain::routing::{CaptureMode, ChannelMatrix, Mixdown};
+use crate::util::audio::musical::{DEFAULT_A4_HZ, Temperament};
+use crate::util::audio::{DbRange, DecimalSeparator, LevelScale, db_to_gain};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 const MAIN_WINDOW_DEFAULT_WIDTH: u32 = 420;
 const MAIN_WINDOW_DEFAULT_HEIGHT: u32 = 520;
@@ -15,7 +19,15 @@ pub fn clamp_bar_height(height: u32) -> u32 {
     height.clamp(BAR_MIN_HEIGHT, BAR_MAX_HEIGHT)
 }
 
+pub const OUTPUT_DIM_MIN_DB: f32 = 3.0;
+pub const OUTPUT_DIM_MUTE_DB: f32 = 60.0;
+
+pub const WINDOW_OPACITY_MIN: f32 = 0.1;
+
+pub const APPLICATION_TRIM_MIN_DB: f32 = -40.0;
+
 crate::macros::choice_enum!(all pub enum BarAlignment { #[default] Top => "Top", Bottom => "Bottom" });
+crate::macros::choice_enum!(all pub enum RenderBackend { #[default] Auto => "Auto", Gpu => "GPU", Gl => "OpenGL", Software => "Software" });
 
 crate::macros::default_struct! {
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -38,6 +50,117 @@ crate::macros::default_struct! {
     }
 }
 
+crate::macros::default_struct! {
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    pub struct WindowOpacitySettings {
+        pub level: f32 = 1.0,
+    }
+}
+
+impl WindowOpacitySettings {
+    /// Never fully transparent, so the window stays findable.
+    pub fn alpha(self) -> f32 {
+        if self.level.is_finite() {
+            self.level.clamp(WINDOW_OPACITY_MIN, 1.0)
+        } else {
+            1.0
+        }
+    }
+}
+
+crate::macros::default_struct! {
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    pub struct OutputDimSettings {
+        pub amount_db: f32 = 20.0,
+    }
+}
+
+impl OutputDimSettings {
+    /// Loopback gain while dimmed; the top of the range mutes.
+    pub fn gain(self) -> f32 {
+        if self.amount_db >= OUTPUT_DIM_MUTE_DB {
+            0.0
+        } else {
+            db_to_gain(-self.amount_db.max(0.0))
+        }
+    }
+}
+
+/// An imported measurement-mic calibration; only the rows are needed once read.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MicCalibration {
+    /// File it was read from, for display.
+    pub name: String,
+    /// `[Hz, dB]` rows of the mic's response.
+    pub points: Vec<[f32; 2]>,
+}
+
+crate::macros::choice_enum!(all pub enum BeatPulseTarget {
+    #[default] Off => "Off", Visuals => "Visuals", Background => "Background",
+    Both => "Visuals + background"
+});
+
+impl BeatPulseTarget {
+    pub fn visuals(self) -> bool {
+        matches!(self, Self::Visuals | Self::Both)
+    }
+
+    pub fn background(self) -> bool {
+        matches!(self, Self::Background | Self::Both)
+    }
+}
+
+crate::macros::default_struct! {
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    pub struct BeatPulseSettings {
+        pub target: BeatPulseTarget = BeatPulseTarget::default(),
+        pub intensity: f32 = 0.35,
+    }
+}
+
+crate::macros::default_struct! {
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    pub struct TuningSettings {
+        pub a4_hz: f32 = DEFAULT_A4_HZ,
+        pub temperament: Temperament = Temperament::default(),
+    }
+}
+
+crate::macros::default_struct! {
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    pub struct LevelRanges {
+        pub spectrum: DbRange = LevelScale::Spectrum.default_range(),
+        pub spectrogram: DbRange = LevelScale::Spectrogram.default_range(),
+        pub loudness: DbRange = LevelScale::Loudness.default_range(),
+        pub waveform: DbRange = LevelScale::Waveform.default_range(),
+    }
+}
+
+impl LevelRanges {
+    pub fn get(&self, scale: LevelScale) -> DbRange {
+        match scale {
+            LevelScale::Spectrum => self.spectrum,
+            LevelScale::Spectrogram => self.spectrogram,
+            LevelScale::Loudness => self.loudness,
+            LevelScale::Waveform => self.waveform,
+        }
+    }
+
+    pub fn get_mut(&mut self, scale: LevelScale) -> &mut DbRange {
+        match scale {
+            LevelScale::Spectrum => &mut self.spectrum,
+            LevelScale::Spectrogram => &mut self.spectrogram,
+            LevelScale::Loudness => &mut self.loudness,
+            LevelScale::Waveform => &mut self.waveform,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
 pub struct UiSettings {
@@ -48,7 +171,39 @@ pub struct UiSettings {
     pub main_window: MainWindowSettings,
     pub bar: BarSettings,
     pub capture_mode: CaptureMode,
+    pub render_backend: RenderBackend,
     pub last_device_name: Option<String>,
+    /// Sink the loopback plays into; `None` follows the default sink.
+    pub loopback_sink: Option<String>,
+    /// Lag, in milliseconds, of the loopback behind the meters.
+    pub loopback_delay_ms: f32,
+    /// Divided out of the spectrum and spectrogram while capturing a device.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mic_calibration: Option<MicCalibration>,
+    #[serde(skip_serializing_if = "ChannelMatrix::is_passthrough")]
+    pub channel_matrix: ChannelMatrix,
+    #[serde(skip_serializing_if = "Mixdown::is_average")]
+    pub mixdown: Mixdown,
+    pub output_dim: OutputDimSettings,
+    /// Attenuation, in dB, of each application's stream in the capture mix,
+    /// keyed by application name.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub application_trims: BTreeMap<String, f32>,
+    pub opacity: WindowOpacitySettings,
+    /// Reference pitch and temperament for note names.
+    pub tuning: TuningSettings,
+    /// Cosmetic brightening on detected beats.
+    pub beat_pulse: BeatPulseSettings,
+    /// Floor and ceiling, in dBFS, that each kind of meter reads in.
+    pub level_ranges: LevelRanges,
+    /// Separator for decimals in readouts, labels and settings.
+    pub decimal_separator: DecimalSeparator,
+    pub rules: Vec<AutomationRule>,
+    pub rule_command: RuleCommand,
+    pub idle_pause: IdlePause,
+    pub silence_watch: SilenceWatch,
+    /// Checkpoints the running per-track measurement so a restart resumes it.
+    pub resume_track_loudness: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub theme: Option<String>,
 }
@@ -78,8 +233,85 @@ impl UiSettings {
                     lossy::fields!(map, out, "bar"; enabled, alignment, height, monitor);
                 });
             }
+            if let Some(value) = map.remove("output_dim") {
+                out.output_dim = lossy::settings(
+                    value,
+                    "output_dim",
+                    OutputDimSettings::default(),
+                    |map, out| {
+                        lossy::fields!(map, out, "output_dim"; amount_db);
+                    },
+                );
+            }
+            if let Some(value) = map.remove("opacity") {
+                out.opacity = lossy::settings(
+                    value,
+                    "opacity",
+                    WindowOpacitySettings::default(),
+                    |map, out| {
+                        lossy::fields!(map, out, "opacity"; level);
+                    },
+                );
+            }
+            if let Some(value) = map.remove("tuning") {
+                out.tuning =
+                    lossy::settings(value, "tuning", TuningSettings::default(), |map, out| {
+                        lossy::fields!(map, out, "tuning"; a4_hz, temperament);
+                    });
+            }
+            if let Some(value) = map.remove("beat_pulse") {
+                out.beat_pulse = lossy::settings(
+                    value,
+                    "beat_pulse",
+                    BeatPulseSettings::default(),
+                    |map, out| {
+                        lossy::fields!(map, out, "beat_pulse"; target, intensity);
+                    },
+                );
+            }
+            if let Some(value) = map.remove("level_ranges") {
+                out.level_ranges =
+                    lossy::settings(value, "level_ranges", LevelRanges::default(), |map, out| {
+                        lossy::fields!(map, out, "level_ranges";
+                            spectrum, spectrogram, loudness, waveform
+                        );
+                    });
+            }
+            if let Some(value) = map.remove("mixdown") {
+                out.mixdown = lossy::settings(value, "mixdown", Mixdown::default(), |map, out| {
+                    lossy::fields!(map, out, "mixdown"; weights, exclude_lfe);
+                });
+            }
+            if let Some(value) = map.remove("rule_command") {
+                out.rule_command =
+                    lossy::settings(value, "rule_command", RuleCommand::default(), |map, out| {
+                        lossy::fields!(map, out, "rule_command"; command, min_interval_secs);
+                    });
+            }
+            if let Some(value) = map.remove("idle_pause") {
+                out.idle_pause =
+                    lossy::settings(value, "idle_pause", IdlePause::default(), |map, out| {
+                        lossy::fields!(map, out, "idle_pause";
+                            enabled, application, silence_secs, collapse
+                        );
+                    });
+            }
+            if let Some(value) = map.remove("silence_watch") {
+                out.silence_watch = lossy::settings(
+                    value,
+                    "silence_watch",
+                    SilenceWatch::default(),
+                    |map, out| {
+                        lossy::fields!(map, out, "silence_watch";
+                            enabled, silence_secs, notify, osc_target
+                        );
+                    },
+                );
+            }
             lossy::fields!(map, out, "settings";
-                background_color, decorations, capture_mode, last_device_name, theme
+                background_color, decorations, capture_mode, render_backend, last_device_name,
+                loopback_sink, loopback_delay_ms, mic_calibration, channel_matrix, theme, rules,
+                resume_track_loudness, decimal_separator, application_trims
             );
         })
     }
@@ -87,9 +319,9 @@ impl UiSettings {
 
 #[cfg(test)]
 mod tests {
-    use super::super::visuals::{PopoutWindowSettings, SpectrumSettings};
+    use super::super::visuals::{DisplayOverride, PopoutWindowSettings, SpectrumSettings};
     use super::*;
-    use crate::domain::visuals::VisualKind;
+    use crate::domain::visuals::{VisualId, VisualKind};
 
     #[test]
     fn persisted_container_defaults_are_stable() {
@@ -113,19 +345,24 @@ mod tests {
     fn popout_json_omits_default_active_state() {
         let mut settings = UiSettings::default();
         settings.visuals.popouts.insert(
-            VisualKind::Spectrum,
+            VisualId::primary(VisualKind::Spectrum),
             PopoutWindowSettings {
                 width: 640,
                 height: 360,
                 popped_out: true,
+                display: None,
             },
         );
         settings.visuals.popouts.insert(
-            VisualKind::Waveform,
+            VisualId::primary(VisualKind::Waveform),
             PopoutWindowSettings {
                 width: 320,
                 height: 200,
                 popped_out: false,
+                display: Some(DisplayOverride {
+                    floor_db: -80.0,
+                    gamma: 1.5,
+                }),
             },
         );
 
@@ -133,6 +370,8 @@ mod tests {
         let popouts = &value["visuals"]["popouts"];
         assert!(popouts["spectrum"].get("popped_out").is_none());
         assert_eq!(popouts["waveform"]["popped_out"], false);
+        assert!(popouts["spectrum"].get("display").is_none());
+        assert_eq!(popouts["waveform"]["display"]["floor_db"], -80.0);
     }
 
     #[test]
@@ -150,6 +389,7 @@ mod tests {
                 "height": "tall",
                 "monitor": "HDMI-A-1",
             },
+            "idle_pause": { "enabled": true, "silence_secs": "long" },
             "visuals": {
                 "modules": {
                     "spectrum": {
@@ -160,15 +400,19 @@ mod tests {
                             "show_grid": false,
                         },
                     },
+                    "spectrum#2": { "enabled": false },
+                    "spectrum#9": { "enabled": true },
                     "made_up": { "enabled": true },
                 },
-                "order": ["spectrum", "made_up", 4],
+                "order": ["spectrum", "spectrum#2", "made_up", 4],
                 "width_basis": {
                     "spectrum": 320.0,
                     "waveform": "wide",
                     "loudness": -1.0,
                     "made_up": 1.0,
                 },
+                "height_basis": { "spectrogram": 240.0, "spectrum": 0.0 },
+                "vertical": true,
                 "popouts": {
                     "spectrum": { "width": 640, "height": "tall" },
                     "oscilloscope": { "width": 300, "height": 200, "popped_out": false },
@@ -186,22 +430,38 @@ mod tests {
         assert_eq!(settings.bar.alignment, BarAlignment::Bottom);
         assert_eq!(settings.bar.height, BAR_DEFAULT_HEIGHT);
         assert_eq!(settings.bar.monitor.as_deref(), Some("HDMI-A-1"));
+        assert!(settings.idle_pause.enabled);
+        assert_eq!(
+            settings.idle_pause.silence_secs,
+            IdlePause::default().silence_secs
+        );
 
-        assert_eq!(settings.visuals.order, vec![VisualKind::Spectrum]);
+        let [spectrum, spectrogram, oscilloscope] = [
+            VisualKind::Spectrum,
+            VisualKind::Spectrogram,
+            VisualKind::Oscilloscope,
+        ]
+        .map(VisualId::primary);
+        let second = VisualId {
+            instance: 1,
+            ..spectrum
+        };
+        assert_eq!(settings.visuals.order, vec![spectrum, second]);
         assert_eq!(settings.visuals.width_basis.len(), 1);
-        assert_eq!(settings.visuals.width_basis[&VisualKind::Spectrum], 320.0);
+        assert_eq!(settings.visuals.width_basis[&spectrum], 320.0);
+        assert_eq!(settings.visuals.height_basis.len(), 1);
+        assert_eq!(settings.visuals.height_basis[&spectrogram], 240.0);
+        assert!(settings.visuals.vertical);
         assert_eq!(settings.visuals.popouts.len(), 2);
-        assert_eq!(settings.visuals.popouts[&VisualKind::Spectrum].width, 640);
-        assert_eq!(settings.visuals.popouts[&VisualKind::Spectrum].height, 0);
-        assert!(settings.visuals.popouts[&VisualKind::Spectrum].popped_out);
-        assert_eq!(
-            settings.visuals.popouts[&VisualKind::Oscilloscope].width,
-            300
-        );
-        assert!(!settings.visuals.popouts[&VisualKind::Oscilloscope].popped_out);
+        assert_eq!(settings.visuals.popouts[&spectrum].width, 640);
+        assert_eq!(settings.visuals.popouts[&spectrum].height, 0);
+        assert!(settings.visuals.popouts[&spectrum].popped_out);
+        assert_eq!(settings.visuals.popouts[&oscilloscope].width, 300);
+        assert!(!settings.visuals.popouts[&oscilloscope].popped_out);
 
-        assert_eq!(settings.visuals.modules.len(), 1);
-        let module = settings.visuals.modules.get(&VisualKind::Spectrum).unwrap();
+        assert_eq!(settings.visuals.modules.len(), 2);
+        assert_eq!(settings.visuals.modules[&second].enabled, Some(false));
+        let module = settings.visuals.modules.get(&spectrum).unwrap();
         assert_eq!(module.enabled, Some(true));
 
         let spectrum = module.parse_config::<SpectrumSettings>().unwrap();
@@ -210,4 +470,38 @@ mod tests {
             (2048, SpectrumSettings::default().floor_db, false)
         );
     }
+
+    #[test]
+    fn output_dim_mutes_at_the_top_of_its_range() {
+        let dim = |amount_db| OutputDimSettings { amount_db }.gain();
+        assert!((dim(20.0) - 0.1).abs() < 1e-6);
+        assert_eq!(dim(-6.0), 1.0);
+        assert_eq!(dim(OUTPUT_DIM_MUTE_DB), 0.0);
+    }
+
+    #[test]
+    fn window_opacity_defaults_opaque_and_keeps_a_floor() {
+        assert_eq!(UiSettings::default().opacity.alpha(), 1.0);
+        let alpha = |level| WindowOpacitySettings { level }.alpha();
+        assert_eq!(alpha(0.5), 0.5);
+        assert_eq!(alpha(0.0), WINDOW_OPACITY_MIN);
+        assert_eq!(alpha(f32::NAN), 1.0);
+
+        let settings = UiSettings::from_json_lossy(r#"{"opacity":{"level":"x"}}"#).unwrap();
+        assert_eq!(settings.opacity, WindowOpacitySettings::default());
+    }
+
+    #[test]
+    fn rule_command_loads_off_with_its_interval_in_range() {
+        let settings = UiSettings::from_json_lossy(
+            r#"{"rule_command":{"enabled":true,"command":"true","min_interval_secs":0.5}}"#,
+        )
+        .unwrap();
+        assert!(!settings.rule_command.enabled);
+        assert_eq!(settings.rule_command.command, "true");
+        assert_eq!(settings.rule_command.interval_secs(), 10.0);
+
+        let saved = serde_json::to_value(&settings).unwrap();
+        assert!(saved["rule_command"].get("enabled").is_none());
+    }
 }
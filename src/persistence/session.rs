@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Measurement sessions: loudness, a coarse spectrogram and events over a
+// stretch of listening, bundled into one JSON file for read-only review.
+
+use super::store::data_dir;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const SESSION_VERSION: u32 = 1;
+pub const FRAME_SECONDS: f32 = 0.25;
+pub const SPECTRUM_BANDS: usize = 48;
+pub const SPECTRUM_RANGE_HZ: [f32; 2] = [40.0, 16_000.0];
+/// Levels are stored relative to this floor; anything quieter reads as the floor.
+pub const LEVEL_FLOOR_DB: f32 = -120.0;
+const SESSIONS_DIR: &str = "sessions";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeasurementSession {
+    pub version: u32,
+    /// UTC timestamp of the first frame, as written by `utc_timestamp`.
+    pub started: String,
+    pub frame_seconds: f32,
+    pub band_range_hz: [f32; 2],
+    pub frames: Vec<SessionFrame>,
+    pub events: Vec<SessionEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionFrame {
+    /// Momentary and short-term LUFS and true peak dBTP, or `None` while the
+    /// loudness meter was off.
+    pub loudness: Option<[f32; 3]>,
+    /// Log-spaced band levels in whole dB above `LEVEL_FLOOR_DB`.
+    pub bands: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionEvent {
+    pub seconds: f32,
+    pub text: String,
+}
+
+impl MeasurementSession {
+    pub fn new(started: String) -> Self {
+        Self {
+            version: SESSION_VERSION,
+            started,
+            frame_seconds: FRAME_SECONDS,
+            band_range_hz: SPECTRUM_RANGE_HZ,
+            frames: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.frames.len() as f32 * self.frame_seconds
+    }
+
+    /// Highest value of loudness field `index` (see `SessionFrame::loudness`).
+    pub fn max_loudness(&self, index: usize) -> Option<f32> {
+        self.frames
+            .iter()
+            .filter_map(|frame| frame.loudness.map(|levels| levels[index]))
+            .reduce(f32::max)
+    }
+
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let session: Self = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        if session.version > SESSION_VERSION {
+            return Err(format!(
+                "session format {} is newer than this build supports",
+                session.version
+            ));
+        }
+        Ok(session)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        Self::parse(&fs::read_to_string(path).map_err(|e| e.to_string())?)
+    }
+
+    /// Writes the session to the sessions directory and returns its path.
+    pub fn save(&self) -> io::Result<PathBuf> {
+        let path = sessions_dir().join(format!("session-{}.json", self.started));
+        let json = serde_json::to_string(self).map_err(io::Error::other)?;
+        super::write_json_atomic(&path, &json)?;
+        Ok(path)
+    }
+}
+
+/// Clamps a level for storage; JSON has no infinities.
+pub fn floor_level(db: f32) -> f32 {
+    if db.is_nan() {
+        LEVEL_FLOOR_DB
+    } else {
+        db.max(LEVEL_FLOOR_DB)
+    }
+}
+
+pub fn sessions_dir() -> PathBuf {
+    data_dir().join(SESSIONS_DIR)
+}
+
+/// Saved sessions, newest first.
+pub fn list() -> Vec<PathBuf> {
+    let mut paths: Vec<_> = fs::read_dir(sessions_dir())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|e| e == "json"))
+        .collect();
+    // Names embed a UTC timestamp, so they sort chronologically.
+    paths.sort_unstable_by(|a, b| b.cmp(a));
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sessions_round_trip_and_reject_newer_formats() {
+        let mut session = MeasurementSession::new("20260101T000000Z".into());
+        session.frames.push(SessionFrame {
+            loudness: Some([floor_level(f32::NEG_INFINITY), -23.0, -1.5]),
+            bands: vec![0, 60, 120],
+        });
+        session.frames.push(SessionFrame {
+            loudness: None,
+            bands: vec![0; 3],
+        });
+        session.events.push(SessionEvent {
+            seconds: 0.25,
+            text: "True peak above -1.0 dBTP".into(),
+        });
+
+        let json = serde_json::to_string(&session).unwrap();
+        let parsed = MeasurementSession::parse(&json).unwrap();
+        assert_eq!(parsed, session);
+        assert_eq!(parsed.duration(), 0.5);
+        assert_eq!(parsed.max_loudness(0), Some(LEVEL_FLOOR_DB));
+        assert_eq!(parsed.max_loudness(2), Some(-1.5));
+
+        let newer = json.replacen("\"version\":1", "\"version\":99", 1);
+        assert!(MeasurementSession::parse(&newer).is_err());
+    }
+}
@@ -3,12 +3,12 @@
 
 use super::{
     schema::UiSettings,
-    theme::{BUILTIN_THEME, ThemeFile, ThemeStore},
+    theme::{BUILTIN_THEME, ThemeFile, ThemeStore, canonical_theme_name},
 };
 use std::{
     cell::{Ref, RefCell},
     fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
     rc::Rc,
     sync::{Mutex, PoisonError, mpsc},
     thread::JoinHandle,
@@ -16,7 +16,7 @@ use std::{
 };
 use tracing::warn;
 
-fn config_dir() -> PathBuf {
+pub(super) fn config_dir() -> PathBuf {
     std::env::var_os("XDG_CONFIG_HOME")
         .map(PathBuf::from)
         .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))
@@ -24,6 +24,29 @@ fn config_dir() -> PathBuf {
         .join("openmeters")
 }
 
+/// Where logs and snapshots produced at runtime are written.
+pub fn data_dir() -> PathBuf {
+    std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local/share")))
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("openmeters")
+}
+
+const PROFILES_DIR: &str = "profiles";
+
+fn settings_path(dir: &Path, profile: Option<&str>) -> PathBuf {
+    match profile.map(canonical_theme_name).filter(|p| !p.is_empty()) {
+        Some(name) => dir.join(PROFILES_DIR).join(format!("{name}.json")),
+        None => dir.join("settings.json"),
+    }
+}
+
+/// Settings file read for `profile`, whether or not it exists yet.
+pub fn settings_file(profile: Option<&str>) -> PathBuf {
+    settings_path(&config_dir(), profile)
+}
+
 pub struct SettingsManager {
     path: PathBuf,
     pub data: UiSettings,
@@ -31,9 +54,9 @@ pub struct SettingsManager {
 }
 
 impl SettingsManager {
-    pub fn load_or_default() -> Self {
+    pub fn load_or_default(profile: Option<&str>) -> Self {
         let dir = config_dir();
-        let path = dir.join("settings.json");
+        let path = settings_path(&dir, profile);
         let mut data: UiSettings = fs::read_to_string(&path)
             .ok()
             .and_then(|s| {
@@ -158,8 +181,19 @@ fn settings_saver_loop(rx: mpsc::Receiver<PersistRequest>) {
 pub struct SettingsHandle(Rc<RefCell<SettingsManager>>);
 
 impl SettingsHandle {
-    pub fn load_or_default() -> Self {
-        Self(Rc::new(RefCell::new(SettingsManager::load_or_default())))
+    pub fn load_or_default(profile: Option<&str>) -> Self {
+        Self(Rc::new(RefCell::new(SettingsManager::load_or_default(
+            profile,
+        ))))
+    }
+    /// Flushes pending writes, then loads `profile` in place.
+    pub fn switch_profile(&self, profile: Option<&str>) {
+        flush_persist();
+        *self.0.borrow_mut() = SettingsManager::load_or_default(profile);
+    }
+    /// Mutates the loaded settings without persisting them.
+    pub fn apply_overrides(&self, mutate: impl FnOnce(&mut UiSettings)) {
+        mutate(&mut self.0.borrow_mut().data);
     }
     pub fn borrow(&self) -> Ref<'_, SettingsManager> {
         self.0.borrow()
@@ -207,6 +241,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn profiles_live_beside_default_settings() {
+        let dir = Path::new("/cfg");
+        assert_eq!(settings_path(dir, None), dir.join("settings.json"));
+        assert_eq!(settings_path(dir, Some("")), dir.join("settings.json"));
+        assert_eq!(
+            settings_path(dir, Some("../stream")),
+            dir.join("profiles/..stream.json")
+        );
+    }
+
     #[test]
     fn flush_writes_pending_settings_without_waiting_for_debounce() {
         flush_persist();
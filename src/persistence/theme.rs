@@ -2,7 +2,7 @@
 // Copyright (C) 2026 Maika Namuo
 
 use super::palette::{ColorSetting, PaletteSettings};
-use crate::domain::visuals::VisualKind;
+use crate::domain::visuals::VisualId;
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::{fs, io};
@@ -49,7 +49,7 @@ pub struct ThemeFile {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub background: Option<ColorSetting>,
     #[serde(skip_serializing_if = "BTreeMap::is_empty")]
-    pub palettes: BTreeMap<VisualKind, PaletteSettings>,
+    pub palettes: BTreeMap<VisualId, PaletteSettings>,
 }
 
 pub struct ThemeStore {
@@ -139,6 +139,7 @@ impl ThemeStore {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::visuals::VisualKind;
     use iced::Color;
     use std::fs;
 
@@ -146,11 +147,12 @@ mod tests {
     fn roundtrip_partial_theme() {
         let dir = tempfile::tempdir().unwrap();
         let store = ThemeStore::new(dir.path());
+        let second = VisualId::parse("spectrum#2").unwrap();
 
         let theme = ThemeFile {
             name: Some("Test".into()),
             palettes: BTreeMap::from([(
-                VisualKind::Spectrum,
+                second,
                 PaletteSettings {
                     stops: vec![Color::WHITE.into(), Color::BLACK.into()],
                     ..Default::default()
@@ -162,8 +164,12 @@ mod tests {
         store.save("test", &theme).unwrap();
         let loaded = store.load("test").unwrap();
         assert_eq!(loaded.name.as_deref(), Some("Test"));
-        assert!(loaded.palettes.contains_key(&VisualKind::Spectrum));
-        assert!(!loaded.palettes.contains_key(&VisualKind::Oscilloscope));
+        assert!(loaded.palettes.contains_key(&second));
+        assert!(
+            !loaded
+                .palettes
+                .contains_key(&VisualId::primary(VisualKind::Spectrum))
+        );
     }
 
     #[test]
@@ -0,0 +1,224 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Per-track loudness results, exported as CSV for spreadsheets, and the
+// running measurement kept across restarts.
+
+use super::store::{config_dir, data_dir};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io;
+use std::path::PathBuf;
+use std::{fs, time::SystemTime};
+
+const TRACKS_DIR: &str = "tracks";
+const HEADER: &str = "started,player,artist,title,seconds,lufs_i,peak_dbtp,lra_lu,dr";
+const RUNNING_FILE: &str = "running-loudness.json";
+const RUNNING_VERSION: u32 = 1;
+// Gating blocks are stored to the hundredth of an LU.
+const BLOCK_STEPS_PER_LU: f32 = 100.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackResult {
+    /// UTC timestamp, as written by `utc_timestamp`.
+    pub started: String,
+    pub player: String,
+    pub artist: String,
+    pub title: String,
+    pub seconds: f32,
+    pub integrated_lufs: Option<f32>,
+    pub peak_dbtp: Option<f32>,
+    pub range_lu: Option<f32>,
+    pub dynamic_range: Option<f32>,
+}
+
+impl TrackResult {
+    pub fn label(&self) -> String {
+        match (self.artist.as_str(), self.title.as_str()) {
+            (_, "") => "untitled".to_owned(),
+            ("", title) => title.to_owned(),
+            (artist, title) => format!("{artist} - {title}"),
+        }
+    }
+}
+
+pub fn to_csv(tracks: &[TrackResult]) -> String {
+    let level = |value: Option<f32>| value.map_or_else(String::new, |v| format!("{v:.1}"));
+    let mut csv = format!("{HEADER}\n");
+    for track in tracks {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{:.1},{},{},{},{}",
+            track.started,
+            quote(&track.player),
+            quote(&track.artist),
+            quote(&track.title),
+            track.seconds,
+            level(track.integrated_lufs),
+            level(track.peak_dbtp),
+            level(track.range_lu),
+            track
+                .dynamic_range
+                .map_or_else(String::new, |dr| format!("{dr:.0}")),
+        );
+    }
+    csv
+}
+
+fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// A measurement still in progress. Integration and LRA don't depend on the
+/// order of the gating blocks, so they are kept as counts per level and hours
+/// of blocks fit in a few kilobytes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunningTrack {
+    version: u32,
+    pub started: String,
+    pub player: String,
+    pub artist: String,
+    pub title: String,
+    pub seconds: f64,
+    pub peak_dbtp: Option<f32>,
+    momentary: Vec<(i16, u32)>,
+    short_term: Vec<(i16, u32)>,
+}
+
+impl RunningTrack {
+    pub fn new(
+        started: String,
+        [player, artist, title]: [String; 3],
+        seconds: f64,
+        peak_dbtp: Option<f32>,
+        [momentary, short_term]: [&[f32]; 2],
+    ) -> Self {
+        Self {
+            version: RUNNING_VERSION,
+            started,
+            player,
+            artist,
+            title,
+            seconds,
+            peak_dbtp,
+            momentary: count_blocks(momentary),
+            short_term: count_blocks(short_term),
+        }
+    }
+
+    /// Momentary and short-term gating blocks, grouped by level.
+    pub fn blocks(&self) -> [Vec<f32>; 2] {
+        [&self.momentary, &self.short_term].map(|counts| {
+            counts
+                .iter()
+                .flat_map(|&(level, count)| {
+                    std::iter::repeat_n(f32::from(level) / BLOCK_STEPS_PER_LU, count as usize)
+                })
+                .collect()
+        })
+    }
+}
+
+fn count_blocks(blocks: &[f32]) -> Vec<(i16, u32)> {
+    let mut counts = BTreeMap::new();
+    for &lufs in blocks.iter().filter(|lufs| lufs.is_finite()) {
+        let level = (lufs * BLOCK_STEPS_PER_LU)
+            .round()
+            .clamp(f32::from(i16::MIN), f32::from(i16::MAX)) as i16;
+        *counts.entry(level).or_insert(0_u32) += 1;
+    }
+    counts.into_iter().collect()
+}
+
+fn running_path() -> PathBuf {
+    config_dir().join(RUNNING_FILE)
+}
+
+pub fn save_running(track: &RunningTrack) -> io::Result<()> {
+    let json = serde_json::to_string(track).map_err(io::Error::other)?;
+    super::write_json_atomic(&running_path(), &json)
+}
+
+/// The measurement left by the last run, if it was written by this version.
+pub fn load_running() -> Option<RunningTrack> {
+    let json = fs::read_to_string(running_path()).ok()?;
+    serde_json::from_str(&json)
+        .ok()
+        .filter(|track: &RunningTrack| track.version == RUNNING_VERSION)
+}
+
+pub fn clear_running() {
+    match fs::remove_file(running_path()) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => tracing::warn!("[tracks] failed to remove the running measurement: {err}"),
+    }
+}
+
+pub fn tracks_dir() -> PathBuf {
+    data_dir().join(TRACKS_DIR)
+}
+
+/// Writes the table to the tracks directory and returns its path.
+pub fn export(tracks: &[TrackResult]) -> io::Result<PathBuf> {
+    let dir = tracks_dir();
+    fs::create_dir_all(&dir)?;
+    let stamp = crate::util::utc_timestamp(SystemTime::now());
+    let path = dir.join(format!("tracks-{stamp}.csv"));
+    fs::write(&path, to_csv(tracks))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_quotes_awkward_fields_and_blanks_missing_levels() {
+        let track = TrackResult {
+            started: "20260101T200000Z".into(),
+            player: "mpv".into(),
+            artist: "Simon & Garfunkel".into(),
+            title: "Cecilia, \"live\"".into(),
+            seconds: 172.34,
+            integrated_lufs: Some(-9.84),
+            peak_dbtp: Some(0.3),
+            range_lu: None,
+            dynamic_range: Some(11.6),
+        };
+        let csv = to_csv(&[track]);
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some(HEADER));
+        assert_eq!(
+            lines.next(),
+            Some(
+                "20260101T200000Z,mpv,Simon & Garfunkel,\"Cecilia, \"\"live\"\"\",172.3,-9.8,0.3,,12"
+            )
+        );
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn running_tracks_keep_blocks_as_level_counts() {
+        let momentary = [-23.004, -23.0, -18.5, -23.001, f32::NEG_INFINITY];
+        let track = RunningTrack::new(
+            "20260101T200000Z".into(),
+            ["mpv".into(), String::new(), "live".into()],
+            3_600.0,
+            Some(-1.2),
+            [&momentary, &[-20.0; 3]],
+        );
+        assert_eq!(track.momentary, [(-2300, 3), (-1850, 1)]);
+        let json = serde_json::to_string(&track).unwrap();
+        let restored: RunningTrack = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, track);
+        let [momentary, short_term] = restored.blocks();
+        assert_eq!(momentary, [-23.0, -23.0, -23.0, -18.5]);
+        assert_eq!(short_term, [-20.0; 3]);
+    }
+}
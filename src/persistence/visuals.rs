@@ -5,18 +5,21 @@ use super::{
     lossy,
     palette::{HasPalette, PaletteSettings},
 };
-use crate::domain::visuals::VisualKind;
+use crate::domain::automation::LoudnessAlert;
+use crate::domain::visuals::{VisualId, VisualKind};
 use crate::util::audio::{Channel, FrequencyScale, WindowKind};
 use crate::visuals::options::{
-    CorrelationMeterMode, CorrelationMeterSide, MeterMode, PianoRollOverlay, SpectrumDisplayMode,
-    SpectrumWeightingMode, StereometerMode, StereometerScale, WaveformColorMode,
-    WaveformHistoryMode,
+    AspectLock, CorrelationMeterMode, CorrelationMeterSide, MeterMode, MeterOrientation,
+    PianoRollOverlay, SnapshotRate, SpectrogramInterpolation, SpectrumDisplayMode,
+    SpectrumLevelGrid, SpectrumWeightingMode, StereometerMode, StereometerScale, TraceColor,
+    TunerPreset, WaveformColorMode, WaveformHistoryMode,
 };
 use crate::visuals::{
     oscilloscope::processor::{OscilloscopeConfig, TriggerMode},
-    spectrogram::processor::SpectrogramConfig,
+    spectrogram::processor::{SpectrogramChannels, SpectrogramConfig},
     spectrum::processor::{AveragingMode, SpectrumConfig},
     stereometer::processor::StereometerConfig,
+    tuner::processor::TunerConfig,
     waveform::processor::{DEFAULT_BAND_DB_FLOOR, WaveformConfig},
 };
 use serde::{Deserialize, Serialize};
@@ -28,26 +31,211 @@ fn is_true(value: &bool) -> bool {
     *value
 }
 
+/// A fixed frequency line, or a shaded band when `end_hz` is set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrequencyMarker {
+    pub hz: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_hz: Option<f32>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub label: String,
+}
+
+impl FrequencyMarker {
+    pub fn span(&self) -> (f32, f32) {
+        let end = self.end_hz.unwrap_or(self.hz);
+        (self.hz.min(end), self.hz.max(end))
+    }
+
+    /// Parses entries like `50 Hum, 60, 15.6k-16k TV`; malformed entries are dropped.
+    pub fn parse_list(text: &str) -> Vec<Self> {
+        text.split(',').filter_map(Self::parse).collect()
+    }
+
+    fn parse(entry: &str) -> Option<Self> {
+        let entry = entry.trim();
+        let (freq, label) = entry.split_once(char::is_whitespace).unwrap_or((entry, ""));
+        let (hz, end_hz) = match freq.split_once('-') {
+            Some((lo, hi)) => (parse_hz(lo)?, Some(parse_hz(hi)?)),
+            None => (parse_hz(freq)?, None),
+        };
+        Some(Self {
+            hz,
+            end_hz,
+            label: label.trim().to_owned(),
+        })
+    }
+
+    pub fn format_list(markers: &[Self]) -> String {
+        let entries: Vec<String> = markers
+            .iter()
+            .map(|marker| {
+                let mut entry = marker.hz.to_string();
+                if let Some(end) = marker.end_hz {
+                    entry.push_str(&format!("-{end}"));
+                }
+                if !marker.label.is_empty() {
+                    entry.push(' ');
+                    entry.push_str(&marker.label);
+                }
+                entry
+            })
+            .collect();
+        entries.join(", ")
+    }
+}
+
+fn parse_hz(text: &str) -> Option<f32> {
+    let text = text.trim().to_ascii_lowercase();
+    let text = text.strip_suffix("hz").unwrap_or(&text);
+    let (number, scale) = match text.strip_suffix('k') {
+        Some(number) => (number, 1000.0),
+        None => (text, 1.0),
+    };
+    crate::util::finite_positive(number.parse::<f32>().ok()? * scale)
+}
+
 crate::macros::default_struct! {
-    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    /// Spectrogram floor and contrast a pop-out shows instead of the docked values.
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    pub struct DisplayOverride {
+        pub floor_db: f32 = -96.0,
+        pub gamma: f32 = 1.0,
+    }
+}
+
+pub const SPECTRUM_MEMORY_SLOTS: usize = 4;
+
+/// A spectrum held for comparison, as `[hz, db]` points.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemoryTrace {
+    pub visible: bool,
+    pub points: Vec<[f32; 2]>,
+}
+
+/// Spectrum memory slots. They are only written to the settings file with
+/// `keep` set, so by default they last for the session.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct SpectrumMemory {
+    pub keep: bool,
+    pub slots: Vec<Option<MemoryTrace>>,
+}
+
+impl SpectrumMemory {
+    pub fn slot(&self, slot: usize) -> Option<&MemoryTrace> {
+        self.slots.get(slot)?.as_ref()
+    }
+
+    pub fn set(&mut self, slot: usize, trace: Option<MemoryTrace>) {
+        if slot >= SPECTRUM_MEMORY_SLOTS {
+            return;
+        }
+        if self.slots.len() <= slot {
+            self.slots.resize(slot + 1, None);
+        }
+        self.slots[slot] = trace;
+        while self.slots.last().is_some_and(Option::is_none) {
+            self.slots.pop();
+        }
+    }
+}
+
+impl Serialize for SpectrumMemory {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let slots: &[Option<MemoryTrace>] = if self.keep { &self.slots } else { &[] };
+        let mut out = serializer.serialize_struct("SpectrumMemory", 2)?;
+        out.serialize_field("keep", &self.keep)?;
+        out.serialize_field("slots", slots)?;
+        out.end()
+    }
+}
+
+/// A named bundle of spectrogram analysis and display choices.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpectrogramPreset {
+    pub name: String,
+    pub fft_size: usize,
+    pub hop_size: usize,
+    pub window: WindowKind,
+    pub frequency_scale: FrequencyScale,
+    pub floor_db: f32,
+    pub gamma: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub palette: Option<PaletteSettings>,
+}
+
+impl SpectrogramPreset {
+    pub fn capture(
+        name: String,
+        settings: &SpectrogramSettings,
+        palette: Option<PaletteSettings>,
+    ) -> Self {
+        Self {
+            name,
+            fft_size: settings.fft_size,
+            hop_size: settings.hop_size,
+            window: settings.window,
+            frequency_scale: settings.frequency_scale,
+            floor_db: settings.floor_db,
+            gamma: settings.gamma,
+            palette,
+        }
+    }
+
+    pub fn apply_to(&self, settings: &mut SpectrogramSettings) {
+        settings.fft_size = self.fft_size;
+        settings.hop_size = self.hop_size;
+        settings.window = self.window;
+        settings.frequency_scale = self.frequency_scale;
+        settings.floor_db = self.floor_db;
+        settings.gamma = self.gamma;
+    }
+
+    pub fn matches(
+        &self,
+        settings: &SpectrogramSettings,
+        palette: Option<&PaletteSettings>,
+    ) -> bool {
+        *self == Self::capture(self.name.clone(), settings, palette.cloned())
+    }
+}
+
+crate::macros::default_struct! {
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[serde(default)]
     pub struct PopoutWindowSettings {
         pub width: u32 = 0,
         pub height: u32 = 0,
         #[serde(skip_serializing_if = "is_true")]
         pub popped_out: bool = true,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub display: Option<DisplayOverride> = None,
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
 pub struct VisualSettings {
-    pub modules: BTreeMap<VisualKind, ModuleSettings>,
-    pub order: Vec<VisualKind>,
+    pub modules: BTreeMap<VisualId, ModuleSettings>,
+    pub order: Vec<VisualId>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub width_basis: BTreeMap<VisualId, f32>,
+    /// Used instead of `width_basis` while panes are stacked vertically.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub height_basis: BTreeMap<VisualId, f32>,
     #[serde(skip_serializing_if = "BTreeMap::is_empty")]
-    pub width_basis: BTreeMap<VisualKind, f32>,
+    pub popouts: BTreeMap<VisualId, PopoutWindowSettings>,
+    /// Shape pop-outs of each kind keep while resized.
     #[serde(skip_serializing_if = "BTreeMap::is_empty")]
-    pub popouts: BTreeMap<VisualKind, PopoutWindowSettings>,
+    pub aspect_locks: BTreeMap<VisualKind, AspectLock>,
+    pub align_latency: bool,
+    pub vertical: bool,
+    pub snapshot_rate: SnapshotRate,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub spectrogram_presets: Vec<SpectrogramPreset>,
 }
 
 impl VisualSettings {
@@ -61,20 +249,30 @@ impl VisualSettings {
                 out.order = visual_order(value);
             }
             if let Some(value) = map.remove("width_basis") {
-                out.width_basis = visual_map(value, "visuals.width_basis", width_basis);
+                out.width_basis = visual_map(value, "visuals.width_basis", pane_basis);
+            }
+            if let Some(value) = map.remove("height_basis") {
+                out.height_basis = visual_map(value, "visuals.height_basis", pane_basis);
             }
             if let Some(value) = map.remove("popouts") {
                 out.popouts = visual_map(value, "visuals.popouts", popout_window);
             }
+            if let Some(value) = map.remove("aspect_locks") {
+                out.aspect_locks = visual_map(value, "visuals.aspect_locks", lossy::value);
+            }
+            lossy::fields!(
+                map, out, "visuals";
+                align_latency, vertical, snapshot_rate, spectrogram_presets,
+            );
         })
     }
 }
 
-fn visual_map<T>(
+fn visual_map<K: serde::de::DeserializeOwned + Ord, T>(
     value: Value,
     scope: &str,
     mut parse: impl FnMut(Value, &str) -> Option<T>,
-) -> BTreeMap<VisualKind, T> {
+) -> BTreeMap<K, T> {
     lossy::object(value, scope)
         .unwrap_or_default()
         .into_iter()
@@ -86,7 +284,7 @@ fn visual_map<T>(
         .collect()
 }
 
-fn visual_order(value: Value) -> Vec<VisualKind> {
+fn visual_order(value: Value) -> Vec<VisualId> {
     let Value::Array(items) = value else {
         warn!("[settings] visuals.order must be an array");
         return Vec::new();
@@ -97,7 +295,7 @@ fn visual_order(value: Value) -> Vec<VisualKind> {
         .collect()
 }
 
-fn width_basis(value: Value, scope: &str) -> Option<f32> {
+fn pane_basis(value: Value, scope: &str) -> Option<f32> {
     let basis: f32 = lossy::value(value, scope)?;
     if let Some(basis) = crate::util::finite_positive(basis) {
         Some(basis)
@@ -110,7 +308,7 @@ fn width_basis(value: Value, scope: &str) -> Option<f32> {
 fn popout_window(value: Value, scope: &str) -> Option<PopoutWindowSettings> {
     let mut map = lossy::object(value, scope)?;
     let mut out = PopoutWindowSettings::default();
-    lossy::fields!(&mut map, out, scope; width, height, popped_out);
+    lossy::fields!(&mut map, out, scope; width, height, popped_out, display);
     lossy::unknown(scope, &map);
     Some(out)
 }
@@ -220,23 +418,27 @@ visual_settings!(OscilloscopeSettings from OscilloscopeConfig {
 } extra {
     persistence: f32 = 0.0,
     stacked: bool = false,
+    single_shot: bool = false,
+    trigger_level_db: f32 = -24.0,
 });
 
 visual_settings!(WaveformSettings from WaveformConfig {
-    scroll_speed: f32,
+    scroll_speed: f32, detect_clicks: bool, click_threshold: f32,
 } extra {
     band_db_floor: f32 = DEFAULT_BAND_DB_FLOOR,
     channel_1: Channel = Channel::Mid,
     channel_2: Channel = Channel::None,
     color_mode: WaveformColorMode = WaveformColorMode::default(),
     history_mode: WaveformHistoryMode = WaveformHistoryMode::default(),
+    rms_layer: bool = true,
 });
 
 visual_settings!(SpectrumSettings from SpectrumConfig {
     fft_size: usize, hop_size: usize, window: WindowKind, averaging: AveragingMode,
-    source: Channel, secondary_source: Channel,
+    source: Channel, secondary_source: Channel, overlay_channels: u8,
     frequency_scale: FrequencyScale, reverse_frequency: bool, show_grid: bool, show_peak_label: bool,
-    floor_db: f32,
+    show_noise_floor: bool, floor_db: f32, iq_input: bool,
+    multi_rate: bool, crossover_hz: f32,
 } extra {
     display_mode: SpectrumDisplayMode = SpectrumDisplayMode::default(),
     weighting_mode: SpectrumWeightingMode = SpectrumWeightingMode::default(),
@@ -244,17 +446,43 @@ visual_settings!(SpectrumSettings from SpectrumConfig {
     bar_count: usize = 64,
     bar_gap: f32 = 0.16,
     highlight_threshold: f32 = 0.52,
+    fill_alpha: f32 = 0.7,
+    peak_list: usize = 0,
+    level_grid: SpectrumLevelGrid = SpectrumLevelGrid::default(),
+    markers: Vec<FrequencyMarker> = Vec::new(),
+    mute_marked_bands: bool = false,
+    overlay_colors: Vec<TraceColor> = Vec::new(),
+    trace_legend: bool = true,
+    mirrored: bool = false,
+    note_axis: bool = false,
+    pause_on_hover: bool = false,
+    memory: SpectrumMemory = SpectrumMemory::default(),
 });
 
 visual_settings!(SpectrogramSettings from SpectrogramConfig {
     fft_size: usize, hop_size: usize, window: WindowKind, frequency_scale: FrequencyScale,
     use_reassignment: bool,
-    zero_padding_factor: usize,
+    zero_padding_factor: usize, columns_per_second: f32, channels: SpectrogramChannels,
+    history_memory_mb: u32,
 } extra {
     floor_db: f32 = -96.0,
     tilt_db: f32 = 0.0,
+    gamma: f32 = 1.0,
+    knee: f32 = 0.0,
+    interpolation: SpectrogramInterpolation = SpectrogramInterpolation::default(),
+    sharpen: f32 = 0.0,
     piano_roll_overlay: PianoRollOverlay = PianoRollOverlay::default(),
     rotation: i8 = 0,
+    invert_frequency: bool = false,
+    markers: Vec<FrequencyMarker> = Vec::new(),
+    tone_labels: bool = false,
+    tone_threshold_db: f32 = 15.0,
+    tone_min_seconds: f32 = 5.0,
+    scale_bar: bool = false,
+    zoom_refine: bool = false,
+    pitch_readout: bool = false,
+    time_grid: bool = false,
+    pause_on_hover: bool = false,
 });
 
 visual_settings!(StereometerSettings from StereometerConfig {
@@ -263,6 +491,8 @@ visual_settings!(StereometerSettings from StereometerConfig {
     dot_radius: f32 = 1.5, mode: StereometerMode = StereometerMode::default(),
     scale: StereometerScale = StereometerScale::default(), rotation: i8 = -1, flip: bool = true,
     unipolar: bool = false,
+    axis_lines: bool = true, lr_guides: bool = false, db_rings: bool = false,
+    grid_labels: bool = false,
     correlation_meter: CorrelationMeterMode = CorrelationMeterMode::default(),
     correlation_meter_side: CorrelationMeterSide = CorrelationMeterSide::default(),
 });
@@ -270,4 +500,118 @@ visual_settings!(StereometerSettings from StereometerConfig {
 visual_settings!(LoudnessSettings {
     left_mode: MeterMode = MeterMode::TruePeak,
     right_mode: MeterMode = MeterMode::LufsShortTerm,
+    orientation: MeterOrientation = MeterOrientation::Vertical,
+    alert: LoudnessAlert = LoudnessAlert::default(),
+    // Tops of the low, mid and high zones; the danger zone runs above.
+    loudness_zones: [f32; 3] = [-24.0, -18.0, -9.0],
+    level_zones: [f32; 3] = [-12.0, -6.0, -1.0],
 });
+
+visual_settings!(TunerSettings from TunerConfig {
+    preset: TunerPreset,
+} extra {
+    in_tune_cents: f32 = 3.0,
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frequency_markers_round_trip_through_text() {
+        let markers = FrequencyMarker::parse_list("50 Hum, 60Hz, 15.6k-16k TV whine, bogus, -3");
+        assert_eq!(
+            markers,
+            [
+                FrequencyMarker {
+                    hz: 50.0,
+                    end_hz: None,
+                    label: "Hum".into()
+                },
+                FrequencyMarker {
+                    hz: 60.0,
+                    end_hz: None,
+                    label: String::new()
+                },
+                FrequencyMarker {
+                    hz: 15_600.0,
+                    end_hz: Some(16_000.0),
+                    label: "TV whine".into()
+                },
+            ]
+        );
+        assert_eq!(markers[2].span(), (15_600.0, 16_000.0));
+
+        let text = FrequencyMarker::format_list(&markers);
+        assert_eq!(text, "50 Hum, 60, 15600-16000 TV whine");
+        assert_eq!(FrequencyMarker::parse_list(&text), markers);
+    }
+
+    #[test]
+    fn markers_load_from_settings_json() {
+        let settings = SpectrogramSettings::from_value_lossy(
+            serde_json::json!({ "markers": [{ "hz": 50.0, "label": "Hum" }] }),
+            "config",
+        );
+        assert_eq!(settings.markers[0].label, "Hum");
+        assert!(SpectrumSettings::default().markers.is_empty());
+    }
+
+    #[test]
+    fn spectrum_memory_is_only_saved_when_kept() {
+        let mut settings = SpectrumSettings::default();
+        let trace = MemoryTrace {
+            visible: false,
+            points: vec![[100.0, -20.0], [200.0, -30.0]],
+        };
+        settings.memory.set(1, Some(trace.clone()));
+        settings
+            .memory
+            .set(SPECTRUM_MEMORY_SLOTS, Some(trace.clone()));
+        assert_eq!(settings.memory.slots, [None, Some(trace.clone())]);
+
+        let saved = serde_json::to_value(&settings).unwrap();
+        assert_eq!(
+            saved["memory"],
+            serde_json::json!({ "keep": false, "slots": [] })
+        );
+        settings.memory.keep = true;
+        let saved = serde_json::to_value(&settings).unwrap();
+        let loaded = SpectrumSettings::from_value_lossy(saved, "spectrum");
+        assert_eq!(loaded.memory, settings.memory);
+
+        settings.memory.set(1, None);
+        assert!(settings.memory.slots.is_empty());
+    }
+
+    #[test]
+    fn aspect_locks_load_per_kind_and_drop_bad_entries() {
+        let settings = VisualSettings::from_value_lossy(serde_json::json!({
+            "aspect_locks": { "stereometer": "square", "nope": "square", "tuner": "round" }
+        }));
+        assert_eq!(
+            settings.aspect_locks.into_iter().collect::<Vec<_>>(),
+            [(VisualKind::Stereometer, AspectLock::Square)]
+        );
+    }
+
+    #[test]
+    fn spectrogram_presets_round_trip_through_settings() {
+        let mut settings = SpectrogramSettings {
+            fft_size: 1024,
+            hop_size: 128,
+            floor_db: -80.0,
+            ..SpectrogramSettings::default()
+        };
+        let preset = SpectrogramPreset::capture("Speech".into(), &settings, None);
+        let value = serde_json::json!({ "spectrogram_presets": [&preset] });
+        let loaded = VisualSettings::from_value_lossy(value).spectrogram_presets;
+        assert_eq!(loaded, [preset.clone()]);
+
+        settings = SpectrogramSettings::default();
+        assert!(!preset.matches(&settings, None));
+        preset.apply_to(&mut settings);
+        assert!(preset.matches(&settings, None));
+        assert_eq!((settings.fft_size, settings.hop_size), (1024, 128));
+    }
+}
@@ -8,11 +8,11 @@ macro_rules! form {
 }
 
 macro_rules! slider {
-    ($label:expr, $value:expr, $range:expr, $on_change:expr, $fmt:literal) => {
-        slider!($label, $value, $range, $on_change, format!($fmt, $value))
+    ($numbers:expr; $label:expr, $value:expr, $range:expr, $on_change:expr, $fmt:literal) => {
+        slider!($numbers; $label, $value, $range, $on_change, format!($fmt, $value))
     };
-    ($label:expr, $value:expr, $range:expr, $on_change:expr, $display:expr) => {
-        $crate::ui::widgets::slide($label, $value, $display, $range, $on_change)
+    ($numbers:expr; $label:expr, $value:expr, $range:expr, $on_change:expr, $display:expr) => {
+        $crate::ui::widgets::slide($numbers, $label, $value, $display, $range, $on_change)
     };
 }
 
@@ -31,4 +31,4 @@ pub(crate) fn scroll_delta_lines(delta: iced::advanced::mouse::ScrollDelta) -> f
     }
 }
 
-pub(crate) use app::{UiConfig, run};
+pub(crate) use app::{UiConfig, layershell_available, run};
@@ -1,41 +1,54 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // Copyright (C) 2026 Maika Namuo
 
+mod automation;
 mod message;
+mod pulse;
+mod session;
+mod tracks;
 mod windowing;
 
 use crate::domain::routing::RoutingCommand;
-use crate::infra::pipewire::{meter_tap::AudioBatch, registry::RegistrySnapshot};
-use crate::persistence::settings::{BarAlignment, BarSettings, SettingsHandle, clamp_bar_height};
-use crate::ui::config::ConfigPage;
+use crate::dsp::dynamic_range::DynamicRange;
+use crate::dsp::replay::{CaptureRing, REPLAY_SPEED, SlowReplay};
+use crate::infra::ipc::{IpcCommand, IpcRequest};
+use crate::infra::pipewire::meter_tap::{self, AudioBatch, GraphClock, MeterFormat};
+use crate::infra::pipewire::registry::RegistrySnapshot;
+use crate::persistence::settings::{
+    BarAlignment, BarSettings, SettingsHandle, ThemeFile, VisualSettings, clamp_bar_height,
+};
+use crate::ui::config::{ConfigMessage, ConfigPage};
 use crate::ui::settings::ActiveSettings;
-use crate::ui::subscription::channel_subscription;
+use crate::ui::subscription::{self, channel_subscription};
 use crate::ui::theme;
 use crate::ui::visuals::VisualsPage;
 use crate::ui::widgets::{fill, scroll_glow::ScrollGlow};
-use crate::visuals::registry::{VisualManager, VisualManagerHandle};
+use crate::visuals::loudness::processor::LoudnessSnapshot;
+use crate::visuals::registry::{VisualId, VisualManager, VisualManagerHandle};
 use async_channel::Receiver as AsyncReceiver;
 use iced::alignment::{Horizontal, Vertical};
 use iced::event::{self, Event};
 use iced::widget::{container, mouse_area, row, stack, text};
 use iced::{
     Element, Length, Settings as IcedSettings, Size, Subscription, Task, daemon as iced_daemon,
-    window,
+    keyboard, window,
 };
 use iced_layershell::settings::{LayerShellSettings, Settings as LayerSettings, StartMode};
 use message::{Message, keyboard_shortcut, update, view};
+use serde_json::{Value, json};
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::{Arc, mpsc};
 use std::time::{Duration, Instant};
+pub(crate) use windowing::layershell_available;
 use windowing::{
-    APP_ID, BarResizeState, PopoutWindow, layershell_available, main_window_size, open_main_window,
-    open_tool_base_window,
+    APP_ID, BarResizeState, PopoutWindow, main_window_size, open_main_window, open_tool_base_window,
 };
 
 const TOAST_DISPLAY_DURATION: Duration = Duration::from_secs(2);
 const BAR_RESIZE_HANDLE_THICKNESS: f32 = 6.0;
+const GRAPH_WATCH_PERIOD: Duration = Duration::from_millis(500);
 
 #[derive(Clone)]
 pub(crate) struct UiConfig {
@@ -43,6 +56,8 @@ pub(crate) struct UiConfig {
     pub(crate) registry_updates: Option<Arc<AsyncReceiver<RegistrySnapshot>>>,
     pub(crate) audio_frames: Arc<AsyncReceiver<AudioBatch>>,
     pub(crate) settings_handle: SettingsHandle,
+    pub(crate) start_hidden: bool,
+    pub(crate) ipc_requests: Option<Arc<AsyncReceiver<IpcRequest>>>,
 }
 
 pub(crate) fn run(config: UiConfig) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -87,6 +102,7 @@ struct UiApp {
     visual_manager: VisualManagerHandle,
     settings_handle: SettingsHandle,
     audio_frames: Arc<AsyncReceiver<AudioBatch>>,
+    ipc_requests: Option<Arc<AsyncReceiver<IpcRequest>>>,
     config_window: Option<window::Id>,
     bar_resize_state: Option<BarResizeState>,
     rendering_paused: bool,
@@ -99,7 +115,24 @@ struct UiApp {
     settings_window: Option<(window::Id, ActiveSettings)>,
     settings_scroll: ScrollGlow,
     popout_windows: HashMap<window::Id, PopoutWindow>,
+    modifiers: keyboard::Modifiers,
+    // Visuals hidden with a number key, still counted for their number.
+    key_hidden: Vec<VisualId>,
     exit_warning_until: Option<Instant>,
+    automation: automation::RuleEngine,
+    loudness_alert: automation::AlertTracker,
+    idle: automation::IdleWatch,
+    silence: automation::SilenceWatchdog,
+    glitches: automation::GlitchWatch,
+    graph_clock: Option<GraphClock>,
+    capture: CaptureRing,
+    replay: Option<SlowReplay>,
+    session: Option<session::SessionRecorder>,
+    review_window: Option<(window::Id, session::SessionReview)>,
+    tracks: Option<tracks::TrackSegmenter>,
+    // Manual DR measurement and when it started.
+    dynamic_range: Option<(String, DynamicRange)>,
+    beat_clock: pulse::BeatClock,
 }
 
 impl UiApp {
@@ -109,6 +142,8 @@ impl UiApp {
             registry_updates,
             audio_frames,
             settings_handle,
+            start_hidden,
+            ipc_requests,
         } = config;
         let (visual_settings, use_decorations, bar_settings, main_window, theme_file) = {
             let guard = settings_handle.borrow();
@@ -121,12 +156,10 @@ impl UiApp {
                 guard.theme_store().load(guard.active_theme()),
             )
         };
-        let mut manager = VisualManager::default();
-        manager.apply_visual_settings(&visual_settings);
-        if let Some(theme_file) = theme_file {
-            manager.apply_theme(&theme_file);
-        }
-        let visual_manager = Rc::new(RefCell::new(manager));
+        let visual_manager = Rc::new(RefCell::new(load_visual_manager(
+            &visual_settings,
+            theme_file,
+        )));
         let config_page = ConfigPage::new(
             routing_sender,
             registry_updates,
@@ -138,12 +171,18 @@ impl UiApp {
         let base_size = main_window_size(main_window);
         let (main_id, open_task, main_is_layer, main_size) =
             open_main_window(use_layershell, bar_settings, base_size, use_decorations);
+        let open_task = if start_hidden && !main_is_layer {
+            open_task.chain(window::minimize(main_id, true))
+        } else {
+            open_task
+        };
         let mut app = Self {
             config_page,
             visuals_page,
             visual_manager,
             settings_handle,
             audio_frames,
+            ipc_requests,
             config_window: None,
             bar_resize_state: None,
             rendering_paused: false,
@@ -156,8 +195,24 @@ impl UiApp {
             settings_window: None,
             settings_scroll: ScrollGlow::default(),
             popout_windows: HashMap::default(),
+            modifiers: keyboard::Modifiers::default(),
+            key_hidden: Vec::new(),
             exit_warning_until: None,
+            automation: automation::RuleEngine::default(),
+            loudness_alert: automation::AlertTracker::default(),
+            idle: automation::IdleWatch::default(),
+            silence: automation::SilenceWatchdog::default(),
+            glitches: automation::GlitchWatch::default(),
+            graph_clock: None,
+            capture: CaptureRing::default(),
+            replay: None,
+            session: None,
+            review_window: None,
+            tracks: None,
+            dynamic_range: None,
+            beat_clock: pulse::BeatClock::default(),
         };
+        app.resume_track_loudness();
         let restore_popouts = app.restore_popout_windows(&visual_settings.popouts);
         if !app.popout_windows.is_empty() {
             app.sync_visuals_page();
@@ -175,16 +230,161 @@ impl UiApp {
                 Event::Window(window::Event::Opened { size, .. }) => {
                     Some(Message::WindowResized(wid, size))
                 }
+                Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                    Some(Message::ModifiersChanged(modifiers))
+                }
                 _ => None,
             }),
         ];
         subs.push(channel_subscription(Arc::clone(&self.audio_frames)).map(Message::AudioFrame));
+        // Glitches and clock changes matter most when audio stops arriving.
+        subs.push(subscription::every(GRAPH_WATCH_PERIOD).map(|_| Message::GraphWatchTick));
+        if let Some(requests) = &self.ipc_requests {
+            subs.push(channel_subscription(Arc::clone(requests)).map(Message::Ipc));
+        }
+        if let Some(changes) = self
+            .tracks
+            .as_ref()
+            .and_then(tracks::TrackSegmenter::changes)
+        {
+            subs.push(channel_subscription(changes).map(Message::TrackChanged));
+        }
         if self.bar_resize_state.is_some() {
             subs.push(event::listen_with(message::bar_drag_events));
         }
+        // Alt + scroll fades the main window or a pop-out from anywhere over it.
+        if self.modifiers.alt() {
+            subs.push(event::listen_with(message::opacity_scroll_events));
+        }
+        if self
+            .settings_window
+            .as_ref()
+            .is_some_and(|(_, panel)| panel.on_trial())
+        {
+            subs.push(window::frames().map(Message::SettingsTrialTick));
+        }
         Subscription::batch(subs)
     }
 
+    fn handle_ipc(&mut self, request: IpcRequest) -> Task<Message> {
+        match &request.command {
+            IpcCommand::Pause { paused } => {
+                self.rendering_paused = paused.unwrap_or(!self.rendering_paused);
+                request.reply(Ok(json!({ "paused": self.rendering_paused })));
+                Task::none()
+            }
+            &IpcCommand::SetVisual { visual, enabled } => {
+                request.reply(Ok(Value::Null));
+                update(
+                    self,
+                    Message::Config(ConfigMessage::VisualToggled {
+                        visual: visual.into(),
+                        enabled,
+                    }),
+                )
+            }
+            IpcCommand::SetProfile { profile } => {
+                self.switch_profile(profile.as_deref());
+                request.reply(Ok(Value::Null));
+                self.sync_all_windows()
+            }
+            IpcCommand::GetLevels => {
+                let levels = self.visual_manager.borrow().loudness_levels();
+                request.reply(
+                    levels
+                        .map(levels_json)
+                        .ok_or_else(|| "loudness is disabled".into()),
+                );
+                Task::none()
+            }
+        }
+    }
+
+    // The capture ring keeps recording during a replay, but only the replay
+    // reaches the visuals, one chunk per live batch.
+    fn ingest_audio(
+        &mut self,
+        samples: &[f32],
+        format: MeterFormat,
+        captured: Instant,
+    ) -> Task<Message> {
+        self.watch_silence(samples);
+        let paused = self.idle_paused(samples);
+        self.pulse_beats(if paused { &[] } else { samples }, format);
+        self.capture
+            .push(samples, format.channels, format.sample_rate);
+        let Some(replay) = self.replay.as_mut() else {
+            let fresh =
+                self.visual_manager
+                    .borrow_mut()
+                    .ingest_samples(samples, format, Some(captured));
+            self.measure_dynamic_range(samples, format);
+            if self.session.is_some() || self.tracks.is_some() {
+                let manager = self.visual_manager.borrow();
+                let levels = manager.loudness_levels();
+                if let Some(recorder) = self.session.as_mut() {
+                    recorder.record(samples, format, levels, manager.mixdown());
+                }
+                if let Some(segmenter) = self.tracks.as_mut() {
+                    segmenter.record(samples, format, levels);
+                }
+            }
+            if !fresh {
+                return Task::none();
+            }
+            if paused {
+                return self.run_automation();
+            }
+            return Task::batch([self.sync_all_windows(), self.run_automation()]);
+        };
+        if paused {
+            return Task::none();
+        }
+        match replay.next_chunk(samples.len() / format.channels.max(1)) {
+            Some(chunk) => {
+                let format = MeterFormat {
+                    channels: replay.channels(),
+                    sample_rate: replay.sample_rate(),
+                };
+                if !self
+                    .visual_manager
+                    .borrow_mut()
+                    .ingest_samples(&chunk, format, None)
+                {
+                    return Task::none();
+                }
+            }
+            None => self.replay = None,
+        }
+        self.sync_all_windows()
+    }
+
+    fn toggle_replay(&mut self) {
+        self.replay = match self.replay.take() {
+            Some(_) => None,
+            None => self.capture.replay(REPLAY_SPEED),
+        };
+    }
+
+    fn switch_profile(&mut self, profile: Option<&str>) {
+        self.settings_handle.switch_profile(profile);
+        let (visual_settings, theme_file) = {
+            let guard = self.settings_handle.borrow();
+            (
+                guard.data.visuals.clone(),
+                guard.theme_store().load(guard.active_theme()),
+            )
+        };
+        *self.visual_manager.borrow_mut() = load_visual_manager(&visual_settings, theme_file);
+        for popout in self.popout_windows.values() {
+            self.visual_manager
+                .borrow()
+                .set_display_override(popout.visual, popout.display);
+        }
+        self.config_page.reload_from_settings();
+        self.refresh_settings_panel();
+    }
+
     fn toggle_config_window(&mut self) -> Task<Message> {
         if let Some(id) = self.config_window.take() {
             return window::close(id);
@@ -255,7 +455,13 @@ impl UiApp {
 
     fn visuals_with_toasts(&self) -> Element<'_, Message> {
         let config_open = self.config_window.is_some();
-        let visuals_view = self.visuals_page.view(config_open).map(Message::Visuals);
+        let idle = self.idle.is_paused();
+        let collapsed = idle && self.settings_handle.borrow().data.idle_pause.collapse;
+        let visuals_view = if collapsed {
+            container(text(" ")).into()
+        } else {
+            self.visuals_page.view(config_open).map(Message::Visuals)
+        };
 
         let now = Instant::now();
         let is_active = |deadline: Option<Instant>| deadline.is_some_and(|expires| now < expires);
@@ -263,6 +469,30 @@ impl UiApp {
             (config_open && is_active(self.toast_until))
                 .then_some("drag visuals to rearrange | ctrl+shift+h to close config"),
             self.rendering_paused.then_some("paused (p to resume)"),
+            idle.then_some("idle: paused until audio returns"),
+            self.silence
+                .is_alarmed()
+                .then_some("program audio silent while applications play"),
+            self.glitches
+                .is_toasting(now)
+                .then_some("capture gaps: audio glitches likely (counters in config)"),
+            self.visual_manager
+                .borrow()
+                .over_budget()
+                .then_some("over CPU budget: visual detail reduced"),
+            self.visual_manager
+                .borrow()
+                .any_frozen()
+                .then_some("frozen by automation (f to resume)"),
+            self.replay
+                .is_some()
+                .then_some("replaying the last 5 s at 0.25x (r to stop)"),
+            meter_tap::test_signal()
+                .is_some()
+                .then_some("test signal: capture bypassed (stop from config)"),
+            self.session
+                .is_some()
+                .then_some("recording session (stop from config)"),
             is_active(self.exit_warning_until).then_some("q again to exit"),
         ];
 
@@ -294,12 +524,16 @@ impl UiApp {
         if !(self.main_window_is_layer && bar.enabled) {
             return content;
         }
+        let main_id = self.main_window_id;
         let handle = mouse_area(
             container(text(" "))
                 .width(Length::Fill)
                 .height(BAR_RESIZE_HANDLE_THICKNESS),
         )
         .on_press(Message::BarResizeStart)
+        .on_scroll(move |delta| {
+            Message::OpacityScrolled(main_id, crate::ui::scroll_delta_lines(delta))
+        })
         .interaction(iced::mouse::Interaction::ResizingVertically);
         let handle_layer = fill(handle).align_y(match bar.alignment {
             BarAlignment::Top => Vertical::Bottom,
@@ -321,3 +555,23 @@ impl UiApp {
         }
     }
 }
+
+fn load_visual_manager(settings: &VisualSettings, theme: Option<ThemeFile>) -> VisualManager {
+    let mut manager = VisualManager::default();
+    manager.apply_visual_settings(settings);
+    if let Some(theme) = theme {
+        manager.apply_theme(&theme);
+    }
+    manager
+}
+
+fn levels_json(levels: LoudnessSnapshot) -> Value {
+    let channels = levels.channel_count.min(levels.true_peak_db.len());
+    json!({
+        "momentary_lufs": levels.momentary_loudness,
+        "short_term_lufs": levels.short_term_loudness,
+        "rms_fast_db": &levels.rms_fast_db[..channels],
+        "rms_slow_db": &levels.rms_slow_db[..channels],
+        "true_peak_db": &levels.true_peak_db[..channels],
+    })
+}
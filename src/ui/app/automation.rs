@@ -0,0 +1,688 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+use super::UiApp;
+use super::message::Message;
+use crate::domain::automation::{
+    AutomationRule, IdlePause, LoudnessAlert, RuleAction, RuleMetric, SilenceWatch,
+};
+use crate::infra::pipewire::meter_tap;
+use crate::infra::{hook, notify, osc};
+use crate::persistence::settings::data_dir;
+use crate::util::{png, utc_timestamp, wav};
+use crate::visuals::loudness::processor::LoudnessSnapshot;
+use crate::visuals::registry::VisualKind;
+use iced::{Rectangle, Task, window};
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+use tracing::{info, warn};
+
+const COOLDOWN: Duration = Duration::from_secs(10);
+const LOG_FILE: &str = "automation.log";
+const SNAPSHOT_DIR: &str = "snapshots";
+const SILENCE_OSC_ADDRESS: &str = "/openmeters/silence";
+// -80 dBFS; dithered silence and idle noise gates sit below this.
+const SILENCE_PEAK: f32 = 1.0e-4;
+// A lone gap now and then isn't worth a toast; this many within the window is.
+const GLITCH_SPIKE: u64 = 3;
+const GLITCH_WINDOW: Duration = Duration::from_secs(10);
+const GLITCH_TOAST: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, Default)]
+struct RuleState {
+    tripped: bool,
+    last_fired: Option<Instant>,
+}
+
+/// Fires a rule once per breach, then waits for the condition to clear.
+#[derive(Debug, Default)]
+pub(super) struct RuleEngine {
+    rules: Vec<AutomationRule>,
+    states: Vec<RuleState>,
+    // Shared by every rule, so several rules can't stack up commands.
+    last_command: Option<Instant>,
+}
+
+impl RuleEngine {
+    fn evaluate(
+        &mut self,
+        rules: &[AutomationRule],
+        value_of: impl Fn(RuleMetric) -> f32,
+        now: Instant,
+    ) -> Vec<(AutomationRule, f32)> {
+        if self.rules != rules {
+            self.rules = rules.to_vec();
+            self.states = vec![RuleState::default(); rules.len()];
+        }
+        let mut fired = Vec::new();
+        for (rule, state) in self.rules.iter().zip(&mut self.states) {
+            let value = value_of(rule.metric);
+            if !rule.enabled || !rule.is_met(value) {
+                state.tripped = false;
+                continue;
+            }
+            let cooling = state
+                .last_fired
+                .is_some_and(|at| now.duration_since(at) < COOLDOWN);
+            if state.tripped || cooling {
+                continue;
+            }
+            *state = RuleState {
+                tripped: true,
+                last_fired: Some(now),
+            };
+            fired.push((*rule, value));
+        }
+        fired
+    }
+
+    fn command_due(&mut self, min_interval_secs: f32, now: Instant) -> bool {
+        let waiting = self
+            .last_command
+            .is_some_and(|at| now.duration_since(at).as_secs_f32() < min_interval_secs);
+        if !waiting {
+            self.last_command = Some(now);
+        }
+        !waiting
+    }
+}
+
+/// Tracks how long short-term loudness has been out of bounds.
+#[derive(Debug, Default)]
+pub(super) struct AlertTracker {
+    since: Option<Instant>,
+    last_sent: Option<Instant>,
+}
+
+impl AlertTracker {
+    // True once the condition has held for the hold time, at most once per cooldown.
+    fn update(&mut self, alert: &LoudnessAlert, lufs: f32, now: Instant) -> bool {
+        if !alert.enabled || !lufs.is_finite() || !alert.condition.holds(lufs, alert.target_lufs) {
+            self.since = None;
+            return false;
+        }
+        let since = *self.since.get_or_insert(now);
+        let held = now.duration_since(since).as_secs_f32() >= alert.hold_secs;
+        let cooling = self
+            .last_sent
+            .is_some_and(|at| now.duration_since(at).as_secs_f32() < alert.cooldown_secs);
+        if !held || cooling {
+            return false;
+        }
+        self.last_sent = Some(now);
+        true
+    }
+}
+
+/// Tracks how long the watched audio has been silent. Any audible batch
+/// resumes at once.
+#[derive(Debug, Default)]
+pub(super) struct IdleWatch {
+    silent_since: Option<Instant>,
+    paused: bool,
+}
+
+impl IdleWatch {
+    pub(super) fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    fn update(&mut self, rule: &IdlePause, audible: bool, now: Instant) -> bool {
+        if !rule.enabled || audible {
+            *self = Self::default();
+            return false;
+        }
+        let since = *self.silent_since.get_or_insert(now);
+        self.paused = now.duration_since(since).as_secs_f32() >= rule.silence_secs.max(0.0);
+        self.paused
+    }
+}
+
+/// Tracks dead air: silence while captured applications should be playing.
+/// Nothing playing is not dead air, so it restarts the count.
+#[derive(Debug, Default)]
+pub(super) struct SilenceWatchdog {
+    silent_since: Option<Instant>,
+    alarmed: bool,
+}
+
+impl SilenceWatchdog {
+    pub(super) fn is_alarmed(&self) -> bool {
+        self.alarmed
+    }
+
+    /// `Some(true)` when the alarm goes off, `Some(false)` when it clears.
+    fn update(
+        &mut self,
+        watch: &SilenceWatch,
+        audible: bool,
+        playing: bool,
+        now: Instant,
+    ) -> Option<bool> {
+        let was = self.alarmed;
+        if !watch.enabled || audible || !playing {
+            *self = Self::default();
+        } else {
+            let since = *self.silent_since.get_or_insert(now);
+            self.alarmed = now.duration_since(since).as_secs_f32() >= watch.silence_secs;
+        }
+        (self.alarmed != was).then_some(self.alarmed)
+    }
+}
+
+/// Watches the gap and drop counters for bursts.
+#[derive(Debug, Default)]
+pub(super) struct GlitchWatch {
+    last_total: Option<u64>,
+    recent: VecDeque<(Instant, u64)>,
+    toast_until: Option<Instant>,
+}
+
+impl GlitchWatch {
+    pub(super) fn is_toasting(&self, now: Instant) -> bool {
+        self.toast_until.is_some_and(|until| now < until)
+    }
+
+    /// The glitches in the window when a burst starts.
+    fn update(&mut self, total: u64, now: Instant) -> Option<u64> {
+        let fresh = total.saturating_sub(self.last_total.replace(total).unwrap_or(total));
+        if fresh > 0 {
+            self.recent.push_back((now, fresh));
+        }
+        while self
+            .recent
+            .front()
+            .is_some_and(|&(at, _)| now.duration_since(at) > GLITCH_WINDOW)
+        {
+            self.recent.pop_front();
+        }
+        let burst: u64 = self.recent.iter().map(|&(_, count)| count).sum();
+        if burst < GLITCH_SPIKE || self.is_toasting(now) {
+            return None;
+        }
+        self.recent.clear();
+        self.toast_until = Some(now + GLITCH_TOAST);
+        Some(burst)
+    }
+}
+
+pub(super) fn metric_value(levels: &LoudnessSnapshot, metric: RuleMetric) -> f32 {
+    match metric {
+        RuleMetric::TruePeak => levels.true_peak_db
+            [..levels.channel_count.min(levels.true_peak_db.len())]
+            .iter()
+            .copied()
+            .fold(f32::NEG_INFINITY, f32::max),
+        RuleMetric::ShortTerm => levels.short_term_loudness,
+        RuleMetric::Momentary => levels.momentary_loudness,
+    }
+}
+
+// One thread per batch keeps the file I/O off the UI thread.
+fn append_log(lines: Vec<String>) {
+    if lines.is_empty() {
+        return;
+    }
+    for line in &lines {
+        info!("[automation] {line}");
+    }
+    std::thread::spawn(move || {
+        let dir = data_dir();
+        let written = fs::create_dir_all(&dir).and_then(|()| {
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(dir.join(LOG_FILE))?;
+            lines.iter().try_for_each(|line| writeln!(file, "{line}"))
+        });
+        if let Err(err) = written {
+            warn!("[automation] failed to write log: {err}");
+        }
+    });
+}
+
+pub(super) fn save_snapshot(path: PathBuf, shot: window::Screenshot) {
+    std::thread::spawn(move || {
+        let Some(png) = png::encode_rgba(shot.size.width, shot.size.height, &shot.rgba) else {
+            warn!("[automation] screenshot had an unexpected size");
+            return;
+        };
+        match path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(&path, png))
+        {
+            Ok(()) => info!("[automation] saved {}", path.display()),
+            Err(err) => warn!("[automation] failed to save {}: {err}", path.display()),
+        }
+    });
+}
+
+fn save_audio(path: PathBuf, samples: Vec<f32>, channels: usize, sample_rate: f32) {
+    std::thread::spawn(move || {
+        let Some(wav) = wav::encode_f32(&samples, channels as u16, sample_rate as u32) else {
+            warn!("[automation] lead-up audio could not be encoded");
+            return;
+        };
+        match path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(&path, wav))
+        {
+            Ok(()) => info!("[automation] saved {}", path.display()),
+            Err(err) => warn!("[automation] failed to save {}: {err}", path.display()),
+        }
+    });
+}
+
+impl UiApp {
+    pub(super) fn run_automation(&mut self) -> Task<Message> {
+        let (levels, alert) = {
+            let manager = self.visual_manager.borrow();
+            (manager.loudness_levels(), manager.loudness_alert())
+        };
+        let Some(levels) = levels else {
+            return Task::none();
+        };
+        let now = Instant::now();
+        if let Some(alert) = alert {
+            self.check_loudness_alert(&alert, levels.short_term_loudness, now);
+        }
+        let fired = {
+            let settings = self.settings_handle.borrow();
+            let rules = &settings.data.rules;
+            if rules.is_empty() {
+                return Task::none();
+            }
+            self.automation
+                .evaluate(rules, |metric| metric_value(&levels, metric), now)
+        };
+        let mut log = Vec::with_capacity(fired.len());
+        let tasks: Vec<_> = fired
+            .into_iter()
+            .filter_map(|(rule, value)| {
+                let stamp = utc_timestamp(SystemTime::now());
+                log.push(format!("{stamp} {} (measured {value:.1})", rule.describe()));
+                self.record_session_event(format!("{} (measured {value:.1})", rule.describe()));
+                match rule.action {
+                    RuleAction::SnapshotAndLog => self.snapshot_spectrogram(&data_dir(), &stamp),
+                    RuleAction::Log => None,
+                    RuleAction::Freeze => {
+                        self.freeze_on_event(&rule, &data_dir(), &stamp);
+                        None
+                    }
+                    RuleAction::RunCommand => {
+                        self.run_rule_command(&rule, value, &stamp, now);
+                        None
+                    }
+                }
+            })
+            .collect();
+        append_log(log);
+        Task::batch(tasks)
+    }
+
+    /// True while the idle rule holds the visuals back; `samples` is only
+    /// scanned for a peak.
+    pub(super) fn idle_paused(&mut self, samples: &[f32]) -> bool {
+        let (rule, was_paused) = (
+            self.settings_handle.borrow().data.idle_pause.clone(),
+            self.idle.is_paused(),
+        );
+        if !rule.enabled && !was_paused {
+            self.idle = IdleWatch::default();
+            return false;
+        }
+        // A named application is silent while none of its streams run, even
+        // if others keep the capture audible.
+        let audible = (rule.application.is_empty()
+            || self.config_page.application_running(&rule.application))
+            && samples.iter().any(|s| s.abs() > SILENCE_PEAK);
+        let paused = self.idle.update(&rule, audible, Instant::now());
+        if paused != was_paused {
+            self.visual_manager.borrow_mut().set_idle(paused);
+            let source = if rule.application.is_empty() {
+                "capture"
+            } else {
+                rule.application.as_str()
+            };
+            let event = if paused {
+                format!(
+                    "Idle: {source} silent for {:.0} s, visuals paused",
+                    rule.silence_secs
+                )
+            } else {
+                format!("Idle: {source} audible again, visuals resumed")
+            };
+            info!("[automation] {event}");
+            self.record_session_event(event);
+        }
+        paused
+    }
+
+    pub(super) fn watch_silence(&mut self, samples: &[f32]) {
+        let watch = self.settings_handle.borrow().data.silence_watch.clone();
+        if !watch.enabled {
+            self.silence = SilenceWatchdog::default();
+            return;
+        }
+        let audible = samples.iter().any(|s| s.abs() > SILENCE_PEAK);
+        let playing = self.config_page.applications_playing();
+        let Some(alarmed) = self
+            .silence
+            .update(&watch, audible, playing, Instant::now())
+        else {
+            return;
+        };
+        let event = if alarmed {
+            format!(
+                "Silence: no program audio for {:.0} s while applications play",
+                watch.silence_secs
+            )
+        } else {
+            "Silence: program audio is back".to_owned()
+        };
+        warn!("[automation] {event}");
+        if alarmed && watch.notify {
+            notify::send("Program audio is silent", &event);
+        }
+        let target = watch.osc_target.trim();
+        if !target.is_empty() {
+            let seconds = if alarmed { watch.silence_secs } else { 0.0 };
+            osc::send(target, SILENCE_OSC_ADDRESS, seconds);
+        }
+        self.record_session_event(event);
+    }
+
+    pub(super) fn watch_glitches(&mut self) {
+        let counts = meter_tap::glitch_counts();
+        let fresh = self
+            .glitches
+            .last_total
+            .map_or(0, |last| counts.total().saturating_sub(last));
+        if fresh > 0 {
+            self.config_page.note_event(format!(
+                "Capture glitch x{fresh} ({} sink cycle gaps, {} dropped packets since start)",
+                counts.gaps, counts.dropped
+            ));
+        }
+        let Some(burst) = self.glitches.update(counts.total(), Instant::now()) else {
+            return;
+        };
+        let event = format!(
+            "Capture glitches: {burst} in the last {} s \
+             ({} sink cycle gaps, {} dropped packets since start)",
+            GLITCH_WINDOW.as_secs(),
+            counts.gaps,
+            counts.dropped
+        );
+        warn!("[automation] {event}");
+        self.record_session_event(event);
+    }
+
+    // Rate and quantum as the capture sees them; a change often lines up with
+    // a glitch.
+    pub(super) fn watch_graph_clock(&mut self) {
+        let Some(clock) = meter_tap::graph_clock() else {
+            return;
+        };
+        let Some(last) = self
+            .graph_clock
+            .replace(clock)
+            .filter(|&last| last != clock)
+        else {
+            return;
+        };
+        let event = if last.rate == clock.rate {
+            format!("Quantum {} -> {} frames", last.quantum, clock.quantum)
+        } else {
+            format!(
+                "Graph rate {} -> {} Hz ({} frames)",
+                last.rate, clock.rate, clock.quantum
+            )
+        };
+        info!("[automation] {event}");
+        self.config_page.note_event(event);
+    }
+
+    fn check_loudness_alert(&mut self, alert: &LoudnessAlert, lufs: f32, now: Instant) {
+        if !self.loudness_alert.update(alert, lufs, now) {
+            return;
+        }
+        let body = format!(
+            "Short-term loudness has been {} {:.1} LUFS for {:.0} s (now {lufs:.1} LUFS)",
+            alert.condition.label().to_ascii_lowercase(),
+            alert.target_lufs,
+            alert.hold_secs,
+        );
+        info!("[automation] {body}");
+        notify::send("Loudness alert", &body);
+        self.record_session_event(body);
+    }
+
+    // Opt-in: a rule can be set to this action before any command is
+    // enabled, and then only logs.
+    fn run_rule_command(&mut self, rule: &AutomationRule, value: f32, stamp: &str, now: Instant) {
+        let settings = self.settings_handle.borrow().data.rule_command.clone();
+        let command = settings.command.trim();
+        if !settings.enabled || command.is_empty() {
+            info!("[automation] alert command is not enabled; nothing run");
+            return;
+        }
+        if !self.automation.command_due(settings.interval_secs(), now) {
+            info!("[automation] alert command ran recently; skipped");
+            return;
+        }
+        hook::run(
+            command,
+            vec![
+                ("OPENMETERS_RULE", rule.describe()),
+                ("OPENMETERS_METRIC", rule.metric.label().to_owned()),
+                ("OPENMETERS_CONDITION", rule.condition.label().to_owned()),
+                ("OPENMETERS_THRESHOLD", format!("{:.1}", rule.threshold_db)),
+                ("OPENMETERS_VALUE", format!("{value:.1}")),
+                ("OPENMETERS_TIME", stamp.to_owned()),
+            ],
+        );
+        self.record_session_event(format!("Ran the alert command for {}", rule.describe()));
+    }
+
+    // The frozen visual already holds the lead-up on screen; the capture ring
+    // holds it as audio, saved next to the snapshots.
+    fn freeze_on_event(&mut self, rule: &AutomationRule, dir: &Path, stamp: &str) {
+        let visual = rule.freeze;
+        if !self.visual_manager.borrow_mut().freeze(visual) {
+            return;
+        }
+        self.record_session_event(format!("Froze {visual}"));
+        if let Some((samples, channels, rate)) = self.capture.recent(rule.pre_seconds) {
+            let path = dir
+                .join(SNAPSHOT_DIR)
+                .join(format!("{}-{stamp}.wav", visual.key()));
+            save_audio(path, samples, channels, rate);
+        }
+    }
+
+    // Captures a spectrogram, popped out or else the first one shown in the
+    // main window, cropped to where that instance was last drawn.
+    fn snapshot_spectrogram(&self, dir: &Path, stamp: &str) -> Option<Task<Message>> {
+        let manager = self.visual_manager.borrow();
+        let (window, visual) = self
+            .popout_windows
+            .iter()
+            .find(|(_, popout)| popout.visual.kind == VisualKind::Spectrogram)
+            .map(|(id, popout)| (*id, popout.visual))
+            .or_else(|| {
+                let visual = manager.order().into_iter().find(|visual| {
+                    visual.kind == VisualKind::Spectrogram && manager.is_enabled(*visual)
+                })?;
+                Some((self.main_window_id, visual))
+            })?;
+        let bounds = manager.spectrogram_bounds(visual);
+        let path = dir
+            .join(SNAPSHOT_DIR)
+            .join(format!("{}-{stamp}.png", visual.key()));
+        Some(window::screenshot(window).map(move |shot| {
+            let shot = match bounds {
+                Some(bounds) => crop_to(shot, bounds),
+                None => shot,
+            };
+            Message::SaveSnapshot(path.clone(), shot)
+        }))
+    }
+}
+
+// `bounds` is logical; the screenshot is in physical pixels. Falls back to
+// the whole window when the view lies outside it.
+fn crop_to(shot: window::Screenshot, bounds: Rectangle) -> window::Screenshot {
+    let scaled = bounds * shot.scale_factor as f32;
+    let (x, y) = (scaled.x.max(0.0) as u32, scaled.y.max(0.0) as u32);
+    let region = Rectangle {
+        x,
+        y,
+        width: (scaled.width.round() as u32).min(shot.size.width.saturating_sub(x)),
+        height: (scaled.height.round() as u32).min(shot.size.height.saturating_sub(y)),
+    };
+    if region.width == 0 || region.height == 0 {
+        return shot;
+    }
+    shot.crop(region).unwrap_or(shot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::automation::RuleCondition;
+
+    #[test]
+    fn rules_fire_once_per_breach_with_a_cooldown() {
+        let rules = [AutomationRule::default()];
+        let mut engine = RuleEngine::default();
+        let t0 = Instant::now();
+        let mut fire = |value: f32, secs: u64| {
+            engine
+                .evaluate(&rules, |_| value, t0 + Duration::from_secs(secs))
+                .len()
+        };
+
+        assert_eq!(fire(-3.0, 0), 0);
+        assert_eq!(fire(0.5, 1), 1);
+        assert_eq!(fire(0.8, 2), 0);
+        // Cleared and breached again, but still cooling down.
+        assert_eq!(fire(-3.0, 3), 0);
+        assert_eq!(fire(0.5, 4), 0);
+        assert_eq!(fire(0.5, 12), 1);
+
+        // Commands share one rate limit across rules.
+        assert!(engine.command_due(60.0, t0));
+        assert!(!engine.command_due(60.0, t0 + Duration::from_secs(30)));
+        assert!(engine.command_due(60.0, t0 + Duration::from_secs(61)));
+
+        let below = AutomationRule {
+            condition: RuleCondition::Below,
+            threshold_db: -30.0,
+            ..AutomationRule::default()
+        };
+        assert!(below.is_met(-40.0) && !below.is_met(-20.0));
+    }
+
+    #[test]
+    fn loudness_alerts_wait_for_the_hold_time_and_cool_down() {
+        let alert = LoudnessAlert {
+            enabled: true,
+            hold_secs: 5.0,
+            cooldown_secs: 60.0,
+            ..LoudnessAlert::default()
+        };
+        let mut tracker = AlertTracker::default();
+        let t0 = Instant::now();
+        let mut check =
+            |lufs: f32, secs: u64| tracker.update(&alert, lufs, t0 + Duration::from_secs(secs));
+
+        assert!(!check(-10.0, 0));
+        assert!(!check(-10.0, 4));
+        // Dipping under the target restarts the hold.
+        assert!(!check(-20.0, 5));
+        assert!(!check(-10.0, 6));
+        assert!(check(-10.0, 11));
+        assert!(!check(-10.0, 30));
+        assert!(check(-10.0, 71));
+        assert!(!check(f32::NEG_INFINITY, 200));
+
+        let disabled = LoudnessAlert::default();
+        assert!(!AlertTracker::default().update(&disabled, 0.0, t0));
+    }
+
+    #[test]
+    fn idle_pause_waits_out_the_silence_and_resumes_on_audio() {
+        let rule = IdlePause {
+            enabled: true,
+            silence_secs: 30.0,
+            ..IdlePause::default()
+        };
+        let mut watch = IdleWatch::default();
+        let t0 = Instant::now();
+        let mut check =
+            |audible: bool, secs: u64| watch.update(&rule, audible, t0 + Duration::from_secs(secs));
+
+        assert!(!check(true, 0));
+        assert!(!check(false, 1));
+        assert!(!check(false, 30));
+        assert!(check(false, 31));
+        assert!(check(false, 600));
+        assert!(!check(true, 601));
+        // The silence count starts over after audio.
+        assert!(!check(false, 602));
+        assert!(!check(false, 631));
+        assert!(check(false, 632));
+
+        let disabled = IdlePause::default();
+        assert!(!IdleWatch::default().update(&disabled, false, t0));
+    }
+
+    #[test]
+    fn silence_alarms_only_while_applications_play() {
+        let watch = SilenceWatch {
+            enabled: true,
+            silence_secs: 10.0,
+            ..SilenceWatch::default()
+        };
+        let mut dog = SilenceWatchdog::default();
+        let t0 = Instant::now();
+        let mut check = |audible: bool, playing: bool, secs: u64| {
+            dog.update(&watch, audible, playing, t0 + Duration::from_secs(secs))
+        };
+
+        assert_eq!(check(false, true, 0), None);
+        assert_eq!(check(false, true, 9), None);
+        assert_eq!(check(false, true, 10), Some(true));
+        assert_eq!(check(false, true, 20), None);
+        assert_eq!(check(true, true, 21), Some(false));
+        // Nothing playing is not dead air and restarts the count.
+        assert_eq!(check(false, true, 22), None);
+        assert_eq!(check(false, false, 31), None);
+        assert_eq!(check(false, true, 32), None);
+        assert_eq!(check(false, true, 42), Some(true));
+    }
+
+    #[test]
+    fn glitch_bursts_toast_once_but_stray_glitches_do_not() {
+        let mut watch = GlitchWatch::default();
+        let t0 = Instant::now();
+        let mut check = |total: u64, secs: u64| watch.update(total, t0 + Duration::from_secs(secs));
+
+        // Glitches from before the watch started don't count.
+        assert_eq!(check(40, 0), None);
+        assert_eq!(check(41, 1), None);
+        assert_eq!(check(42, 20), None);
+        assert_eq!(check(44, 25), Some(3));
+        // Still toasting, then the burst has aged out of the window.
+        assert_eq!(check(47, 27), None);
+        assert_eq!(check(47, 40), None);
+        assert_eq!(check(50, 41), Some(3));
+        assert!(watch.is_toasting(t0 + Duration::from_secs(45)));
+    }
+}
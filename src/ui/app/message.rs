@@ -1,39 +1,56 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // Copyright (C) 2026 Maika Namuo
 
-use super::{TOAST_DISPLAY_DURATION, UiApp};
+use super::{TOAST_DISPLAY_DURATION, UiApp, automation};
+use crate::infra::ipc::IpcRequest;
+use crate::infra::mpris::TrackInfo;
 use crate::infra::pipewire::meter_tap::AudioBatch;
+use crate::persistence::settings::WINDOW_OPACITY_MIN;
 use crate::ui::config::ConfigMessage;
+use crate::ui::scroll_delta_lines;
 use crate::ui::settings::SettingsMessage;
 use crate::ui::visuals::VisualsMessage;
 use crate::ui::widgets::{fill, page, scroll_glow::ScrollGlow};
 use iced::event::{self, Event};
 use iced::keyboard::{self, Key};
-use iced::widget::text;
-use iced::{Element, Size, Task, exit, mouse, window};
+use iced::widget::{scrollable, text};
+use iced::{Element, Size, Task, mouse, window};
 use iced_layershell::actions::{IcedXdgWindowSettings, OutputSnapshot};
 use iced_layershell::reexport::NewLayerShellSettings;
 use iced_layershell::to_layer_message;
+use std::path::PathBuf;
 use std::time::Instant;
 
+const OPACITY_SCROLL_STEP: f32 = 0.05;
+
 #[to_layer_message(multi)]
 #[derive(Debug, Clone)]
 pub(super) enum Message {
     Config(ConfigMessage),
     Visuals(VisualsMessage),
     AudioFrame(AudioBatch),
+    Ipc(IpcRequest),
+    TrackChanged(Option<TrackInfo>),
+    GraphWatchTick,
     BarOutputResolved(window::Id, Option<OutputSnapshot>),
     ToggleConfig,
     TogglePause,
+    ToggleReplay,
+    ResumeFrozen,
+    ToggleVisualAt(usize),
     PopOutOrDock(window::Id),
     BarResizeStart,
     BarResizeMove(iced::Point),
     BarResizeEnd,
+    ModifiersChanged(keyboard::Modifiers),
+    OpacityScrolled(window::Id, f32),
     Quit,
     WindowClosed(window::Id),
     WindowResized(window::Id, Size),
     Settings(window::Id, SettingsMessage),
+    SettingsTrialTick(Instant),
     SettingsScrolled(ScrollGlow),
+    SaveSnapshot(PathBuf, window::Screenshot),
 }
 
 pub(super) fn base_window_open(settings: IcedXdgWindowSettings) -> (window::Id, Task<Message>) {
@@ -56,6 +73,20 @@ pub(super) fn bar_drag_events(evt: Event, _: event::Status, _: window::Id) -> Op
     }
 }
 
+pub(super) fn opacity_scroll_events(
+    evt: Event,
+    _: event::Status,
+    window_id: window::Id,
+) -> Option<Message> {
+    match evt {
+        Event::Mouse(mouse::Event::WheelScrolled { delta }) => Some(Message::OpacityScrolled(
+            window_id,
+            scroll_delta_lines(delta),
+        )),
+        _ => None,
+    }
+}
+
 pub(super) fn keyboard_shortcut(
     event: Event,
     status: event::Status,
@@ -74,6 +105,14 @@ pub(super) fn keyboard_shortcut(
         Key::Character(ch) if no_modifiers && status != event::Status::Captured => {
             if ch.eq_ignore_ascii_case("p") {
                 Some(Message::TogglePause)
+            } else if ch.eq_ignore_ascii_case("r") {
+                Some(Message::ToggleReplay)
+            } else if ch.eq_ignore_ascii_case("f") {
+                Some(Message::ResumeFrozen)
+            } else if ch.eq_ignore_ascii_case("d") {
+                Some(Message::Config(ConfigMessage::ToggleOutputDim))
+            } else if let Ok(digit @ 1..=9) = ch.parse::<usize>() {
+                Some(Message::ToggleVisualAt(digit - 1))
             } else {
                 ch.eq_ignore_ascii_case("q").then_some(Message::Quit)
             }
@@ -94,11 +133,40 @@ pub(super) fn update(app: &mut UiApp, msg: Message) -> Task<Message> {
             };
             let restore_popout = match &config_msg {
                 ConfigMessage::VisualToggled {
-                    kind,
+                    visual,
                     enabled: true,
-                } => Some(*kind),
+                } => Some(*visual),
                 _ => None,
             };
+            // Shown or removed some other way, a visual stops holding its number.
+            if let ConfigMessage::VisualToggled {
+                visual,
+                enabled: true,
+            }
+            | ConfigMessage::VisualRemoved(visual) = &config_msg
+            {
+                app.key_hidden.retain(|hidden| hidden != visual);
+            }
+            let session_task = match &config_msg {
+                ConfigMessage::RecordSession(record) => {
+                    app.set_session_recording(*record);
+                    Task::none()
+                }
+                ConfigMessage::ReviewSession(path) => app.open_session_review(path.clone()),
+                ConfigMessage::SplitByTrack(enabled) => {
+                    app.set_track_splitting(*enabled);
+                    Task::none()
+                }
+                ConfigMessage::ResumeTrackLoudness(resume) => {
+                    app.set_track_persistence(*resume);
+                    Task::none()
+                }
+                ConfigMessage::MeasureDynamicRange(measure) => {
+                    app.set_dynamic_range_measuring(*measure);
+                    Task::none()
+                }
+                _ => Task::none(),
+            };
             let bar_task = app.handle_bar_config_message(&config_msg);
             let theme_changed = matches!(&config_msg, ConfigMessage::ThemeChanged(_));
             app.config_page.update(config_msg);
@@ -108,15 +176,54 @@ pub(super) fn update(app: &mut UiApp, msg: Message) -> Task<Message> {
             let restore_task =
                 restore_popout.map_or_else(Task::none, |kind| app.restore_popout_window(kind));
             let sync_task = app.sync_all_windows();
-            Task::batch([decoration_task, bar_task, restore_task, sync_task])
+            Task::batch([
+                decoration_task,
+                session_task,
+                bar_task,
+                restore_task,
+                sync_task,
+            ])
+        }
+        Message::Visuals(VisualsMessage::SettingsRequested(visual)) => {
+            app.open_settings_window(visual)
         }
-        Message::Visuals(VisualsMessage::SettingsRequested(kind)) => app.open_settings_window(kind),
         Message::Visuals(visuals_msg) => app.visuals_page.update(visuals_msg).map(Message::Visuals),
         Message::ToggleConfig => app.toggle_config_window(),
         Message::TogglePause => {
             app.rendering_paused = !app.rendering_paused;
             Task::none()
         }
+        Message::ToggleReplay => {
+            app.toggle_replay();
+            Task::none()
+        }
+        Message::ResumeFrozen => {
+            app.visual_manager.borrow_mut().resume_frozen();
+            Task::none()
+        }
+        // Counts the enabled visuals in configured order; one hidden by its
+        // number keeps that number until it is shown again.
+        Message::ToggleVisualAt(index) => {
+            let visual = {
+                let manager = app.visual_manager.borrow();
+                let mut numbered = manager.order().into_iter().filter(|visual| {
+                    manager.is_enabled(*visual) || app.key_hidden.contains(visual)
+                });
+                numbered.nth(index)
+            };
+            let Some(visual) = visual else {
+                return Task::none();
+            };
+            let enabled = !app.visual_manager.borrow().is_enabled(visual);
+            app.key_hidden.retain(|hidden| *hidden != visual);
+            if !enabled {
+                app.key_hidden.push(visual);
+            }
+            update(
+                app,
+                Message::Config(ConfigMessage::VisualToggled { visual, enabled }),
+            )
+        }
         Message::PopOutOrDock(window_id) => app.handle_popout_or_dock(window_id),
         Message::BarResizeStart => {
             app.begin_bar_resize();
@@ -127,18 +234,39 @@ pub(super) fn update(app: &mut UiApp, msg: Message) -> Task<Message> {
             Task::none()
         }
         Message::BarResizeEnd => app.finish_bar_resize(),
+        Message::ModifiersChanged(modifiers) => {
+            app.modifiers = modifiers;
+            Task::none()
+        }
+        Message::OpacityScrolled(window_id, lines) => {
+            if window_id != app.main_window_id && !app.popout_windows.contains_key(&window_id) {
+                return Task::none();
+            }
+            let opacity = app.settings_handle.borrow().data.opacity.alpha();
+            let level = (opacity + lines * OPACITY_SCROLL_STEP).clamp(WINDOW_OPACITY_MIN, 1.0);
+            update(app, Message::Config(ConfigMessage::OpacityChanged(level)))
+        }
         Message::Quit => {
             if app.exit_warning_until.is_some_and(|d| Instant::now() < d) {
-                return exit();
+                return app.shut_down();
             }
             app.exit_warning_until = Some(Instant::now() + TOAST_DISPLAY_DURATION);
             Task::none()
         }
-        Message::AudioFrame(AudioBatch { samples, format }) if !app.rendering_paused => {
-            app.visual_manager
-                .borrow_mut()
-                .ingest_samples(&samples, format);
-            app.sync_all_windows()
+        Message::AudioFrame(AudioBatch {
+            samples,
+            format,
+            captured,
+        }) if !app.rendering_paused => app.ingest_audio(&samples, format, captured),
+        Message::Ipc(request) => app.handle_ipc(request),
+        Message::TrackChanged(track) => {
+            app.on_track_changed(track);
+            Task::none()
+        }
+        Message::GraphWatchTick => {
+            app.watch_glitches();
+            app.watch_graph_clock();
+            Task::none()
         }
         Message::BarOutputResolved(id, Some(snapshot))
             if app.main_window_is_layer && id == app.main_window_id =>
@@ -147,15 +275,37 @@ pub(super) fn update(app: &mut UiApp, msg: Message) -> Task<Message> {
             Task::none()
         }
         Message::WindowClosed(window_id) => app.on_window_closed(window_id),
+        Message::SettingsTrialTick(now) => {
+            if let Some((_, panel)) = app.settings_window.as_mut() {
+                panel.tick(now, &app.visual_manager, &app.settings_handle);
+            }
+            Task::none()
+        }
         Message::Settings(window_id, settings_msg) => {
             if let Some((wid, panel)) = app.settings_window.as_mut()
                 && *wid == window_id
             {
+                // Pop-out overrides live with the window, not the module settings.
+                if let SettingsMessage::PopoutDisplay(display) = settings_msg {
+                    let visual = panel.visual;
+                    panel.handle(settings_msg, &app.visual_manager, &app.settings_handle);
+                    app.set_popout_display(visual, display);
+                    return Task::none();
+                }
+                if let SettingsMessage::PopoutAspect(aspect) = settings_msg {
+                    let kind = panel.visual.kind;
+                    panel.handle(settings_msg, &app.visual_manager, &app.settings_handle);
+                    return app.set_aspect_lock(kind, aspect);
+                }
                 panel.handle(settings_msg, &app.visual_manager, &app.settings_handle);
                 app.config_page.refresh_theme_choices_if_needed();
             }
             Task::none()
         }
+        Message::SaveSnapshot(path, shot) => {
+            automation::save_snapshot(path, shot);
+            Task::none()
+        }
         Message::SettingsScrolled(g) => {
             app.settings_scroll = g;
             Task::none()
@@ -175,13 +325,22 @@ pub(super) fn view(app: &UiApp, window_id: window::Id) -> Element<'_, Message> {
     if app.config_window == Some(window_id) {
         return page(app.config_page.view().map(Message::Config)).into();
     }
+    if let Some((_, review)) = app
+        .review_window
+        .as_ref()
+        .filter(|(id, _)| *id == window_id)
+    {
+        let numbers = app.visual_manager.borrow().number_format();
+        return page(scrollable(review.view(numbers))).into();
+    }
     if let Some((_, panel)) = app
         .settings_window
         .as_ref()
         .filter(|(id, _)| *id == window_id)
     {
+        let numbers = app.visual_manager.borrow().number_format();
         let mapped = panel
-            .view()
+            .view(numbers)
             .map(move |msg| Message::Settings(window_id, msg));
         return page(
             app.settings_scroll
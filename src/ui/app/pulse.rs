@@ -0,0 +1,66 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Beat pulse, a purely cosmetic mode for streaming overlays. Onsets in the
+// live capture restart a short decay clock, and its level brightens the
+// visual colors, the window background, or both.
+
+use super::UiApp;
+use crate::dsp::onset::OnsetDetector;
+use crate::infra::pipewire::meter_tap::MeterFormat;
+use std::time::{Duration, Instant};
+
+const DECAY: Duration = Duration::from_millis(250);
+// At full intensity a beat takes the background this far towards white.
+const BACKGROUND_LIFT: f32 = 0.3;
+
+#[derive(Debug, Default)]
+pub(super) struct BeatClock {
+    detector: Option<OnsetDetector>,
+    beat: Option<Instant>,
+    level: f32,
+}
+
+impl BeatClock {
+    fn advance(&mut self, samples: &[f32], format: MeterFormat, now: Instant) -> f32 {
+        let detector = match &mut self.detector {
+            Some(detector) if detector.sample_rate() == format.sample_rate => detector,
+            slot => slot.insert(OnsetDetector::new(format.sample_rate)),
+        };
+        if detector.push(samples, format.channels) {
+            self.beat = Some(now);
+        }
+        // Eases out, so the flash lands on the beat and fades quickly.
+        let left = self.beat.map_or(0.0, |beat| {
+            1.0 - now.duration_since(beat).as_secs_f32() / DECAY.as_secs_f32()
+        });
+        self.level = left.max(0.0).powi(2);
+        self.level
+    }
+}
+
+impl UiApp {
+    /// Runs the beat clock on live audio; idle pauses pass no samples so the
+    /// last pulse still fades out.
+    pub(super) fn pulse_beats(&mut self, samples: &[f32], format: MeterFormat) {
+        let pulse = self.settings_handle.borrow().data.beat_pulse;
+        if !pulse.target.visuals() && !pulse.target.background() {
+            self.beat_clock = BeatClock::default();
+            self.visual_manager.borrow_mut().set_beat_pulse(0.0);
+            return;
+        }
+        let level = self.beat_clock.advance(samples, format, Instant::now()) * pulse.intensity;
+        self.visual_manager
+            .borrow_mut()
+            .set_beat_pulse(if pulse.target.visuals() { level } else { 0.0 });
+    }
+
+    /// How far the visual windows' background is lifted towards white.
+    pub(super) fn background_pulse(&self) -> f32 {
+        let pulse = self.settings_handle.borrow().data.beat_pulse;
+        if !pulse.target.background() {
+            return 0.0;
+        }
+        (self.beat_clock.level * pulse.intensity).clamp(0.0, 1.0) * BACKGROUND_LIFT
+    }
+}
@@ -0,0 +1,332 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+use super::UiApp;
+use super::automation::metric_value;
+use super::message::Message;
+use super::windowing::open_tool_base_window;
+use crate::domain::automation::RuleMetric;
+use crate::domain::routing::Mixdown;
+use crate::infra::pipewire::meter_tap::MeterFormat;
+use crate::persistence::session::{
+    FRAME_SECONDS, LEVEL_FLOOR_DB, MeasurementSession, SPECTRUM_BANDS, SPECTRUM_RANGE_HZ,
+    SessionEvent, SessionFrame, floor_level,
+};
+use crate::ui::theme;
+use crate::ui::widgets::{card, session_plot::session_plot};
+use crate::util::audio::{
+    MixdownGains, NumberFormat, WindowKind, mix_frame, power_to_db, window_coefficients,
+};
+use crate::util::utc_timestamp;
+use crate::visuals::loudness::processor::LoudnessSnapshot;
+use iced::widget::{Column, column, text};
+use iced::{Element, Task, window};
+use realfft::{RealFftPlanner, RealToComplex};
+use rustfft::num_complex::Complex32;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tracing::{info, warn};
+
+const FFT_SIZE: usize = 4096;
+// Four hours; a longer session keeps its events but stops adding frames.
+const MAX_FRAMES: usize = (4.0 * 3600.0 / FRAME_SECONDS) as usize;
+
+/// Mono mix of the latest input, reduced to `SPECTRUM_BANDS` log-spaced
+/// band peaks on demand.
+struct BandAnalyzer {
+    fft: Arc<dyn RealToComplex<f32>>,
+    window: Arc<[f32]>,
+    history: VecDeque<f32>,
+    input: Vec<f32>,
+    spectrum: Vec<Complex32>,
+    mixdown: MixdownGains,
+}
+
+impl BandAnalyzer {
+    fn new() -> Self {
+        let fft = RealFftPlanner::<f32>::new().plan_fft_forward(FFT_SIZE);
+        Self {
+            input: fft.make_input_vec(),
+            spectrum: fft.make_output_vec(),
+            fft,
+            window: window_coefficients(WindowKind::Hann, FFT_SIZE),
+            history: VecDeque::with_capacity(FFT_SIZE),
+            mixdown: MixdownGains::default(),
+        }
+    }
+
+    fn push(&mut self, samples: &[f32], channels: usize, mixdown: &Mixdown) {
+        self.mixdown
+            .set_weights(&mixdown.weights, mixdown.exclude_lfe);
+        let gains = self.mixdown.for_channels(channels);
+        self.history.extend(
+            samples
+                .chunks_exact(channels)
+                .map(|frame| mix_frame(frame, gains)),
+        );
+        let excess = self.history.len().saturating_sub(FFT_SIZE);
+        self.history.drain(..excess);
+    }
+
+    fn bands(&mut self, sample_rate: f32) -> Vec<u8> {
+        let offset = FFT_SIZE - self.history.len();
+        self.input[..offset].fill(0.0);
+        for ((dst, &sample), &w) in self.input[offset..]
+            .iter_mut()
+            .zip(&self.history)
+            .zip(&self.window[offset..])
+        {
+            *dst = sample * w;
+        }
+        if self
+            .fft
+            .process(&mut self.input, &mut self.spectrum)
+            .is_err()
+        {
+            return vec![0; SPECTRUM_BANDS];
+        }
+        // A full-scale sine peaks at 0 dB.
+        let gain = 2.0 / self.window.iter().sum::<f32>();
+        let bin_hz = sample_rate / FFT_SIZE as f32;
+        let [low, high] = SPECTRUM_RANGE_HZ;
+        let edge = |band: usize| {
+            let hz = low * (high / low).powf(band as f32 / SPECTRUM_BANDS as f32);
+            (hz / bin_hz).round() as usize
+        };
+        let last = self.spectrum.len() - 1;
+        (0..SPECTRUM_BANDS)
+            .map(|band| {
+                let start = edge(band).min(last);
+                let end = edge(band + 1).clamp(start + 1, last + 1);
+                let peak = self.spectrum[start..end]
+                    .iter()
+                    .map(Complex32::norm_sqr)
+                    .fold(0.0, f32::max);
+                let db = power_to_db(peak * gain * gain, LEVEL_FLOOR_DB);
+                (db - LEVEL_FLOOR_DB).round().clamp(0.0, 255.0) as u8
+            })
+            .collect()
+    }
+}
+
+/// Appends one frame per `FRAME_SECONDS` of audio, timed by the samples seen
+/// rather than the wall clock.
+pub(super) struct SessionRecorder {
+    session: MeasurementSession,
+    analyzer: BandAnalyzer,
+    elapsed: f64,
+}
+
+impl SessionRecorder {
+    pub(super) fn new(started: String) -> Self {
+        Self {
+            session: MeasurementSession::new(started),
+            analyzer: BandAnalyzer::new(),
+            elapsed: 0.0,
+        }
+    }
+
+    pub(super) fn record(
+        &mut self,
+        samples: &[f32],
+        format: MeterFormat,
+        levels: Option<LoudnessSnapshot>,
+        mixdown: &Mixdown,
+    ) {
+        let channels = format.channels.max(1);
+        if format.sample_rate <= 0.0 || samples.len() < channels {
+            return;
+        }
+        self.analyzer.push(samples, channels, mixdown);
+        self.elapsed += (samples.len() / channels) as f64 / f64::from(format.sample_rate);
+        let loudness = levels.map(|levels| {
+            [
+                levels.momentary_loudness,
+                levels.short_term_loudness,
+                metric_value(&levels, RuleMetric::TruePeak),
+            ]
+            .map(floor_level)
+        });
+        let frames = &mut self.session.frames;
+        while frames.len() < MAX_FRAMES
+            && (frames.len() + 1) as f64 * f64::from(FRAME_SECONDS) <= self.elapsed
+        {
+            frames.push(SessionFrame {
+                loudness,
+                bands: self.analyzer.bands(format.sample_rate),
+            });
+        }
+    }
+
+    pub(super) fn event(&mut self, text: String) {
+        self.session.events.push(SessionEvent {
+            seconds: self.elapsed as f32,
+            text,
+        });
+    }
+
+    pub(super) fn finish(self) -> MeasurementSession {
+        self.session
+    }
+}
+
+pub(super) struct SessionReview {
+    name: String,
+    session: MeasurementSession,
+}
+
+impl SessionReview {
+    fn load(path: &Path) -> Result<Self, String> {
+        Ok(Self {
+            name: path
+                .file_stem()
+                .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned()),
+            session: MeasurementSession::load(path)?,
+        })
+    }
+
+    pub(super) fn view(&self, numbers: NumberFormat) -> Element<'_, Message> {
+        let session = &self.session;
+        let level = |index, unit| {
+            session.max_loudness(index).map_or_else(
+                || "-".to_owned(),
+                |db| numbers.localize(format!("{db:.1} {unit}")),
+            )
+        };
+        let summary = format!(
+            "Started {} | {} | max momentary {} | max short-term {} | max true peak {}",
+            session.started,
+            numbers.duration(session.duration()),
+            level(0, "LUFS"),
+            level(1, "LUFS"),
+            level(2, "dBTP"),
+        );
+        let events = if session.events.is_empty() {
+            Column::new().push(text("No events recorded").size(theme::BODY_TEXT_SIZE))
+        } else {
+            Column::with_children(session.events.iter().map(|event| {
+                text(format!("{}  {}", clock(event.seconds), event.text))
+                    .size(theme::BODY_TEXT_SIZE)
+                    .into()
+            }))
+        };
+        column![
+            text(&self.name).size(16),
+            text(summary)
+                .size(theme::BODY_TEXT_SIZE)
+                .style(theme::weak_text_style),
+            session_plot(session),
+            card("Events", events.spacing(4)),
+        ]
+        .spacing(theme::SECTION_GAP)
+        .into()
+    }
+}
+
+fn clock(seconds: f32) -> String {
+    let secs = seconds.max(0.0) as u32;
+    format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+impl UiApp {
+    pub(super) fn set_session_recording(&mut self, record: bool) {
+        match (record, self.session.take()) {
+            (true, recorder) => {
+                self.session = recorder.or_else(|| {
+                    info!("[session] recording started");
+                    Some(SessionRecorder::new(utc_timestamp(SystemTime::now())))
+                });
+            }
+            (false, Some(recorder)) => match recorder.finish().save() {
+                Ok(path) => info!("[session] saved {}", path.display()),
+                Err(err) => warn!("[session] failed to save: {err}"),
+            },
+            (false, None) => {}
+        }
+    }
+
+    pub(super) fn record_session_event(&mut self, text: String) {
+        if let Some(recorder) = self.session.as_mut() {
+            recorder.event(text);
+        }
+    }
+
+    pub(super) fn open_session_review(&mut self, path: PathBuf) -> Task<Message> {
+        let review = match SessionReview::load(&path) {
+            Ok(review) => review,
+            Err(err) => {
+                warn!("[session] failed to open {}: {err}", path.display());
+                return Task::none();
+            }
+        };
+        if let Some((id, current)) = self.review_window.as_mut() {
+            *current = review;
+            return window::gain_focus(*id);
+        }
+        let (id, task) = open_tool_base_window(self.use_layershell);
+        self.review_window = Some((id, review));
+        task
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: f32 = 48_000.0;
+
+    fn sine(hz: f32, frames: usize) -> Vec<f32> {
+        (0..frames)
+            .flat_map(|n| {
+                let s = (std::f32::consts::TAU * hz * n as f32 / RATE).sin();
+                [s, s]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn recorder_emits_a_frame_per_interval_with_band_peaks() {
+        let mut recorder = SessionRecorder::new("20260101T000000Z".into());
+        let format = MeterFormat {
+            channels: 2,
+            sample_rate: RATE,
+        };
+        let levels = LoudnessSnapshot {
+            momentary_loudness: f32::NEG_INFINITY,
+            short_term_loudness: -20.0,
+            ..LoudnessSnapshot::default()
+        };
+        // Just over a second, so float drift cannot drop the fourth frame.
+        let signal = sine(1_000.0, RATE as usize + 480);
+        for (i, block) in signal.chunks(2 * 480).enumerate() {
+            recorder.record(block, format, Some(levels), &Mixdown::default());
+            if i == 30 {
+                recorder.event("marker".into());
+            }
+        }
+        let session = recorder.finish();
+
+        assert_eq!(session.frames.len(), 4);
+        assert!((session.events[0].seconds - 0.31).abs() < 1e-4);
+        let frame = session.frames.last().unwrap();
+        let loudness = frame.loudness.unwrap();
+        assert_eq!(loudness[..2], [LEVEL_FLOOR_DB, -20.0]);
+
+        let (band, &peak) = frame
+            .bands
+            .iter()
+            .enumerate()
+            .max_by_key(|&(_, &level)| level)
+            .unwrap();
+        let [low, high] = SPECTRUM_RANGE_HZ;
+        let band_hz = |band: usize| low * (high / low).powf(band as f32 / SPECTRUM_BANDS as f32);
+        assert!((band_hz(band)..band_hz(band + 1)).contains(&1_000.0));
+        assert!(
+            (f32::from(peak) + LEVEL_FLOOR_DB).abs() < 2.0,
+            "peak {peak}"
+        );
+        assert!(frame.bands[SPECTRUM_BANDS - 1] < peak - 60);
+    }
+}
@@ -0,0 +1,407 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+use super::UiApp;
+use super::automation::metric_value;
+use crate::domain::automation::RuleMetric;
+use crate::dsp::dynamic_range::DynamicRange;
+use crate::dsp::gated_loudness::GatedLoudness;
+use crate::infra::mpris::{self, TrackFollower, TrackInfo};
+use crate::infra::pipewire::meter_tap::MeterFormat;
+use crate::persistence::tracks::{self, RunningTrack, TrackResult};
+use crate::ui::config::{ConfigMessage, DynamicRangeReading};
+use crate::util::utc_timestamp;
+use crate::visuals::loudness::processor::LoudnessSnapshot;
+use async_channel::Receiver as AsyncReceiver;
+use std::sync::{Arc, mpsc};
+use std::thread;
+use std::time::SystemTime;
+
+const BLOCK_SECONDS: f64 = 0.1;
+// The meter's windows still hold the previous track until they refill.
+const MOMENTARY_SECONDS: f64 = 0.4;
+const SHORT_TERM_SECONDS: f64 = 3.0;
+// Anything shorter was skipped rather than listened to.
+const MIN_TRACK_SECONDS: f64 = 5.0;
+const CHECKPOINT_SECONDS: f64 = 10.0;
+
+struct Segment {
+    track: Option<TrackInfo>,
+    started: String,
+    loudness: GatedLoudness,
+    // Not checkpointed: after a restart it covers only what played since.
+    dynamic_range: DynamicRange,
+    peak: Option<f32>,
+    elapsed: f64,
+    next_block: f64,
+    // Where the meter's windows started filling; later than zero on resume.
+    origin: f64,
+    saved_at: f64,
+}
+
+impl Segment {
+    fn new(track: Option<TrackInfo>) -> Self {
+        Self {
+            track,
+            started: utc_timestamp(SystemTime::now()),
+            loudness: GatedLoudness::default(),
+            dynamic_range: DynamicRange::default(),
+            peak: None,
+            elapsed: 0.0,
+            next_block: BLOCK_SECONDS,
+            origin: 0.0,
+            saved_at: 0.0,
+        }
+    }
+
+    fn resume(saved: RunningTrack) -> Self {
+        let track = (!saved.title.is_empty()).then(|| TrackInfo {
+            player: saved.player.clone(),
+            artist: saved.artist.clone(),
+            title: saved.title.clone(),
+        });
+        let elapsed = saved.seconds.max(0.0);
+        Self {
+            track,
+            loudness: GatedLoudness::from_blocks(saved.blocks()),
+            started: saved.started,
+            dynamic_range: DynamicRange::default(),
+            peak: saved.peak_dbtp,
+            elapsed,
+            next_block: elapsed + BLOCK_SECONDS,
+            origin: elapsed,
+            saved_at: elapsed,
+        }
+    }
+
+    fn running(&self) -> RunningTrack {
+        let track = self.track.clone().unwrap_or_default();
+        RunningTrack::new(
+            self.started.clone(),
+            [track.player, track.artist, track.title],
+            self.elapsed,
+            self.peak,
+            self.loudness.blocks(),
+        )
+    }
+
+    fn checkpoint(&mut self) -> RunningTrack {
+        self.saved_at = self.elapsed;
+        self.running()
+    }
+
+    fn record(&mut self, frames: usize, sample_rate: f32, levels: &LoudnessSnapshot) {
+        self.elapsed += frames as f64 / f64::from(sample_rate);
+        while self.next_block <= self.elapsed {
+            let filled = self.next_block - self.origin;
+            if filled >= MOMENTARY_SECONDS {
+                self.loudness.push_momentary(levels.momentary_loudness);
+            }
+            if filled >= SHORT_TERM_SECONDS {
+                self.loudness.push_short_term(levels.short_term_loudness);
+            }
+            self.next_block += BLOCK_SECONDS;
+        }
+        let peak = metric_value(levels, RuleMetric::TruePeak);
+        if peak.is_finite() {
+            self.peak = Some(self.peak.map_or(peak, |p| p.max(peak)));
+        }
+    }
+
+    fn finish(self) -> Option<TrackResult> {
+        if self.elapsed < MIN_TRACK_SECONDS {
+            return None;
+        }
+        let track = self.track.unwrap_or_default();
+        Some(TrackResult {
+            started: self.started,
+            player: track.player,
+            artist: track.artist,
+            title: track.title,
+            seconds: self.elapsed as f32,
+            integrated_lufs: self.loudness.integrated(),
+            peak_dbtp: self.peak,
+            range_lu: self.loudness.range(),
+            dynamic_range: self.dynamic_range.value(),
+        })
+    }
+}
+
+/// Restarts integrated loudness whenever the playing track changes. Without
+/// `playerctl` everything lands in one untitled segment. With `persist` the
+/// running segment is checkpointed so a restart picks it up again.
+pub(super) struct TrackSegmenter {
+    follower: Option<TrackFollower>,
+    segment: Option<Segment>,
+    persist: bool,
+    // A resumed segment not yet confirmed by the follower's first report.
+    resumed: bool,
+    writer: CheckpointWriter,
+}
+
+impl TrackSegmenter {
+    pub(super) fn start(persist: bool) -> Self {
+        let follower = mpris::follow();
+        let saved = persist
+            .then(tracks::load_running)
+            .flatten()
+            .map(Segment::resume);
+        // The follower reports the current track straight away; a resumed
+        // segment survives that if the track is the same.
+        let resumed = saved.is_some();
+        let segment = saved.or_else(|| follower.is_none().then(|| Segment::new(None)));
+        Self {
+            follower,
+            segment,
+            persist,
+            resumed,
+            writer: CheckpointWriter::spawn(),
+        }
+    }
+
+    pub(super) fn set_persist(&mut self, persist: bool) {
+        self.persist = persist;
+        let running = self
+            .segment
+            .as_mut()
+            .filter(|_| persist)
+            .map(Segment::checkpoint);
+        self.writer.write(running);
+    }
+
+    pub(super) fn changes(&self) -> Option<Arc<AsyncReceiver<Option<TrackInfo>>>> {
+        self.follower
+            .as_ref()
+            .map(|follower| Arc::clone(&follower.changes))
+    }
+
+    pub(super) fn record(
+        &mut self,
+        samples: &[f32],
+        format: MeterFormat,
+        levels: Option<LoudnessSnapshot>,
+    ) {
+        let Some(segment) = self.segment.as_mut() else {
+            return;
+        };
+        segment
+            .dynamic_range
+            .push(samples, format.channels, format.sample_rate);
+        if let Some(levels) = levels
+            && format.sample_rate > 0.0
+        {
+            let frames = samples.len() / format.channels.max(1);
+            segment.record(frames, format.sample_rate, &levels);
+            if self.persist && segment.elapsed - segment.saved_at >= CHECKPOINT_SECONDS {
+                self.writer.write(Some(segment.checkpoint()));
+            }
+        }
+    }
+
+    /// Closes the running segment and opens one for `track`, if anything plays.
+    pub(super) fn track_changed(&mut self, track: Option<TrackInfo>) -> Option<TrackResult> {
+        if std::mem::take(&mut self.resumed)
+            && track.is_some()
+            && self.segment.as_ref().is_some_and(|s| s.track == track)
+        {
+            return None;
+        }
+        if self.persist {
+            self.writer.write(None);
+        }
+        let finished = self.segment.take().and_then(Segment::finish);
+        self.segment = track.map(|track| Segment::new(Some(track)));
+        finished
+    }
+
+    pub(super) fn current(&self) -> Option<String> {
+        self.segment.as_ref().map(|segment| {
+            segment
+                .track
+                .as_ref()
+                .map_or_else(|| "untitled".to_owned(), TrackInfo::label)
+        })
+    }
+
+    pub(super) fn finish(mut self) -> Option<TrackResult> {
+        if self.persist {
+            self.writer.write(None);
+        }
+        self.segment.take().and_then(Segment::finish)
+    }
+}
+
+// Checkpoints and clears of the running measurement, written in order off
+// the UI thread, like the snapshots. The thread drains its queue and ends
+// once the segmenter is dropped.
+struct CheckpointWriter(Option<mpsc::Sender<Option<RunningTrack>>>);
+
+impl CheckpointWriter {
+    fn spawn() -> Self {
+        let (tx, rx) = mpsc::channel();
+        let spawned = thread::Builder::new()
+            .name("openmeters-track-checkpoint".into())
+            .spawn(move || rx.into_iter().for_each(write_running));
+        match spawned {
+            Ok(_) => Self(Some(tx)),
+            Err(err) => {
+                tracing::warn!("[tracks] checkpoints fall back to the UI thread: {err}");
+                Self(None)
+            }
+        }
+    }
+
+    fn write(&self, running: Option<RunningTrack>) {
+        match &self.0 {
+            Some(tx) => {
+                if let Err(mpsc::SendError(running)) = tx.send(running) {
+                    write_running(running);
+                }
+            }
+            None => write_running(running),
+        }
+    }
+}
+
+fn write_running(running: Option<RunningTrack>) {
+    match running {
+        Some(running) => {
+            if let Err(err) = tracks::save_running(&running) {
+                tracing::warn!("[tracks] failed to save the running measurement: {err}");
+            }
+        }
+        None => tracks::clear_running(),
+    }
+}
+
+impl UiApp {
+    pub(super) fn set_track_splitting(&mut self, enabled: bool) {
+        let persist = self.settings_handle.borrow().data.resume_track_loudness;
+        match (enabled, self.tracks.take()) {
+            (true, segmenter) => {
+                self.tracks = Some(segmenter.unwrap_or_else(|| TrackSegmenter::start(persist)));
+            }
+            (false, Some(segmenter)) => {
+                if let Some(result) = segmenter.finish() {
+                    self.config_page.track_finished(result);
+                }
+            }
+            (false, None) => {}
+        }
+        self.config_page
+            .set_current_track(self.tracks.as_ref().and_then(TrackSegmenter::current));
+    }
+
+    /// Starts or stops a manual DR measurement, independent of tracks.
+    pub(super) fn set_dynamic_range_measuring(&mut self, measure: bool) {
+        match (measure, self.dynamic_range.take()) {
+            (true, meter) => {
+                self.dynamic_range = meter
+                    .or_else(|| Some((utc_timestamp(SystemTime::now()), DynamicRange::default())));
+            }
+            (false, Some((started, meter))) => {
+                self.config_page
+                    .dynamic_range_finished(DynamicRangeReading::new(started, &meter));
+            }
+            (false, None) => {}
+        }
+    }
+
+    pub(super) fn measure_dynamic_range(&mut self, samples: &[f32], format: MeterFormat) {
+        if let Some((started, meter)) = self.dynamic_range.as_mut()
+            && meter.push(samples, format.channels, format.sample_rate)
+        {
+            self.config_page
+                .set_dynamic_range(DynamicRangeReading::new(started.clone(), meter));
+        }
+    }
+
+    pub(super) fn set_track_persistence(&mut self, persist: bool) {
+        match self.tracks.as_mut() {
+            Some(segmenter) => segmenter.set_persist(persist),
+            None if !persist => tracks::clear_running(),
+            None => {}
+        }
+    }
+
+    /// Picks up a measurement interrupted by the last exit.
+    pub(super) fn resume_track_loudness(&mut self) {
+        if self.settings_handle.borrow().data.resume_track_loudness
+            && tracks::load_running().is_some()
+        {
+            self.config_page.update(ConfigMessage::SplitByTrack(true));
+            self.set_track_splitting(true);
+        }
+    }
+
+    pub(super) fn on_track_changed(&mut self, track: Option<TrackInfo>) {
+        let Some(segmenter) = self.tracks.as_mut() else {
+            return;
+        };
+        let label = track.as_ref().map(TrackInfo::label);
+        if let Some(result) = segmenter.track_changed(track) {
+            self.config_page.track_finished(result);
+        }
+        self.config_page.set_current_track(label.clone());
+        if let Some(label) = label {
+            self.record_session_event(format!("Track: {label}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn levels(lufs: f32) -> LoudnessSnapshot {
+        let mut levels = LoudnessSnapshot {
+            momentary_loudness: lufs,
+            short_term_loudness: lufs,
+            channel_count: 2,
+            ..LoudnessSnapshot::default()
+        };
+        levels.true_peak_db[..2].copy_from_slice(&[lufs + 3.0, lufs + 1.0]);
+        levels
+    }
+
+    fn play(segmenter: &mut TrackSegmenter, seconds: usize, lufs: f32) {
+        let format = MeterFormat {
+            channels: 2,
+            sample_rate: 48_000.0,
+        };
+        for _ in 0..seconds * 100 {
+            segmenter.record(&[0.0; 960], format, Some(levels(lufs)));
+        }
+    }
+
+    #[test]
+    fn track_changes_close_segments_and_drop_skips() {
+        let track = |title: &str| TrackInfo {
+            player: "mpv".into(),
+            artist: String::new(),
+            title: title.into(),
+        };
+        let mut segmenter = TrackSegmenter {
+            follower: None,
+            segment: None,
+            persist: false,
+            resumed: false,
+            writer: CheckpointWriter(None),
+        };
+        play(&mut segmenter, 10, -30.0);
+        assert_eq!(segmenter.track_changed(Some(track("one"))), None);
+
+        play(&mut segmenter, 20, -12.0);
+        let one = segmenter.track_changed(Some(track("skipped"))).unwrap();
+        assert_eq!(one.title, "one");
+        assert!((one.seconds - 20.0).abs() < 0.1);
+        assert!((one.integrated_lufs.unwrap() + 12.0).abs() < 1e-3);
+        assert_eq!(one.range_lu, Some(0.0));
+        assert_eq!(one.peak_dbtp, Some(-9.0));
+
+        play(&mut segmenter, 2, -20.0);
+        assert_eq!(segmenter.track_changed(None), None);
+        assert_eq!(segmenter.current(), None);
+        assert_eq!(segmenter.finish(), None);
+    }
+}
@@ -4,14 +4,16 @@
 use super::message::{self, Message};
 use super::{ActiveSettings, UiApp};
 use crate::persistence::settings::{
-    BarAlignment, BarSettings, MainWindowSettings, PopoutWindowSettings, clamp_bar_height,
+    BarAlignment, BarSettings, DisplayOverride, MainWindowSettings, PopoutWindowSettings,
+    clamp_bar_height,
 };
 use crate::ui::config::ConfigMessage;
 use crate::ui::theme;
 use crate::ui::visuals::VisualsMessage;
 use crate::ui::widgets::{fill, scroll_glow::ScrollGlow};
-use crate::util::color::with_alpha;
-use crate::visuals::registry::{VisualContent, VisualKind, VisualSlotSnapshot};
+use crate::util::color::{lerp_color, with_alpha};
+use crate::visuals::options::AspectLock;
+use crate::visuals::registry::{VisualContent, VisualId, VisualKind, VisualSlotSnapshot};
 use iced::widget::{mouse_area, text};
 use iced::{Element, Size, Task, exit, window};
 use iced_layershell::actions::OutputSnapshotCallback;
@@ -41,7 +43,7 @@ impl Dispatch<wl_registry::WlRegistry, GlobalListContents> for LayerShellProbe {
     }
 }
 
-pub(super) fn layershell_available() -> bool {
+pub(crate) fn layershell_available() -> bool {
     let Ok(conn) = Connection::connect_to_env() else {
         return false;
     };
@@ -155,12 +157,33 @@ fn popout_window_size(saved: Option<PopoutWindowSettings>) -> Size {
     clamp_window_size(Size::new(dim(saved.width, 400.0), dim(saved.height, 300.0)))
 }
 
-fn popout_window_settings(size: Size, popped_out: bool) -> PopoutWindowSettings {
+/// `new` fitted to `ratio` (width over height), following whichever side
+/// moved further from `previous`.
+fn locked_size(previous: Size, new: Size, ratio: f32) -> Size {
+    let by_width = (new.width - previous.width).abs() >= (new.height - previous.height).abs();
+    let size = if by_width {
+        Size::new(new.width, new.width / ratio)
+    } else {
+        Size::new(new.height * ratio, new.height)
+    };
+    // Grown as a whole past the minimum, so the shape survives it.
+    let grow = (WINDOW_MIN_SIZE.width / size.width)
+        .max(WINDOW_MIN_SIZE.height / size.height)
+        .max(1.0);
+    Size::new((size.width * grow).round(), (size.height * grow).round())
+}
+
+fn popout_window_settings(
+    size: Size,
+    popped_out: bool,
+    display: Option<DisplayOverride>,
+) -> PopoutWindowSettings {
     let (width, height) = persisted_window_size(size);
     PopoutWindowSettings {
         width,
         height,
         popped_out,
+        display,
     }
 }
 
@@ -172,9 +195,10 @@ pub(super) struct BarResizeState {
 }
 
 pub(super) struct PopoutWindow {
-    pub kind: VisualKind,
+    pub visual: VisualId,
     pub original_index: usize,
     pub size: Size,
+    pub display: Option<DisplayOverride>,
     pub cached: Option<VisualContent>,
 }
 
@@ -182,7 +206,7 @@ impl PopoutWindow {
     pub fn sync_from_snapshot(&mut self, snapshot: &[VisualSlotSnapshot]) {
         self.cached = snapshot
             .iter()
-            .find(|slot| slot.kind == self.kind && slot.enabled)
+            .find(|slot| slot.id == self.visual && slot.enabled)
             .map(|slot| slot.content.clone());
     }
 
@@ -190,7 +214,7 @@ impl PopoutWindow {
         let Some(content) = &self.cached else {
             return fill(text("")).into();
         };
-        let msg = VisualsMessage::SettingsRequested(self.kind);
+        let msg = VisualsMessage::SettingsRequested(self.visual);
         mouse_area(fill(content.render()))
             .on_right_press(msg)
             .into()
@@ -198,41 +222,117 @@ impl PopoutWindow {
 }
 
 impl UiApp {
+    fn settings_panel(&self, visual: VisualId) -> ActiveSettings {
+        let popout = self
+            .popout_windows
+            .values()
+            .find(|popout| popout.visual == visual);
+        ActiveSettings::new(visual, &self.visual_manager, &self.settings_handle)
+            .with_popout_display(popout.map(|popout| popout.display))
+            .with_popout_aspect(popout.map(|_| self.aspect_lock(visual.kind)))
+    }
+
+    fn aspect_lock(&self, kind: VisualKind) -> AspectLock {
+        let settings = self.settings_handle.borrow();
+        settings
+            .data
+            .visuals
+            .aspect_locks
+            .get(&kind)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Stores the lock for `kind` and reshapes its open pop-outs to match.
+    pub(super) fn set_aspect_lock(
+        &mut self,
+        kind: VisualKind,
+        aspect: AspectLock,
+    ) -> Task<Message> {
+        self.settings_handle.update(|s| {
+            let locks = &mut s.data.visuals.aspect_locks;
+            if aspect == AspectLock::Off {
+                locks.remove(&kind);
+            } else {
+                locks.insert(kind, aspect);
+            }
+        });
+        let Some(ratio) = aspect.ratio() else {
+            return Task::none();
+        };
+        Task::batch(
+            self.popout_windows
+                .iter()
+                .filter(|(_, popout)| popout.visual.kind == kind)
+                .map(|(&id, popout)| {
+                    window::resize(id, locked_size(popout.size, popout.size, ratio))
+                }),
+        )
+    }
+
     pub(super) fn refresh_settings_panel(&mut self) {
-        let Some((_, panel)) = self.settings_window.as_mut() else {
+        let Some(visual) = self.settings_window.as_ref().map(|(_, panel)| panel.visual) else {
             return;
         };
-        *panel = ActiveSettings::new(panel.kind, &self.visual_manager);
+        let new_panel = self.settings_panel(visual);
+        if let Some((id, old)) = self.settings_window.take() {
+            self.settings_window = Some((id, new_panel.with_trial_of(old)));
+        }
     }
 
-    pub(super) fn open_settings_window(&mut self, kind: VisualKind) -> Task<Message> {
-        let new_panel = ActiveSettings::new(kind, &self.visual_manager);
+    pub(super) fn set_popout_display(
+        &mut self,
+        visual: VisualId,
+        display: Option<DisplayOverride>,
+    ) {
+        let Some(popout) = self
+            .popout_windows
+            .values_mut()
+            .find(|p| p.visual == visual)
+        else {
+            return;
+        };
+        popout.display = display;
+        let settings = popout_window_settings(popout.size, true, display);
+        self.visual_manager
+            .borrow()
+            .set_display_override(visual, display);
+        self.settings_handle.update(|s| {
+            s.data.visuals.popouts.insert(visual, settings);
+        });
+    }
+
+    pub(super) fn open_settings_window(&mut self, visual: VisualId) -> Task<Message> {
+        let new_panel = self.settings_panel(visual);
         let previous = self.settings_window.take();
         if previous
             .as_ref()
-            .is_some_and(|(_, panel)| panel.kind == kind)
+            .is_some_and(|(_, panel)| panel.visual == visual)
         {
-            self.settings_window = previous.map(|(id, _)| (id, new_panel));
+            self.settings_window = previous.map(|(id, old)| (id, new_panel.with_trial_of(old)));
             return Task::none();
         }
         let (new_id, open_task) = open_tool_base_window(self.use_layershell);
         self.settings_scroll = ScrollGlow::default();
         self.settings_window = Some((new_id, new_panel));
         match previous {
-            Some((old_id, _)) => Task::batch([window::close(old_id), open_task]),
+            Some((old_id, old)) => {
+                old.dismiss(&self.visual_manager, &self.settings_handle);
+                Task::batch([window::close(old_id), open_task])
+            }
             None => open_task,
         }
     }
 
     fn create_popout_window(
         &mut self,
-        kind: VisualKind,
+        visual: VisualId,
         saved_size: Option<PopoutWindowSettings>,
     ) -> Option<(PopoutWindowSettings, Task<Message>)> {
         if self
             .popout_windows
             .values()
-            .any(|popout| popout.kind == kind)
+            .any(|popout| popout.visual == visual)
         {
             return None;
         }
@@ -240,66 +340,78 @@ impl UiApp {
         let (index, _) = snapshot
             .iter()
             .enumerate()
-            .find(|(_, s)| s.kind == kind && s.enabled)?;
-        let window_size = popout_window_size(saved_size);
+            .find(|(_, s)| s.id == visual && s.enabled)?;
+        let mut window_size = popout_window_size(saved_size);
+        if let Some(ratio) = self.aspect_lock(visual.kind).ratio() {
+            window_size = locked_size(window_size, window_size, ratio);
+        }
+        let display = saved_size.and_then(|saved| saved.display);
         let use_decorations = self.settings_handle.borrow().data.decorations;
         let (new_id, open_task) =
             open_base_window(self.use_layershell, window_size, use_decorations);
         let mut popout = PopoutWindow {
-            kind,
+            visual,
             original_index: index,
             size: window_size,
+            display,
             cached: None,
         };
         popout.sync_from_snapshot(&snapshot);
         self.popout_windows.insert(new_id, popout);
-        Some((popout_window_settings(window_size, true), open_task))
+        self.visual_manager
+            .borrow()
+            .set_display_override(visual, display);
+        self.refresh_settings_panel();
+        Some((
+            popout_window_settings(window_size, true, display),
+            open_task,
+        ))
     }
 
     pub(super) fn restore_popout_windows(
         &mut self,
-        saved: &std::collections::BTreeMap<VisualKind, PopoutWindowSettings>,
+        saved: &std::collections::BTreeMap<VisualId, PopoutWindowSettings>,
     ) -> Task<Message> {
         let order = self.visual_manager.borrow().order();
-        Task::batch(order.into_iter().filter_map(|kind| {
-            let settings = saved.get(&kind).copied().filter(|s| s.popped_out)?;
-            self.create_popout_window(kind, Some(settings))
+        Task::batch(order.into_iter().filter_map(|visual| {
+            let settings = saved.get(&visual).copied().filter(|s| s.popped_out)?;
+            self.create_popout_window(visual, Some(settings))
                 .map(|(_, task)| task)
         }))
     }
 
-    pub(super) fn restore_popout_window(&mut self, kind: VisualKind) -> Task<Message> {
+    pub(super) fn restore_popout_window(&mut self, visual: VisualId) -> Task<Message> {
         let saved = {
             self.settings_handle
                 .borrow()
                 .data
                 .visuals
                 .popouts
-                .get(&kind)
+                .get(&visual)
                 .copied()
                 .filter(|s| s.popped_out)
         };
         let Some(settings) = saved else {
             return Task::none();
         };
-        self.create_popout_window(kind, Some(settings))
+        self.create_popout_window(visual, Some(settings))
             .map_or_else(Task::none, |(_, task)| task)
     }
 
-    fn open_popout_window(&mut self, kind: VisualKind) -> Task<Message> {
+    fn open_popout_window(&mut self, visual: VisualId) -> Task<Message> {
         let saved_size = self
             .settings_handle
             .borrow()
             .data
             .visuals
             .popouts
-            .get(&kind)
+            .get(&visual)
             .copied();
-        let Some((settings, task)) = self.create_popout_window(kind, saved_size) else {
+        let Some((settings, task)) = self.create_popout_window(visual, saved_size) else {
             return Task::none();
         };
         self.settings_handle.update(|s| {
-            s.data.visuals.popouts.insert(kind, settings);
+            s.data.visuals.popouts.insert(visual, settings);
         });
         task
     }
@@ -307,30 +419,45 @@ impl UiApp {
     fn dock_popout(&mut self, popout: PopoutWindow) {
         let order = {
             let mut manager = self.visual_manager.borrow_mut();
-            manager.move_to(popout.kind, popout.original_index);
+            manager.move_to(popout.visual, popout.original_index);
+            manager.set_display_override(popout.visual, None);
             manager.order()
         };
-        let popout_settings = popout_window_settings(popout.size, false);
+        let popout_settings = popout_window_settings(popout.size, false, popout.display);
         self.sync_visuals_page();
+        self.refresh_settings_panel();
         self.settings_handle.update(|settings| {
             settings
                 .data
                 .visuals
                 .popouts
-                .insert(popout.kind, popout_settings);
+                .insert(popout.visual, popout_settings);
             settings.data.visuals.order = order;
         });
     }
 
+    // Unconfirmed settings changes are reverted before they can be saved.
+    pub(super) fn shut_down(&mut self) -> Task<Message> {
+        if let Some((_, panel)) = self.settings_window.take() {
+            panel.dismiss(&self.visual_manager, &self.settings_handle);
+        }
+        // A running session recording is saved rather than lost.
+        self.set_session_recording(false);
+        exit()
+    }
+
     pub(super) fn on_window_closed(&mut self, id: window::Id) -> Task<Message> {
         if id == self.main_window_id {
-            return exit();
+            return self.shut_down();
         }
         if self.config_window == Some(id) {
             self.config_window = None;
         }
-        if self.settings_window.as_ref().is_some_and(|(w, _)| *w == id) {
-            self.settings_window = None;
+        if self.review_window.as_ref().is_some_and(|(w, _)| *w == id) {
+            self.review_window = None;
+        }
+        if let Some((_, panel)) = self.settings_window.take_if(|(w, _)| *w == id) {
+            panel.dismiss(&self.visual_manager, &self.settings_handle);
         }
         if let Some(popout) = self.popout_windows.remove(&id) {
             self.dock_popout(popout);
@@ -345,38 +472,49 @@ impl UiApp {
             .take_if(|(_, panel)| {
                 !snapshot
                     .iter()
-                    .any(|slot| slot.kind == panel.kind && slot.enabled)
+                    .any(|slot| slot.id == panel.visual && slot.enabled)
             })
-            .map(|(id, _)| window::close::<Message>(id));
+            .map(|(id, panel)| {
+                panel.dismiss(&self.visual_manager, &self.settings_handle);
+                window::close::<Message>(id)
+            });
         self.popout_windows
             .values_mut()
             .for_each(|popout| popout.sync_from_snapshot(&snapshot));
         let stale_windows: Vec<_> = self
             .popout_windows
             .extract_if(|_, popout| popout.cached.is_none())
-            .map(|(id, popout)| (id, popout.kind, popout.size))
+            .map(|(id, popout)| (id, popout.visual, popout.size, popout.display))
             .collect();
-        // keep disabled popouts restorable when re-enabled.
+        // keep disabled popouts restorable when re-enabled; removed instances are gone.
         if !stale_windows.is_empty() {
+            for (_, visual, _, _) in &stale_windows {
+                self.visual_manager
+                    .borrow()
+                    .set_display_override(*visual, None);
+            }
             self.settings_handle.update(|settings| {
-                for (_, kind, size) in &stale_windows {
+                let kept = stale_windows
+                    .iter()
+                    .filter(|(_, visual, _, _)| snapshot.iter().any(|slot| slot.id == *visual));
+                for (_, visual, size, display) in kept {
                     settings
                         .data
                         .visuals
                         .popouts
-                        .insert(*kind, popout_window_settings(*size, true));
+                        .insert(*visual, popout_window_settings(*size, true, *display));
                 }
             });
         }
         self.visuals_page
-            .apply_snapshot_excluding(&snapshot, |kind| {
-                self.popout_windows.values().any(|w| w.kind == kind)
+            .apply_snapshot_excluding(&snapshot, |visual| {
+                self.popout_windows.values().any(|w| w.visual == visual)
             });
         Task::batch(
             close_settings_task.into_iter().chain(
                 stale_windows
                     .into_iter()
-                    .map(|(id, _, _)| window::close(id)),
+                    .map(|(id, _, _, _)| window::close(id)),
             ),
         )
     }
@@ -390,39 +528,57 @@ impl UiApp {
             return "Configuration - OpenMeters".into();
         }
 
-        let (kind, suffix) = if let Some((_, panel)) = self
+        if self
+            .review_window
+            .as_ref()
+            .is_some_and(|(w, _)| *w == window_id)
+        {
+            return "Session review - OpenMeters".into();
+        }
+
+        let (visual, suffix) = if let Some((_, panel)) = self
             .settings_window
             .as_ref()
             .filter(|(id, _)| *id == window_id)
         {
-            (panel.kind, " settings")
+            (panel.visual, " settings")
         } else if let Some(popout) = self.popout_windows.get(&window_id) {
-            (popout.kind, "")
+            (popout.visual, "")
         } else {
             return "OpenMeters".into();
         };
 
-        format!("{}{} - OpenMeters", kind.label(), suffix)
+        format!("{visual}{suffix} - OpenMeters")
     }
 
     pub(super) fn theme(&self, window_id: window::Id) -> iced::Theme {
         let is_config = self.config_window == Some(window_id);
         let is_settings = matches!(&self.settings_window, Some((w, _)) if *w == window_id);
-        let is_tool = is_config || is_settings;
+        let is_review = matches!(&self.review_window, Some((w, _)) if *w == window_id);
+        let is_tool = is_config || is_settings || is_review;
         // Tool windows force opaque alpha: they have no wgpu visual backdrop, so a
         // translucent user background would let the desktop bleed through the chrome.
-        let custom_bg = if is_tool
-            || window_id == self.main_window_id
-            || self.popout_windows.contains_key(&window_id)
-        {
-            self.settings_handle.borrow().data.background_color
+        let is_visual =
+            window_id == self.main_window_id || self.popout_windows.contains_key(&window_id);
+        let (background, opacity) = {
+            let data = &self.settings_handle.borrow().data;
+            (data.background_color, data.opacity.alpha())
+        };
+        let pulse = if is_visual {
+            self.background_pulse()
+        } else {
+            0.0
+        };
+        let custom_bg = if is_tool {
+            background.map(|c| with_alpha(c.into(), 1.0))
+        } else if is_visual {
+            // Fading and pulsing need an explicit background even when none is configured.
+            let c: Option<iced::Color> = background.map(Into::into);
+            let c = c.or((opacity < 1.0 || pulse > 0.0).then_some(theme::BG_BASE));
+            c.map(|c| with_alpha(lerp_color(c, iced::Color::WHITE, pulse), c.a * opacity))
         } else {
             None
-        }
-        .map(|c| {
-            let c: iced::Color = c.into();
-            if is_tool { with_alpha(c, 1.0) } else { c }
-        });
+        };
         theme::theme(custom_bg)
     }
 
@@ -431,10 +587,10 @@ impl UiApp {
             self.dock_popout(popout);
             return window::close(source_window);
         }
-        let Some(kind) = self.visuals_page.hovered_visual() else {
+        let Some(visual) = self.visuals_page.hovered_visual() else {
             return Task::none();
         };
-        let task = self.open_popout_window(kind);
+        let task = self.open_popout_window(visual);
         self.sync_visuals_page();
         task
     }
@@ -442,8 +598,8 @@ impl UiApp {
     pub(super) fn sync_visuals_page(&mut self) {
         let snapshot = self.visual_manager.borrow().snapshot();
         self.visuals_page
-            .apply_snapshot_excluding(&snapshot, |kind| {
-                self.popout_windows.values().any(|w| w.kind == kind)
+            .apply_snapshot_excluding(&snapshot, |visual| {
+                self.popout_windows.values().any(|w| w.visual == visual)
             });
     }
 
@@ -475,16 +631,29 @@ impl UiApp {
         window_id: window::Id,
         new_size: Size,
     ) -> Task<Message> {
+        let ratio = self
+            .popout_windows
+            .get(&window_id)
+            .and_then(|popout| self.aspect_lock(popout.visual.kind).ratio());
         if let Some(popout) = self.popout_windows.get_mut(&window_id) {
-            let settings = popout_window_settings(new_size, true);
-            if popout_window_settings(popout.size, true) != settings {
+            let target = ratio.map_or(new_size, |ratio| locked_size(popout.size, new_size, ratio));
+            let settings = popout_window_settings(target, true, popout.display);
+            if popout_window_settings(popout.size, true, popout.display) != settings {
                 popout.size = Size::new(settings.width as f32, settings.height as f32);
-                let kind = popout.kind;
+                let visual = popout.visual;
                 self.settings_handle.update(|s| {
-                    s.data.visuals.popouts.insert(kind, settings);
+                    s.data.visuals.popouts.insert(visual, settings);
                 });
             }
-            return Task::none();
+            // Within a pixel is rounding, and answering it would loop.
+            let off = (target.width - new_size.width)
+                .abs()
+                .max((target.height - new_size.height).abs());
+            return if off > 1.0 {
+                window::resize(window_id, target)
+            } else {
+                Task::none()
+            };
         }
         if window_id != self.main_window_id {
             return Task::none();
@@ -617,10 +786,8 @@ impl UiApp {
             return Task::none();
         };
         let (new_id, open_task) = open_tool_base_window(self.use_layershell);
-        self.settings_window = Some((
-            new_id,
-            ActiveSettings::new(panel.kind, &self.visual_manager),
-        ));
+        let new_panel = self.settings_panel(panel.visual).with_trial_of(panel);
+        self.settings_window = Some((new_id, new_panel));
         Task::batch([open_task, window::close(old_id)])
     }
 
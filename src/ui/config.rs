@@ -1,27 +1,85 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // Copyright (C) 2026 Maika Namuo
 
-use crate::domain::routing::{CaptureMode, DeviceSelection, RoutingCommand};
-use crate::infra::pipewire::registry::RegistrySnapshot;
+use crate::domain::automation::{
+    AutomationRule, IdlePause, MAX_COMMAND_INTERVAL_SECS, MIN_COMMAND_INTERVAL_SECS, RuleAction,
+    RuleCommand, RuleCondition, RuleMetric, SilenceWatch,
+};
+use crate::domain::routing::{
+    CaptureMode, ChannelMatrix, DeviceSelection, MATRIX_INPUTS, MIXDOWN_CHANNELS, Mixdown,
+    RoutingCommand,
+};
+use crate::dsp::calibration::CalibrationCurve;
+use crate::dsp::dynamic_range::DynamicRange;
+use crate::dsp::replay::REPLAY_SECONDS;
+use crate::dsp::test_signal::{TestSignal, TestSignalKind};
+use crate::infra::pipewire::meter_tap::{self, GraphClock};
+use crate::infra::pipewire::registry::{NodeInfo, RegistrySnapshot};
+use crate::infra::pipewire::virtual_sink::{
+    self, DESIRED_LATENCY_FRAMES, MAX_LOOPBACK_DELAY_MS, NegotiatedFormat,
+};
+use crate::persistence::session;
 use crate::persistence::settings::{
-    BAR_MAX_HEIGHT, BAR_MIN_HEIGHT, BUILTIN_THEME, BarAlignment, SettingsHandle, ThemeChoice,
-    ThemeFile, ThemeOrigin, canonical_theme_name,
+    APPLICATION_TRIM_MIN_DB, BAR_MAX_HEIGHT, BAR_MIN_HEIGHT, BUILTIN_THEME, BarAlignment,
+    BeatPulseTarget, MicCalibration, OUTPUT_DIM_MIN_DB, OUTPUT_DIM_MUTE_DB, RenderBackend,
+    SettingsHandle, ThemeChoice, ThemeFile, ThemeOrigin, WINDOW_OPACITY_MIN, canonical_theme_name,
+    data_dir,
 };
+use crate::persistence::tracks::{self, TrackResult};
 use crate::ui::subscription::channel_subscription;
 use crate::ui::theme;
 use crate::ui::widgets::palette_editor::{PaletteEditor, PaletteEvent};
 use crate::ui::widgets::scroll_glow::ScrollGlow;
-use crate::ui::widgets::{SliderRange, action_button, card, pick, selectable_button, toggle};
-use crate::visuals::registry::{VisualKind, VisualManagerHandle, VisualSlotSnapshot};
+use crate::ui::widgets::{
+    SliderRange, Trial, action_button, card, keep_changes_card, pick, selectable_button, split,
+    toggle,
+};
+use crate::util::audio::musical::{MAX_A4_HZ, MIN_A4_HZ, Temperament, Tuning};
+use crate::util::audio::{
+    DB_FLOOR, DbRange, DecimalSeparator, LevelScale, MAX_CEILING_DB, MIN_RANGE_DB, NumberFormat,
+    db_to_gain,
+};
+use crate::util::utc_clock;
+use crate::visuals::options::SnapshotRate;
+use crate::visuals::registry::{
+    MAX_INSTANCES, VisualId, VisualKind, VisualManagerHandle, VisualSlotSnapshot,
+};
 use async_channel::Receiver as AsyncReceiver;
-use iced::widget::{Column, Row, column, container, pick_list, row, text, text_input};
-use iced::{Element, Length, Subscription};
+use iced::widget::{Column, Row, column, container, pick_list, row, scrollable, text, text_input};
+use iced::{Element, Length, Subscription, window};
 use iced_layershell::actions::OutputSnapshot;
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
+use std::path::PathBuf;
 use std::sync::{Arc, mpsc};
+use std::time::{Instant, SystemTime};
 
 const GRID_COLUMNS: usize = 2;
 const MAX_DEVICE_NAME_LEN: usize = 48;
+const APPLICATION_TRIM_RANGE: SliderRange = SliderRange::new(APPLICATION_TRIM_MIN_DB, 0.0, 0.5);
+const MIXDOWN_WEIGHT_RANGE: SliderRange = SliderRange::new(0.0, 2.0, 0.05);
+const RULE_THRESHOLD_RANGE: SliderRange = SliderRange::new(-60.0, 3.0, 0.5);
+const RULE_PRE_RANGE: SliderRange = SliderRange::new(0.5, REPLAY_SECONDS, 0.5);
+const COMMAND_INTERVAL_RANGE: SliderRange =
+    SliderRange::new(MIN_COMMAND_INTERVAL_SECS, MAX_COMMAND_INTERVAL_SECS, 10.0);
+const IDLE_SECONDS_RANGE: SliderRange = SliderRange::new(5.0, 600.0, 5.0);
+const SILENCE_SECONDS_RANGE: SliderRange = SliderRange::new(2.0, 120.0, 1.0);
+const ANY_APPLICATION: &str = "Any audio";
+const TIMELINE_CAPACITY: usize = 200;
+const TIMELINE_HEIGHT: f32 = 160.0;
+const OPACITY_RANGE: SliderRange = SliderRange::new(WINDOW_OPACITY_MIN, 1.0, 0.01);
+const TUNING_RANGE: SliderRange = SliderRange::new(MIN_A4_HZ, MAX_A4_HZ, 0.5);
+const PULSE_RANGE: SliderRange = SliderRange::new(0.05, 1.0, 0.05);
+const LEVEL_FLOOR_RANGE: SliderRange = SliderRange::new(DB_FLOOR, -MIN_RANGE_DB, 1.0);
+const LEVEL_CEILING_RANGE: SliderRange = SliderRange::new(-24.0, MAX_CEILING_DB, 1.0);
+// Semitones from 1 kHz, spanning roughly 20 Hz to 20 kHz.
+const TEST_TONE_RANGE: SliderRange = SliderRange::new(-68.0, 52.0, 1.0);
+const TEST_LEVEL_RANGE: SliderRange = SliderRange::new(-60.0, 0.0, 1.0);
+const LISTED_SESSIONS: usize = 5;
+const LISTED_TRACKS: usize = 8;
+
+fn dr_label(value: Option<f32>) -> String {
+    value.map_or_else(|| "DR -".to_owned(), |dr| format!("DR{dr:.0}"))
+}
 
 fn truncate_label(label: &str, max_chars: usize) -> (&str, bool) {
     if label.chars().count() <= max_chars {
@@ -52,11 +110,55 @@ pub enum ConfigMessage {
     RegistryUpdated(RegistrySnapshot),
     ToggleChanged { node_id: u32, enabled: bool },
     ToggleApplicationsVisibility,
-    VisualToggled { kind: VisualKind, enabled: bool },
+    ApplicationTrim { name: String, db: f32 },
+    VisualToggled { visual: VisualId, enabled: bool },
+    VisualAdded(VisualKind),
+    VisualRemoved(VisualId),
     CaptureModeChanged(CaptureMode),
     CaptureDeviceChanged(DeviceSelection),
+    KeepCaptureDevice,
+    RevertCaptureDevice,
+    DeviceTrialTick(Instant),
+    LoopbackSinkChanged(DeviceSelection),
+    LoopbackDelayChanged(f32),
+    CalibrationPathInput(String),
+    ImportCalibration,
+    ClearCalibration,
+    ChannelRoutingToggled(bool),
+    ChannelRouted { output: usize, input: usize },
+    MixdownWeighted(bool),
+    MixdownWeight { channel: usize, weight: f32 },
+    MixdownLfeExcluded(bool),
+    ToggleOutputDim,
+    OutputDimChanged(f32),
+    ForceQuantum(u32),
+    TestSignalChanged(TestSignal),
+    RuleAdded,
+    RuleRemoved(usize),
+    RuleChanged(usize, AutomationRule),
+    RuleCommandChanged(RuleCommand),
+    IdlePauseChanged(IdlePause),
+    SilenceWatchChanged(SilenceWatch),
+    RecordSession(bool),
+    ReviewSession(PathBuf),
+    SplitByTrack(bool),
+    ResumeTrackLoudness(bool),
+    ClearTimeline,
+    ExportTracks,
+    MeasureDynamicRange(bool),
     BgPalette(PaletteEvent),
     DecorationsToggled(bool),
+    OpacityChanged(f32),
+    TuningChanged(f32),
+    DecimalSeparatorChanged(DecimalSeparator),
+    TemperamentChanged(Temperament),
+    BeatPulseChanged(BeatPulseTarget),
+    BeatPulseIntensity(f32),
+    LevelRangeChanged(LevelScale, DbRange),
+    AlignLatencyToggled(bool),
+    SnapshotRateChanged(SnapshotRate),
+    VerticalLayoutToggled(bool),
+    RenderBackendChanged(RenderBackend),
     BarModeToggled(bool),
     BarAlignmentChanged(BarAlignment),
     BarHeightChanged(u32),
@@ -67,13 +169,33 @@ pub enum ConfigMessage {
     Scrolled(ScrollGlow),
 }
 
+/// A manual DR measurement, live or finished.
+#[derive(Debug, Clone)]
+pub struct DynamicRangeReading {
+    started: String,
+    seconds: f32,
+    value: Option<f32>,
+}
+
+impl DynamicRangeReading {
+    pub fn new(started: String, meter: &DynamicRange) -> Self {
+        Self {
+            started,
+            seconds: meter.seconds() as f32,
+            value: meter.value(),
+        }
+    }
+}
+
 struct ApplicationRow {
     node_id: u32,
+    name: String,
     label: String,
+    running: bool,
 }
 
 impl ApplicationRow {
-    fn from_node(node: &crate::infra::pipewire::registry::NodeInfo) -> Self {
+    fn from_node(node: &NodeInfo) -> Self {
         let primary = node
             .app_name()
             .map(str::to_owned)
@@ -81,13 +203,15 @@ impl ApplicationRow {
             .unwrap_or_else(|| node.capture_device_token());
         let node_label = node.capture_device_token();
         let label = if primary.eq_ignore_ascii_case(&node_label) {
-            primary
+            primary.clone()
         } else {
             format!("{primary} ({node_label})")
         };
         Self {
             node_id: node.id,
+            name: primary,
             label,
+            running: node.running,
         }
     }
 }
@@ -103,14 +227,34 @@ pub struct ConfigPage {
     applications: Vec<ApplicationRow>,
     hardware_sink_label: String,
     hardware_sink_last_known: Option<String>,
+    forced_quantum: Option<u32>,
     registry_ready: bool,
     applications_expanded: bool,
     device_choices: Vec<DeviceOption>,
     selected_device: DeviceSelection,
+    // Device from before an unconfirmed switch.
+    device_trial: Option<Trial<DeviceSelection>>,
+    loopback_choices: Vec<DeviceOption>,
+    selected_loopback: DeviceSelection,
+    output_dimmed: bool,
     bg_palette: PaletteEditor,
     scroll: ScrollGlow,
     theme_choices: Vec<ThemeChoice>,
     save_theme_name: String,
+    calibration_path: String,
+    calibration_error: Option<String>,
+    // Session-only, so the app never starts up playing a test signal.
+    test_signal: TestSignal,
+    recording: bool,
+    sessions: Vec<PathBuf>,
+    splitting: bool,
+    current_track: Option<String>,
+    tracks: Vec<TrackResult>,
+    measuring_dr: bool,
+    dynamic_range: Option<DynamicRangeReading>,
+    dynamic_ranges: Vec<DynamicRangeReading>,
+    // Routing and audio events this session, oldest first.
+    timeline: VecDeque<(SystemTime, String)>,
 }
 
 impl ConfigPage {
@@ -123,12 +267,32 @@ impl ConfigPage {
     ) -> Self {
         use theme::background as bg;
 
-        let (current_bg, last_device_name, theme_choices) = {
+        let (current_bg, last_device_name, loopback_sink, theme_choices) = {
             let guard = settings.borrow();
             let data = &guard.data;
+            visual_manager
+                .borrow_mut()
+                .set_content_opacity(data.opacity.alpha());
+            visual_manager
+                .borrow_mut()
+                .set_tuning(Tuning::new(data.tuning.a4_hz, data.tuning.temperament));
+            visual_manager
+                .borrow_mut()
+                .set_number_format(NumberFormat::new(data.decimal_separator));
+            visual_manager
+                .borrow_mut()
+                .set_level_ranges(&data.level_ranges);
+            meter_tap::set_channel_matrix(&data.channel_matrix);
+            visual_manager.borrow_mut().set_mixdown(&data.mixdown);
+            apply_calibration(
+                &visual_manager,
+                data.capture_mode,
+                data.mic_calibration.as_ref(),
+            );
             (
                 data.background_color.map_or(theme::BG_BASE, Into::into),
                 data.last_device_name.clone(),
+                data.loopback_sink.clone(),
                 guard.theme_store().list(),
             )
         };
@@ -147,30 +311,57 @@ impl ConfigPage {
             applications: Vec::new(),
             hardware_sink_label: String::from("(detecting hardware sink...)"),
             hardware_sink_last_known: None,
+            forced_quantum: None,
             registry_ready: false,
             applications_expanded: false,
             device_choices: Vec::new(),
             selected_device: DeviceSelection::from_token(last_device_name),
+            device_trial: None,
+            loopback_choices: Vec::new(),
+            selected_loopback: DeviceSelection::from_token(loopback_sink),
+            output_dimmed: false,
             bg_palette,
             scroll: ScrollGlow::default(),
             theme_choices,
             save_theme_name: String::new(),
+            calibration_path: String::new(),
+            calibration_error: None,
+            test_signal: TestSignal::default(),
+            recording: false,
+            sessions: session::list(),
+            splitting: false,
+            current_track: None,
+            tracks: Vec::new(),
+            measuring_dr: false,
+            dynamic_range: None,
+            dynamic_ranges: Vec::new(),
+            timeline: VecDeque::new(),
         }
     }
 
     pub fn subscription(&self) -> Subscription<ConfigMessage> {
-        self.registry_updates
+        let registry = self
+            .registry_updates
             .as_ref()
             .map_or_else(Subscription::none, |receiver| {
                 channel_subscription(Arc::clone(receiver)).map(ConfigMessage::RegistryUpdated)
-            })
+            });
+        if self.device_trial.is_none() {
+            return registry;
+        }
+        Subscription::batch([
+            registry,
+            window::frames().map(ConfigMessage::DeviceTrialTick),
+        ])
     }
 
     pub fn update(&mut self, message: ConfigMessage) {
         match message {
             ConfigMessage::RegistryUpdated(snapshot) => {
+                // The first snapshot is the starting state, not a change.
+                let announce = self.registry_ready;
                 self.registry_ready = true;
-                self.apply_snapshot(snapshot);
+                self.apply_snapshot(snapshot, announce);
             }
             ConfigMessage::ToggleChanged { node_id, enabled } => {
                 if enabled {
@@ -179,30 +370,222 @@ impl ConfigPage {
                     self.disabled_applications.insert(node_id);
                 }
                 self.send_routing(RoutingCommand::SetApplicationEnabled { node_id, enabled });
+                if let Some(app) = self.applications.iter().find(|app| app.node_id == node_id) {
+                    let event = if enabled {
+                        format!("Routed {} to the meters", app.label)
+                    } else {
+                        format!("Unrouted {} from the meters", app.label)
+                    };
+                    self.note_event(event);
+                }
             }
             ConfigMessage::ToggleApplicationsVisibility => {
                 self.applications_expanded = !self.applications_expanded;
             }
-            ConfigMessage::VisualToggled { kind, enabled } => {
-                self.visual_manager.borrow_mut().set_enabled(kind, enabled);
+            ConfigMessage::ApplicationTrim { name, db } => {
+                let db = APPLICATION_TRIM_RANGE.snap(db);
+                self.settings.update(|s| {
+                    if db < 0.0 {
+                        s.data.application_trims.insert(name.clone(), db);
+                    } else {
+                        s.data.application_trims.remove(&name);
+                    }
+                });
+                self.dispatch_trims(|app| app.name == name);
+            }
+            ConfigMessage::VisualToggled { visual, enabled } => {
+                self.visual_manager
+                    .borrow_mut()
+                    .set_enabled(visual, enabled);
                 self.settings.update(|s| {
-                    s.data.visuals.modules.entry(kind).or_default().enabled = Some(enabled);
+                    s.data.visuals.modules.entry(visual).or_default().enabled = Some(enabled);
                 });
             }
+            ConfigMessage::VisualAdded(kind) => {
+                let mut manager = self.visual_manager.borrow_mut();
+                if let Some(visual) = manager.add_instance(kind) {
+                    let module = manager.module_settings(visual).unwrap_or_default();
+                    let order = manager.order();
+                    drop(manager);
+                    self.settings.update(|s| {
+                        s.data.visuals.modules.insert(visual, module);
+                        s.data.visuals.order = order;
+                    });
+                }
+            }
+            ConfigMessage::VisualRemoved(visual) => {
+                let mut manager = self.visual_manager.borrow_mut();
+                if manager.remove_instance(visual) {
+                    let order = manager.order();
+                    drop(manager);
+                    self.settings.update(|s| {
+                        let visuals = &mut s.data.visuals;
+                        visuals.modules.remove(&visual);
+                        visuals.width_basis.remove(&visual);
+                        visuals.height_basis.remove(&visual);
+                        visuals.popouts.remove(&visual);
+                        visuals.order = order;
+                    });
+                }
+            }
             ConfigMessage::CaptureModeChanged(mode) => {
                 if self.settings.borrow().data.capture_mode != mode {
                     self.settings.update(|s| s.data.capture_mode = mode);
                     self.dispatch_capture_state();
                 }
             }
+            // A device that stalls or floods the graph can leave the
+            // window unresponsive, so the switch reverts unless kept.
             ConfigMessage::CaptureDeviceChanged(selection) => {
                 if self.selected_device != selection {
+                    let previous = match self.device_trial.take() {
+                        Some(trial) => trial.previous,
+                        None => self.selected_device.clone(),
+                    };
+                    self.device_trial = Some(Trial::new(previous, Instant::now()));
+                    self.select_capture_device(selection);
+                }
+            }
+            ConfigMessage::KeepCaptureDevice => self.device_trial = None,
+            ConfigMessage::RevertCaptureDevice => self.revert_capture_device(),
+            ConfigMessage::DeviceTrialTick(now) => {
+                if self
+                    .device_trial
+                    .as_ref()
+                    .is_some_and(|trial| trial.is_due(now))
+                {
+                    self.revert_capture_device();
+                }
+            }
+            ConfigMessage::LoopbackSinkChanged(selection) => {
+                if self.selected_loopback != selection {
                     let token = selection.token().map(str::to_owned);
-                    self.selected_device = selection;
-                    self.dispatch_capture_state();
-                    self.settings.update(|s| s.data.last_device_name = token);
+                    self.selected_loopback = selection;
+                    self.dispatch_loopback_sink();
+                    self.settings.update(|s| s.data.loopback_sink = token);
                 }
             }
+            ConfigMessage::LoopbackDelayChanged(ms) => {
+                self.settings.update(|s| s.data.loopback_delay_ms = ms);
+                self.send_routing(RoutingCommand::SetLoopbackDelay(ms));
+            }
+            ConfigMessage::CalibrationPathInput(path) => {
+                self.calibration_path = path;
+                self.calibration_error = None;
+            }
+            ConfigMessage::ImportCalibration => self.import_calibration(),
+            ConfigMessage::ClearCalibration => {
+                self.settings.update(|s| s.data.mic_calibration = None);
+                self.dispatch_capture_state();
+            }
+            ConfigMessage::ChannelRoutingToggled(enabled) => {
+                let matrix = if enabled {
+                    ChannelMatrix::stereo()
+                } else {
+                    ChannelMatrix::default()
+                };
+                meter_tap::set_channel_matrix(&matrix);
+                self.settings.update(|s| s.data.channel_matrix = matrix);
+            }
+            ConfigMessage::ChannelRouted { output, input } => {
+                self.settings.update(|s| {
+                    s.data.channel_matrix.toggle(output, input);
+                    meter_tap::set_channel_matrix(&s.data.channel_matrix);
+                });
+            }
+            ConfigMessage::MixdownWeighted(enabled) => {
+                let weights = if enabled {
+                    vec![1.0; self.analysis_channels()]
+                } else {
+                    Vec::new()
+                };
+                self.update_mixdown(|mixdown| mixdown.weights = weights);
+            }
+            ConfigMessage::MixdownWeight { channel, weight } => self.update_mixdown(|mixdown| {
+                if let Some(slot) = mixdown.weights.get_mut(channel) {
+                    *slot = MIXDOWN_WEIGHT_RANGE.snap(weight);
+                }
+            }),
+            ConfigMessage::MixdownLfeExcluded(exclude) => {
+                self.update_mixdown(|mixdown| mixdown.exclude_lfe = exclude);
+            }
+            // Only the application loopback has monitor ports to dim.
+            ConfigMessage::ToggleOutputDim => {
+                if self.settings.borrow().data.capture_mode != CaptureMode::Applications {
+                    return;
+                }
+                self.output_dimmed = !self.output_dimmed;
+                self.dispatch_output_gain();
+            }
+            ConfigMessage::ForceQuantum(frames) => {
+                self.send_routing(RoutingCommand::ForceQuantum(frames));
+            }
+            ConfigMessage::TestSignalChanged(signal) => {
+                self.test_signal = signal;
+                meter_tap::set_test_signal(signal);
+            }
+            ConfigMessage::OutputDimChanged(amount_db) => {
+                self.settings
+                    .update(|s| s.data.output_dim.amount_db = amount_db);
+                if self.output_dimmed {
+                    self.dispatch_output_gain();
+                }
+            }
+            ConfigMessage::RuleAdded => {
+                self.settings
+                    .update(|s| s.data.rules.push(AutomationRule::default()));
+            }
+            ConfigMessage::RuleRemoved(index) => self.settings.update(|s| {
+                if index < s.data.rules.len() {
+                    s.data.rules.remove(index);
+                }
+            }),
+            ConfigMessage::RuleChanged(index, rule) => self.settings.update(|s| {
+                if let Some(slot) = s.data.rules.get_mut(index) {
+                    *slot = rule;
+                }
+            }),
+            ConfigMessage::RuleCommandChanged(command) => self.settings.update(|s| {
+                s.data.rule_command = RuleCommand {
+                    min_interval_secs: COMMAND_INTERVAL_RANGE.snap(command.min_interval_secs),
+                    ..command
+                };
+            }),
+            ConfigMessage::IdlePauseChanged(idle) => self.settings.update(|s| {
+                s.data.idle_pause = IdlePause {
+                    silence_secs: IDLE_SECONDS_RANGE.snap(idle.silence_secs),
+                    ..idle
+                };
+            }),
+            ConfigMessage::SilenceWatchChanged(watch) => self.settings.update(|s| {
+                s.data.silence_watch = SilenceWatch {
+                    silence_secs: SILENCE_SECONDS_RANGE.snap(watch.silence_secs),
+                    ..watch
+                };
+            }),
+            // The app owns the recorder; this only tracks what to show.
+            ConfigMessage::RecordSession(recording) => {
+                self.recording = recording;
+                if !recording {
+                    self.sessions = session::list();
+                }
+            }
+            ConfigMessage::ReviewSession(_) => {}
+            ConfigMessage::SplitByTrack(enabled) => self.splitting = enabled,
+            ConfigMessage::ResumeTrackLoudness(resume) => {
+                self.settings
+                    .update(|s| s.data.resume_track_loudness = resume);
+            }
+            // The app owns the meter and reports readings back.
+            ConfigMessage::MeasureDynamicRange(measuring) => {
+                self.measuring_dr = measuring;
+                self.dynamic_range = None;
+            }
+            ConfigMessage::ClearTimeline => self.timeline.clear(),
+            ConfigMessage::ExportTracks => match tracks::export(&self.tracks) {
+                Ok(path) => tracing::info!("[tracks] exported {}", path.display()),
+                Err(err) => tracing::warn!("[tracks] failed to export: {err}"),
+            },
             ConfigMessage::BgPalette(event) => {
                 if self.bg_palette.update(event) {
                     let color = self.bg_palette.colors().first().copied();
@@ -216,6 +599,67 @@ impl ConfigPage {
             ConfigMessage::DecorationsToggled(v) => {
                 self.settings.update(|s| s.data.decorations = v);
             }
+            ConfigMessage::OpacityChanged(level) => {
+                self.settings.update(|s| s.data.opacity.level = level);
+                let alpha = self.settings.borrow().data.opacity.alpha();
+                self.visual_manager.borrow_mut().set_content_opacity(alpha);
+            }
+            ConfigMessage::TuningChanged(a4_hz) => {
+                let a4_hz = TUNING_RANGE.snap(a4_hz);
+                self.settings.update(|s| s.data.tuning.a4_hz = a4_hz);
+                let temperament = self.settings.borrow().data.tuning.temperament;
+                self.visual_manager
+                    .borrow_mut()
+                    .set_tuning(Tuning::new(a4_hz, temperament));
+            }
+            ConfigMessage::DecimalSeparatorChanged(separator) => {
+                self.settings
+                    .update(|s| s.data.decimal_separator = separator);
+                self.visual_manager
+                    .borrow_mut()
+                    .set_number_format(NumberFormat::new(separator));
+            }
+            ConfigMessage::TemperamentChanged(temperament) => {
+                self.settings
+                    .update(|s| s.data.tuning.temperament = temperament);
+                let a4_hz = self.settings.borrow().data.tuning.a4_hz;
+                self.visual_manager
+                    .borrow_mut()
+                    .set_tuning(Tuning::new(a4_hz, temperament));
+            }
+            ConfigMessage::BeatPulseChanged(target) => {
+                self.settings.update(|s| s.data.beat_pulse.target = target);
+            }
+            ConfigMessage::BeatPulseIntensity(intensity) => {
+                let intensity = PULSE_RANGE.snap(intensity);
+                self.settings
+                    .update(|s| s.data.beat_pulse.intensity = intensity);
+            }
+            ConfigMessage::LevelRangeChanged(scale, range) => {
+                let range = DbRange::new(
+                    LEVEL_FLOOR_RANGE.snap(range.floor_db),
+                    LEVEL_CEILING_RANGE.snap(range.ceiling_db),
+                )
+                .sanitized(scale.default_range());
+                self.settings
+                    .update(|s| *s.data.level_ranges.get_mut(scale) = range);
+                let ranges = self.settings.borrow().data.level_ranges;
+                self.visual_manager.borrow_mut().set_level_ranges(&ranges);
+            }
+            ConfigMessage::AlignLatencyToggled(v) => {
+                self.visual_manager.borrow_mut().set_align_latency(v);
+                self.settings.update(|s| s.data.visuals.align_latency = v);
+            }
+            ConfigMessage::SnapshotRateChanged(v) => {
+                self.visual_manager.borrow_mut().set_snapshot_rate(v);
+                self.settings.update(|s| s.data.visuals.snapshot_rate = v);
+            }
+            ConfigMessage::VerticalLayoutToggled(v) => {
+                self.settings.update(|s| s.data.visuals.vertical = v);
+            }
+            ConfigMessage::RenderBackendChanged(v) => {
+                self.settings.update(|s| s.data.render_backend = v);
+            }
             ConfigMessage::BarModeToggled(v) => self.settings.update(|s| s.data.bar.enabled = v),
             ConfigMessage::BarAlignmentChanged(v) => {
                 self.settings.update(|s| s.data.bar.alignment = v);
@@ -243,9 +687,16 @@ impl ConfigPage {
         let snapshot = self.visual_manager.borrow().snapshot();
         let mut content = column![
             self.render_capture_card(),
+            self.render_diagnostics_card(),
             self.render_visuals_card(&snapshot),
             self.render_theme_card(),
             self.render_global_card(),
+            self.render_levels_card(),
+            self.render_automation_card(),
+            self.render_sessions_card(),
+            self.render_tracks_card(),
+            self.render_dynamic_range_card(),
+            self.render_timeline_card(),
         ]
         .spacing(theme::SECTION_GAP);
         if self.bar_supported {
@@ -254,18 +705,316 @@ impl ConfigPage {
         self.scroll.vertical(content, ConfigMessage::Scrolled)
     }
 
+    // Readouts here follow the same decimal separator as the visuals.
+    fn numbers(&self) -> NumberFormat {
+        self.visual_manager.borrow().number_format()
+    }
+
+    fn render_timeline_card(&self) -> container::Container<'_, ConfigMessage> {
+        let content = if self.timeline.is_empty() {
+            form!(
+                text(
+                    "Streams, routing toggles, default sink changes, capture glitches and \
+                     format changes show up here."
+                )
+                .size(theme::BODY_TEXT_SIZE)
+                .style(theme::weak_text_style);
+            )
+        } else {
+            let events = self.timeline.iter().map(|(at, event)| {
+                text(format!("{}  {event}", utc_clock(*at)))
+                    .size(theme::BODY_TEXT_SIZE)
+                    .into()
+            });
+            form!(
+                scrollable(Column::with_children(events).spacing(2))
+                    .anchor_bottom()
+                    .height(Length::Fixed(TIMELINE_HEIGHT));
+                action_button("Clear", Some(ConfigMessage::ClearTimeline));
+            )
+        };
+        card("Event timeline (UTC)", content)
+    }
+
     fn render_capture_card(&self) -> container::Container<'_, ConfigMessage> {
         let mode = self.settings.borrow().data.capture_mode;
-        let content = form!(
+        let mut content = form!(
             pick("Mode", CaptureMode::ALL, mode, ConfigMessage::CaptureModeChanged);
             match mode {
                 CaptureMode::Applications => self.render_applications_section(),
                 CaptureMode::Device => self.render_device_section(),
             };
         );
+        if mode == CaptureMode::Applications {
+            content = content
+                .push(self.render_loopback_sink())
+                .push(self.render_loopback_delay())
+                .push(self.render_output_dim());
+        }
+        content = content
+            .push(self.render_channel_routing())
+            .push(self.render_mixdown())
+            .push(self.render_test_signal());
         card("Audio Capture", content)
     }
 
+    fn render_diagnostics_card(&self) -> container::Container<'_, ConfigMessage> {
+        let content = form!(
+            self.render_graph_clock();
+            self.render_cpu_load();
+            self.render_stream_formats();
+        );
+        card("Diagnostics", content)
+    }
+
+    fn render_test_signal(&self) -> Column<'_, ConfigMessage> {
+        use ConfigMessage::TestSignalChanged;
+        let signal = self.test_signal;
+        let mut section = form!(
+            pick("Test signal", TestSignalKind::ALL, signal.kind, move |kind| {
+                TestSignalChanged(TestSignal { kind, ..signal })
+            });
+        );
+        if signal.kind == TestSignalKind::Tone {
+            let semitones = 12.0 * (signal.frequency_hz / 1_000.0).log2();
+            section = section.push(slider!(
+                self.numbers();
+                "Frequency",
+                semitones,
+                TEST_TONE_RANGE,
+                move |semitones| TestSignalChanged(TestSignal {
+                    frequency_hz: 1_000.0 * (semitones / 12.0).exp2(),
+                    ..signal
+                }),
+                self.numbers().freq(signal.frequency_hz)
+            ));
+        }
+        if signal.is_active() {
+            section = section
+                .push(slider!(
+                    self.numbers();
+                    "Level",
+                    signal.level_db,
+                    TEST_LEVEL_RANGE,
+                    move |level_db| TestSignalChanged(TestSignal { level_db, ..signal }),
+                    format!("{:.0} dBFS", signal.level_db)
+                ))
+                .push(
+                    text("Capture is bypassed: the visuals see only the test signal.")
+                        .size(theme::BODY_TEXT_SIZE)
+                        .style(theme::weak_text_style),
+                );
+        }
+        section
+    }
+
+    fn render_graph_clock(&self) -> Column<'_, ConfigMessage> {
+        let clock = meter_tap::graph_clock();
+        let status = clock.map_or_else(
+            || "Graph: waiting for audio".to_owned(),
+            |clock| {
+                self.numbers().localize(format!(
+                    "Graph: {} frames at {} Hz ({:.1} ms)",
+                    clock.quantum,
+                    clock.rate,
+                    clock.millis()
+                ))
+            },
+        );
+        let glitches = meter_tap::glitch_counts();
+        let mut section = column![
+            text(status)
+                .size(theme::BODY_TEXT_SIZE)
+                .style(theme::weak_text_style),
+            text(format!(
+                "Sink cycle gaps: {}, dropped capture packets: {}",
+                glitches.gaps, glitches.dropped
+            ))
+            .size(theme::BODY_TEXT_SIZE)
+            .style(theme::weak_text_style),
+        ]
+        .spacing(theme::CONTROL_GAP);
+        if let Some(frames) = self.forced_quantum {
+            section = section.push(
+                row![
+                    text(format!("Quantum forced to {frames} frames"))
+                        .size(theme::BODY_TEXT_SIZE)
+                        .width(Length::Fill),
+                    action_button("Release", Some(ConfigMessage::ForceQuantum(0))).padding([4, 8]),
+                ]
+                .spacing(theme::CONTROL_GAP)
+                .align_y(iced::alignment::Vertical::Center),
+            );
+        } else if clock.is_some_and(GraphClock::is_sluggish) {
+            section = section.push(
+                text("Large quantum: meters will update in coarse steps.")
+                    .size(theme::BODY_TEXT_SIZE)
+                    .style(text::danger),
+            );
+            section = section.push(action_button(
+                format!("Force {DESIRED_LATENCY_FRAMES}-frame quantum (whole graph, until exit)"),
+                Some(ConfigMessage::ForceQuantum(DESIRED_LATENCY_FRAMES)),
+            ));
+        }
+        section
+    }
+
+    fn render_cpu_load(&self) -> Column<'_, ConfigMessage> {
+        let loads = self.visual_manager.borrow().loads();
+        let total: f64 = loads.iter().map(|(_, load)| load).sum();
+        let line = |label: String| {
+            text(self.numbers().localize(label))
+                .size(theme::BODY_TEXT_SIZE)
+                .style(theme::weak_text_style)
+        };
+        let mut section = column![line(format!(
+            "CPU: {:.1}% audio callbacks, {:.1}% visuals",
+            virtual_sink::callback_load() * 100.0,
+            total * 100.0
+        ))]
+        .spacing(theme::CONTROL_GAP);
+        let manager = self.visual_manager.borrow();
+        for (id, load) in loads {
+            let latency = manager
+                .display_latency_ms(id)
+                .map_or_else(String::new, |ms| format!(", {ms:.0} ms capture to draw"));
+            section = section.push(line(format!("  {id}: {:.1}%{latency}", load * 100.0)));
+        }
+        section
+    }
+
+    fn render_stream_formats(&self) -> Column<'_, ConfigMessage> {
+        let line = |label: &str, format: Option<NegotiatedFormat>| {
+            let style: fn(&iced::Theme) -> text::Style = match &format {
+                Some(format) if !format.is_supported() => text::danger,
+                _ => theme::weak_text_style,
+            };
+            let status = format.map_or_else(|| "not negotiated".to_owned(), |f| f.describe());
+            text(format!("{label}: {status}"))
+                .size(theme::BODY_TEXT_SIZE)
+                .style(style)
+        };
+        let note = |status: String| {
+            text(status)
+                .size(theme::BODY_TEXT_SIZE)
+                .style(theme::weak_text_style)
+        };
+        let sink = virtual_sink::sink_format();
+        let (mode, delay_ms) = {
+            let data = &self.settings.borrow().data;
+            (data.capture_mode, data.loopback_delay_ms)
+        };
+        let mut section = column![line("Sink", sink.clone())];
+        // Undelayed, the sink's monitor ports link straight to the output.
+        if mode == CaptureMode::Applications {
+            section = section.push(if delay_ms > 0.0 {
+                line("Delayed loopback", virtual_sink::loopback_format())
+            } else {
+                note("Loopback: sink monitor linked directly, in the sink's format".to_owned())
+            });
+        }
+        // The tap reads the sink's samples into f32 at the graph rate.
+        let tap = match (sink, meter_tap::graph_clock()) {
+            (Some(sink), Some(clock)) if sink.is_supported() => format!(
+                "Tap: {} read as f32, {} Hz, {} ch in, {} ch to visuals",
+                sink.format,
+                clock.rate,
+                meter_tap::input_channels(),
+                self.analysis_channels()
+            ),
+            _ => "Tap: waiting for audio".to_owned(),
+        };
+        section.push(note(tap)).spacing(theme::CONTROL_GAP)
+    }
+
+    // Channels reaching the visuals: the routing matrix rows, or the capture.
+    fn analysis_channels(&self) -> usize {
+        let matrix = &self.settings.borrow().data.channel_matrix;
+        if matrix.is_passthrough() {
+            meter_tap::input_channels()
+        } else {
+            matrix.0.len()
+        }
+        .clamp(2, MIXDOWN_CHANNELS)
+    }
+
+    fn update_mixdown(&self, edit: impl FnOnce(&mut Mixdown)) {
+        self.settings.update(|s| {
+            edit(&mut s.data.mixdown);
+            self.visual_manager
+                .borrow_mut()
+                .set_mixdown(&s.data.mixdown);
+        });
+    }
+
+    fn render_mixdown(&self) -> Column<'_, ConfigMessage> {
+        use ConfigMessage::{MixdownLfeExcluded, MixdownWeight, MixdownWeighted};
+        let mixdown = self.settings.borrow().data.mixdown.clone();
+        let mut section = form!(
+            toggle("Weighted mixdown", !mixdown.weights.is_empty(), MixdownWeighted);
+            toggle("Leave LFE out of the mixdown (5.1+)", mixdown.exclude_lfe, MixdownLfeExcluded);
+        );
+        let count = mixdown.weights.len();
+        for (channel, &weight) in mixdown.weights.iter().enumerate() {
+            let name = match (count, channel) {
+                (2, 0) => "Left".to_owned(),
+                (2, 1) => "Right".to_owned(),
+                _ => format!("Channel {}", channel + 1),
+            };
+            section = section.push(slider!(
+                self.numbers();
+                name,
+                weight,
+                MIXDOWN_WEIGHT_RANGE,
+                move |weight| MixdownWeight { channel, weight },
+                "{:.2}"
+            ));
+        }
+        section
+    }
+
+    // Rows are analysis channels, columns capture channels; a row with several
+    // inputs lit averages them.
+    fn render_channel_routing(&self) -> Column<'_, ConfigMessage> {
+        use ConfigMessage::{ChannelRouted, ChannelRoutingToggled};
+        let matrix = self.settings.borrow().data.channel_matrix.clone();
+        let mut section = Column::new().spacing(theme::CONTROL_GAP).push(toggle(
+            "Route input channels",
+            !matrix.is_passthrough(),
+            ChannelRoutingToggled,
+        ));
+        if matrix.is_passthrough() {
+            return section;
+        }
+        let inputs = meter_tap::input_channels()
+            .max(matrix.inputs())
+            .clamp(2, MATRIX_INPUTS);
+        for output in 0..matrix.0.len() {
+            let name = match (matrix.0.len(), output) {
+                (2, 0) => "L".to_owned(),
+                (2, 1) => "R".to_owned(),
+                _ => format!("{}", output + 1),
+            };
+            let mut cells = Row::new()
+                .spacing(4)
+                .align_y(iced::alignment::Vertical::Center)
+                .push(
+                    text(name)
+                        .size(theme::BODY_TEXT_SIZE)
+                        .width(Length::Fixed(16.0)),
+                );
+            for input in 0..inputs {
+                cells = cells.push(selectable_button(
+                    format!("{}", input + 1),
+                    matrix.routes(output, input),
+                    ChannelRouted { output, input },
+                ));
+            }
+            section = section.push(cells.wrap());
+        }
+        section
+    }
+
     fn render_applications_section(&self) -> Column<'_, ConfigMessage> {
         let status_suffix: String = match (
             self.applications.len(),
@@ -300,7 +1049,7 @@ impl ConfigPage {
                 render_toggle_grid(&self.applications, |entry| {
                     let enabled = !self.disabled_applications.contains(&entry.node_id);
                     (
-                        entry.label.as_str(),
+                        entry.label.clone(),
                         enabled,
                         ConfigMessage::ToggleChanged {
                             node_id: entry.node_id,
@@ -311,10 +1060,123 @@ impl ConfigPage {
                 .into()
             };
             section = section.push(content);
+            section = section.push(self.render_application_trims());
         }
         section
     }
 
+    // One trim per application name, since that is what they are saved by.
+    fn render_application_trims(&self) -> Column<'_, ConfigMessage> {
+        let trims = self.settings.borrow().data.application_trims.clone();
+        let mut names: Vec<&str> = self
+            .applications
+            .iter()
+            .filter(|app| !self.disabled_applications.contains(&app.node_id))
+            .map(|app| app.name.as_str())
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        let mut section = Column::new().spacing(theme::CONTROL_GAP);
+        for name in names {
+            let db = trims.get(name).copied().unwrap_or(0.0);
+            let owned = name.to_owned();
+            section = section.push(slider!(
+                self.numbers();
+                format!("{name} trim"),
+                db,
+                APPLICATION_TRIM_RANGE,
+                move |db| ConfigMessage::ApplicationTrim {
+                    name: owned.clone(),
+                    db,
+                },
+                "{:.1} dB"
+            ));
+        }
+        section
+    }
+
+    fn render_loopback_sink(&self) -> Row<'_, ConfigMessage> {
+        let selected = self
+            .loopback_choices
+            .iter()
+            .find(|opt| opt.selection == self.selected_loopback)
+            .cloned()
+            .unwrap_or_else(|| DeviceOption {
+                label: "Default sink".to_owned(),
+                selection: DeviceSelection::Default,
+            });
+        pick(
+            "Listen on",
+            self.loopback_choices.as_slice(),
+            selected,
+            |opt| ConfigMessage::LoopbackSinkChanged(opt.selection),
+        )
+    }
+
+    fn render_loopback_delay(&self) -> Column<'_, ConfigMessage> {
+        let ms = self.settings.borrow().data.loopback_delay_ms;
+        let range = SliderRange::new(0.0, MAX_LOOPBACK_DELAY_MS, 5.0);
+        column![
+            slider!(
+                self.numbers();
+                "Delay",
+                ms,
+                range,
+                ConfigMessage::LoopbackDelayChanged,
+                if ms > 0.0 {
+                    format!("{ms:.0} ms")
+                } else {
+                    "Off".to_owned()
+                }
+            ),
+            text("Delays what you hear; the meters stay live.")
+                .size(theme::BODY_TEXT_SIZE)
+                .style(theme::weak_text_style),
+        ]
+        .spacing(theme::CONTROL_GAP)
+    }
+
+    fn render_output_dim(&self) -> Column<'_, ConfigMessage> {
+        let amount_db = self.settings.borrow().data.output_dim.amount_db;
+        let range = SliderRange::new(OUTPUT_DIM_MIN_DB, OUTPUT_DIM_MUTE_DB, 1.0);
+        let label = if self.output_dimmed {
+            "Output dimmed (d)"
+        } else {
+            "Dim output (d)"
+        };
+        column![
+            selectable_button(label, self.output_dimmed, ConfigMessage::ToggleOutputDim),
+            slider!(
+                self.numbers();
+                "Dim by",
+                amount_db,
+                range,
+                ConfigMessage::OutputDimChanged,
+                if amount_db >= OUTPUT_DIM_MUTE_DB {
+                    "Mute".to_owned()
+                } else {
+                    format!("{amount_db:.0} dB")
+                }
+            ),
+        ]
+        .spacing(theme::CONTROL_GAP)
+    }
+
+    fn select_capture_device(&mut self, selection: DeviceSelection) {
+        let token = selection.token().map(str::to_owned);
+        self.selected_device = selection;
+        self.dispatch_capture_state();
+        self.settings.update(|s| s.data.last_device_name = token);
+    }
+
+    fn revert_capture_device(&mut self) {
+        if let Some(trial) = self.device_trial.take()
+            && trial.previous != self.selected_device
+        {
+            self.select_capture_device(trial.previous);
+        }
+    }
+
     fn render_device_section(&self) -> Column<'_, ConfigMessage> {
         let selected = self
             .device_choices
@@ -329,24 +1191,100 @@ impl ConfigPage {
             picker = picker.placeholder("No devices available");
         }
 
-        column![
-            container(picker).width(Length::Fill).clip(true),
-            text("Direct device capture. Application routing disabled.")
-                .size(theme::BODY_TEXT_SIZE)
-                .style(theme::weak_text_style)
-        ]
-        .spacing(6)
+        let mut section = column![container(picker).width(Length::Fill).clip(true)].spacing(6);
+        if let Some(trial) = &self.device_trial {
+            section = section.push(keep_changes_card(
+                trial.seconds_left(Instant::now()),
+                ConfigMessage::KeepCaptureDevice,
+                ConfigMessage::RevertCaptureDevice,
+            ));
+        }
+        section
+            .push(
+                text("Direct device capture. Application routing disabled.")
+                    .size(theme::BODY_TEXT_SIZE)
+                    .style(theme::weak_text_style),
+            )
+            .push(self.render_mic_calibration())
+    }
+
+    fn render_mic_calibration(&self) -> Column<'_, ConfigMessage> {
+        let loaded = self.settings.borrow().data.mic_calibration.clone();
+        let path = self.calibration_path.trim();
+        let input = text_input("Mic calibration file (.txt, .cal)", &self.calibration_path)
+            .on_input(ConfigMessage::CalibrationPathInput)
+            .on_submit(ConfigMessage::ImportCalibration)
+            .size(theme::BODY_TEXT_SIZE)
+            .width(Length::Fill);
+        let import = action_button(
+            "Import",
+            (!path.is_empty()).then_some(ConfigMessage::ImportCalibration),
+        )
+        .padding([4, 8]);
+        let mut section =
+            column![row![input, import].spacing(theme::CONTROL_GAP)].spacing(theme::CONTROL_GAP);
+        if let Some(error) = &self.calibration_error {
+            section = section.push(
+                text(error.clone())
+                    .size(theme::BODY_TEXT_SIZE)
+                    .style(text::danger),
+            );
+        }
+        if let Some(cal) = loaded {
+            section = section.push(
+                row![
+                    text(format!(
+                        "Calibrated with {} ({} points)",
+                        cal.name,
+                        cal.points.len()
+                    ))
+                    .size(theme::BODY_TEXT_SIZE)
+                    .style(theme::weak_text_style)
+                    .width(Length::Fill),
+                    action_button("Remove", Some(ConfigMessage::ClearCalibration)).padding([4, 8]),
+                ]
+                .spacing(theme::CONTROL_GAP)
+                .align_y(iced::alignment::Vertical::Center),
+            );
+        }
+        section
     }
 
-    fn build_device_choices(&self, snapshot: &RegistrySnapshot) -> Vec<DeviceOption> {
+    fn import_calibration(&mut self) {
+        let path = PathBuf::from(self.calibration_path.trim());
+        let points = match std::fs::read_to_string(&path) {
+            Ok(text) => CalibrationCurve::parse(&text),
+            Err(e) => {
+                tracing::warn!("[calibration] failed to read {}: {e}", path.display());
+                self.calibration_error = Some(format!("Could not read the file: {e}"));
+                return;
+            }
+        };
+        let Some(points) = points else {
+            self.calibration_error = Some("No frequency and dB rows found.".to_owned());
+            return;
+        };
+        let name = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |n| n.to_string_lossy().into_owned(),
+        );
+        self.settings.update(|s| {
+            s.data.mic_calibration = Some(MicCalibration { name, points });
+        });
+        self.calibration_path.clear();
+        self.calibration_error = None;
+        self.dispatch_capture_state();
+    }
+
+    fn build_device_choices<'a>(
+        &self,
+        nodes: impl Iterator<Item = &'a NodeInfo>,
+    ) -> Vec<DeviceOption> {
         let mut choices = vec![DeviceOption {
             label: format!("Default sink - {}", self.hardware_sink_label),
             selection: DeviceSelection::Default,
         }];
-        let mut devices: Vec<_> = snapshot
-            .nodes
-            .iter()
-            .filter(|node| node.is_capture_device_candidate())
+        let mut devices: Vec<_> = nodes
             .map(|node| {
                 let token = node.capture_device_token();
                 DeviceOption {
@@ -361,16 +1299,485 @@ impl ConfigPage {
     }
 
     fn render_global_card(&self) -> container::Container<'_, ConfigMessage> {
-        use ConfigMessage::{BgPalette, DecorationsToggled};
-        let decorations = self.settings.borrow().data.decorations;
+        use ConfigMessage::{
+            AlignLatencyToggled, BeatPulseChanged, BeatPulseIntensity, BgPalette,
+            DecimalSeparatorChanged, DecorationsToggled, OpacityChanged, RenderBackendChanged,
+            SnapshotRateChanged, TemperamentChanged, TuningChanged, VerticalLayoutToggled,
+        };
+        let (decorations, align, rate, vertical, opacity, backend, tuning, pulse, separator) = {
+            let data = &self.settings.borrow().data;
+            (
+                data.decorations,
+                data.visuals.align_latency,
+                data.visuals.snapshot_rate,
+                data.visuals.vertical,
+                data.opacity.alpha(),
+                data.render_backend,
+                data.tuning,
+                data.beat_pulse,
+                data.decimal_separator,
+            )
+        };
         let content = column![
             self.bg_palette.view().map(BgPalette),
+            slider!(
+                self.numbers();
+                "Window opacity",
+                opacity,
+                OPACITY_RANGE,
+                OpacityChanged,
+                format!("{:.0}%", opacity * 100.0)
+            ),
             toggle("Window decorations", decorations, DecorationsToggled),
+            toggle("Align visual latency", align, AlignLatencyToggled),
+            pick(
+                "Visual update rate",
+                SnapshotRate::ALL,
+                rate,
+                SnapshotRateChanged
+            ),
+            toggle("Stack visuals vertically", vertical, VerticalLayoutToggled),
+            slider!(
+                self.numbers();
+                "Tuning reference (A4)",
+                tuning.a4_hz,
+                TUNING_RANGE,
+                TuningChanged,
+                format!("{:.1} Hz", tuning.a4_hz)
+            ),
+            pick(
+                "Temperament",
+                Temperament::ALL,
+                tuning.temperament,
+                TemperamentChanged
+            ),
+            pick(
+                "Decimal separator",
+                DecimalSeparator::ALL,
+                separator,
+                DecimalSeparatorChanged
+            ),
+            pick(
+                "Beat pulse",
+                BeatPulseTarget::ALL,
+                pulse.target,
+                BeatPulseChanged
+            ),
+            slider!(
+                self.numbers();
+                "Pulse intensity",
+                pulse.intensity,
+                PULSE_RANGE,
+                BeatPulseIntensity,
+                format!("{:.0}%", pulse.intensity * 100.0)
+            ),
+            pick(
+                "Renderer (restart to apply)",
+                RenderBackend::ALL,
+                backend,
+                RenderBackendChanged
+            ),
         ]
         .spacing(theme::SECTION_GAP);
         card("Global", content)
     }
 
+    fn render_levels_card(&self) -> container::Container<'_, ConfigMessage> {
+        use ConfigMessage::LevelRangeChanged;
+        let ranges = self.settings.borrow().data.level_ranges;
+        let content = LevelScale::ALL
+            .iter()
+            .fold(Column::new(), |content, &scale| {
+                let range = ranges.get(scale);
+                content.push(split(
+                    slider!(
+                        self.numbers();
+                        format!("{scale} floor"),
+                        range.floor_db,
+                        LEVEL_FLOOR_RANGE,
+                        move |floor_db| LevelRangeChanged(scale, DbRange { floor_db, ..range }),
+                        "{:.0} dB"
+                    ),
+                    slider!(
+                        self.numbers();
+                        "Ceiling",
+                        range.ceiling_db,
+                        LEVEL_CEILING_RANGE,
+                        move |ceiling_db| LevelRangeChanged(
+                            scale,
+                            DbRange {
+                                ceiling_db,
+                                ..range
+                            }
+                        ),
+                        "{:.0} dB"
+                    ),
+                ))
+            })
+            .spacing(theme::SECTION_GAP);
+        card("Level ranges", content)
+    }
+
+    fn render_automation_card(&self) -> container::Container<'_, ConfigMessage> {
+        use ConfigMessage::{RuleAdded, RuleChanged, RuleRemoved};
+        let rules = self.settings.borrow().data.rules.clone();
+        let mut content = Column::new().spacing(theme::SECTION_GAP);
+        for (i, rule) in rules.iter().copied().enumerate() {
+            let header = row![
+                toggle(rule.describe(), rule.enabled, move |enabled| {
+                    RuleChanged(i, AutomationRule { enabled, ..rule })
+                })
+                .width(Length::Fill),
+                action_button("Remove", Some(RuleRemoved(i))).padding([4, 8]),
+            ]
+            .spacing(theme::CONTROL_GAP)
+            .align_y(iced::alignment::Vertical::Center);
+            let mut rule_form = form!(
+                header;
+                pick("When", RuleMetric::ALL, rule.metric, move |metric| {
+                    RuleChanged(i, AutomationRule { metric, ..rule })
+                });
+                pick("Goes", RuleCondition::ALL, rule.condition, move |condition| {
+                    RuleChanged(i, AutomationRule { condition, ..rule })
+                });
+                slider!(
+                    self.numbers();
+                    "Threshold",
+                    rule.threshold_db,
+                    RULE_THRESHOLD_RANGE,
+                    move |threshold_db| RuleChanged(i, AutomationRule { threshold_db, ..rule }),
+                    "{:.1} dB"
+                );
+                pick("Then", RuleAction::ALL, rule.action, move |action| {
+                    RuleChanged(i, AutomationRule { action, ..rule })
+                });
+            );
+            if rule.action == RuleAction::Freeze {
+                rule_form = rule_form
+                    .push(pick(
+                        "Visual",
+                        self.visual_manager.borrow().order(),
+                        rule.freeze,
+                        move |freeze| RuleChanged(i, AutomationRule { freeze, ..rule }),
+                    ))
+                    .push(slider!(
+                        self.numbers();
+                        "Save audio from before",
+                        rule.pre_seconds,
+                        RULE_PRE_RANGE,
+                        move |pre_seconds| RuleChanged(
+                            i,
+                            AutomationRule {
+                                pre_seconds,
+                                ..rule
+                            }
+                        ),
+                        "{:.1} s"
+                    ));
+            }
+            content = content.push(rule_form);
+        }
+        if rules
+            .iter()
+            .any(|rule| rule.action == RuleAction::RunCommand)
+        {
+            content = content.push(self.render_rule_command());
+        }
+        let hint = format!(
+            "Rules read the loudness meter while it is enabled. Logs, snapshots and audio are written to {}.",
+            data_dir().display()
+        );
+        content = content
+            .push(self.render_idle_pause())
+            .push(self.render_silence_watch())
+            .push(
+                text(hint)
+                    .size(theme::BODY_TEXT_SIZE)
+                    .style(theme::weak_text_style),
+            )
+            .push(action_button("Add rule", Some(RuleAdded)));
+        card("Automation", content)
+    }
+
+    // Off at every launch until enabled here, even with a command typed in,
+    // so a settings file from elsewhere can't run anything on its own.
+    fn render_rule_command(&self) -> Column<'_, ConfigMessage> {
+        use ConfigMessage::RuleCommandChanged;
+        let command = self.settings.borrow().data.rule_command.clone();
+        let interval = command.interval_secs();
+        let (a, b, c) = (command.clone(), command.clone(), command);
+        form!(
+            toggle("Allow rules to run a command", a.enabled, move |enabled| {
+                RuleCommandChanged(RuleCommand { enabled, ..a.clone() })
+            });
+            text_input("Command, e.g. notify-send \"$OPENMETERS_RULE\"", &b.command)
+                .on_input(move |command| RuleCommandChanged(RuleCommand { command, ..b.clone() }))
+                .size(theme::BODY_TEXT_SIZE)
+                .width(Length::Fill);
+            slider!(
+                self.numbers();
+                "At most every",
+                interval,
+                COMMAND_INTERVAL_RANGE,
+                move |min_interval_secs| RuleCommandChanged(RuleCommand {
+                    min_interval_secs,
+                    ..c.clone()
+                }),
+                "{:.0} s"
+            );
+            text("Gets OPENMETERS_RULE, _METRIC, _CONDITION, _THRESHOLD, _VALUE and _TIME.")
+                .size(theme::BODY_TEXT_SIZE)
+                .style(theme::weak_text_style);
+        )
+    }
+
+    // Silence is judged on the captured mix, so the watched application also
+    // counts as silent once it leaves the capture.
+    fn render_idle_pause(&self) -> Column<'_, ConfigMessage> {
+        use ConfigMessage::IdlePauseChanged;
+        let idle = self.settings.borrow().data.idle_pause.clone();
+        let mut sources = vec![ANY_APPLICATION.to_owned()];
+        for app in &self.applications {
+            if !sources.contains(&app.name) {
+                sources.push(app.name.clone());
+            }
+        }
+        let selected = if idle.application.is_empty() {
+            ANY_APPLICATION.to_owned()
+        } else {
+            if !sources.contains(&idle.application) {
+                sources.push(idle.application.clone());
+            }
+            idle.application.clone()
+        };
+        let seconds = idle.silence_secs;
+        let (a, b, c, d) = (idle.clone(), idle.clone(), idle.clone(), idle);
+        form!(
+            toggle("Pause visuals when idle", a.enabled, move |enabled| {
+                IdlePauseChanged(IdlePause { enabled, ..a.clone() })
+            });
+            pick("Watch", sources, selected, move |source: String| {
+                let application = if source == ANY_APPLICATION { String::new() } else { source };
+                IdlePauseChanged(IdlePause { application, ..b.clone() })
+            });
+            slider!(
+                self.numbers();
+                "Silent for",
+                seconds,
+                IDLE_SECONDS_RANGE,
+                move |silence_secs| IdlePauseChanged(IdlePause { silence_secs, ..c.clone() }),
+                "{:.0} s"
+            );
+            toggle("Hide visuals while idle", d.collapse, move |collapse| {
+                IdlePauseChanged(IdlePause { collapse, ..d.clone() })
+            });
+        )
+    }
+
+    fn render_silence_watch(&self) -> Column<'_, ConfigMessage> {
+        use ConfigMessage::SilenceWatchChanged;
+        let watch = self.settings.borrow().data.silence_watch.clone();
+        let seconds = watch.silence_secs;
+        let (a, b, c, d) = (watch.clone(), watch.clone(), watch.clone(), watch);
+        form!(
+            toggle("Warn when the program goes silent", a.enabled, move |enabled| {
+                SilenceWatchChanged(SilenceWatch { enabled, ..a.clone() })
+            });
+            slider!(
+                self.numbers();
+                "Silent for",
+                seconds,
+                SILENCE_SECONDS_RANGE,
+                move |silence_secs| SilenceWatchChanged(SilenceWatch { silence_secs, ..b.clone() }),
+                "{:.0} s"
+            );
+            toggle("Desktop notification", c.notify, move |notify| {
+                SilenceWatchChanged(SilenceWatch { notify, ..c.clone() })
+            });
+            text_input("OSC target, e.g. 127.0.0.1:9000", &d.osc_target)
+                .on_input(move |osc_target| {
+                    SilenceWatchChanged(SilenceWatch { osc_target, ..d.clone() })
+                })
+                .size(theme::BODY_TEXT_SIZE)
+                .width(Length::Fill);
+        )
+    }
+
+    /// True while a stream the capture hears is running: a captured
+    /// application in application mode, any application when capturing a
+    /// device. Paused players don't count. Without a registry there is
+    /// nothing to check against.
+    pub fn applications_playing(&self) -> bool {
+        !self.registry_ready || self.running_applications().next().is_some()
+    }
+
+    /// Whether `name` has a running stream the capture hears.
+    pub fn application_running(&self, name: &str) -> bool {
+        !self.registry_ready || self.running_applications().any(|app| app.name == name)
+    }
+
+    fn running_applications(&self) -> impl Iterator<Item = &ApplicationRow> {
+        let device = self.settings.borrow().data.capture_mode != CaptureMode::Applications;
+        self.applications.iter().filter(move |app| {
+            app.running && (device || !self.disabled_applications.contains(&app.node_id))
+        })
+    }
+
+    fn render_sessions_card(&self) -> container::Container<'_, ConfigMessage> {
+        use ConfigMessage::{RecordSession, ReviewSession};
+        let (label, hint) = if self.recording {
+            (
+                "Stop and save",
+                "Recording loudness, a coarse spectrogram and automation events.",
+            )
+        } else {
+            (
+                "Start recording",
+                "Sessions bundle loudness, a coarse spectrogram and events for later review.",
+            )
+        };
+        let mut content = column![
+            text(format!(
+                "{hint} Saved to {}.",
+                session::sessions_dir().display()
+            ))
+            .size(theme::BODY_TEXT_SIZE)
+            .style(theme::weak_text_style),
+            action_button(label, Some(RecordSession(!self.recording))),
+        ]
+        .spacing(theme::SECTION_GAP);
+        for path in self.sessions.iter().take(LISTED_SESSIONS) {
+            let name = path
+                .file_stem()
+                .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned());
+            content = content.push(
+                row![
+                    text(name).size(theme::BODY_TEXT_SIZE).width(Length::Fill),
+                    action_button("Review", Some(ReviewSession(path.clone()))).padding([4, 8]),
+                ]
+                .spacing(theme::CONTROL_GAP)
+                .align_y(iced::alignment::Vertical::Center),
+            );
+        }
+        card("Sessions", content)
+    }
+
+    fn render_tracks_card(&self) -> container::Container<'_, ConfigMessage> {
+        use ConfigMessage::{ExportTracks, ResumeTrackLoudness, SplitByTrack};
+        let resume = self.settings.borrow().data.resume_track_loudness;
+        let status = match (&self.current_track, self.splitting) {
+            (Some(track), _) => format!("Measuring {track}"),
+            (None, true) => "Waiting for a player to start a track".to_owned(),
+            (None, false) => format!(
+                "Restarts integrated loudness at each MPRIS track change (via playerctl) \
+                 while the loudness meter runs. Exports go to {}.",
+                tracks::tracks_dir().display()
+            ),
+        };
+        let mut content = column![
+            toggle("Split at track changes", self.splitting, SplitByTrack),
+            toggle("Resume after a restart", resume, ResumeTrackLoudness),
+            text(status)
+                .size(theme::BODY_TEXT_SIZE)
+                .style(theme::weak_text_style),
+        ]
+        .spacing(theme::SECTION_GAP);
+        let level = |value: Option<f32>, unit| {
+            value.map_or_else(
+                || "-".to_owned(),
+                |v| self.numbers().localize(format!("{v:.1} {unit}")),
+            )
+        };
+        for track in self.tracks.iter().rev().take(LISTED_TRACKS) {
+            content = content.push(
+                row![
+                    text(track.label())
+                        .size(theme::BODY_TEXT_SIZE)
+                        .width(Length::Fill),
+                    text(format!(
+                        "{}  {}  LRA {}  {}",
+                        level(track.integrated_lufs, "LUFS"),
+                        level(track.peak_dbtp, "dBTP"),
+                        level(track.range_lu, "LU"),
+                        dr_label(track.dynamic_range),
+                    ))
+                    .size(theme::BODY_TEXT_SIZE),
+                ]
+                .spacing(theme::CONTROL_GAP),
+            );
+        }
+        let export = (!self.tracks.is_empty()).then_some(ExportTracks);
+        content = content.push(action_button(
+            format!("Export CSV ({} tracks)", self.tracks.len()),
+            export,
+        ));
+        card("Per-track loudness", content)
+    }
+
+    pub fn track_finished(&mut self, track: TrackResult) {
+        self.tracks.push(track);
+    }
+
+    pub fn set_current_track(&mut self, track: Option<String>) {
+        self.current_track = track;
+    }
+
+    fn render_dynamic_range_card(&self) -> container::Container<'_, ConfigMessage> {
+        use ConfigMessage::MeasureDynamicRange;
+        let (label, status) = match (self.measuring_dr, &self.dynamic_range) {
+            (true, Some(reading)) => (
+                "Stop",
+                format!(
+                    "{} after {}",
+                    dr_label(reading.value),
+                    self.numbers().duration(reading.seconds)
+                ),
+            ),
+            (true, None) => (
+                "Stop",
+                "Measuring; the first reading comes after 3 s.".to_owned(),
+            ),
+            (false, _) => (
+                "Start measuring",
+                "Second-highest peak against the loudest 20 % of 3 s blocks, as DR \
+                 meters report it. Split tracks get a DR value of their own."
+                    .to_owned(),
+            ),
+        };
+        let mut content = column![
+            text(status)
+                .size(theme::BODY_TEXT_SIZE)
+                .style(theme::weak_text_style),
+            action_button(label, Some(MeasureDynamicRange(!self.measuring_dr))),
+        ]
+        .spacing(theme::SECTION_GAP);
+        for reading in self.dynamic_ranges.iter().rev().take(LISTED_TRACKS) {
+            content = content.push(
+                row![
+                    text(reading.started.as_str())
+                        .size(theme::BODY_TEXT_SIZE)
+                        .width(Length::Fill),
+                    text(format!(
+                        "{}  {}",
+                        dr_label(reading.value),
+                        self.numbers().duration(reading.seconds)
+                    ))
+                    .size(theme::BODY_TEXT_SIZE),
+                ]
+                .spacing(theme::CONTROL_GAP),
+            );
+        }
+        card("Dynamic range (DR)", content)
+    }
+
+    pub fn set_dynamic_range(&mut self, reading: DynamicRangeReading) {
+        self.dynamic_range = Some(reading);
+    }
+
+    pub fn dynamic_range_finished(&mut self, reading: DynamicRangeReading) {
+        self.dynamic_ranges.push(reading);
+    }
+
     fn render_theme_card(&self) -> container::Container<'_, ConfigMessage> {
         let active = self.settings.borrow().active_theme().to_owned();
         let selected = self.theme_choices.iter().find(|c| c.name == active);
@@ -442,6 +1849,46 @@ impl ConfigPage {
         Some(name)
     }
 
+    pub(in crate::ui) fn reload_from_settings(&mut self) {
+        let (bg, last_device_name, loopback_sink) = {
+            let guard = self.settings.borrow();
+            let data = &guard.data;
+            (
+                data.background_color.map_or(theme::BG_BASE, Into::into),
+                data.last_device_name.clone(),
+                data.loopback_sink.clone(),
+            )
+        };
+        self.bg_palette.set_colors(&[bg]);
+        self.selected_device = DeviceSelection::from_token(last_device_name);
+        self.device_trial = None;
+        self.selected_loopback = DeviceSelection::from_token(loopback_sink);
+        let alpha = self.settings.borrow().data.opacity.alpha();
+        self.visual_manager.borrow_mut().set_content_opacity(alpha);
+        let tuning = self.settings.borrow().data.tuning;
+        self.visual_manager
+            .borrow_mut()
+            .set_tuning(Tuning::new(tuning.a4_hz, tuning.temperament));
+        let separator = self.settings.borrow().data.decimal_separator;
+        self.visual_manager
+            .borrow_mut()
+            .set_number_format(NumberFormat::new(separator));
+        let ranges = self.settings.borrow().data.level_ranges;
+        self.visual_manager.borrow_mut().set_level_ranges(&ranges);
+        meter_tap::set_channel_matrix(&self.settings.borrow().data.channel_matrix);
+        let mixdown = self.settings.borrow().data.mixdown.clone();
+        self.visual_manager.borrow_mut().set_mixdown(&mixdown);
+        self.refresh_theme_choices();
+        self.dispatch_capture_state();
+        self.dispatch_loopback_sink();
+        let delay_ms = self.settings.borrow().data.loopback_delay_ms;
+        self.send_routing(RoutingCommand::SetLoopbackDelay(delay_ms));
+        self.dispatch_trims(|_| true);
+        if self.output_dimmed {
+            self.dispatch_output_gain();
+        }
+    }
+
     pub(in crate::ui) fn refresh_theme_choices_if_needed(&mut self) {
         let active = self.settings.borrow().active_theme().to_owned();
         if !self.theme_choices.iter().any(|c| c.name == active) {
@@ -496,6 +1943,7 @@ impl ConfigPage {
             .width(Length::Fill);
             let alignment = pick("Alignment", BarAlignment::ALL, bar.alignment, Alignment);
             let height_slider = slider!(
+                self.numbers();
                 "Height",
                 height as f32,
                 height_range,
@@ -511,26 +1959,64 @@ impl ConfigPage {
         &self,
         snapshot: &[VisualSlotSnapshot],
     ) -> container::Container<'_, ConfigMessage> {
+        use ConfigMessage::{VisualAdded, VisualRemoved, VisualToggled};
         let enabled = snapshot.iter().filter(|slot| slot.enabled).count();
+        let toggles = render_toggle_grid(snapshot, |slot| {
+            (
+                slot.id.to_string(),
+                slot.enabled,
+                VisualToggled {
+                    visual: slot.id,
+                    enabled: !slot.enabled,
+                },
+            )
+        });
+        let instances =
+            |kind: VisualKind| snapshot.iter().filter(|slot| slot.id.kind == kind).count();
+        let addable: Vec<VisualKind> = VisualKind::ALL
+            .iter()
+            .copied()
+            .filter(|&kind| instances(kind) < usize::from(MAX_INSTANCES))
+            .collect();
+        let mut extras = Row::new()
+            .spacing(theme::CONTROL_GAP)
+            .align_y(iced::alignment::Vertical::Center)
+            .push(
+                pick_list(addable, None::<VisualKind>, VisualAdded)
+                    .placeholder("Add another...")
+                    .text_size(theme::BODY_TEXT_SIZE),
+            );
+        for slot in snapshot.iter().filter(|slot| !slot.id.is_primary()) {
+            let label = format!("Remove {}", slot.id);
+            extras =
+                extras.push(action_button(label, Some(VisualRemoved(slot.id))).padding([4, 8]));
+        }
         card(
             format!("Visual Modules ({enabled}/{})", snapshot.len()),
-            render_toggle_grid(snapshot, |slot| {
-                (
-                    slot.kind.label(),
-                    slot.enabled,
-                    ConfigMessage::VisualToggled {
-                        kind: slot.kind,
-                        enabled: !slot.enabled,
-                    },
-                )
-            }),
+            column![toggles, extras.wrap()].spacing(theme::SECTION_GAP),
         )
     }
 
-    fn update_hardware_sink_label(&mut self, snapshot: &RegistrySnapshot) {
+    /// Adds an entry to the event timeline.
+    pub fn note_event(&mut self, event: String) {
+        if self.timeline.len() >= TIMELINE_CAPACITY {
+            self.timeline.pop_front();
+        }
+        self.timeline.push_back((SystemTime::now(), event));
+    }
+
+    fn update_hardware_sink_label(&mut self, snapshot: &RegistrySnapshot, announce: bool) {
         let summary = snapshot.describe_default_target(snapshot.defaults.audio_sink.as_ref());
         let known = summary.display != "(none)" || summary.raw != "(none)";
         if known {
+            if announce
+                && self
+                    .hardware_sink_last_known
+                    .as_ref()
+                    .is_some_and(|last| *last != summary.display)
+            {
+                self.note_event(format!("Default sink changed to {}", summary.display));
+            }
             self.hardware_sink_last_known = Some(summary.display.clone());
             self.hardware_sink_label = summary.display;
         } else {
@@ -541,16 +2027,37 @@ impl ConfigPage {
         }
     }
 
-    fn apply_snapshot(&mut self, snapshot: RegistrySnapshot) {
-        self.update_hardware_sink_label(&snapshot);
-        let mut choices = self.build_device_choices(&snapshot);
-        if sync_selected_device_with_choices(&mut self.selected_device, &mut choices, &snapshot) {
+    fn apply_snapshot(&mut self, snapshot: RegistrySnapshot, announce: bool) {
+        self.update_hardware_sink_label(&snapshot, announce);
+        self.forced_quantum = snapshot.defaults.forced_quantum;
+        let capture_devices = snapshot
+            .nodes
+            .iter()
+            .filter(|node| node.is_capture_device_candidate());
+        let mut choices = self.build_device_choices(capture_devices);
+        if sync_selected_device_with_choices(&mut self.selected_device, &mut choices, |token| {
+            snapshot.find_capture_device_by_token(token)
+        }) {
             let token = self.selected_device.token().map(str::to_owned);
+            if announce {
+                let device = token.as_deref().unwrap_or("the default source");
+                self.note_event(format!("Capture device gone; now capturing {device}"));
+            }
             self.settings.update(|s| s.data.last_device_name = token);
             self.dispatch_capture_state();
         }
         self.device_choices = choices;
 
+        let mut choices = self.build_device_choices(snapshot.playback_sinks());
+        if sync_selected_device_with_choices(&mut self.selected_loopback, &mut choices, |token| {
+            snapshot.find_playback_sink_by_token(token)
+        }) {
+            let token = self.selected_loopback.token().map(str::to_owned);
+            self.settings.update(|s| s.data.loopback_sink = token);
+            self.dispatch_loopback_sink();
+        }
+        self.loopback_choices = choices;
+
         let mut seen = HashSet::new();
         let mut entries: Vec<_> = snapshot
             .virtual_sink()
@@ -563,16 +2070,70 @@ impl ConfigPage {
             .collect();
         self.disabled_applications.retain(|id| seen.contains(id));
         entries.sort_by_cached_key(|entry| (entry.label.to_ascii_lowercase(), entry.node_id));
+        let known: HashSet<u32> = self.applications.iter().map(|app| app.node_id).collect();
+        if announce {
+            // Both lists are sorted, so the timeline reads in label order.
+            let opened: Vec<String> = entries
+                .iter()
+                .filter(|app| !known.contains(&app.node_id))
+                .map(|app| format!("Stream opened: {}", app.label))
+                .collect();
+            let closed: Vec<String> = self
+                .applications
+                .iter()
+                .filter(|app| !seen.contains(&app.node_id))
+                .map(|app| format!("Stream closed: {}", app.label))
+                .collect();
+            for event in opened.into_iter().chain(closed) {
+                self.note_event(event);
+            }
+        }
         self.applications = entries;
+        self.dispatch_trims(|app| !known.contains(&app.node_id));
     }
 
     fn dispatch_capture_state(&self) {
+        let settings = self.settings.borrow();
+        let mode = settings.data.capture_mode;
+        apply_calibration(
+            &self.visual_manager,
+            mode,
+            settings.data.mic_calibration.as_ref(),
+        );
         self.send_routing(RoutingCommand::SetCaptureState(
-            self.settings.borrow().data.capture_mode,
+            mode,
             self.selected_device.clone(),
         ));
     }
 
+    fn dispatch_loopback_sink(&self) {
+        self.send_routing(RoutingCommand::SetLoopbackSink(
+            self.selected_loopback.clone(),
+        ));
+    }
+
+    fn dispatch_trims(&self, matches: impl Fn(&ApplicationRow) -> bool) {
+        let settings = self.settings.borrow();
+        for app in self.applications.iter().filter(|app| matches(app)) {
+            let db = settings.data.application_trims.get(&app.name);
+            self.send_routing(RoutingCommand::SetApplicationTrim {
+                node_id: app.node_id,
+                gain: db.map_or(1.0, |&db| {
+                    db_to_gain(db.clamp(APPLICATION_TRIM_MIN_DB, 0.0))
+                }),
+            });
+        }
+    }
+
+    fn dispatch_output_gain(&self) {
+        let gain = if self.output_dimmed {
+            self.settings.borrow().data.output_dim.gain()
+        } else {
+            1.0
+        };
+        self.send_routing(RoutingCommand::SetOutputGain(gain));
+    }
+
     fn send_routing(&self, command: RoutingCommand) {
         if let Err(err) = self.routing_sender.send(command) {
             tracing::error!("[ui] failed to send routing command: {err}");
@@ -580,16 +2141,28 @@ impl ConfigPage {
     }
 }
 
-fn sync_selected_device_with_choices(
+// Mic calibration only means anything for a mic, so it follows device capture.
+fn apply_calibration(
+    visual_manager: &VisualManagerHandle,
+    mode: CaptureMode,
+    cal: Option<&MicCalibration>,
+) {
+    let curve = cal
+        .filter(|_| mode == CaptureMode::Device)
+        .and_then(|cal| CalibrationCurve::new(&cal.points));
+    visual_manager.borrow_mut().set_calibration(curve);
+}
+
+fn sync_selected_device_with_choices<'a>(
     selected: &mut DeviceSelection,
     choices: &mut Vec<DeviceOption>,
-    snapshot: &RegistrySnapshot,
+    find: impl Fn(&str) -> Option<&'a NodeInfo>,
 ) -> bool {
     let DeviceSelection::Device(token) = selected else {
         return false;
     };
     let mut changed = false;
-    if let Some(node) = snapshot.find_capture_device_by_token(token) {
+    if let Some(node) = find(token) {
         let canonical = node.capture_device_token();
         changed = token.as_str() != canonical;
         *token = canonical;
@@ -608,7 +2181,7 @@ fn sync_selected_device_with_choices(
 
 fn render_toggle_grid<'a, T, F>(items: &[T], mut project: F) -> Column<'a, ConfigMessage>
 where
-    for<'b> F: FnMut(&'b T) -> (&'b str, bool, ConfigMessage),
+    F: FnMut(&T) -> (String, bool, ConfigMessage),
 {
     let mut grid = Column::new().spacing(6);
     for chunk in items.chunks(GRID_COLUMNS) {
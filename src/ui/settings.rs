@@ -3,14 +3,18 @@
 
 macro_rules! settings_view {
     (
-        $pane:ident as $settings:ident { $($body:tt)* }
+        $pane:ident as $settings:ident, $numbers:ident { $($body:tt)* }
         $($label:expr => $content:expr;)*
     ) => {
         impl Pane {
-            pub(super) fn view(&self) -> iced::Element<'_, Message> {
+            pub(super) fn view(
+                &self,
+                numbers: $crate::util::audio::NumberFormat,
+            ) -> iced::Element<'_, Message> {
                 use Message::*;
                 let $pane = self;
                 let $settings = &$pane.settings;
+                let $numbers = numbers;
                 $($body)*
                 iced::widget::Column::new()
                     .spacing($crate::ui::theme::SECTION_GAP)
@@ -30,25 +34,34 @@ macro_rules! settings_modules {
         $(mod $module;)+
 
         #[derive(Debug, Clone)]
-        pub(in crate::ui) enum SettingsMessage { $($variant($module::Message),)+ }
+        pub(in crate::ui) enum SettingsMessage {
+            $($variant($module::Message),)+
+            PopoutDisplay(Option<DisplayOverride>),
+            PopoutAspect(AspectLock),
+            KeepChanges,
+            RevertChanges,
+        }
 
         enum SettingsPane { $($variant($module::Pane),)+ }
 
         impl SettingsPane {
-            fn new(kind: VisualKind, manager: &VisualManagerHandle) -> Self {
-                match kind {
-                    $(VisualKind::$variant => Self::$variant($module::create(manager, kind)),)+
+            fn new(id: VisualId, manager: &VisualManagerHandle, settings: &SettingsHandle) -> Self {
+                match id.kind {
+                    $(VisualKind::$variant => {
+                        Self::$variant($module::create(manager, settings, id))
+                    })+
                 }
             }
 
-            fn view(&self) -> Element<'_, SettingsMessage> {
+            fn view(&self, numbers: NumberFormat) -> Element<'_, SettingsMessage> {
                 match self {
-                    $(Self::$variant(pane) => pane.view().map(SettingsMessage::$variant),)+
+                    $(Self::$variant(pane) => pane.view(numbers).map(SettingsMessage::$variant),)+
                 }
             }
 
             fn handle(
                 &mut self,
+                id: VisualId,
                 message: SettingsMessage,
                 manager: &VisualManagerHandle,
                 settings: &SettingsHandle,
@@ -58,9 +71,9 @@ macro_rules! settings_modules {
                         let SettingsMessage::$variant(message) = message else {
                             return;
                         };
-                        if pane.handle(message) {
+                        if pane.handle(message, settings) {
                             persist_with_palette(
-                                manager, settings, VisualKind::$variant,
+                                manager, settings, id,
                                 &pane.settings, &pane.palette,
                             );
                         }
@@ -74,7 +87,7 @@ macro_rules! settings_modules {
 macro_rules! settings_pane {
     (
         $settings_ty:ty
-        $(, extra_from_settings($source:ident) {
+        $(, extra_from_settings($source:ident $(, $store:ident)?) {
             $($field:ident: $ty:ty = $init:expr),* $(,)?
         })?
         $(, init_palette($editor:ident $(, $palette_source:ident)?) $init_body:block)?
@@ -88,16 +101,16 @@ macro_rules! settings_pane {
 
         pub(super) fn create(
             visual_manager: &super::VisualManagerHandle,
-            kind: crate::visuals::registry::VisualKind,
+            _settings_handle: &super::SettingsHandle,
+            id: crate::visuals::registry::VisualId,
         ) -> Pane {
             let (loaded_settings, palette) =
-                super::load_settings_and_palette::<$settings_ty>(visual_manager, kind);
-            $($(
-                let $field: $ty = {
-                    let $source = &loaded_settings;
-                    $init
-                };
-            )*)?
+                super::load_settings_and_palette::<$settings_ty>(visual_manager, id);
+            $(
+                let $source = &loaded_settings;
+                $(let $store = _settings_handle;)?
+                $(let $field: $ty = $init;)*
+            )?
             $(
                 let mut palette = palette;
                 let $editor = &mut palette;
@@ -110,7 +123,7 @@ macro_rules! settings_pane {
 }
 
 macro_rules! settings_messages {
-    ($pane:ident, $settings:ident, $value:ident {
+    ($pane:ident, $settings:ident, $value:ident $(; $store:ident)? {
         $($variant:ident($ty:ty) => $handler:expr;)+
     }) => {
         #[derive(Debug, Clone)]
@@ -120,7 +133,12 @@ macro_rules! settings_messages {
         }
 
         impl Pane {
-            pub(super) fn handle(&mut self, message: Message) -> bool {
+            pub(super) fn handle(
+                &mut self,
+                message: Message,
+                _store: &super::SettingsHandle,
+            ) -> bool {
+                $(let $store = _store;)?
                 let $pane = self;
                 let $settings = &mut $pane.settings;
                 match message {
@@ -133,15 +151,23 @@ macro_rules! settings_messages {
 }
 
 use crate::persistence::settings::{
-    BUILTIN_THEME, HasPalette, ModuleSettings, PaletteSettings, SettingsConfig, SettingsHandle,
+    BUILTIN_THEME, DisplayOverride, FrequencyMarker, HasPalette, ModuleSettings, PaletteSettings,
+    SettingsConfig, SettingsHandle,
+};
+use crate::ui::theme::{self, Palette};
+use crate::ui::widgets::{
+    SliderRange, Trial, card, keep_changes_card, palette_editor::PaletteEditor, pick, toggle,
 };
-use crate::ui::theme::Palette;
-use crate::ui::widgets::{SliderRange, palette_editor::PaletteEditor};
-use crate::visuals::registry::{VisualKind, VisualManagerHandle};
+use crate::util::audio::NumberFormat;
+use crate::visuals::options::AspectLock;
+use crate::visuals::registry::{VisualId, VisualKind, VisualManagerHandle};
 use iced::{Color, Element};
+use std::time::Instant;
+use tracing::warn;
 
 const FFT_OPTIONS: [usize; 5] = [1024, 2048, 4096, 8192, 16384];
 const HOP_DIVISORS: [usize; 7] = [4, 6, 8, 16, 32, 64, 128];
+const MARKERS_PLACEHOLDER: &str = "50 Hum, 15.6k-16k TV";
 
 fn set<T: PartialEq>(target: &mut T, value: T) -> bool {
     if *target == value {
@@ -171,6 +197,13 @@ fn set_usize(target: &mut usize, value: f32, range: SliderRange) -> bool {
     set(target, range.snap(value).round() as usize)
 }
 
+// Keeps the raw text so half-typed entries survive until they parse.
+fn set_markers(text: &mut String, markers: &mut Vec<FrequencyMarker>, value: String) -> bool {
+    let parsed = FrequencyMarker::parse_list(&value);
+    *text = value;
+    set(markers, parsed)
+}
+
 fn get_closest_hop_divisor(fft_size: usize, hop_size: usize) -> usize {
     if fft_size == 0 || hop_size == 0 {
         return 8;
@@ -206,24 +239,102 @@ settings_modules! {
     spectrogram => Spectrogram,
     spectrum => Spectrum,
     stereometer => Stereometer,
+    tuner => Tuner,
     waveform => Waveform,
 }
 
+impl SettingsMessage {
+    // Changes that can make the analysis heavy enough to stall the UI; a
+    // preset may carry any FFT size.
+    fn is_risky(&self) -> bool {
+        matches!(
+            self,
+            Self::Spectrum(spectrum::Message::FftSize(_))
+                | Self::Spectrogram(
+                    spectrogram::Message::FftSize(_)
+                        | spectrogram::Message::ZeroPadding(_)
+                        | spectrogram::Message::Preset(_)
+                )
+        )
+    }
+}
+
 pub(in crate::ui) struct ActiveSettings {
-    pub(in crate::ui) kind: VisualKind,
+    pub(in crate::ui) visual: VisualId,
     pane: SettingsPane,
+    // Module settings from before the first unconfirmed risky change.
+    trial: Option<Trial<ModuleSettings>>,
+    // `Some` while a spectrogram is popped out, holding that window's override.
+    popout_display: Option<Option<DisplayOverride>>,
+    // `Some` while popped out, holding the lock for this kind of visual.
+    popout_aspect: Option<AspectLock>,
 }
 
 impl ActiveSettings {
-    pub(in crate::ui) fn new(kind: VisualKind, visual_manager: &VisualManagerHandle) -> Self {
+    pub(in crate::ui) fn new(
+        visual: VisualId,
+        visual_manager: &VisualManagerHandle,
+        settings_handle: &SettingsHandle,
+    ) -> Self {
         Self {
-            kind,
-            pane: SettingsPane::new(kind, visual_manager),
+            visual,
+            pane: SettingsPane::new(visual, visual_manager, settings_handle),
+            trial: None,
+            popout_display: None,
+            popout_aspect: None,
         }
     }
 
-    pub(in crate::ui) fn view(&self) -> Element<'_, SettingsMessage> {
-        self.pane.view()
+    pub(in crate::ui) fn with_popout_display(
+        mut self,
+        display: Option<Option<DisplayOverride>>,
+    ) -> Self {
+        self.popout_display = display.filter(|_| self.visual.kind == VisualKind::Spectrogram);
+        self
+    }
+
+    pub(in crate::ui) fn with_popout_aspect(mut self, aspect: Option<AspectLock>) -> Self {
+        self.popout_aspect = aspect;
+        self
+    }
+
+    // A rebuilt panel for the same visual keeps the unconfirmed change.
+    pub(in crate::ui) fn with_trial_of(mut self, old: ActiveSettings) -> Self {
+        if old.visual == self.visual {
+            self.trial = old.trial;
+        }
+        self
+    }
+
+    // Closing the panel before a risky change is kept reverts it.
+    pub(in crate::ui) fn dismiss(
+        mut self,
+        visual_manager: &VisualManagerHandle,
+        settings_handle: &SettingsHandle,
+    ) {
+        self.revert(visual_manager, settings_handle);
+    }
+
+    pub(in crate::ui) fn view(&self, numbers: NumberFormat) -> Element<'_, SettingsMessage> {
+        let mut pane = self.pane.view(numbers);
+        if let Some(trial) = &self.trial {
+            let prompt = keep_changes_card(
+                trial.seconds_left(Instant::now()),
+                SettingsMessage::KeepChanges,
+                SettingsMessage::RevertChanges,
+            );
+            pane = iced::widget::column![prompt, pane]
+                .spacing(theme::SECTION_GAP)
+                .into();
+        }
+        match self.popout_aspect {
+            Some(aspect) => {
+                iced::widget::column![pane, popout_card(aspect, self.popout_display, numbers)]
+                    .spacing(theme::SECTION_GAP)
+                    .into()
+            }
+            None => pane,
+        }
     }
 
     pub(in crate::ui) fn handle(
@@ -232,62 +343,198 @@ impl ActiveSettings {
         visual_manager: &VisualManagerHandle,
         settings_handle: &SettingsHandle,
     ) {
-        self.pane.handle(message, visual_manager, settings_handle);
+        match message {
+            SettingsMessage::PopoutDisplay(display) => {
+                if let Some(current) = &mut self.popout_display {
+                    *current = display;
+                }
+                return;
+            }
+            SettingsMessage::PopoutAspect(aspect) => {
+                if let Some(current) = &mut self.popout_aspect {
+                    *current = aspect;
+                }
+                return;
+            }
+            SettingsMessage::KeepChanges => {
+                self.trial = None;
+                return;
+            }
+            SettingsMessage::RevertChanges => {
+                self.revert(visual_manager, settings_handle);
+                return;
+            }
+            _ => {}
+        }
+        let message = match message {
+            SettingsMessage::Spectrum(spectrum::Message::StoreMemory(slot)) => {
+                let trace = visual_manager.borrow().spectrum_memory_trace(self.visual);
+                let Some(trace) = trace else {
+                    return;
+                };
+                SettingsMessage::Spectrum(spectrum::Message::MemoryStored((slot, trace)))
+            }
+            SettingsMessage::Oscilloscope(oscilloscope::Message::Capture(action)) => {
+                let manager = visual_manager.borrow();
+                match action {
+                    oscilloscope::CaptureAction::Rearm => manager.rearm_oscilloscope(self.visual),
+                    export => match manager.oscilloscope_capture(self.visual) {
+                        Some(snapshot) => oscilloscope::save_capture(snapshot, export),
+                        None => warn!("[oscilloscope] nothing captured yet"),
+                    },
+                }
+                return;
+            }
+            other => other,
+        };
+        // Later risky changes restart the countdown but keep the first
+        // fallback, which is the last state known to work.
+        if message.is_risky() {
+            let previous = match self.trial.take() {
+                Some(trial) => Some(trial.previous),
+                None => visual_manager.borrow().module_settings(self.visual),
+            };
+            self.trial = previous.map(|previous| Trial::new(previous, Instant::now()));
+        }
+        self.pane
+            .handle(self.visual, message, visual_manager, settings_handle);
+    }
+
+    pub(in crate::ui) fn on_trial(&self) -> bool {
+        self.trial.is_some()
+    }
+
+    pub(in crate::ui) fn tick(
+        &mut self,
+        now: Instant,
+        visual_manager: &VisualManagerHandle,
+        settings_handle: &SettingsHandle,
+    ) {
+        if self.trial.as_ref().is_some_and(|trial| trial.is_due(now)) {
+            self.revert(visual_manager, settings_handle);
+        }
+    }
+
+    fn revert(&mut self, visual_manager: &VisualManagerHandle, settings_handle: &SettingsHandle) {
+        let Some(Trial { previous, .. }) = self.trial.take() else {
+            return;
+        };
+        let id = self.visual;
+        visual_manager
+            .borrow_mut()
+            .apply_module_settings(id, &previous);
+        settings_handle.update(move |settings| {
+            settings.data.visuals.modules.insert(id, previous);
+        });
+        self.pane = SettingsPane::new(id, visual_manager, settings_handle);
     }
 }
 
+// `display` is `Some` for visuals with a per-window display override.
+fn popout_card<'a>(
+    aspect: AspectLock,
+    display: Option<Option<DisplayOverride>>,
+    numbers: NumberFormat,
+) -> Element<'a, SettingsMessage> {
+    use SettingsMessage::{PopoutAspect, PopoutDisplay};
+    let mut content = form!(
+        pick("Aspect ratio", AspectLock::ALL, aspect, PopoutAspect);
+    );
+    if let Some(display) = display {
+        content = content.push(toggle("Own floor and contrast", display.is_some(), |on| {
+            PopoutDisplay(on.then(DisplayOverride::default))
+        }));
+    }
+    if let Some(display) = display.flatten() {
+        content = content
+            .push(slider!(
+                numbers;
+                "Floor",
+                display.floor_db,
+                spectrogram::FLOOR_RANGE,
+                move |floor_db| PopoutDisplay(Some(DisplayOverride {
+                    floor_db,
+                    ..display
+                })),
+                "{:.0} dB"
+            ))
+            .push(slider!(
+                numbers;
+                "Gamma",
+                display.gamma,
+                spectrogram::GAMMA_RANGE,
+                move |gamma| PopoutDisplay(Some(DisplayOverride { gamma, ..display })),
+                "{:.2}"
+            ));
+    }
+    card("Pop-out window", content).into()
+}
+
 pub(super) fn load_settings_and_palette<T: SettingsConfig + HasPalette>(
     visual_manager: &VisualManagerHandle,
-    kind: VisualKind,
+    id: VisualId,
 ) -> (T, PaletteEditor) {
     let settings: T = visual_manager
         .borrow()
-        .module_settings(kind)
+        .module_settings(id)
         .and_then(|stored| stored.parse_config::<T>())
         .unwrap_or_default();
-    let mut editor = PaletteEditor::new(Palette::for_kind(kind));
+    let mut editor = PaletteEditor::new(Palette::for_kind(id.kind));
     if let Some(stored) = settings.palette() {
-        let stops: Vec<Color> = stored.stops.iter().copied().map(Into::into).collect();
-        editor.set_colors(&stops);
-        editor.set_positions(stored.stop_positions.as_deref());
-        editor.set_spreads(stored.stop_spreads.as_deref());
+        apply_palette(&mut editor, Some(stored));
     }
     (settings, editor)
 }
 
+// `None` puts the editor back to the kind's stock palette.
+pub(super) fn apply_palette(editor: &mut PaletteEditor, stored: Option<&PaletteSettings>) {
+    let stops: Vec<Color> = stored
+        .map(|stored| stored.stops.iter().copied().map(Into::into).collect())
+        .unwrap_or_default();
+    editor.set_colors(&stops);
+    editor.set_positions(stored.and_then(|stored| stored.stop_positions.as_deref()));
+    editor.set_spreads(stored.and_then(|stored| stored.stop_spreads.as_deref()));
+    editor.set_blend(stored.and_then(|stored| stored.blend).unwrap_or_default());
+}
+
+pub(super) fn palette_settings(palette: &PaletteEditor) -> Option<PaletteSettings> {
+    PaletteSettings::from_state(
+        palette.colors(),
+        palette.defaults(),
+        palette.positions(),
+        palette.default_positions(),
+        palette.spreads(),
+        palette.blend(),
+    )
+}
+
 pub(super) fn persist_with_palette<T: Clone + serde::Serialize + HasPalette>(
     visual_manager: &VisualManagerHandle,
     settings_handle: &SettingsHandle,
-    kind: VisualKind,
+    id: VisualId,
     config: &T,
     palette: &PaletteEditor,
 ) {
     let mut stored = config.clone();
-    let palette_settings = PaletteSettings::from_state(
-        palette.colors(),
-        palette.defaults(),
-        palette.positions(),
-        palette.default_positions(),
-        palette.spreads(),
-    );
+    let palette_settings = palette_settings(palette);
     stored.set_palette(palette_settings.clone());
     visual_manager
         .borrow_mut()
-        .apply_module_settings(kind, &ModuleSettings::with_config(&stored));
+        .apply_module_settings(id, &ModuleSettings::with_config(&stored));
     settings_handle.update(move |settings| {
         settings
             .data
             .visuals
             .modules
-            .entry(kind)
+            .entry(id)
             .or_default()
             .set_config(&stored);
         if palette_settings.is_some() || settings.active_theme() != BUILTIN_THEME {
             settings.update_active_theme(|theme| {
                 if let Some(ps) = palette_settings {
-                    theme.palettes.insert(kind, ps);
+                    theme.palettes.insert(id, ps);
                 } else {
-                    theme.palettes.remove(&kind);
+                    theme.palettes.remove(&id);
                 }
             });
         }
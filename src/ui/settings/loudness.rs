@@ -1,22 +1,81 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // Copyright (C) 2026 Maika Namuo
 
-use super::set;
+use super::{set, set_f32};
+use crate::domain::automation::RuleCondition;
 use crate::persistence::settings::LoudnessSettings;
-use crate::ui::widgets::pick;
-use crate::visuals::options::MeterMode;
+use crate::ui::widgets::{SliderRange, pick, split, toggle};
+use crate::visuals::options::{MeterMode, MeterOrientation};
 
 settings_pane!(LoudnessSettings);
 
+const TARGET_RANGE: SliderRange = SliderRange::new(-40.0, 0.0, 0.5);
+const HOLD_RANGE: SliderRange = SliderRange::new(1.0, 60.0, 1.0);
+const COOLDOWN_RANGE: SliderRange = SliderRange::new(10.0, 600.0, 10.0);
+const LUFS_ZONE_RANGE: SliderRange = SliderRange::new(-60.0, 0.0, 0.5);
+const DB_ZONE_RANGE: SliderRange = SliderRange::new(-60.0, 4.0, 0.5);
+const ZONE_LABELS: [&str; 3] = ["Low zone up to", "Mid zone up to", "High zone up to"];
+
 settings_messages!(pane, settings, value {
     LeftMode(MeterMode) => set(&mut settings.left_mode, value);
     RightMode(MeterMode) => set(&mut settings.right_mode, value);
+    Orientation(MeterOrientation) => set(&mut settings.orientation, value);
+    AlertEnabled(bool) => set(&mut settings.alert.enabled, value);
+    AlertCondition(RuleCondition) => set(&mut settings.alert.condition, value);
+    AlertTarget(f32) => set_f32(&mut settings.alert.target_lufs, value, TARGET_RANGE);
+    AlertHold(f32) => set_f32(&mut settings.alert.hold_secs, value, HOLD_RANGE);
+    AlertCooldown(f32) => set_f32(&mut settings.alert.cooldown_secs, value, COOLDOWN_RANGE);
+    LoudnessZone((usize, f32)) => set_zone(&mut settings.loudness_zones, value, LUFS_ZONE_RANGE);
+    LevelZone((usize, f32)) => set_zone(&mut settings.level_zones, value, DB_ZONE_RANGE);
 });
 
+// Dragging one edge past another pushes it along, so the zones stay in order.
+fn set_zone(zones: &mut [f32; 3], (index, value): (usize, f32), range: SliderRange) -> bool {
+    let value = range.snap(value);
+    let mut next = *zones;
+    for (i, zone) in next.iter_mut().enumerate() {
+        if i == index {
+            *zone = value;
+        } else if i < index {
+            *zone = zone.min(value);
+        } else {
+            *zone = zone.max(value);
+        }
+    }
+    set(zones, next)
+}
+
 settings_view! {
-    pane as settings {}
+    pane as settings, numbers {
+        let alert = &settings.alert;
+        let mut loudness_zones = form!();
+        let mut level_zones = form!();
+        for (index, label) in ZONE_LABELS.into_iter().enumerate() {
+            let lufs = settings.loudness_zones[index];
+            let db = settings.level_zones[index];
+            loudness_zones = loudness_zones.push(slider!(
+                numbers;
+                label, lufs, LUFS_ZONE_RANGE,
+                move |v| LoudnessZone((index, v)), format!("{lufs:.1} LUFS")
+            ));
+            level_zones = level_zones.push(slider!(
+                numbers;
+                label, db, DB_ZONE_RANGE,
+                move |v| LevelZone((index, v)), format!("{db:.1} dB")
+            ));
+        }
+    }
     "Meters" => form!(
         pick("Left meter mode", MeterMode::ALL, settings.left_mode, LeftMode);
         pick("Right meter mode", MeterMode::ALL, settings.right_mode, RightMode);
+        pick("Orientation", MeterOrientation::ALL, settings.orientation, Orientation);
+    );
+    "Zones" => split(loudness_zones, level_zones);
+    "Alerts" => form!(
+        toggle("Notify on short-term loudness", alert.enabled, AlertEnabled);
+        pick("When loudness stays", RuleCondition::ALL, alert.condition, AlertCondition);
+        slider!(numbers; "Target", alert.target_lufs, TARGET_RANGE, AlertTarget, "{:.1} LUFS");
+        slider!(numbers; "For", alert.hold_secs, HOLD_RANGE, AlertHold, "{:.0} s");
+        slider!(numbers; "Cooldown", alert.cooldown_secs, COOLDOWN_RANGE, AlertCooldown, "{:.0} s");
     );
 }
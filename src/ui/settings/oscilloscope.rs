@@ -2,11 +2,16 @@
 // Copyright (C) 2026 Maika Namuo
 
 use super::{set, set_f32};
-use crate::persistence::settings::OscilloscopeSettings;
-use crate::ui::widgets::{SliderRange, pick, toggle};
-use crate::util::audio::Channel;
-use crate::visuals::oscilloscope::processor::TriggerMode;
+use crate::persistence::settings::{OscilloscopeSettings, data_dir};
+use crate::ui::widgets::ballistics::{Ballistics, ballistics_preview};
+use crate::ui::widgets::{SliderRange, action_button, pick, toggle};
+use crate::util::audio::{Channel, DEFAULT_SAMPLE_RATE};
+use crate::util::{utc_timestamp, wav};
+use crate::visuals::oscilloscope::processor::{OscilloscopeSnapshot, TriggerMode};
+use iced::widget::row;
 use std::fmt;
+use std::time::SystemTime;
+use tracing::{info, warn};
 
 settings_pane!(
     OscilloscopeSettings,
@@ -21,6 +26,9 @@ settings_pane!(
 const DURATION_RANGE: SliderRange = SliderRange::new(0.005, 0.1, 0.001);
 const PERSISTENCE_RANGE: SliderRange = SliderRange::new(0.0, 1.0, 0.01);
 const CYCLES_RANGE: SliderRange = SliderRange::new(1.0, 4.0, 1.0);
+const TRIGGER_LEVEL_RANGE: SliderRange = SliderRange::new(-60.0, 0.0, 1.0);
+// Persistence blends once per capture batch of about this many frames.
+const BATCH_FRAMES: f32 = 1024.0;
 
 #[derive(Clone, Copy, PartialEq)]
 struct TriggerSourceChoice(Channel);
@@ -32,6 +40,8 @@ impl TriggerSourceChoice {
         Self(Channel::Right),
         Self(Channel::Mid),
         Self(Channel::Side),
+        Self(Channel::Sum),
+        Self(Channel::Difference),
     ];
 }
 
@@ -66,10 +76,14 @@ settings_messages!(pane, settings, value {
     Channel1(Channel) => set(&mut settings.channel_1, value);
     Channel2(Channel) => set(&mut settings.channel_2, value);
     Stacked(bool) => set(&mut settings.stacked, value);
+    SingleShot(bool) => set(&mut settings.single_shot, value);
+    TriggerLevel(f32) => set_f32(&mut settings.trigger_level_db, value, TRIGGER_LEVEL_RANGE);
+    // The panel handles this, as only it knows which scope's capture to reach.
+    Capture(CaptureAction) => false;
 });
 
 settings_view! {
-    pane as settings {
+    pane as settings, numbers {
         let preset = TriggerPreset::from_mode(settings.trigger_mode);
         let duration_label = match preset {
             TriggerPreset::Stable => "Segment duration (fallback)",
@@ -85,11 +99,13 @@ settings_view! {
         );
         if let TriggerMode::Stable { num_cycles } = settings.trigger_mode {
             trigger = trigger.push(slider!(
+                numbers;
                 "Cycles", num_cycles as f32, CYCLES_RANGE,
                 |value| NumCycles(value.round() as usize), num_cycles.to_string()
             ));
         }
         trigger = trigger.push(slider!(
+            numbers;
             duration_label, settings.segment_duration, DURATION_RANGE, SegmentDuration,
             format!("{:.1} ms", settings.segment_duration * 1000.0)
         ));
@@ -99,9 +115,30 @@ settings_view! {
         pick("Channel 2", Channel::ALL, settings.channel_2, Channel2);
     );
     "Trigger" => trigger;
+    "Capture" => form!(
+        toggle("Single shot", settings.single_shot, SingleShot);
+        slider!(
+            numbers;
+            "Trigger level", settings.trigger_level_db, TRIGGER_LEVEL_RANGE, TriggerLevel,
+            "{:.0} dBFS"
+        );
+        row(CaptureAction::ALL.iter().map(|&action| {
+            action_button(action.to_string(), settings.single_shot.then_some(Capture(action)))
+                .into()
+        }))
+        .spacing(crate::ui::theme::CONTROL_GAP);
+    );
     "Display" => form!(
         toggle("Stacked", settings.stacked, Stacked);
-        slider!("Persistence", settings.persistence, PERSISTENCE_RANGE, Persistence, "{:.2}");
+        slider!(
+            numbers;
+            "Persistence", settings.persistence, PERSISTENCE_RANGE, Persistence, "{:.2}"
+        );
+        ballistics_preview(Ballistics::Smoothing {
+            factor: settings.persistence,
+            rate: DEFAULT_SAMPLE_RATE / BATCH_FRAMES,
+            log: false,
+        });
     );
 }
 
@@ -118,3 +155,33 @@ impl TriggerPreset {
         }
     }
 }
+
+crate::macros::choice_enum!(no_default all pub(in crate::ui) enum CaptureAction {
+    Rearm => "Re-arm",
+    Wav => "Save WAV",
+    Csv => "Save CSV",
+});
+
+// Written to the data directory off the UI thread, like rule snapshots.
+pub(in crate::ui) fn save_capture(snapshot: OscilloscopeSnapshot, action: CaptureAction) {
+    let extension = if action == CaptureAction::Wav { "wav" } else { "csv" };
+    let name = format!("capture-{}.{extension}", utc_timestamp(SystemTime::now()));
+    let path = data_dir().join(name);
+    std::thread::spawn(move || {
+        let bytes = if action == CaptureAction::Wav {
+            let channels = u16::try_from(snapshot.raw_channels).unwrap_or(0);
+            let rate = snapshot.sample_rate.round() as u32;
+            wav::encode_f32(snapshot.interleaved(), channels, rate)
+        } else {
+            Some(snapshot.to_csv().into_bytes())
+        };
+        let Some(bytes) = bytes else {
+            warn!("[oscilloscope] capture could not be encoded");
+            return;
+        };
+        match std::fs::create_dir_all(data_dir()).and_then(|()| std::fs::write(&path, bytes)) {
+            Ok(()) => info!("[oscilloscope] saved {}", path.display()),
+            Err(err) => warn!("[oscilloscope] failed to save {}: {err}", path.display()),
+        }
+    });
+}
@@ -2,51 +2,226 @@
 // Copyright (C) 2026 Maika Namuo
 
 use super::{
-    FFT_OPTIONS, HOP_DIVISORS, get_closest_hop_divisor, set, set_f32, update_fft_size,
-    update_hop_divisor,
+    FFT_OPTIONS, HOP_DIVISORS, MARKERS_PLACEHOLDER, apply_palette, get_closest_hop_divisor,
+    palette_settings, set, set_f32, set_markers, update_fft_size, update_hop_divisor,
 };
-use crate::persistence::settings::SpectrogramSettings;
-use crate::ui::widgets::{SliderRange, pick, split, toggle};
+use crate::persistence::settings::{
+    FrequencyMarker, PaletteSettings, SpectrogramPreset, SpectrogramSettings,
+};
+use crate::ui::theme;
+use crate::ui::widgets::palette_editor::PresetPreview;
+use crate::ui::widgets::{SliderRange, action_button, pick, split, text_field, toggle};
 use crate::util::audio::{FrequencyScale, WindowKind};
-use crate::visuals::options::PianoRollOverlay;
+use crate::visuals::palettes;
+use crate::visuals::options::{PianoRollOverlay, SpectrogramInterpolation};
+use crate::visuals::spectrogram::processor::{
+    MAX_GAMMA, MAX_HISTORY_MEMORY_MB, MAX_KNEE, MAX_SHARPEN, MIN_GAMMA, MIN_HISTORY_MEMORY_MB,
+    SpectrogramChannels, history_byte_cap, history_column_bytes, history_columns_within,
+};
+use iced::widget::{row, text};
+use std::sync::LazyLock;
 
 const ZERO_PAD_OPTIONS: [usize; 6] = [1, 2, 4, 8, 16, 32];
-const FLOOR_RANGE: SliderRange = SliderRange::new(-140.0, -1.0, 1.0);
+pub(super) const FLOOR_RANGE: SliderRange = SliderRange::new(-140.0, -1.0, 1.0);
 const TILT_RANGE: SliderRange = SliderRange::new(-6.0, 6.0, 0.5);
+pub(super) const GAMMA_RANGE: SliderRange = SliderRange::new(MIN_GAMMA, MAX_GAMMA, 0.05);
+const KNEE_RANGE: SliderRange = SliderRange::new(0.0, MAX_KNEE, 0.1);
+const SHARPEN_RANGE: SliderRange = SliderRange::new(0.0, MAX_SHARPEN, 0.05);
 const ROTATION_RANGE: SliderRange = SliderRange::new(-1.0, 2.0, 1.0);
+const SCROLL_RANGE: SliderRange = SliderRange::new(0.0, 1000.0, 10.0);
+const TONE_THRESHOLD_RANGE: SliderRange = SliderRange::new(6.0, 40.0, 1.0);
+const TONE_SECONDS_RANGE: SliderRange = SliderRange::new(1.0, 60.0, 1.0);
+const MEMORY_RANGE: SliderRange = SliderRange::new(
+    MIN_HISTORY_MEMORY_MB as f32,
+    MAX_HISTORY_MEMORY_MB as f32,
+    32.0,
+);
+const MIB: f64 = 1024.0 * 1024.0;
+const CUSTOM_PRESET: &str = "Custom";
 
-settings_pane!(SpectrogramSettings, init_palette(palette) {
-    palette.set_show_ramp(true);
+static BUILTIN_PRESETS: LazyLock<[SpectrogramPreset; 3]> = LazyLock::new(|| {
+    [
+        SpectrogramPreset {
+            name: "Speech".to_owned(),
+            fft_size: 1024,
+            hop_size: 128,
+            window: WindowKind::Hann,
+            frequency_scale: FrequencyScale::Erb,
+            floor_db: -80.0,
+            gamma: 1.0,
+            palette: stock_ramp("Viridis"),
+        },
+        SpectrogramPreset {
+            name: "Music".to_owned(),
+            fft_size: 4096,
+            hop_size: 512,
+            window: WindowKind::BlackmanHarris,
+            frequency_scale: FrequencyScale::Logarithmic,
+            floor_db: -96.0,
+            gamma: 1.0,
+            palette: None,
+        },
+        SpectrogramPreset {
+            name: "Bass analysis".to_owned(),
+            fft_size: 16384,
+            hop_size: 1024,
+            window: WindowKind::BlackmanHarris,
+            frequency_scale: FrequencyScale::Logarithmic,
+            floor_db: -90.0,
+            gamma: 0.8,
+            palette: stock_ramp("Magma"),
+        },
+    ]
 });
 
-settings_messages!(pane, settings, value {
+fn stock_ramp(name: &str) -> Option<PaletteSettings> {
+    let (_, colors) = palettes::spectrogram::PRESETS
+        .iter()
+        .find(|(preset, _)| *preset == name)?;
+    PaletteSettings::if_differs_from(colors, &palettes::spectrogram::COLORS)
+}
+
+fn is_builtin(name: &str) -> bool {
+    BUILTIN_PRESETS.iter().any(|preset| preset.name == name)
+}
+
+// Saved presets can't shadow built-ins, so the first match is the only one.
+fn find_preset<'a>(saved: &'a [SpectrogramPreset], name: &str) -> Option<&'a SpectrogramPreset> {
+    BUILTIN_PRESETS.iter().chain(saved).find(|preset| preset.name == name)
+}
+
+crate::macros::choice_enum!(no_default all pub(in crate::ui) enum PresetAction {
+    Save => "Save",
+    Delete => "Delete",
+});
+
+// MB per 1000 columns (px of width) across channels, and the width the cap holds.
+fn history_memory(settings: &SpectrogramSettings) -> (f64, usize) {
+    let bins = settings.fft_size * settings.zero_padding_factor.max(1) / 2 + 1;
+    let channels = 1 + u64::from(settings.channels == SpectrogramChannels::Split);
+    let column = history_column_bytes(bins, settings.use_reassignment);
+    let cap = history_byte_cap(settings.history_memory_mb, settings.channels);
+    let per_thousand = (column * channels) as f64 * 1000.0 / MIB;
+    (per_thousand, history_columns_within(cap, column))
+}
+
+settings_pane!(
+    SpectrogramSettings,
+    extra_from_settings(settings, store) {
+        markers_text: String = FrequencyMarker::format_list(&settings.markers),
+        saved_presets: Vec<SpectrogramPreset> =
+            store.borrow().data.visuals.spectrogram_presets.clone(),
+        preset_name: String = String::new(),
+    },
+    init_palette(palette) {
+        palette.set_show_ramp(true);
+        palette.set_blendable(true);
+        palette.set_presets(palettes::spectrogram::PRESETS, PresetPreview::Spectrogram);
+    }
+);
+
+settings_messages!(pane, settings, value; store {
+    Preset(String) => match find_preset(&pane.saved_presets, &value).cloned() {
+        Some(preset) => {
+            preset.apply_to(settings);
+            apply_palette(&mut pane.palette, preset.palette.as_ref());
+            pane.preset_name = preset.name;
+            true
+        }
+        None => false,
+    };
+    PresetName(String) => {
+        pane.preset_name = value;
+        false
+    };
+    ManagePreset(PresetAction) => {
+        let name = pane.preset_name.trim().to_owned();
+        if name.is_empty() || is_builtin(&name) {
+            return false;
+        }
+        pane.saved_presets.retain(|preset| preset.name != name);
+        if value == PresetAction::Save {
+            let palette = palette_settings(&pane.palette);
+            pane.saved_presets.push(SpectrogramPreset::capture(name, settings, palette));
+        }
+        let saved = pane.saved_presets.clone();
+        store.update(move |stored| stored.data.visuals.spectrogram_presets = saved);
+        false
+    };
     FftSize(usize) => update_fft_size(&mut settings.fft_size, &mut settings.hop_size, value);
     HopDivisor(usize) => update_hop_divisor(settings.fft_size, &mut settings.hop_size, value);
+    ScrollSpeed(f32) => set_f32(&mut settings.columns_per_second, value, SCROLL_RANGE);
     Window(WindowKind) => set(&mut settings.window, value);
+    Channels(SpectrogramChannels) => set(&mut settings.channels, value);
     Scale(FrequencyScale) => set(&mut settings.frequency_scale, value);
     UseReassignment(bool) => set(&mut settings.use_reassignment, value);
+    ZoomRefine(bool) => set(&mut settings.zoom_refine, value);
     FloorDb(f32) => set_f32(&mut settings.floor_db, value, FLOOR_RANGE);
     TiltDb(f32) => set_f32(&mut settings.tilt_db, value, TILT_RANGE);
+    Gamma(f32) => set_f32(&mut settings.gamma, value, GAMMA_RANGE);
+    Knee(f32) => set_f32(&mut settings.knee, value, KNEE_RANGE);
+    Interpolation(SpectrogramInterpolation) => set(&mut settings.interpolation, value);
+    Sharpen(f32) => set_f32(&mut settings.sharpen, value, SHARPEN_RANGE);
     Rotation(f32) => set(&mut settings.rotation, ROTATION_RANGE.snap(value).round() as i8);
+    InvertFrequency(bool) => set(&mut settings.invert_frequency, value);
     ZeroPadding(usize) => set(&mut settings.zero_padding_factor, value);
     PianoRoll(PianoRollOverlay) => set(&mut settings.piano_roll_overlay, value);
+    Markers(String) => set_markers(&mut pane.markers_text, &mut settings.markers, value);
+    ScaleBar(bool) => set(&mut settings.scale_bar, value);
+    PitchReadout(bool) => set(&mut settings.pitch_readout, value);
+    TimeGrid(bool) => set(&mut settings.time_grid, value);
+    PauseOnHover(bool) => set(&mut settings.pause_on_hover, value);
+    ToneLabels(bool) => set(&mut settings.tone_labels, value);
+    ToneThreshold(f32) => set_f32(&mut settings.tone_threshold_db, value, TONE_THRESHOLD_RANGE);
+    ToneSeconds(f32) => set_f32(&mut settings.tone_min_seconds, value, TONE_SECONDS_RANGE);
+    HistoryMemory(f32) => set(&mut settings.history_memory_mb, MEMORY_RANGE.snap(value) as u32);
 });
 
 settings_view! {
-    pane as settings {
+    pane as settings, numbers {
         let hop_divisor = get_closest_hop_divisor(settings.fft_size, settings.hop_size);
         let tilt_db = settings.tilt_db;
         let tilt = if tilt_db == 0.0 { "Off".to_string() } else { format!("{tilt_db:+.1} dB/oct") };
+        let knee = if settings.knee == 0.0 { "Off".to_string() } else { format!("{:.1}", settings.knee) };
+        let sharpen = if settings.sharpen == 0.0 { "Off".to_string() } else { format!("{:.2}", settings.sharpen) };
+        let cps = settings.columns_per_second;
+        let scroll = if cps > 0.0 { format!("{cps:.0} px/s") } else { "From hop".to_string() };
+        let (per_thousand, held) = history_memory(settings);
+        let palette = palette_settings(&pane.palette);
+        let presets = BUILTIN_PRESETS.iter().chain(&pane.saved_presets);
+        let preset_names: Vec<String> =
+            presets.clone().map(|preset| preset.name.clone()).collect();
+        let current = presets
+            .find(|preset| preset.matches(settings, palette.as_ref()))
+            .map_or_else(|| CUSTOM_PRESET.to_owned(), |preset| preset.name.clone());
+        let name = pane.preset_name.trim();
+        let is_saved = pane.saved_presets.iter().any(|preset| preset.name == name);
+        let can_save = !name.is_empty() && !is_builtin(name);
     }
+    "Presets" => form!(
+        pick("Preset", preset_names, current, Preset);
+        text_field("Save as", "Preset name", &pane.preset_name, PresetName);
+        row(PresetAction::ALL.iter().map(|&action| {
+            let enabled = match action {
+                PresetAction::Save => can_save,
+                PresetAction::Delete => is_saved,
+            };
+            action_button(action.to_string(), enabled.then_some(ManagePreset(action))).into()
+        }))
+        .spacing(theme::CONTROL_GAP);
+    );
     "Analysis" => split(
         form!(
             pick("FFT size", &FFT_OPTIONS[..], settings.fft_size, FftSize);
             pick("Hop divisor", &HOP_DIVISORS[..], hop_divisor, HopDivisor);
             pick("Window", WindowKind::ALL, settings.window, Window);
+            pick("Channels", SpectrogramChannels::ALL, settings.channels, Channels);
         ),
         form!(
             pick("Zero pad", &ZERO_PAD_OPTIONS[..], settings.zero_padding_factor, ZeroPadding);
             toggle("Time-frequency reassignment", settings.use_reassignment, UseReassignment);
+            toggle("Refine when zoomed", settings.zoom_refine, ZoomRefine);
+            slider!(numbers; "Scroll speed", cps, SCROLL_RANGE, ScrollSpeed, scroll);
         ),
     );
     "Display" => form!(
@@ -55,11 +230,50 @@ settings_view! {
             "Piano roll overlay", PianoRollOverlay::ALL,
             settings.piano_roll_overlay, PianoRoll
         );
-        slider!("Floor", settings.floor_db, FLOOR_RANGE, FloorDb, "{:.0} dB");
-        slider!("Spectral tilt", tilt_db, TILT_RANGE, TiltDb, tilt);
+        slider!(numbers; "Floor", settings.floor_db, FLOOR_RANGE, FloorDb, "{:.0} dB");
+        slider!(numbers; "Spectral tilt", tilt_db, TILT_RANGE, TiltDb, tilt);
+        slider!(numbers; "Gamma", settings.gamma, GAMMA_RANGE, Gamma, "{:.2}");
+        slider!(numbers; "Soft knee", settings.knee, KNEE_RANGE, Knee, knee);
+        pick(
+            "Bin interpolation", SpectrogramInterpolation::ALL,
+            settings.interpolation, Interpolation
+        );
+        slider!(numbers; "Sharpen", settings.sharpen, SHARPEN_RANGE, Sharpen, sharpen);
         slider!(
+            numbers;
             "Rotation", settings.rotation as f32, ROTATION_RANGE, Rotation,
             format!("{}\u{00b0}", settings.rotation as i32 * 90)
         );
+        toggle("Invert frequency axis", settings.invert_frequency, InvertFrequency);
+        text_field("Frequency markers", MARKERS_PLACEHOLDER, &pane.markers_text, Markers);
+        toggle("dB scale bar", settings.scale_bar, ScaleBar);
+        toggle("Time gridlines", settings.time_grid, TimeGrid);
+        toggle("Pitch readout (reassigned)", settings.pitch_readout, PitchReadout);
+        toggle("Pause on hover", settings.pause_on_hover, PauseOnHover);
+    );
+    "History memory" => form!(
+        slider!(
+            numbers;
+            "Memory cap", settings.history_memory_mb as f32, MEMORY_RANGE, HistoryMemory,
+            format!("{} MB", settings.history_memory_mb)
+        );
+        text(numbers.localize(format!(
+            "~{per_thousand:.1} MB per 1000 px of width; the cap holds {held} px"
+        )))
+            .size(theme::BODY_TEXT_SIZE)
+            .style(theme::weak_text_style);
+    );
+    "Steady tones" => form!(
+        toggle("Label steady tones", settings.tone_labels, ToneLabels);
+        slider!(
+            numbers;
+            "Prominence", settings.tone_threshold_db, TONE_THRESHOLD_RANGE, ToneThreshold,
+            "{:.0} dB"
+        );
+        slider!(
+            numbers;
+            "Minimum duration", settings.tone_min_seconds, TONE_SECONDS_RANGE, ToneSeconds,
+            "{:.0} s"
+        );
     );
 }
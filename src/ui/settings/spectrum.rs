@@ -2,30 +2,50 @@
 // Copyright (C) 2026 Maika Namuo
 
 use super::{
-    FFT_OPTIONS, HOP_DIVISORS, get_closest_hop_divisor, set, set_f32, set_usize,
-    update_fft_size, update_hop_divisor,
+    FFT_OPTIONS, HOP_DIVISORS, MARKERS_PLACEHOLDER, get_closest_hop_divisor, set, set_f32,
+    set_markers, set_usize, update_fft_size, update_hop_divisor,
+};
+use crate::persistence::settings::{
+    FrequencyMarker, MemoryTrace, SPECTRUM_MEMORY_SLOTS, SpectrumSettings,
+};
+use crate::ui::theme;
+use crate::ui::widgets::ballistics::{Ballistics, ballistics_preview};
+use crate::ui::widgets::palette_editor::PresetPreview;
+use crate::ui::widgets::{SliderRange, action_button, pick, split, text_field, toggle};
+use crate::util::audio::{Channel, DEFAULT_SAMPLE_RATE, FrequencyScale};
+use crate::visuals::palettes;
+use crate::visuals::options::{
+    SpectrumDisplayMode, SpectrumLevelGrid, SpectrumWeightingMode as WeightingMode, TraceColor,
 };
-use crate::persistence::settings::SpectrumSettings;
-use crate::ui::widgets::{SliderRange, pick, split, toggle};
-use crate::util::audio::{Channel, FrequencyScale};
-use crate::visuals::options::{SpectrumDisplayMode, SpectrumWeightingMode as WeightingMode};
 use crate::visuals::spectrum::processor::{
-    AveragingMode, MAX_SPECTRUM_DB_FLOOR, MAX_SPECTRUM_EXP_FACTOR, MAX_SPECTRUM_PEAK_DECAY,
-    MIN_SPECTRUM_DB_FLOOR, MIN_SPECTRUM_EXP_FACTOR, MIN_SPECTRUM_PEAK_DECAY,
+    AveragingMode, MAX_SPECTRUM_ATTACK_MS, MAX_SPECTRUM_CROSSOVER_HZ, MAX_SPECTRUM_DB_FLOOR,
+    MAX_SPECTRUM_EXP_FACTOR, MAX_SPECTRUM_PEAK_DECAY, MAX_SPECTRUM_RELEASE_MS,
+    MIN_SPECTRUM_ATTACK_MS, MIN_SPECTRUM_CROSSOVER_HZ, MIN_SPECTRUM_DB_FLOOR,
+    MIN_SPECTRUM_EXP_FACTOR, MIN_SPECTRUM_PEAK_DECAY, MIN_SPECTRUM_RELEASE_MS, OVERLAY_CHANNELS,
+    SHORT_FFT_DIVISOR, overlays_channel,
 };
 
 const EXP_R: SliderRange = SliderRange::new(MIN_SPECTRUM_EXP_FACTOR, MAX_SPECTRUM_EXP_FACTOR, 0.01);
 const DECAY_R: SliderRange =
     SliderRange::new(MIN_SPECTRUM_PEAK_DECAY, MAX_SPECTRUM_PEAK_DECAY, 0.5);
+const ATTACK_R: SliderRange = SliderRange::new(MIN_SPECTRUM_ATTACK_MS, MAX_SPECTRUM_ATTACK_MS, 5.0);
+// The top of the release slider means infinite hold.
+const RELEASE_R: SliderRange =
+    SliderRange::new(MIN_SPECTRUM_RELEASE_MS, MAX_SPECTRUM_RELEASE_MS, 10.0);
 const BARS_R: SliderRange = SliderRange::new(8.0, 128.0, 1.0);
 const GAP_R: SliderRange = SliderRange::new(0.0, 0.8, 0.05);
 const HIGH_R: SliderRange = SliderRange::new(0.0, 0.9, 0.01);
+const FILL_R: SliderRange = SliderRange::new(0.05, 1.0, 0.05);
+const PEAKS_R: SliderRange = SliderRange::new(0.0, 12.0, 1.0);
 const FLOOR_R: SliderRange = SliderRange::new(MIN_SPECTRUM_DB_FLOOR, MAX_SPECTRUM_DB_FLOOR, 1.0);
+const CROSSOVER_R: SliderRange =
+    SliderRange::new(MIN_SPECTRUM_CROSSOVER_HZ, MAX_SPECTRUM_CROSSOVER_HZ, 50.0);
 
 crate::macros::choice_enum!(no_default all pub(in crate::ui) enum AvgMode {
     None => "None",
     Exponential => "Exponential",
     PeakHold => "Peak hold",
+    AttackRelease => "Attack / release",
 });
 
 crate::macros::choice_enum!(no_default all pub(in crate::ui) enum FrequencyDirection {
@@ -37,12 +57,19 @@ struct AveragingControls {
     mode: AvgMode,
     factor: f32,
     peak_decay: f32,
+    attack_ms: f32,
+    release_ms: f32,
 }
 
 settings_pane!(
     SpectrumSettings,
     extra_from_settings(settings) {
         averaging: AveragingControls = split_averaging(settings.averaging),
+        markers_text: String = FrequencyMarker::format_list(&settings.markers),
+    },
+    init_palette(palette) {
+        palette.set_blendable(true);
+        palette.set_presets(palettes::spectrum::PRESETS, PresetPreview::Spectrum);
     }
 );
 
@@ -51,6 +78,9 @@ settings_messages!(pane, settings, value {
     HopDivisor(usize) => update_hop_divisor(settings.fft_size, &mut settings.hop_size, value);
     Source(Channel) => set(&mut settings.source, value);
     SecondarySource(Channel) => set(&mut settings.secondary_source, value);
+    IqInput(bool) => set(&mut settings.iq_input, value);
+    MultiRate(bool) => set(&mut settings.multi_rate, value);
+    Crossover(f32) => set_f32(&mut settings.crossover_hz, value, CROSSOVER_R);
     Scale(FrequencyScale) => set(&mut settings.frequency_scale, value);
     Direction(FrequencyDirection) => {
         set(&mut settings.reverse_frequency, value == FrequencyDirection::HighToLow)
@@ -58,38 +88,108 @@ settings_messages!(pane, settings, value {
     Display(SpectrumDisplayMode) => set(&mut settings.display_mode, value);
     Weighting(WeightingMode) => set(&mut settings.weighting_mode, value);
     SecondaryWeighting(WeightingMode) => set(&mut settings.secondary_weighting_mode, value);
+    OverlayChannel((usize, bool)) => {
+        let (channel, shown) = value;
+        let bit = 1 << channel;
+        let mask = settings.overlay_channels;
+        set(&mut settings.overlay_channels, if shown { mask | bit } else { mask & !bit })
+    };
+    OverlayColor((usize, TraceColor)) => set_overlay_color(&mut settings.overlay_colors, value);
+    TraceLegend(bool) => set(&mut settings.trace_legend, value);
+    Mirrored(bool) => set(&mut settings.mirrored, value);
+    PauseOnHover(bool) => set(&mut settings.pause_on_hover, value);
     Averaging(AvgMode) => pane.update_avg(|average| set(&mut average.mode, value));
     AvgFactor(f32) => pane.update_avg(|average| set_f32(&mut average.factor, value, EXP_R));
     PeakDecay(f32) => pane.update_avg(|average| {
         set_f32(&mut average.peak_decay, value, DECAY_R)
     });
+    Attack(f32) => pane.update_avg(|average| set_f32(&mut average.attack_ms, value, ATTACK_R));
+    Release(f32) => pane.update_avg(|average| set_f32(&mut average.release_ms, value, RELEASE_R));
     ShowGrid(bool) => set(&mut settings.show_grid, value);
+    NoteAxis(bool) => set(&mut settings.note_axis, value);
+    LevelGrid(SpectrumLevelGrid) => set(&mut settings.level_grid, value);
     ShowPeakLabel(bool) => set(&mut settings.show_peak_label, value);
+    ShowNoiseFloor(bool) => set(&mut settings.show_noise_floor, value);
+    PeakList(f32) => set_usize(&mut settings.peak_list, value, PEAKS_R);
     FloorDb(f32) => set_f32(&mut settings.floor_db, value, FLOOR_R);
     BarCount(f32) => set_usize(&mut settings.bar_count, value, BARS_R);
     BarGap(f32) => set_f32(&mut settings.bar_gap, value, GAP_R);
     Highlight(f32) => set_f32(&mut settings.highlight_threshold, value, HIGH_R);
+    FillAlpha(f32) => set_f32(&mut settings.fill_alpha, value, FILL_R);
+    Markers(String) => set_markers(&mut pane.markers_text, &mut settings.markers, value);
+    MuteMarkedBands(bool) => set(&mut settings.mute_marked_bands, value);
+    // The panel swaps this for `MemoryStored`, as only it can reach the live trace.
+    StoreMemory(usize) => false;
+    MemoryStored((usize, MemoryTrace)) => {
+        let (slot, trace) = value;
+        settings.memory.set(slot, Some(trace));
+        true
+    };
+    ShowMemory((usize, bool)) => {
+        let (slot, shown) = value;
+        match settings.memory.slots.get_mut(slot).and_then(Option::as_mut) {
+            Some(trace) => set(&mut trace.visible, shown),
+            None => false,
+        }
+    };
+    ClearMemory(usize) => {
+        let stored = settings.memory.slot(value).is_some();
+        settings.memory.set(value, None);
+        stored
+    };
+    KeepMemory(bool) => set(&mut settings.memory.keep, value);
 });
 
 settings_view! {
-    pane as settings {
+    pane as settings, numbers {
         use FrequencyDirection::{HighToLow, LowToHigh};
         let hop_divisor = get_closest_hop_divisor(settings.fft_size, settings.hop_size);
         let direction = if settings.reverse_frequency { HighToLow } else { LowToHigh };
 
-        let sources = split(
-            form!(
-                pick("Primary source", Channel::ALL, settings.source, Source);
-                pick("Primary weighting", WeightingMode::ALL, settings.weighting_mode, Weighting);
-            ),
+        let iq_input = toggle("I/Q input (left = I, right = Q)", settings.iq_input, IqInput);
+        let mut sources = if settings.iq_input {
+            form!(iq_input;)
+        } else {
             form!(
-                pick("Secondary source", Channel::ALL, settings.secondary_source, SecondarySource);
-                pick(
-                    "Secondary weighting", WeightingMode::ALL,
-                    settings.secondary_weighting_mode, SecondaryWeighting
+                split(
+                    form!(
+                        pick("Primary source", Channel::BASIC, settings.source, Source);
+                        pick(
+                            "Primary weighting", WeightingMode::ALL,
+                            settings.weighting_mode, Weighting
+                        );
+                    ),
+                    form!(
+                        pick(
+                            "Secondary source", Channel::BASIC,
+                            settings.secondary_source, SecondarySource
+                        );
+                        pick(
+                            "Secondary weighting", WeightingMode::ALL,
+                            settings.secondary_weighting_mode, SecondaryWeighting
+                        );
+                    ),
                 );
-            ),
-        );
+                iq_input;
+            )
+        };
+        for channel in (0..OVERLAY_CHANNELS).filter(|_| !settings.iq_input) {
+            let overlaid = overlays_channel(settings.overlay_channels, channel);
+            let label = format!("Overlay channel {}", channel + 1);
+            let shown = toggle(label, overlaid, move |on| OverlayChannel((channel, on)));
+            if overlaid {
+                let color = settings.overlay_colors.get(channel).copied().unwrap_or_default();
+                let color = pick("Color", TraceColor::ALL, color, move |color| {
+                    OverlayColor((channel, color))
+                });
+                sources = sources.push(split(shown, color));
+            } else {
+                sources = sources.push(shown);
+            }
+        }
+        if settings.overlay_channels != 0 && !settings.iq_input {
+            sources = sources.push(toggle("Trace legend", settings.trace_legend, TraceLegend));
+        }
         let mut analysis = form!(
             split(
                 form!(
@@ -104,16 +204,54 @@ settings_view! {
         );
         match pane.averaging.mode {
             AvgMode::Exponential => {
-                analysis = analysis.push(slider!(
-                    "Exp factor", pane.averaging.factor, EXP_R, AvgFactor, "{:.2}"
-                ));
+                analysis = analysis
+                    .push(slider!(
+                        numbers;
+                        "Exp factor", pane.averaging.factor, EXP_R, AvgFactor, "{:.2}"
+                    ))
+                    .push(ballistics_preview(Ballistics::Smoothing {
+                        factor: pane.averaging.factor,
+                        rate: DEFAULT_SAMPLE_RATE / settings.hop_size.max(1) as f32,
+                        log: true,
+                    }));
             }
             AvgMode::PeakHold => {
+                analysis = analysis
+                    .push(slider!(
+                        numbers;
+                        "Peak decay", pane.averaging.peak_decay, DECAY_R, PeakDecay, "{:.1} dB/s"
+                    ))
+                    .push(ballistics_preview(Ballistics::PeakDecay {
+                        db_per_second: pane.averaging.peak_decay,
+                    }));
+            }
+            AvgMode::AttackRelease => {
+                let AveragingControls { attack_ms, release_ms, .. } = pane.averaging;
+                let release = release_from_slider(release_ms);
+                analysis = analysis
+                    .push(slider!(numbers; "Attack", attack_ms, ATTACK_R, Attack, "{:.0} ms"))
+                    .push(slider!(
+                        numbers;
+                        "Release", release_ms, RELEASE_R, Release,
+                        release.map_or_else(|| "Hold".to_string(), |ms| format!("{ms:.0} ms"))
+                    ))
+                    .push(ballistics_preview(Ballistics::AttackRelease {
+                        attack_seconds: attack_ms / 1_000.0,
+                        release_seconds: release.map(|ms| ms / 1_000.0),
+                    }));
+            }
+            AvgMode::None => {}
+        }
+        if !settings.iq_input {
+            let label = format!("Multi-rate (1/{SHORT_FFT_DIVISOR} FFT above the crossover)");
+            analysis = analysis.push(toggle(label, settings.multi_rate, MultiRate));
+            if settings.multi_rate {
                 analysis = analysis.push(slider!(
-                    "Peak decay", pane.averaging.peak_decay, DECAY_R, PeakDecay, "{:.1} dB/s"
+                    numbers;
+                    "Crossover", settings.crossover_hz, CROSSOVER_R, Crossover,
+                    numbers.freq(settings.crossover_hz)
                 ));
             }
-            AvgMode::None => {}
         }
 
         let mut display = form!(
@@ -122,30 +260,85 @@ settings_view! {
                 form!(
                     pick("Direction", FrequencyDirection::ALL, direction, Direction);
                     toggle("Frequency grid", settings.show_grid, ShowGrid);
+                    toggle("Note names on log axis", settings.note_axis, NoteAxis);
+                    pick("Level grid", SpectrumLevelGrid::ALL, settings.level_grid, LevelGrid);
+                ),
+                form!(
+                    toggle("Peak label", settings.show_peak_label, ShowPeakLabel);
+                    toggle("Noise estimate + SNR", settings.show_noise_floor, ShowNoiseFloor);
+                    toggle("Mirror around the center", settings.mirrored, Mirrored);
+                    toggle("Pause on hover", settings.pause_on_hover, PauseOnHover);
                 ),
-                form!(toggle("Peak label", settings.show_peak_label, ShowPeakLabel);),
             );
-            slider!("Noise floor", settings.floor_db, FLOOR_R, FloorDb, "{:.0} dB");
+            slider!(numbers; "Noise floor", settings.floor_db, FLOOR_R, FloorDb, "{:.0} dB");
         );
         if settings.display_mode == SpectrumDisplayMode::Bar {
             display = display
                 .push(slider!(
+                    numbers;
                     "Bar count", settings.bar_count as f32, BARS_R, BarCount,
                     settings.bar_count.to_string()
                 ))
                 .push(slider!(
+                    numbers;
                     "Bar gap", settings.bar_gap, GAP_R, BarGap,
                     format!("{:.0}%", settings.bar_gap * 100.0)
                 ));
         }
+        if settings.display_mode == SpectrumDisplayMode::Fill {
+            display = display.push(slider!(
+                numbers;
+                "Fill opacity", settings.fill_alpha, FILL_R, FillAlpha,
+                format!("{:.0}%", settings.fill_alpha * 100.0)
+            ));
+        }
         display = display.push(slider!(
+            numbers;
             "Color floor", settings.highlight_threshold, HIGH_R, Highlight,
             format!("{:.0}%", settings.highlight_threshold * 100.0)
         ));
+        display = display.push(slider!(
+            numbers;
+            "Peak list", settings.peak_list as f32, PEAKS_R, PeakList,
+            match settings.peak_list {
+                0 => "Off".to_string(),
+                n => n.to_string(),
+            }
+        ));
+        display = display.push(text_field(
+            "Frequency markers", MARKERS_PLACEHOLDER, &pane.markers_text, Markers
+        ));
+        display = display.push(toggle(
+            "Mute marked bands in all visuals (not playback)",
+            settings.mute_marked_bands,
+            MuteMarkedBands,
+        ));
+
+        let mut memory = form!();
+        for slot in 0..SPECTRUM_MEMORY_SLOTS {
+            let stored = settings.memory.slot(slot);
+            let mut controls = iced::widget::row![
+                iced::widget::text(format!("M{}", slot + 1)).size(theme::BODY_TEXT_SIZE),
+                action_button("Store", Some(StoreMemory(slot))),
+                action_button("Clear", stored.map(|_| ClearMemory(slot))),
+            ]
+            .spacing(theme::CONTROL_GAP)
+            .align_y(iced::alignment::Vertical::Center);
+            if let Some(trace) = stored {
+                controls = controls.push(toggle("Show", trace.visible, move |on| {
+                    ShowMemory((slot, on))
+                }));
+            }
+            memory = memory.push(controls);
+        }
+        memory = memory.push(toggle(
+            "Save memory with the profile", settings.memory.keep, KeepMemory
+        ));
     }
     "Sources" => sources;
     "Analysis" => analysis;
     "Display" => display;
+    "Memory" => memory;
 }
 
 impl Pane {
@@ -161,26 +354,49 @@ impl Pane {
             AvgMode::PeakHold => AveragingMode::PeakHold {
                 decay_per_second: self.averaging.peak_decay,
             },
+            AvgMode::AttackRelease => AveragingMode::AttackRelease {
+                attack_ms: self.averaging.attack_ms,
+                release_ms: release_from_slider(self.averaging.release_ms),
+            },
         };
         true
     }
 }
 
+fn set_overlay_color(colors: &mut Vec<TraceColor>, (channel, color): (usize, TraceColor)) -> bool {
+    if colors.len() <= channel {
+        colors.resize(channel + 1, TraceColor::Auto);
+    }
+    set(&mut colors[channel], color)
+}
+
+fn release_from_slider(release_ms: f32) -> Option<f32> {
+    (release_ms < MAX_SPECTRUM_RELEASE_MS).then_some(release_ms)
+}
+
 fn split_averaging(avg: AveragingMode) -> AveragingControls {
-    let default_factor = AveragingMode::default_exponential_factor();
-    let default_peak_decay = AveragingMode::default_peak_decay();
-    let (mode, factor, peak_decay) = match avg {
-        AveragingMode::None => (AvgMode::None, default_factor, default_peak_decay),
+    let mut controls = AveragingControls {
+        mode: AvgMode::None,
+        factor: AveragingMode::default_exponential_factor(),
+        peak_decay: AveragingMode::default_peak_decay(),
+        attack_ms: AveragingMode::default_attack_ms(),
+        release_ms: AveragingMode::default_release_ms(),
+    };
+    match avg {
+        AveragingMode::None => {}
         AveragingMode::Exponential { factor } => {
-            (AvgMode::Exponential, factor, default_peak_decay)
+            controls.mode = AvgMode::Exponential;
+            controls.factor = factor;
         }
         AveragingMode::PeakHold { decay_per_second } => {
-            (AvgMode::PeakHold, default_factor, decay_per_second)
+            controls.mode = AvgMode::PeakHold;
+            controls.peak_decay = decay_per_second;
+        }
+        AveragingMode::AttackRelease { attack_ms, release_ms } => {
+            controls.mode = AvgMode::AttackRelease;
+            controls.attack_ms = attack_ms;
+            controls.release_ms = release_ms.unwrap_or(MAX_SPECTRUM_RELEASE_MS);
         }
-    };
-    AveragingControls {
-        mode,
-        factor,
-        peak_decay,
     }
+    controls
 }
@@ -27,6 +27,10 @@ settings_messages!(pane, settings, value {
     );
     Flip(bool) => set(&mut settings.flip, value);
     Unipolar(bool) => set(&mut settings.unipolar, value);
+    AxisLines(bool) => set(&mut settings.axis_lines, value);
+    LrGuides(bool) => set(&mut settings.lr_guides, value);
+    DbRings(bool) => set(&mut settings.db_rings, value);
+    GridLabels(bool) => set(&mut settings.grid_labels, value);
     Mode(StereometerMode) => set(&mut settings.mode, value);
     Scale(StereometerScale) => set(&mut settings.scale, value);
     CorrelationMeter(CorrelationMeterMode) => set(&mut settings.correlation_meter, value);
@@ -34,15 +38,17 @@ settings_messages!(pane, settings, value {
 });
 
 settings_view! {
-    pane as settings {
+    pane as settings, numbers {
         let dot_mode = settings.mode != StereometerMode::Lissajous;
         let mut meter = form!(
             pick("Mode", StereometerMode::ALL, settings.mode, Mode);
             slider!(
+                numbers;
                 "Segment duration", settings.segment_duration, DURATION_RANGE,
                 SegmentDuration, format!("{:.1} ms", settings.segment_duration * 1000.0)
             );
             slider!(
+                numbers;
                 "Sample count", settings.target_sample_count as f32, SAMPLE_COUNT_RANGE,
                 SampleCount, settings.target_sample_count.to_string()
             );
@@ -53,6 +59,7 @@ settings_view! {
 
         let mut display = form!(
             slider!(
+                numbers;
                 "Rotation", settings.rotation as f32, ROTATION_RANGE,
                 Rotation, settings.rotation.to_string()
             );
@@ -64,12 +71,28 @@ settings_view! {
                     toggle("Unipolar", settings.unipolar, Unipolar),
                 ))
                 .push(slider!(
+                    numbers;
                     "Dot size", settings.dot_radius, DOT_RANGE, DotRadius, "{:.1}px"
                 ));
         } else {
             display = display.push(toggle("Flip", settings.flip, Flip));
         }
 
+        let mut grid = form!(
+            split(
+                toggle("Axes", settings.axis_lines, AxisLines),
+                toggle("L/R guides", settings.lr_guides, LrGuides),
+            );
+        );
+        grid = if dot_mode && settings.scale == StereometerScale::Scaled {
+            grid.push(split(
+                toggle("dB rings", settings.db_rings, DbRings),
+                toggle("Labels", settings.grid_labels, GridLabels),
+            ))
+        } else {
+            grid.push(toggle("Labels", settings.grid_labels, GridLabels))
+        };
+
         let mut correlation = form!(
             pick(
                 "Meter", CorrelationMeterMode::ALL, settings.correlation_meter,
@@ -83,6 +106,7 @@ settings_view! {
                     CorrelationSide,
                 ))
                 .push(slider!(
+                    numbers;
                     "Window", settings.correlation_window, CORRELATION_RANGE,
                     CorrelationWindow,
                     format!("{:.0} ms", settings.correlation_window * 1000.0)
@@ -91,5 +115,6 @@ settings_view! {
     }
     "Meter" => meter;
     "Display" => display;
+    "Grid" => grid;
     "Phase Correlation" => correlation;
 }
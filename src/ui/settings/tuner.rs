@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+use super::{set, set_f32};
+use crate::persistence::settings::TunerSettings;
+use crate::ui::widgets::{SliderRange, pick};
+use crate::visuals::options::TunerPreset;
+
+settings_pane!(TunerSettings);
+
+const IN_TUNE_RANGE: SliderRange = SliderRange::new(0.5, 10.0, 0.5);
+
+settings_messages!(pane, settings, value {
+    Preset(TunerPreset) => set(&mut settings.preset, value);
+    InTune(f32) => set_f32(&mut settings.in_tune_cents, value, IN_TUNE_RANGE);
+});
+
+settings_view! {
+    pane as settings, numbers {}
+    "Tuner" => form!(
+        pick("Strings", TunerPreset::ALL, settings.preset, Preset);
+        slider!(
+            numbers;
+            "In tune within", settings.in_tune_cents, IN_TUNE_RANGE, InTune, "±{:.1} cents"
+        );
+    );
+}
@@ -2,8 +2,9 @@
 // Copyright (C) 2026 Maika Namuo
 
 use super::{set, set_f32};
+use crate::dsp::clicks::{MAX_CLICK_THRESHOLD, MIN_CLICK_THRESHOLD};
 use crate::persistence::settings::WaveformSettings;
-use crate::ui::widgets::{SliderRange, palette_editor::PaletteEditor, pick};
+use crate::ui::widgets::{SliderRange, palette_editor::PaletteEditor, pick, toggle};
 use crate::util::audio::Channel;
 use crate::visuals::options::{WaveformColorMode, WaveformHistoryMode};
 use crate::visuals::waveform::processor::{
@@ -17,12 +18,20 @@ settings_pane!(WaveformSettings, init_palette(palette, settings) {
 const SPEED_RANGE: SliderRange = SliderRange::new(MIN_SCROLL_SPEED, MAX_SCROLL_SPEED, 1.0);
 const FLOOR_RANGE: SliderRange =
     SliderRange::new(MIN_BAND_DB_FLOOR, MAX_BAND_DB_FLOOR, 1.0);
+const CLICK_RANGE: SliderRange = SliderRange::new(MIN_CLICK_THRESHOLD, MAX_CLICK_THRESHOLD, 1.0);
 
 fn configure_palette_for_mode(palette: &mut PaletteEditor, mode: WaveformColorMode) {
     palette.set_visible_indices((mode == WaveformColorMode::Static).then_some(&[0][..]));
+    palette.set_blendable(!matches!(
+        mode,
+        WaveformColorMode::Frequency | WaveformColorMode::Static
+    ));
     palette.set_label_overrides(match mode {
         WaveformColorMode::Static => &[(0, "Color")],
-        WaveformColorMode::Loudness => &[(0, "Quiet"), (1, "->"), (2, "Loud")],
+        WaveformColorMode::Loudness | WaveformColorMode::Rms => {
+            &[(0, "Quiet"), (1, "->"), (2, "Loud")]
+        }
+        WaveformColorMode::Brightness => &[(0, "Dark"), (1, "->"), (2, "Bright")],
         WaveformColorMode::Frequency => &[],
     });
 }
@@ -40,25 +49,44 @@ settings_messages!(pane, settings, value {
         changed
     };
     HistoryMode(WaveformHistoryMode) => set(&mut settings.history_mode, value);
+    RmsLayer(bool) => set(&mut settings.rms_layer, value);
+    DetectClicks(bool) => set(&mut settings.detect_clicks, value);
+    ClickThreshold(f32) => set_f32(&mut settings.click_threshold, value, CLICK_RANGE);
 });
 
 settings_view! {
-    pane as settings {
+    pane as settings, numbers {
         let mut display = form!(
-            slider!("Scroll speed", settings.scroll_speed, SPEED_RANGE, ScrollSpeed, "{:.0} px/s");
+            slider!(
+                numbers;
+                "Scroll speed", settings.scroll_speed, SPEED_RANGE, ScrollSpeed, "{:.0} px/s"
+            );
             pick("Color mode", WaveformColorMode::ALL, settings.color_mode, ColorMode);
             pick("History", WaveformHistoryMode::ALL, settings.history_mode, HistoryMode);
+            toggle("RMS inside peaks", settings.rms_layer, RmsLayer);
         );
         if settings.history_mode != WaveformHistoryMode::Off {
             display = display.push(slider!(
+                numbers;
                 "History floor", settings.band_db_floor, FLOOR_RANGE,
                 BandDbFloor, "{:.0} dB"
             ));
         }
+        let mut clicks = form!(
+            toggle("Mark clicks and dropouts", settings.detect_clicks, DetectClicks);
+        );
+        if settings.detect_clicks {
+            clicks = clicks.push(slider!(
+                numbers;
+                "Threshold", settings.click_threshold, CLICK_RANGE,
+                ClickThreshold, "{:.0}× RMS"
+            ));
+        }
     }
     "Signal" => form!(
-        pick("Channel 1", Channel::ALL, settings.channel_1, Channel1);
-        pick("Channel 2", Channel::ALL, settings.channel_2, Channel2);
+        pick("Channel 1", Channel::BASIC, settings.channel_1, Channel1);
+        pick("Channel 2", Channel::BASIC, settings.channel_2, Channel2);
     );
     "Display" => display;
+    "Clicks" => clicks;
 }
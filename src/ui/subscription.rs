@@ -8,6 +8,8 @@ use iced::futures::{StreamExt, stream::BoxStream};
 use std::fmt;
 use std::hash::Hasher as _;
 use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
 
 pub fn channel_subscription<T>(receiver: Arc<AsyncReceiver<T>>) -> Subscription<T>
 where
@@ -16,6 +18,12 @@ where
     from_recipe(ChannelRecipe { receiver })
 }
 
+/// Ticks every `period` from a thread of its own; iced's timers need an async
+/// runtime this build leaves out.
+pub fn every(period: Duration) -> Subscription<Instant> {
+    from_recipe(TickRecipe { period })
+}
+
 struct ChannelRecipe<T> {
     receiver: Arc<AsyncReceiver<T>>,
 }
@@ -35,6 +43,38 @@ where
     }
 }
 
+#[derive(Debug)]
+struct TickRecipe {
+    period: Duration,
+}
+
+impl Recipe for TickRecipe {
+    type Output = Instant;
+
+    fn hash(&self, state: &mut Hasher) {
+        state.write_u128(self.period.as_nanos());
+    }
+
+    fn stream(self: Box<Self>, _input: EventStream) -> BoxStream<'static, Instant> {
+        let (sender, receiver) = async_channel::bounded(1);
+        let period = self.period;
+        // Ends once the subscription drops the receiver.
+        let spawned = thread::Builder::new()
+            .name("openmeters-tick".into())
+            .spawn(move || {
+                while !sender.is_closed() {
+                    thread::sleep(period);
+                    // A tick still waiting to be read covers this one.
+                    let _ = sender.try_send(Instant::now());
+                }
+            });
+        if let Err(err) = spawned {
+            tracing::warn!("[ui] failed to spawn tick thread: {err}");
+        }
+        receiver.boxed()
+    }
+}
+
 impl<T> fmt::Debug for ChannelRecipe<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ChannelRecipe").finish_non_exhaustive()
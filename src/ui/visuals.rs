@@ -1,34 +1,46 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // Copyright (C) 2026 Maika Namuo
 
-use crate::persistence::settings::SettingsHandle;
+use crate::persistence::settings::{SettingsHandle, data_dir};
 use crate::ui::widgets::pane_grid::{self, Content as PaneContent, Pane};
+use crate::util::utc_timestamp;
 use crate::visuals::registry::{
-    VisualContent, VisualKind, VisualManagerHandle, VisualSlotSnapshot,
+    VisualContent, VisualId, VisualKind, VisualManagerHandle, VisualSlotSnapshot,
 };
+use crate::visuals::waveform::state::WaveformClip;
 use iced::widget::{container, text};
 use iced::{Element, Length, Task};
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+use tracing::{info, warn};
 
 #[derive(Debug, Clone)]
 pub enum VisualsMessage {
     PaneDragged(pane_grid::DragEvent),
     PaneResized(pane_grid::ResizeWidths),
     PaneContextRequested(Pane),
+    PaneExportRequested(Pane),
     PaneHovered(Option<Pane>),
-    SettingsRequested(VisualKind),
+    SettingsRequested(VisualId),
 }
 
 #[derive(Clone)]
 struct VisualPane {
-    kind: VisualKind,
+    id: VisualId,
     content: VisualContent,
     min_width: f32,
+    max_width: f32,
     width_basis: f32,
+    min_height: f32,
+    height_basis: f32,
 }
 
 impl VisualPane {
     fn view(&self) -> PaneContent<'_, VisualsMessage> {
-        PaneContent::new(self.content.render()).with_width_basis(self.min_width, self.width_basis)
+        PaneContent::new(self.content.render())
+            .with_width_basis(self.min_width, self.width_basis)
+            .with_max_width(self.max_width)
+            .with_height_basis(self.min_height, self.height_basis)
     }
 }
 
@@ -54,18 +66,25 @@ impl VisualsPage {
 
     pub fn update(&mut self, message: VisualsMessage) -> Task<VisualsMessage> {
         match message {
-            VisualsMessage::PaneResized(widths) => {
-                let bases = self.apply_resize_width_basis(&widths);
+            VisualsMessage::PaneResized(lengths) => {
+                let vertical = self.vertical();
+                let bases = self.apply_resize_basis(&lengths, vertical);
                 if !bases.is_empty() {
-                    self.settings
-                        .update(|s| s.data.visuals.width_basis.extend(bases));
+                    self.settings.update(|s| {
+                        let saved = if vertical {
+                            &mut s.data.visuals.height_basis
+                        } else {
+                            &mut s.data.visuals.width_basis
+                        };
+                        saved.extend(bases);
+                    });
                 }
             }
             VisualsMessage::PaneDragged(pane_grid::DragEvent::Moved { pane, target }) => {
                 if let Some(panes) = self.panes.as_mut()
                     && panes.move_to(pane, target)
                 {
-                    let order: Vec<_> = panes.iter().map(|(_, p)| p.kind).collect();
+                    let order: Vec<_> = panes.iter().map(|(_, p)| p.id).collect();
                     self.visual_manager.borrow_mut().reorder(&order);
                 }
             }
@@ -76,7 +95,33 @@ impl VisualsPage {
             }
             VisualsMessage::PaneContextRequested(pane) => {
                 if let Some(p) = self.panes.as_ref().and_then(|ps| ps.get(pane)) {
-                    return Task::done(VisualsMessage::SettingsRequested(p.kind));
+                    return Task::done(VisualsMessage::SettingsRequested(p.id));
+                }
+            }
+            // Shift+right-click saves what a waveform shows or the peaks of a
+            // spectrogram selection, since a plain right-click already opens
+            // the visual's settings; elsewhere it's a plain right-click.
+            VisualsMessage::PaneExportRequested(pane) => {
+                let Some(id) = self
+                    .panes
+                    .as_ref()
+                    .and_then(|ps| ps.get(pane))
+                    .map(|p| p.id)
+                else {
+                    return Task::none();
+                };
+                match id.kind {
+                    VisualKind::Waveform => match self.visual_manager.borrow().waveform_clip(id) {
+                        Some(clip) => save_clip(clip),
+                        None => warn!("[waveform] no audio retained for the visible window"),
+                    },
+                    VisualKind::Spectrogram => {
+                        match self.visual_manager.borrow().spectrogram_peaks_csv(id) {
+                            Some(csv) => save_peaks(csv),
+                            None => warn!("[spectrogram] shift+drag a region to export its peaks"),
+                        }
+                    }
+                    _ => return Task::done(VisualsMessage::SettingsRequested(id)),
                 }
             }
             VisualsMessage::PaneHovered(pane) => self.hovered_pane = pane,
@@ -85,8 +130,12 @@ impl VisualsPage {
         Task::none()
     }
 
-    pub fn hovered_visual(&self) -> Option<VisualKind> {
-        self.panes.as_ref()?.get(self.hovered_pane?).map(|p| p.kind)
+    fn vertical(&self) -> bool {
+        self.settings.borrow().data.visuals.vertical
+    }
+
+    pub fn hovered_visual(&self) -> Option<VisualId> {
+        self.panes.as_ref()?.get(self.hovered_pane?).map(|p| p.id)
     }
 
     pub fn view(&self, reorder_enabled: bool) -> Element<'_, VisualsMessage> {
@@ -99,11 +148,18 @@ impl VisualsPage {
                 .into();
         };
 
+        let axis = if self.vertical() {
+            pane_grid::Axis::Vertical
+        } else {
+            pane_grid::Axis::Horizontal
+        };
         let mut grid = pane_grid::PaneGrid::new(panes, |_, p| p.view())
+            .axis(axis)
             .width(Length::Fill)
             .height(Length::Fill)
             .on_resize(VisualsMessage::PaneResized)
             .on_context_request(VisualsMessage::PaneContextRequested)
+            .on_shift_context_request(VisualsMessage::PaneExportRequested)
             .on_hover(VisualsMessage::PaneHovered);
 
         if reorder_enabled {
@@ -118,33 +174,40 @@ impl VisualsPage {
     pub(in crate::ui) fn apply_snapshot_excluding(
         &mut self,
         snapshot: &[VisualSlotSnapshot],
-        exclude: impl Fn(VisualKind) -> bool,
+        exclude: impl Fn(VisualId) -> bool,
     ) {
-        let slots = || snapshot.iter().filter(|s| s.enabled && !exclude(s.kind));
+        let slots = || snapshot.iter().filter(|s| s.enabled && !exclude(s.id));
         if slots().next().is_none() {
             self.panes = None;
             self.hovered_pane = None;
             return;
         }
-        if self.panes.as_ref().is_none_or(|panes| {
-            panes
-                .iter()
-                .map(|(_, p)| p.kind)
-                .ne(slots().map(|s| s.kind))
-        }) {
+        if self
+            .panes
+            .as_ref()
+            .is_none_or(|panes| panes.iter().map(|(_, p)| p.id).ne(slots().map(|s| s.id)))
+        {
             let settings = self.settings.borrow();
-            let saved_width_basis = &settings.data.visuals.width_basis;
-            self.panes = Some(pane_grid::State::from_iter(slots().map(|slot| {
-                VisualPane {
-                    kind: slot.kind,
+            let saved = |bases: &BTreeMap<VisualId, f32>, id: VisualId, default: f32| {
+                bases
+                    .get(&id)
+                    .copied()
+                    .and_then(crate::util::finite_positive)
+                    .unwrap_or(default)
+            };
+            let visuals = &settings.data.visuals;
+            // Keyed by id so a pane keeps its identity (and animates) across rebuilds.
+            self.panes = Some(pane_grid::State::from_keyed(slots().map(|slot| {
+                let pane = VisualPane {
+                    id: slot.id,
                     content: slot.content.clone(),
                     min_width: slot.min_width,
-                    width_basis: saved_width_basis
-                        .get(&slot.kind)
-                        .copied()
-                        .and_then(crate::util::finite_positive)
-                        .unwrap_or(slot.default_width_basis),
-                }
+                    max_width: slot.max_width,
+                    width_basis: saved(&visuals.width_basis, slot.id, slot.default_width_basis),
+                    min_height: slot.min_height,
+                    height_basis: saved(&visuals.height_basis, slot.id, slot.default_height_basis),
+                };
+                (slot.id.index(), pane)
             })));
             self.hovered_pane = None;
             return;
@@ -156,18 +219,57 @@ impl VisualsPage {
         }
     }
 
-    fn apply_resize_width_basis(&mut self, widths: &[(Pane, f32)]) -> Vec<(VisualKind, f32)> {
+    fn apply_resize_basis(
+        &mut self,
+        lengths: &[(Pane, f32)],
+        vertical: bool,
+    ) -> Vec<(VisualId, f32)> {
         let Some(panes) = self.panes.as_mut() else {
             return Vec::new();
         };
-        widths
+        lengths
             .iter()
             .filter_map(|&(pane, basis)| {
                 let basis = crate::util::finite_positive(basis)?;
                 let visual = panes.get_mut(pane)?;
-                visual.width_basis = basis;
-                Some((visual.kind, basis))
+                if vertical {
+                    visual.height_basis = basis;
+                } else {
+                    visual.width_basis = basis;
+                }
+                Some((visual.id, basis))
             })
             .collect()
     }
 }
+
+// Written off the UI thread, like oscilloscope captures.
+fn save_peaks(csv: String) {
+    let name = format!("spectrogram-peaks-{}.csv", utc_timestamp(SystemTime::now()));
+    let path = data_dir().join(name);
+    std::thread::spawn(move || {
+        match std::fs::create_dir_all(data_dir()).and_then(|()| std::fs::write(&path, csv)) {
+            Ok(()) => info!("[spectrogram] saved peaks to {}", path.display()),
+            Err(err) => warn!("[spectrogram] failed to save {}: {err}", path.display()),
+        }
+    });
+}
+
+fn save_clip(clip: WaveformClip) {
+    let name = format!("waveform-{}.wav", utc_timestamp(SystemTime::now()));
+    let path = data_dir().join(name);
+    std::thread::spawn(move || {
+        let Some(bytes) = clip.to_wav() else {
+            warn!("[waveform] clip could not be encoded");
+            return;
+        };
+        match std::fs::create_dir_all(data_dir()).and_then(|()| std::fs::write(&path, bytes)) {
+            Ok(()) => info!(
+                "[waveform] saved {:.1} s to {}",
+                clip.seconds(),
+                path.display()
+            ),
+            Err(err) => warn!("[waveform] failed to save {}: {err}", path.display()),
+        }
+    });
+}
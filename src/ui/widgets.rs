@@ -1,11 +1,14 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // Copyright (C) 2026 Maika Namuo
 
+pub(super) mod ballistics;
 pub(super) mod palette_editor;
 pub(super) mod pane_grid;
 pub(super) mod scroll_glow;
+pub(super) mod session_plot;
 
 use crate::ui::theme;
+use crate::util::audio::NumberFormat;
 use iced::{
     Element,
     Length::{Fill, Shrink},
@@ -14,11 +17,41 @@ use iced::{
         Button, Column, Container, Row, Toggler, button, column, container, pick_list, row, slider,
         text,
         text::{IntoFragment, Wrapping},
-        toggler,
+        text_input, toggler,
     },
 };
+use std::time::{Duration, Instant};
 use std::{borrow::Cow, fmt};
 
+const TRIAL_PERIOD: Duration = Duration::from_secs(10);
+
+/// A risky change applied on trial: `previous` goes back in unless the change
+/// is kept within [`TRIAL_PERIOD`].
+pub(super) struct Trial<T> {
+    pub(super) previous: T,
+    deadline: Instant,
+}
+
+impl<T> Trial<T> {
+    pub(super) fn new(previous: T, now: Instant) -> Self {
+        Self {
+            previous,
+            deadline: now + TRIAL_PERIOD,
+        }
+    }
+
+    pub(super) fn is_due(&self, now: Instant) -> bool {
+        now >= self.deadline
+    }
+
+    pub(super) fn seconds_left(&self, now: Instant) -> u64 {
+        self.deadline
+            .saturating_duration_since(now)
+            .as_secs_f32()
+            .ceil() as u64
+    }
+}
+
 pub(super) struct SliderRange {
     pub(super) min: f32,
     pub(super) max: f32,
@@ -55,12 +88,14 @@ pub(super) fn clipped_text<'a, M: 'a>(
 }
 
 pub(super) fn slide<'a, M: Clone + 'a>(
+    numbers: NumberFormat,
     label: impl IntoFragment<'a>,
     value: f32,
     formatted: impl IntoFragment<'a>,
     range: SliderRange,
     on_change: impl Fn(f32) -> M + 'a,
 ) -> Column<'a, M> {
+    let formatted = numbers.localize(formatted.into_fragment().into_owned());
     column![
         row![
             clipped_text(label, theme::BODY_TEXT_SIZE).width(Fill),
@@ -118,6 +153,23 @@ where
     .width(Fill)
 }
 
+pub(super) fn text_field<'a, M: Clone + 'a>(
+    label: impl IntoFragment<'a>,
+    placeholder: &str,
+    value: &str,
+    on_input: impl Fn(String) -> M + 'a,
+) -> Column<'a, M> {
+    column![
+        clipped_text(label, theme::BODY_TEXT_SIZE),
+        text_input(placeholder, value)
+            .on_input(on_input)
+            .size(theme::BODY_TEXT_SIZE)
+            .width(Fill),
+    ]
+    .spacing(theme::CONTROL_GAP)
+    .width(Fill)
+}
+
 pub(super) fn toggle<'a, M: 'a>(
     label: impl IntoFragment<'a>,
     value: bool,
@@ -151,3 +203,24 @@ pub(super) fn selectable_button<'a, M: Clone + 'a>(
         .style(move |theme, status| theme::button_style(theme, selected, status))
         .on_press(message)
 }
+
+pub(super) fn keep_changes_card<'a, M: Clone + 'a>(
+    seconds_left: u64,
+    keep: M,
+    revert: M,
+) -> Container<'a, M> {
+    card(
+        "Keep changes?",
+        row![
+            clipped_text(
+                format!("Reverting in {seconds_left} s"),
+                theme::BODY_TEXT_SIZE
+            )
+            .width(Fill),
+            action_button("Keep", Some(keep)),
+            action_button("Revert", Some(revert)),
+        ]
+        .spacing(theme::CONTROL_GAP)
+        .align_y(Vertical::Center),
+    )
+}
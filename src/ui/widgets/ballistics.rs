@@ -0,0 +1,244 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Loops a tone burst through a meter's time constants so settings panels can
+// show how a slider changes the response without switching to the live view.
+
+use crate::util::color::with_alpha;
+use iced::advanced::renderer::{self, Quad};
+use iced::advanced::widget::{Tree, tree};
+use iced::advanced::{Clipboard, Layout, Renderer as _, Shell, Widget, layout, mouse};
+use iced::{Background, Element, Event, Length, Point, Rectangle, Size, window};
+use std::time::Instant;
+
+const HEIGHT: f32 = 40.0;
+const PERIOD_SECONDS: f32 = 2.0;
+const BURST_SECONDS: f32 = 0.5;
+// Range shown for responses plotted in dB.
+const RANGE_DB: f32 = 60.0;
+const COLUMN_WIDTH: f32 = 2.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(in crate::ui) enum Ballistics {
+    /// One-pole smoothing applied `rate` times a second; `log` plots it in dB.
+    Smoothing { factor: f32, rate: f32, log: bool },
+    /// Instant attack followed by a fixed fall in dB per second.
+    PeakDecay { db_per_second: f32 },
+    /// Separate one-pole time constants on power, plotted in dB; no release
+    /// holds the peak.
+    AttackRelease {
+        attack_seconds: f32,
+        release_seconds: Option<f32>,
+    },
+}
+
+impl Ballistics {
+    /// Displayed level, 0 to 1, `t` seconds into the loop. The burst fills
+    /// the first `BURST_SECONDS`; the loop starts from the settled floor.
+    fn response(self, t: f32) -> f32 {
+        let t = t.rem_euclid(PERIOD_SECONDS);
+        match self {
+            Self::Smoothing { factor, rate, log } => {
+                let alpha = factor.clamp(0.0, 0.9999);
+                let floor = if log { db_to_power(-RANGE_DB) } else { 0.0 };
+                let frames = |seconds: f32| (seconds * rate.max(1.0)).floor();
+                let settle = |from: f32, to: f32, seconds: f32| {
+                    to + (from - to) * alpha.powf(frames(seconds))
+                };
+                let peak = settle(floor, 1.0, BURST_SECONDS);
+                let level = if t < BURST_SECONDS {
+                    settle(floor, 1.0, t)
+                } else {
+                    settle(peak, floor, t - BURST_SECONDS)
+                };
+                if log {
+                    1.0 + 10.0 * level.max(floor).log10() / RANGE_DB
+                } else {
+                    level
+                }
+            }
+            Self::PeakDecay { db_per_second } if t >= BURST_SECONDS => {
+                1.0 - db_per_second.max(0.0) * (t - BURST_SECONDS) / RANGE_DB
+            }
+            Self::PeakDecay { .. } => 1.0,
+            Self::AttackRelease {
+                attack_seconds,
+                release_seconds,
+            } => {
+                let floor = db_to_power(-RANGE_DB);
+                let settle = |from: f32, to: f32, seconds: f32, tau: f32| {
+                    if tau <= 0.0 {
+                        to
+                    } else {
+                        to + (from - to) * (-seconds / tau).exp()
+                    }
+                };
+                let peak = settle(floor, 1.0, BURST_SECONDS, attack_seconds);
+                let level = match release_seconds {
+                    _ if t < BURST_SECONDS => settle(floor, 1.0, t, attack_seconds),
+                    Some(release) => settle(peak, floor, t - BURST_SECONDS, release),
+                    None => peak,
+                };
+                1.0 + 10.0 * level.max(floor).log10() / RANGE_DB
+            }
+        }
+        .clamp(0.0, 1.0)
+    }
+}
+
+fn db_to_power(db: f32) -> f32 {
+    10.0_f32.powf(db / 10.0)
+}
+
+pub(in crate::ui) fn ballistics_preview<'a, M: 'a>(ballistics: Ballistics) -> Element<'a, M> {
+    Element::new(Preview { ballistics })
+}
+
+struct Preview {
+    ballistics: Ballistics,
+}
+
+#[derive(Default)]
+struct PreviewState {
+    start: Option<Instant>,
+}
+
+impl<M> Widget<M, iced::Theme, iced::Renderer> for Preview {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<PreviewState>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(PreviewState::default())
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fill, Length::Fixed(HEIGHT))
+    }
+
+    fn layout(
+        &mut self,
+        _tree: &mut Tree,
+        _renderer: &iced::Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::Node::new(limits.resolve(Length::Fill, Length::Fixed(HEIGHT), Size::ZERO))
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: &Event,
+        _layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _renderer: &iced::Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, M>,
+        _viewport: &Rectangle,
+    ) {
+        if let Event::Window(window::Event::RedrawRequested(now)) = event {
+            tree.state
+                .downcast_mut::<PreviewState>()
+                .start
+                .get_or_insert(*now);
+            shell.request_redraw();
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut iced::Renderer,
+        theme: &iced::Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let palette = theme.extended_palette();
+        let accent = palette.primary.base.color;
+        let playhead = tree
+            .state
+            .downcast_ref::<PreviewState>()
+            .start
+            .map_or(0.0, |start| start.elapsed().as_secs_f32() % PERIOD_SECONDS);
+        let mut paint = |bounds: Rectangle, color| {
+            renderer.fill_quad(
+                Quad {
+                    bounds,
+                    snap: true,
+                    ..Default::default()
+                },
+                Background::Color(color),
+            );
+        };
+
+        paint(bounds, palette.background.weak.color);
+        let burst_width = bounds.width * BURST_SECONDS / PERIOD_SECONDS;
+        paint(
+            Rectangle::new(bounds.position(), Size::new(burst_width, bounds.height)),
+            with_alpha(palette.background.strong.color, 0.5),
+        );
+
+        let columns = (bounds.width / COLUMN_WIDTH).floor().max(1.0) as usize;
+        let width = bounds.width / columns as f32;
+        for i in 0..columns {
+            let t = (i as f32 + 0.5) / columns as f32 * PERIOD_SECONDS;
+            let height = self.ballistics.response(t) * bounds.height;
+            let alpha = if t <= playhead { 0.85 } else { 0.3 };
+            paint(
+                Rectangle::new(
+                    Point::new(
+                        bounds.x + i as f32 * width,
+                        bounds.y + bounds.height - height,
+                    ),
+                    Size::new(width, height),
+                ),
+                with_alpha(accent, alpha),
+            );
+        }
+        paint(
+            Rectangle::new(
+                Point::new(
+                    bounds.x + bounds.width * playhead / PERIOD_SECONDS - 1.0,
+                    bounds.y,
+                ),
+                Size::new(2.0, bounds.height),
+            ),
+            palette.background.base.text,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn responses_rise_during_the_burst_and_fall_after() {
+        let slow = Ballistics::Smoothing {
+            factor: 0.9,
+            rate: 50.0,
+            log: false,
+        };
+        assert_eq!(slow.response(0.0), 0.0);
+        assert!(slow.response(0.2) > 0.5 && slow.response(0.2) < 1.0);
+        let after = slow.response(BURST_SECONDS + 0.1);
+        assert!(after > 0.0 && after < slow.response(BURST_SECONDS - 0.01));
+
+        let off = Ballistics::Smoothing {
+            factor: 0.0,
+            rate: 50.0,
+            log: true,
+        };
+        assert_eq!(off.response(0.1), 1.0);
+        assert_eq!(off.response(BURST_SECONDS + 0.1), 0.0);
+
+        let hold = Ballistics::PeakDecay {
+            db_per_second: 30.0,
+        };
+        assert_eq!(hold.response(0.2), 1.0);
+        assert!((hold.response(BURST_SECONDS + 1.0) - 0.5).abs() < 1e-6);
+    }
+}
@@ -4,11 +4,12 @@
 use crate::ui::scroll_delta_lines;
 use crate::ui::theme::{self as ui_theme, Palette};
 use crate::ui::widgets::scroll_glow::ScrollGlow;
-use crate::ui::widgets::{action_button, clipped_text};
+use crate::ui::widgets::{action_button, clipped_text, pick};
 use crate::util::color::{
-    EPSILON, STOP_SPREAD_MAX, STOP_SPREAD_MIN, colors_equal, lerp_color, sanitize_stop_positions,
-    sanitize_stop_spreads, with_alpha,
+    ColorBlend, EPSILON, STOP_SPREAD_MAX, STOP_SPREAD_MIN, colors_equal, mix_color, palettes_equal,
+    sanitize_stop_positions, sanitize_stop_spreads, stop_segment, with_alpha,
 };
+use crate::visuals::palettes::{BG_BASE, Preset};
 use iced::advanced::renderer::{self, Quad};
 use iced::advanced::widget::{Tree, tree};
 use iced::advanced::{Layout, Renderer as _, Widget, layout, mouse};
@@ -20,6 +21,16 @@ const SWATCH_SIZE: (f32, f32) = (56.0, 28.0);
 const GRADIENT_BAR_HEIGHT: f32 = 24.0;
 const MARKER_HEIGHT: f32 = 8.0;
 const MIN_STOP_GAP: f32 = 0.01;
+const PREVIEW_SIZE: (f32, f32) = (64.0, 40.0);
+const PREVIEW_STRIP: f32 = 8.0;
+const PREVIEW_CELLS: (usize, usize) = (32, 16);
+
+/// What the preset thumbnails draw under their gradient strip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PresetPreview {
+    Spectrogram,
+    Spectrum,
+}
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PaletteEvent {
@@ -28,7 +39,9 @@ pub enum PaletteEvent {
     Adjust { index: usize, color: Color },
     AdjustPosition { index: usize, position: f32 },
     AdjustSpread { index: usize, spread: f32 },
+    Blend(ColorBlend),
     Reset,
+    Preset(usize),
     HorizontalScroll(ScrollGlow),
 }
 
@@ -37,10 +50,14 @@ pub struct PaletteEditor {
     palette: Palette,
     positions: Vec<f32>,
     spreads: Vec<f32>,
+    blend: ColorBlend,
+    blendable: bool,
     active: Option<usize>,
     visible_indices: Option<&'static [usize]>,
     label_overrides: &'static [(usize, &'static str)],
     show_ramp: bool,
+    presets: &'static [Preset],
+    preview: PresetPreview,
     scroll: ScrollGlow,
 }
 
@@ -49,11 +66,15 @@ impl PaletteEditor {
         Self {
             positions: palette.default_positions.to_vec(),
             spreads: vec![1.0; palette.len()],
+            blend: ColorBlend::default(),
+            blendable: false,
             palette,
             active: None,
             visible_indices: None,
             label_overrides: &[],
             show_ramp: false,
+            presets: &[],
+            preview: PresetPreview::Spectrogram,
             scroll: ScrollGlow::default(),
         }
     }
@@ -62,6 +83,16 @@ impl PaletteEditor {
         self.show_ramp = show;
     }
 
+    /// Offers `presets` as thumbnails; ones that don't fit the palette are skipped.
+    pub fn set_presets(&mut self, presets: &'static [Preset], preview: PresetPreview) {
+        self.presets = presets;
+        self.preview = preview;
+    }
+
+    pub fn set_blendable(&mut self, blendable: bool) {
+        self.blendable = blendable;
+    }
+
     pub fn set_visible_indices(&mut self, indices: Option<&'static [usize]>) {
         self.visible_indices = indices;
         if let Some((active, visible)) = self.active.zip(self.visible_indices)
@@ -107,6 +138,14 @@ impl PaletteEditor {
         self.spreads = sanitize_stop_spreads(spreads, self.palette.len());
     }
 
+    pub fn blend(&self) -> ColorBlend {
+        self.blend
+    }
+
+    pub fn set_blend(&mut self, blend: ColorBlend) {
+        self.blend = blend;
+    }
+
     pub fn set_colors(&mut self, colors: &[Color]) {
         self.palette.set_colors(colors);
     }
@@ -161,6 +200,22 @@ impl PaletteEditor {
                 self.spreads[index] = next;
                 true
             }
+            PaletteEvent::Blend(blend) => {
+                let changed = self.blend != blend;
+                self.blend = blend;
+                changed
+            }
+            PaletteEvent::Preset(i) => {
+                let Some(&(_, colors)) = self.presets.get(i) else {
+                    return false;
+                };
+                if colors.len() != self.palette.len() || palettes_equal(colors, self.colors()) {
+                    return false;
+                }
+                self.active = None;
+                self.palette.set_colors(colors);
+                true
+            }
             PaletteEvent::HorizontalScroll(g) => {
                 self.scroll = g;
                 false
@@ -173,6 +228,7 @@ impl PaletteEditor {
                     self.palette.reset();
                     self.positions = self.palette.default_positions.to_vec();
                     self.spreads = vec![1.0; self.palette.len()];
+                    self.blend = ColorBlend::default();
                     true
                 }
             }
@@ -188,6 +244,7 @@ impl PaletteEditor {
             && self.positions == self.palette.default_positions
             && self.spreads.len() == self.palette.len()
             && self.spreads.iter().all(|&spread| spread == 1.0)
+            && self.blend == ColorBlend::default()
     }
 
     pub fn view(&self) -> Element<'_, PaletteEvent> {
@@ -205,10 +262,27 @@ impl PaletteEditor {
             }
         }
         let mut col = Column::new().spacing(12);
+        if let Some(presets) = self.preset_row() {
+            col = col.push(presets);
+        }
         if self.show_ramp && colors.len() >= 2 {
             let positions = self.positions();
             let spreads = self.spreads();
-            col = col.push(gradient_bar(colors, positions, spreads, self.active));
+            col = col.push(gradient_bar(
+                colors,
+                positions,
+                spreads,
+                self.blend,
+                self.active,
+            ));
+        }
+        if self.blendable {
+            col = col.push(pick(
+                "Blend",
+                ColorBlend::ALL,
+                self.blend,
+                PaletteEvent::Blend,
+            ));
         }
         col = col.push(self.scroll.horizontal(row, PaletteEvent::HorizontalScroll));
         if let Some(i) = self.active
@@ -223,6 +297,39 @@ impl PaletteEditor {
         .into()
     }
 
+    fn preset_row(&self) -> Option<Element<'_, PaletteEvent>> {
+        let current = self.colors();
+        let mut fitting = self
+            .presets
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, colors))| colors.len() == current.len())
+            .peekable();
+        fitting.peek()?;
+        let row = fitting.fold(Row::new().spacing(8), |row, (i, &(name, colors))| {
+            let selected = palettes_equal(colors, current);
+            row.push(
+                Button::new(
+                    Column::new()
+                        .spacing(4)
+                        .align_x(Horizontal::Center)
+                        .push(clipped_text(name, 11.0))
+                        .push(Element::new(PresetThumbnail {
+                            colors,
+                            positions: &self.positions,
+                            spreads: &self.spreads,
+                            blend: self.blend,
+                            preview: self.preview,
+                        })),
+                )
+                .padding([6, 8])
+                .style(move |theme, status| ui_theme::button_style(theme, selected, status))
+                .on_press(PaletteEvent::Preset(i)),
+            )
+        });
+        Some(row.wrap().into())
+    }
+
     fn color_picker(&self, i: usize, c: Color) -> Element<'_, PaletteEvent> {
         let (w, h) = SWATCH_SIZE;
         let active = self.active == Some(i);
@@ -282,6 +389,117 @@ fn to_hex(c: Color) -> String {
     }
 }
 
+/// A preset drawn with the editor's current stops and blend, so it looks as
+/// it would once applied.
+struct PresetThumbnail<'a> {
+    colors: &'static [Color],
+    positions: &'a [f32],
+    spreads: &'a [f32],
+    blend: ColorBlend,
+    preview: PresetPreview,
+}
+
+impl PresetThumbnail<'_> {
+    fn color_at(&self, t: f32) -> Color {
+        let (lo, hi, f) = stop_segment(self.positions, self.spreads, t.clamp(0.0, 1.0));
+        mix_color(self.colors[lo], self.colors[hi], f, self.blend)
+    }
+}
+
+impl Widget<PaletteEvent, iced::Theme, iced::Renderer> for PresetThumbnail<'_> {
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fixed(PREVIEW_SIZE.0), Length::Fixed(PREVIEW_SIZE.1))
+    }
+
+    fn layout(
+        &mut self,
+        _tree: &mut Tree,
+        _renderer: &iced::Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let (w, h) = PREVIEW_SIZE;
+        layout::Node::new(limits.resolve(Length::Fixed(w), Length::Fixed(h), Size::ZERO))
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut iced::Renderer,
+        theme: &iced::Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let mut paint = |bounds: Rectangle, border, color| {
+            renderer.fill_quad(
+                Quad {
+                    bounds,
+                    border,
+                    ..Default::default()
+                },
+                Background::Color(color),
+            );
+        };
+        paint(bounds, iced::Border::default(), BG_BASE);
+        if self.colors.len() >= 2 && self.positions.len() == self.colors.len() {
+            let (cols, rows) = PREVIEW_CELLS;
+            let cell_w = bounds.width / cols as f32;
+            let area = bounds.height - PREVIEW_STRIP;
+            let cell_h = area / rows as f32;
+            let floor = bounds.y + bounds.height;
+            for cx in 0..cols {
+                let x = bounds.x + cx as f32 * cell_w;
+                let t = cx as f32 / (cols - 1) as f32;
+                let strip =
+                    Rectangle::new(Point::new(x, bounds.y), Size::new(cell_w, PREVIEW_STRIP));
+                paint(strip, iced::Border::default(), self.color_at(t));
+                match self.preview {
+                    PresetPreview::Spectrogram => {
+                        for cy in 0..rows {
+                            let level = sample_level(t, (cy as f32 + 0.5) / rows as f32);
+                            let y = floor - (cy + 1) as f32 * cell_h;
+                            let cell = Rectangle::new(Point::new(x, y), Size::new(cell_w, cell_h));
+                            paint(cell, iced::Border::default(), self.color_at(level));
+                        }
+                    }
+                    PresetPreview::Spectrum => {
+                        let level = sample_level(0.3, t);
+                        let h = level * area;
+                        let bar = Rectangle::new(Point::new(x, floor - h), Size::new(cell_w, h));
+                        paint(bar, iced::Border::default(), self.color_at(level));
+                    }
+                }
+            }
+        }
+        paint(bounds, ui_theme::border(theme, false), Color::TRANSPARENT);
+    }
+}
+
+// A made-up moment of music, 0..1 in time and frequency: a gliding voice with
+// harmonics over a noise floor, and a drum hit two thirds of the way in.
+fn sample_level(time: f32, freq: f32) -> f32 {
+    let grain = ((time * 91.7 + freq * 47.3).sin() * 43_758.547)
+        .fract()
+        .abs();
+    let floor = 0.08 + 0.14 * grain * (1.0 - freq);
+    let f0 = 0.1 + 0.04 * (time * std::f32::consts::TAU).sin();
+    let voice = (1..=5)
+        .map(|k| {
+            let d = (freq - k as f32 * f0) / 0.02;
+            0.95 / k as f32 * (-d * d).exp()
+        })
+        .fold(0.0, f32::max);
+    let since_hit = time - 0.66;
+    let hit = if since_hit >= 0.0 {
+        0.8 * (-since_hit * 30.0).exp() * (1.0 - 0.4 * freq)
+    } else {
+        0.0
+    };
+    floor.max(voice).max(hit).min(1.0)
+}
+
 const HANDLE_WIDTH: f32 = 10.0;
 const HANDLE_HIT_SLOP: f32 = 6.0;
 const INDICATOR_WIDTH: f32 = 1.0;
@@ -303,29 +521,6 @@ fn nearest_handle(
         .map(|(i, _)| i)
 }
 
-fn find_segment(positions: &[f32], spreads: &[f32], t: f32) -> (usize, usize, f32) {
-    let count = positions.len();
-    if count < 2 {
-        return (0, 0, 0.0);
-    }
-
-    let t = t.clamp(0.0, 1.0);
-    let hi = positions
-        .partition_point(|&pos| pos < t)
-        .clamp(1, count - 1);
-    let lo = hi - 1;
-    let linear =
-        ((t - positions[lo]) / (positions[hi] - positions[lo]).max(f32::EPSILON)).clamp(0.0, 1.0);
-    let sl = spreads.get(lo).copied().unwrap_or(1.0);
-    let sr = spreads.get(hi).copied().unwrap_or(1.0);
-    let f = if (sl - 1.0).abs() < EPSILON && (sr - 1.0).abs() < EPSILON {
-        linear
-    } else {
-        linear.powf(sl / sr).clamp(0.0, 1.0)
-    };
-    (lo, hi, f)
-}
-
 #[derive(Debug, Default)]
 struct GradientBarState {
     dragging: Option<usize>,
@@ -335,6 +530,7 @@ struct GradientBar<'a> {
     colors: &'a [Color],
     positions: &'a [f32],
     spreads: &'a [f32],
+    blend: ColorBlend,
     active: Option<usize>,
 }
 
@@ -342,12 +538,14 @@ fn gradient_bar<'a>(
     colors: &'a [Color],
     positions: &'a [f32],
     spreads: &'a [f32],
+    blend: ColorBlend,
     active: Option<usize>,
 ) -> Element<'a, PaletteEvent> {
     Element::new(GradientBar {
         colors,
         positions,
         spreads,
+        blend,
         active,
     })
 }
@@ -467,8 +665,8 @@ impl Widget<PaletteEvent, iced::Theme, iced::Renderer> for GradientBar<'_> {
         let step_w = bar_w / steps as f32;
         for i in 0..steps {
             let t = i as f32 / (steps - 1).max(1) as f32;
-            let (lo, hi, f) = find_segment(self.positions, self.spreads, t);
-            let c = lerp_color(self.colors[lo], self.colors[hi], f);
+            let (lo, hi, f) = stop_segment(self.positions, self.spreads, t);
+            let c = mix_color(self.colors[lo], self.colors[hi], f, self.blend);
             let x = bounds.x + i as f32 * step_w;
             paint(
                 Rectangle::new(
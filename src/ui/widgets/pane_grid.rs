@@ -11,7 +11,9 @@ use iced::advanced::widget::{
     tree::{self, Tree},
 };
 use iced::advanced::{Clipboard, Layout, Renderer as _, Shell, Widget, layout, mouse};
-use iced::{Background, Element, Event, Length, Point, Rectangle, Size};
+use iced::{Background, Element, Event, Length, Point, Rectangle, Size, keyboard, window};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 use crate::util::color::with_alpha;
 
@@ -24,11 +26,12 @@ pub struct State<T> {
 }
 
 impl<T> State<T> {
-    pub fn from_iter(items: impl IntoIterator<Item = T>) -> Self {
+    /// Keys must be unique. Panes rebuilt with the same key animate from
+    /// where they were drawn.
+    pub fn from_keyed(items: impl IntoIterator<Item = (usize, T)>) -> Self {
         Self {
             panes: items
                 .into_iter()
-                .enumerate()
                 .map(|(id, item)| (Pane(id), item))
                 .collect(),
         }
@@ -70,14 +73,108 @@ impl<T> State<T> {
     }
 }
 
+/// Direction panes are laid out in; dividers run across it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Axis {
+    #[default]
+    Horizontal,
+    Vertical,
+}
+
+impl Axis {
+    fn main(self, point: Point) -> f32 {
+        match self {
+            Self::Horizontal => point.x,
+            Self::Vertical => point.y,
+        }
+    }
+
+    fn length(self, size: Size) -> f32 {
+        match self {
+            Self::Horizontal => size.width,
+            Self::Vertical => size.height,
+        }
+    }
+
+    // (start, length) of `bounds` along the axis.
+    fn span(self, bounds: Rectangle) -> (f32, f32) {
+        (self.main(bounds.position()), self.length(bounds.size()))
+    }
+
+    fn rectangle(self, (start, length): (f32, f32), cross: Rectangle) -> Rectangle {
+        match self {
+            Self::Horizontal => {
+                Rectangle::new(Point::new(start, cross.y), Size::new(length, cross.height))
+            }
+            Self::Vertical => {
+                Rectangle::new(Point::new(cross.x, start), Size::new(cross.width, length))
+            }
+        }
+    }
+}
+
 const DIVIDER_HIT_WIDTH: f32 = 8.0;
 const EPS: f32 = 0.001;
+const MOTION_DURATION: Duration = Duration::from_millis(150);
+
+// Span (start, length) along the axis a pane eases along after its slot changes.
+#[derive(Debug, Clone, Copy)]
+struct PaneMotion {
+    from: (f32, f32),
+    to: (f32, f32),
+    start: Instant,
+}
+
+impl PaneMotion {
+    fn settled(span: (f32, f32), now: Instant) -> Self {
+        Self {
+            from: span,
+            to: span,
+            start: now,
+        }
+    }
+
+    fn is_settled(&self) -> bool {
+        self.from == self.to
+    }
+
+    fn span_at(&self, now: Instant) -> (f32, f32) {
+        let t =
+            now.saturating_duration_since(self.start).as_secs_f32() / MOTION_DURATION.as_secs_f32();
+        if t >= 1.0 {
+            return self.to;
+        }
+        let eased = 1.0 - (1.0 - t).powi(3);
+        let lerp = |a: f32, b: f32| a + (b - a) * eased;
+        (lerp(self.from.0, self.to.0), lerp(self.from.1, self.to.1))
+    }
+
+    // Retargets from the current position; settles once the motion has run.
+    fn advance(&mut self, target: (f32, f32), now: Instant) -> (f32, f32) {
+        let span = self.span_at(now);
+        if !spans_equal(target, self.to) {
+            *self = Self {
+                from: span,
+                to: target,
+                start: now,
+            };
+        } else if spans_equal(span, self.to) {
+            self.from = self.to;
+        }
+        span
+    }
+}
+
+fn spans_equal(a: (f32, f32), b: (f32, f32)) -> bool {
+    (a.0 - b.0).abs() <= EPS && (a.1 - b.1).abs() <= EPS
+}
 
 struct ResizeState {
     divider: usize,
-    origin_x: f32,
+    origin: f32,
     start: Vec<f32>,
     min: Vec<f32>,
+    max: Vec<f32>,
     current: Vec<f32>,
 }
 
@@ -85,10 +182,61 @@ struct ResizeState {
 struct Interaction {
     dragging: Option<(Pane, Point)>,
     resizing: Option<ResizeState>,
-    last_x: Option<f32>,
+    last_main: Option<f32>,
     cursor_over: Option<Pane>,
+    motion: HashMap<Pane, PaneMotion>,
+    laid_out: Option<(Axis, f32)>,
+    modifiers: keyboard::Modifiers,
 }
 
+impl Interaction {
+    fn is_animating(&self) -> bool {
+        !self.motion.values().all(PaneMotion::is_settled)
+    }
+
+    // Window resizes, axis flips and divider drags snap; order and membership
+    // changes ease.
+    fn spans(
+        &mut self,
+        panes: &[Pane],
+        lengths: &[f32],
+        axis: Axis,
+        available: f32,
+    ) -> Vec<(f32, f32)> {
+        let now = Instant::now();
+        let snap = self.resizing.is_some()
+            || self.motion.is_empty()
+            || self.laid_out.is_none_or(|(laid_axis, length)| {
+                laid_axis != axis || (length - available).abs() > EPS
+            });
+        self.laid_out = Some((axis, available));
+        self.motion.retain(|pane, _| panes.contains(pane));
+
+        let mut start = 0.0;
+        panes
+            .iter()
+            .zip(lengths)
+            .map(|(pane, &length)| {
+                let target = (start, length.max(0.0));
+                start += target.1;
+                if snap {
+                    self.motion.insert(*pane, PaneMotion::settled(target, now));
+                    return target;
+                }
+                self.motion
+                    .entry(*pane)
+                    .or_insert_with(|| PaneMotion {
+                        from: (target.0, 0.0),
+                        to: target,
+                        start: now,
+                    })
+                    .advance(target, now)
+            })
+            .collect()
+    }
+}
+
+/// New pane lengths along the grid's axis.
 pub type ResizeWidths = Vec<(Pane, f32)>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -101,35 +249,66 @@ pub enum DragEvent {
 #[allow(missing_debug_implementations)]
 pub struct Content<'a, Message> {
     body: Element<'a, Message>,
-    min_width: f32,
-    basis_width: f32,
+    width: SizeSpec,
+    height: SizeSpec,
 }
 
 impl<'a, Message> Content<'a, Message> {
     pub fn new(body: impl Into<Element<'a, Message>>) -> Self {
         Self {
             body: body.into(),
-            min_width: 0.0,
-            basis_width: 0.0,
+            width: SizeSpec::default(),
+            height: SizeSpec::default(),
         }
     }
 
     pub fn with_width_basis(mut self, min: f32, basis: f32) -> Self {
-        self.min_width = min;
-        self.basis_width = basis;
+        self.width.min = min;
+        self.width.basis = basis;
         self
     }
+
+    /// Exceeded only when every other pane is already at its own maximum.
+    pub fn with_max_width(mut self, max: f32) -> Self {
+        self.width.max = max;
+        self
+    }
+
+    pub fn with_height_basis(mut self, min: f32, basis: f32) -> Self {
+        self.height.min = min;
+        self.height.basis = basis;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SizeSpec {
+    min: f32,
+    basis: f32,
+    max: f32,
+}
+
+impl Default for SizeSpec {
+    fn default() -> Self {
+        Self {
+            min: 0.0,
+            basis: 0.0,
+            max: f32::INFINITY,
+        }
+    }
 }
 
 // Callback closures do not implement Debug; this mirrors iced's widget types.
 #[allow(missing_debug_implementations)]
 pub struct PaneGrid<'a, Message> {
     entries: Vec<(Pane, Content<'a, Message>)>,
+    axis: Axis,
     width: Length,
     height: Length,
     on_drag: Option<Box<dyn Fn(DragEvent) -> Message + 'a>>,
     on_resize: Option<Box<dyn Fn(ResizeWidths) -> Message + 'a>>,
     on_context: Option<Box<dyn Fn(Pane) -> Message + 'a>>,
+    on_shift_context: Option<Box<dyn Fn(Pane) -> Message + 'a>>,
     on_hover: Option<Box<dyn Fn(Option<Pane>) -> Message + 'a>>,
 }
 
@@ -140,15 +319,22 @@ impl<'a, Message: 'a> PaneGrid<'a, Message> {
                 .iter()
                 .map(|(pane, value)| (*pane, view(*pane, value)))
                 .collect(),
+            axis: Axis::default(),
             width: Length::Fill,
             height: Length::Fill,
             on_drag: None,
             on_resize: None,
             on_context: None,
+            on_shift_context: None,
             on_hover: None,
         }
     }
 
+    pub fn axis(mut self, axis: Axis) -> Self {
+        self.axis = axis;
+        self
+    }
+
     pub fn width(mut self, width: impl Into<Length>) -> Self {
         self.width = width.into();
         self
@@ -174,6 +360,12 @@ impl<'a, Message: 'a> PaneGrid<'a, Message> {
         self
     }
 
+    /// Shift+right-click; falls back to `on_context_request` when unset.
+    pub fn on_shift_context_request(mut self, callback: impl Fn(Pane) -> Message + 'a) -> Self {
+        self.on_shift_context = Some(Box::new(callback));
+        self
+    }
+
     pub fn on_hover(mut self, callback: impl Fn(Option<Pane>) -> Message + 'a) -> Self {
         self.on_hover = Some(Box::new(callback));
         self
@@ -196,15 +388,16 @@ impl<'a, Message: 'a> PaneGrid<'a, Message> {
             .take(self.entries.len() - 1)
             .enumerate()
             .find_map(|(i, child)| {
-                let x = child.bounds().x + child.bounds().width;
-                ((cursor.x - x).abs() <= half).then_some(i)
+                let (start, length) = self.axis.span(child.bounds());
+                ((self.axis.main(cursor) - start - length).abs() <= half).then_some(i)
             })
     }
 
-    fn width_specs(&self) -> impl Iterator<Item = (f32, f32)> + '_ {
-        self.entries
-            .iter()
-            .map(|(_, content)| (content.min_width, content.basis_width))
+    fn size_specs(&self) -> impl Iterator<Item = SizeSpec> + '_ {
+        self.entries.iter().map(|(_, content)| match self.axis {
+            Axis::Horizontal => content.width,
+            Axis::Vertical => content.height,
+        })
     }
 
     fn pair_widths(&self, widths: &[f32]) -> Vec<(Pane, f32)> {
@@ -256,37 +449,36 @@ impl<Message: 'static> Widget<Message, iced::Theme, iced::Renderer> for PaneGrid
             return layout::Node::new(size);
         }
 
-        let available_width = size.width.max(0.0);
+        let available = self.axis.length(size).max(0.0);
         let resizing = tree.state.downcast_ref::<Interaction>().resizing.as_ref();
         let widths = resizing
             .filter(|r| {
-                r.current.len() == count
-                    && (r.current.iter().sum::<f32>() - available_width).abs() < 0.5
+                r.current.len() == count && (r.current.iter().sum::<f32>() - available).abs() < 0.5
             })
             .map_or_else(
-                || solve_widths(self.width_specs(), available_width),
-                |r| fit_sum(r.current.clone(), available_width),
+                || solve_widths(self.size_specs(), available),
+                |r| fit_sum(r.current.clone(), available),
             );
 
-        let mut x = 0.0;
+        let panes: Vec<_> = self.entries.iter().map(|(pane, _)| *pane).collect();
+        let spans = tree
+            .state
+            .downcast_mut::<Interaction>()
+            .spans(&panes, &widths, self.axis, available);
+        let bounds = Rectangle::with_size(size);
         let children = self
             .entries
             .iter_mut()
             .zip(tree.children.iter_mut())
-            .zip(widths)
-            .map(|(((_, content), child), width)| {
-                let width = width.max(0.0);
-                let limits = layout::Limits::new(
-                    Size::new(width, size.height),
-                    Size::new(width, size.height),
-                );
-                let node = content
+            .zip(spans)
+            .map(|(((_, content), child), span)| {
+                let slot = self.axis.rectangle(span, bounds);
+                let limits = layout::Limits::new(slot.size(), slot.size());
+                content
                     .body
                     .as_widget_mut()
                     .layout(child, renderer, &limits)
-                    .move_to(Point::new(x, 0.0));
-                x += width;
-                node
+                    .move_to(slot.position())
             })
             .collect();
         layout::Node::with_children(size, children)
@@ -323,6 +515,12 @@ impl<Message: 'static> Widget<Message, iced::Theme, iced::Renderer> for PaneGrid
         shell: &mut Shell<'_, Message>,
         viewport: &Rectangle,
     ) {
+        if let Event::Window(window::Event::RedrawRequested(_)) = event
+            && tree.state.downcast_ref::<Interaction>().is_animating()
+        {
+            shell.invalidate_layout();
+            shell.request_redraw();
+        }
         if self.update_resize(tree, event, shell)
             || self.update_interaction(tree, event, layout, cursor, shell)
         {
@@ -372,7 +570,10 @@ impl<Message: 'static> Widget<Message, iced::Theme, iced::Renderer> for PaneGrid
                     .position()
                     .is_some_and(|p| self.divider_at(layout, p).is_some()))
         {
-            return mouse::Interaction::ResizingHorizontally;
+            return match self.axis {
+                Axis::Horizontal => mouse::Interaction::ResizingHorizontally,
+                Axis::Vertical => mouse::Interaction::ResizingVertically,
+            };
         }
         if self.on_drag.is_some()
             && cursor
@@ -446,13 +647,12 @@ impl<Message: 'static> Widget<Message, iced::Theme, iced::Renderer> for PaneGrid
         if let Some(r) = &interaction.resizing
             && let Some(child) = layout.children().nth(r.divider)
         {
-            let b = layout.bounds();
+            let (start, length) = self.axis.span(child.bounds());
             renderer.fill_quad(
                 Quad {
-                    bounds: Rectangle::new(
-                        Point::new(child.bounds().x + child.bounds().width - 1.0, b.y),
-                        Size::new(2.0, b.height),
-                    ),
+                    bounds: self
+                        .axis
+                        .rectangle((start + length - 1.0, 2.0), layout.bounds()),
                     snap: true,
                     ..Default::default()
                 },
@@ -483,6 +683,9 @@ impl<'a, Message: 'a> PaneGrid<'a, Message> {
     ) -> bool {
         use mouse::Button;
 
+        if let Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) = event {
+            tree.state.downcast_mut::<Interaction>().modifiers = *modifiers;
+        }
         let Event::Mouse(mouse_event) = event else {
             return false;
         };
@@ -490,7 +693,7 @@ impl<'a, Message: 'a> PaneGrid<'a, Message> {
         if matches!(mouse_event, mouse::Event::CursorLeft) {
             let interaction = tree.state.downcast_mut::<Interaction>();
             let dragging = interaction.dragging.take();
-            interaction.last_x = None;
+            interaction.last_main = None;
             self.publish_hover(tree, None, shell);
             if dragging.is_some() {
                 self.publish_drop(shell);
@@ -502,17 +705,18 @@ impl<'a, Message: 'a> PaneGrid<'a, Message> {
         if let Some((pane, origin)) = tree.state.downcast_ref::<Interaction>().dragging {
             match mouse_event {
                 mouse::Event::CursorMoved { position } => {
-                    let last_x = tree
+                    let main = self.axis.main(*position);
+                    let last_main = tree
                         .state
                         .downcast_ref::<Interaction>()
-                        .last_x
-                        .unwrap_or(position.x);
+                        .last_main
+                        .unwrap_or(main);
                     if position.distance(origin) > 5.0
                         && let Some(idx) = self.entries.iter().position(|(p, _)| *p == pane)
                     {
-                        let neighbor = if position.x > last_x {
+                        let neighbor = if main > last_main {
                             (idx + 1 < self.entries.len()).then_some(idx + 1)
-                        } else if position.x < last_x {
+                        } else if main < last_main {
                             idx.checked_sub(1)
                         } else {
                             None
@@ -520,9 +724,10 @@ impl<'a, Message: 'a> PaneGrid<'a, Message> {
                         if let Some(n) =
                             neighbor.and_then(|n| layout.children().nth(n).map(|l| (n, l)))
                         {
-                            let b = n.1.bounds();
-                            let crossed = (n.0 > idx && position.x > b.x + b.width / 2.0)
-                                || (n.0 < idx && position.x < b.x + b.width / 2.0);
+                            let (start, length) = self.axis.span(n.1.bounds());
+                            let middle = start + length / 2.0;
+                            let crossed =
+                                (n.0 > idx && main > middle) || (n.0 < idx && main < middle);
                             if crossed && let Some(on_drag) = &self.on_drag {
                                 shell.publish(on_drag(DragEvent::Moved {
                                     pane,
@@ -531,12 +736,12 @@ impl<'a, Message: 'a> PaneGrid<'a, Message> {
                             }
                         }
                     }
-                    tree.state.downcast_mut::<Interaction>().last_x = Some(position.x);
+                    tree.state.downcast_mut::<Interaction>().last_main = Some(main);
                 }
                 mouse::Event::ButtonReleased(Button::Left) => {
                     let interaction = tree.state.downcast_mut::<Interaction>();
                     interaction.dragging = None;
-                    interaction.last_x = None;
+                    interaction.last_main = None;
                     self.publish_drop(shell);
                 }
                 _ => {}
@@ -555,12 +760,17 @@ impl<'a, Message: 'a> PaneGrid<'a, Message> {
                 {
                     let start: Vec<_> = layout
                         .children()
-                        .map(|c| c.bounds().width.max(0.0))
+                        .map(|c| self.axis.length(c.bounds().size()).max(0.0))
+                        .collect();
+                    let min = fit_mins(self.size_specs(), start.iter().sum());
+                    let max = std::iter::zip(self.size_specs(), &min)
+                        .map(|(spec, &min)| max_width(spec, min))
                         .collect();
                     tree.state.downcast_mut::<Interaction>().resizing = Some(ResizeState {
                         divider,
-                        origin_x: position.x,
-                        min: fit_mins(self.width_specs(), start.iter().sum()),
+                        origin: self.axis.main(position),
+                        min,
+                        max,
                         current: start.clone(),
                         start,
                     });
@@ -573,13 +783,18 @@ impl<'a, Message: 'a> PaneGrid<'a, Message> {
                 {
                     let interaction = tree.state.downcast_mut::<Interaction>();
                     interaction.dragging = Some((pane, position));
-                    interaction.last_x = Some(position.x);
+                    interaction.last_main = Some(self.axis.main(position));
                     shell.capture_event();
                     return true;
                 }
             }
             mouse::Event::ButtonPressed(Button::Right) => {
-                if let Some(on_context) = &self.on_context
+                let shift = tree.state.downcast_ref::<Interaction>().modifiers.shift();
+                let callback = match &self.on_shift_context {
+                    Some(on_shift_context) if shift => Some(on_shift_context),
+                    _ => self.on_context.as_ref(),
+                };
+                if let Some(on_context) = callback
                     && let Some(position) = cursor.position()
                     && let Some(pane) = self.pane_at(layout, position)
                 {
@@ -619,8 +834,9 @@ impl<'a, Message: 'a> PaneGrid<'a, Message> {
                 let next = resize_widths(
                     &resizing.start,
                     &resizing.min,
+                    &resizing.max,
                     resizing.divider,
-                    position.x - resizing.origin_x,
+                    self.axis.main(*position) - resizing.origin,
                 );
                 if !widths_equal(&next, &resizing.current) {
                     resizing.current = next;
@@ -652,45 +868,67 @@ impl<'a, Message: 'a> PaneGrid<'a, Message> {
     }
 }
 
-fn solve_widths(specs: impl IntoIterator<Item = (f32, f32)>, available: f32) -> Vec<f32> {
+fn solve_widths(specs: impl IntoIterator<Item = SizeSpec>, available: f32) -> Vec<f32> {
     let available = finite_nonnegative(available);
-    let mut free: Vec<_> = specs.into_iter().enumerate().collect();
-    let mut min = fit_mins(free.iter().map(|&(_, spec)| spec), available);
+    let specs: Vec<_> = specs.into_iter().collect();
+    let min = fit_mins(specs.iter().copied(), available);
     if min.iter().sum::<f32>() >= available - EPS {
         return fit_sum(min, available);
     }
+    let max: Vec<_> = std::iter::zip(&specs, &min)
+        .map(|(&spec, &min)| max_width(spec, min))
+        .collect();
+    let basis: Vec<_> = std::iter::zip(&specs, &min)
+        .map(|(&spec, &min)| width_basis(spec, min))
+        .collect();
 
+    // Share by basis, then pin whichever bound the shares overshoot in total
+    // and share what is left among the rest.
+    let mut widths = min.clone();
+    let mut free: Vec<_> = (0..specs.len()).collect();
     let mut remaining = available;
     while !free.is_empty() {
-        let basis_sum: f64 = free
+        let basis_sum: f64 = free.iter().map(|&i| basis[i]).sum();
+        let share = |i: usize| (f64::from(remaining.max(0.0)) * basis[i] / basis_sum) as f32;
+        let violation: f32 = free
             .iter()
-            .map(|&(i, spec)| width_basis(spec, min[i]))
+            .map(|&i| share(i).clamp(min[i], max[i]) - share(i))
             .sum();
-        let available = f64::from(remaining.max(0.0));
-        let mut fixed = false;
-        for (i, spec) in std::mem::take(&mut free) {
-            let width = (available * width_basis(spec, min[i]) / basis_sum) as f32;
-            if width < min[i] - EPS {
-                remaining -= min[i];
-                fixed = true;
-            } else {
-                free.push((i, spec));
+        if violation.abs() <= EPS {
+            for &i in &free {
+                widths[i] = share(i);
             }
+            break;
         }
-        if !fixed {
-            for (i, spec) in free {
-                min[i] = (available * width_basis(spec, min[i]) / basis_sum) as f32;
+        let (pinned, rest): (Vec<_>, Vec<_>) = free.iter().partition(|&&i| {
+            if violation > 0.0 {
+                share(i) < min[i]
+            } else {
+                share(i) > max[i]
             }
-            break;
+        });
+        for i in pinned {
+            widths[i] = if violation > 0.0 { min[i] } else { max[i] };
+            remaining -= widths[i];
+        }
+        free = rest;
+    }
+
+    // Every pane hit its maximum; stretch them by basis rather than leave a gap.
+    let slack = available - widths.iter().sum::<f32>();
+    if slack > EPS {
+        let basis_sum: f64 = basis.iter().sum();
+        for (width, &basis) in widths.iter_mut().zip(&basis) {
+            *width += (f64::from(slack) * basis / basis_sum) as f32;
         }
     }
-    fit_sum(min, available)
+    fit_sum(widths, available)
 }
 
-fn fit_mins(specs: impl IntoIterator<Item = (f32, f32)>, available: f32) -> Vec<f32> {
+fn fit_mins(specs: impl IntoIterator<Item = SizeSpec>, available: f32) -> Vec<f32> {
     let mut min: Vec<_> = specs
         .into_iter()
-        .map(|(min, _)| finite_nonnegative(min))
+        .map(|spec| finite_nonnegative(spec.min))
         .collect();
     let sum = min.iter().sum::<f32>();
     if sum > available && sum > EPS {
@@ -701,8 +939,16 @@ fn fit_mins(specs: impl IntoIterator<Item = (f32, f32)>, available: f32) -> Vec<
     min
 }
 
-fn width_basis(spec: (f32, f32), min: f32) -> f64 {
-    f64::from(finite_nonnegative(spec.1).max(min).max(1.0))
+fn max_width(spec: SizeSpec, min: f32) -> f32 {
+    if spec.max.is_nan() {
+        f32::INFINITY
+    } else {
+        spec.max.max(min)
+    }
+}
+
+fn width_basis(spec: SizeSpec, min: f32) -> f64 {
+    f64::from(finite_nonnegative(spec.basis).max(min).max(1.0))
 }
 
 fn finite_nonnegative(value: f32) -> f32 {
@@ -721,45 +967,48 @@ fn widths_equal(a: &[f32], b: &[f32]) -> bool {
     a.len() == b.len() && std::iter::zip(a, b).all(|(a, b)| (a - b).abs() <= EPS)
 }
 
-fn resize_widths(start: &[f32], min: &[f32], divider: usize, delta: f32) -> Vec<f32> {
-    if start.len() != min.len() || divider + 1 >= start.len() || delta.abs() <= EPS {
+fn resize_widths(start: &[f32], min: &[f32], max: &[f32], divider: usize, delta: f32) -> Vec<f32> {
+    if start.len() != min.len()
+        || start.len() != max.len()
+        || divider + 1 >= start.len()
+        || delta.abs() <= EPS
+    {
         return start.to_vec();
     }
-    let mut widths = start.to_vec();
-    if delta > 0.0 {
-        let amount = delta.min(shrink_capacity(&widths, min, divider + 1..start.len()));
-        apply_nearest(&mut widths, min, (0..=divider).rev(), amount, true);
-        apply_nearest(&mut widths, min, divider + 1..start.len(), amount, false);
+    let left: Vec<_> = (0..=divider).rev().collect();
+    let right: Vec<_> = (divider + 1..start.len()).collect();
+    let (grow, shrink) = if delta > 0.0 {
+        (left, right)
     } else {
-        let amount = (-delta).min(shrink_capacity(&widths, min, (0..=divider).rev()));
-        apply_nearest(&mut widths, min, divider + 1..start.len(), amount, true);
-        apply_nearest(&mut widths, min, (0..=divider).rev(), amount, false);
-    }
+        (right, left)
+    };
+    let mut widths = start.to_vec();
+    let amount = delta
+        .abs()
+        .min(capacity(&widths, min, &shrink, -1.0))
+        .min(capacity(&widths, max, &grow, 1.0));
+    apply_nearest(&mut widths, max, &grow, 1.0, amount);
+    apply_nearest(&mut widths, min, &shrink, -1.0, amount);
     fit_sum(widths, start.iter().sum())
 }
 
-fn shrink_capacity(widths: &[f32], min: &[f32], order: impl Iterator<Item = usize>) -> f32 {
-    order.map(|i| (widths[i] - min[i]).max(0.0)).sum()
+// How far `order` can move toward `bound`: outward for `sign` 1, inward for -1.
+fn capacity(widths: &[f32], bound: &[f32], order: &[usize], sign: f32) -> f32 {
+    order
+        .iter()
+        .map(|&i| ((bound[i] - widths[i]) * sign).max(0.0))
+        .sum()
 }
 
-fn apply_nearest(
-    widths: &mut [f32],
-    min: &[f32],
-    order: impl Iterator<Item = usize>,
-    mut amount: f32,
-    grow: bool,
-) {
-    for i in order {
+// Moves each pane toward its bound, nearest the divider first.
+fn apply_nearest(widths: &mut [f32], bound: &[f32], order: &[usize], sign: f32, mut amount: f32) {
+    for &i in order {
         if amount <= EPS {
             break;
         }
-        let cap = if grow {
-            amount
-        } else {
-            (widths[i] - min[i]).max(0.0).min(amount)
-        };
-        widths[i] += if grow { cap } else { -cap };
-        amount -= cap;
+        let step = ((bound[i] - widths[i]) * sign).max(0.0).min(amount);
+        widths[i] += step * sign;
+        amount -= step;
     }
 }
 
@@ -773,23 +1022,81 @@ impl<'a, Message: 'static> From<PaneGrid<'a, Message>> for Element<'a, Message>
 mod tests {
     use super::*;
 
+    fn spec(min: f32, basis: f32, max: f32) -> SizeSpec {
+        SizeSpec { min, basis, max }
+    }
+
     #[test]
     fn solve_widths_uses_basis_and_minimums() {
+        let open = f32::INFINITY;
         assert_eq!(
-            solve_widths([(0.0, 1.0), (0.0, 3.0)], 800.0),
+            solve_widths([spec(0.0, 1.0, open), spec(0.0, 3.0, open)], 800.0),
             [200.0, 600.0]
         );
         assert_eq!(
-            solve_widths([(300.0, 1.0), (0.0, 100.0)], 400.0),
+            solve_widths([spec(300.0, 1.0, open), spec(0.0, 100.0, open)], 400.0),
             [300.0, 100.0]
         );
     }
 
+    #[test]
+    fn solve_widths_caps_maximums_and_degrades_gracefully() {
+        let open = f32::INFINITY;
+        assert_eq!(
+            solve_widths([spec(0.0, 1.0, 150.0), spec(0.0, 1.0, open)], 800.0),
+            [150.0, 650.0]
+        );
+        // Nowhere else to put the slack, so the maximums give way by basis.
+        assert_eq!(
+            solve_widths([spec(0.0, 1.0, 100.0), spec(0.0, 3.0, 300.0)], 800.0),
+            [200.0, 600.0]
+        );
+        // Too narrow for the minimums: they shrink in proportion.
+        assert_eq!(
+            solve_widths([spec(300.0, 1.0, open), spec(100.0, 1.0, open)], 200.0),
+            [150.0, 50.0]
+        );
+    }
+
+    #[test]
+    fn vertical_axis_stacks_full_width_rows() {
+        let bounds = Rectangle::new(Point::new(0.0, 0.0), Size::new(300.0, 800.0));
+        let row = Axis::Vertical.rectangle((200.0, 150.0), bounds);
+        assert_eq!(
+            row,
+            Rectangle::new(Point::new(0.0, 200.0), Size::new(300.0, 150.0))
+        );
+        assert_eq!(Axis::Vertical.span(row), (200.0, 150.0));
+        assert_eq!(Axis::Horizontal.span(row), (0.0, 300.0));
+    }
+
+    #[test]
+    fn pane_motion_eases_to_new_slots_and_settles() {
+        let start = Instant::now();
+        let mut motion = PaneMotion::settled((0.0, 100.0), start);
+        assert_eq!(motion.advance((100.0, 100.0), start), (0.0, 100.0));
+        assert!(!motion.is_settled());
+
+        let halfway = motion.span_at(start + MOTION_DURATION / 2);
+        assert!(halfway.0 > 50.0 && halfway.0 < 100.0, "{halfway:?}");
+
+        let end = start + MOTION_DURATION;
+        assert_eq!(motion.advance((100.0, 100.0), end), (100.0, 100.0));
+        assert!(motion.is_settled());
+    }
+
     #[test]
     fn resize_widths_takes_from_nearest_pane_first() {
+        let (start, min) = ([200.0, 300.0, 500.0], [100.0, 250.0, 100.0]);
         assert_eq!(
-            resize_widths(&[200.0, 300.0, 500.0], &[100.0, 250.0, 100.0], 0, 200.0),
+            resize_widths(&start, &min, &[f32::INFINITY; 3], 0, 200.0),
             [400.0, 250.0, 350.0],
         );
+        // The growing pane stops at its maximum.
+        let max = [250.0, f32::INFINITY, f32::INFINITY];
+        assert_eq!(
+            resize_widths(&start, &min, &max, 0, 200.0),
+            [250.0, 250.0, 500.0]
+        );
     }
 }
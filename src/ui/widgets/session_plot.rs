@@ -0,0 +1,141 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Static overview of a saved measurement session: the coarse spectrogram as a
+// heat map, short-term and momentary loudness on top, and a tick per event.
+
+use crate::persistence::session::{LEVEL_FLOOR_DB, MeasurementSession};
+use crate::util::color::{ColorBlend, color_to_rgba, sample_rgba_gradient, with_alpha};
+use crate::visuals::palettes;
+use iced::advanced::renderer::{self, Quad};
+use iced::advanced::widget::Tree;
+use iced::advanced::{Layout, Renderer as _, Widget, layout, mouse};
+use iced::{Background, Color, Element, Length, Point, Rectangle, Size};
+
+const HEIGHT: f32 = 260.0;
+const COLUMN_WIDTH: f32 = 2.0;
+// Band levels below this read as background.
+const HEAT_FLOOR_DB: f32 = -96.0;
+const LUFS_RANGE: (f32, f32) = (-60.0, 0.0);
+
+pub(in crate::ui) fn session_plot<'a, M: 'a>(session: &'a MeasurementSession) -> Element<'a, M> {
+    Element::new(Plot { session })
+}
+
+struct Plot<'a> {
+    session: &'a MeasurementSession,
+}
+
+impl<M> Widget<M, iced::Theme, iced::Renderer> for Plot<'_> {
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fill, Length::Fixed(HEIGHT))
+    }
+
+    fn layout(
+        &mut self,
+        _tree: &mut Tree,
+        _renderer: &iced::Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::Node::new(limits.resolve(Length::Fill, Length::Fixed(HEIGHT), Size::ZERO))
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut iced::Renderer,
+        theme: &iced::Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let palette = theme.extended_palette();
+        let mut paint = |bounds: Rectangle, color| {
+            renderer.fill_quad(
+                Quad {
+                    bounds,
+                    snap: true,
+                    ..Default::default()
+                },
+                Background::Color(color),
+            );
+        };
+        paint(bounds, palette.background.weak.color);
+
+        let frames = &self.session.frames;
+        if frames.is_empty() {
+            return;
+        }
+        let ramp = palettes::spectrogram::COLORS.map(color_to_rgba);
+        let bands = frames
+            .iter()
+            .map(|frame| frame.bands.len())
+            .max()
+            .unwrap_or(0);
+        let row_height = bounds.height / bands.max(1) as f32;
+        let columns = ((bounds.width / COLUMN_WIDTH) as usize).clamp(1, frames.len());
+        let column_width = bounds.width / columns as f32;
+        let bottom = bounds.y + bounds.height;
+        let lufs_y = |lufs: f32| {
+            let t = (lufs - LUFS_RANGE.0) / (LUFS_RANGE.1 - LUFS_RANGE.0);
+            bottom - t.clamp(0.0, 1.0) * bounds.height
+        };
+        let line = palette.background.base.text;
+        let mut peaks = vec![0_u8; bands];
+
+        for column in 0..columns {
+            let start = column * frames.len() / columns;
+            let end = ((column + 1) * frames.len() / columns).max(start + 1);
+            let x = bounds.x + column as f32 * column_width;
+            peaks.fill(0);
+            let (mut momentary, mut short_term) = (None::<f32>, None::<f32>);
+            for frame in &frames[start..end] {
+                for (peak, &level) in peaks.iter_mut().zip(&frame.bands) {
+                    *peak = (*peak).max(level);
+                }
+                if let Some([m, s, _]) = frame.loudness {
+                    momentary = Some(momentary.map_or(m, |v| v.max(m)));
+                    short_term = Some(short_term.map_or(s, |v| v.max(s)));
+                }
+            }
+            for (band, &level) in peaks.iter().enumerate() {
+                let t = (f32::from(level) + LEVEL_FLOOR_DB - HEAT_FLOOR_DB) / -HEAT_FLOOR_DB;
+                if t <= 0.0 {
+                    continue;
+                }
+                let [r, g, b, a] = sample_rgba_gradient(&ramp, t, ColorBlend::default());
+                paint(
+                    Rectangle::new(
+                        Point::new(x, bottom - (band + 1) as f32 * row_height),
+                        Size::new(column_width, row_height),
+                    ),
+                    Color::from_rgba(r, g, b, a),
+                );
+            }
+            let mut trace = |lufs: Option<f32>, thickness: f32, alpha: f32| {
+                if let Some(lufs) = lufs.filter(|&lufs| lufs > LUFS_RANGE.0) {
+                    paint(
+                        Rectangle::new(
+                            Point::new(x, lufs_y(lufs) - thickness * 0.5),
+                            Size::new(column_width, thickness),
+                        ),
+                        with_alpha(line, alpha),
+                    );
+                }
+            };
+            trace(momentary, 1.0, 0.4);
+            trace(short_term, 2.0, 0.9);
+        }
+
+        let duration = self.session.duration().max(f32::EPSILON);
+        for event in &self.session.events {
+            let x = bounds.x + (event.seconds / duration).clamp(0.0, 1.0) * bounds.width;
+            paint(
+                Rectangle::new(Point::new(x, bounds.y), Size::new(1.0, bounds.height)),
+                palette.primary.base.color,
+            );
+        }
+    }
+}
@@ -3,20 +3,93 @@
 
 use std::collections::VecDeque;
 
+// 5.1 and wider put the LFE fourth (FL FR FC LFE ...); narrower layouts
+// have none, or nothing that says which channel it is.
+const LFE_INDEX: usize = 3;
+const LFE_MIN_CHANNELS: usize = 6;
+
 crate::macros::choice_enum!(no_default all pub enum Channel {
     Left => "Left",
     Right => "Right",
     Mid => "Mid",
     Side => "Side",
+    Sum => "L+R",
+    Difference => "L-R",
+    Product => "L×R",
     None => "None",
 });
 
+impl Channel {
+    /// Sources without the sum/difference/product math channels.
+    pub const BASIC: &'static [Self] =
+        &[Self::Left, Self::Right, Self::Mid, Self::Side, Self::None];
+}
+
+/// The gains `Channel::Mid` mixes with, rebuilt only when the weights or the
+/// channel count change.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MixdownGains {
+    weights: Vec<f32>,
+    exclude_lfe: bool,
+    gains: Vec<f32>,
+}
+
+impl MixdownGains {
+    /// Per-channel weights; missing weights count as 1.
+    pub fn set_weights(&mut self, weights: &[f32], exclude_lfe: bool) {
+        if self.weights != weights || self.exclude_lfe != exclude_lfe {
+            self.weights = weights.to_vec();
+            self.exclude_lfe = exclude_lfe;
+            self.gains.clear();
+        }
+    }
+
+    /// Gains that collapse a `channels`-wide frame to mono, summing to 1 so
+    /// equal weights give the plain average.
+    pub fn for_channels(&mut self, channels: usize) -> &[f32] {
+        if self.gains.len() != channels {
+            self.gains = gains_for(&self.weights, self.exclude_lfe, channels);
+        }
+        &self.gains
+    }
+}
+
+fn gains_for(weights: &[f32], exclude_lfe: bool, channels: usize) -> Vec<f32> {
+    let mut gains: Vec<f32> = (0..channels)
+        .map(|ch| {
+            let weight = weights.get(ch).copied().unwrap_or(1.0);
+            if weight.is_finite() {
+                weight.max(0.0)
+            } else {
+                1.0
+            }
+        })
+        .collect();
+    if exclude_lfe && channels >= LFE_MIN_CHANNELS {
+        gains[LFE_INDEX] = 0.0;
+    }
+    let total: f32 = gains.iter().sum();
+    if total > 0.0 {
+        gains.iter_mut().for_each(|gain| *gain /= total);
+    }
+    gains
+}
+
+pub(crate) fn mix_frame(frame: &[f32], gains: &[f32]) -> f32 {
+    frame
+        .iter()
+        .zip(gains)
+        .map(|(sample, gain)| sample * gain)
+        .sum()
+}
+
 pub(crate) fn project_interleaved_channel_into(
     output: &mut Vec<f32>,
     interleaved: &[f32],
     channels: usize,
     frames: usize,
     channel: Channel,
+    mixdown: &mut MixdownGains,
 ) -> bool {
     output.clear();
     if channels == 0 || channel == Channel::None {
@@ -30,15 +103,14 @@ pub(crate) fn project_interleaved_channel_into(
     match channel {
         Channel::Left => output.extend(chunks.map(|frame| frame[0])),
         Channel::Right => output.extend(chunks.map(right)),
-        Channel::Mid => match channels {
-            1 => output.extend(chunks.map(|frame| frame[0])),
-            2 => output.extend(chunks.map(|frame| (frame[0] + frame[1]) * 0.5)),
-            _ => {
-                let gain = 1.0 / channels as f32;
-                output.extend(chunks.map(|frame| frame.iter().sum::<f32>() * gain));
-            }
-        },
+        Channel::Mid => {
+            let gains = mixdown.for_channels(channels);
+            output.extend(chunks.map(|frame| mix_frame(frame, gains)));
+        }
         Channel::Side => output.extend(chunks.map(|frame| (frame[0] - right(frame)) * 0.5)),
+        Channel::Sum => output.extend(chunks.map(|frame| frame[0] + right(frame))),
+        Channel::Difference => output.extend(chunks.map(|frame| frame[0] - right(frame))),
+        Channel::Product => output.extend(chunks.map(|frame| frame[0] * right(frame))),
         Channel::None => unreachable!(),
     }
     !output.is_empty()
@@ -73,3 +145,30 @@ pub fn extend_interleaved_history(
     }
     history.extend(samples);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mixdown_gains_normalise_weights_and_can_drop_the_lfe() {
+        assert_eq!(gains_for(&[], false, 2), [0.5, 0.5]);
+        assert_eq!(gains_for(&[3.0, 1.0], false, 2), [0.75, 0.25]);
+        // Missing and invalid weights count as 1; negative ones as 0.
+        assert_eq!(gains_for(&[f32::NAN, -1.0], false, 3), [0.5, 0.0, 0.5]);
+
+        let surround = gains_for(&[], true, 6);
+        assert_eq!(surround[LFE_INDEX], 0.0);
+        assert!((surround.iter().sum::<f32>() - 1.0).abs() < 1e-6);
+        // Quad has no LFE to drop.
+        assert_eq!(gains_for(&[], true, 4), [0.25; 4]);
+        assert_eq!(gains_for(&[0.0, 0.0], false, 2), [0.0, 0.0]);
+        assert_eq!(mix_frame(&[1.0, -1.0, 0.5], &[0.5, 0.25, 0.25]), 0.375);
+
+        let mut mixdown = MixdownGains::default();
+        assert_eq!(mixdown.for_channels(2), [0.5, 0.5]);
+        mixdown.set_weights(&[3.0, 1.0], false);
+        assert_eq!(mixdown.for_channels(2), [0.75, 0.25]);
+        assert_eq!(mixdown.for_channels(3), [0.6, 0.2, 0.2]);
+    }
+}
@@ -1,7 +1,82 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // Copyright (C) 2026 Maika Namuo
 
-pub fn fmt_freq(f: f32) -> String {
+crate::macros::choice_enum!(all pub enum DecimalSeparator {
+    #[default] Auto => "From locale",
+    Point => "Point (1.5)",
+    Comma => "Comma (1,5)",
+});
+
+// Languages that write one and a half as 1,5.
+const COMMA_LANGUAGES: &[&str] = &[
+    "af", "az", "be", "bg", "bs", "ca", "cs", "da", "de", "el", "es", "et", "eu", "fi", "fo", "fr",
+    "gl", "hr", "hu", "hy", "id", "is", "it", "ka", "kk", "ky", "lt", "lv", "mk", "mn", "nb", "nl",
+    "nn", "no", "pl", "pt", "ro", "ru", "sk", "sl", "sq", "sr", "sv", "tr", "uk", "uz", "vi",
+];
+
+/// How readouts write their decimal points, resolved once from the setting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NumberFormat {
+    comma: bool,
+}
+
+impl NumberFormat {
+    /// `Auto` reads the numeric locale from `LC_ALL`, `LC_NUMERIC` or `LANG`.
+    pub fn new(separator: DecimalSeparator) -> Self {
+        let comma = match separator {
+            DecimalSeparator::Auto => ["LC_ALL", "LC_NUMERIC", "LANG"]
+                .iter()
+                .find_map(|var| std::env::var(var).ok().filter(|value| !value.is_empty()))
+                .is_some_and(|locale| locale_uses_comma(&locale)),
+            DecimalSeparator::Point => false,
+            DecimalSeparator::Comma => true,
+        };
+        Self { comma }
+    }
+
+    /// Writes the decimal points in a readout with the chosen separator.
+    pub fn localize(self, text: String) -> String {
+        swap_decimal_points(text, self.comma)
+    }
+
+    pub fn freq(self, f: f32) -> String {
+        self.localize(fmt_freq(f))
+    }
+
+    pub fn duration(self, secs: f32) -> String {
+        self.localize(fmt_duration(secs))
+    }
+}
+
+fn locale_uses_comma(locale: &str) -> bool {
+    let language = locale
+        .split(['_', '-', '.', '@'])
+        .next()
+        .unwrap_or_default();
+    COMMA_LANGUAGES.contains(&language.to_ascii_lowercase().as_str())
+}
+
+// Only a point between two digits is a decimal point.
+fn swap_decimal_points(text: String, comma: bool) -> String {
+    let bytes = text.as_bytes();
+    let decimal = |i: usize| {
+        bytes[i] == b'.'
+            && i > 0
+            && bytes[i - 1].is_ascii_digit()
+            && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+    };
+    if !comma || !(0..bytes.len()).any(decimal) {
+        return text;
+    }
+    text.char_indices()
+        .map(|(i, c)| if decimal(i) { ',' } else { c })
+        .collect()
+}
+
+fn fmt_freq(f: f32) -> String {
+    if f < 0.0 {
+        return format!("-{}", fmt_freq(-f));
+    }
     match f {
         f if f >= 10_000.0 => format!("{:.1}kHz", f / 1000.0),
         f if f >= 1_000.0 => format!("{:.2}kHz", f / 1000.0),
@@ -10,10 +85,27 @@ pub fn fmt_freq(f: f32) -> String {
     }
 }
 
-pub fn fmt_duration(secs: f32) -> String {
+fn fmt_duration(secs: f32) -> String {
     if secs >= 60.0 {
         format!("{:.0}m {:.0}s", (secs / 60.0).floor(), secs % 60.0)
     } else {
         format!("{secs:.2}s")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commas_replace_only_decimal_points() {
+        let swap = |text: &str| swap_decimal_points(text.to_owned(), true);
+        assert_eq!(swap("-12.5 dB   1.25kHz"), "-12,5 dB   1,25kHz");
+        assert_eq!(swap("v1. 2.x .5"), "v1. 2.x .5");
+        assert_eq!(swap_decimal_points("3.0 s".to_owned(), false), "3.0 s");
+        assert!(locale_uses_comma("de_DE.UTF-8"));
+        assert!(locale_uses_comma("pt-BR"));
+        assert!(!locale_uses_comma("en_US.UTF-8"));
+        assert!(!locale_uses_comma("C"));
+    }
+}
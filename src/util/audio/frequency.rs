@@ -39,10 +39,11 @@ impl FrequencyScale {
     }
 }
 
+// Odd, like the log scale, so signed I/Q frequencies mirror around zero.
 fn hz_to_erb_rate(hz: f32) -> f32 {
-    21.4 * (1.0 + hz / 228.8).log10()
+    (21.4 * (1.0 + hz.abs() / 228.8).log10()).copysign(hz)
 }
 
 fn erb_rate_to_hz(erb: f32) -> f32 {
-    228.8 * (10.0f32.powf(erb / 21.4) - 1.0)
+    (228.8 * (10.0f32.powf(erb.abs() / 21.4) - 1.0)).copysign(erb)
 }
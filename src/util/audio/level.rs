@@ -1,8 +1,73 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // Copyright (C) 2026 Maika Namuo
 
+use serde::{Deserialize, Serialize};
+
 pub const DB_FLOOR: f32 = -140.0;
 pub const LN_TO_DB: f32 = 4.342_944_8;
+/// Highest ceiling a range may take, for summed channels that run hot.
+pub const MAX_CEILING_DB: f32 = 24.0;
+/// Narrowest range, which still leaves room for a scale.
+pub const MIN_RANGE_DB: f32 = 6.0;
+
+crate::macros::choice_enum!(no_default all pub enum LevelScale {
+    Spectrum => "Spectrum",
+    Spectrogram => "Spectrogram",
+    Loudness => "Loudness",
+    Waveform => "Waveform",
+});
+
+impl LevelScale {
+    pub const fn default_range(self) -> DbRange {
+        match self {
+            Self::Spectrum | Self::Spectrogram => DbRange::new(DB_FLOOR, 0.0),
+            Self::Loudness => DbRange::new(-60.0, 4.0),
+            Self::Waveform => DbRange::new(-96.0, 0.0),
+        }
+    }
+}
+
+/// The dBFS window a visual reads in; quieter levels draw as silence.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DbRange {
+    pub floor_db: f32,
+    pub ceiling_db: f32,
+}
+
+impl DbRange {
+    pub const fn new(floor_db: f32, ceiling_db: f32) -> Self {
+        Self {
+            floor_db,
+            ceiling_db,
+        }
+    }
+
+    pub fn span(self) -> f32 {
+        self.ceiling_db - self.floor_db
+    }
+
+    /// Inside `DB_FLOOR..=MAX_CEILING_DB` and at least `MIN_RANGE_DB` wide,
+    /// with `fallback` standing in for edges that aren't finite.
+    pub fn sanitized(self, fallback: Self) -> Self {
+        let finite = |db: f32, default: f32| if db.is_finite() { db } else { default };
+        let ceiling_db = finite(self.ceiling_db, fallback.ceiling_db)
+            .clamp(DB_FLOOR + MIN_RANGE_DB, MAX_CEILING_DB);
+        let floor_db =
+            finite(self.floor_db, fallback.floor_db).clamp(DB_FLOOR, ceiling_db - MIN_RANGE_DB);
+        Self::new(floor_db, ceiling_db)
+    }
+
+    /// Raises the floor to a visual's own, as far as `MIN_RANGE_DB` under the ceiling.
+    pub fn with_floor(self, floor_db: f32) -> Self {
+        let floor_db = if floor_db.is_finite() {
+            floor_db
+        } else {
+            self.floor_db
+        };
+        let floor_db = floor_db.clamp(self.floor_db, self.ceiling_db - MIN_RANGE_DB);
+        Self { floor_db, ..self }
+    }
+}
 
 // Stop recursive state well below audibility but before it becomes subnormal.
 pub fn flush_denormal_f32(value: &mut f32) {
@@ -38,6 +103,10 @@ pub fn db_to_power(db: f32) -> f32 {
     (db * DB_TO_LOG2).exp2()
 }
 
+pub fn db_to_gain(db: f32) -> f32 {
+    db_to_power(0.5 * db)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -46,4 +115,19 @@ mod tests {
     fn power_conversion_preserves_deep_levels() {
         assert!((power_to_db(1.0e-21, -300.0) + 210.0).abs() < 1.0e-4);
     }
+
+    #[test]
+    fn ranges_stay_ordered_and_wide_enough() {
+        let fallback = LevelScale::Loudness.default_range();
+        let range = DbRange::new(f32::NAN, -200.0).sanitized(fallback);
+        assert_eq!(range, DbRange::new(DB_FLOOR, DB_FLOOR + MIN_RANGE_DB));
+        let range = DbRange::new(-10.0, -12.0).sanitized(fallback);
+        assert_eq!(range, DbRange::new(-18.0, -12.0));
+
+        // A visual's floor narrows the range but never crosses it.
+        let range = DbRange::new(-96.0, 0.0);
+        assert_eq!(range.with_floor(-60.0), DbRange::new(-60.0, 0.0));
+        assert_eq!(range.with_floor(-120.0), range);
+        assert_eq!(range.with_floor(-1.0).floor_db, -MIN_RANGE_DB);
+    }
 }
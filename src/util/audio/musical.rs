@@ -1,8 +1,10 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // Copyright (C) 2026 Maika Namuo
 
-const A440_HZ: f32 = 440.0;
-const A440_MIDI: i32 = 69;
+pub const DEFAULT_A4_HZ: f32 = 440.0;
+pub const MIN_A4_HZ: f32 = 415.0;
+pub const MAX_A4_HZ: f32 = 466.0;
+const A4_MIDI: i32 = 69;
 const SEMITONES_PER_OCTAVE: i32 = 12;
 const MIDI_OCTAVE_OFFSET: i32 = 1;
 
@@ -10,6 +12,93 @@ const NOTE_NAMES: [&str; 12] = [
     "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
 ];
 
+crate::macros::choice_enum!(all pub enum Temperament {
+    #[default] Equal => "Equal",
+    Pythagorean => "Pythagorean",
+    Meantone => "Quarter-comma meantone",
+    Just => "Just (C major)",
+    Werckmeister => "Werckmeister III",
+});
+
+impl Temperament {
+    // Cents from equal temperament per pitch class, C as the tonic.
+    const fn offsets(self) -> [f32; 12] {
+        match self {
+            Self::Equal => [0.0; 12],
+            Self::Pythagorean => [
+                0.0, 13.69, 3.91, -5.87, 7.82, -1.96, 11.73, 1.96, 15.64, 5.87, -3.91, 9.78,
+            ],
+            Self::Meantone => [
+                0.0, -23.95, -6.84, 10.26, -13.69, 3.42, -20.53, -3.42, -27.37, -10.26, 6.84,
+                -17.11,
+            ],
+            Self::Just => [
+                0.0, 11.73, 3.91, 15.64, -13.69, -1.96, -9.78, 1.96, 13.69, -15.64, 17.60, -11.73,
+            ],
+            Self::Werckmeister => [
+                0.0, -9.78, -7.82, -5.87, -9.78, -1.96, -11.73, -3.91, -7.82, -11.73, -3.91, -7.82,
+            ],
+        }
+    }
+
+    // Where a note sits in equal-tempered semitones, with A held at the reference.
+    fn pitch(self, midi_number: i32) -> f32 {
+        let offsets = self.offsets();
+        let class = midi_number.rem_euclid(SEMITONES_PER_OCTAVE) as usize;
+        midi_number as f32 + (offsets[class] - offsets[9]) / 100.0
+    }
+}
+
+/// The reference pitch and temperament note names follow.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tuning {
+    a4_hz: f32,
+    temperament: Temperament,
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Self {
+            a4_hz: DEFAULT_A4_HZ,
+            temperament: Temperament::Equal,
+        }
+    }
+}
+
+impl Tuning {
+    /// The reference is clamped to `MIN_A4_HZ..=MAX_A4_HZ`.
+    pub fn new(a4_hz: f32, temperament: Temperament) -> Self {
+        let a4_hz = if a4_hz.is_finite() {
+            a4_hz.clamp(MIN_A4_HZ, MAX_A4_HZ)
+        } else {
+            DEFAULT_A4_HZ
+        };
+        Self { a4_hz, temperament }
+    }
+
+    fn midi(self, freq_hz: f32) -> Option<f32> {
+        let freq_hz = crate::util::finite_positive(freq_hz)?;
+        let m = A4_MIDI as f32 + SEMITONES_PER_OCTAVE as f32 * (freq_hz / self.a4_hz).log2();
+        m.is_finite().then_some(m)
+    }
+
+    // Nearest note and how far off it the frequency is, in semitones. No
+    // offset reaches half a semitone, so the neighbours of the equal-tempered
+    // guess are the only candidates.
+    fn nearest(self, freq_hz: f32) -> Option<(i32, f32)> {
+        let midi = self.midi(freq_hz)?;
+        let guess = midi.round() as i32;
+        (guess - 1..=guess + 1)
+            .map(|note| (note, midi - self.temperament.pitch(note)))
+            .min_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+    }
+
+    fn frequency(self, midi_number: i32) -> f32 {
+        let semitones = self.temperament.pitch(midi_number) - A4_MIDI as f32;
+        self.a4_hz * (semitones / SEMITONES_PER_OCTAVE as f32).exp2()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MusicalNote {
     pub midi_number: i32,
@@ -17,15 +106,11 @@ pub struct MusicalNote {
     pub octave: i32,
 }
 
-fn freq_to_midi(freq_hz: f32) -> Option<f32> {
-    let freq_hz = crate::util::finite_positive(freq_hz)?;
-    let m = A440_MIDI as f32 + SEMITONES_PER_OCTAVE as f32 * (freq_hz / A440_HZ).log2();
-    m.is_finite().then_some(m)
-}
-
 impl MusicalNote {
-    pub fn from_frequency(freq_hz: f32) -> Option<Self> {
-        freq_to_midi(freq_hz).map(|m| Self::from_midi(m.round() as i32))
+    pub fn from_frequency(freq_hz: f32, tuning: Tuning) -> Option<Self> {
+        tuning
+            .nearest(freq_hz)
+            .map(|(note, _)| Self::from_midi(note))
     }
 
     pub fn from_midi(midi_number: i32) -> Self {
@@ -38,8 +123,8 @@ impl MusicalNote {
         }
     }
 
-    pub fn to_frequency(self) -> f32 {
-        A440_HZ * ((self.midi_number - A440_MIDI) as f32 / SEMITONES_PER_OCTAVE as f32).exp2()
+    pub fn to_frequency(self, tuning: Tuning) -> f32 {
+        tuning.frequency(self.midi_number)
     }
 
     pub fn is_black(self) -> bool {
@@ -61,14 +146,10 @@ pub struct NoteInfo {
 }
 
 impl NoteInfo {
-    pub fn from_frequency(freq_hz: f32) -> Option<Self> {
-        freq_to_midi(freq_hz).map(|midi| {
-            let rounded = midi.round() as i32;
-            let cents = ((midi - rounded as f32) * 100.0).round() as i32;
-            Self {
-                note: MusicalNote::from_midi(rounded),
-                cents,
-            }
+    pub fn from_frequency(freq_hz: f32, tuning: Tuning) -> Option<Self> {
+        tuning.nearest(freq_hz).map(|(note, off)| Self {
+            note: MusicalNote::from_midi(note),
+            cents: (off * 100.0).round() as i32,
         })
     }
 
@@ -78,3 +159,57 @@ impl NoteInfo {
         format!("{:<4}{sign} {} Cents", self.note, self.cents.abs())
     }
 }
+
+const INTERVAL_NAMES: [&str; 12] = [
+    "P1", "m2", "M2", "m3", "M3", "P4", "TT", "P5", "m6", "M6", "m7", "M7",
+];
+
+// Nearest equal-tempered interval between two frequencies, e.g. `"1 oct + M3 -14 cents"`.
+// A ratio, so the reference pitch drops out.
+pub fn fmt_interval(a_hz: f32, b_hz: f32) -> Option<String> {
+    let tuning = Tuning::default();
+    let semitones = (tuning.midi(a_hz)? - tuning.midi(b_hz)?).abs();
+    let rounded = semitones.round() as i32;
+    let cents = ((semitones - rounded as f32) * 100.0).round() as i32;
+    let step = INTERVAL_NAMES[rounded.rem_euclid(SEMITONES_PER_OCTAVE) as usize];
+    let name = match (rounded / SEMITONES_PER_OCTAVE, step) {
+        (0, step) => step.to_owned(),
+        (1, "P1") => "P8".to_owned(),
+        (octaves, "P1") => format!("{octaves} oct"),
+        (octaves, step) => format!("{octaves} oct + {step}"),
+    };
+    Some(if cents == 0 {
+        name
+    } else {
+        format!("{name} {cents:+} cents")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notes_follow_the_reference_and_temperament() {
+        let baroque = Tuning {
+            a4_hz: 415.0,
+            temperament: Temperament::Equal,
+        };
+        assert_eq!(baroque.nearest(415.0), Some((69, 0.0)));
+        assert!((baroque.frequency(81) - 830.0).abs() < 1e-3);
+
+        // Just intonation keeps A at the reference; its major third above C
+        // sits 14 cents under equal temperament, so E4 reads as in tune.
+        let just = Tuning {
+            a4_hz: 440.0,
+            temperament: Temperament::Just,
+        };
+        assert!((just.frequency(69) - 440.0).abs() < 1e-3);
+        let e4 = just.frequency(64);
+        let (note, off) = just.nearest(e4).unwrap();
+        assert_eq!(note, 64);
+        assert!(off.abs() < 1e-4);
+        let c4 = just.frequency(60);
+        assert!((e4 / c4 - 1.25).abs() < 1e-4, "{}", e4 / c4);
+    }
+}
@@ -8,6 +8,13 @@ pub const EPSILON: f32 = 1e-4;
 pub const STOP_SPREAD_MIN: f32 = 0.2;
 pub const STOP_SPREAD_MAX: f32 = 5.0;
 
+crate::macros::choice_enum!(all pub enum ColorBlend {
+    #[default]
+    Srgb => "sRGB",
+    Oklab => "Oklab",
+    Lch => "LCh",
+});
+
 pub fn colors_equal(a: Color, b: Color) -> bool {
     (a.r - b.r).abs() <= EPSILON
         && (a.g - b.g).abs() <= EPSILON
@@ -33,6 +40,93 @@ pub fn lerp_color(a: Color, b: Color, t: f32) -> Color {
     )
 }
 
+/// Mixes two sRGB colors. Oklab keeps midpoints about as bright and saturated
+/// as the ends; LCh additionally walks the shorter way around the hue circle.
+pub fn mix_rgba(a: [f32; 4], b: [f32; 4], t: f32, blend: ColorBlend) -> [f32; 4] {
+    let t = t.clamp(0.0, 1.0);
+    let [x, y] = [a, b].map(|c| [c[0], c[1], c[2]]);
+    let [r, g, bl] = match blend {
+        ColorBlend::Srgb => std::array::from_fn(|ch| lerp(x[ch], y[ch], t)),
+        ColorBlend::Oklab => {
+            let [x, y] = [x, y].map(srgb_to_oklab);
+            oklab_to_srgb(std::array::from_fn(|ch| lerp(x[ch], y[ch], t)))
+        }
+        ColorBlend::Lch => {
+            let [x, y] = [x, y].map(|c| oklab_to_lch(srgb_to_oklab(c)));
+            oklab_to_srgb(lch_to_oklab(lerp_lch(x, y, t)))
+        }
+    };
+    [r, g, bl, lerp(a[3], b[3], t)]
+}
+
+pub fn mix_color(a: Color, b: Color, t: f32, blend: ColorBlend) -> Color {
+    let [r, g, b, a] = mix_rgba([a.r, a.g, a.b, a.a], [b.r, b.g, b.b, b.a], t, blend);
+    Color::from_rgba(r, g, b, a)
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.040_45 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    let c = c.clamp(0.0, 1.0);
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+// Björn Ottosson's Oklab matrices.
+fn srgb_to_oklab(rgb: [f32; 3]) -> [f32; 3] {
+    let [r, g, b] = rgb.map(srgb_to_linear);
+    let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+    let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+    let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+    [
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    ]
+}
+
+fn oklab_to_srgb([l, a, b]: [f32; 3]) -> [f32; 3] {
+    let l_ = (l + 0.396_337_78 * a + 0.215_803_76 * b).powi(3);
+    let m_ = (l - 0.105_561_346 * a - 0.063_854_17 * b).powi(3);
+    let s_ = (l - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+    [
+        4.076_741_7 * l_ - 3.307_711_6 * m_ + 0.230_969_94 * s_,
+        -1.268_438 * l_ + 2.609_757_4 * m_ - 0.341_319_38 * s_,
+        -0.004_196_086_3 * l_ - 0.703_418_6 * m_ + 1.707_614_7 * s_,
+    ]
+    .map(linear_to_srgb)
+}
+
+fn oklab_to_lch([l, a, b]: [f32; 3]) -> [f32; 3] {
+    [l, a.hypot(b), b.atan2(a)]
+}
+
+fn lch_to_oklab([l, c, h]: [f32; 3]) -> [f32; 3] {
+    [l, c * h.cos(), c * h.sin()]
+}
+
+// Greys have no meaningful hue, so they borrow the other end's.
+fn lerp_lch(a: [f32; 3], b: [f32; 3], t: f32) -> [f32; 3] {
+    const ACHROMATIC: f32 = 1e-4;
+    let (ha, hb) = match (a[1] < ACHROMATIC, b[1] < ACHROMATIC) {
+        (true, false) => (b[2], b[2]),
+        (false, true) => (a[2], a[2]),
+        _ => (a[2], b[2]),
+    };
+    let tau = std::f32::consts::TAU;
+    let dh = (hb - ha + std::f32::consts::PI).rem_euclid(tau) - std::f32::consts::PI;
+    [lerp(a[0], b[0], t), lerp(a[1], b[1], t), ha + dh * t]
+}
+
 pub fn with_alpha(color: Color, alpha: f32) -> Color {
     Color {
         a: alpha.clamp(0.0, 1.0),
@@ -52,16 +146,38 @@ fn gradient_segment(count: usize, t: f32) -> Option<(usize, f32)> {
     })
 }
 
-pub fn sample_rgba_gradient(palette: &[[f32; 4]], t: f32) -> [f32; 4] {
+pub fn sample_rgba_gradient(palette: &[[f32; 4]], t: f32, blend: ColorBlend) -> [f32; 4] {
     match gradient_segment(palette.len(), t) {
-        Some((i, f)) => {
-            let [a, b] = [palette[i], palette[i + 1]];
-            std::array::from_fn(|ch| lerp(a[ch], b[ch], f))
-        }
+        Some((i, f)) => mix_rgba(palette[i], palette[i + 1], f, blend),
         None => palette.first().copied().unwrap_or([0.0; 4]),
     }
 }
 
+/// Stops either side of `t` and the spread-shaped blend between them; the
+/// spectrogram shader's `palette_color` does the same.
+pub fn stop_segment(positions: &[f32], spreads: &[f32], t: f32) -> (usize, usize, f32) {
+    let count = positions.len();
+    if count < 2 {
+        return (0, 0, 0.0);
+    }
+
+    let t = t.clamp(0.0, 1.0);
+    let hi = positions
+        .partition_point(|&pos| pos < t)
+        .clamp(1, count - 1);
+    let lo = hi - 1;
+    let linear =
+        ((t - positions[lo]) / (positions[hi] - positions[lo]).max(f32::EPSILON)).clamp(0.0, 1.0);
+    let sl = spreads.get(lo).copied().unwrap_or(1.0);
+    let sr = spreads.get(hi).copied().unwrap_or(1.0);
+    let f = if (sl - 1.0).abs() < EPSILON && (sr - 1.0).abs() < EPSILON {
+        linear
+    } else {
+        linear.powf(sl / sr).clamp(0.0, 1.0)
+    };
+    (lo, hi, f)
+}
+
 pub fn sanitize_stop_positions(raw: Option<&[f32]>, defaults: &[f32]) -> Vec<f32> {
     let count = defaults.len();
     if count < 2 {
@@ -107,3 +223,33 @@ pub fn sanitize_stop_spreads(raw: Option<&[f32]>, count: usize) -> Vec<f32> {
     }
     out
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn perceptual_blends_keep_midpoints_saturated() {
+        let (red, blue) = ([1.0, 0.0, 0.0, 1.0], [0.0, 0.0, 1.0, 0.0]);
+        for blend in ColorBlend::ALL.iter().copied() {
+            for (c, t) in [(red, 0.0), (blue, 1.0)] {
+                let end = mix_rgba(red, blue, t, blend);
+                assert!(
+                    end.iter().zip(c).all(|(a, b)| (a - b).abs() < 1e-3),
+                    "{blend}"
+                );
+            }
+            assert_eq!(mix_rgba(red, blue, 0.5, blend)[3], 0.5);
+        }
+
+        let brightness = |c: [f32; 4]| c[0] + c[1] + c[2];
+        let srgb = mix_rgba(red, blue, 0.5, ColorBlend::Srgb);
+        let oklab = mix_rgba(red, blue, 0.5, ColorBlend::Oklab);
+        assert!(brightness(oklab) > brightness(srgb));
+
+        // Grey to green keeps green's hue rather than swinging through red.
+        let grey = [0.5, 0.5, 0.5, 1.0];
+        let mid = mix_rgba(grey, [0.0, 0.8, 0.0, 1.0], 0.5, ColorBlend::Lch);
+        assert!(mid[1] > mid[0] && mid[1] > mid[2]);
+    }
+}
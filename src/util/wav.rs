@@ -0,0 +1,58 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// 32-bit IEEE float WAV writer for captured traces.
+
+const FORMAT_IEEE_FLOAT: u16 = 3;
+const HEADER_LEN: usize = 44;
+
+/// `samples` are interleaved. Returns `None` if they don't divide into whole
+/// frames or the data would overflow the RIFF size fields.
+pub fn encode_f32(samples: &[f32], channels: u16, sample_rate: u32) -> Option<Vec<u8>> {
+    if channels == 0 || sample_rate == 0 || !samples.len().is_multiple_of(channels.into()) {
+        return None;
+    }
+    let data_len = u32::try_from(samples.len() * 4).ok()?;
+    let riff_len = data_len.checked_add(HEADER_LEN as u32 - 8)?;
+    let block_align = channels * 4;
+
+    let mut out = Vec::with_capacity(HEADER_LEN + data_len as usize);
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&riff_len.to_le_bytes());
+    out.extend_from_slice(b"WAVEfmt ");
+    out.extend_from_slice(&16_u32.to_le_bytes());
+    out.extend_from_slice(&FORMAT_IEEE_FLOAT.to_le_bytes());
+    out.extend_from_slice(&channels.to_le_bytes());
+    out.extend_from_slice(&sample_rate.to_le_bytes());
+    out.extend_from_slice(&(sample_rate * u32::from(block_align)).to_le_bytes());
+    out.extend_from_slice(&block_align.to_le_bytes());
+    out.extend_from_slice(&32_u16.to_le_bytes());
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        out.extend_from_slice(&sample.to_le_bytes());
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_interleaved_float_frames() {
+        let wav = encode_f32(&[0.5, -0.5, 1.0, 0.0], 2, 48_000).unwrap();
+        assert_eq!(wav.len(), HEADER_LEN + 16);
+        assert_eq!(&wav[..4], b"RIFF");
+        assert_eq!(wav[4..8], (wav.len() as u32 - 8).to_le_bytes());
+        assert_eq!(&wav[8..16], b"WAVEfmt ");
+        assert_eq!(wav[20..22], FORMAT_IEEE_FLOAT.to_le_bytes());
+        assert_eq!(wav[28..32], 384_000_u32.to_le_bytes());
+        assert_eq!(wav[32..34], 8_u16.to_le_bytes());
+        assert_eq!(&wav[36..40], b"data");
+        assert_eq!(wav[44..48], 0.5_f32.to_le_bytes());
+
+        assert!(encode_f32(&[0.0; 3], 2, 48_000).is_none());
+        assert!(encode_f32(&[], 0, 48_000).is_none());
+    }
+}
@@ -9,12 +9,19 @@ macro_rules! visual_modules {
             pub mod state;
             pub(in crate::visuals) use processor::{$config, $processor};
             pub(in crate::visuals) use state::{widget, $state};
+
+            impl crate::visuals::snapshot::Snapshot for $state {
+                fn element<'a, M: 'a>(state: &'a std::cell::RefCell<Self>) -> iced::Element<'a, M> {
+                    widget(state)
+                }
+            }
         })+
     };
 }
 
 macro_rules! visualization_widget {
-    (@base $widget:ident, $state:ty, |$this:ident, $renderer:ident, $theme:ident, $bounds:ident| $draw:block) => {
+    (@base $widget:ident, $state:ty, |$this:ident, $renderer:ident, $theme:ident, $bounds:ident| $draw:block
+        $(, update |$uthis:ident, $event:ident, $cursor:ident, $ubounds:ident, $shell:ident| $update:block)?) => {
         struct $widget<'a> {
             state: &'a std::cell::RefCell<$state>,
         }
@@ -53,24 +60,48 @@ macro_rules! visualization_widget {
                 _: iced::advanced::mouse::Cursor,
                 _: &iced::Rectangle,
             ) {
-                use iced_wgpu::primitive::Renderer as _;
                 let ($this, $renderer, $theme, $bounds) = (self, renderer, theme, layout.bounds());
                 $draw
             }
+
+            $(
+            fn update(
+                &mut self,
+                _: &mut iced::advanced::widget::Tree,
+                event: &iced::Event,
+                layout: iced::advanced::Layout<'_>,
+                cursor: iced::advanced::mouse::Cursor,
+                _: &iced::Renderer,
+                _: &mut dyn iced::advanced::Clipboard,
+                shell: &mut iced::advanced::Shell<'_, M>,
+                _: &iced::Rectangle,
+            ) {
+                let ($uthis, $event, $cursor, $ubounds, $shell) =
+                    (&*self, event, cursor, layout.bounds(), shell);
+                $update
+            }
+            )?
         }
 
         pub(in crate::visuals) fn widget<'a, M: 'a>(state: &'a std::cell::RefCell<$state>) -> iced::Element<'a, M> {
             iced::Element::new($widget::new(state))
         }
     };
-    ($widget:ident, $state:ty, |$this:ident, $renderer:ident, $theme:ident, $bounds:ident| $draw:block) => {
-        $crate::visuals::visualization_widget!(@base $widget, $state, |$this, $renderer, $theme, $bounds| $draw);
+    ($widget:ident, $state:ty, |$this:ident, $renderer:ident, $theme:ident, $bounds:ident| $draw:block
+        $(, update |$uthis:ident, $event:ident, $cursor:ident, $ubounds:ident, $shell:ident| $update:block)?) => {
+        $crate::visuals::visualization_widget!(@base $widget, $state, |$this, $renderer, $theme, $bounds| $draw
+            $(, update |$uthis, $event, $cursor, $ubounds, $shell| $update)?);
     };
     ($widget:ident, $state:ty, $primitive:ty) => {
         $crate::visuals::visualization_widget!(@base $widget, $state, |this, renderer, theme, bounds| {
             let state = this.state.borrow();
             match state.visual_params(bounds) {
-                Some(params) => renderer.draw_primitive(bounds, <$primitive>::new(params)),
+                Some(params) => $crate::visuals::render::common::draw_shader(
+                    renderer,
+                    theme,
+                    bounds,
+                    <$primitive>::new(params),
+                ),
                 None => $crate::visuals::render::common::fill_rect(
                     renderer,
                     bounds,
@@ -89,6 +120,7 @@ visual_modules! {
     spectrogram { SpectrogramProcessor, SpectrogramConfig, SpectrogramState },
     spectrum { SpectrumProcessor, SpectrumConfig, SpectrumState },
     stereometer { StereometerProcessor, StereometerConfig, StereometerState },
+    tuner { TunerProcessor, TunerConfig, TunerState },
     waveform { WaveformProcessor, WaveformConfig, WaveformState },
 }
 
@@ -102,6 +134,41 @@ pub mod options {
     crate::macros::choice_enum!(all pub enum CorrelationMeterMode { Off => "Off", SingleBand => "Single Band", #[default] MultiBand => "Multi Band" });
     crate::macros::choice_enum!(all pub enum CorrelationMeterSide { Left => "Left", #[default] Right => "Right" });
     crate::macros::choice_enum!(all pub enum PianoRollOverlay { #[default] Off => "Off", Right => "Right", Left => "Left" });
+    crate::macros::choice_enum!(all pub enum SpectrogramInterpolation { Nearest => "Nearest", #[default] Linear => "Linear", Cubic => "Cubic" });
+    crate::macros::choice_enum!(all pub enum AspectLock {
+        #[default] Off => "Off",
+        Square => "Square",
+        Standard => "4:3",
+        Wide => "16:9",
+        Portrait => "3:4",
+    });
+
+    impl AspectLock {
+        /// Width over height, `None` when the window resizes freely.
+        pub const fn ratio(self) -> Option<f32> {
+            match self {
+                Self::Off => None,
+                Self::Square => Some(1.0),
+                Self::Standard => Some(4.0 / 3.0),
+                Self::Wide => Some(16.0 / 9.0),
+                Self::Portrait => Some(3.0 / 4.0),
+            }
+        }
+    }
+
+    crate::macros::choice_enum!(all pub enum SnapshotRate { #[default] Batch => "Every batch", Hz30 => "30 Hz", Hz60 => "60 Hz", Hz120 => "120 Hz" });
+
+    impl SnapshotRate {
+        /// `None` hands every capture batch straight to the visuals.
+        pub const fn hz(self) -> Option<f32> {
+            match self {
+                Self::Batch => None,
+                Self::Hz30 => Some(30.0),
+                Self::Hz60 => Some(60.0),
+                Self::Hz120 => Some(120.0),
+            }
+        }
+    }
 
     crate::macros::choice_enum!(no_default all pub enum MeterMode {
         LufsShortTerm => "LUFS Short-term",
@@ -110,15 +177,53 @@ pub mod options {
         RmsSlow => "RMS Slow",
         TruePeak => "True Peak",
     });
+    crate::macros::choice_enum!(all pub enum MeterOrientation { #[default] Vertical => "Vertical", Horizontal => "Horizontal" });
 
-    crate::macros::choice_enum!(all pub enum SpectrumDisplayMode { #[default] Line => "Line", Bar => "Bar" });
+    crate::macros::choice_enum!(all pub enum SpectrumDisplayMode { #[default] Line => "Line", Bar => "Bar", Fill => "Filled" });
+    crate::macros::choice_enum!(all pub enum SpectrumLevelGrid { #[default] Off => "Off", Db6 => "6 dB", Db10 => "10 dB", Db12 => "12 dB" });
     crate::macros::choice_enum!(all pub enum SpectrumWeightingMode { #[default] AWeighted => "A-Weighted", Raw => "Raw" });
-    crate::macros::choice_enum!(all pub enum WaveformColorMode { #[default] Frequency => "Frequency Bands", Loudness => "Loudness", Static => "Static" });
+    crate::macros::choice_enum!(all pub enum TraceColor {
+        #[default] Auto => "Auto",
+        Red => "Red",
+        Orange => "Orange",
+        Yellow => "Yellow",
+        Green => "Green",
+        Cyan => "Cyan",
+        Blue => "Blue",
+        Violet => "Violet",
+        Pink => "Pink",
+    });
+    crate::macros::choice_enum!(all pub enum TunerPreset {
+        #[default] Chromatic => "Chromatic",
+        Guitar => "Guitar (EADGBE)",
+        DropD => "Guitar, drop D",
+        Bass4 => "Bass, 4-string",
+        Bass5 => "Bass, 5-string",
+        Ukulele => "Ukulele (GCEA)",
+    });
+
+    impl TunerPreset {
+        /// Open strings as MIDI notes, in string order; empty for chromatic.
+        pub const fn strings(self) -> &'static [i32] {
+            match self {
+                Self::Chromatic => &[],
+                Self::Guitar => &[40, 45, 50, 55, 59, 64],
+                Self::DropD => &[38, 45, 50, 55, 59, 64],
+                Self::Bass4 => &[28, 33, 38, 43],
+                Self::Bass5 => &[23, 28, 33, 38, 43],
+                Self::Ukulele => &[67, 60, 64, 69],
+            }
+        }
+    }
+    crate::macros::choice_enum!(all pub enum WaveformColorMode { #[default] Frequency => "Frequency Bands", Loudness => "Loudness", Rms => "RMS Level", Brightness => "Brightness", Static => "Static" });
     crate::macros::choice_enum!(all pub enum WaveformHistoryMode { #[default] Off => "Off", RmsFast => "RMS Fast", RmsSlow => "RMS Slow" });
 }
 
+mod budget;
+mod latency;
 pub mod palettes;
 pub mod registry;
+pub mod snapshot;
 pub mod render {
     pub mod common;
 }
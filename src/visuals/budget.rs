@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Keeps visual ingest inside a share of real time so audio batches don't
+// queue behind the UI thread. Each window the costliest module that can
+// still shed detail steps down once; with headroom to spare, a cut module
+// steps back up.
+
+/// Share of real time the visuals may spend ingesting audio.
+pub(super) const BUDGET: f64 = 0.25;
+pub(super) const WINDOW_SECONDS: f64 = 2.0;
+// Restoring a step is assumed to double that module's cost; the margin keeps
+// it from bouncing straight back over the budget.
+const RESTORE_COST: f64 = 2.0;
+const RESTORE_MARGIN: f64 = 0.8;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct Load {
+    /// Ingest time over audio time for the last window.
+    pub share: f64,
+    pub cut: u8,
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) enum Adjust {
+    /// Try these in order until one accepts a deeper cut.
+    Cut(Vec<usize>),
+    Restore(usize),
+    Hold,
+}
+
+pub(super) fn adjust(loads: &[Load]) -> Adjust {
+    let total: f64 = loads.iter().map(|load| load.share).sum();
+    if total > BUDGET {
+        let mut order: Vec<usize> = (0..loads.len())
+            .filter(|&index| loads[index].share > 0.0)
+            .collect();
+        order.sort_by(|&a, &b| loads[b].share.total_cmp(&loads[a].share));
+        return Adjust::Cut(order);
+    }
+    // The cheapest restore first, so headroom goes as far as it can.
+    loads
+        .iter()
+        .enumerate()
+        .filter(|(_, load)| load.cut > 0)
+        .map(|(index, load)| (index, load.share * (RESTORE_COST - 1.0)))
+        .filter(|&(_, extra)| total + extra < BUDGET * RESTORE_MARGIN)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or(Adjust::Hold, |(index, _)| Adjust::Restore(index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cuts_the_costliest_module_and_restores_with_headroom() {
+        let load = |share, cut| Load { share, cut };
+        assert_eq!(
+            adjust(&[load(0.02, 0), load(0.3, 0), load(0.0, 0), load(0.05, 0)]),
+            Adjust::Cut(vec![1, 3, 0])
+        );
+        assert_eq!(adjust(&[load(0.02, 0), load(0.15, 1)]), Adjust::Hold);
+        assert_eq!(
+            adjust(&[load(0.02, 0), load(0.06, 2), load(0.03, 1)]),
+            Adjust::Restore(2)
+        );
+    }
+}
@@ -0,0 +1,195 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Capture-to-draw latency, measured per visual: each snapshot carries the
+// capture time of the newest audio in it, and the next frame that draws the
+// visual closes the measurement. iced gives no hook once a frame is
+// presented, so the draw is the closest honest point.
+
+use iced::advanced::renderer;
+use iced::advanced::widget::{self, Tree};
+use iced::advanced::{Clipboard, Layout, Shell, Widget, layout, mouse};
+use iced::{Element, Event, Length, Rectangle, Size};
+use std::cell::Cell;
+use std::time::Instant;
+
+// Weight of each new frame in the smoothed latency.
+const SMOOTHING: f32 = 0.1;
+
+#[derive(Debug)]
+pub(crate) struct DisplayLatency {
+    // Capture time of the newest snapshot no frame has drawn yet.
+    undrawn: Cell<Option<Instant>>,
+    // NaN until the first frame.
+    smoothed_ms: Cell<f32>,
+}
+
+impl Default for DisplayLatency {
+    fn default() -> Self {
+        Self {
+            undrawn: Cell::new(None),
+            smoothed_ms: Cell::new(f32::NAN),
+        }
+    }
+}
+
+impl DisplayLatency {
+    /// A snapshot holding audio captured at `at` reached the visual.
+    pub(crate) fn note_captured(&self, at: Instant) {
+        self.undrawn.set(Some(at));
+    }
+
+    fn note_drawn(&self, now: Instant) {
+        let Some(at) = self.undrawn.take() else {
+            return;
+        };
+        let ms = now.saturating_duration_since(at).as_secs_f32() * 1_000.0;
+        let prev = self.smoothed_ms.get();
+        self.smoothed_ms.set(if prev.is_nan() {
+            ms
+        } else {
+            prev + (ms - prev) * SMOOTHING
+        });
+    }
+
+    /// Smoothed time from capture to the first frame drawing that audio.
+    pub(crate) fn ms(&self) -> Option<f32> {
+        Some(self.smoothed_ms.get()).filter(|ms| ms.is_finite())
+    }
+}
+
+/// Wraps a visual so drawing it closes its latency measurement.
+pub(crate) fn probe<'a, M: 'a>(
+    content: Element<'a, M>,
+    latency: &'a DisplayLatency,
+) -> Element<'a, M> {
+    Element::new(Probe { content, latency })
+}
+
+struct Probe<'a, M> {
+    content: Element<'a, M>,
+    latency: &'a DisplayLatency,
+}
+
+impl<M> Widget<M, iced::Theme, iced::Renderer> for Probe<'_, M> {
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn layout(
+        &mut self,
+        tree: &mut Tree,
+        renderer: &iced::Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content
+            .as_widget_mut()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn operate(
+        &mut self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &iced::Renderer,
+        operation: &mut dyn widget::Operation,
+    ) {
+        self.content
+            .as_widget_mut()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &iced::Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, M>,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget_mut().update(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        );
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &iced::Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut iced::Renderer,
+        theme: &iced::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.latency.note_drawn(Instant::now());
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn each_snapshot_is_measured_once_by_the_first_draw() {
+        let latency = DisplayLatency::default();
+        let at = Instant::now();
+        latency.note_drawn(at);
+        assert_eq!(latency.ms(), None);
+
+        latency.note_captured(at);
+        latency.note_drawn(at + Duration::from_millis(20));
+        // Redraws without new audio leave the reading alone.
+        latency.note_drawn(at + Duration::from_millis(500));
+        assert!((latency.ms().unwrap() - 20.0).abs() < 0.01);
+
+        latency.note_captured(at);
+        latency.note_drawn(at + Duration::from_millis(30));
+        assert!((latency.ms().unwrap() - 21.0).abs() < 0.01);
+    }
+}
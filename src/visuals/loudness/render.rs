@@ -5,7 +5,7 @@ use iced::Rectangle;
 use iced::advanced::graphics::Viewport;
 
 use crate::visuals::render::common::sdf_primitive;
-use crate::visuals::render::common::{GeometryScratch, ClipTransform, line_vertices, quad_vertices};
+use crate::visuals::render::common::{GeometryScratch, ClipTransform, quad_vertices, rule_vertices};
 
 const GAP_FRACTION: f32 = 0.1;
 const BAR_WIDTH_SCALE: f32 = 0.6;
@@ -23,6 +23,30 @@ pub struct MeterFill {
     pub peak: Option<(f32, [f32; 4])>,
 }
 
+/// Geometry in screen coordinates; lines are horizontal or vertical.
+#[derive(Debug, Clone, Copy)]
+pub enum MeterShape {
+    Quad { min: (f32, f32), max: (f32, f32), color: [f32; 4] },
+    Line { from: (f32, f32), to: (f32, f32), color: [f32; 4], thickness: f32 },
+}
+
+impl MeterShape {
+    /// Rectangle covering the shape, for renderers that only fill quads.
+    pub fn rect(self) -> (Rectangle, [f32; 4]) {
+        let (min, max, color) = match self {
+            Self::Quad { min, max, color } => (min, max, color),
+            Self::Line { from, to, color, thickness } => {
+                let half = thickness * 0.5;
+                let (dx, dy) = if from.1 == to.1 { (0.0, half) } else { (half, 0.0) };
+                let min = (from.0.min(to.0) - dx, from.1.min(to.1) - dy);
+                let max = (from.0.max(to.0) + dx, from.1.max(to.1) + dy);
+                (min, max, color)
+            }
+        };
+        (Rectangle::new(min.into(), (max.0 - min.0, max.1 - min.1).into()), color)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct LoudnessParams {
     pub key: u64,
@@ -35,8 +59,13 @@ pub struct LoudnessParams {
     pub guides: &'static [f32],
     pub guide_color: [f32; 4],
     pub threshold_db: Option<f32>,
-    pub left_padding: f32,
-    pub right_padding: f32,
+    pub horizontal: bool,
+    // Across the bars: guide labels before, value label after.
+    pub leading_padding: f32,
+    pub trailing_padding: f32,
+    pub opacity: f32,
+    /// Beat-pulse brightening, `0..=1`.
+    pub pulse: f32,
 }
 
 impl LoudnessParams {
@@ -47,9 +76,31 @@ impl LoudnessParams {
         raw.powf(0.9)
     }
 
+    /// Screen position of `db` along the level axis.
+    pub fn level_of(&self, db: f32) -> f32 {
+        let ratio = self.db_to_ratio(db);
+        let b = self.bounds;
+        if self.horizontal {
+            b.x + b.width * ratio
+        } else {
+            b.y + b.height * (1.0 - ratio)
+        }
+    }
+
+    /// Maps (across, along) meter coordinates to screen (x, y).
+    pub fn point(&self, cross: f32, level: f32) -> (f32, f32) {
+        if self.horizontal { (level, cross) } else { (cross, level) }
+    }
+
+    /// Start of the first bar, bar thickness and bar stride across the meter.
     pub fn meter_bounds(&self) -> Option<(f32, f32, f32)> {
         let bar_count = self.bars.len();
-        let meter_width = (self.bounds.width - self.left_padding - self.right_padding).max(0.0);
+        let (origin, extent) = if self.horizontal {
+            (self.bounds.y, self.bounds.height)
+        } else {
+            (self.bounds.x, self.bounds.width)
+        };
+        let meter_width = (extent - self.leading_padding - self.trailing_padding).max(0.0);
         if meter_width <= 0.0 { return None; }
 
         let gap = meter_width * GAP_FRACTION;
@@ -58,44 +109,35 @@ impl LoudnessParams {
         let bar_width = bar_slot * BAR_WIDTH_SCALE;
         let bar_offset = (bar_slot - bar_width) * 0.5;
         let stride = bar_width + gap;
-        let meter_x = self.bounds.x + self.left_padding + bar_offset;
+        let meter_x = origin + self.leading_padding + bar_offset;
 
         Some((meter_x, bar_width, stride))
     }
-}
-
-fn sub_bar_gap(bar_width: f32, fill_count: usize) -> f32 {
-    if fill_count <= 1 || bar_width <= 2.0 { return 0.0; }
 
-    let desired = (bar_width * INNER_GAP_RATIO).max(0.5);
-    let max_gap = bar_width / (fill_count - 1) as f32 * 0.5;
-    desired.min(max_gap)
-}
-
-impl LoudnessPrimitive {
-    fn build_vertices(&self, viewport: &Viewport, scratch: &mut GeometryScratch) {
-        let clip = ClipTransform::from_viewport(viewport);
-        let Some((meter_x, bar_width, stride)) = self.params.meter_bounds() else {
+    /// Calls `emit` with every bar segment, peak tick, guide and threshold line.
+    pub fn for_each_shape(&self, mut emit: impl FnMut(MeterShape)) {
+        let Some((meter_x, bar_width, stride)) = self.meter_bounds() else {
             return;
         };
 
-        let bounds = self.params.bounds;
-        let y0 = bounds.y;
-        let y1 = bounds.y + bounds.height;
-        let height = y1 - y0;
-        let y_of = |db| (y1 - height * self.params.db_to_ratio(db)).clamp(y0, y1);
-        let bar_count = self.params.bars.len();
-        let fill_count: usize = self.params.fill_counts.iter().sum();
-        let vertices = &mut scratch.vertices;
-        vertices.reserve(bar_count * 12 + fill_count * 30 + self.params.guides.len() * 6);
+        let (min_db, max_db) = (self.min_db, self.max_db);
+        let level_of = |db: f32| self.level_of(db.clamp(min_db, max_db));
+        let quad = |c0: f32, l0: f32, c1: f32, l1: f32, color| {
+            let (min, max) = (self.point(c0, l0.min(l1)), self.point(c1, l0.max(l1)));
+            MeterShape::Quad { min, max, color }
+        };
+        let line = |c0: f32, c1: f32, l: f32, color, thickness| {
+            MeterShape::Line { from: self.point(c0, l), to: self.point(c1, l), color, thickness }
+        };
+        let bar_count = self.bars.len();
 
-        for (i, (bar, &sub_bar_count)) in self.params.bars.iter().zip(&self.params.fill_counts).enumerate() {
+        for (i, (bar, &sub_bar_count)) in self.bars.iter().zip(&self.fill_counts).enumerate() {
             let sub_bar_count = sub_bar_count.min(bar.len());
             if sub_bar_count == 0 { continue; }
             let x0 = meter_x + i as f32 * stride;
             let x1 = x0 + bar_width;
 
-            vertices.extend(quad_vertices(x0, y0, x1, y1, clip, self.params.bg_color));
+            emit(quad(x0, level_of(min_db), x1, level_of(max_db), self.bg_color));
             let inner_gap = sub_bar_gap(bar_width, sub_bar_count);
             let total_inner = inner_gap * (sub_bar_count - 1) as f32;
             let seg_width = ((bar_width - total_inner) / sub_bar_count as f32).max(0.0);
@@ -107,20 +149,13 @@ impl LoudnessPrimitive {
                 } else {
                     sx0 + seg_width
                 };
-                let value = fill.db.clamp(self.params.min_db, self.params.max_db);
-                let mut lower = self.params.min_db;
+                let value = fill.db.clamp(min_db, max_db);
+                let mut lower = min_db;
                 for &(ceiling, color) in &fill.segments {
-                    let ceiling = ceiling.clamp(self.params.min_db, self.params.max_db);
+                    let ceiling = ceiling.clamp(min_db, max_db);
                     let upper = value.min(ceiling);
                     if upper > lower {
-                        vertices.extend(quad_vertices(
-                            sx0,
-                            y_of(upper),
-                            sx1,
-                            y_of(lower),
-                            clip,
-                            color,
-                        ));
+                        emit(quad(sx0, level_of(upper), sx1, level_of(lower), color));
                     }
                     lower = lower.max(ceiling);
                     if value <= ceiling {
@@ -129,48 +164,61 @@ impl LoudnessPrimitive {
                 }
 
                 if let Some((db, color)) = fill.peak {
-                    let cy = y_of(db);
-                    vertices.extend(line_vertices(
-                        (sx0, cy),
-                        (sx1, cy),
-                        color,
-                        color,
-                        PEAK_THICKNESS,
-                        clip,
-                    ));
+                    emit(line(sx0, sx1, level_of(db), color, PEAK_THICKNESS));
                 }
             }
         }
 
         let guide_anchor = meter_x - GUIDE_PADDING;
-        for &db in self.params.guides {
-            let cy = y_of(db);
-            vertices.extend(line_vertices(
-                (guide_anchor - GUIDE_LENGTH, cy),
-                (guide_anchor, cy),
-                self.params.guide_color,
-                self.params.guide_color,
+        for &db in self.guides.iter().filter(|db| (min_db..=max_db).contains(*db)) {
+            emit(line(
+                guide_anchor - GUIDE_LENGTH,
+                guide_anchor,
+                level_of(db),
+                self.guide_color,
                 GUIDE_THICKNESS,
-                clip,
             ));
         }
 
-        if let Some(db) = self.params.threshold_db {
-            let cy = y_of(db);
+        if let Some(db) = self.threshold_db {
             for i in 0..bar_count {
                 let x0 = meter_x + i as f32 * stride;
-                let x1 = x0 + bar_width;
-                vertices.extend(line_vertices(
-                    (x0, cy),
-                    (x1, cy),
-                    self.params.guide_color,
-                    self.params.guide_color,
+                emit(line(
+                    x0,
+                    x0 + bar_width,
+                    level_of(db),
+                    self.guide_color,
                     THRESHOLD_THICKNESS,
-                    clip,
                 ));
             }
         }
+    }
+}
+
+fn sub_bar_gap(bar_width: f32, fill_count: usize) -> f32 {
+    if fill_count <= 1 || bar_width <= 2.0 { return 0.0; }
 
+    let desired = (bar_width * INNER_GAP_RATIO).max(0.5);
+    let max_gap = bar_width / (fill_count - 1) as f32 * 0.5;
+    desired.min(max_gap)
+}
+
+impl LoudnessPrimitive {
+    fn build_vertices(&self, viewport: &Viewport, scratch: &mut GeometryScratch) {
+        let clip = ClipTransform::from_viewport(viewport);
+        let params = &self.params;
+        let fill_count: usize = params.fill_counts.iter().sum();
+        let vertices = &mut scratch.vertices;
+        vertices.reserve(params.bars.len() * 12 + fill_count * 30 + params.guides.len() * 6);
+
+        params.for_each_shape(|shape| match shape {
+            MeterShape::Quad { min, max, color } => {
+                vertices.extend(quad_vertices(min.0, min.1, max.0, max.1, clip, color));
+            }
+            MeterShape::Line { from, to, color, thickness } => {
+                vertices.extend(rule_vertices(from, to, color, thickness, clip));
+            }
+        });
     }
 }
 
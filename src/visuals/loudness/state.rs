@@ -3,27 +3,33 @@
 
 use super::processor::{LoudnessSnapshot, MAX_CHANNELS};
 use super::render::{LoudnessParams, LoudnessPrimitive, MeterFill};
+use crate::domain::automation::LoudnessAlert;
 use crate::persistence::settings::LoudnessSettings;
-use crate::visuals::options::MeterMode;
+use crate::visuals::options::{MeterMode, MeterOrientation};
 use crate::visuals::palettes;
+use crate::util::audio::{DbRange, LevelScale, NumberFormat};
 use crate::util::color::color_to_rgba;
-use crate::visuals::render::common::{fill_rect, make_text};
+use crate::visuals::render::common::{
+    draw_shader, faded, fill_rect, make_text, software_rendering,
+};
 use iced::advanced::text;
 use iced::alignment::{Horizontal, Vertical};
 use iced::{Color, Point, Rectangle, Size};
 use std::time::{Duration, Instant};
 
-const DEFAULT_RANGE: (f32, f32) = (-60.0, 4.0);
 const GUIDE_LEVELS: [f32; 6] = [0.0, -6.0, -12.0, -18.0, -24.0, -36.0];
 const PEAK_HOLD: Duration = Duration::from_secs(2);
 const PEAK_DECAY_DB_PER_SEC: f32 = 60.0;
 const LEFT_PADDING: f32 = 28.0;
 const RIGHT_PADDING: f32 = 64.0;
+const TOP_PADDING: f32 = 14.0;
+const BOTTOM_PADDING: f32 = 22.0;
 const LABEL_FONT_SIZE: f32 = 10.0;
 const GUIDE_LABEL_HEIGHT: f32 = 12.0;
 const GUIDE_LABEL_GAP: f32 = 2.0;
 const GUIDE_LABEL_ORDER: [usize; GUIDE_LEVELS.len()] = [0, 2, 5, 3, 4, 1];
 const VALUE_FONT_SIZE: f32 = 12.0;
+const VALUE_LABEL_SIZE: Size = Size::new(68.0, 20.0);
 
 pub const LOUDNESS_PALETTE_SIZE: usize = palettes::loudness::COLORS.len();
 
@@ -68,25 +74,39 @@ impl PeakHold {
 pub(in crate::visuals) struct LoudnessState {
     snapshot: LoudnessSnapshot,
     settings: LoudnessSettings,
+    // The shared loudness range.
+    levels: DbRange,
     pub(in crate::visuals) palette: [Color; LOUDNESS_PALETTE_SIZE],
     peaks: [PeakHold; VISIBLE_METER_COUNT],
     key: u64,
+    opacity: f32,
+    pulse: f32,
+    numbers: NumberFormat,
 }
 
 impl LoudnessState {
     pub fn new() -> Self {
-        let mut snapshot = LoudnessSnapshot::with_floor(DEFAULT_RANGE.0);
+        let levels = LevelScale::Loudness.default_range();
+        let mut snapshot = LoudnessSnapshot::with_floor(levels.floor_db);
         snapshot.channel_count = 2;
-        let peak = PeakHold::new(DEFAULT_RANGE.0, Instant::now());
+        let peak = PeakHold::new(levels.floor_db, Instant::now());
         Self {
             snapshot,
             settings: LoudnessSettings::default(),
+            levels,
             palette: palettes::loudness::COLORS,
             peaks: [peak; VISIBLE_METER_COUNT],
             key: crate::visuals::next_key(),
+            opacity: 1.0,
+            pulse: 0.0,
+            numbers: NumberFormat::default(),
         }
     }
 
+    pub fn set_level_range(&mut self, range: DbRange) {
+        self.levels = range;
+    }
+
     pub fn apply_snapshot(&mut self, mut snapshot: LoudnessSnapshot) {
         snapshot.channel_count = snapshot.channel_count.clamp(1, MAX_CHANNELS);
         self.snapshot = snapshot;
@@ -101,17 +121,50 @@ impl LoudnessState {
         self.settings.right_mode = right;
     }
 
+    pub fn set_orientation(&mut self, orientation: MeterOrientation) {
+        self.settings.orientation = orientation;
+    }
+
+    pub fn set_alert(&mut self, alert: LoudnessAlert) {
+        self.settings.alert = alert;
+    }
+
+    pub fn set_zones(&mut self, loudness: [f32; 3], level: [f32; 3]) {
+        self.settings.loudness_zones = loudness;
+        self.settings.level_zones = level;
+    }
+
+    pub fn alert(&self) -> LoudnessAlert {
+        self.settings.alert
+    }
+
     pub fn export_settings(&self) -> LoudnessSettings {
         self.settings.clone()
     }
 
+    pub fn snapshot(&self) -> LoudnessSnapshot {
+        self.snapshot
+    }
+
+    pub fn set_number_format(&mut self, numbers: NumberFormat) {
+        self.numbers = numbers;
+    }
+
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity;
+    }
+
+    pub fn set_pulse(&mut self, pulse: f32) {
+        self.pulse = pulse;
+    }
+
     pub fn set_palette(&mut self, palette: &[Color; LOUDNESS_PALETTE_SIZE]) {
         self.palette = *palette;
     }
 
     fn get_value(&self, mode: MeterMode, channel: usize) -> f32 {
         let per_channel =
-            |buf: &[f32; MAX_CHANNELS]| buf.get(channel).copied().unwrap_or(DEFAULT_RANGE.0);
+            |buf: &[f32; MAX_CHANNELS]| buf.get(channel).copied().unwrap_or(self.levels.floor_db);
         match mode {
             MeterMode::LufsShortTerm => self.snapshot.short_term_loudness,
             MeterMode::LufsMomentary => self.snapshot.momentary_loudness,
@@ -122,16 +175,21 @@ impl LoudnessState {
     }
 
     fn visual_params(&self, bounds: Rectangle) -> LoudnessParams {
-        let (min, max) = DEFAULT_RANGE;
         let guide_color = color_to_rgba(self.palette[PAL_GUIDE]);
         let bg_color = color_to_rgba(self.palette[PAL_BACKGROUND]);
         let values = self.visible_values();
+        let horizontal = self.settings.orientation == MeterOrientation::Horizontal;
+        let (leading_padding, trailing_padding) = if horizontal {
+            (TOP_PADDING, BOTTOM_PADDING)
+        } else {
+            (LEFT_PADDING, RIGHT_PADDING)
+        };
 
         LoudnessParams {
             key: self.key,
             bounds,
-            min_db: min,
-            max_db: max,
+            min_db: self.levels.floor_db,
+            max_db: self.levels.ceiling_db,
             bg_color,
             bars: [
                 [
@@ -144,8 +202,11 @@ impl LoudnessState {
             guides: &GUIDE_LEVELS,
             guide_color,
             threshold_db: Some(0.0),
-            left_padding: LEFT_PADDING,
-            right_padding: RIGHT_PADDING,
+            horizontal,
+            leading_padding,
+            trailing_padding,
+            opacity: self.opacity,
+            pulse: self.pulse,
         }
     }
 
@@ -159,7 +220,7 @@ impl LoudnessState {
                 side == MeterSide::Both || side == wanted
             })
             .map(|ch| self.get_value(mode, ch))
-            .fold(DEFAULT_RANGE.0, f32::max)
+            .fold(self.levels.floor_db, f32::max)
     }
 
     fn visible_values(&self) -> [f32; VISIBLE_METER_COUNT] {
@@ -175,8 +236,8 @@ impl LoudnessState {
         MeterFill {
             db,
             segments: self.meter_segments(mode),
-            peak: (peak_db > DEFAULT_RANGE.0).then(|| {
-                let color = self.palette[if is_danger_zone(mode, peak_db) {
+            peak: (peak_db > self.levels.floor_db).then(|| {
+                let color = self.palette[if self.is_danger_zone(mode, peak_db) {
                     PAL_DANGER
                 } else {
                     PAL_PEAK
@@ -187,24 +248,39 @@ impl LoudnessState {
     }
 
     fn meter_segments(&self, mode: MeterMode) -> [(f32, [f32; 4]); ZONE_COUNT] {
-        let [low, mid, high] = zone_thresholds(mode);
+        let [low, mid, high] = self.zone_thresholds(mode);
         [
             (low, color_to_rgba(self.palette[PAL_LOW])),
             (mid, color_to_rgba(self.palette[PAL_MID])),
             (high, color_to_rgba(self.palette[PAL_HIGH])),
-            (DEFAULT_RANGE.1, color_to_rgba(self.palette[PAL_DANGER])),
+            (self.levels.ceiling_db, color_to_rgba(self.palette[PAL_DANGER])),
         ]
     }
 
+    fn zone_thresholds(&self, mode: MeterMode) -> [f32; 3] {
+        let mut zones = match mode {
+            MeterMode::LufsShortTerm | MeterMode::LufsMomentary => self.settings.loudness_zones,
+            MeterMode::RmsFast | MeterMode::RmsSlow | MeterMode::TruePeak => {
+                self.settings.level_zones
+            }
+        };
+        zones.sort_by(f32::total_cmp);
+        zones
+    }
+
+    fn is_danger_zone(&self, mode: MeterMode, db: f32) -> bool {
+        db >= self.zone_thresholds(mode)[DANGER_THRESHOLD_INDEX]
+    }
+
     fn reset_peaks(&mut self, now: Instant) {
-        self.peaks.fill(PeakHold::new(DEFAULT_RANGE.0, now));
+        self.peaks.fill(PeakHold::new(self.levels.floor_db, now));
     }
 
     fn update_peak_holds(&mut self, now: Instant) {
         let values = self.visible_values();
-        let (min, max) = DEFAULT_RANGE;
+        let DbRange { floor_db, ceiling_db } = self.levels;
         for (peak, value) in self.peaks.iter_mut().zip(values) {
-            peak.update(value.clamp(min, max), now);
+            peak.update(value.clamp(floor_db, ceiling_db), now);
         }
     }
 }
@@ -232,13 +308,6 @@ fn fallback_side(channel_index: usize, total_channels: usize) -> MeterSide {
     }
 }
 
-fn zone_thresholds(mode: MeterMode) -> [f32; 3] {
-    match mode {
-        MeterMode::LufsShortTerm | MeterMode::LufsMomentary => [-24.0, -18.0, -9.0],
-        MeterMode::RmsFast | MeterMode::RmsSlow | MeterMode::TruePeak => [-12.0, -6.0, -1.0],
-    }
-}
-
 fn meter_unit_label(mode: MeterMode) -> &'static str {
     match mode {
         MeterMode::LufsShortTerm | MeterMode::LufsMomentary => "LUFS",
@@ -247,28 +316,31 @@ fn meter_unit_label(mode: MeterMode) -> &'static str {
     }
 }
 
-fn is_danger_zone(mode: MeterMode, db: f32) -> bool {
-    db >= zone_thresholds(mode)[DANGER_THRESHOLD_INDEX]
-}
-
 fn visible_guide_labels(
     params: &LoudnessParams,
     bounds: Rectangle,
 ) -> [Option<(f32, Rectangle)>; GUIDE_LABEL_ORDER.len()] {
     let mut labels = [None; GUIDE_LABEL_ORDER.len()];
-    if bounds.height < GUIDE_LABEL_HEIGHT {
+    let size = Size::new(LEFT_PADDING, GUIDE_LABEL_HEIGHT);
+    if bounds.height < size.height || bounds.width < size.width {
         return labels;
     }
 
-    let max_top = bounds.y + bounds.height - GUIDE_LABEL_HEIGHT;
+    let max_x = bounds.x + bounds.width - size.width;
+    let max_y = bounds.y + bounds.height - size.height;
     let mut len = 0;
     for &i in &GUIDE_LABEL_ORDER {
         let db = params.guides[i];
-        let y = bounds.y + bounds.height * (1.0 - params.db_to_ratio(db));
-        let rect = Rectangle::new(
-            Point::new(bounds.x, (y - GUIDE_LABEL_HEIGHT * 0.5).clamp(bounds.y, max_top)),
-            Size::new(LEFT_PADDING, GUIDE_LABEL_HEIGHT),
-        );
+        if !(params.min_db..=params.max_db).contains(&db) {
+            continue;
+        }
+        let level = params.level_of(db);
+        let origin = if params.horizontal {
+            Point::new((level - size.width * 0.5).clamp(bounds.x, max_x), bounds.y)
+        } else {
+            Point::new(bounds.x, (level - size.height * 0.5).clamp(bounds.y, max_y))
+        };
+        let rect = Rectangle::new(origin, size);
 
         if !labels[..len]
             .iter()
@@ -283,51 +355,70 @@ fn visible_guide_labels(
     labels
 }
 
+fn value_label_rect(
+    params: &LoudnessParams,
+    bounds: Rectangle,
+    cross: f32,
+    level: f32,
+) -> Rectangle {
+    let size = VALUE_LABEL_SIZE;
+    let max_x = (bounds.x + bounds.width - size.width).max(bounds.x);
+    let max_y = (bounds.y + bounds.height - size.height).max(bounds.y);
+    let origin = if params.horizontal {
+        Point::new((level - size.width * 0.5).clamp(bounds.x, max_x), cross.min(max_y))
+    } else {
+        Point::new(cross, (level - size.height * 0.5).clamp(bounds.y, max_y))
+    };
+    Rectangle::new(origin, size)
+}
+
 crate::visuals::visualization_widget!(Loudness, LoudnessState, |this, renderer, theme, bounds| {
     let state = this.state.borrow();
     let params = state.visual_params(bounds);
 
-    renderer.draw_primitive(bounds, LoudnessPrimitive::new(params.clone()));
+    if software_rendering() {
+        params.for_each_shape(|shape| {
+            let (rect, color) = shape.rect();
+            fill_rect(renderer, rect, faded(Color::from(color), state.opacity));
+        });
+    } else {
+        draw_shader(renderer, theme, bounds, LoudnessPrimitive::new(params.clone()));
+    }
 
     let palette = theme.extended_palette();
-    let label_color = state.palette[PAL_GUIDE];
+    let label_color = faded(state.palette[PAL_GUIDE], state.opacity);
 
     if let Some((meter_x, bar_width, stride)) = params.meter_bounds() {
-        let y_of = |db| bounds.y + bounds.height * (1.0 - params.db_to_ratio(db));
-
         for (db, rect) in visible_guide_labels(&params, bounds).into_iter().flatten() {
             let label = if db == 0.0 { "0".to_owned() } else { format!("{db:+.0}") };
 
             let mut text = make_text(label, LABEL_FONT_SIZE, rect.size());
-            text.align_x = Horizontal::Right.into();
             text.align_y = Vertical::Center;
-            text::Renderer::fill_text(
-                renderer,
-                text,
-                Point::new(rect.x + rect.width - 4.0, rect.y + rect.height * 0.5),
-                label_color,
-                bounds,
-            );
+            let anchor = if params.horizontal {
+                text.align_x = Horizontal::Center.into();
+                rect.center()
+            } else {
+                text.align_x = Horizontal::Right.into();
+                Point::new(rect.x + rect.width - 4.0, rect.center_y())
+            };
+            text::Renderer::fill_text(renderer, text, anchor, label_color, bounds);
         }
 
         let value = state.get_value(state.settings.right_mode, 0);
         let unit = meter_unit_label(state.settings.right_mode);
-        let y = y_of(value);
-        let label = format!("{value:.1} {unit}");
-
-        let label_x = meter_x + stride + bar_width + 4.0;
-        let clamp_max = (bounds.y + bounds.height - 20.0).max(bounds.y);
-        let label_rect = Rectangle {
-            x: label_x,
-            y: (y - 10.0).clamp(bounds.y, clamp_max),
-            width: 68.0,
-            height: 20.0,
-        };
+        let label = state.numbers.localize(format!("{value:.1} {unit}"));
+        let gap = if params.horizontal { 1.0 } else { 4.0 };
+        let label_rect = value_label_rect(
+            &params,
+            bounds,
+            meter_x + stride + bar_width + gap,
+            params.level_of(value),
+        );
 
         fill_rect(
             renderer,
             label_rect,
-            state.palette[PAL_BACKGROUND],
+            faded(state.palette[PAL_BACKGROUND], state.opacity),
         );
 
         let mut text = make_text(
@@ -348,7 +439,7 @@ crate::visuals::visualization_widget!(Loudness, LoudnessState, |this, renderer,
                 label_rect.x + label_rect.width / 2.0,
                 label_rect.y + label_rect.height / 2.0,
             ),
-            palette.background.base.text,
+            faded(palette.background.base.text, state.opacity),
             bounds,
         );
     }
@@ -358,6 +449,8 @@ crate::visuals::visualization_widget!(Loudness, LoudnessState, |this, renderer,
 mod tests {
     use super::*;
 
+    const RANGE: DbRange = LevelScale::Loudness.default_range();
+
     fn visible_bar_values(state: &LoudnessState) -> Vec<Vec<f32>> {
         let params = state.visual_params(Rectangle::new(Point::ORIGIN, Size::new(200.0, 100.0)));
         params
@@ -391,26 +484,57 @@ mod tests {
         let snapshot = |true_peak_db, channel_count| LoudnessSnapshot {
             short_term_loudness: -9.0,
             momentary_loudness: -9.0,
-            rms_fast_db: [DEFAULT_RANGE.0; MAX_CHANNELS],
-            rms_slow_db: [DEFAULT_RANGE.0; MAX_CHANNELS],
+            rms_fast_db: [RANGE.floor_db; MAX_CHANNELS],
+            rms_slow_db: [RANGE.floor_db; MAX_CHANNELS],
             true_peak_db,
             channel_count,
         };
         let mut state = LoudnessState::new();
         state.set_modes(MeterMode::TruePeak, MeterMode::LufsShortTerm);
 
-        let mut mono = [DEFAULT_RANGE.0; MAX_CHANNELS];
+        let mut mono = [RANGE.floor_db; MAX_CHANNELS];
         mono[0] = -12.0;
         state.apply_snapshot(snapshot(mono, 1));
         assert_eq!(visible_bar_values(&state)[0], vec![-12.0, -12.0]);
 
-        let mut quad = [DEFAULT_RANGE.0; MAX_CHANNELS];
+        let mut quad = [RANGE.floor_db; MAX_CHANNELS];
         quad[2] = -6.0;
         quad[3] = -3.0;
         state.apply_snapshot(snapshot(quad, 4));
         assert_eq!(visible_bar_values(&state)[0], vec![-6.0, -3.0]);
     }
 
+    #[test]
+    fn horizontal_layout_runs_levels_left_to_right() {
+        let mut state = LoudnessState::new();
+        state.set_orientation(MeterOrientation::Horizontal);
+        let bounds = Rectangle::new(Point::new(10.0, 0.0), Size::new(400.0, 60.0));
+        let params = state.visual_params(bounds);
+
+        assert_eq!(params.level_of(RANGE.floor_db), 10.0);
+        assert_eq!(params.level_of(RANGE.ceiling_db), 410.0);
+        assert_eq!(params.point(5.0, 20.0), (20.0, 5.0));
+        let (start, width, stride) = params.meter_bounds().unwrap();
+        assert!(start >= TOP_PADDING && start + stride + width <= 60.0 - BOTTOM_PADDING);
+
+        for (_, rect) in visible_guide_labels(&params, bounds).into_iter().flatten() {
+            assert_eq!(rect.y, 0.0);
+            assert!(rect.x >= bounds.x && rect.x + rect.width <= 410.0);
+        }
+    }
+
+    #[test]
+    fn custom_zones_color_segments_and_peaks() {
+        let mut state = LoudnessState::new();
+        // Out-of-order edges are sorted rather than rejected.
+        state.set_zones([-14.0, -23.0, -18.0], [-20.0, -10.0, -3.0]);
+        let edges = |mode| state.meter_segments(mode).map(|(db, _)| db);
+        assert_eq!(edges(MeterMode::LufsShortTerm), [-23.0, -18.0, -14.0, RANGE.ceiling_db]);
+        assert_eq!(edges(MeterMode::TruePeak), [-20.0, -10.0, -3.0, RANGE.ceiling_db]);
+        assert!(state.is_danger_zone(MeterMode::TruePeak, -2.0));
+        assert!(!state.is_danger_zone(MeterMode::LufsMomentary, -15.0));
+    }
+
     #[test]
     fn peak_hold_waits_before_decaying() {
         let mut state = LoudnessState::new();
@@ -2,7 +2,7 @@
 // Copyright (C) 2026 Maika Namuo
 
 use crate::dsp::AudioBlock;
-use crate::util::audio::{self, Channel, DEFAULT_SAMPLE_RATE};
+use crate::util::audio::{self, Channel, DEFAULT_SAMPLE_RATE, MixdownGains};
 use realfft::{ComplexToReal, RealFftPlanner, RealToComplex};
 use rustfft::num_complex::Complex;
 use serde::{Deserialize, Serialize};
@@ -554,6 +554,39 @@ pub struct OscilloscopeSnapshot {
     pub slots: [usize; TRACE_COUNT],
     pub samples: Arc<[f32]>,
     pub samples_per_channel: usize,
+    // Input frames around the trigger, interleaved; only kept for single shot.
+    pub raw: Arc<[f32]>,
+    pub raw_channels: usize,
+    pub raw_trigger: usize,
+    pub sample_rate: f32,
+}
+
+impl OscilloscopeSnapshot {
+    pub fn peak(&self) -> f32 {
+        self.samples.iter().fold(0.0, |peak, s| peak.max(s.abs()))
+    }
+
+    /// The captured input frames, at the input rate, for WAV export.
+    pub fn interleaved(&self) -> &[f32] {
+        &self.raw
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("time_s");
+        for channel in 0..self.raw_channels {
+            out.push_str(&format!(",ch{}", channel + 1));
+        }
+        out.push('\n');
+        let step = 1.0 / self.sample_rate.max(f32::EPSILON);
+        for (i, frame) in self.raw.chunks_exact(self.raw_channels.max(1)).enumerate() {
+            out.push_str(&format!("{:.7}", (i as f32 - self.raw_trigger as f32) * step));
+            for sample in frame {
+                out.push_str(&format!(",{sample}"));
+            }
+            out.push('\n');
+        }
+        out
+    }
 }
 
 #[derive(Default)]
@@ -578,6 +611,8 @@ pub struct OscilloscopeProcessor {
     history_channels: Option<usize>,
     traces: [TraceState; TRACE_COUNT],
     source: TraceState,
+    keep_raw: bool,
+    mixdown: MixdownGains,
 }
 
 impl OscilloscopeProcessor {
@@ -590,6 +625,8 @@ impl OscilloscopeProcessor {
             history_channels: None,
             traces: std::array::from_fn(|_| TraceState::default()),
             source: TraceState::default(),
+            keep_raw: false,
+            mixdown: MixdownGains::default(),
         }
     }
 
@@ -597,6 +634,16 @@ impl OscilloscopeProcessor {
         self.config
     }
 
+    /// Per-channel weights the Mid traces mix with.
+    pub fn set_mixdown(&mut self, weights: &[f32], exclude_lfe: bool) {
+        self.mixdown.set_weights(weights, exclude_lfe);
+    }
+
+    /// Single shot keeps the input frames around the trigger for export.
+    pub fn set_keep_raw(&mut self, keep: bool) {
+        self.keep_raw = keep;
+    }
+
     #[cfg(test)]
     fn last_cycle_rate(&self) -> Option<f32> {
         self.source
@@ -668,6 +715,7 @@ impl OscilloscopeProcessor {
                 channel_count,
                 available,
                 channel,
+                &mut self.mixdown,
             );
         }
 
@@ -683,6 +731,7 @@ impl OscilloscopeProcessor {
             channel_count,
             available,
             trigger_source,
+            &mut self.mixdown,
         ) {
             capture(&self.source.buffer, &mut self.source.trigger)
         } else {
@@ -699,6 +748,18 @@ impl OscilloscopeProcessor {
 
         if captures.iter().all(Option::is_none) { return None; }
 
+        // One segment before the trigger and the segment itself, as far as history reaches.
+        let (raw, raw_trigger) = match captures.iter().flatten().next() {
+            Some(capture) if self.keep_raw => {
+                let span = capture.span.ceil() as usize;
+                let first = capture.start.saturating_sub(span);
+                let last = (capture.start + span + 1).min(available);
+                let frames = &data[first * channel_count..last * channel_count];
+                (Arc::from(frames), capture.start - first)
+            }
+            _ => (Arc::default(), 0),
+        };
+
         self.write_snapshot(&captures);
         Some(OscilloscopeSnapshot {
             epoch: self.epoch,
@@ -706,6 +767,10 @@ impl OscilloscopeProcessor {
             slots: self.snapshot.slots,
             samples: Arc::from(self.snapshot.samples.as_slice()),
             samples_per_channel: self.snapshot.samples_per_channel,
+            raw,
+            raw_channels: channel_count,
+            raw_trigger,
+            sample_rate,
         })
     }
 
@@ -723,12 +788,12 @@ impl OscilloscopeProcessor {
     fn write_snapshot(&mut self, captures: &[Option<Capture>; TRACE_COUNT]) {
         const TARGET: usize = 4096;
 
-        let target = captures
+        let span = captures
             .iter()
-            .filter_map(|capture| capture.map(|capture| capture.span.round().max(1.0) as usize + 1))
-            .max()
-            .unwrap_or(2)
-            .clamp(2, TARGET);
+            .flatten()
+            .map(|capture| capture.span)
+            .fold(1.0, f32::max);
+        let target = (span.round() as usize + 1).clamp(2, TARGET);
 
         self.snapshot.samples.clear();
         self.snapshot.channels = 0;
@@ -749,9 +814,9 @@ impl OscilloscopeProcessor {
 
     pub fn update_config(&mut self, config: OscilloscopeConfig) {
         if self.config != config {
-            let epoch = self.epoch.wrapping_add(1);
+            let (epoch, keep_raw) = (self.epoch.wrapping_add(1), self.keep_raw);
             *self = Self::new(config);
-            self.epoch = epoch;
+            (self.epoch, self.keep_raw) = (epoch, keep_raw);
         }
     }
 }
@@ -1077,6 +1142,7 @@ mod tests {
         }
 
         let mut projected = Vec::new();
+        let mut mixdown = MixdownGains::default();
         let same_stereo: Vec<f32> = mono.iter().flat_map(|&s| [s, s]).collect();
         assert!(audio::project_interleaved_channel_into(
             &mut projected,
@@ -1084,6 +1150,7 @@ mod tests {
             2,
             mono.len(),
             Channel::Mid,
+            &mut mixdown,
         ));
         let c = find_rising_zero_crossing(&projected, (0..=3840).rev()).unwrap();
         assert!(projected[c] > 0.0 && projected[c - 1] <= 0.0);
@@ -1096,6 +1163,7 @@ mod tests {
                 2,
                 mono.len(),
                 channel,
+                &mut mixdown,
             ));
             assert_eq!(
                 find_rising_zero_crossing(&projected, 0..=4799).is_some(),
@@ -1183,6 +1251,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn math_channels_fold_inverted_stereo() {
+        let mut processor = OscilloscopeProcessor::new(OscilloscopeConfig {
+            trigger_source: Channel::Difference,
+            channel_1: Channel::Sum,
+            channel_2: Channel::Difference,
+            ..stable_config()
+        });
+        let stereo: Vec<f32> = sine_samples(440.0, RATE, BLOCK * 20)
+            .iter()
+            .flat_map(|&s| [s, -s])
+            .collect();
+        let snap = stereo
+            .chunks_exact(BLOCK * 2)
+            .filter_map(|block| processor.process_block(&make_block(block, 2, RATE)))
+            .last()
+            .expect("snapshot");
+
+        let (sum, difference) = snap.samples.split_at(snap.samples_per_channel);
+        let peak = |samples: &[f32]| samples.iter().fold(0.0_f32, |acc, s| acc.max(s.abs()));
+        assert!(peak(sum) < 1e-3, "L+R of inverted stereo should cancel");
+        assert!((peak(difference) - 2.0).abs() < 0.05, "L-R peak {}", peak(difference));
+    }
+
     #[test]
     fn lock_acquisition_and_cycle_rate_transitions() {
         let mut processor = OscilloscopeProcessor::new(stable_config());
@@ -1234,4 +1326,45 @@ mod tests {
             |p| p.last_cycle_rate().is_some(),
         );
     }
+
+    #[test]
+    fn captures_export_the_input_frames_around_the_trigger() {
+        let mut processor = OscilloscopeProcessor::new(OscilloscopeConfig {
+            trigger_mode: TriggerMode::ZeroCrossing,
+            channel_2: Channel::Side,
+            ..OscilloscopeConfig::default()
+        });
+        processor.set_keep_raw(true);
+        let stereo: Vec<f32> = sine_samples(440.0, RATE, BLOCK * 4)
+            .into_iter()
+            .flat_map(|s| [s, 0.5 * s])
+            .collect();
+        let snap = (0..4)
+            .filter_map(|i| {
+                processor.process_block(&make_block(&stereo[i * BLOCK * 2..][..BLOCK * 2], 2, RATE))
+            })
+            .last()
+            .expect("snapshot");
+        assert_eq!((snap.raw_channels, snap.sample_rate), (2, RATE));
+        assert!(snap.raw_trigger > 0 && snap.raw_trigger < snap.raw.len() / 2 - 1);
+
+        // Unresampled input: every frame keeps the 2:1 ratio between the channels.
+        let frames = snap.interleaved();
+        assert!(frames.chunks_exact(2).all(|f| (f[0] - 2.0 * f[1]).abs() < 1e-6));
+        let trigger = &frames[snap.raw_trigger * 2..];
+        assert!(frames[snap.raw_trigger * 2 - 2] <= 0.0 && trigger[0] > 0.0);
+
+        let csv = snap.to_csv();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("time_s,ch1,ch2"));
+        let at_trigger = lines.nth(snap.raw_trigger).expect("trigger row");
+        assert!(at_trigger.starts_with("0.0000000,"), "{at_trigger}");
+        assert_eq!(lines.count(), frames.len() / 2 - snap.raw_trigger - 1);
+
+        processor.set_keep_raw(false);
+        let snap = processor
+            .process_block(&make_block(&stereo[..BLOCK * 2], 2, RATE))
+            .expect("snapshot");
+        assert!(snap.raw.is_empty());
+    }
 }
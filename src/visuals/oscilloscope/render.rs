@@ -24,6 +24,9 @@ pub struct OscilloscopeParams {
     pub colors: [[f32; 4]; TRACE_COUNT],
     pub stacked: bool,
     pub fill_alpha: f32,
+    pub opacity: f32,
+    /// Beat-pulse brightening, `0..=1`.
+    pub pulse: f32,
 }
 
 impl OscilloscopePrimitive {
@@ -4,21 +4,36 @@
 use super::processor::{OscilloscopeSnapshot, TRACE_COUNT};
 use super::render::{OscilloscopeParams, OscilloscopePrimitive};
 use crate::persistence::settings::OscilloscopeSettings;
+use crate::util::audio::db_to_gain;
 use crate::util::color::color_to_rgba;
 use crate::visuals::palettes;
-use iced::Color;
+use crate::visuals::render::common::{draw_shader, faded, fill_rect, make_text};
+use iced::advanced::{mouse, text};
+use iced::{Color, Point, Size};
 use std::sync::Arc;
 
 const OSCILLOSCOPE_PALETTE_SIZE: usize = TRACE_COUNT;
 const MAX_PERSISTENCE: f32 = 0.98;
 const FILL_ALPHA: f32 = 0.15;
+const CAPTURE_LABEL_SIZE: f32 = 11.0;
+const CAPTURE_LABEL_INSET: f32 = 6.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Capture {
+    Off,
+    Armed,
+    Frozen,
+}
 
 #[derive(Debug, Clone)]
 pub(in crate::visuals) struct OscilloscopeState {
     snapshot: OscilloscopeSnapshot,
     pub(in crate::visuals) colors: [Color; OSCILLOSCOPE_PALETTE_SIZE],
     settings: OscilloscopeSettings,
+    capture: Capture,
     key: u64,
+    opacity: f32,
+    pulse: f32,
 }
 
 impl OscilloscopeState {
@@ -27,11 +42,17 @@ impl OscilloscopeState {
             snapshot: OscilloscopeSnapshot::default(),
             colors: palettes::oscilloscope::COLORS,
             settings: OscilloscopeSettings::default(),
+            capture: Capture::Off,
             key: crate::visuals::next_key(),
+            opacity: 1.0,
+            pulse: 0.0,
         }
     }
 
     pub fn update_view_settings(&mut self, settings: &OscilloscopeSettings, reset_snapshot: bool) {
+        if settings.single_shot != self.settings.single_shot || reset_snapshot {
+            self.capture = if settings.single_shot { Capture::Armed } else { Capture::Off };
+        }
         self.settings = settings.clone();
         self.settings.persistence = if settings.persistence.is_finite() {
             settings.persistence.clamp(0.0, 1.0)
@@ -47,11 +68,55 @@ impl OscilloscopeState {
         self.settings.clone()
     }
 
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity;
+    }
+
+    pub fn set_pulse(&mut self, pulse: f32) {
+        self.pulse = pulse;
+    }
+
     pub fn set_palette(&mut self, palette: &[Color; OSCILLOSCOPE_PALETTE_SIZE]) {
         self.colors = *palette;
     }
 
+    /// Returns false unless a segment was frozen.
+    pub fn rearm(&mut self) -> bool {
+        let frozen = self.capture == Capture::Frozen;
+        if frozen {
+            self.capture = Capture::Armed;
+        }
+        frozen
+    }
+
+    /// The frozen single-shot segment, if one has been captured.
+    pub fn capture(&self) -> Option<OscilloscopeSnapshot> {
+        (self.capture == Capture::Frozen).then(|| self.snapshot.clone())
+    }
+
+    fn capture_label(&self) -> Option<&'static str> {
+        match self.capture {
+            Capture::Off => None,
+            Capture::Armed => Some("Armed"),
+            Capture::Frozen => Some("Captured \u{2014} click to re-arm"),
+        }
+    }
+
     pub fn apply_snapshot(&mut self, snapshot: OscilloscopeSnapshot) {
+        // Armed traces bypass persistence so the frozen segment is the raw capture.
+        match self.capture {
+            Capture::Frozen => return,
+            Capture::Armed => {
+                if snapshot.channels > 0
+                    && snapshot.peak() >= db_to_gain(self.settings.trigger_level_db)
+                {
+                    self.capture = Capture::Frozen;
+                }
+                self.snapshot = snapshot;
+                return;
+            }
+            Capture::Off => {}
+        }
         if !snapshot.samples.is_empty()
             && !self.snapshot.samples.is_empty()
             && snapshot.epoch == self.snapshot.epoch
@@ -95,8 +160,39 @@ impl OscilloscopeState {
             colors: self.colors.map(color_to_rgba),
             stacked: self.settings.stacked,
             fill_alpha: FILL_ALPHA,
+            opacity: self.opacity,
+            pulse: self.pulse,
         })
     }
 }
 
-crate::visuals::visualization_widget!(Oscilloscope, OscilloscopeState, OscilloscopePrimitive);
+crate::visuals::visualization_widget!(Oscilloscope, OscilloscopeState, |this, renderer, theme, bounds| {
+    let state = this.state.borrow();
+    match state.visual_params(bounds) {
+        Some(params) => draw_shader(renderer, theme, bounds, OscilloscopePrimitive::new(params)),
+        None => {
+            let background = theme.extended_palette().background.base.color;
+            fill_rect(renderer, bounds, faded(background, state.opacity));
+        }
+    }
+    if let Some(label) = state.capture_label() {
+        let size = Size::new(bounds.width, CAPTURE_LABEL_SIZE * 1.5);
+        let origin = Point::new(bounds.x + CAPTURE_LABEL_INSET, bounds.y + CAPTURE_LABEL_INSET);
+        let color = faded(theme.extended_palette().background.base.text, state.opacity);
+        text::Renderer::fill_text(
+            renderer,
+            make_text(label, CAPTURE_LABEL_SIZE, size),
+            origin,
+            color,
+            bounds,
+        );
+    }
+}, update |this, event, cursor, b, shell| {
+    if let iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = event
+        && cursor.is_over(b)
+        && this.state.borrow_mut().rearm()
+    {
+        shell.capture_event();
+        shell.request_redraw();
+    }
+});
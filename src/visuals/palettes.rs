@@ -15,6 +15,46 @@ const HEAT_RAMP: [Color; 5] = [
     Color::from_rgb8(0xFF, 0xFF, 0xFF),
 ];
 
+const MAGMA: [Color; 5] = [
+    Color::TRANSPARENT,
+    Color::from_rgb8(0x3B, 0x0F, 0x70),
+    Color::from_rgb8(0x8C, 0x29, 0x81),
+    Color::from_rgb8(0xDE, 0x49, 0x68),
+    Color::from_rgb8(0xFC, 0xFD, 0xBF),
+];
+
+const VIRIDIS: [Color; 5] = [
+    Color::TRANSPARENT,
+    Color::from_rgb8(0x3B, 0x52, 0x8B),
+    Color::from_rgb8(0x21, 0x91, 0x8C),
+    Color::from_rgb8(0x5E, 0xC9, 0x62),
+    Color::from_rgb8(0xFD, 0xE7, 0x25),
+];
+
+const ICE: [Color; 5] = [
+    Color::TRANSPARENT,
+    Color::from_rgb8(0x08, 0x30, 0x6B),
+    Color::from_rgb8(0x21, 0x71, 0xB5),
+    Color::from_rgb8(0x6B, 0xAE, 0xD6),
+    Color::from_rgb8(0xF7, 0xFB, 0xFF),
+];
+
+const GREY: [Color; 5] = [
+    Color::TRANSPARENT,
+    Color::from_rgb8(0x40, 0x40, 0x40),
+    Color::from_rgb8(0x80, 0x80, 0x80),
+    Color::from_rgb8(0xC0, 0xC0, 0xC0),
+    Color::from_rgb8(0xFF, 0xFF, 0xFF),
+];
+
+/// A named set of colours the palette editor can apply in one go.
+pub type Preset = (&'static str, &'static [Color]);
+
+// The spectrum repeats the loudest stop for its peak colour.
+const fn with_peak([floor, low, low_mid, mid, high]: [Color; 5]) -> [Color; 6] {
+    [floor, low, low_mid, mid, high, high]
+}
+
 #[derive(Debug, Clone)]
 pub struct Palette {
     colors: Vec<Color>,
@@ -81,27 +121,58 @@ impl Palette {
             VisualKind::Oscilloscope => p!(oscilloscope),
             VisualKind::Stereometer => p!(stereometer),
             VisualKind::Loudness => p!(loudness),
+            VisualKind::Tuner => p!(tuner),
         }
     }
 }
 
 pub mod spectrogram {
-    use super::{Color, HEAT_RAMP};
+    use super::{Color, GREY, HEAT_RAMP, ICE, MAGMA, Preset, VIRIDIS};
     pub const COLORS: [Color; 5] = HEAT_RAMP;
     pub const LABELS: &[&str] = &["Quietest", "->", "->", "->", "Loud"];
+    pub const PRESETS: &[Preset] = &[
+        ("Heat", &COLORS),
+        ("Magma", &MAGMA),
+        ("Viridis", &VIRIDIS),
+        ("Ice", &ICE),
+        ("Grey", &GREY),
+    ];
 
     pub const DEFAULT_POSITIONS: [f32; COLORS.len()] =
         [0.0, 0.402_523_83, 0.679_189_3, 0.869_322_26, 1.0];
 }
 
 pub mod spectrum {
-    use super::{Color, HEAT_RAMP};
-    pub const COLORS: [Color; 6] = {
-        let [floor, low, low_mid, mid, high] = HEAT_RAMP;
-        [floor, low, low_mid, mid, high, high]
-    };
+    use super::{Color, GREY, HEAT_RAMP, ICE, MAGMA, Preset, VIRIDIS, with_peak};
+    pub const COLORS: [Color; 6] = with_peak(HEAT_RAMP);
     pub const LABELS: &[&str] = &["Floor", "Low", "Low-Mid", "Mid", "High", "Peak"];
+    pub const PRESETS: &[Preset] = &[
+        ("Heat", &COLORS),
+        ("Magma", &with_peak(MAGMA)),
+        ("Viridis", &with_peak(VIRIDIS)),
+        ("Ice", &with_peak(ICE)),
+        ("Grey", &with_peak(GREY)),
+    ];
     pub const DEFAULT_POSITIONS: [f32; COLORS.len()] = [0.0, 0.2, 0.4, 0.6, 0.8, 1.0];
+    /// Overlaid channel traces, in `TraceColor` order.
+    pub const TRACES: [Color; 8] = [
+        Color::from_rgb8(0xF2, 0x5C, 0x54),
+        Color::from_rgb8(0xF7, 0x9D, 0x3C),
+        Color::from_rgb8(0xF2, 0xD5, 0x4B),
+        Color::from_rgb8(0x6B, 0xD1, 0x6B),
+        Color::from_rgb8(0x4F, 0xD3, 0xD9),
+        Color::from_rgb8(0x5B, 0x8D, 0xF5),
+        Color::from_rgb8(0xA7, 0x7B, 0xF0),
+        Color::from_rgb8(0xF0, 0x7B, 0xC4),
+    ];
+
+    /// Spectrum memory slots, kept apart from the channel overlay colors.
+    pub const MEMORY: [Color; 4] = [
+        Color::from_rgb8(0xE8, 0xE0, 0xA0),
+        Color::from_rgb8(0x9F, 0xE0, 0xC8),
+        Color::from_rgb8(0xC8, 0xB0, 0xF0),
+        Color::from_rgb8(0xF0, 0xB8, 0x98),
+    ];
 }
 
 pub mod waveform {
@@ -175,6 +246,20 @@ pub mod loudness {
     ];
     pub const DEFAULT_POSITIONS: [f32; COLORS.len()] = [0.0, 0.16, 0.32, 0.48, 0.64, 0.80, 1.0];
 }
+
+pub mod tuner {
+    use super::Color;
+    pub const COLORS: [Color; 6] = [
+        Color::from_rgb8(0x29, 0x29, 0x29),
+        Color::from_rgb8(0xE8, 0xEC, 0xEE),
+        Color::from_rgba8(0xB7, 0xC2, 0xC9, 160.0 / 255.0),
+        Color::from_rgb8(0x6B, 0xD1, 0x6B),
+        Color::from_rgb8(0xFF, 0xB7, 0x54),
+        Color::from_rgb8(0xF5, 0xED, 0xC4),
+    ];
+    pub const LABELS: &[&str] = &["Background", "Text", "Scale", "In tune", "Off", "Strobe"];
+    pub const DEFAULT_POSITIONS: [f32; COLORS.len()] = [0.0, 0.2, 0.4, 0.6, 0.8, 1.0];
+}
 pub mod background {
     use super::{BG_BASE, Color};
     pub const COLORS: [Color; 1] = [BG_BASE];
@@ -2,24 +2,35 @@
 // Copyright (C) 2026 Maika Namuo
 
 use super::{
+    budget::{self, Adjust, Load},
+    latency::{self, DisplayLatency},
     loudness,
-    options::{CorrelationMeterMode, StereometerMode, WaveformColorMode, WaveformHistoryMode},
+    options::{
+        CorrelationMeterMode, SnapshotRate, StereometerMode, WaveformColorMode, WaveformHistoryMode,
+    },
     oscilloscope, palettes,
+    snapshot::{Image, Offscreen},
     spectrogram::{self, processor::MAX_SPECTROGRAM_HISTORY_COLUMNS},
-    spectrum, stereometer, waveform,
+    spectrum, stereometer, tuner, waveform,
 };
-pub use crate::domain::visuals::VisualKind;
+pub use crate::domain::visuals::{MAX_INSTANCES, VisualId, VisualKind};
 use crate::{
-    dsp::AudioBlock,
+    domain::{automation::LoudnessAlert, routing::Mixdown},
+    dsp::{AudioBlock, band_mute::BandMute, calibration::CalibrationCurve, delay::FrameDelay},
     infra::pipewire::meter_tap::MeterFormat,
     persistence::settings::{
-        self as settings_cfg, ModuleSettings, PaletteSettings, ThemeFile, VisualSettings,
+        self as settings_cfg, DisplayOverride, LevelRanges, MemoryTrace, ModuleSettings,
+        PaletteSettings, ThemeFile, VisualSettings,
     },
-    util::audio::{Channel, DEFAULT_SAMPLE_RATE},
+    ui::theme,
+    util::audio::{Channel, DEFAULT_SAMPLE_RATE, LevelScale, NumberFormat, musical::Tuning},
     util::color::{sanitize_stop_positions, sanitize_stop_spreads},
 };
-use iced::{Color, Element, Length, widget::container};
-use std::{cell::RefCell, rc::Rc};
+use iced::widget::{column, container, text};
+use iced::{Color, Element, Length, Rectangle, Size};
+use std::time::{Duration, Instant};
+use std::{cell::RefCell, rc::Rc, sync::Arc};
+use tracing::info;
 
 type Shared<T> = Rc<RefCell<T>>;
 
@@ -50,23 +61,63 @@ macro_rules! visuals {
     (@export_palette $state:expr, $default:expr) => {
         PaletteSettings::if_differs_from($state, $default)
     };
+    (@export_palette $state:expr, $default:expr, $blend:expr) => {
+        PaletteSettings::with_blend(PaletteSettings::if_differs_from($state, $default), $blend)
+    };
     (@apply_config $proc:ident, $settings:ident) => {{
         let mut config = $proc.config();
         $settings.apply_to(&mut config);
         $proc.update_config(config)
     }};
+    (@latency $proc:expr) => { 0 };
+    (@latency $proc:expr, |$p:ident| $body:expr) => {{ let $p = $proc; $body }};
+    (@paused $state:expr) => { false };
+    (@paused $state:expr, |$s:ident| $body:expr) => {{ let $s = $state; $body }};
+    (@quality_cut $proc:expr, $cut:expr) => { $cut == 0 };
+    (@quality_cut $proc:expr, $cut:expr, |$p:ident, $c:ident| $body:expr) => {{
+        let ($p, $c) = ($proc, $cut);
+        $body
+    }};
+    (@calibration $proc:expr, $curve:expr) => { drop($curve) };
+    (@calibration $proc:expr, $curve:expr, |$p:ident, $c:ident| $body:expr) => {{
+        let ($p, $c) = ($proc, $curve);
+        $body
+    }};
+    (@mixdown $proc:expr, $mixdown:expr) => {{ let _ = $mixdown; }};
+    (@mixdown $proc:expr, $mixdown:expr, |$p:ident, $m:ident| $body:expr) => {{
+        let ($p, $m) = ($proc, $mixdown);
+        $body
+    }};
     (@apply_palette $st:expr, $settings:ident, $default:expr) => {
         $st.set_palette(&resolve_palette($settings.palette.as_ref(), $default))
     };
-    ($($variant:ident($default_width_basis:expr, $min_w:expr) =>
+    (@apply_blend $st:expr, $settings:ident) => {
+        $st.set_palette_blend(PaletteSettings::blend_or_default($settings.palette.as_ref()))
+    };
+    (@max_width) => { f32::INFINITY };
+    (@max_width $max_w:expr) => { $max_w };
+    ($($variant:ident(
+        $default_width_basis:expr, $min_w:expr $(, $max_w:expr)?;
+        $default_height_basis:expr, $min_h:expr
+    ) =>
        $module:ident :: $processor:ident, $config:ident, $state:ident;
        $settings_ty:ty;
+       $(paused($ps:ident) $paused_body:expr;)?
        $(pre_ingest($pip:ident, $pis:ident) $pre_ingest_body:expr;)?
+       $(latency($lp:ident) $latency_body:expr;)?
+       $(quality_cut($qp:ident, $qc:ident) $quality_body:expr;)?
+       $(calibration($cp:ident, $cc:ident) $calibration_body:expr;)?
+       $(mixdown($mp:ident, $mm:ident) $mixdown_body:expr;)?
        apply($ap:ident, $as:ident, $aset:ident) $apply_body:expr;
        export($ep:ident, $es:ident) $export_body:expr;
     )*) => {
+        /// A visual's state, and whether it is still waiting for its first audio.
         #[derive(Clone)]
-        pub(crate) struct VisualContent(VisualContentInner);
+        pub(crate) struct VisualContent {
+            inner: VisualContentInner,
+            waiting: bool,
+            latency: Rc<DisplayLatency>,
+        }
 
         #[derive(Clone)]
         enum VisualContentInner {
@@ -75,31 +126,51 @@ macro_rules! visuals {
 
         impl VisualContent {
             pub(crate) fn render<M: 'static>(&self) -> Element<'_, M> {
-                container(match &self.0 {
+                if self.waiting {
+                    return waiting_placeholder();
+                }
+                let visual = match &self.inner {
                     $(VisualContentInner::$variant(s) => $module::widget(s)),*
-                })
+                };
+                container(latency::probe(visual, &self.latency))
                 .width(Length::Fill)
                 .height(Length::Fill)
                 .center(Length::Fill)
                 .into()
             }
+
+            /// Draws the state offscreen, even before any audio has arrived.
+            pub(crate) fn snapshot(&self, offscreen: &mut Offscreen, size: Size<u32>) -> Image {
+                match &self.inner {
+                    $(VisualContentInner::$variant(s) => offscreen.render_state(&**s, size)),*
+                }
+            }
         }
 
         const DESCRIPTORS: &[Descriptor] = &[$(Descriptor {
             kind: VisualKind::$variant,
             default_width_basis: $default_width_basis,
             min_width: $min_w,
+            max_width: visuals!(@max_width $($max_w)?),
+            default_height_basis: $default_height_basis,
+            min_height: $min_h,
             build: || Box::new(Visual {
                 processor: $module::$processor::new($module::$config {
                     sample_rate: DEFAULT_SAMPLE_RATE,
                     ..Default::default()
                 }),
                 state: Rc::new(RefCell::new($module::$state::new())),
+                fed: false,
+                latency: Rc::default(),
             }),
         }),*];
 
         $(impl VisualModule for Visual<$module::$processor, Shared<$module::$state>> {
-            fn ingest(&mut self, samples: &[f32], fmt: MeterFormat) {
+            fn ingest(&mut self, samples: &[f32], fmt: MeterFormat, captured: Option<Instant>) {
+                if visuals!(@paused &self.state $(, |$ps| $paused_body)?) {
+                    return;
+                }
+                self.fed |= !samples.is_empty();
                 $({
                     let ($pip, $pis) = (&mut self.processor, &self.state);
                     $pre_ingest_body
@@ -110,11 +181,39 @@ macro_rules! visuals {
                     fmt.sample_rate,
                 )) {
                     self.state.borrow_mut().apply_snapshot(snap);
+                    if let Some(at) = captured {
+                        self.latency.note_captured(at);
+                    }
                 }
             }
 
+            fn display_latency_ms(&self) -> Option<f32> {
+                self.latency.ms()
+            }
+
+            fn latency_frames(&self) -> usize {
+                visuals!(@latency &self.processor $(, |$lp| $latency_body)?)
+            }
+
+            fn set_quality_cut(&mut self, cut: u8) -> bool {
+                visuals!(@quality_cut &mut self.processor, cut $(, |$qp, $qc| $quality_body)?)
+            }
+
+            fn set_calibration(&mut self, curve: Option<Arc<CalibrationCurve>>) {
+                visuals!(@calibration &mut self.processor, curve
+                    $(, |$cp, $cc| $calibration_body)?)
+            }
+
+            fn set_mixdown(&mut self, mixdown: &Mixdown) {
+                visuals!(@mixdown &mut self.processor, mixdown $(, |$mp, $mm| $mixdown_body)?)
+            }
+
             fn content(&self) -> VisualContent {
-                VisualContent(VisualContentInner::$variant(self.state.clone()))
+                VisualContent {
+                    inner: VisualContentInner::$variant(self.state.clone()),
+                    waiting: !self.fed,
+                    latency: self.latency.clone(),
+                }
             }
 
             fn apply(&mut self, module_cfg: &ModuleSettings) {
@@ -132,25 +231,30 @@ macro_rules! visuals {
 }
 
 visuals! {
-    Loudness(140.0, 80.0) =>
+    Loudness(140.0, 80.0, 360.0; 120.0, 60.0) =>
         loudness::LoudnessProcessor, LoudnessConfig, LoudnessState;
         settings_cfg::LoudnessSettings;
         apply(_p, s, set) { let mut st = s.borrow_mut();
             st.set_modes(set.left_mode, set.right_mode);
+            st.set_orientation(set.orientation);
+            st.set_alert(set.alert);
+            st.set_zones(set.loudness_zones, set.level_zones);
             visuals!(@apply_palette st, set, &palettes::loudness::COLORS); };
         export(_p, s) { let st = s.borrow(); let mut out = st.export_settings();
             out.palette = visuals!(@export_palette &st.palette, &palettes::loudness::COLORS); out };
 
-    Oscilloscope(150.0, 100.0) =>
+    Oscilloscope(150.0, 100.0; 160.0, 80.0) =>
         oscilloscope::OscilloscopeProcessor, OscilloscopeConfig, OscilloscopeState;
         settings_cfg::OscilloscopeSettings;
-        apply(p, s, set) { visuals!(@apply_config p, set); let reset = [set.channel_1, set.channel_2] == [Channel::None; 2];
+        mixdown(p, m) p.set_mixdown(&m.weights, m.exclude_lfe);
+        apply(p, s, set) { visuals!(@apply_config p, set); p.set_keep_raw(set.single_shot);
+            let reset = [set.channel_1, set.channel_2] == [Channel::None; 2];
             let mut st = s.borrow_mut(); st.update_view_settings(&set, reset);
             visuals!(@apply_palette st, set, &palettes::oscilloscope::COLORS); };
         export(p, s) { let st = s.borrow(); let mut out = st.export_settings(); out.sync_from_config(&p.config());
             out.palette = visuals!(@export_palette &st.colors, &palettes::oscilloscope::COLORS); out };
 
-    Waveform(220.0, 220.0) =>
+    Waveform(220.0, 220.0; 160.0, 80.0) =>
         waveform::WaveformProcessor, WaveformConfig, WaveformState;
         settings_cfg::WaveformSettings;
         pre_ingest(p, s) {
@@ -161,6 +265,7 @@ visuals! {
                 p.update_config(cfg);
             }
         };
+        mixdown(p, m) p.set_mixdown(&m.weights, m.exclude_lfe);
         apply(p, s, set) {
             let mut cfg = p.config();
             set.apply_to(&mut cfg);
@@ -168,13 +273,16 @@ visuals! {
             cfg.analyze_bands = set.color_mode == WaveformColorMode::Frequency || cfg.track_history;
             p.update_config(cfg);
             let mut st = s.borrow_mut(); st.update_view_settings(&set);
-            visuals!(@apply_palette st, set, &palettes::waveform::COLORS); };
+            visuals!(@apply_palette st, set, &palettes::waveform::COLORS);
+            visuals!(@apply_blend st, set); };
         export(p, s) { let st = s.borrow(); let mut out = st.export_settings(); out.sync_from_config(&p.config());
-            out.palette = visuals!(@export_palette &st.style.palette, &palettes::waveform::COLORS); out };
+            out.palette = visuals!(@export_palette &st.style.palette, &palettes::waveform::COLORS,
+                st.style.palette_blend); out };
 
-    Spectrogram(320.0, 300.0) =>
+    Spectrogram(320.0, 300.0; 240.0, 120.0) =>
         spectrogram::SpectrogramProcessor, SpectrogramConfig, SpectrogramState;
         settings_cfg::SpectrogramSettings;
+        paused(s) s.borrow().hover_paused();
         pre_ingest(p, s) {
             let vw = { s.borrow().view_width };
             if vw > 0 {
@@ -185,9 +293,21 @@ visuals! {
                     p.update_config(cfg);
                 }
             }
+            let mut st = s.borrow_mut();
+            p.set_keep_recent(st.refine_enabled());
+            if let Some(columns) = st.refine_request(Instant::now())
+                && let Some(job) = p.refine_job(columns)
+            {
+                st.start_refine(job);
+            }
         };
+        latency(p) p.latency_frames();
+        quality_cut(p, cut) p.set_quality_cut(cut);
+        calibration(p, curve) p.set_calibration(curve);
+        mixdown(p, m) p.set_mixdown(&m.weights, m.exclude_lfe);
         apply(p, s, set) { visuals!(@apply_config p, set); let mut st = s.borrow_mut();
             visuals!(@apply_palette st, set, &palettes::spectrogram::COLORS);
+            visuals!(@apply_blend st, set);
             st.set_stop_positions(&sanitize_stop_positions(
                 set.palette.as_ref().and_then(|p| p.stop_positions.as_deref()),
                 &palettes::spectrogram::DEFAULT_POSITIONS));
@@ -196,18 +316,26 @@ visuals! {
                 palettes::spectrogram::COLORS.len()));
             st.update_view_settings(&set); };
         export(p, s) { let st = s.borrow(); let mut out = st.export_settings(); out.sync_from_config(&p.config());
-            out.palette = PaletteSettings::from_state(&st.palette, &palettes::spectrogram::COLORS, &st.stop_positions, &palettes::spectrogram::DEFAULT_POSITIONS, &st.stop_spreads); out };
+            out.palette = PaletteSettings::from_state(&st.palette, &palettes::spectrogram::COLORS,
+                &st.stop_positions, &palettes::spectrogram::DEFAULT_POSITIONS, &st.stop_spreads,
+                st.palette_blend); out };
 
-    Spectrum(400.0, 400.0) =>
+    Spectrum(400.0, 400.0; 240.0, 120.0) =>
         spectrum::SpectrumProcessor, SpectrumConfig, SpectrumState;
         settings_cfg::SpectrumSettings;
+        paused(s) s.borrow().hover_paused();
+        latency(p) p.latency_frames();
+        calibration(p, curve) p.set_calibration(curve);
+        mixdown(p, m) p.set_mixdown(&m.weights, m.exclude_lfe);
         apply(p, s, set) { visuals!(@apply_config p, set); let cfg = p.config(); let mut st = s.borrow_mut();
             st.update_view_settings(&set, cfg.floor_db);
-            visuals!(@apply_palette st, set, &palettes::spectrum::COLORS); };
+            visuals!(@apply_palette st, set, &palettes::spectrum::COLORS);
+            visuals!(@apply_blend st, set); };
         export(p, s) { let st = s.borrow(); let mut out = st.export_settings(); out.sync_from_config(&p.config());
-            out.palette = visuals!(@export_palette &st.spectrum_palette, &palettes::spectrum::COLORS); out };
+            out.palette = visuals!(@export_palette &st.spectrum_palette, &palettes::spectrum::COLORS,
+                st.palette_blend()); out };
 
-    Stereometer(150.0, 100.0) =>
+    Stereometer(150.0, 100.0; 220.0, 120.0) =>
         stereometer::StereometerProcessor, StereometerConfig, StereometerState;
         settings_cfg::StereometerSettings;
         apply(p, s, set) {
@@ -223,15 +351,57 @@ visuals! {
         };
         export(p, s) { let st = s.borrow(); let mut out = st.export_settings(); out.sync_from_config(&p.config());
             out.palette = visuals!(@export_palette &st.palette, &palettes::stereometer::COLORS); out };
+
+    Tuner(220.0, 160.0; 160.0, 120.0) =>
+        tuner::TunerProcessor, TunerConfig, TunerState;
+        settings_cfg::TunerSettings;
+        apply(p, s, set) { visuals!(@apply_config p, set); let mut st = s.borrow_mut();
+            st.update_view_settings(&set);
+            visuals!(@apply_palette st, set, &palettes::tuner::COLORS); };
+        export(p, s) { let st = s.borrow(); let mut out = st.export_settings(); out.sync_from_config(&p.config());
+            out.palette = visuals!(@export_palette &st.palette, &palettes::tuner::COLORS); out };
 }
 
 struct Visual<P, S> {
     processor: P,
     state: S,
+    // Until the first samples arrive the visual shows a placeholder.
+    fed: bool,
+    latency: Rc<DisplayLatency>,
+}
+
+fn waiting_placeholder<'a, M: 'a>() -> Element<'a, M> {
+    let hint = "Play something, or check the capture mode in config (ctrl+shift+h).";
+    container(
+        column![
+            text("waiting for audio\u{2026}").size(theme::BODY_TEXT_SIZE),
+            text(hint)
+                .size(theme::BODY_TEXT_SIZE - 1.0)
+                .style(theme::weak_text_style),
+        ]
+        .spacing(4)
+        .align_x(iced::alignment::Horizontal::Center),
+    )
+    .padding(8)
+    .center(Length::Fill)
+    .into()
 }
 
 pub trait VisualModule {
-    fn ingest(&mut self, samples: &[f32], format: MeterFormat);
+    /// `captured` is when the newest of `samples` was recorded, if live.
+    fn ingest(&mut self, samples: &[f32], format: MeterFormat, captured: Option<Instant>);
+    /// Smoothed time from capture to the first frame drawing that audio.
+    fn display_latency_ms(&self) -> Option<f32>;
+    /// Frames between a sound arriving and it showing up in the visual.
+    fn latency_frames(&self) -> usize;
+    /// Runs `cut` steps below the configured quality; `false` if it has no
+    /// such step. Never reflected in exported settings.
+    fn set_quality_cut(&mut self, cut: u8) -> bool;
+    /// The mic's response to divide out; ignored by visuals that do not
+    /// measure level per frequency.
+    fn set_calibration(&mut self, curve: Option<Arc<CalibrationCurve>>);
+    /// How frames collapse to the Mid channel, for visuals that read it.
+    fn set_mixdown(&mut self, mixdown: &Mixdown);
     fn content(&self) -> VisualContent;
     fn apply(&mut self, settings: &ModuleSettings);
     fn export(&self) -> ModuleSettings;
@@ -241,57 +411,132 @@ struct Descriptor {
     kind: VisualKind,
     default_width_basis: f32,
     min_width: f32,
+    max_width: f32,
+    default_height_basis: f32,
+    min_height: f32,
     build: fn() -> Box<dyn VisualModule>,
 }
 
+/// A visual with default settings, outside any manager; for offscreen renders.
+pub(crate) fn standalone(kind: VisualKind) -> Option<Box<dyn VisualModule>> {
+    let descriptor = DESCRIPTORS
+        .iter()
+        .find(|descriptor| descriptor.kind == kind)?;
+    let mut module = (descriptor.build)();
+    module.apply(&ModuleSettings::default());
+    Some(module)
+}
+
 struct Entry {
+    id: VisualId,
     descriptor: &'static Descriptor,
     enabled: bool,
     module: Box<dyn VisualModule>,
+    // Holds back faster visuals when latency alignment is on.
+    delay: FrameDelay,
+    // Ingest time this budget window, and steps shed to stay within it.
+    spent: Duration,
+    cut: u8,
+    // Share of real time spent ingesting over the last budget window.
+    load: f64,
+    // Left unfed, so the visual keeps showing what led up to an event.
+    frozen: bool,
 }
 impl Entry {
+    fn new(descriptor: &'static Descriptor, instance: u8) -> Self {
+        Self {
+            id: VisualId {
+                kind: descriptor.kind,
+                instance,
+            },
+            descriptor,
+            enabled: false,
+            module: (descriptor.build)(),
+            delay: FrameDelay::default(),
+            spent: Duration::ZERO,
+            cut: 0,
+            load: 0.0,
+            frozen: false,
+        }
+    }
+
     fn apply_settings(&mut self, settings: &ModuleSettings) {
         if let Some(enabled) = settings.enabled {
             self.enabled = enabled;
         }
         self.module.apply(settings);
+        // New settings start at full quality; the budget cuts again if needed.
+        if self.cut > 0 && self.module.set_quality_cut(0) {
+            self.cut = 0;
+        }
     }
 }
 
 #[derive(Clone)]
 pub(crate) struct VisualSlotSnapshot {
-    pub kind: VisualKind,
+    pub id: VisualId,
     pub enabled: bool,
     pub default_width_basis: f32,
     pub min_width: f32,
+    pub max_width: f32,
+    pub default_height_basis: f32,
+    pub min_height: f32,
     pub content: VisualContent,
 }
 
 pub(crate) struct VisualManager {
     entries: Vec<Entry>,
+    align_latency: bool,
+    budget_window: f64,
+    snapshot_rate: SnapshotRate,
+    // Capture batches waiting for the next snapshot tick.
+    held: Vec<f32>,
+    held_format: Option<MeterFormat>,
+    held_captured: Option<Instant>,
+    // Bands a spectrum marks for muting, notched out ahead of every visual.
+    band_mute: BandMute,
+    calibration: Option<Arc<CalibrationCurve>>,
+    mixdown: Mixdown,
+    level_ranges: LevelRanges,
+    opacity: f32,
+    pulse: f32,
+    tuning: Tuning,
+    numbers: NumberFormat,
+    // Idle pause feeds only the loudness meter, whose levels sessions, track
+    // segmentation and automation keep reading.
+    idle: bool,
 }
 impl Default for VisualManager {
     fn default() -> Self {
         Self {
             entries: DESCRIPTORS
                 .iter()
-                .map(|descriptor| Entry {
-                    descriptor,
-                    enabled: false,
-                    module: (descriptor.build)(),
-                })
+                .map(|descriptor| Entry::new(descriptor, 0))
                 .collect(),
+            align_latency: false,
+            budget_window: 0.0,
+            snapshot_rate: SnapshotRate::default(),
+            held: Vec::new(),
+            held_format: None,
+            held_captured: None,
+            band_mute: BandMute::default(),
+            calibration: None,
+            mixdown: Mixdown::default(),
+            level_ranges: LevelRanges::default(),
+            opacity: 1.0,
+            pulse: 0.0,
+            tuning: Tuning::default(),
+            numbers: NumberFormat::default(),
+            idle: false,
         }
     }
 }
 impl VisualManager {
-    fn position(&self, kind: VisualKind) -> Option<usize> {
-        self.entries
-            .iter()
-            .position(|entry| entry.descriptor.kind == kind)
+    fn position(&self, id: VisualId) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.id == id)
     }
-    pub fn move_to(&mut self, kind: VisualKind, target: usize) {
-        let Some(current) = self.position(kind) else {
+    pub fn move_to(&mut self, id: VisualId, target: usize) {
+        let Some(current) = self.position(id) else {
             return;
         };
         let target = target.min(self.entries.len().saturating_sub(1));
@@ -304,79 +549,528 @@ impl VisualManager {
         self.entries
             .iter()
             .map(|entry| VisualSlotSnapshot {
-                kind: entry.descriptor.kind,
+                id: entry.id,
                 enabled: entry.enabled,
                 default_width_basis: entry.descriptor.default_width_basis,
                 min_width: entry.descriptor.min_width,
+                max_width: entry.descriptor.max_width,
+                default_height_basis: entry.descriptor.default_height_basis,
+                min_height: entry.descriptor.min_height,
                 content: entry.module.content(),
             })
             .collect()
     }
-    pub fn order(&self) -> Vec<VisualKind> {
-        self.entries
-            .iter()
-            .map(|entry| entry.descriptor.kind)
-            .collect()
+    pub fn order(&self) -> Vec<VisualId> {
+        self.entries.iter().map(|entry| entry.id).collect()
     }
-    pub fn module_settings(&self, kind: VisualKind) -> Option<ModuleSettings> {
-        let entry = &self.entries[self.position(kind)?];
+    pub fn module_settings(&self, id: VisualId) -> Option<ModuleSettings> {
+        let entry = &self.entries[self.position(id)?];
         let mut settings = entry.module.export();
         settings.enabled.get_or_insert(entry.enabled);
         Some(settings)
     }
-    pub fn theme_palettes(&self) -> impl Iterator<Item = (VisualKind, PaletteSettings)> + '_ {
+    pub fn theme_palettes(&self) -> impl Iterator<Item = (VisualId, PaletteSettings)> + '_ {
         self.entries.iter().filter_map(|entry| {
-            entry
-                .module
-                .export()
-                .extract_palette()
-                .map(|palette| (entry.descriptor.kind, palette))
+            let palette = entry.module.export().extract_palette()?;
+            Some((entry.id, palette))
         })
     }
-    pub fn apply_module_settings(&mut self, kind: VisualKind, settings: &ModuleSettings) {
-        let index = self
-            .position(kind)
-            .expect("visual kind missing from registry");
+    pub fn apply_module_settings(&mut self, id: VisualId, settings: &ModuleSettings) {
+        let Some(index) = self.position(id) else {
+            return;
+        };
         self.entries[index].apply_settings(settings);
+        self.sync_band_mute();
     }
-    pub fn set_enabled(&mut self, kind: VisualKind, enabled: bool) {
-        if let Some(index) = self.position(kind) {
+    pub fn is_enabled(&self, id: VisualId) -> bool {
+        self.position(id)
+            .is_some_and(|index| self.entries[index].enabled)
+    }
+    pub fn set_enabled(&mut self, id: VisualId, enabled: bool) {
+        if let Some(index) = self.position(id) {
             self.entries[index].enabled = enabled;
         }
+        self.sync_band_mute();
+    }
+    /// Adds the next free instance of `kind` right after the last one, starting
+    /// from a copy of the built-in instance's settings.
+    pub fn add_instance(&mut self, kind: VisualKind) -> Option<VisualId> {
+        let instance = (1..MAX_INSTANCES)
+            .find(|&instance| self.position(VisualId { kind, instance }).is_none())?;
+        let settings = self.module_settings(VisualId::primary(kind));
+        let after = self
+            .entries
+            .iter()
+            .rposition(|entry| entry.id.kind == kind)
+            .map_or(self.entries.len(), |index| index + 1);
+        let entry = self.insert_instance(kind, instance, after)?;
+        if let Some(settings) = settings {
+            entry.apply_settings(&settings);
+        }
+        entry.enabled = true;
+        let id = entry.id;
+        let index = self.position(id)?;
+        self.push_shared(&self.entries[index]);
+        self.sync_band_mute();
+        Some(id)
+    }
+    /// The built-in instance of each kind can only be disabled.
+    pub fn remove_instance(&mut self, id: VisualId) -> bool {
+        match self.position(id) {
+            Some(index) if !id.is_primary() => {
+                self.entries.remove(index);
+                self.sync_band_mute();
+                true
+            }
+            _ => false,
+        }
+    }
+    fn sync_band_mute(&mut self) {
+        let bands = self
+            .entries
+            .iter()
+            .filter(|entry| entry.enabled)
+            .filter_map(|entry| match entry.module.content().inner {
+                VisualContentInner::Spectrum(state) => Some(state.borrow().muted_bands()),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        self.band_mute.set_bands(bands);
+    }
+    fn insert_instance(&mut self, kind: VisualKind, instance: u8, at: usize) -> Option<&mut Entry> {
+        let descriptor = DESCRIPTORS
+            .iter()
+            .find(|descriptor| descriptor.kind == kind)?;
+        let at = at.min(self.entries.len());
+        let mut entry = Entry::new(descriptor, instance);
+        entry.module.set_calibration(self.calibration.clone());
+        entry.module.set_mixdown(&self.mixdown);
+        self.entries.insert(at, entry);
+        Some(&mut self.entries[at])
     }
     pub fn apply_visual_settings(&mut self, settings: &VisualSettings) {
+        self.entries
+            .retain(|entry| entry.id.is_primary() || settings.modules.contains_key(&entry.id));
+        for id in settings.modules.keys().filter(|id| !id.is_primary()) {
+            if self.position(*id).is_none() {
+                self.insert_instance(id.kind, id.instance, usize::MAX);
+            }
+        }
         let default_settings = ModuleSettings::default();
         for entry in &mut self.entries {
-            entry.apply_settings(
-                settings
-                    .modules
-                    .get(&entry.descriptor.kind)
-                    .unwrap_or(&default_settings),
-            );
+            entry.apply_settings(settings.modules.get(&entry.id).unwrap_or(&default_settings));
         }
         self.reorder(&settings.order);
+        self.set_align_latency(settings.align_latency);
+        self.set_snapshot_rate(settings.snapshot_rate);
+        self.sync_band_mute();
+    }
+    pub fn set_align_latency(&mut self, align: bool) {
+        if self.align_latency && !align {
+            // Drop the held-back audio instead of releasing it late.
+            for entry in &mut self.entries {
+                entry.delay = FrameDelay::default();
+            }
+        }
+        self.align_latency = align;
+    }
+    /// Every visual of a kind shares its kind's range.
+    pub fn set_level_ranges(&mut self, ranges: &LevelRanges) {
+        for &scale in LevelScale::ALL {
+            *self.level_ranges.get_mut(scale) = ranges.get(scale).sanitized(scale.default_range());
+        }
+        for entry in &self.entries {
+            self.push_shared(entry);
+        }
+    }
+    /// Fades everything the visuals draw, clamped to `0..=1`.
+    pub fn set_content_opacity(&mut self, opacity: f32) {
+        self.opacity = if opacity.is_finite() {
+            opacity.clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        for entry in &self.entries {
+            self.push_shared(entry);
+        }
+    }
+    /// Divides a mic's response out of every spectrum and spectrogram;
+    /// `None` turns it off.
+    pub fn set_calibration(&mut self, curve: Option<CalibrationCurve>) {
+        if self.calibration.as_deref() == curve.as_ref() {
+            return;
+        }
+        self.calibration = curve.map(Arc::new);
+        for entry in &mut self.entries {
+            entry.module.set_calibration(self.calibration.clone());
+        }
+    }
+    /// Every Mid channel mixes the capture with the same weights.
+    pub fn set_mixdown(&mut self, mixdown: &Mixdown) {
+        if self.mixdown == *mixdown {
+            return;
+        }
+        self.mixdown.clone_from(mixdown);
+        for entry in &mut self.entries {
+            entry.module.set_mixdown(mixdown);
+        }
+    }
+    pub fn mixdown(&self) -> &Mixdown {
+        &self.mixdown
+    }
+    /// Brightens everything the visual pipelines draw, clamped to `0..=1`.
+    pub fn set_beat_pulse(&mut self, pulse: f32) {
+        let pulse = if pulse.is_finite() {
+            pulse.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        if self.pulse == pulse {
+            return;
+        }
+        self.pulse = pulse;
+        for entry in &self.entries {
+            self.push_pulse(entry);
+        }
+    }
+    /// Every note name and cents reading follows one tuning.
+    pub fn set_tuning(&mut self, tuning: Tuning) {
+        self.tuning = tuning;
+        for entry in &self.entries {
+            self.push_shared(entry);
+        }
+    }
+    /// Every readout writes its decimal points the same way.
+    pub fn set_number_format(&mut self, numbers: NumberFormat) {
+        self.numbers = numbers;
+        for entry in &self.entries {
+            self.push_shared(entry);
+        }
+    }
+    pub fn number_format(&self) -> NumberFormat {
+        self.numbers
+    }
+    // Hands an entry what all visuals share: the window opacity, the beat
+    // pulse, its kind's range, the tuning and the number format.
+    fn push_shared(&self, entry: &Entry) {
+        let (ranges, opacity, tuning, numbers) =
+            (&self.level_ranges, self.opacity, self.tuning, self.numbers);
+        match entry.module.content().inner {
+            VisualContentInner::Spectrum(state) => {
+                let mut state = state.borrow_mut();
+                state.set_level_range(ranges.spectrum);
+                state.set_opacity(opacity);
+                state.set_tuning(tuning);
+                state.set_number_format(numbers);
+            }
+            VisualContentInner::Spectrogram(state) => {
+                let mut state = state.borrow_mut();
+                state.set_level_range(ranges.spectrogram);
+                state.set_opacity(opacity);
+                state.set_tuning(tuning);
+                state.set_number_format(numbers);
+            }
+            VisualContentInner::Loudness(state) => {
+                let mut state = state.borrow_mut();
+                state.set_level_range(ranges.loudness);
+                state.set_opacity(opacity);
+                state.set_number_format(numbers);
+            }
+            VisualContentInner::Waveform(state) => {
+                let mut state = state.borrow_mut();
+                state.set_level_range(ranges.waveform);
+                state.set_opacity(opacity);
+            }
+            VisualContentInner::Oscilloscope(state) => state.borrow_mut().set_opacity(opacity),
+            VisualContentInner::Stereometer(state) => state.borrow_mut().set_opacity(opacity),
+            VisualContentInner::Tuner(state) => {
+                let mut state = state.borrow_mut();
+                state.set_opacity(opacity);
+                state.set_tuning(tuning);
+                state.set_number_format(numbers);
+            }
+        }
+        self.push_pulse(entry);
+    }
+    // Apart from the rest, since it changes on every batch while a beat fades.
+    fn push_pulse(&self, entry: &Entry) {
+        let pulse = self.pulse;
+        match entry.module.content().inner {
+            VisualContentInner::Spectrum(state) => state.borrow_mut().set_pulse(pulse),
+            VisualContentInner::Spectrogram(state) => state.borrow_mut().set_pulse(pulse),
+            VisualContentInner::Loudness(state) => state.borrow_mut().set_pulse(pulse),
+            VisualContentInner::Waveform(state) => state.borrow_mut().set_pulse(pulse),
+            VisualContentInner::Oscilloscope(state) => state.borrow_mut().set_pulse(pulse),
+            VisualContentInner::Stereometer(state) => state.borrow_mut().set_pulse(pulse),
+            VisualContentInner::Tuner(_) => {}
+        }
+    }
+    pub fn set_idle(&mut self, idle: bool) {
+        self.idle = idle;
     }
-    pub fn reorder(&mut self, order: &[VisualKind]) {
-        for (position, kind) in order.iter().copied().take(self.entries.len()).enumerate() {
-            self.move_to(kind, position);
+    pub fn set_snapshot_rate(&mut self, rate: SnapshotRate) {
+        self.snapshot_rate = rate;
+        if rate.hz().is_none() {
+            self.flush_held();
+        }
+    }
+    pub fn reorder(&mut self, order: &[VisualId]) {
+        for (position, id) in order.iter().copied().take(self.entries.len()).enumerate() {
+            self.move_to(id, position);
         }
     }
     pub fn apply_theme(&mut self, theme: &ThemeFile) {
         for entry in &mut self.entries {
             let mut settings = entry.module.export();
-            settings.override_palette(theme.palettes.get(&entry.descriptor.kind));
+            settings.override_palette(theme.palettes.get(&entry.id));
             entry.module.apply(&settings);
         }
     }
-    pub fn ingest_samples(&mut self, samples: &[f32], format: MeterFormat) {
+    fn with_loudness<T>(&self, f: impl FnOnce(&loudness::LoudnessState) -> T) -> Option<T> {
+        let entry = &self.entries[self.position(VisualKind::Loudness.into())?];
+        match entry.module.content().inner {
+            VisualContentInner::Loudness(state) if entry.enabled => Some(f(&state.borrow())),
+            _ => None,
+        }
+    }
+    pub fn loudness_levels(&self) -> Option<loudness::processor::LoudnessSnapshot> {
+        self.with_loudness(|state| state.snapshot())
+    }
+    pub fn loudness_alert(&self) -> Option<LoudnessAlert> {
+        self.with_loudness(|state| state.alert())
+    }
+    fn with_oscilloscope<T>(
+        &self,
+        id: VisualId,
+        f: impl FnOnce(&mut oscilloscope::OscilloscopeState) -> T,
+    ) -> Option<T> {
+        let entry = &self.entries[self.position(id)?];
+        match entry.module.content().inner {
+            VisualContentInner::Oscilloscope(state) => Some(f(&mut state.borrow_mut())),
+            _ => None,
+        }
+    }
+    pub fn oscilloscope_capture(
+        &self,
+        id: VisualId,
+    ) -> Option<oscilloscope::processor::OscilloscopeSnapshot> {
+        self.with_oscilloscope(id, |state| state.capture())
+            .flatten()
+    }
+    pub fn rearm_oscilloscope(&self, id: VisualId) {
+        self.with_oscilloscope(id, |state| state.rearm());
+    }
+    pub fn waveform_clip(&self, id: VisualId) -> Option<waveform::state::WaveformClip> {
+        let entry = &self.entries[self.position(id)?];
+        match entry.module.content().inner {
+            VisualContentInner::Waveform(state) => state.borrow().visible_clip(),
+            _ => None,
+        }
+    }
+    pub fn spectrogram_peaks_csv(&self, id: VisualId) -> Option<String> {
+        let entry = &self.entries[self.position(id)?];
+        match entry.module.content().inner {
+            VisualContentInner::Spectrogram(state) => state.borrow().selection_peaks_csv(),
+            _ => None,
+        }
+    }
+    pub fn spectrogram_bounds(&self, id: VisualId) -> Option<Rectangle> {
+        let entry = &self.entries[self.position(id)?];
+        match entry.module.content().inner {
+            VisualContentInner::Spectrogram(state) => state.borrow().drawn_bounds(),
+            _ => None,
+        }
+    }
+    pub fn spectrum_memory_trace(&self, id: VisualId) -> Option<MemoryTrace> {
+        let entry = &self.entries[self.position(id)?];
+        match entry.module.content().inner {
+            VisualContentInner::Spectrum(state) => state.borrow().memory_trace(),
+            _ => None,
+        }
+    }
+    /// Only the spectrogram honours pop-out display overrides.
+    pub fn set_display_override(&self, id: VisualId, display: Option<DisplayOverride>) {
+        let Some(index) = self.position(id) else {
+            return;
+        };
+        if let VisualContentInner::Spectrogram(state) = self.entries[index].module.content().inner {
+            state.borrow_mut().set_display_override(display);
+        }
+    }
+    /// Feeds the visuals, holding batches back until the next snapshot tick
+    /// when a rate is set. True when the visuals have new audio to show.
+    /// `captured` is when live audio was recorded; replays pass `None`.
+    pub fn ingest_samples(
+        &mut self,
+        samples: &[f32],
+        format: MeterFormat,
+        captured: Option<Instant>,
+    ) -> bool {
         if samples.is_empty() {
+            return false;
+        }
+        // Only the analysis copy is filtered; playback never passes through here.
+        let mut band_mute = std::mem::take(&mut self.band_mute);
+        let samples = band_mute.process(samples, format.channels, format.sample_rate);
+        let fresh = self.hold_or_fan_out(samples, format, captured);
+        self.band_mute = band_mute;
+        fresh
+    }
+    fn hold_or_fan_out(
+        &mut self,
+        samples: &[f32],
+        format: MeterFormat,
+        captured: Option<Instant>,
+    ) -> bool {
+        let Some(hz) = self.snapshot_rate.hz() else {
+            self.fan_out(samples, format, captured);
+            return true;
+        };
+        let mut fresh = false;
+        if self.held_format.is_some_and(|held| held != format) {
+            fresh = self.flush_held();
+        }
+        self.held.extend_from_slice(samples);
+        self.held_format = Some(format);
+        self.held_captured = captured;
+        let frames = self.held.len() / format.channels.max(1);
+        if (frames as f32) < format.sample_rate / hz {
+            return fresh;
+        }
+        self.flush_held()
+    }
+    fn flush_held(&mut self) -> bool {
+        let Some(format) = self.held_format.take() else {
+            return false;
+        };
+        let mut held = std::mem::take(&mut self.held);
+        let captured = self.held_captured.take();
+        self.fan_out(&held, format, captured);
+        // Keep the allocation for the next tick.
+        held.clear();
+        self.held = held;
+        true
+    }
+    fn fan_out(&mut self, samples: &[f32], format: MeterFormat, captured: Option<Instant>) {
+        let target = self
+            .entries
+            .iter()
+            .filter(|entry| self.align_latency && entry.enabled)
+            .map(|entry| entry.module.latency_frames())
+            .max()
+            .unwrap_or(0);
+        for entry in self
+            .entries
+            .iter_mut()
+            .filter(|entry| entry.enabled && !entry.frozen)
+            .filter(|entry| !self.idle || entry.id.kind == VisualKind::Loudness)
+        {
+            let started = Instant::now();
+            let frames = target.saturating_sub(entry.module.latency_frames());
+            if frames == 0 && entry.delay.is_empty() {
+                entry.module.ingest(samples, format, captured);
+            } else {
+                let delayed = entry.delay.process(samples, format.channels, frames);
+                if !delayed.is_empty() {
+                    // The held-back audio was captured that much earlier.
+                    let held = Duration::from_secs_f64(
+                        frames as f64 / f64::from(format.sample_rate.max(1.0)),
+                    );
+                    let captured = captured.and_then(|at| at.checked_sub(held));
+                    entry.module.ingest(&delayed, format, captured);
+                }
+            }
+            entry.spent += started.elapsed();
+        }
+        let frames = samples.len() / format.channels.max(1);
+        self.supervise_budget(frames as f64 / f64::from(format.sample_rate.max(1.0)));
+    }
+    /// Stops feeding a shown visual; false if it was hidden or already frozen.
+    pub fn freeze(&mut self, id: VisualId) -> bool {
+        let Some(entry) = self.position(id).map(|index| &mut self.entries[index]) else {
+            return false;
+        };
+        let froze = entry.enabled && !entry.frozen;
+        entry.frozen |= froze;
+        froze
+    }
+    pub fn any_frozen(&self) -> bool {
+        self.entries.iter().any(|entry| entry.frozen)
+    }
+    pub fn resume_frozen(&mut self) {
+        for entry in self.entries.iter_mut().filter(|entry| entry.frozen) {
+            entry.frozen = false;
+            // Audio held for alignment is stale by now.
+            entry.delay = FrameDelay::default();
+        }
+    }
+    /// Smoothed capture-to-draw latency of a shown visual.
+    pub fn display_latency_ms(&self, id: VisualId) -> Option<f32> {
+        let entry = &self.entries[self.position(id)?];
+        entry.module.display_latency_ms().filter(|_| entry.enabled)
+    }
+    /// Share of real time each shown visual spends ingesting, averaged over
+    /// the last budget window.
+    pub fn loads(&self) -> Vec<(VisualId, f64)> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.enabled)
+            .map(|entry| (entry.id, entry.load))
+            .collect()
+    }
+    /// True while any shown visual runs below its configured quality.
+    pub fn over_budget(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.enabled && entry.cut > 0)
+    }
+    fn supervise_budget(&mut self, seconds: f64) {
+        self.budget_window += seconds;
+        if self.budget_window < budget::WINDOW_SECONDS {
             return;
         }
-
-        for entry in &mut self.entries {
-            if entry.enabled {
-                entry.module.ingest(samples, format);
+        let window = std::mem::take(&mut self.budget_window);
+        let loads: Vec<Load> = self
+            .entries
+            .iter_mut()
+            .map(|entry| {
+                entry.load = std::mem::take(&mut entry.spent).as_secs_f64() / window;
+                Load {
+                    share: entry.load,
+                    cut: entry.cut,
+                }
+            })
+            .collect();
+        let total: f64 = loads.iter().map(|load| load.share).sum();
+        match budget::adjust(&loads) {
+            Adjust::Cut(order) => {
+                for index in order {
+                    let entry = &mut self.entries[index];
+                    if entry.module.set_quality_cut(entry.cut + 1) {
+                        entry.cut += 1;
+                        info!(
+                            "[visuals] ingest at {:.0}% of real time; {} quality cut to step {}",
+                            total * 100.0,
+                            entry.id.key(),
+                            entry.cut
+                        );
+                        break;
+                    }
+                }
             }
+            Adjust::Restore(index) => {
+                let entry = &mut self.entries[index];
+                if entry.module.set_quality_cut(entry.cut - 1) {
+                    entry.cut -= 1;
+                    info!(
+                        "[visuals] {} quality restored to step {}",
+                        entry.id.key(),
+                        entry.cut
+                    );
+                }
+            }
+            Adjust::Hold => {}
         }
     }
 }
@@ -405,4 +1099,147 @@ mod tests {
             assert_eq!(resolve_palette(Some(&palette), &defaults), expected);
         }
     }
+
+    #[test]
+    fn extra_instances_copy_the_primary_and_follow_saved_settings() {
+        let spectrum = VisualId::primary(VisualKind::Spectrum);
+        let mut manager = VisualManager::default();
+        let narrow = settings_cfg::SpectrumSettings {
+            bar_count: 12,
+            ..Default::default()
+        };
+        manager.apply_module_settings(spectrum, &ModuleSettings::with_config(&narrow));
+
+        let second = manager.add_instance(VisualKind::Spectrum).unwrap();
+        assert_eq!(second.key(), "spectrum#2");
+        assert!(manager.is_enabled(second) && !manager.is_enabled(spectrum));
+        let order = manager.order();
+        assert!(order.windows(2).any(|pair| pair == [spectrum, second]));
+        let copied = manager.module_settings(second).unwrap();
+        let copied: settings_cfg::SpectrumSettings = copied.parse_config().unwrap();
+        assert_eq!(copied.bar_count, 12);
+
+        let third = manager.add_instance(VisualKind::Spectrum).unwrap();
+        assert!(
+            (0..MAX_INSTANCES - 3).all(|_| manager.add_instance(VisualKind::Spectrum).is_some())
+        );
+        assert_eq!(manager.add_instance(VisualKind::Spectrum), None);
+        assert!(!manager.remove_instance(spectrum));
+        assert!(manager.remove_instance(third));
+        assert_eq!(manager.add_instance(VisualKind::Spectrum), Some(third));
+
+        let mut saved = VisualSettings::default();
+        saved.modules.insert(second, ModuleSettings::default());
+        manager.apply_visual_settings(&saved);
+        let spectra: Vec<_> = manager
+            .order()
+            .into_iter()
+            .filter(|id| id.kind == VisualKind::Spectrum)
+            .collect();
+        assert_eq!(spectra, [spectrum, second]);
+    }
+
+    #[test]
+    fn snapshot_rate_holds_batches_until_the_next_tick() {
+        let stereo = MeterFormat {
+            channels: 2,
+            sample_rate: 48_000.0,
+        };
+        let batch = [0.0; 512];
+        let mut manager = VisualManager::default();
+        assert!(manager.ingest_samples(&batch, stereo, None));
+
+        // 30 Hz at 48 kHz is 1600 frames, i.e. every seventh 256-frame batch.
+        manager.set_snapshot_rate(SnapshotRate::Hz30);
+        let ticks: Vec<bool> = (0..14)
+            .map(|_| manager.ingest_samples(&batch, stereo, None))
+            .collect();
+        let expected: Vec<bool> = (1..=14).map(|n| n % 7 == 0).collect();
+        assert_eq!(ticks, expected);
+
+        // A format change or switching the rate off flushes what is held.
+        assert!(!manager.ingest_samples(&batch, stereo, None));
+        let mono = MeterFormat {
+            channels: 1,
+            ..stereo
+        };
+        assert!(manager.ingest_samples(&batch, mono, None));
+        assert!(manager.held_format.is_some());
+        manager.set_snapshot_rate(SnapshotRate::Batch);
+        assert!(manager.held.is_empty() && manager.held_format.is_none());
+    }
+
+    #[test]
+    fn frozen_visuals_skip_audio_until_resumed() {
+        let stereo = MeterFormat {
+            channels: 2,
+            sample_rate: 48_000.0,
+        };
+        let spectrum = VisualId::primary(VisualKind::Spectrum);
+        let mut manager = VisualManager::default();
+        assert!(!manager.freeze(spectrum));
+        manager.set_enabled(spectrum, true);
+        assert!(manager.freeze(spectrum) && !manager.freeze(spectrum));
+
+        manager.ingest_samples(&[0.0; 512], stereo, None);
+        let waiting = manager
+            .snapshot()
+            .into_iter()
+            .find(|slot| slot.id == spectrum);
+        assert!(waiting.unwrap().content.waiting);
+
+        manager.resume_frozen();
+        assert!(!manager.any_frozen());
+        manager.ingest_samples(&[0.0; 512], stereo, None);
+        let fed = manager
+            .snapshot()
+            .into_iter()
+            .find(|slot| slot.id == spectrum);
+        assert!(!fed.unwrap().content.waiting);
+    }
+
+    #[test]
+    fn visuals_wait_for_their_first_audio() {
+        let waiting = |manager: &VisualManager, kind: VisualKind| {
+            let snapshot = manager.snapshot();
+            let slot = snapshot.iter().find(|slot| slot.id.kind == kind).unwrap();
+            slot.content.waiting
+        };
+        let mut manager = VisualManager::default();
+        manager.set_enabled(VisualKind::Spectrum.into(), true);
+        assert!(waiting(&manager, VisualKind::Spectrum));
+
+        let stereo = MeterFormat {
+            channels: 2,
+            sample_rate: 48_000.0,
+        };
+        manager.ingest_samples(&[0.0; 512], stereo, None);
+        assert!(!waiting(&manager, VisualKind::Spectrum));
+        assert!(waiting(&manager, VisualKind::Oscilloscope));
+    }
+
+    #[test]
+    fn idle_feeds_only_the_loudness_meter() {
+        let waiting = |manager: &VisualManager, kind: VisualKind| {
+            let snapshot = manager.snapshot();
+            let slot = snapshot.iter().find(|slot| slot.id.kind == kind).unwrap();
+            slot.content.waiting
+        };
+        let mut manager = VisualManager::default();
+        manager.set_enabled(VisualKind::Spectrum.into(), true);
+        manager.set_enabled(VisualKind::Loudness.into(), true);
+        manager.set_idle(true);
+
+        let stereo = MeterFormat {
+            channels: 2,
+            sample_rate: 48_000.0,
+        };
+        manager.ingest_samples(&[0.0; 512], stereo, None);
+        assert!(waiting(&manager, VisualKind::Spectrum));
+        assert!(!waiting(&manager, VisualKind::Loudness));
+
+        manager.set_idle(false);
+        manager.ingest_samples(&[0.0; 512], stereo, None);
+        assert!(!waiting(&manager, VisualKind::Spectrum));
+    }
 }
@@ -1,28 +1,83 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // Copyright (C) 2026 Maika Namuo
 
+use crate::persistence::settings::FrequencyMarker;
+use crate::util::color::with_alpha;
 use bytemuck::{Pod, Zeroable};
 use iced::advanced::graphics::Viewport;
 use iced::advanced::text::Text as IcedText;
-use iced::{Border, Color, Rectangle, Renderer, Size};
+use iced::{Border, Color, Point, Rectangle, Renderer, Size};
 use std::collections::HashMap;
 use std::mem::size_of;
+use std::sync::atomic::{AtomicBool, Ordering};
 
+// Chosen once at startup, before the first window opens.
+static SOFTWARE_RENDERING: AtomicBool = AtomicBool::new(false);
+
+pub fn set_software_rendering(enabled: bool) {
+    SOFTWARE_RENDERING.store(enabled, Ordering::Relaxed);
+}
+
+/// True when iced draws with tiny-skia, which cannot run custom shaders.
+pub fn software_rendering() -> bool {
+    SOFTWARE_RENDERING.load(Ordering::Relaxed)
+}
+
+/// Maps logical coordinates to clip space and snaps them to the physical
+/// pixel grid, so edges stay sharp under fractional scaling.
 #[derive(Clone, Copy)]
-pub struct ClipTransform(f32, f32);
+pub struct ClipTransform {
+    sx: f32,
+    sy: f32,
+    scale: f32,
+}
 
 impl ClipTransform {
-    fn new(w: f32, h: f32) -> Self {
-        Self(2.0 / w.max(1.0), 2.0 / h.max(1.0))
+    fn new(w: f32, h: f32, scale: f32) -> Self {
+        Self {
+            sx: 2.0 / w.max(1.0),
+            sy: 2.0 / h.max(1.0),
+            scale: if scale.is_finite() && scale > 0.0 {
+                scale
+            } else {
+                1.0
+            },
+        }
     }
 
     pub fn from_viewport(vp: &Viewport) -> Self {
         let s = vp.logical_size();
-        Self::new(s.width, s.height)
+        Self::new(s.width, s.height, vp.scale_factor())
     }
 
     pub fn to_clip(self, x: f32, y: f32) -> [f32; 2] {
-        [x * self.0 - 1.0, 1.0 - y * self.1]
+        [x * self.sx - 1.0, 1.0 - y * self.sy]
+    }
+
+    /// Rounds `v` to the nearest physical pixel boundary.
+    fn snap(self, v: f32) -> f32 {
+        (v * self.scale).round() / self.scale
+    }
+
+    /// Snaps the span `a..b` to pixel boundaries without letting a non-empty
+    /// span collapse below one physical pixel.
+    fn snap_span(self, a: f32, b: f32) -> (f32, f32) {
+        let (sa, sb) = (self.snap(a), self.snap(b));
+        if sa == sb && a != b {
+            let px = (b - a).signum() / self.scale;
+            (sa, sa + px)
+        } else {
+            (sa, sb)
+        }
+    }
+
+    /// Whole-pixel width and centre for an axis-aligned line, so odd widths
+    /// sit on pixel centres and even widths on pixel edges.
+    fn snap_line(self, center: f32, width: f32) -> (f32, f32) {
+        let px = (width * self.scale).round().max(1.0);
+        let offset = if px % 2.0 == 1.0 { 0.5 } else { 0.0 };
+        let center = ((center * self.scale - offset).round() + offset) / self.scale;
+        (center, px / self.scale)
     }
 }
 
@@ -117,20 +172,156 @@ pub(in crate::visuals) fn fill_snapped_bordered_rect(
     fill_rect_quad(r, bounds, color, border, true);
 }
 
+const MARKER_LABEL_SIZE: f32 = 10.0;
+const MARKER_LABEL_PAD: f32 = 4.0;
+const SOFTWARE_NOTICE: &str = "Needs GPU rendering";
+const SOFTWARE_NOTICE_SIZE: f32 = 12.0;
+const BADGE_TEXT_SIZE: f32 = 10.0;
+const BADGE_PAD: f32 = 4.0;
+
+/// `color` with its alpha scaled by `opacity`. Text and quads go through
+/// the renderer rather than the visual pipelines, so they fade through this.
+pub(in crate::visuals) fn faded(color: Color, opacity: f32) -> Color {
+    with_alpha(color, color.a * opacity)
+}
+
+/// `to_axis` maps Hz to a screen coordinate along x, or along y when
+/// `vertical`; values outside `bounds` are clipped.
+pub(in crate::visuals) fn draw_frequency_markers(
+    r: &mut Renderer,
+    theme: &iced::Theme,
+    bounds: Rectangle,
+    markers: &[FrequencyMarker],
+    vertical: bool,
+    opacity: f32,
+    to_axis: impl Fn(f32) -> f32,
+) {
+    use iced::advanced::text::Renderer as _;
+    let color = theme.extended_palette().danger.base.color;
+    let (org, ext) = if vertical {
+        (bounds.y, bounds.height)
+    } else {
+        (bounds.x, bounds.width)
+    };
+    for marker in markers {
+        let (lo, hi) = marker.span();
+        let (a, b) = (to_axis(lo), to_axis(hi));
+        if !a.is_finite() || !b.is_finite() || a.max(b) < org || a.min(b) > org + ext {
+            continue;
+        }
+        let (start, end) = (a.min(b).max(org), a.max(b).min(org + ext));
+        let (pos, len, alpha) = if marker.end_hz.is_some() {
+            (start, (end - start).max(1.0), 0.18)
+        } else {
+            (
+                (start - 0.5).clamp(org, (org + ext - 1.0).max(org)),
+                1.0,
+                0.7,
+            )
+        };
+        let rect = if vertical {
+            Rectangle::new(Point::new(bounds.x, pos), Size::new(bounds.width, len))
+        } else {
+            Rectangle::new(Point::new(pos, bounds.y), Size::new(len, bounds.height))
+        };
+        fill_rect(r, rect, with_alpha(color, alpha * opacity));
+
+        if marker.label.is_empty() {
+            continue;
+        }
+        let size = measure_text(&marker.label, MARKER_LABEL_SIZE);
+        let at = if vertical {
+            Point::new(
+                bounds.x + MARKER_LABEL_PAD,
+                (pos - size.height - 1.0).max(bounds.y),
+            )
+        } else {
+            Point::new(
+                (pos + MARKER_LABEL_PAD).min(bounds.x + bounds.width - size.width),
+                bounds.y + bounds.height - size.height - MARKER_LABEL_PAD,
+            )
+        };
+        r.fill_text(
+            make_text(marker.label.as_str(), MARKER_LABEL_SIZE, size),
+            at,
+            with_alpha(color, 0.9 * opacity),
+            Rectangle::new(at, size),
+        );
+    }
+}
+
+/// A short note centred on the top edge, such as that calibration is applied.
+pub(in crate::visuals) fn draw_badge(
+    r: &mut Renderer,
+    theme: &iced::Theme,
+    bounds: Rectangle,
+    label: &str,
+    opacity: f32,
+) {
+    use iced::advanced::text::Renderer as _;
+    let size = measure_text(label, BADGE_TEXT_SIZE);
+    let rect = Rectangle::new(
+        Point::new(
+            bounds.center_x() - size.width * 0.5 - BADGE_PAD,
+            bounds.y + BADGE_PAD,
+        ),
+        Size::new(size.width + BADGE_PAD * 2.0, size.height),
+    );
+    if rect.width > bounds.width || rect.height > bounds.height {
+        return;
+    }
+    let pal = theme.extended_palette();
+    fill_rect(
+        r,
+        rect,
+        with_alpha(pal.background.base.color, 0.6 * opacity),
+    );
+    let at = Point::new(rect.x + BADGE_PAD, rect.y);
+    r.fill_text(
+        make_text(label, BADGE_TEXT_SIZE, size),
+        at,
+        with_alpha(pal.primary.base.color, 0.9 * opacity),
+        Rectangle::new(at, size),
+    );
+}
+
+/// Draws `primitive`, or a notice in its place when shaders are unavailable.
+pub(in crate::visuals) fn draw_shader(
+    r: &mut Renderer,
+    theme: &iced::Theme,
+    bounds: Rectangle,
+    primitive: impl iced_wgpu::primitive::Primitive,
+) {
+    use iced::advanced::text::Renderer as _;
+    use iced_wgpu::primitive::Renderer as _;
+    if !software_rendering() {
+        r.draw_primitive(bounds, primitive);
+        return;
+    }
+    let mut notice = make_text(SOFTWARE_NOTICE, SOFTWARE_NOTICE_SIZE, bounds.size());
+    notice.align_x = iced::alignment::Horizontal::Center.into();
+    notice.align_y = iced::alignment::Vertical::Center;
+    let color = with_alpha(theme.extended_palette().background.base.text, 0.6);
+    r.fill_text(notice, bounds.center(), color, bounds);
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct SdfVertex {
     pub position: [f32; 2],
     pub color: [f32; 4],
-    pub params: [f32; 4],
+    pub params: [f32; 3],
+    /// Above 0.5 for additive dots, whose colour is pre-scaled with zero alpha.
+    pub additive: f32,
 }
 
 impl SdfVertex {
-    const SOLID_PARAMS: [f32; 4] = [0.0, 0.0, 1000.0, 0.0];
+    const SOLID_PARAMS: [f32; 3] = [0.0, 0.0, 1000.0];
 
     pub fn layout() -> wgpu::VertexBufferLayout<'static> {
-        const ATTRS: [wgpu::VertexAttribute; 3] =
-            wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x4, 2 => Float32x4];
+        const ATTRS: [wgpu::VertexAttribute; 4] = wgpu::vertex_attr_array![
+            0 => Float32x2, 1 => Float32x4, 2 => Float32x3, 3 => Float32
+        ];
         wgpu::VertexBufferLayout {
             array_stride: size_of::<Self>() as wgpu::BufferAddress,
             step_mode: wgpu::VertexStepMode::Vertex,
@@ -143,6 +334,7 @@ impl SdfVertex {
             position: pos,
             color,
             params: Self::SOLID_PARAMS,
+            additive: 0.0,
         }
     }
 
@@ -150,7 +342,8 @@ impl SdfVertex {
         Self {
             position: pos,
             color,
-            params: [dist, 0.0, radius, 0.0],
+            params: [dist, 0.0, radius],
+            additive: 0.0,
         }
     }
 }
@@ -175,6 +368,7 @@ pub(in crate::visuals) fn gradient_quad_vertices(
     top: [f32; 4],
     bot: [f32; 4],
 ) -> [SdfVertex; 6] {
+    let ((x0, x1), (y0, y1)) = (clip.snap_span(x0, x1), clip.snap_span(y0, y1));
     let (tl, tr, bl, br) = (
         clip.to_clip(x0, y0),
         clip.to_clip(x1, y0),
@@ -234,6 +428,25 @@ pub fn line_vertices(
     ]
 }
 
+/// A single-color line that lands on whole physical pixels when it is
+/// horizontal or vertical; meant for grids and guides, not for traces.
+pub fn rule_vertices(
+    mut p0: (f32, f32),
+    mut p1: (f32, f32),
+    color: [f32; 4],
+    mut width: f32,
+    clip: ClipTransform,
+) -> [SdfVertex; 6] {
+    if p0.1 == p1.1 {
+        (p0.1, width) = clip.snap_line(p0.1, width);
+        p1.1 = p0.1;
+    } else if p0.0 == p1.0 {
+        (p0.0, width) = clip.snap_line(p0.0, width);
+        p1.0 = p0.0;
+    }
+    line_vertices(p0, p1, color, color, width, clip)
+}
+
 pub fn dot_vertices(
     cx: f32,
     cy: f32,
@@ -243,11 +456,11 @@ pub fn dot_vertices(
     additive: bool,
 ) -> [SdfVertex; 6] {
     let outer = radius + 1.0;
-    let flag = if additive { 1.0 } else { 0.0 };
     let v = |ox, oy| SdfVertex {
         position: clip.to_clip(cx + ox, cy + oy),
         color,
-        params: [ox, oy, radius, flag],
+        params: [ox, oy, radius],
+        additive: if additive { 1.0 } else { 0.0 },
     };
     [
         v(-outer, -outer),
@@ -309,6 +522,33 @@ impl GeometryScratch {
         self.points2.clear();
         self.scalars.clear();
     }
+
+    /// Scales colours up by `1 + amount`, saturating at white.
+    pub fn brighten(&mut self, amount: f32) {
+        if amount <= 0.0 {
+            return;
+        }
+        for vertex in &mut self.vertices {
+            for c in vertex.color.iter_mut().take(3) {
+                *c = (*c * (1.0 + amount)).min(1.0);
+            }
+        }
+    }
+
+    /// Scales coverage by `opacity`. Additive vertices carry zero alpha, so
+    /// their colour is scaled instead.
+    pub fn fade(&mut self, opacity: f32) {
+        if opacity >= 1.0 {
+            return;
+        }
+        for vertex in &mut self.vertices {
+            if vertex.additive > 0.5 {
+                vertex.color.iter_mut().take(3).for_each(|c| *c *= opacity);
+            } else {
+                vertex.color[3] *= opacity;
+            }
+        }
+    }
 }
 
 /// Decimates points known to be finite and ordered by x.
@@ -652,6 +892,8 @@ macro_rules! sdf_primitive {
                 let key: $key_ty = $key_expr;
                 pipeline.scratch.clear();
                 $self.build_vertices(viewport, &mut pipeline.scratch);
+                pipeline.scratch.brighten($self.params.pulse);
+                pipeline.scratch.fade($self.params.opacity);
                 pipeline.inner.prepare_instance(device, queue, $label, key, &pipeline.scratch.vertices);
             }
 
@@ -701,6 +943,21 @@ pub(in crate::visuals) use sdf_primitive;
 mod tests {
     use super::*;
 
+    #[test]
+    fn fading_keeps_additive_vertices_additive() {
+        let mut scratch = GeometryScratch::default();
+        scratch
+            .vertices
+            .push(SdfVertex::solid([0.0; 2], [1.0, 0.5, 0.5, 0.8]));
+        let mut additive = SdfVertex::solid([0.0; 2], [0.4, 0.4, 0.4, 0.0]);
+        additive.additive = 1.0;
+        scratch.vertices.push(additive);
+
+        scratch.fade(0.5);
+        assert_eq!(scratch.vertices[0].color, [1.0, 0.5, 0.5, 0.4]);
+        assert_eq!(scratch.vertices[1].color, [0.2, 0.2, 0.2, 0.0]);
+    }
+
     #[test]
     fn decimate_line_advances_when_bucket_edge_rounds_below_point() {
         let mut pts = vec![(667.6, 0.0), (3881.2603, 1.0)];
@@ -724,6 +981,25 @@ mod tests {
         assert!(pts.iter().any(|point| point.1 == max));
     }
 
+    #[test]
+    fn fractional_scaling_snaps_to_physical_pixels() {
+        let clip = ClipTransform::new(100.0, 100.0, 1.5);
+        // Logical values back in physical pixels, checked to sit on the half-pixel grid.
+        let physical = |(a, b): (f32, f32)| {
+            [a, b].map(|v| {
+                let half_pixels = (v * 3.0).round() / 2.0;
+                assert!((v * 1.5 - half_pixels).abs() < 1e-4, "{v} is off the grid");
+                half_pixels
+            })
+        };
+
+        assert_eq!(physical(clip.snap_span(10.2, 11.1)), [15.0, 17.0]);
+        assert_eq!(physical(clip.snap_span(10.2, 10.3)), [15.0, 16.0]);
+        assert_eq!(physical(clip.snap_line(20.1, 2.0)), [30.5, 3.0]);
+        assert_eq!(physical(clip.snap_line(20.1, 0.5)), [30.5, 1.0]);
+        assert_eq!(physical(clip.snap_line(20.1, 1.5)), [30.0, 2.0]);
+    }
+
     #[test]
     fn ordered_decimation_honors_budget_at_rounded_bucket_edges() {
         let mut pts = (0..8)
@@ -1,20 +1,22 @@
 // SDF shader - antialiasing via screen-space derivatives.
 //
-// params: [dist_x, dist_y, radius, additive]
-// - Solid: (0, 0, large, 0)
-// - Line: (+-outer, 0, half_width, 0)
-// - Dot: (ox, oy, radius, additive)
+// params: [dist_x, dist_y, radius]
+// - Solid: (0, 0, large)
+// - Line: (+-outer, 0, half_width)
+// - Dot: (ox, oy, radius)
+// additive: 1 for additive dots, 0 otherwise
 
 struct VertexInput {
     @location(0) position: vec2<f32>,
     @location(1) color: vec4<f32>,
-    @location(2) params: vec4<f32>,
+    @location(2) params: vec3<f32>,
+    @location(3) additive: f32,
 };
 
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
     @location(0) color: vec4<f32>,
-    @location(1) params: vec4<f32>,
+    @location(1) params: vec3<f32>,
 };
 
 fn premultiply(color: vec4<f32>) -> vec4<f32> {
@@ -25,9 +27,9 @@ fn premultiply(color: vec4<f32>) -> vec4<f32> {
 fn vs_main(input: VertexInput) -> VertexOutput {
     var output: VertexOutput;
     output.position = vec4<f32>(input.position, 0.0, 1.0);
-    // params.w > 0.5 marks additive dots: caller has pre-scaled the color with
+    // additive > 0.5 marks additive dots: caller has pre-scaled the color with
     // alpha=0 so that premultiplied-alpha blending reduces to dst += src.
-    output.color = select(premultiply(input.color), input.color, input.params.w > 0.5);
+    output.color = select(premultiply(input.color), input.color, input.additive > 0.5);
     output.params = input.params;
     return output;
 }
@@ -6,15 +6,15 @@ const LOW_POWER_SCALE: f32 = 16777216.0;
 const INV_LOW_POWER_SCALE: f32 = 0.000000059604644775390625;
 const F16_MAX: f32 = 65504.0;
 const LOG_KNEE_HZ: f32 = 20.0;
-
-// Classic storage domain -- keep in sync with processor.rs CLASSIC_DB_STORE_*.
-const DB_STORE_LO: f32 = -144.0;
-const DB_STORE_HI: f32 = 12.0;
-const DB_STORE_RANGE: f32 = DB_STORE_HI - DB_STORE_LO;
+const PI: f32 = 3.14159265;
+const TAU: f32 = 6.28318531;
 
 // Analysis floor -- keep in sync with util::audio::DB_FLOOR.
 const DB_ANALYSIS_FLOOR: f32 = -140.0;
 const DB_FLOOR_EPS: f32 = 0.01;
+// Caps the sharpening boost, so an isolated point above an empty neighbourhood
+// does not blow straight through the ceiling.
+const SHARPEN_LIMIT_DB: f32 = 24.0;
 
 // Must match Rust-side Uniforms layout exactly.
 struct Uniforms {
@@ -34,7 +34,7 @@ struct Uniforms {
 
     floor_db: f32,
     ceiling_db: f32,
-    contrast: f32,
+    gamma: f32,
     tilt_db: f32,
 
     newest_col: u32,
@@ -51,6 +51,22 @@ struct Uniforms {
     stops: array<vec4<f32>, 2>,
     // Palette colors come from a uniform array (legacy texture path removed).
     palette: array<vec4<f32>, 5>,
+    // Soft-knee compression of the upper range; 0 disables it.
+    knee: f32,
+    // Stop interpolation space: 0 = sRGB, 1 = Oklab, 2 = LCh.
+    blend: u32,
+    // Window opacity, applied after shading.
+    opacity: f32,
+    // Classic bin sampling: 0 = nearest, 1 = linear, 2 = cubic.
+    interpolation: u32,
+    // Unsharp-mask amount; 0 disables it.
+    sharpen: f32,
+    // Beat pulse brightening, 0..1.
+    pulse: f32,
+    // Columns still sliding in from the newest edge, plus any time pan.
+    scroll_lag: f32,
+    // Logical pixels per column along time; 1 unless time-zoomed.
+    time_scale: f32,
 }
 
 struct AccumOutput {
@@ -94,6 +110,67 @@ fn spread_t(linear_t: f32, sl: f32, sr: f32) -> f32 {
     return clamp(pow(linear_t, sl / sr), 0.0, 1.0);
 }
 
+fn srgb_to_linear(c: vec3<f32>) -> vec3<f32> {
+    return select(pow((c + 0.055) / 1.055, vec3(2.4)), c / 12.92, c <= vec3(0.04045));
+}
+
+fn linear_to_srgb(c: vec3<f32>) -> vec3<f32> {
+    let x = clamp(c, vec3(0.0), vec3(1.0));
+    return select(1.055 * pow(x, vec3(1.0 / 2.4)) - 0.055, x * 12.92, x <= vec3(0.0031308));
+}
+
+fn srgb_to_oklab(c: vec3<f32>) -> vec3<f32> {
+    let rgb = srgb_to_linear(c);
+    let lms = pow(vec3(
+        dot(rgb, vec3(0.4122214708, 0.5363325363, 0.0514459929)),
+        dot(rgb, vec3(0.2119034982, 0.6806995451, 0.1073969566)),
+        dot(rgb, vec3(0.0883024619, 0.2817188376, 0.6299787005)),
+    ), vec3(1.0 / 3.0));
+    return vec3(
+        dot(lms, vec3(0.2104542553, 0.7936177850, -0.0040720468)),
+        dot(lms, vec3(1.9779984951, -2.4285922050, 0.4505937099)),
+        dot(lms, vec3(0.0259040371, 0.7827717662, -0.8086757660)),
+    );
+}
+
+fn oklab_to_srgb(lab: vec3<f32>) -> vec3<f32> {
+    let l = lab.x + dot(lab.yz, vec2(0.3963377774, 0.2158037573));
+    let m = lab.x + dot(lab.yz, vec2(-0.1055613458, -0.0638541728));
+    let s = lab.x + dot(lab.yz, vec2(-0.0894841775, -1.2914855480));
+    let lms = vec3(l, m, s) * vec3(l, m, s) * vec3(l, m, s);
+    return linear_to_srgb(vec3(
+        dot(lms, vec3(4.0767416621, -3.3077115913, 0.2309699292)),
+        dot(lms, vec3(-1.2684380046, 2.6097574011, -0.3413193965)),
+        dot(lms, vec3(-0.0041960863, -0.7034186147, 1.7076147010)),
+    ));
+}
+
+// Mirrors `mix_rgba` in util/color.rs.
+fn mix_stops(lo: vec4<f32>, hi: vec4<f32>, t: f32) -> vec4<f32> {
+    if (u.blend == 0u) {
+        return mix(lo, hi, t);
+    }
+    let a = srgb_to_oklab(lo.rgb);
+    let b = srgb_to_oklab(hi.rgb);
+    var lab = mix(a, b, t);
+    if (u.blend == 2u) {
+        let ca = length(a.yz);
+        let cb = length(b.yz);
+        var ha = atan2(a.z, a.y);
+        var hb = atan2(b.z, b.y);
+        // Greys have no meaningful hue, so they borrow the other end's.
+        if (ca < 1e-4 && cb >= 1e-4) {
+            ha = hb;
+        } else if (cb < 1e-4) {
+            hb = ha;
+        }
+        let dh = hb - ha - TAU * floor((hb - ha + PI) / TAU);
+        let h = ha + dh * t;
+        lab = vec3(lab.x, mix(ca, cb, t) * vec2(cos(h), sin(h)));
+    }
+    return vec4(oklab_to_srgb(lab), mix(lo.a, hi.a, t));
+}
+
 fn palette_color(t: f32) -> vec4<f32> {
     let tc = clamp(t, 0.0, 1.0);
     var lo = u.palette[3];
@@ -125,7 +202,7 @@ fn palette_color(t: f32) -> vec4<f32> {
         sr = u.stops[1].z;
     }
     let linear_t = clamp((tc - p_lo) / max(p_hi - p_lo, 1e-6), 0.0, 1.0);
-    return mix(lo, hi, spread_t(linear_t, sl, sr));
+    return mix_stops(lo, hi, spread_t(linear_t, sl, sr));
 }
 
 // 0 = newest. Single formula handles both partial and full rings via newest_col.
@@ -143,10 +220,12 @@ fn extents() -> vec2<f32> {
 }
 
 // `col_stride_u16` rounds ppc up to even so u16 pairs never straddle u32 words.
+// Classic bins are bfloat16 linear power -- see processor.rs pack_classic_power.
 fn unpack_mag(slot: u32, bin_in_col: u32) -> f32 {
     let idx = slot * u.col_stride_u16 + bin_in_col;
-    let pair = unpack2x16unorm(mags[idx / 2u]);
-    return select(pair.y, pair.x, (idx & 1u) == 0u) * DB_STORE_RANGE + DB_STORE_LO;
+    let word = mags[idx / 2u];
+    let bits = select(word >> 16u, word & 0xffffu, (idx & 1u) == 0u);
+    return power_to_db(bitcast<f32>(bits << 16u));
 }
 
 const CULL_POS: vec4<f32> = vec4<f32>(0.0, 0.0, 2.0, 1.0);
@@ -171,8 +250,9 @@ fn vs_accum_splat(
     }
     let ext = extents();
     let age = compute_age(inst / max(u.points_per_col, 1u));
-    let pos = vec2<f32>(ext.x - (f32(age) - time_offset) * u.scale_factor, (1.0 - zoomed) * ext.y)
-        + corner * u.scale_factor;
+    let column_px = u.scale_factor * u.time_scale;
+    let x = ext.x - (f32(age) - time_offset - u.scroll_lag) * column_px;
+    let pos = vec2<f32>(x, (1.0 - zoomed) * ext.y) + corner * vec2<f32>(column_px, u.scale_factor);
     return AccumOutput(place_accum(pos), magnitude_db, freq_hz);
 }
 
@@ -221,7 +301,9 @@ fn classic_sample(frag_xy: vec2<f32>) -> vec2<f32> {
         return vec2<f32>(CLASSIC_SENTINEL_DB, 0.0);
     }
 
-    let age_f = floor((ext.x - pos.x) / max(u.scale_factor, 1e-6));
+    let lag = max(u.scroll_lag, 0.0);
+    let column_px = max(u.scale_factor * u.time_scale, 1e-6);
+    let age_f = floor((ext.x - pos.x) / column_px) + floor(lag);
     if age_f < 0.0 || age_f >= f32(u.col_count) {
         return vec2<f32>(CLASSIC_SENTINEL_DB, 0.0);
     }
@@ -240,12 +322,56 @@ fn classic_sample(frag_xy: vec2<f32>) -> vec2<f32> {
         return vec2<f32>(CLASSIC_SENTINEL_DB, 0.0);
     }
 
-    let bin0 = min(u32(floor(bin_f)), max_bin);
-    let bin1 = min(bin0 + 1u, max_bin);
-    let mag = mix(unpack_mag(slot, bin0), unpack_mag(slot, bin1), fract(bin_f));
+    var mag = column_mag(slot, bin_f, max_bin);
+    // Part way through a scroll step, blend toward the next older column.
+    let step = fract(lag);
+    if step > 0.0 && age_f + 1.0 < f32(u.col_count) {
+        mag = mix(mag, column_mag((slot + hl - 1u) % hl, bin_f, max_bin), step);
+    }
     return vec2<f32>(mag, freq_hz);
 }
 
+fn column_mag(slot: u32, bin_f: f32, max_bin: u32) -> f32 {
+    let mag = sample_bins(slot, bin_f, max_bin);
+    if u.sharpen <= 0.0 {
+        return mag;
+    }
+    let below = sample_bins(slot, max(bin_f - 1.0, 0.0), max_bin);
+    let above = sample_bins(slot, min(bin_f + 1.0, f32(max_bin)), max_bin);
+    return sharpen(mag, max(below, u.floor_db) * 0.5 + max(above, u.floor_db) * 0.5);
+}
+
+fn bin_mag(slot: u32, bin: i32, max_bin: u32) -> f32 {
+    return unpack_mag(slot, u32(clamp(bin, 0, i32(max_bin))));
+}
+
+// Interpolates between FFT bins; columns are `time_scale` pixels wide along time.
+fn sample_bins(slot: u32, bin_f: f32, max_bin: u32) -> f32 {
+    let bin = i32(floor(bin_f));
+    let t = fract(bin_f);
+    switch u.interpolation {
+        case 0u: { return bin_mag(slot, i32(round(bin_f)), max_bin); }
+        case 2u: {
+            // Catmull-Rom through the four nearest bins.
+            let p0 = bin_mag(slot, bin - 1, max_bin);
+            let p1 = bin_mag(slot, bin, max_bin);
+            let p2 = bin_mag(slot, bin + 1, max_bin);
+            let p3 = bin_mag(slot, bin + 2, max_bin);
+            let a = -0.5 * p0 + 1.5 * p1 - 1.5 * p2 + 0.5 * p3;
+            let b = p0 - 2.5 * p1 + 2.0 * p2 - 0.5 * p3;
+            let c = 0.5 * (p2 - p0);
+            return max(((a * t + b) * t + c) * t + p1, DB_ANALYSIS_FLOOR);
+        }
+        default: { return mix(bin_mag(slot, bin, max_bin), bin_mag(slot, bin + 1, max_bin), t); }
+    }
+}
+
+fn sharpen(center_db: f32, surround_db: f32) -> f32 {
+    let detail = clamp(center_db - surround_db, -SHARPEN_LIMIT_DB, SHARPEN_LIMIT_DB);
+    // Never pushes anything below the display floor.
+    return max(center_db + u.sharpen * detail, min(center_db, u.floor_db));
+}
+
 fn apply_tilt(mut_mag: f32, freq_hz: f32) -> f32 {
     var mag = mut_mag;
     // dB/octave tilt relative to 1 kHz. Do not lift sentinels/floor bins.
@@ -264,15 +390,21 @@ fn shade_db(mag: f32) -> vec4<f32> {
     let range = max(u.ceiling_db - u.floor_db, 0.001);
     let normalized = clamp((mag - u.floor_db) / range, 0.0, 1.0);
     var adjusted = normalized;
-    if abs(u.contrast - 1.0) > 1e-4 {
-        adjusted = pow(normalized, max(u.contrast, 0.01));
+    if abs(u.gamma - 1.0) > 1e-4 {
+        adjusted = pow(normalized, max(u.gamma, 0.01));
+    }
+    // Keeps 0 and 1 fixed: slope 1 + k at the floor, 1 / (1 + k) at the ceiling.
+    if u.knee > 0.0 {
+        adjusted = adjusted * (1.0 + u.knee) / (1.0 + u.knee * adjusted);
     }
 
-    // Mix palette stops in sRGB space (web-colors pipeline).
+    // Palette stops are sRGB-encoded (web-colors pipeline).
     let color = palette_color(adjusted);
+    let rgb = min(color.rgb * (1.0 + u.pulse), vec3<f32>(1.0));
 
     // iced expects premultiplied alpha
-    return vec4<f32>(color.rgb * color.a, color.a);
+    let alpha = color.a * u.opacity;
+    return vec4<f32>(rgb * alpha, alpha);
 }
 
 fn shade(mut_mag: f32, freq_hz: f32) -> vec4<f32> {
@@ -311,13 +443,26 @@ fn accumulated_power(pos: vec2<f32>) -> f32 {
     return select(scaled.r, scaled.g * INV_LOW_POWER_SCALE, scaled_low_power);
 }
 
+fn resolved_db(pos: vec2<f32>) -> f32 {
+    let power = accumulated_power(pos) * u.reassigned_power_scale;
+    return select(u.floor_db, max(power_to_db(power), u.floor_db), power > 0.0);
+}
+
 @fragment
 fn fs_resolve(in: ResolveOutput) -> @location(0) vec4<f32> {
     let power = accumulated_power(in.accum_pos) * u.reassigned_power_scale;
     if power <= 0.0 {
         return vec4<f32>(0.0);
     }
-    return shade_db(power_to_db(power));
+    var db = power_to_db(power);
+    if u.sharpen > 0.0 {
+        let surround = resolved_db(in.accum_pos + vec2<f32>(1.0, 0.0))
+            + resolved_db(in.accum_pos - vec2<f32>(1.0, 0.0))
+            + resolved_db(in.accum_pos + vec2<f32>(0.0, 1.0))
+            + resolved_db(in.accum_pos - vec2<f32>(0.0, 1.0));
+        db = sharpen(db, surround * 0.25);
+    }
+    return shade_db(db);
 }
 
 @fragment
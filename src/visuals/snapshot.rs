@@ -0,0 +1,237 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Offscreen renders of the visuals, for tests and for the documentation
+// screenshots (`openmeters --screenshots <DIR>`). A visual's state is drawn
+// by the same widget the app shows, into an RGBA image. Needs a GPU adapter,
+// since the shader visuals have nothing to draw on tiny-skia.
+
+use super::registry::{self, VisualKind};
+use crate::infra::pipewire::meter_tap::MeterFormat;
+use crate::ui::theme;
+use crate::util::png;
+use iced::advanced::renderer::{self, Headless};
+use iced::advanced::widget::Tree;
+use iced::advanced::{Layout, layout, mouse};
+use iced::{Element, Rectangle, Size, Theme};
+use std::cell::RefCell;
+use std::f32::consts::TAU;
+use std::path::Path;
+use std::process::ExitCode;
+
+const SCREENSHOT_SIZE: Size<u32> = Size::new(640, 360);
+const CANNED_RATE: f32 = 48_000.0;
+const CANNED_SECONDS: f32 = 3.0;
+// Frames per ingest, about what a capture batch carries.
+const CANNED_BATCH: usize = 1024;
+
+/// RGBA8 pixels, rows top to bottom.
+#[derive(Debug, Clone)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+impl Image {
+    pub fn to_png(&self) -> Option<Vec<u8>> {
+        png::encode_rgba(self.width, self.height, &self.rgba)
+    }
+}
+
+/// A visual state the offscreen renderer can draw.
+pub trait Snapshot: Sized {
+    fn element<'a, M: 'a>(state: &'a RefCell<Self>) -> Element<'a, M>;
+}
+
+pub struct Offscreen {
+    renderer: iced::Renderer,
+    theme: Theme,
+}
+
+impl Offscreen {
+    /// `None` without a GPU adapter.
+    pub fn new(theme: Theme) -> Option<Self> {
+        let settings = iced::Settings::default();
+        let renderer = iced::futures::executor::block_on(<iced::Renderer as Headless>::new(
+            settings.default_font,
+            settings.default_text_size,
+            Some("wgpu"),
+        ))?;
+        Some(Self { renderer, theme })
+    }
+
+    /// Draws `state` after whatever snapshots it has been given, at `size`
+    /// logical pixels.
+    pub fn render_state<S: Snapshot>(&mut self, state: &RefCell<S>, size: Size<u32>) -> Image {
+        self.render(S::element::<()>(state), size)
+    }
+
+    pub fn render<M>(&mut self, mut element: Element<'_, M>, size: Size<u32>) -> Image {
+        let logical = Size::new(size.width as f32, size.height as f32);
+        let mut tree = Tree::new(element.as_widget());
+        let node = element.as_widget_mut().layout(
+            &mut tree,
+            &self.renderer,
+            &layout::Limits::new(Size::ZERO, logical),
+        );
+        let style = renderer::Style {
+            text_color: self.theme.extended_palette().background.base.text,
+        };
+        element.as_widget().draw(
+            &tree,
+            &mut self.renderer,
+            &self.theme,
+            &style,
+            Layout::new(&node),
+            mouse::Cursor::Unavailable,
+            &Rectangle::with_size(logical),
+        );
+        let background = self.theme.extended_palette().background.base.color;
+        let rgba = self.renderer.screenshot(size, 1.0, background);
+        Image {
+            width: size.width,
+            height: size.height,
+            rgba,
+        }
+    }
+}
+
+// A few seconds of an A major chord over quiet noise, panned a little left,
+// so every visual has something to show.
+fn canned_signal() -> Vec<f32> {
+    let frames = (CANNED_RATE * CANNED_SECONDS) as usize;
+    let mut seed = 0x2545_f491_u32;
+    let mut noise = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        seed as f32 / u32::MAX as f32 * 2.0 - 1.0
+    };
+    (0..frames)
+        .flat_map(|n| {
+            let t = n as f32 / CANNED_RATE;
+            let chord: f32 = [220.0, 277.18, 329.63]
+                .iter()
+                .flat_map(|hz| (1..=4).map(move |k| (TAU * hz * k as f32 * t).sin() / k as f32))
+                .sum();
+            let mono = 0.12 * chord + 0.01 * noise();
+            [mono * 1.1, mono * 0.9]
+        })
+        .collect()
+}
+
+fn screenshot(offscreen: &mut Offscreen, kind: VisualKind, signal: &[f32]) -> Option<Image> {
+    let format = MeterFormat {
+        channels: 2,
+        sample_rate: CANNED_RATE,
+    };
+    let mut module = registry::standalone(kind)?;
+    // A first draw sizes the history to the view before any audio arrives.
+    module.content().snapshot(offscreen, SCREENSHOT_SIZE);
+    for batch in signal.chunks(CANNED_BATCH * format.channels) {
+        module.ingest(batch, format, None);
+    }
+    Some(module.content().snapshot(offscreen, SCREENSHOT_SIZE))
+}
+
+/// `openmeters --screenshots <DIR>`: writes `<visual>.png` for every visual.
+pub fn run(dir: &Path) -> ExitCode {
+    let Some(mut offscreen) = Offscreen::new(theme::theme(None)) else {
+        eprintln!("openmeters: screenshots need a GPU adapter");
+        return ExitCode::FAILURE;
+    };
+    if let Err(err) = std::fs::create_dir_all(dir) {
+        eprintln!("openmeters: cannot create {}: {err}", dir.display());
+        return ExitCode::FAILURE;
+    }
+    let signal = canned_signal();
+    for &kind in VisualKind::ALL {
+        let path = dir.join(format!("{}.png", kind.key()));
+        let written = screenshot(&mut offscreen, kind, &signal)
+            .and_then(|image| image.to_png())
+            .ok_or_else(|| "nothing to draw".to_owned())
+            .and_then(|png| std::fs::write(&path, png).map_err(|err| err.to_string()));
+        match written {
+            Ok(()) => println!("{}", path.display()),
+            Err(err) => {
+                eprintln!("openmeters: cannot write {}: {err}", path.display());
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visuals::options::SpectrumDisplayMode;
+    use crate::visuals::spectrum::SpectrumState;
+    use crate::visuals::spectrum::processor::{SpectrumSnapshot, SpectrumTraceSnapshot};
+
+    // A filled trace peaking at `peak_hz` over a -90 dB floor, or just the
+    // floor, with no peak label to draw beside it.
+    fn spectrum(peak_hz: Option<f32>) -> RefCell<SpectrumState> {
+        let state = RefCell::new(SpectrumState::new());
+        let mut style = state.borrow().export_settings();
+        style.display_mode = SpectrumDisplayMode::Fill;
+        style.show_peak_label = false;
+        state
+            .borrow_mut()
+            .update_view_settings(&style, style.floor_db);
+        let bins: Vec<f32> = (0..512).map(|i| i as f32 * 40.0).collect();
+        let trace: Vec<f32> = bins
+            .iter()
+            .map(|hz| match peak_hz {
+                Some(peak) => -6.0 - ((hz - peak) / 10.0).abs().min(84.0),
+                None => -90.0,
+            })
+            .collect();
+        state.borrow_mut().apply_snapshot(&SpectrumSnapshot {
+            frequency_bins: bins,
+            traces: [[trace.clone(), trace], SpectrumTraceSnapshot::default()],
+            noise_floor: SpectrumTraceSnapshot::default(),
+            overlays: Default::default(),
+            calibrated: false,
+            iq: false,
+        });
+        state
+    }
+
+    #[test]
+    fn draws_a_canned_spectrum_offscreen() {
+        let Some(mut offscreen) = Offscreen::new(Theme::Dark) else {
+            return; // No GPU adapter here.
+        };
+        let size = Size::new(320, 160);
+        let floor = offscreen.render_state(&spectrum(None), size);
+        let state = spectrum(Some(1_000.0));
+        let image = offscreen.render_state(&state, size);
+        assert_eq!((image.width, image.height), (320, 160));
+        assert_eq!(image.rgba.len(), 320 * 160 * 4);
+        assert!(image.to_png().is_some());
+
+        // The column the peak changes most stands where the axis puts 1 kHz.
+        let mut changed = vec![0_usize; size.width as usize];
+        let pixels = image.rgba.chunks_exact(4).zip(floor.rgba.chunks_exact(4));
+        for (index, (px, under)) in pixels.enumerate() {
+            changed[index % size.width as usize] += usize::from(px != under);
+        }
+        let (column, &count) = changed
+            .iter()
+            .enumerate()
+            .max_by_key(|&(_, count)| count)
+            .unwrap();
+        assert!(
+            count > size.height as usize / 2,
+            "the peak rises over half the height"
+        );
+        let bounds = Rectangle::with_size(Size::new(320.0, 160.0));
+        let expected = state.borrow().x_of_freq(bounds, 1_000.0).unwrap();
+        assert!(
+            (column as f32 - expected).abs() <= 4.0,
+            "peak drawn at x = {column}, 1 kHz is at {expected}"
+        );
+    }
+}
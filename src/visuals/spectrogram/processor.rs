@@ -21,12 +21,15 @@
 // 7. D.J. Nelson, "Cross-spectral methods for processing speech",
 //    JASA, vol. 110, no. 5, pp. 2575-2592, Nov 2001.
 
-use crate::dsp::AudioBlock;
+mod refine;
+
+use crate::dsp::calibration::{self, CalibrationCurve};
+use crate::dsp::{AudioBlock, power_spectrum};
 use crate::util::audio::{
-    DB_FLOOR, DEFAULT_SAMPLE_RATE, FrequencyScale, LN_TO_DB, WindowKind,
+    Channel, DB_FLOOR, DEFAULT_SAMPLE_RATE, FrequencyScale, LN_TO_DB, MixdownGains, WindowKind,
     compute_fft_bin_normalization, copy_dc_removed_from_deque,
-    copy_dc_removed_windowed_from_deque, db_to_power, power_to_db, sanitize_sample_rate,
-    window_coefficients,
+    copy_dc_removed_windowed_from_deque, db_to_power, mix_frame, power_to_db,
+    sanitize_sample_rate, window_coefficients,
 };
 use bytemuck::{Pod, Zeroable};
 use realfft::{RealFftPlanner, RealToComplex};
@@ -35,6 +38,8 @@ use rustfft::{Fft, FftPlanner};
 use std::collections::VecDeque;
 use std::sync::Arc;
 
+pub(in crate::visuals) use refine::{MIN_ZOOM as REFINE_MIN_ZOOM, RefineJob};
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable, PartialEq)]
 pub struct SpectrogramPoint {
@@ -43,6 +48,12 @@ pub struct SpectrogramPoint {
     pub magnitude_db: f32,
 }
 
+crate::macros::choice_enum!(all pub enum SpectrogramChannels {
+    #[default] Mono => "Mono",
+    Split => "Split L/R",
+    Difference => "Difference (L-R)",
+});
+
 crate::macros::default_struct! {
     #[derive(Debug, Clone, Copy)]
     pub struct SpectrogramConfig {
@@ -54,20 +65,60 @@ crate::macros::default_struct! {
         pub history_length: usize = 0,
         pub use_reassignment: bool = true,
         pub zero_padding_factor: usize = 1,
+        /// Fixed scroll speed; when positive, hop_size is derived from the sample rate.
+        pub columns_per_second: f32 = 0.0,
+        pub channels: SpectrogramChannels = SpectrogramChannels::Mono,
+        /// Cap on history memory across channels, GPU and CPU together.
+        pub history_memory_mb: u32 = DEFAULT_HISTORY_MEMORY_MB,
     }
 }
 
 const DEFAULT_SPECTROGRAM_FFT_SIZE: usize = 2048;
 const DEFAULT_SPECTROGRAM_HOP_SIZE: usize = 64;
 pub(in crate::visuals) const MAX_SPECTROGRAM_HISTORY_COLUMNS: usize = 8192;
-pub(super) const SPECTROGRAM_HISTORY_BYTE_BUDGET: usize = 128 * 1024 * 1024;
+pub const DEFAULT_HISTORY_MEMORY_MB: u32 = 128;
+pub const MIN_HISTORY_MEMORY_MB: u32 = 32;
+pub const MAX_HISTORY_MEMORY_MB: u32 = 2048;
+// Palette shaping limits, shared by the sliders and the load-time clamps.
+pub const MIN_GAMMA: f32 = 0.25;
+pub const MAX_GAMMA: f32 = 4.0;
+pub const MAX_KNEE: f32 = 8.0;
+pub const MAX_SHARPEN: f32 = 2.0;
+// Bands each column keeps on the CPU for region statistics.
+pub(super) const REGION_MAX_BANDS: usize = 512;
+
+/// Estimated bytes one column of history holds per channel, before any audio
+/// has set the slot size. Reassigned slots are sized to the busiest column,
+/// which rarely reaches half the bins.
+pub fn history_column_bytes(bins: usize, reassigned: bool) -> u64 {
+    let slot = reassigned.then(|| bins.max(1).div_ceil(2) as u32);
+    history_slot_bytes(bins, slot)
+}
+
+/// Bytes one column of history holds per channel: the GPU ring slot, holding
+/// `reassigned_points` points or packed classic bins, plus the band copy, with
+/// each band's peak position, kept for region statistics.
+pub fn history_slot_bytes(bins: usize, reassigned_points: Option<u32>) -> u64 {
+    let bins = bins.max(1) as u64;
+    let gpu = match reassigned_points {
+        Some(points) => u64::from(points.max(1)) * size_of::<SpectrogramPoint>() as u64,
+        None => bins.div_ceil(2) * 4,
+    };
+    let bands = bins.div_ceil(bins.div_ceil(REGION_MAX_BANDS as u64));
+    gpu + bands * 4
+}
 
-// Fixed [dB] storage domain -- must match the shader constants in spectrogram.wgsl.
-// u16 unorm over this range gives ~0.0024 dB/step, decoupled from the live
-// floor/ceiling window so history recolors cleanly on slider drags.
-pub(super) const CLASSIC_DB_STORE_LO: f32 = -144.0;
-pub(super) const CLASSIC_DB_STORE_HI: f32 = 12.0;
-pub(super) const CLASSIC_DB_STORE_RANGE: f32 = CLASSIC_DB_STORE_HI - CLASSIC_DB_STORE_LO;
+/// Bytes each channel's history may take; split mode halves the cap.
+pub fn history_byte_cap(memory_mb: u32, channels: SpectrogramChannels) -> u64 {
+    let histories = 1 + u64::from(channels == SpectrogramChannels::Split);
+    let memory_mb = memory_mb.clamp(MIN_HISTORY_MEMORY_MB, MAX_HISTORY_MEMORY_MB);
+    u64::from(memory_mb) * 1024 * 1024 / histories
+}
+
+/// Columns of history that fit in `cap_bytes`, never more than the widest view.
+pub fn history_columns_within(cap_bytes: u64, column_bytes: u64) -> usize {
+    ((cap_bytes / column_bytes.max(1)) as usize).min(MAX_SPECTROGRAM_HISTORY_COLUMNS)
+}
 
 impl SpectrogramConfig {
     fn normalize(&mut self) {
@@ -79,6 +130,20 @@ impl SpectrogramConfig {
             self.hop_size = DEFAULT_SPECTROGRAM_HOP_SIZE.min(self.fft_size).max(1);
         }
         self.zero_padding_factor = self.zero_padding_factor.max(1);
+        self.history_memory_mb = self
+            .history_memory_mb
+            .clamp(MIN_HISTORY_MEMORY_MB, MAX_HISTORY_MEMORY_MB);
+        self.derive_hop_size();
+    }
+
+    // One column is one pixel on screen, so a fixed column rate keeps the
+    // scroll speed independent of sample rate and FFT size. A hop longer than
+    // the FFT would skip audio, so slow rates scroll faster than asked.
+    fn derive_hop_size(&mut self) {
+        if self.columns_per_second > 0.0 {
+            let hop = (self.sample_rate / self.columns_per_second).round();
+            self.hop_size = hop.clamp(1.0, self.fft_size.max(1) as f32) as usize;
+        }
     }
 }
 
@@ -98,11 +163,17 @@ fn resize_trim<T: Clone>(buf: &mut Vec<T>, len: usize, value: T) {
     }
 }
 
-pub(super) fn pack_classic_db(db: f32) -> u16 {
-    const SCALE: f32 = 65535.0 / CLASSIC_DB_STORE_RANGE;
-    ((db - CLASSIC_DB_STORE_LO) * SCALE)
-        .round()
-        .clamp(0.0, 65535.0) as u16
+// Classic bins ship linear power as bfloat16 (the top half of an f32, rounded
+// to nearest even). The shader widens it and does the dB conversion, so the CPU
+// never takes a log per bin; ~0.03 dB of precision is plenty for display, and
+// power is never negative so codes stay monotonic.
+pub(super) fn pack_classic_power(power: f32) -> u16 {
+    let bits = power.max(0.0).to_bits();
+    ((bits + 0x7fff + ((bits >> 16) & 1)) >> 16) as u16
+}
+
+pub(super) fn unpack_classic_power(code: u16) -> f32 {
+    f32::from_bits(u32::from(code) << 16)
 }
 
 // Correct coherent-gain power for ENBW and zero-padding after splat accumulation.
@@ -126,8 +197,8 @@ impl ReassignmentBuffers {
 
 // Reassigned ships only visible fractional (t, f, mag) splats; bins below
 // the analysis floor are omitted instead of sent as invisible sentinels.
-// Classic ships packed fixed-domain dB per bin; freq is implicit (k * bin_hz)
-// and the renderer fills between adjacent bins.
+// Classic ships bfloat16 linear power per bin; freq is implicit (k * bin_hz)
+// and the shader interpolates between adjacent bins and converts to dB.
 #[derive(Debug, Clone)]
 pub enum SpectrogramColumn {
     Reassigned(Vec<SpectrogramPoint>),
@@ -144,11 +215,17 @@ pub struct SpectrogramUpdate {
     pub reset: bool,
     pub points_per_column: usize,
     pub reassigned_power_scale: f32,
+    /// A mic calibration curve is divided out of the columns.
+    pub calibrated: bool,
     pub new_columns: Vec<SpectrogramColumn>,
+    /// Right-channel columns in split mode, produced in lockstep with the left.
+    pub right: Option<Box<SpectrogramUpdate>>,
 }
 
 pub struct SpectrogramProcessor {
     config: SpectrogramConfig,
+    input: Channel,
+    right: Option<Box<SpectrogramProcessor>>,
     fft: Arc<dyn Fft<f32>>,
     classic_fft: Arc<dyn RealToComplex<f32>>,
     hilbert_fft: Arc<dyn Fft<f32>>,
@@ -161,16 +238,28 @@ pub struct SpectrogramProcessor {
     hilbert_buf: Vec<Complex32>,
     spectrum: Vec<Complex32>,
     scratch: Vec<Complex32>,
+    classic_power: Vec<f32>,
     classic_bins: Vec<u16>,
     reassign: ReassignmentBuffers,
+    window_norm: Vec<f32>,
+    // The window's, with any mic calibration folded in.
     bin_norm: Vec<f32>,
+    calibration: Option<Arc<CalibrationCurve>>,
+    calibrated: bool,
     reassigned_power_scale: f32,
     audio_buffer: VecDeque<f32>,
+    mixdown: MixdownGains,
     pending_skip_samples: usize,
     audio_front_sample: u64,
     audio_last_nonzero: Option<u64>,
     bin_hz: f32,
     reset: bool,
+    // Detail shed to stay within the CPU budget; see `set_quality_cut`.
+    quality_cut: u8,
+    // Raw input kept for zoom refinement, interleaved.
+    recent: VecDeque<f32>,
+    recent_channels: usize,
+    keep_recent: bool,
 }
 
 impl SpectrogramProcessor {
@@ -181,6 +270,8 @@ impl SpectrogramProcessor {
         let classic_fft = RealFftPlanner::new().plan_fft_forward(1024);
         let mut processor = Self {
             config: cfg,
+            input: Channel::Mid,
+            right: None,
             fft: placeholder_fft.clone(),
             classic_fft,
             hilbert_fft: placeholder_fft.clone(),
@@ -193,34 +284,147 @@ impl SpectrogramProcessor {
             hilbert_buf: Vec::new(),
             spectrum: Vec::new(),
             scratch: Vec::new(),
+            classic_power: Vec::new(),
             classic_bins: Vec::new(),
             reassign: ReassignmentBuffers::default(),
+            window_norm: Vec::new(),
             bin_norm: Vec::new(),
+            calibration: None,
+            calibrated: false,
             reassigned_power_scale: 1.0,
             audio_buffer: VecDeque::new(),
+            mixdown: MixdownGains::default(),
             pending_skip_samples: 0,
             audio_front_sample: 0,
             audio_last_nonzero: None,
             bin_hz: 0.0,
             reset: true,
+            quality_cut: 0,
+            recent: VecDeque::new(),
+            recent_channels: 0,
+            keep_recent: false,
         };
         processor.rebuild_fft();
+        processor.sync_channels();
         processor
     }
 
+    // Split mode runs a second chain on the right channel; the nested
+    // processor always sees Mono config so it never nests further.
+    fn sync_channels(&mut self) {
+        let cfg = self.config;
+        self.input = match cfg.channels {
+            SpectrogramChannels::Mono => Channel::Mid,
+            SpectrogramChannels::Split => Channel::Left,
+            SpectrogramChannels::Difference => Channel::Side,
+        };
+        if cfg.channels != SpectrogramChannels::Split {
+            self.right = None;
+            return;
+        }
+        let right_cfg = SpectrogramConfig {
+            channels: SpectrogramChannels::Mono,
+            ..cfg
+        };
+        let right = self
+            .right
+            .get_or_insert_with(|| Box::new(Self::new(right_cfg)));
+        right.update_config(right_cfg);
+        right.set_quality_cut(self.quality_cut);
+        right.set_calibration(self.calibration.clone());
+        right.input = Channel::Right;
+    }
+
     pub fn config(&self) -> SpectrogramConfig {
         self.config
     }
 
+    /// Halves the zero padding once per step of `cut`, then drops
+    /// reassignment; `false` once there is nothing left to shed.
+    pub fn set_quality_cut(&mut self, cut: u8) -> bool {
+        if cut > self.padding_steps() + u8::from(self.config.use_reassignment) {
+            return false;
+        }
+        if let Some(right) = self.right.as_mut() {
+            right.set_quality_cut(cut);
+        }
+        if cut != self.quality_cut {
+            self.quality_cut = cut;
+            self.rebuild_fft();
+            self.reset = true;
+        }
+        true
+    }
+
+    fn padding_steps(&self) -> u8 {
+        self.config.zero_padding_factor.max(1).ilog2() as u8
+    }
+
+    fn reassigning(&self) -> bool {
+        self.config.use_reassignment && self.quality_cut <= self.padding_steps()
+    }
+
+    /// Keeps the last few seconds of input for [`Self::refine_job`].
+    pub fn set_keep_recent(&mut self, keep: bool) {
+        self.keep_recent = keep;
+        if !keep {
+            self.recent = VecDeque::new();
+        }
+    }
+
+    fn remember(&mut self, block: &AudioBlock<'_>) {
+        if !self.keep_recent {
+            return;
+        }
+        if block.channels != self.recent_channels {
+            self.recent.clear();
+            self.recent_channels = block.channels;
+        }
+        self.recent.extend(block.samples);
+        let keep = (refine::KEEP_SECONDS * block.sample_rate) as usize * block.channels;
+        let excess = self.recent.len().saturating_sub(keep);
+        self.recent.drain(..excess);
+    }
+
+    /// Reanalysis of the newest `columns` columns, if enough input was kept.
+    pub fn refine_job(&self, columns: usize) -> Option<RefineJob> {
+        let channels = self.recent_channels.max(1);
+        // Refinement always reassigns, so it reads the Hilbert length.
+        let read = Self::hilbert_len_for(self.window_size);
+        let frames = (columns.checked_sub(1)? * self.config.hop_size + read)
+            .min(self.recent.len() / channels);
+        if frames < read {
+            return None;
+        }
+        let start = self.recent.len() - frames * channels;
+        let samples = self.recent.range(start..).copied().collect();
+        Some(RefineJob::new(samples, channels, self.config, columns))
+    }
+
+    fn zero_padding(&self) -> usize {
+        let halvings = self.quality_cut.min(self.padding_steps());
+        (self.config.zero_padding_factor.max(1) >> halvings).max(1)
+    }
+
+    // Columns are centred in the analysis read, which grows to the Hilbert
+    // length with reassignment.
+    pub fn latency_frames(&self) -> usize {
+        if self.reassigning() {
+            Self::hilbert_len_for(self.window_size) / 2
+        } else {
+            self.window_size / 2
+        }
+    }
+
     fn hilbert_len_for(window_size: usize) -> usize {
         (window_size * 2).next_power_of_two().max(2)
     }
 
     fn rebuild_fft(&mut self) {
         self.window_size = self.config.fft_size;
-        self.fft_size = self.window_size * self.config.zero_padding_factor.max(1);
+        self.fft_size = self.window_size * self.zero_padding();
         let hilbert_len = Self::hilbert_len_for(self.window_size);
-        let use_reassignment = self.config.use_reassignment;
+        let use_reassignment = self.reassigning();
         let active_len = if use_reassignment { hilbert_len } else { self.fft_size };
         let mut planner = FftPlanner::new();
         self.fft = planner.plan_fft_forward(self.fft_size);
@@ -247,8 +451,9 @@ impl SpectrogramProcessor {
             self.classic_fft.get_scratch_len()
         };
         resize_trim(&mut self.scratch, scratch_len, Complex32::ZERO);
+        resize_trim(&mut self.classic_power, bin_count, 0.0);
         resize_trim(&mut self.classic_bins, bin_count, 0);
-        self.bin_norm = compute_fft_bin_normalization(&self.window, self.fft_size);
+        self.window_norm = compute_fft_bin_normalization(&self.window, self.fft_size);
         self.reassigned_power_scale = if use_reassignment {
             self.reassign.rebuild(&mut planner, &self.window, bin_count);
             reassigned_power_scale(&self.window, self.fft_size)
@@ -261,23 +466,42 @@ impl SpectrogramProcessor {
         self.pending_skip_samples = 0;
         self.shrink_audio_buffer(buffered_len);
         self.bin_hz = self.config.sample_rate / self.fft_size.max(1) as f32;
+        self.apply_calibration();
+    }
+
+    /// Per-channel weights the Mid input mixes with.
+    pub fn set_mixdown(&mut self, weights: &[f32], exclude_lfe: bool) {
+        self.mixdown.set_weights(weights, exclude_lfe);
+    }
+
+    /// Divides the mic's response out of the columns; `None` turns it off.
+    pub fn set_calibration(&mut self, curve: Option<Arc<CalibrationCurve>>) {
+        if let Some(right) = self.right.as_mut() {
+            right.set_calibration(curve.clone());
+        }
+        if self.calibration != curve {
+            self.calibration = curve;
+            self.apply_calibration();
+        }
+    }
+
+    fn apply_calibration(&mut self) {
+        let curve = self.calibration.as_deref();
+        self.calibrated =
+            calibration::apply_to_bins(curve, &self.window_norm, self.bin_hz, &mut self.bin_norm);
     }
 
     fn max_retained_columns(&self, bin_count: usize) -> usize {
-        let reassigned = self.config.use_reassignment;
-        let stride = if reassigned {
-            bin_count.saturating_mul(std::mem::size_of::<SpectrogramPoint>())
-        } else {
-            bin_count.div_ceil(2).saturating_mul(4)
-        };
-        let max_cols = SPECTROGRAM_HISTORY_BYTE_BUDGET * (1 + usize::from(reassigned)) / stride.max(1);
+        let cap = history_byte_cap(self.config.history_memory_mb, self.config.channels);
+        let column = history_column_bytes(bin_count, self.reassigning());
+        let max_cols = history_columns_within(cap, column);
         self.config.history_length.clamp(1, MAX_SPECTROGRAM_HISTORY_COLUMNS).min(max_cols)
     }
 
     fn process_ready_windows(&mut self) -> Vec<SpectrogramColumn> {
         if self.window_size == 0 { return Vec::new(); }
         let (hop_size, sample_rate) = (self.config.hop_size, self.config.sample_rate);
-        let reassignment_enabled = self.config.use_reassignment && sample_rate > f32::EPSILON;
+        let reassignment_enabled = self.reassigning() && sample_rate > f32::EPSILON;
         let bin_count = self.fft_size / 2 + 1;
 
         let (read_len, center_offset) = if reassignment_enabled {
@@ -303,7 +527,7 @@ impl SpectrogramProcessor {
                 let col = if reassignment_enabled {
                     SpectrogramColumn::Reassigned(Vec::new())
                 } else {
-                    self.classic_bins[..bin_count].fill(pack_classic_db(DB_FLOOR));
+                    self.classic_bins[..bin_count].fill(0);
                     SpectrogramColumn::Classic(self.classic_bins[..bin_count].to_vec())
                 };
                 output.push(col);
@@ -353,19 +577,17 @@ impl SpectrogramProcessor {
                     &self.window,
                 );
                 self.real[self.window_size..].fill(0.0);
-                if self
-                    .classic_fft
-                    .process_with_scratch(
-                        &mut self.real,
-                        &mut self.spectrum,
-                        &mut self.scratch,
-                    )
-                    .is_err()
-                {
+                if !power_spectrum(
+                    &*self.classic_fft,
+                    &mut self.real,
+                    &mut self.spectrum,
+                    &mut self.scratch,
+                    &mut self.classic_power,
+                ) {
                     break;
                 }
                 Self::compute_classic_bins(
-                    &self.spectrum,
+                    &self.classic_power,
                     &self.bin_norm,
                     &mut self.classic_bins,
                 );
@@ -420,7 +642,7 @@ impl SpectrogramProcessor {
             return;
         }
 
-        if channels == 1 {
+        if channels == 1 && matches!(self.input, Channel::Left | Channel::Right | Channel::Mid) {
             let base = self.audio_front_sample + self.audio_buffer.len() as u64;
             if let Some(i) = samples.iter().rposition(|&sample| sample != 0.0) {
                 self.audio_last_nonzero = Some(base + i as u64);
@@ -430,9 +652,22 @@ impl SpectrogramProcessor {
         }
 
         self.audio_buffer.reserve(samples.len() / channels);
-        let inv = 1.0 / channels as f32;
+        let input = self.input;
+        let gains: &[f32] = match input {
+            Channel::Mid | Channel::None => self.mixdown.for_channels(channels),
+            _ => &[],
+        };
         for frame in samples.chunks_exact(channels) {
-            let sample = frame.iter().sum::<f32>() * inv;
+            let right = || frame.get(1).copied().unwrap_or(frame[0]);
+            let sample = match input {
+                Channel::Left => frame[0],
+                Channel::Right => right(),
+                Channel::Side => (frame[0] - right()) * 0.5,
+                Channel::Sum => frame[0] + right(),
+                Channel::Difference => frame[0] - right(),
+                Channel::Product => frame[0] * right(),
+                Channel::Mid | Channel::None => mix_frame(frame, gains),
+            };
             if sample != 0.0 {
                 self.audio_last_nonzero =
                     Some(self.audio_front_sample + self.audio_buffer.len() as u64);
@@ -441,10 +676,9 @@ impl SpectrogramProcessor {
         }
     }
 
-    fn compute_classic_bins(spectrum: &[Complex32], bin_norm: &[f32], bins: &mut [u16]) {
-        for (i, c) in spectrum.iter().enumerate() {
-            let power = (c.re * c.re + c.im * c.im) * bin_norm[i];
-            bins[i] = pack_classic_db(power_to_db(power, DB_FLOOR));
+    fn compute_classic_bins(power: &[f32], bin_norm: &[f32], bins: &mut [u16]) {
+        for ((bin, &power), &norm) in bins.iter_mut().zip(power).zip(bin_norm) {
+            *bin = pack_classic_power(power * norm);
         }
     }
 
@@ -496,15 +730,23 @@ impl SpectrogramProcessor {
         let sample_rate = block.sample_rate;
         if self.config.sample_rate != sample_rate {
             self.config.sample_rate = sample_rate;
+            self.config.derive_hop_size();
             self.rebuild_fft();
             self.audio_buffer.clear();
             self.audio_front_sample = 0;
             self.audio_last_nonzero = None;
+            self.recent.clear();
             self.reset = true;
         }
+        self.remember(block);
         self.push_audio(block.samples, block.channels);
         let cols = self.process_ready_windows();
         let bin_count = self.fft_size / 2 + 1;
+        let right = self
+            .right
+            .as_mut()
+            .and_then(|right| right.process_block(block))
+            .map(Box::new);
         if cols.is_empty() {
             None
         } else {
@@ -517,7 +759,9 @@ impl SpectrogramProcessor {
                 reset: std::mem::take(&mut self.reset),
                 points_per_column: bin_count,
                 reassigned_power_scale: self.reassigned_power_scale,
+                calibrated: self.calibrated,
                 new_columns: cols,
+                right,
             })
         }
     }
@@ -546,7 +790,13 @@ impl SpectrogramProcessor {
         if hop_changed {
             self.pending_skip_samples = 0;
         }
-        self.reset |= rebuild || hop_changed;
+        let channels_changed = prev.channels != cfg.channels;
+        if channels_changed {
+            self.audio_buffer.clear();
+            self.audio_last_nonzero = None;
+        }
+        self.sync_channels();
+        self.reset |= rebuild || hop_changed || channels_changed;
     }
 }
 
@@ -693,10 +943,18 @@ mod tests {
     }
 
     #[test]
-    fn classic_db_packing_rounds_to_nearest_code() {
-        let step = CLASSIC_DB_STORE_RANGE / 65535.0;
-        assert_eq!(pack_classic_db(CLASSIC_DB_STORE_LO + step * 1234.49), 1234);
-        assert_eq!(pack_classic_db(CLASSIC_DB_STORE_LO + step * 1234.50), 1235);
+    fn classic_power_packing_rounds_to_nearest_bfloat16() {
+        let widen = |code: u16| f32::from_bits(u32::from(code) << 16);
+        assert_eq!(pack_classic_power(1.0), 0x3f80);
+        assert_eq!(pack_classic_power(f32::from_bits(0x3f80_7fff)), 0x3f80);
+        assert_eq!(pack_classic_power(f32::from_bits(0x3f80_8000)), 0x3f80);
+        assert_eq!(pack_classic_power(f32::from_bits(0x3f81_8000)), 0x3f82);
+        assert_eq!(pack_classic_power(-1.0), 0);
+
+        for db in [-140.0, -96.5, -12.25, 0.0, 6.0] {
+            let decoded = power_to_db(widen(pack_classic_power(db_to_power(db))), DB_FLOOR);
+            assert!((decoded - db).abs() < 0.02, "{db} -> {decoded}");
+        }
     }
 
     #[test]
@@ -730,7 +988,7 @@ mod tests {
         assert_eq!(update.points_per_column, cfg.fft_size / 2 + 1);
         assert_eq!(mags.len(), update.points_per_column);
         assert_eq!(idx, 200);
-        assert!(mags[idx] >= pack_classic_db(-0.01));
+        assert!(mags[idx] >= pack_classic_power(db_to_power(-0.01)));
     }
 
     #[test]
@@ -791,11 +1049,19 @@ mod tests {
         });
         let bins = processor.fft_size / 2 + 1;
         let packed_stride = bins.div_ceil(2) * std::mem::size_of::<u32>();
+        let column = packed_stride + bins.div_ceil(bins.div_ceil(REGION_MAX_BANDS)) * 4;
+        assert_eq!(history_column_bytes(bins, false), column as u64);
 
-        assert_eq!(
-            processor.max_retained_columns(bins),
-            SPECTROGRAM_HISTORY_BYTE_BUDGET / packed_stride
-        );
+        let cap = DEFAULT_HISTORY_MEMORY_MB as usize * 1024 * 1024;
+        assert_eq!(processor.max_retained_columns(bins), cap / column);
+
+        // A tighter cap keeps less; split mode shares it between channels.
+        let tight = SpectrogramProcessor::new(SpectrogramConfig {
+            history_memory_mb: 32,
+            channels: SpectrogramChannels::Split,
+            ..processor.config
+        });
+        assert_eq!(tight.max_retained_columns(bins), 16 * 1024 * 1024 / column);
     }
 
     #[test]
@@ -813,6 +1079,59 @@ mod tests {
         assert_eq!(processor.bin_hz, next.sample_rate / processor.fft_size as f32);
     }
 
+    #[test]
+    fn fixed_column_rate_tracks_sample_rate() {
+        let mut processor = SpectrogramProcessor::new(SpectrogramConfig {
+            columns_per_second: 100.0,
+            ..cfg(1024, 64, false)
+        });
+        assert_eq!(processor.config.hop_size, 480);
+
+        let update = processor
+            .process_block(&AudioBlock::new(&vec![0.0; 4096], 1, 96_000.0))
+            .expect("expected snapshot");
+        assert_eq!(update.hop_size, 960);
+
+        let mut next = processor.config();
+        next.columns_per_second = 10.0;
+        processor.update_config(next);
+        assert_eq!(processor.config.hop_size, 1024);
+
+        let mut next = processor.config();
+        next.columns_per_second = 0.0;
+        next.hop_size = 128;
+        processor.update_config(next);
+        assert_eq!(processor.config.hop_size, 128);
+    }
+
+    #[test]
+    fn split_channels_run_a_right_chain_in_lockstep() {
+        let mut processor = SpectrogramProcessor::new(SpectrogramConfig {
+            channels: SpectrogramChannels::Split,
+            ..cfg(64, 16, false)
+        });
+        let tone = sine(3000.0, DEFAULT_SAMPLE_RATE, 256);
+        let stereo: Vec<f32> = tone.iter().flat_map(|&s| [s, 0.0]).collect();
+        let update = processor
+            .process_block(&AudioBlock::new(&stereo, 2, DEFAULT_SAMPLE_RATE))
+            .expect("expected snapshot");
+        let right = update.right.as_deref().expect("expected right channel");
+
+        assert_eq!(update.new_columns.len(), right.new_columns.len());
+        let left_peak = classic_mags(&update.new_columns[0]);
+        let right_peak = classic_mags(&right.new_columns[0]);
+        assert!(left_peak[peak_bin(left_peak)] > right_peak[peak_bin(left_peak)]);
+
+        let mut next = processor.config();
+        next.channels = SpectrogramChannels::Difference;
+        processor.update_config(next);
+        assert!(processor.right.is_none());
+        let update = processor
+            .process_block(&AudioBlock::new(&stereo, 2, DEFAULT_SAMPLE_RATE))
+            .expect("expected snapshot");
+        assert!(update.right.is_none());
+    }
+
     #[test]
     fn fft_rebuild_keeps_newest_pending_audio() {
         let mut p = SpectrogramProcessor::new(cfg(64, 16, false));
@@ -828,14 +1147,13 @@ mod tests {
     #[test]
     fn silent_input_advances_transparent_columns() {
         let samples = vec![0.0; 192];
-        let floor = pack_classic_db(DB_FLOOR);
 
         let classic = process_samples(cfg(64, 16, false), &samples);
         assert_eq!(classic.new_columns.len(), 4);
         assert!(classic
             .new_columns
             .iter()
-            .all(|col| classic_mags(col).iter().all(|&mag| mag == floor)));
+            .all(|col| classic_mags(col).iter().all(|&mag| mag == 0)));
 
         let reassigned = process_samples(cfg(64, 16, true), &samples);
         assert_eq!(reassigned.new_columns.len(), 4);
@@ -879,4 +1197,31 @@ mod tests {
             assert!(points.len() < update.points_per_column);
         }
     }
+
+    #[test]
+    fn quality_cuts_halve_the_transform_but_not_the_config() {
+        let mut processor = SpectrogramProcessor::new(SpectrogramConfig {
+            zero_padding_factor: 4,
+            ..cfg(1024, 256, false)
+        });
+        assert_eq!(processor.fft_size, 4096);
+        assert!(processor.set_quality_cut(1));
+        assert_eq!(processor.fft_size, 2048);
+        assert!(processor.set_quality_cut(2));
+        assert_eq!(processor.fft_size, 1024);
+        assert!(!processor.set_quality_cut(3));
+        assert_eq!(processor.config().zero_padding_factor, 4);
+        assert!(processor.set_quality_cut(0));
+        assert_eq!(processor.fft_size, 4096);
+    }
+
+    #[test]
+    fn default_settings_can_shed_reassignment() {
+        let mut processor = SpectrogramProcessor::new(SpectrogramConfig::default());
+        let reassigned = processor.latency_frames();
+        assert!(processor.set_quality_cut(1));
+        assert!(processor.latency_frames() < reassigned);
+        assert!(processor.config().use_reassignment);
+        assert!(!processor.set_quality_cut(2));
+    }
 }
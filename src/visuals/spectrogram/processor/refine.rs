@@ -0,0 +1,117 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// One-off reanalysis of the newest columns with finer zero padding, run off
+// the UI thread once the view is zoomed far enough for bin spacing to show.
+
+use super::{SpectrogramConfig, SpectrogramProcessor, SpectrogramUpdate};
+use crate::dsp::AudioBlock;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use tracing::warn;
+
+/// Frequency zoom from which the view is refined.
+pub(in crate::visuals) const MIN_ZOOM: f32 = 4.0;
+/// Input kept for refinement.
+pub(super) const KEEP_SECONDS: f32 = 8.0;
+const PADDING_GAIN: usize = 4;
+const MAX_PADDING: usize = 32;
+
+pub(in crate::visuals) struct RefineJob {
+    samples: Vec<f32>,
+    channels: usize,
+    config: SpectrogramConfig,
+    columns: usize,
+}
+
+impl RefineJob {
+    pub(super) fn new(
+        samples: Vec<f32>,
+        channels: usize,
+        config: SpectrogramConfig,
+        columns: usize,
+    ) -> Self {
+        Self {
+            samples,
+            channels,
+            config,
+            columns,
+        }
+    }
+
+    /// The update arrives once on the returned channel; dropping it abandons the result.
+    pub(in crate::visuals) fn spawn(self) -> Option<Receiver<SpectrogramUpdate>> {
+        let (sender, receiver) = mpsc::sync_channel(1);
+        thread::Builder::new()
+            .name("openmeters-refine".into())
+            .spawn(move || {
+                if let Some(update) = self.run() {
+                    let _ = sender.send(update);
+                }
+            })
+            .inspect_err(|err| warn!("[spectrogram] failed to spawn refinement: {err}"))
+            .ok()?;
+        Some(receiver)
+    }
+
+    fn run(self) -> Option<SpectrogramUpdate> {
+        let padding = self.config.zero_padding_factor.max(1) * PADDING_GAIN;
+        let config = SpectrogramConfig {
+            zero_padding_factor: padding.min(MAX_PADDING),
+            use_reassignment: true,
+            history_length: self.columns,
+            ..self.config
+        };
+        let mut processor = SpectrogramProcessor::new(config);
+        let block = AudioBlock::new(&self.samples, self.channels, config.sample_rate);
+        let mut update = processor.process_block(&block)?;
+        keep_newest(&mut update, self.columns);
+        Some(update)
+    }
+}
+
+fn keep_newest(update: &mut SpectrogramUpdate, columns: usize) {
+    let excess = update.new_columns.len().saturating_sub(columns);
+    update.new_columns.drain(..excess);
+    update.history_length = update.new_columns.len();
+    update.reset = true;
+    if let Some(right) = update.right.as_mut() {
+        keep_newest(right, columns);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visuals::spectrogram::processor::SpectrogramColumn;
+
+    #[test]
+    fn refines_the_newest_columns_with_finer_bins() {
+        let config = SpectrogramConfig {
+            sample_rate: 48_000.0,
+            fft_size: 1024,
+            hop_size: 256,
+            zero_padding_factor: 2,
+            ..Default::default()
+        };
+        let mut live = SpectrogramProcessor::new(config);
+        live.set_keep_recent(true);
+        let tone: Vec<f32> = (0..48_000)
+            .map(|i| (i as f32 * 1000.0 * std::f32::consts::TAU / 48_000.0).sin() * 0.5)
+            .collect();
+        live.process_block(&AudioBlock::new(&tone, 1, 48_000.0));
+
+        let update = live.refine_job(40).unwrap().run().unwrap();
+        assert_eq!(update.new_columns.len(), 40);
+        assert_eq!(update.history_length, 40);
+        assert_eq!(update.fft_size, 1024 * 8);
+        let SpectrogramColumn::Reassigned(points) = &update.new_columns[39] else {
+            panic!("refinement always reassigns");
+        };
+        let loudest = points
+            .iter()
+            .max_by(|a, b| a.magnitude_db.total_cmp(&b.magnitude_db))
+            .unwrap();
+        assert!((loudest.freq_hz - 1000.0).abs() < 2.0, "{}", loudest.freq_hz);
+    }
+}
@@ -15,10 +15,14 @@ use crate::visuals::render::common::{
 
 use super::processor::SpectrogramPoint;
 use crate::util::audio::FrequencyScale;
+use crate::visuals::options::SpectrogramInterpolation;
+use crate::util::color::ColorBlend;
 
 pub const SPECTROGRAM_PALETTE_SIZE: usize = 5;
 
 const ACCUM_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rg16Float;
+// Largest single ring write when batching adjacent columns.
+const UPLOAD_BATCH_BYTES: usize = 4 << 20;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ColumnKind {
@@ -61,12 +65,24 @@ pub struct SpectrogramParams {
     pub palette: [[f32; 4]; SPECTROGRAM_PALETTE_SIZE],
     pub stop_positions: [f32; SPECTROGRAM_PALETTE_SIZE],
     pub stop_spreads: [f32; SPECTROGRAM_PALETTE_SIZE],
-    pub contrast: f32,
+    pub palette_blend: ColorBlend,
+    pub gamma: f32,
+    pub knee: f32,
+    pub interpolation: SpectrogramInterpolation,
+    pub sharpen: f32,
     pub floor_db: f32,
     pub ceiling_db: f32,
     pub tilt_db: f32,
     pub uv_y_range: [f32; 2],
     pub rotation: i8,
+    /// Columns still sliding in from the newest edge, plus any time pan back
+    /// into the history; may be fractional.
+    pub scroll_lag: f32,
+    /// Logical pixels per column along the time axis; 1 unless time-zoomed.
+    pub time_scale: f32,
+    pub opacity: f32,
+    /// Beat-pulse brightening, `0..=1`.
+    pub pulse: f32,
 }
 
 pub struct SpectrogramPrimitive {
@@ -259,7 +275,7 @@ struct Uniforms {
     scale_factor: f32,
     floor_db: f32,
     ceiling_db: f32,
-    contrast: f32,
+    gamma: f32,
     tilt_db: f32,
     newest_col: u32,
     inv_uv_range: f32,
@@ -272,15 +288,35 @@ struct Uniforms {
     // Stops 0 and 4 are constant 0.0 / 1.0 and live in the shader.
     stops: [[f32; 4]; 2],
     palette: [[f32; 4]; SPECTROGRAM_PALETTE_SIZE],
+    knee: f32,
+    // 0 = sRGB, 1 = Oklab, 2 = LCh; see `ColorBlend`.
+    blend: u32,
+    opacity: f32,
+    // 0 = nearest, 1 = linear, 2 = cubic; see `SpectrogramInterpolation`.
+    interpolation: u32,
+    sharpen: f32,
+    pulse: f32,
+    scroll_lag: f32,
+    time_scale: f32,
 }
 
 // Locks layout to what the WGSL Uniforms struct expects. Stops must land at
-// offset 112 (16-aligned for array<vec4>), palette at 144, total 224 bytes.
-const _: () = assert!(std::mem::size_of::<Uniforms>() == 224);
+// offset 112 (16-aligned for array<vec4>), palette at 144, knee at 224, blend
+// at 228, opacity at 232, interpolation at 236, sharpen at 240, pulse at 244,
+// scroll_lag at 248, time_scale at 252, and the total is 256 bytes.
+const _: () = assert!(std::mem::size_of::<Uniforms>() == 256);
 const _: () = assert!(std::mem::offset_of!(Uniforms, accum_size) == 100);
 const _: () = assert!(std::mem::offset_of!(Uniforms, reassigned_power_scale) == 108);
 const _: () = assert!(std::mem::offset_of!(Uniforms, stops) == 112);
 const _: () = assert!(std::mem::offset_of!(Uniforms, palette) == 144);
+const _: () = assert!(std::mem::offset_of!(Uniforms, knee) == 224);
+const _: () = assert!(std::mem::offset_of!(Uniforms, blend) == 228);
+const _: () = assert!(std::mem::offset_of!(Uniforms, opacity) == 232);
+const _: () = assert!(std::mem::offset_of!(Uniforms, interpolation) == 236);
+const _: () = assert!(std::mem::offset_of!(Uniforms, sharpen) == 240);
+const _: () = assert!(std::mem::offset_of!(Uniforms, pulse) == 244);
+const _: () = assert!(std::mem::offset_of!(Uniforms, scroll_lag) == 248);
+const _: () = assert!(std::mem::offset_of!(Uniforms, time_scale) == 252);
 
 impl Uniforms {
     fn from_params(p: &SpectrogramParams, viewport: [f32; 2], scale_factor: f32) -> Self {
@@ -296,7 +332,9 @@ impl Uniforms {
         let sf = scale_factor.max(1.0);
         let hl = p.ring_capacity.max(1);
         let newest_col = (p.write_slot + hl - 1) % hl;
-        let inv_uv_range = 1.0 / (p.uv_y_range[1] - p.uv_y_range[0]).max(1e-12);
+        // Signed: an inverted frequency axis arrives as a reversed window.
+        let uv_span = p.uv_y_range[1] - p.uv_y_range[0];
+        let inv_uv_range = 1.0 / uv_span.abs().max(1e-12).copysign(uv_span);
         let col_stride_u16 = p.points_per_column.div_ceil(2) * 2;
         let acc_sz = accum_size(p.bounds, p.rotation, sf);
         Self {
@@ -324,7 +362,7 @@ impl Uniforms {
             scale_factor: sf,
             floor_db: p.floor_db,
             ceiling_db: p.ceiling_db,
-            contrast: p.contrast.max(0.01),
+            gamma: p.gamma.max(0.01),
             tilt_db: p.tilt_db,
             newest_col,
             inv_uv_range,
@@ -347,6 +385,22 @@ impl Uniforms {
                 ],
             ],
             palette,
+            knee: p.knee.max(0.0),
+            blend: match p.palette_blend {
+                ColorBlend::Srgb => 0,
+                ColorBlend::Oklab => 1,
+                ColorBlend::Lch => 2,
+            },
+            opacity: p.opacity,
+            interpolation: match p.interpolation {
+                SpectrogramInterpolation::Nearest => 0,
+                SpectrogramInterpolation::Linear => 1,
+                SpectrogramInterpolation::Cubic => 2,
+            },
+            sharpen: p.sharpen.max(0.0),
+            pulse: p.pulse,
+            scroll_lag: p.scroll_lag.max(0.0),
+            time_scale: p.time_scale.max(1.0),
         }
     }
 }
@@ -606,7 +660,7 @@ struct Resources {
     uniform_cache: Uniforms,
     ring: ColumnRing,
     accum: Option<AccumTarget>,
-    classic_upload_scratch: Vec<u16>,
+    upload_scratch: Vec<u8>,
 }
 
 impl Resources {
@@ -630,7 +684,7 @@ impl Resources {
             uniform_cache: Uniforms::zeroed(),
             ring,
             accum: None,
-            classic_upload_scratch: Vec::new(),
+            upload_scratch: Vec::new(),
         }
     }
 
@@ -745,40 +799,13 @@ impl Resources {
     fn upload_pending(&mut self, queue: &wgpu::Queue, p: &SpectrogramParams) {
         let stride = col_byte_stride(p.col_kind, stored_points_per_col(p));
         let ring_buf = &self.ring.buf;
-        let write = |slot: u32, data: &[u8]| {
-            queue.write_buffer(ring_buf, slot as u64 * stride, data);
-        };
-        match p.col_kind {
-            ColumnKind::Reassigned => {
-                let point_stride =
-                    (stride / std::mem::size_of::<SpectrogramPoint>() as u64) as usize;
-                for upload in &p.pending_uploads {
-                    if let PendingUpload::Reassigned { slot, points } = upload
-                        && !points.is_empty()
-                    {
-                        let written = points.len().min(point_stride);
-                        write(*slot, bytemuck::cast_slice(&points[..written]));
-                    }
-                }
-            }
-            ColumnKind::Classic => {
-                let u16_stride = (stride / 2) as usize;
-                self.classic_upload_scratch.resize(u16_stride, 0);
-                let packed = &mut self.classic_upload_scratch;
-                for upload in &p.pending_uploads {
-                    if let PendingUpload::Classic { slot, mags } = upload
-                        && !mags.is_empty()
-                    {
-                        let written = mags.len().min(u16_stride);
-                        packed[..written].copy_from_slice(&mags[..written]);
-                        if written < u16_stride {
-                            packed[written..].fill(0);
-                        }
-                        write(*slot, bytemuck::cast_slice(packed));
-                    }
-                }
-            }
-        }
+        batch_uploads(
+            &p.pending_uploads,
+            p.col_kind,
+            stride as usize,
+            &mut self.upload_scratch,
+            |slot, data| queue.write_buffer(ring_buf, slot as u64 * stride, data),
+        );
     }
 
     fn write_uniforms(
@@ -797,6 +824,64 @@ impl Resources {
 
 }
 
+// Runs of adjacent slots go up as one write, so a burst of columns costs a
+// handful of queue writes rather than one each. Empty columns and the ring
+// wrapping break a run. Columns are zero-padded to the stride only where the
+// next one follows; a run's last column is padded only for classic
+// magnitudes, which must clear the slot's stale tail, while point columns
+// are bounded by their slot count.
+fn batch_uploads(
+    uploads: &VecDeque<PendingUpload>,
+    kind: ColumnKind,
+    stride: usize,
+    scratch: &mut Vec<u8>,
+    mut write: impl FnMut(u32, &[u8]),
+) {
+    if stride == 0 {
+        return;
+    }
+    let mut flush = |start: u32, scratch: &mut Vec<u8>| {
+        if kind == ColumnKind::Classic {
+            scratch.resize(scratch.len().next_multiple_of(stride), 0);
+        }
+        write(start, scratch);
+        scratch.clear();
+    };
+    scratch.clear();
+    let mut start = 0;
+    for upload in uploads {
+        let (slot, data): (u32, &[u8]) = match (kind, upload) {
+            (ColumnKind::Reassigned, PendingUpload::Reassigned { slot, points }) => {
+                (*slot, bytemuck::cast_slice(points))
+            }
+            (ColumnKind::Classic, PendingUpload::Classic { slot, mags }) => {
+                (*slot, bytemuck::cast_slice(mags))
+            }
+            _ => continue,
+        };
+        let columns = scratch.len().div_ceil(stride);
+        if !scratch.is_empty()
+            && (data.is_empty()
+                || slot != start + columns as u32
+                || scratch.len() >= UPLOAD_BATCH_BYTES)
+        {
+            flush(start, scratch);
+        }
+        if data.is_empty() {
+            continue;
+        }
+        if scratch.is_empty() {
+            start = slot;
+        } else {
+            scratch.resize(columns * stride, 0);
+        }
+        scratch.extend_from_slice(&data[..data.len().min(stride)]);
+    }
+    if !scratch.is_empty() {
+        flush(start, scratch);
+    }
+}
+
 fn create_ring(
     device: &wgpu::Device,
     bgls: Bgls<'_>,
@@ -857,3 +942,62 @@ fn make_bind_group(
         entries: &entries,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adjacent_columns_share_one_write_until_a_gap() {
+        let column = |slot, mags: &[u16]| PendingUpload::Classic {
+            slot,
+            mags: mags.to_vec(),
+        };
+        let uploads = VecDeque::from([
+            column(6, &[1, 2]),
+            column(7, &[3]),
+            // The ring wraps.
+            column(0, &[4, 5]),
+            column(1, &[]),
+            column(2, &[6, 7]),
+        ]);
+        let (mut scratch, mut writes) = (Vec::new(), Vec::new());
+        batch_uploads(&uploads, ColumnKind::Classic, 4, &mut scratch, |slot, data| {
+            let mags = data.chunks_exact(2).map(|b| u16::from_ne_bytes([b[0], b[1]]));
+            writes.push((slot, mags.collect::<Vec<_>>()));
+        });
+        assert_eq!(
+            writes,
+            [(6, vec![1, 2, 3, 0]), (0, vec![4, 5]), (2, vec![6, 7])]
+        );
+
+        writes.clear();
+        batch_uploads(&uploads, ColumnKind::Reassigned, 4, &mut scratch, |slot, _| {
+            writes.push((slot, Vec::new()));
+        });
+        assert!(writes.is_empty());
+
+        // Point columns are padded between columns of a run, never after it.
+        let point = |freq_hz| SpectrogramPoint {
+            time_offset: 0.0,
+            freq_hz,
+            magnitude_db: 0.0,
+        };
+        let uploads = VecDeque::from([
+            PendingUpload::Reassigned {
+                slot: 3,
+                points: vec![point(100.0)],
+            },
+            PendingUpload::Reassigned {
+                slot: 4,
+                points: vec![point(200.0)],
+            },
+        ]);
+        let size = size_of::<SpectrogramPoint>();
+        let mut sizes = Vec::new();
+        batch_uploads(&uploads, ColumnKind::Reassigned, size * 2, &mut scratch, |slot, data| {
+            sizes.push((slot, data.len()));
+        });
+        assert_eq!(sizes, [(3, size * 3)]);
+    }
+}
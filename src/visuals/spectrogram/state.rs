@@ -1,35 +1,51 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // Copyright (C) 2026 Maika Namuo
 
+mod region;
+
 use super::processor::{
-    MAX_SPECTROGRAM_HISTORY_COLUMNS, SPECTROGRAM_HISTORY_BYTE_BUDGET, SpectrogramColumn,
-    SpectrogramConfig, SpectrogramUpdate,
+    MAX_GAMMA, MAX_HISTORY_MEMORY_MB, MAX_KNEE, MAX_SHARPEN, MAX_SPECTROGRAM_HISTORY_COLUMNS,
+    MIN_GAMMA, MIN_HISTORY_MEMORY_MB, REFINE_MIN_ZOOM, RefineJob, SpectrogramColumn,
+    SpectrogramConfig, SpectrogramUpdate, history_byte_cap, history_column_bytes,
+    history_columns_within, history_slot_bytes, unpack_classic_power,
 };
 use super::render::{
     ColumnKind, PendingUpload, RingCopyPlan, SPECTROGRAM_PALETTE_SIZE, SpectrogramParams,
-    SpectrogramPrimitive, col_byte_stride,
+    SpectrogramPrimitive,
 };
-use crate::persistence::settings::SpectrogramSettings;
+use crate::dsp::pitch::PitchTracker;
+use crate::dsp::steady_tones::SteadyTones;
+use crate::persistence::settings::{DisplayOverride, SpectrogramSettings};
 use crate::ui::{scroll_delta_lines, theme};
 use crate::util::{
-    audio::musical::{MusicalNote, NoteInfo},
-    audio::{DB_FLOOR, fmt_duration, fmt_freq, sanitize_negative_db},
-    color::{color_to_rgba, lerp_color, rgba_with_alpha, with_alpha},
+    audio::musical::{MusicalNote, NoteInfo, Tuning},
+    audio::{
+        DB_FLOOR, DbRange, LevelScale, NumberFormat, db_to_power, power_to_db,
+        sanitize_negative_db,
+    },
+    color::{
+        ColorBlend, color_to_rgba, lerp_color, mix_color, rgba_with_alpha, stop_segment,
+        with_alpha,
+    },
 };
 use crate::visuals::options::PianoRollOverlay;
 use crate::visuals::palettes;
-use crate::visuals::render::common::{fill_bordered_rect, fill_rect, make_text, measure_text};
-use iced::advanced::renderer;
+use crate::visuals::render::common::{
+    draw_badge, draw_frequency_markers, draw_shader, faded, fill_bordered_rect, fill_rect,
+    make_text, measure_text, software_rendering,
+};
+use iced::advanced::{clipboard, renderer};
 use iced::advanced::text::Renderer as _;
 use iced::advanced::widget::{Tree, tree};
 use iced::advanced::{Layout, Renderer as _, Widget, layout, mouse};
-use iced::{Color, Element, Length, Point, Rectangle, Size, keyboard};
-use iced_wgpu::primitive::Renderer as _;
+use iced::{Color, Element, Length, Point, Rectangle, Size, keyboard, window};
+use region::{ColumnStore, RegionStats};
 use std::cell::RefCell;
 use std::collections::VecDeque;
 use std::sync::Arc;
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::time::{Duration, Instant};
 
-const DB_CEILING: f32 = 0.0;
 const TOOLTIP_SIZE: f32 = 14.0;
 const TOOLTIP_PAD: f32 = 8.0;
 const TOOLTIP_GAP: f32 = 2.0;
@@ -41,6 +57,31 @@ const PIANO_BLACK_KEY_RATIO: f32 = 0.6;
 const PIANO_LABEL_SIZE: f32 = 9.0;
 const PIANO_MIDI_LO: i32 = 21; // A0
 const PIANO_MIDI_HI: i32 = 119; // C8
+const PANE_GAP: f32 = 2.0;
+const PANE_LABEL_SIZE: f32 = 10.0;
+const TONE_STEP_SECONDS: f32 = 0.1;
+const TONE_LABEL_SIZE: f32 = 10.0;
+const TONE_LINE_GAP: f32 = 3.0;
+const SCALE_BAR_WIDTH: f32 = 8.0;
+const SCALE_BAR_MAX_HEIGHT: f32 = 180.0;
+const SCALE_BAR_MARGIN: f32 = 6.0;
+const SCALE_LABEL_SIZE: f32 = 9.0;
+const SCALE_TICK_STEPS_DB: [f32; 8] = [1.0, 2.0, 3.0, 6.0, 10.0, 12.0, 20.0, 30.0];
+const SCALE_MAX_TICKS: f32 = 6.0;
+const REGION_TEXT_SIZE: f32 = 11.0;
+const PITCH_TEXT_SIZE: f32 = 16.0;
+const REFINE_SETTLE: Duration = Duration::from_millis(300);
+const TIME_GRID_STEPS: [f32; 9] = [1.0, 2.0, 5.0, 10.0, 15.0, 30.0, 60.0, 120.0, 300.0];
+const TIME_GRID_MIN_GAP: f32 = 24.0;
+const TIME_LABEL_SIZE: f32 = 10.0;
+// Software fallback cells: at least this big, and no more than this many
+// across either axis.
+const SOFTWARE_CELL_PX: f32 = 4.0;
+const SOFTWARE_MAX_CELLS: f32 = 128.0;
+// A burst of columns drains at the column rate plus its own size over this long.
+const CATCH_UP_SECONDS: f32 = 0.1;
+// Further behind than this, the image jumps instead.
+const MAX_LAG_SECONDS: f32 = 0.25;
 
 // Display floor for the frequency axis. Reassignment can localize energy far
 // below the FFT bin spacing, so this is intentionally decoupled from fft_size.
@@ -48,6 +89,10 @@ const PIANO_MIDI_HI: i32 = 119; // C8
 // the whole display; ERB and linear scales handle it cleanly either way.
 const DISPLAY_MIN_HZ: f32 = 1.0;
 
+fn sanitize_gamma(gamma: f32) -> f32 {
+    if gamma.is_finite() { gamma.clamp(MIN_GAMMA, MAX_GAMMA) } else { 1.0 }
+}
+
 fn display_axis(sample_rate: f32) -> (f32, f32) {
     let nyq = (sample_rate / 2.0).max(1.0);
     (DISPLAY_MIN_HZ.min(nyq * 0.5), nyq)
@@ -57,9 +102,7 @@ crate::macros::default_struct! {
     #[derive(Debug, Clone, Copy, PartialEq)]
     pub(in crate::visuals) struct SpectrogramStyle {
         pub background: Color = with_alpha(palettes::BG_BASE, 0.0),
-        pub ceiling_db: f32 = DB_CEILING,
         pub opacity: f32 = 0.95,
-        pub contrast: f32 = 1.0,
     }
 }
 
@@ -75,11 +118,12 @@ crate::macros::default_struct! {
         slot_counts: Arc<[u32]> = Arc::from([]),
         pending: VecDeque<PendingUpload> = VecDeque::new(),
         pending_copy: Option<RingCopyPlan> = None,
+        region: ColumnStore = ColumnStore::default(),
     }
 }
 
 impl SpectrogramHistory {
-    fn apply_update(&mut self, snap: SpectrogramUpdate) {
+    fn apply_update(&mut self, snap: SpectrogramUpdate, byte_cap: u64) {
         let ppc = snap.points_per_column;
         if ppc == 0 { return; }
         let new_kind = match snap.new_columns.first() {
@@ -87,15 +131,18 @@ impl SpectrogramHistory {
             Some(SpectrogramColumn::Classic(_)) => ColumnKind::Classic,
             None => self.col_kind,
         };
-        let max_bytes = SPECTROGRAM_HISTORY_BYTE_BUDGET as u64
-            * (1 + u64::from(new_kind == ColumnKind::Reassigned));
-        let max_cols = (max_bytes / col_byte_stride(new_kind, ppc as u32)) as u32;
-        let capacity = (snap.history_length as u32)
-            .clamp(1, MAX_SPECTROGRAM_HISTORY_COLUMNS as u32)
-            .min(max_cols);
+        let reset = snap.reset || self.points_per_column != ppc || new_kind != self.col_kind;
+        // Until columns arrive, a reassigned slot's size is only an estimate.
+        let column_bytes = if reset || self.col_count == 0 {
+            history_column_bytes(ppc, new_kind == ColumnKind::Reassigned)
+        } else {
+            self.column_bytes()
+        };
+        let wanted = (snap.history_length as u32).clamp(1, MAX_SPECTROGRAM_HISTORY_COLUMNS as u32);
+        let capacity = wanted.min(history_columns_within(byte_cap, column_bytes) as u32);
         if capacity == 0 { return; }
 
-        if snap.reset || self.points_per_column != ppc || new_kind != self.col_kind {
+        if reset {
             *self = Self {
                 col_kind: new_kind,
                 points_per_column: ppc,
@@ -108,28 +155,15 @@ impl SpectrogramHistory {
                 ..Default::default()
             };
         } else if capacity != self.ring_capacity {
-            self.ensure_pending_copy();
-            if capacity > self.ring_capacity && self.col_count >= self.ring_capacity {
-                self.remap_retained(self.write_slot, self.col_count);
-                self.write_slot = self.col_count % capacity;
-            } else if capacity < self.ring_capacity && self.col_count >= capacity {
-                let oldest_kept =
-                    (self.write_slot + self.ring_capacity - capacity) % self.ring_capacity;
-                self.remap_retained(oldest_kept, capacity);
-                self.col_count = capacity;
-                self.write_slot = 0;
-            }
-            self.ring_capacity = capacity;
-            if self.col_kind == ColumnKind::Reassigned
-                && self.slot_counts.len() != capacity as usize
-            {
-                let mut counts = self.slot_counts.to_vec();
-                counts.resize(capacity as usize, 0);
-                self.slot_counts = counts.into();
-            }
+            self.resize(capacity);
         }
 
+        let bins = snap.fft_size / 2 + 1;
+        let bin_hz = snap.sample_rate / snap.fft_size.max(1) as f32;
+        let scale = snap.reassigned_power_scale;
+        self.region.configure(bins, bin_hz, scale, capacity as usize);
         for col in snap.new_columns {
+            self.region.push(&col);
             let slot = self.write_slot;
             let upload = match col {
                 SpectrogramColumn::Reassigned(points) => {
@@ -146,6 +180,39 @@ impl SpectrogramHistory {
             if self.col_count < self.ring_capacity { self.col_count += 1; }
         }
         self.fit_reassigned_slot_capacity();
+        // A busier column widens every slot; the oldest go to stay within the cap.
+        let fitted = history_columns_within(byte_cap, self.column_bytes()) as u32;
+        if fitted > 0 && fitted < self.ring_capacity {
+            self.resize(fitted);
+            self.region.configure(bins, bin_hz, scale, fitted as usize);
+        }
+    }
+
+    // Bytes a column takes at the current slot stride.
+    fn column_bytes(&self) -> u64 {
+        let slot = (self.col_kind == ColumnKind::Reassigned)
+            .then_some(self.reassigned_points_per_slot);
+        history_slot_bytes(self.points_per_column, slot)
+    }
+
+    fn resize(&mut self, capacity: u32) {
+        self.ensure_pending_copy();
+        if capacity > self.ring_capacity && self.col_count >= self.ring_capacity {
+            self.remap_retained(self.write_slot, self.col_count);
+            self.write_slot = self.col_count % capacity;
+        } else if capacity < self.ring_capacity && self.col_count >= capacity {
+            let oldest_kept =
+                (self.write_slot + self.ring_capacity - capacity) % self.ring_capacity;
+            self.remap_retained(oldest_kept, capacity);
+            self.col_count = capacity;
+            self.write_slot = 0;
+        }
+        self.ring_capacity = capacity;
+        if self.col_kind == ColumnKind::Reassigned && self.slot_counts.len() != capacity as usize {
+            let mut counts = self.slot_counts.to_vec();
+            counts.resize(capacity as usize, 0);
+            self.slot_counts = counts.into();
+        }
     }
 
     fn ensure_pending_copy(&mut self) {
@@ -200,21 +267,193 @@ impl SpectrogramHistory {
     }
 }
 
+// Averages incoming columns into one power spectrum per step for the tone
+// tracker; reassigned splats are binned back onto the FFT grid.
+#[derive(Default)]
+struct ToneLabels {
+    tracker: SteadyTones,
+    power: Vec<f32>,
+    db: Vec<f32>,
+    columns: u32,
+    elapsed: f32,
+}
+
+impl ToneLabels {
+    fn feed(&mut self, snap: &SpectrogramUpdate, threshold_db: f32, min_seconds: f32) {
+        let bins = snap.fft_size / 2 + 1;
+        if snap.reset || self.power.len() != bins {
+            *self = Self { power: vec![0.0; bins], ..Self::default() };
+        }
+        let bin_hz = snap.sample_rate / snap.fft_size.max(1) as f32;
+        let column_seconds = snap.hop_size as f32 / snap.sample_rate;
+        for col in &snap.new_columns {
+            match col {
+                SpectrogramColumn::Classic(mags) => {
+                    for (power, &code) in self.power.iter_mut().zip(mags) {
+                        *power += unpack_classic_power(code);
+                    }
+                }
+                SpectrogramColumn::Reassigned(points) => {
+                    for point in points {
+                        let bin = (point.freq_hz / bin_hz).round();
+                        if bin >= 0.0 && let Some(power) = self.power.get_mut(bin as usize) {
+                            *power += db_to_power(point.magnitude_db);
+                        }
+                    }
+                }
+            }
+            self.columns += 1;
+            self.elapsed += column_seconds;
+            if self.elapsed < TONE_STEP_SECONDS { continue; }
+            let scale = 1.0 / self.columns as f32;
+            self.db.clear();
+            self.db.extend(self.power.iter().map(|&p| power_to_db(p * scale, DB_FLOOR)));
+            self.tracker.update(&self.db, bin_hz, self.elapsed, threshold_db, min_seconds);
+            self.power.fill(0.0);
+            (self.columns, self.elapsed) = (0, 0.0);
+        }
+    }
+}
+
+// A history drawn by its own GPU instance: the right channel in split mode,
+// or a refined overlay.
+struct KeyedHistory {
+    key: u64,
+    history: SpectrogramHistory,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct RefineView {
+    zoom: f32,
+    pan: f32,
+    width: u32,
+    rotation: u32,
+    hop_size: usize,
+    fft_size: usize,
+}
+
+// A finer reanalysis of the newest columns, drawn over the live history as
+// it scrolls; `age` counts live columns since it was cut.
+struct RefinedOverlay {
+    left: KeyedHistory,
+    right: Option<KeyedHistory>,
+    fft_size: usize,
+    power_scale: f32,
+    columns: u32,
+    age: u32,
+}
+
+impl RefinedOverlay {
+    fn new(mut update: SpectrogramUpdate, age: u32, byte_cap: u64) -> Self {
+        let keyed = |update: SpectrogramUpdate| {
+            let mut history = SpectrogramHistory::default();
+            history.apply_update(update, byte_cap);
+            KeyedHistory { key: crate::visuals::next_key(), history }
+        };
+        let right = update.right.take().map(|right| keyed(*right));
+        let (fft_size, power_scale) = (update.fft_size, update.reassigned_power_scale);
+        let columns = update.new_columns.len() as u32;
+        Self { left: keyed(update), right, fft_size, power_scale, columns, age }
+    }
+}
+
+// Columns that have arrived but not yet scrolled into view, so a burst of
+// them slides in over the following frames instead of landing at once. It
+// drains on the window's frame clock rather than on arrival times, so each
+// frame moves by exactly its own interval at any refresh rate.
+#[derive(Default)]
+struct ScrollLag {
+    columns: f32,
+    // The last frame drained up to.
+    at: Option<Instant>,
+}
+
+impl ScrollLag {
+    fn push(&mut self, columns: usize, columns_per_second: f32, now: Instant) {
+        self.at.get_or_insert(now);
+        self.columns += columns as f32;
+        if self.columns > columns_per_second * MAX_LAG_SECONDS {
+            self.columns = 0.0;
+        }
+    }
+
+    fn advance(&mut self, now: Instant, columns_per_second: f32) -> f32 {
+        let dt = self.at.map_or(0.0, |at| now.saturating_duration_since(at).as_secs_f32());
+        self.at = Some(now);
+        let rate = columns_per_second + self.columns / CATCH_UP_SECONDS;
+        self.columns = (self.columns - rate * dt).max(0.0);
+        self.columns
+    }
+}
+
+#[derive(Default)]
+struct Refinement {
+    // The zoomed view and when it last changed.
+    view: Option<(RefineView, Instant)>,
+    pending: Option<Receiver<SpectrogramUpdate>>,
+    pending_age: u32,
+    overlay: Option<RefinedOverlay>,
+}
+
+impl Refinement {
+    fn advance(&mut self, columns: u32) {
+        self.pending_age += columns;
+        if let Some(overlay) = &mut self.overlay {
+            overlay.age += columns;
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum HistorySource {
+    Live(usize),
+    Refined(usize),
+}
+
 pub(in crate::visuals) struct SpectrogramState {
     pub(in crate::visuals) style: SpectrogramStyle,
     pub(in crate::visuals) palette: [Color; SPECTROGRAM_PALETTE_SIZE],
     pub(in crate::visuals) stop_positions: [f32; SPECTROGRAM_PALETTE_SIZE],
     pub(in crate::visuals) stop_spreads: [f32; SPECTROGRAM_PALETTE_SIZE],
+    pub(in crate::visuals) palette_blend: ColorBlend,
     key: u64,
+    opacity: f32,
+    pulse: f32,
     settings: SpectrogramSettings,
+    // Set while popped out with its own floor/contrast.
+    display_override: Option<DisplayOverride>,
+    // The shared spectrogram range, before either floor.
+    level_range: DbRange,
+    tuning: Tuning,
+    numbers: NumberFormat,
     sample_rate: f32,
     fft_size: usize,
     hop_size: usize,
     reassigned_power_scale: f32,
     zoom: f32,
     pan: f32,
+    // Pixels per column, and columns back from the newest at the trailing edge.
+    time_zoom: f32,
+    time_pan: f32,
     pub(in crate::visuals) view_width: u32,
+    // Where the last frame drew the view, in logical window coordinates.
+    drawn_bounds: Option<Rectangle>,
     history: SpectrogramHistory,
+    right: Option<KeyedHistory>,
+    tones: ToneLabels,
+    pitch: PitchTracker,
+    refine: Refinement,
+    scroll: ScrollLag,
+    // The shift-drag region behind the open statistics card, for export.
+    selection: Option<Selection>,
+    hovered: bool,
+    calibrated: bool,
+}
+
+struct Selection {
+    pane: usize,
+    columns: std::ops::Range<u64>,
+    hz: std::ops::Range<f32>,
 }
 
 impl SpectrogramState {
@@ -225,22 +464,48 @@ impl SpectrogramState {
             palette: palettes::spectrogram::COLORS,
             stop_positions: palettes::spectrogram::DEFAULT_POSITIONS,
             stop_spreads: [1.0; SPECTROGRAM_PALETTE_SIZE],
+            palette_blend: ColorBlend::default(),
             key: crate::visuals::next_key(),
+            opacity: 1.0,
+            pulse: 0.0,
             settings: SpectrogramSettings {
                 floor_db: DB_FLOOR,
                 ..SpectrogramSettings::default()
             },
+            display_override: None,
+            level_range: LevelScale::Spectrogram.default_range(),
+            tuning: Tuning::default(),
+            numbers: NumberFormat::default(),
             sample_rate: cfg.sample_rate,
             fft_size: cfg.fft_size * cfg.zero_padding_factor.max(1),
             hop_size: cfg.hop_size,
             reassigned_power_scale: 1.0,
             zoom: 1.0,
             pan: 0.5,
+            time_zoom: 1.0,
+            time_pan: 0.0,
             view_width: 0,
+            drawn_bounds: None,
             history: SpectrogramHistory::default(),
+            right: None,
+            tones: ToneLabels::default(),
+            pitch: PitchTracker::default(),
+            refine: Refinement::default(),
+            scroll: ScrollLag::default(),
+            selection: None,
+            hovered: false,
+            calibrated: false,
         }
     }
 
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity;
+    }
+
+    pub fn set_pulse(&mut self, pulse: f32) {
+        self.pulse = pulse;
+    }
+
     pub fn set_palette(&mut self, palette: &[Color; SPECTROGRAM_PALETTE_SIZE]) {
         self.palette = *palette;
     }
@@ -257,26 +522,239 @@ impl SpectrogramState {
         }
     }
 
+    pub fn set_palette_blend(&mut self, blend: ColorBlend) {
+        self.palette_blend = blend;
+    }
+
     pub fn update_view_settings(&mut self, settings: &SpectrogramSettings) {
         self.settings = settings.clone();
-        self.settings.floor_db = sanitize_negative_db(settings.floor_db, DB_FLOOR)
-            .min(self.style.ceiling_db - 1.0);
+        self.settings.floor_db = sanitize_negative_db(settings.floor_db, DB_FLOOR);
         self.settings.tilt_db = if settings.tilt_db.is_finite() { settings.tilt_db } else { 0.0 };
         self.settings.rotation = settings.rotation.clamp(-1, 2);
+        self.settings.history_memory_mb =
+            settings.history_memory_mb.clamp(MIN_HISTORY_MEMORY_MB, MAX_HISTORY_MEMORY_MB);
+        self.settings.gamma = sanitize_gamma(settings.gamma);
+        self.settings.knee =
+            if settings.knee.is_finite() { settings.knee.clamp(0.0, MAX_KNEE) } else { 0.0 };
+        self.settings.sharpen = if settings.sharpen.is_finite() {
+            settings.sharpen.clamp(0.0, MAX_SHARPEN)
+        } else {
+            0.0
+        };
+        self.settings.tone_threshold_db = if settings.tone_threshold_db.is_finite() {
+            settings.tone_threshold_db.clamp(3.0, 60.0)
+        } else {
+            15.0
+        };
+        self.settings.tone_min_seconds = if settings.tone_min_seconds.is_finite() {
+            settings.tone_min_seconds.clamp(0.5, 600.0)
+        } else {
+            5.0
+        };
+        if !settings.tone_labels {
+            self.tones = ToneLabels::default();
+        }
+        if !settings.pitch_readout {
+            self.pitch.reset();
+        }
+    }
+
+    pub fn set_level_range(&mut self, range: DbRange) {
+        self.level_range = range;
+    }
+
+    pub fn set_tuning(&mut self, tuning: Tuning) {
+        self.tuning = tuning;
+    }
+
+    pub fn set_number_format(&mut self, numbers: NumberFormat) {
+        self.numbers = numbers;
+    }
+
+    // Only reassigned columns carry the precise frequencies the tracker needs.
+    fn pitch_lines(&self) -> [String; 2] {
+        let pitch = self.pitch.current();
+        let note = pitch.and_then(|p| NoteInfo::from_frequency(p.hz, self.tuning));
+        match (note, pitch) {
+            (Some(note), Some(pitch)) => [note.fmt_note_cents(), self.numbers.freq(pitch.hz)],
+            _ if !self.settings.use_reassignment => {
+                ["--".to_owned(), "needs reassignment".to_owned()]
+            }
+            _ => ["--".to_owned(), String::new()],
+        }
+    }
+
+    pub fn set_display_override(&mut self, display: Option<DisplayOverride>) {
+        self.display_override = display;
+    }
+
+    // The shared range raised to the floor in use, and the contrast.
+    fn display_levels(&self) -> (DbRange, f32) {
+        let (floor_db, gamma) = match self.display_override {
+            Some(o) => (sanitize_negative_db(o.floor_db, DB_FLOOR), sanitize_gamma(o.gamma)),
+            None => (self.settings.floor_db, self.settings.gamma),
+        };
+        (self.level_range.with_floor(floor_db), gamma)
+    }
+
+    // CPU copy of the shader's `shade_db`, for the scale bar.
+    fn level_color(&self, db: f32) -> Color {
+        let (levels, gamma) = self.display_levels();
+        let range = levels.span().max(0.001);
+        let mut t = ((db - levels.floor_db) / range).clamp(0.0, 1.0);
+        if (gamma - 1.0).abs() > 1e-4 {
+            t = t.powf(gamma.max(0.01));
+        }
+        let knee = self.settings.knee;
+        if knee > 0.0 {
+            t = t * (1.0 + knee) / (1.0 + knee * t);
+        }
+        let (lo, hi, f) = stop_segment(&self.stop_positions, &self.stop_spreads, t);
+        let color = mix_color(self.palette[lo], self.palette[hi], f, self.palette_blend);
+        with_alpha(color, color.a * self.style.opacity.clamp(0.0, 1.0))
+    }
+
+    /// Held still under the pointer, so a transient can be read off.
+    pub fn hover_paused(&self) -> bool {
+        self.settings.pause_on_hover && self.hovered
     }
 
     pub fn export_settings(&self) -> SpectrogramSettings {
         self.settings.clone()
     }
 
-    pub fn apply_snapshot(&mut self, snap: SpectrogramUpdate) {
+    fn history_byte_cap(&self) -> u64 {
+        history_byte_cap(self.settings.history_memory_mb, self.settings.channels)
+    }
+
+    pub fn apply_snapshot(&mut self, mut snap: SpectrogramUpdate) {
         if snap.new_columns.is_empty() && !snap.reset { return; }
+        let byte_cap = self.history_byte_cap();
+        match snap.right.take() {
+            Some(right) => self
+                .right
+                .get_or_insert_with(|| KeyedHistory {
+                    key: crate::visuals::next_key(),
+                    history: SpectrogramHistory::default(),
+                })
+                .history
+                .apply_update(*right, byte_cap),
+            None => self.right = None,
+        }
         self.sample_rate = snap.sample_rate;
         self.fft_size = snap.fft_size;
         self.hop_size = snap.hop_size;
         self.reassigned_power_scale = snap.reassigned_power_scale;
+        self.calibrated = snap.calibrated;
         self.settings.frequency_scale = snap.frequency_scale;
-        self.history.apply_update(snap);
+        if self.settings.tone_labels && snap.sample_rate > 0.0 {
+            let (threshold, min_secs) =
+                (self.settings.tone_threshold_db, self.settings.tone_min_seconds);
+            self.tones.feed(&snap, threshold, min_secs);
+        }
+        if snap.reset {
+            self.pitch.reset();
+        }
+        if self.settings.pitch_readout {
+            for col in &snap.new_columns {
+                if let SpectrogramColumn::Reassigned(points) = col {
+                    let peaks = points.iter().map(|p| (p.freq_hz, db_to_power(p.magnitude_db)));
+                    self.pitch.push(peaks);
+                }
+            }
+        }
+        if snap.reset {
+            self.refine = Refinement::default();
+            self.scroll = ScrollLag::default();
+            self.time_pan = 0.0;
+            self.selection = None;
+        } else {
+            self.refine.advance(snap.new_columns.len() as u32);
+            // Panned back, the view holds still on the columns it shows.
+            if self.time_pan > 0.0 {
+                self.time_pan += snap.new_columns.len() as f32;
+            } else if let Some(rate) = self.columns_per_second() {
+                self.scroll.push(snap.new_columns.len(), rate, Instant::now());
+            }
+        }
+        self.history.apply_update(snap, byte_cap);
+        self.clamp_time_pan();
+    }
+
+    pub fn refine_enabled(&self) -> bool {
+        self.settings.zoom_refine && self.settings.use_reassignment
+    }
+
+    /// Columns to reanalyse once a zoomed view has held still, and again after
+    /// half of the last result has scrolled away. Also collects finished work.
+    pub fn refine_request(&mut self, now: Instant) -> Option<usize> {
+        let view = RefineView {
+            zoom: self.zoom,
+            pan: self.pan,
+            width: self.view_width,
+            rotation: self.rotation_index(),
+            hop_size: self.hop_size,
+            fft_size: self.fft_size,
+        };
+        let wanted = self.refine_enabled() && self.zoom >= REFINE_MIN_ZOOM;
+        let byte_cap = self.history_byte_cap();
+        let refine = &mut self.refine;
+        if !wanted || refine.view.is_none_or(|(seen, _)| seen != view) {
+            *refine = Refinement {
+                view: wanted.then_some((view, now)),
+                ..Refinement::default()
+            };
+            return None;
+        }
+        if let Some(receiver) = &refine.pending {
+            match receiver.try_recv() {
+                Ok(update) => {
+                    refine.overlay =
+                        Some(RefinedOverlay::new(update, refine.pending_age, byte_cap));
+                    refine.pending = None;
+                }
+                Err(TryRecvError::Empty) => return None,
+                // Nothing came back; wait out another settle before retrying.
+                Err(TryRecvError::Disconnected) => {
+                    refine.pending = None;
+                    refine.view = Some((view, now));
+                }
+            }
+        }
+        if refine.overlay.as_ref().is_some_and(|o| o.age >= self.view_width) {
+            refine.overlay = None;
+        }
+        let settled = refine
+            .view
+            .is_some_and(|(_, since)| now.duration_since(since) >= REFINE_SETTLE);
+        let stale = refine.overlay.as_ref().is_none_or(|o| o.age * 2 >= o.columns);
+        (settled && stale && self.history.col_count > 0)
+            .then(|| self.history.col_count.min(self.view_width) as usize)
+    }
+
+    pub fn start_refine(&mut self, job: RefineJob) {
+        self.refine.pending = job.spawn();
+        self.refine.pending_age = 0;
+    }
+
+    // Where the refined columns sit in `pane`, or `None` once off screen.
+    fn refined_rect(&self, pane: Rectangle) -> Option<Rectangle> {
+        let overlay = self.refine.overlay.as_ref()?;
+        let age = (overlay.age as f32 - self.scroll.columns - self.time_pan) * self.time_zoom;
+        let n = overlay.columns as f32 * self.time_zoom;
+        let rect = match self.rotation_index() {
+            1 => Rectangle::new(
+                Point::new(pane.x, pane.y + pane.height - age - n),
+                Size::new(pane.width, n),
+            ),
+            2 => Rectangle::new(Point::new(pane.x + age, pane.y), Size::new(n, pane.height)),
+            3 => Rectangle::new(Point::new(pane.x, pane.y + age), Size::new(pane.width, n)),
+            _ => Rectangle::new(
+                Point::new(pane.x + pane.width - age - n, pane.y),
+                Size::new(n, pane.height),
+            ),
+        };
+        rect.intersects(&pane).then_some(rect)
     }
 
     pub fn visual_params(
@@ -284,18 +762,47 @@ impl SpectrogramState {
         bounds: Rectangle,
         uv_y_range: [f32; 2],
     ) -> Option<SpectrogramParams> {
-        let history = &mut self.history;
+        self.channel_params(HistorySource::Live(0), bounds, uv_y_range)
+    }
+
+    fn channel_params(
+        &mut self,
+        source: HistorySource,
+        bounds: Rectangle,
+        uv_y_range: [f32; 2],
+    ) -> Option<SpectrogramParams> {
+        let (levels, gamma) = self.display_levels();
+        let (mut fft_size, mut power_scale) = (self.fft_size, self.reassigned_power_scale);
+        let mut scroll_lag = self.scroll.columns + self.time_pan;
+        let channel = match source {
+            HistorySource::Live(0) => None,
+            HistorySource::Live(_) => Some(self.right.as_mut()?),
+            HistorySource::Refined(pane) => {
+                // refined_rect already slides the overlay along.
+                scroll_lag = 0.0;
+                let overlay = self.refine.overlay.as_mut()?;
+                (fft_size, power_scale) = (overlay.fft_size, overlay.power_scale);
+                Some(match pane {
+                    0 => &mut overlay.left,
+                    _ => overlay.right.as_mut()?,
+                })
+            }
+        };
+        let (key, history) = match channel {
+            Some(channel) => (channel.key, &mut channel.history),
+            None => (self.key, &mut self.history),
+        };
         if history.col_count == 0 && history.pending.is_empty() { return None; }
         let copy_plan = history.pending_copy.take();
         history.gpu_capacity = history.ring_capacity;
         let slot_counts = Arc::clone(&history.slot_counts);
         let op = self.style.opacity.clamp(0.0, 1.0);
         let to_rgba = |c: Color| rgba_with_alpha(color_to_rgba(c), c.a * op);
-        let bin_hz = self.sample_rate / (self.fft_size.max(1) as f32);
+        let bin_hz = self.sample_rate / (fft_size.max(1) as f32);
         let (freq_min, freq_max) = display_axis(self.sample_rate);
 
         Some(SpectrogramParams {
-            key: self.key,
+            key,
             bounds,
             ring_capacity: history.ring_capacity,
             points_per_column: history.points_per_column as u32,
@@ -309,17 +816,30 @@ impl SpectrogramState {
             freq_min,
             freq_max,
             bin_hz,
-            reassigned_power_scale: self.reassigned_power_scale,
+            reassigned_power_scale: power_scale,
             freq_scale: self.settings.frequency_scale,
             palette: self.palette.map(to_rgba),
             stop_positions: self.stop_positions,
             stop_spreads: self.stop_spreads,
-            contrast: self.style.contrast,
-            floor_db: self.settings.floor_db,
-            ceiling_db: self.style.ceiling_db,
+            palette_blend: self.palette_blend,
+            gamma,
+            knee: self.settings.knee,
+            interpolation: self.settings.interpolation,
+            sharpen: self.settings.sharpen,
+            floor_db: levels.floor_db,
+            ceiling_db: levels.ceiling_db,
             tilt_db: self.settings.tilt_db,
-            uv_y_range,
+            // Reversing the window flips the texture along the frequency axis.
+            uv_y_range: if self.settings.invert_frequency {
+                [uv_y_range[1], uv_y_range[0]]
+            } else {
+                uv_y_range
+            },
             rotation: self.settings.rotation,
+            scroll_lag,
+            time_scale: self.time_zoom,
+            opacity: self.opacity,
+            pulse: self.pulse,
         })
     }
 
@@ -336,6 +856,41 @@ impl SpectrogramState {
         crate::util::finite_positive(self.settings.frequency_scale.freq_at(min_f, nyq, tex_uv))
     }
 
+    // Split mode stacks L and R across the frequency axis so both keep the
+    // full time span.
+    fn panes(&self, bounds: Rectangle) -> Vec<Rectangle> {
+        if self.right.is_none() {
+            return vec![bounds];
+        }
+        let gap = PANE_GAP.min(bounds.width.min(bounds.height));
+        if self.freq_axis_is_horizontal() {
+            let w = (bounds.width - gap) * 0.5;
+            vec![
+                Rectangle::new(bounds.position(), Size::new(w, bounds.height)),
+                Rectangle::new(
+                    Point::new(bounds.x + w + gap, bounds.y),
+                    Size::new(w, bounds.height),
+                ),
+            ]
+        } else {
+            let h = (bounds.height - gap) * 0.5;
+            vec![
+                Rectangle::new(bounds.position(), Size::new(bounds.width, h)),
+                Rectangle::new(
+                    Point::new(bounds.x, bounds.y + h + gap),
+                    Size::new(bounds.width, h),
+                ),
+            ]
+        }
+    }
+
+    fn pane_at(&self, bounds: Rectangle, point: Point) -> Rectangle {
+        self.panes(bounds)
+            .into_iter()
+            .find(|pane| pane.contains(point))
+            .unwrap_or(bounds)
+    }
+
     // Normalized rotation (0..3) matching the shader's rotate_uv convention
     fn rotation_index(&self) -> u32 {
         (self.settings.rotation as i32).rem_euclid(4) as u32
@@ -345,20 +900,87 @@ impl SpectrogramState {
         matches!(self.rotation_index(), 1 | 3)
     }
 
+    // Whether frequency rises with the screen coordinate along its axis.
+    fn freq_ascends(&self) -> bool {
+        matches!(self.rotation_index(), 1 | 2) != self.settings.invert_frequency
+    }
+
     // Maps a screen point to the frequency-axis UV (0..1), matching
     // the shader's rotate_uv so CPU-side interactions stay consistent.
     fn freq_axis_norm(&self, cursor: Point, bounds: Rectangle) -> Option<f32> {
         if !bounds.contains(cursor) { return None; }
-        let norm = match self.rotation_index() {
-            1 => (cursor.x - bounds.x) / bounds.width,
-            2 => (cursor.y - bounds.y) / bounds.height,
-            3 => 1.0 - (cursor.x - bounds.x) / bounds.width,
-            _ => 1.0 - (cursor.y - bounds.y) / bounds.height,
+        let along = if self.freq_axis_is_horizontal() {
+            (cursor.x - bounds.x) / bounds.width
+        } else {
+            (cursor.y - bounds.y) / bounds.height
         };
+        let norm = if self.freq_ascends() { along } else { 1.0 - along };
         norm.is_finite().then(|| norm.clamp(0.0, 1.0))
     }
 
-    // 1 column = 1 logical pixel on the time axis, matching the shader.
+    // Columns back from the newest under `cursor`, matching the shader:
+    // each column is `time_zoom` logical pixels wide, offset by the time pan.
+    fn column_age(&self, cursor: Point, bounds: Rectangle) -> f32 {
+        let from_newest = match self.rotation_index() {
+            1 => bounds.y + bounds.height - cursor.y,
+            2 => cursor.x - bounds.x,
+            3 => cursor.y - bounds.y,
+            _ => bounds.x + bounds.width - cursor.x,
+        };
+        self.time_pan + from_newest / self.time_zoom
+    }
+
+    fn columns_per_second(&self) -> Option<f32> {
+        if self.hop_size == 0 || self.sample_rate <= 0.0 { return None; }
+        crate::util::finite_positive(self.sample_rate / self.hop_size as f32)
+    }
+
+    // Columns across the pane, up to what the history holds.
+    fn visible_columns(&self) -> f32 {
+        (self.view_width as f32 / self.time_zoom).min(self.history.ring_capacity as f32)
+    }
+
+    fn visible_seconds(&self) -> Option<f32> {
+        let columns = self.visible_columns();
+        if columns <= 0.0 { return None; }
+        Some(columns / self.columns_per_second()?)
+    }
+
+    // Keeps the oldest visible column within the history.
+    fn clamp_time_pan(&mut self) {
+        let max = (self.history.col_count as f32 - self.visible_columns()).max(0.0);
+        self.time_pan = self.time_pan.clamp(0.0, max);
+    }
+
+    // Zooms the time axis about the column under `cursor`.
+    fn time_zoom_at(&mut self, cursor: Point, pane: Rectangle, factor: f32) {
+        let column = self.column_age(cursor, pane);
+        let from_newest = (column - self.time_pan) * self.time_zoom;
+        self.time_zoom = (self.time_zoom * factor).clamp(MIN_ZOOM, MAX_TIME_ZOOM);
+        self.time_pan = column - from_newest / self.time_zoom;
+        self.clamp_time_pan();
+    }
+
+    // Inverse of column_age: where the line `age` columns back crosses the pane.
+    fn time_line(&self, bounds: Rectangle, age: f32) -> Rectangle {
+        let age = ((age - self.time_pan) * self.time_zoom).round();
+        match self.rotation_index() {
+            1 => Rectangle::new(
+                Point::new(bounds.x, bounds.y + bounds.height - age),
+                Size::new(bounds.width, 1.0),
+            ),
+            2 => Rectangle::new(
+                Point::new(bounds.x + age, bounds.y),
+                Size::new(1.0, bounds.height),
+            ),
+            3 => Rectangle::new(Point::new(bounds.x, bounds.y + age), Size::new(bounds.width, 1.0)),
+            _ => Rectangle::new(
+                Point::new(bounds.x + bounds.width - age, bounds.y),
+                Size::new(1.0, bounds.height),
+            ),
+        }
+    }
+
     fn time_ago_at_cursor(&self, cursor: Point, bounds: Rectangle) -> Option<f32> {
         if !bounds.contains(cursor)
             || self.history.col_count == 0
@@ -367,29 +989,165 @@ impl SpectrogramState {
         {
             return None;
         }
-        let age = match self.rotation_index() {
-            0 => bounds.x + bounds.width - cursor.x,
-            1 => bounds.y + bounds.height - cursor.y,
-            2 => cursor.x - bounds.x,
-            3 => cursor.y - bounds.y,
-            _ => return None,
-        };
+        let age = self.column_age(cursor, bounds);
         if age < 0.0 || age >= self.history.col_count as f32 { return None; }
         let secs = age * (self.hop_size as f32 / self.sample_rate);
         secs.is_finite().then_some(secs)
     }
+
+    // Statistics for the rectangle spanned by `from` and `to`, clipped to the
+    // pane `from` lies in, which also becomes the selection to export.
+    fn select_region(&mut self, bounds: Rectangle, from: Point, to: Point) -> Option<RegionStats> {
+        self.selection = None;
+        if self.hop_size == 0 || self.sample_rate <= 0.0 { return None; }
+        let panes = self.panes(bounds);
+        let index = panes.iter().position(|pane| pane.contains(from))?;
+        let pane = panes[index];
+        let clip = |p: Point| {
+            Point::new(
+                p.x.clamp(pane.x, (pane.x + pane.width - 0.5).max(pane.x)),
+                p.y.clamp(pane.y, (pane.y + pane.height - 0.5).max(pane.y)),
+            )
+        };
+        let (from, to) = (clip(from), clip(to));
+        let uv_range = self.uv_y_range();
+        let f0 = self.frequency_at_cursor(from, pane, uv_range)?;
+        let f1 = self.frequency_at_cursor(to, pane, uv_range)?;
+        let (a0, a1) = (self.column_age(from, pane), self.column_age(to, pane));
+        let history = match index {
+            0 => &self.history,
+            _ => &self.right.as_ref()?.history,
+        };
+        let ages = a0.min(a1).max(0.0) as usize..a0.max(a1).max(0.0) as usize + 1;
+        let column_seconds = self.hop_size as f32 / self.sample_rate;
+        let hz = f0.min(f1)..f0.max(f1);
+        let stats = history.region.stats(ages.clone(), hz.clone(), column_seconds)?;
+        let columns = history.region.span(ages);
+        self.selection = Some(Selection { pane: index, columns, hz });
+        Some(stats)
+    }
+
+    /// Where the view was last drawn, in logical window coordinates.
+    pub fn drawn_bounds(&self) -> Option<Rectangle> {
+        self.drawn_bounds
+    }
+
+    /// Peak frequency and level for each column of the selected region,
+    /// oldest first, as CSV.
+    pub fn selection_peaks_csv(&self) -> Option<String> {
+        let selection = self.selection.as_ref()?;
+        let history = match selection.pane {
+            0 => &self.history,
+            _ => &self.right.as_ref()?.history,
+        };
+        let peaks = history.region.peaks(selection.columns.clone(), selection.hz.clone());
+        let &(first, ..) = peaks.first()?;
+        let column_seconds = self.hop_size as f32 / self.sample_rate;
+        let mut out = String::from("time_s,frequency_hz,level_db\n");
+        for (index, hz, db) in peaks {
+            let time = (index - first) as f32 * column_seconds;
+            out.push_str(&format!("{time:.4},{hz:.1},{db:.1}\n"));
+        }
+        Some(out)
+    }
+
+    // Quad fallback for tiny-skia, drawn from the coarse region copy of the
+    // history; placement mirrors the tooltip's cursor mapping.
+    fn software_cells(
+        &self,
+        index: usize,
+        pane: Rectangle,
+        uv_range: [f32; 2],
+    ) -> Vec<(Rectangle, Color)> {
+        let history = match index {
+            0 => &self.history,
+            _ => match &self.right {
+                Some(right) => &right.history,
+                None => return Vec::new(),
+            },
+        };
+        let size = Size::new(
+            (pane.width / SOFTWARE_MAX_CELLS).max(SOFTWARE_CELL_PX),
+            (pane.height / SOFTWARE_MAX_CELLS).max(SOFTWARE_CELL_PX),
+        );
+        let columns = (pane.width / size.width).ceil() as usize;
+        let rows = (pane.height / size.height).ceil() as usize;
+        let tilt = self.settings.tilt_db;
+        let mut cells = Vec::with_capacity(columns * rows);
+        for row in 0..rows {
+            for column in 0..columns {
+                let corner = Point::new(
+                    pane.x + column as f32 * size.width,
+                    pane.y + row as f32 * size.height,
+                );
+                let Some(rect) = Rectangle::new(corner, size).intersection(&pane) else {
+                    continue;
+                };
+                let center = rect.center();
+                let Some(hz) = self.frequency_at_cursor(center, pane, uv_range) else {
+                    continue;
+                };
+                // Columns still sliding in aren't on screen yet.
+                let age = self.column_age(center, pane) + self.scroll.columns;
+                let Some(mut db) = history.region.level_db(age as usize, hz) else {
+                    continue;
+                };
+                if tilt != 0.0 && db > DB_FLOOR {
+                    db += tilt * (hz / 1000.0).log2();
+                }
+                cells.push((rect, self.level_color(db)));
+            }
+        }
+        cells
+    }
 }
 
 const MIN_ZOOM: f32 = 1.0;
 const MAX_ZOOM: f32 = f32::MAX;
+const MAX_TIME_ZOOM: f32 = 64.0;
 const ZOOM_STEP: f32 = 1.15;
 
 #[derive(Default)]
 struct InteractionState {
     cursor: Option<Point>,
     modifiers: keyboard::Modifiers,
-    drag: Option<(f32, f32)>,
+    // Middle-drag origin with the frequency and time pans it started from.
+    drag: Option<(Point, f32, f32)>,
     left_held: bool,
+    // When this window's current frame was requested.
+    frame_at: Option<Instant>,
+    // Shift-drag start and current corner.
+    selecting: Option<(Point, Point)>,
+    region: Option<RegionCard>,
+}
+
+struct RegionCard {
+    lines: [String; 4],
+    rect: Rectangle,
+    copied: bool,
+}
+
+impl RegionCard {
+    const HINT: &'static str = "click to copy";
+
+    fn new(bounds: Rectangle, anchor: Point, lines: [String; 4]) -> Self {
+        let (width, line_h) = lines
+            .iter()
+            .map(String::as_str)
+            .chain([Self::HINT])
+            .map(|line| measure_text(line, REGION_TEXT_SIZE))
+            .fold((0.0_f32, 0.0_f32), |(w, h), size| (w.max(size.width), h.max(size.height)));
+        let rows = lines.len() as f32 + 1.0;
+        let size = Size::new(
+            width + TOOLTIP_PAD * 2.0,
+            rows * line_h + (rows - 1.0) * TOOLTIP_GAP + TOOLTIP_PAD * 2.0,
+        );
+        Self {
+            lines,
+            rect: place_tooltip(bounds, anchor, size, false),
+            copied: false,
+        }
+    }
 }
 
 impl SpectrogramState {
@@ -415,6 +1173,28 @@ struct Spectrogram<'a> {
     state: &'a RefCell<SpectrogramState>,
 }
 
+// The finest whole-second step that keeps gridlines TIME_GRID_MIN_GAP apart.
+fn time_grid_step(columns_per_second: f32) -> Option<f32> {
+    TIME_GRID_STEPS
+        .into_iter()
+        .find(|&step| step * columns_per_second >= TIME_GRID_MIN_GAP)
+}
+
+// Round-numbered levels between floor and ceiling, at most SCALE_MAX_TICKS.
+fn scale_ticks(floor_db: f32, ceiling_db: f32) -> Vec<f32> {
+    let range = ceiling_db - floor_db;
+    if !range.is_finite() || range <= 0.0 {
+        return Vec::new();
+    }
+    let step = SCALE_TICK_STEPS_DB
+        .into_iter()
+        .find(|&step| range / step <= SCALE_MAX_TICKS)
+        .unwrap_or_else(|| (range / SCALE_MAX_TICKS / 10.0).ceil() * 10.0);
+    let first = (floor_db / step).ceil() as i32;
+    let last = (ceiling_db / step).floor() as i32;
+    (first..=last).map(|n| n as f32 * step).collect()
+}
+
 // Places the tooltip adjacent to the cursor on the side opposite the freq
 // axis, flipping when it would clip the widget bounds.
 fn place_tooltip(bounds: Rectangle, cursor: Point, sz: Size, horizontal: bool) -> Rectangle {
@@ -450,8 +1230,9 @@ impl<'a> Spectrogram<'a> {
         theme: &iced::Theme,
         bounds: Rectangle,
         cursor: Point,
+        opacity: f32,
     ) {
-        let color = theme::border_color(theme, false);
+        let color = faded(theme::border_color(theme, false), opacity);
         for rect in [
             Rectangle::new(
                 Point::new(cursor.x, bounds.y),
@@ -463,6 +1244,61 @@ impl<'a> Spectrogram<'a> {
         }
     }
 
+    fn draw_pane_labels(
+        renderer: &mut iced::Renderer,
+        theme: &iced::Theme,
+        panes: &[Rectangle],
+        opacity: f32,
+    ) {
+        let color = with_alpha(theme.extended_palette().background.base.text, 0.7 * opacity);
+        for (pane, label) in panes.iter().zip(["L", "R"]) {
+            let size = measure_text(label, PANE_LABEL_SIZE);
+            let at = Point::new(
+                pane.x + pane.width - size.width - TOOLTIP_PAD,
+                pane.y + TOOLTIP_GAP,
+            );
+            renderer.fill_text(
+                make_text(label, PANE_LABEL_SIZE, size),
+                at,
+                color,
+                Rectangle::new(at, size),
+            );
+        }
+    }
+
+    // Note, cents and frequency, top left of the pane.
+    fn draw_pitch(
+        renderer: &mut iced::Renderer,
+        theme: &iced::Theme,
+        pane: Rectangle,
+        inset: f32,
+        lines: &[String; 2],
+        opacity: f32,
+    ) {
+        let sizes = lines.each_ref().map(|line| measure_text(line, PITCH_TEXT_SIZE));
+        let content = Size::new(
+            sizes[0].width.max(sizes[1].width),
+            sizes[0].height + TOOLTIP_GAP + sizes[1].height,
+        );
+        let rect = Rectangle::new(
+            Point::new(pane.x + inset + TOOLTIP_PAD, pane.y + TOOLTIP_PAD),
+            Size::new(content.width + TOOLTIP_PAD * 2.0, content.height + TOOLTIP_PAD * 2.0),
+        );
+        let pal = theme.extended_palette();
+        let backdrop = with_alpha(pal.background.strong.color, TOOLTIP_BG_ALPHA * opacity);
+        fill_rect(renderer, rect, backdrop);
+        let mut at = Point::new(rect.x + TOOLTIP_PAD, rect.y + TOOLTIP_PAD);
+        for (line, size) in lines.iter().zip(sizes) {
+            renderer.fill_text(
+                make_text(line, PITCH_TEXT_SIZE, size),
+                at,
+                faded(pal.background.base.text, opacity),
+                Rectangle::new(at, size),
+            );
+            at.y += size.height + TOOLTIP_GAP;
+        }
+    }
+
     fn draw_tooltip(
         &self,
         renderer: &mut iced::Renderer,
@@ -477,12 +1313,13 @@ impl<'a> Spectrogram<'a> {
         };
         let horizontal = state.freq_axis_is_horizontal();
         let time_ago = state.time_ago_at_cursor(cursor, bounds);
+        let (tuning, numbers, opacity) = (state.tuning, state.numbers, state.opacity);
         drop(state);
 
-        let freq_text = fmt_freq(freq);
-        let note_text = NoteInfo::from_frequency(freq)
+        let freq_text = numbers.freq(freq);
+        let note_text = NoteInfo::from_frequency(freq, tuning)
             .map_or_else(|| String::from("--"), |ni| ni.fmt_note_cents());
-        let time_text = time_ago.map_or_else(|| String::from("--"), fmt_duration);
+        let time_text = time_ago.map_or_else(|| String::from("--"), |ago| numbers.duration(ago));
 
         let fsz = measure_text(&freq_text, TOOLTIP_SIZE);
         let nsz = measure_text(&note_text, TOOLTIP_SIZE);
@@ -497,15 +1334,18 @@ impl<'a> Spectrogram<'a> {
         fill_bordered_rect(
             renderer,
             tb,
-            with_alpha(pal.background.strong.color, TOOLTIP_BG_ALPHA),
+            with_alpha(pal.background.strong.color, TOOLTIP_BG_ALPHA * opacity),
             iced::Border {
-                color: with_alpha(theme::border_color(theme, false), TOOLTIP_BORDER_ALPHA),
+                color: with_alpha(
+                    theme::border_color(theme, false),
+                    TOOLTIP_BORDER_ALPHA * opacity,
+                ),
                 width: 1.0,
                 ..Default::default()
             },
         );
 
-        let text_color = pal.background.base.text;
+        let text_color = faded(pal.background.base.text, opacity);
         let tx = tb.x + TOOLTIP_PAD;
         let mut ty = tb.y + TOOLTIP_PAD;
         for (text, sz) in [(&freq_text, fsz), (&note_text, nsz), (&time_text, tsz)] {
@@ -520,6 +1360,310 @@ impl<'a> Spectrogram<'a> {
         }
     }
 
+    fn draw_selection(
+        renderer: &mut iced::Renderer,
+        theme: &iced::Theme,
+        from: Point,
+        to: Point,
+        opacity: f32,
+    ) {
+        let rect = Rectangle::new(
+            Point::new(from.x.min(to.x), from.y.min(to.y)),
+            Size::new((from.x - to.x).abs(), (from.y - to.y).abs()),
+        );
+        let accent = theme.extended_palette().primary.base.color;
+        fill_bordered_rect(
+            renderer,
+            rect,
+            with_alpha(accent, 0.12 * opacity),
+            iced::Border {
+                color: with_alpha(accent, 0.8 * opacity),
+                width: 1.0,
+                ..Default::default()
+            },
+        );
+    }
+
+    fn draw_region_card(
+        renderer: &mut iced::Renderer,
+        theme: &iced::Theme,
+        card: &RegionCard,
+        opacity: f32,
+    ) {
+        let pal = theme.extended_palette();
+        fill_bordered_rect(
+            renderer,
+            card.rect,
+            with_alpha(pal.background.strong.color, TOOLTIP_BG_ALPHA * opacity),
+            iced::Border {
+                color: with_alpha(
+                    theme::border_color(theme, false),
+                    TOOLTIP_BORDER_ALPHA * opacity,
+                ),
+                width: 1.0,
+                ..Default::default()
+            },
+        );
+        let hint = if card.copied { "copied" } else { RegionCard::HINT };
+        let hint_color = with_alpha(pal.background.base.text, 0.6 * opacity);
+        let text_color = faded(pal.background.base.text, opacity);
+        let rows = card.lines.iter().map(|line| (line.as_str(), text_color));
+        let mut at = Point::new(card.rect.x + TOOLTIP_PAD, card.rect.y + TOOLTIP_PAD);
+        for (text, color) in rows.chain([(hint, hint_color)]) {
+            let size = measure_text(text, REGION_TEXT_SIZE);
+            renderer.fill_text(
+                make_text(text, REGION_TEXT_SIZE, size),
+                at,
+                color,
+                Rectangle::new(at, size),
+            );
+            at.y += size.height + TOOLTIP_GAP;
+        }
+    }
+
+    fn draw_markers(
+        &self,
+        renderer: &mut iced::Renderer,
+        theme: &iced::Theme,
+        bounds: Rectangle,
+        uv_range: [f32; 2],
+    ) {
+        let state = self.state.borrow();
+        if state.sample_rate <= 0.0 {
+            return;
+        }
+        let (min_f, nyq) = display_axis(state.sample_rate);
+        let (scale, horizontal) = (state.settings.frequency_scale, state.freq_axis_is_horizontal());
+        let ascends = state.freq_ascends();
+        let (org, ext) = if horizontal {
+            (bounds.x, bounds.width)
+        } else {
+            (bounds.y, bounds.height)
+        };
+        // Same mapping as the piano roll, minus the clamp so bands can be clipped.
+        let to_axis = |f: f32| {
+            let t = (scale.pos_of(min_f, nyq, f) - uv_range[0]) / (uv_range[1] - uv_range[0]);
+            org + ext * if ascends { t } else { 1.0 - t }
+        };
+        let markers = &state.settings.markers;
+        let vertical = !horizontal;
+        draw_frequency_markers(renderer, theme, bounds, markers, vertical, state.opacity, to_axis);
+    }
+
+    // Gridlines back from the newest column, and the span of the whole pane
+    // in its bottom left corner.
+    fn draw_time_scale(
+        &self,
+        renderer: &mut iced::Renderer,
+        theme: &iced::Theme,
+        bounds: Rectangle,
+        grid: bool,
+        label: bool,
+    ) {
+        let state = self.state.borrow();
+        let (Some(rate), Some(span)) = (state.columns_per_second(), state.visible_seconds()) else {
+            return;
+        };
+        if grid && let Some(step) = time_grid_step(rate * state.time_zoom) {
+            let color = with_alpha(theme::border_color(theme, false), 0.6 * state.opacity);
+            let step = step * rate;
+            let end = state.time_pan + span * rate;
+            let mut age = ((state.time_pan / step).floor() + 1.0) * step;
+            while age < end {
+                fill_rect(renderer, state.time_line(bounds, age.round()), color);
+                age += step;
+            }
+        }
+        if label {
+            let inset = if state.settings.piano_roll_overlay == PianoRollOverlay::Left {
+                PIANO_ROLL_WIDTH
+            } else {
+                0.0
+            };
+            let text = if state.time_pan > 0.0 {
+                let ago = state.numbers.duration(state.time_pan / rate);
+                format!("{} ending {ago} ago", state.numbers.duration(span))
+            } else {
+                format!("last {}", state.numbers.duration(span))
+            };
+            let size = measure_text(&text, TIME_LABEL_SIZE);
+            let at = Point::new(
+                bounds.x + inset + TOOLTIP_PAD,
+                bounds.y + bounds.height - size.height - TOOLTIP_GAP,
+            );
+            let pal = theme.extended_palette();
+            let clip = Rectangle::new(at, size);
+            fill_rect(renderer, clip, with_alpha(pal.background.base.color, 0.6 * state.opacity));
+            let color = with_alpha(pal.background.base.text, 0.85 * state.opacity);
+            renderer.fill_text(make_text(&text, TIME_LABEL_SIZE, size), at, color, clip);
+        }
+    }
+
+    // A vertical gradient from floor (bottom) to ceiling with dB ticks on its
+    // left, kept clear of a right-hand piano roll.
+    fn draw_scale_bar(
+        &self,
+        renderer: &mut iced::Renderer,
+        theme: &iced::Theme,
+        bounds: Rectangle,
+    ) {
+        let state = self.state.borrow();
+        let (levels, _) = state.display_levels();
+        let (floor, ceiling) = (levels.floor_db, levels.ceiling_db);
+        let opacity = state.opacity;
+        let height = (bounds.height - SCALE_BAR_MARGIN * 2.0).min(SCALE_BAR_MAX_HEIGHT);
+        if height < SCALE_LABEL_SIZE * 3.0 {
+            return;
+        }
+        let inset = if state.settings.piano_roll_overlay == PianoRollOverlay::Right {
+            PIANO_ROLL_WIDTH
+        } else {
+            0.0
+        };
+        let bar = Rectangle::new(
+            Point::new(
+                bounds.x + bounds.width - inset - SCALE_BAR_MARGIN - SCALE_BAR_WIDTH,
+                bounds.y + (bounds.height - height) * 0.5,
+            ),
+            Size::new(SCALE_BAR_WIDTH, height),
+        );
+        let level_at = |y: f32| ceiling - (y - bar.y) / bar.height * (ceiling - floor);
+        let y_of = |db: f32| bar.y + (ceiling - db) / (ceiling - floor) * bar.height;
+
+        let strips = height.round() as usize;
+        for strip in 0..strips {
+            let y = bar.y + strip as f32;
+            let rect = Rectangle::new(Point::new(bar.x, y), Size::new(bar.width, 1.0));
+            fill_rect(renderer, rect, faded(state.level_color(level_at(y + 0.5)), opacity));
+        }
+        let border = faded(theme::border_color(theme, false), opacity);
+        fill_bordered_rect(
+            renderer,
+            bar,
+            Color::TRANSPARENT,
+            iced::Border {
+                color: border,
+                width: 1.0,
+                ..Default::default()
+            },
+        );
+
+        let pal = theme.extended_palette();
+        let text_color = with_alpha(pal.background.base.text, 0.85 * opacity);
+        let shadow = with_alpha(pal.background.base.color, 0.6 * opacity);
+        let ticks = scale_ticks(floor, ceiling);
+        for (i, &db) in ticks.iter().enumerate() {
+            let y = y_of(db);
+            let tick = Rectangle::new(Point::new(bar.x - 3.0, y), Size::new(3.0, 1.0));
+            fill_rect(renderer, tick, border);
+            // The top label carries the unit.
+            let text = if i + 1 == ticks.len() {
+                format!("{db:.0} dB")
+            } else {
+                format!("{db:.0}")
+            };
+            let size = measure_text(&text, SCALE_LABEL_SIZE);
+            let at = Point::new(bar.x - 5.0 - size.width, y - size.height * 0.5);
+            let clip = Rectangle::new(at, size);
+            fill_rect(renderer, clip, shadow);
+            renderer.fill_text(make_text(&text, SCALE_LABEL_SIZE, size), at, text_color, clip);
+        }
+    }
+
+    // Each tone gets a line along its held span, drawn beside the trace so
+    // the tone itself stays visible, and a label at the newest edge.
+    fn draw_tones(
+        &self,
+        renderer: &mut iced::Renderer,
+        theme: &iced::Theme,
+        bounds: Rectangle,
+        uv_range: [f32; 2],
+    ) {
+        let state = self.state.borrow();
+        let tones = state.tones.tracker.tones();
+        if tones.is_empty() || state.sample_rate <= 0.0 || state.hop_size == 0 {
+            return;
+        }
+        let (min_f, nyq) = display_axis(state.sample_rate);
+        let (scale, rot) = (state.settings.frequency_scale, state.rotation_index());
+        let (horizontal, ascends) = (matches!(rot, 1 | 3), state.freq_ascends());
+        let (freq_org, freq_ext, time_org, time_ext) = if horizontal {
+            (bounds.x, bounds.width, bounds.y, bounds.height)
+        } else {
+            (bounds.y, bounds.height, bounds.x, bounds.width)
+        };
+        let to_axis = |f: f32| {
+            let t = (scale.pos_of(min_f, nyq, f) - uv_range[0]) / (uv_range[1] - uv_range[0]);
+            freq_org + freq_ext * if ascends { t } else { 1.0 - t }
+        };
+        let orient = |freq: f32, time: f32, freq_len: f32, time_len: f32| {
+            if horizontal {
+                Rectangle::new(Point::new(freq, time), Size::new(freq_len, time_len))
+            } else {
+                Rectangle::new(Point::new(time, freq), Size::new(time_len, freq_len))
+            }
+        };
+        // Mirrors time_ago_at_cursor: rotations 0 and 1 scroll towards the end.
+        let newest_at_end = matches!(rot, 0 | 1);
+        let roll_on_newest = match state.settings.piano_roll_overlay {
+            PianoRollOverlay::Right => newest_at_end,
+            PianoRollOverlay::Left => !newest_at_end,
+            PianoRollOverlay::Off => false,
+        };
+        let inset = TOOLTIP_PAD + if roll_on_newest { PIANO_ROLL_WIDTH } else { 0.0 };
+        let columns_per_second = state.sample_rate / state.hop_size as f32;
+        let held = state.history.col_count as f32;
+
+        let pal = theme.extended_palette();
+        let (color, backdrop) = (
+            faded(pal.warning.base.color, state.opacity),
+            with_alpha(pal.background.strong.color, TOOLTIP_BG_ALPHA * state.opacity),
+        );
+        for tone in tones {
+            let pos = to_axis(tone.hz);
+            if !pos.is_finite() || pos < freq_org || pos > freq_org + freq_ext {
+                continue;
+            }
+            let line = if horizontal { pos + TONE_LINE_GAP } else { pos - TONE_LINE_GAP };
+            // Tones run up to now, so panning back shortens them.
+            let columns = (tone.seconds * columns_per_second).min(held) - state.time_pan;
+            let len = (columns * state.time_zoom).min(time_ext);
+            if len <= 0.0 {
+                continue;
+            }
+            let start = if newest_at_end { time_org + time_ext - len } else { time_org };
+            fill_rect(renderer, orient(line, start, 1.0, len), with_alpha(color, 0.8 * color.a));
+
+            let (freq, age) = (state.numbers.freq(tone.hz), state.numbers.duration(tone.seconds));
+            let text = format!("{freq} \u{00b7} {age}");
+            let size = measure_text(&text, TONE_LABEL_SIZE);
+            let (freq_len, time_len) = if horizontal {
+                (size.width, size.height)
+            } else {
+                (size.height, size.width)
+            };
+            let freq_at = if horizontal {
+                line + TOOLTIP_GAP
+            } else {
+                line - TOOLTIP_GAP - freq_len
+            };
+            let freq_at = freq_at.clamp(freq_org, (freq_org + freq_ext - freq_len).max(freq_org));
+            let time_at = if newest_at_end {
+                time_org + time_ext - inset - time_len
+            } else {
+                time_org + inset
+            };
+            let rect = orient(freq_at, time_at, freq_len, time_len);
+            fill_rect(renderer, rect, backdrop);
+            renderer.fill_text(
+                make_text(text, TONE_LABEL_SIZE, size),
+                rect.position(),
+                color,
+                rect,
+            );
+        }
+    }
+
     fn draw_piano_roll(
         &self,
         renderer: &mut iced::Renderer,
@@ -533,23 +1677,24 @@ impl<'a> Spectrogram<'a> {
             return;
         }
         let (min_f, nyq) = display_axis(state.sample_rate);
-        let (scale, rot) = (state.settings.frequency_scale, state.rotation_index());
+        let (scale, horizontal) = (state.settings.frequency_scale, state.freq_axis_is_horizontal());
+        let ascends = state.freq_ascends();
+        let (tuning, opacity) = (state.tuning, state.opacity);
         drop(state);
-        let horizontal = matches!(rot, 1 | 3);
 
         let (freq_top, freq_bot) = (
             scale.freq_at(min_f, nyq, uv_range[1]),
             scale.freq_at(min_f, nyq, uv_range[0]),
         );
-        let midi_lo = MusicalNote::from_frequency(freq_bot.max(16.0))
+        let midi_lo = MusicalNote::from_frequency(freq_bot.max(16.0), tuning)
             .map_or(PIANO_MIDI_LO, |n| (n.midi_number - 1).max(PIANO_MIDI_LO));
-        let midi_hi = MusicalNote::from_frequency(freq_top)
+        let midi_hi = MusicalNote::from_frequency(freq_top, tuning)
             .map_or(PIANO_MIDI_HI, |n| (n.midi_number + 1).min(PIANO_MIDI_HI));
 
         let pal = theme.extended_palette();
         let (white, black) = (
-            lerp_color(pal.background.weak.color, Color::WHITE, 0.5),
-            Color::from_rgb(0.1, 0.1, 0.1),
+            faded(lerp_color(pal.background.weak.color, Color::WHITE, 0.5), opacity),
+            Color::from_rgba(0.1, 0.1, 0.1, opacity),
         );
         let (freq_org, freq_ext, time_org, time_ext) = if horizontal {
             (bounds.x, bounds.width, bounds.y, bounds.height)
@@ -561,7 +1706,7 @@ impl<'a> Spectrogram<'a> {
         let freq_to_px = |f: f32| -> f32 {
             let uv = scale.pos_of(min_f, nyq, f);
             let t = ((uv - uv_range[0]) / (uv_range[1] - uv_range[0])).clamp(0.0, 1.0);
-            freq_org + freq_ext * if matches!(rot, 1 | 2) { t } else { 1.0 - t }
+            freq_org + freq_ext * if ascends { t } else { 1.0 - t }
         };
 
         let strip = match overlay {
@@ -570,7 +1715,7 @@ impl<'a> Spectrogram<'a> {
             PianoRollOverlay::Off => return,
         };
         let wborder = iced::Border {
-            color: with_alpha(black, 0.4),
+            color: with_alpha(black, 0.4 * opacity),
             width: 0.5,
             radius: 0.0.into(),
         };
@@ -618,7 +1763,7 @@ impl<'a> Spectrogram<'a> {
                 if is_blk != (pass == 1) {
                     continue;
                 }
-                let (lo, hi) = key_extent(midi, note.to_frequency(), is_blk);
+                let (lo, hi) = key_extent(midi, note.to_frequency(tuning), is_blk);
                 if hi < freq_org || lo > freq_org + freq_ext {
                     continue;
                 }
@@ -681,7 +1826,7 @@ impl<'a, Message> Widget<Message, iced::Theme, iced::Renderer> for Spectrogram<'
         layout: Layout<'_>,
         _: mouse::Cursor,
         _: &iced::Renderer,
-        _: &mut dyn iced::advanced::Clipboard,
+        clipboard: &mut dyn iced::advanced::Clipboard,
         shell: &mut iced::advanced::Shell<'_, Message>,
         _: &Rectangle,
     ) {
@@ -690,29 +1835,69 @@ impl<'a, Message> Widget<Message, iced::Theme, iced::Renderer> for Spectrogram<'
         match event {
             iced::Event::Mouse(mouse::Event::CursorMoved { position }) => {
                 st.cursor = b.contains(*position).then_some(*position);
-                if st.left_held || st.drag.is_some() {
+                self.state.borrow_mut().hovered = st.cursor.is_some();
+                if let Some((_, corner)) = &mut st.selecting {
+                    *corner = Point::new(
+                        position.x.clamp(b.x, b.x + b.width),
+                        position.y.clamp(b.y, b.y + b.height),
+                    );
+                }
+                if st.left_held || st.drag.is_some() || st.selecting.is_some() {
                     shell.request_redraw();
                 }
-                if let Some((origin, start_pan)) = st.drag {
+                if let Some((origin, start_pan, start_time_pan)) = st.drag {
                     let mut state = self.state.borrow_mut();
                     let h = 0.5 / state.zoom;
                     let horiz = state.freq_axis_is_horizontal();
-                    let extent = if horiz { b.width } else { b.height };
-                    let current = if horiz { position.x } else { position.y };
-                    let sign = if matches!(state.rotation_index(), 1 | 2) {
-                        -1.0
+                    let pane = state.pane_at(b, *position);
+                    let extent = if horiz { pane.width } else { pane.height };
+                    let moved = if horiz {
+                        position.x - origin.x
                     } else {
-                        1.0
+                        position.y - origin.y
                     };
-                    state.pan = (start_pan + sign * (current - origin) / extent / state.zoom)
-                        .clamp(h, 1.0 - h);
+                    let sign = if state.freq_ascends() { -1.0 } else { 1.0 };
+                    state.pan =
+                        (start_pan + sign * moved / extent / state.zoom).clamp(h, 1.0 - h);
+                    // The history follows the pointer along the time axis.
+                    let aged = state.column_age(*position, pane) - state.column_age(origin, pane);
+                    state.time_pan = start_time_pan - aged;
+                    state.clamp_time_pan();
                 }
             }
-            iced::Event::Mouse(mouse::Event::CursorLeft) => st.cursor = None,
+            iced::Event::Window(window::Event::RedrawRequested(now)) => {
+                st.frame_at = Some(*now);
+                if self.state.borrow().scroll.columns > 0.0 {
+                    shell.request_redraw();
+                }
+            }
+            iced::Event::Mouse(mouse::Event::CursorLeft) => {
+                st.cursor = None;
+                self.state.borrow_mut().hovered = false;
+            }
             iced::Event::Keyboard(keyboard::Event::ModifiersChanged(m)) => st.modifiers = *m,
+            iced::Event::Mouse(mouse::Event::WheelScrolled { delta })
+                if st.modifiers.control() && st.modifiers.shift() =>
+            {
+                if let Some(pos) = st.cursor.filter(|p| b.contains(*p)) {
+                    // Shift turns the wheel sideways on some platforms.
+                    let lines = match *delta {
+                        mouse::ScrollDelta::Lines { x, y } => y + x,
+                        mouse::ScrollDelta::Pixels { x, y } => (y + x) / 50.0,
+                    };
+                    let mut state = self.state.borrow_mut();
+                    let pane = state.pane_at(b, pos);
+                    state.time_zoom_at(pos, pane, ZOOM_STEP.powf(lines));
+                    shell.request_redraw();
+                    shell.capture_event();
+                }
+            }
             iced::Event::Mouse(mouse::Event::WheelScrolled { delta }) if st.modifiers.control() => {
                 if let Some(pos) = st.cursor.filter(|p| b.contains(*p)) {
-                    let freq_norm = self.state.borrow().freq_axis_norm(pos, b).unwrap_or(0.5);
+                    let freq_norm = {
+                        let state = self.state.borrow();
+                        state.freq_axis_norm(pos, state.pane_at(b, pos)).unwrap_or(0.5)
+                    };
                     self.state
                         .borrow_mut()
                         .zoom_at(freq_norm, ZOOM_STEP.powf(scroll_delta_lines(*delta)));
@@ -722,32 +1907,47 @@ impl<'a, Message> Widget<Message, iced::Theme, iced::Renderer> for Spectrogram<'
             }
             iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Middle)) => {
                 let state = self.state.borrow();
-                if let Some(pos) = st
-                    .cursor
-                    .filter(|p| b.contains(*p) && state.zoom > MIN_ZOOM)
-                {
-                    let origin = if state.freq_axis_is_horizontal() {
-                        pos.x
-                    } else {
-                        pos.y
-                    };
-                    st.drag = Some((origin, state.pan));
+                let pannable = state.zoom > MIN_ZOOM
+                    || state.history.col_count as f32 > state.visible_columns();
+                if let Some(pos) = st.cursor.filter(|p| b.contains(*p) && pannable) {
+                    st.drag = Some((pos, state.pan, state.time_pan));
                     shell.capture_event();
                 }
             }
             iced::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Middle)) => {
                 st.drag = None;
             }
-            iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
-                if st.cursor.is_some_and(|p| b.contains(p)) => {
-                    st.left_held = true;
-                    shell.request_redraw();
+            iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                let Some(pos) = st.cursor.filter(|p| b.contains(*p)) else { return };
+                if let Some(card) = st.region.as_mut().filter(|card| card.rect.contains(pos)) {
+                    clipboard.write(clipboard::Kind::Standard, card.lines.join("\n"));
+                    card.copied = true;
+                    shell.capture_event();
+                } else {
+                    st.region = None;
+                    self.state.borrow_mut().selection = None;
+                    if st.modifiers.shift() {
+                        st.selecting = Some((pos, pos));
+                        shell.capture_event();
+                    } else {
+                        st.left_held = true;
+                    }
                 }
-            iced::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
-                if st.left_held => {
-                    st.left_held = false;
-                    shell.request_redraw();
+                shell.request_redraw();
+            }
+            iced::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                if let Some((from, to)) = st.selecting.take() {
+                    let mut state = self.state.borrow_mut();
+                    let (tuning, numbers) = (state.tuning, state.numbers);
+                    st.region = state
+                        .select_region(b, from, to)
+                        .map(|stats| RegionCard::new(b, to, stats.lines(tuning, numbers)));
+                } else if !st.left_held {
+                    return;
                 }
+                st.left_held = false;
+                shell.request_redraw();
+            }
             _ => {}
         }
     }
@@ -763,42 +1963,128 @@ impl<'a, Message> Widget<Message, iced::Theme, iced::Renderer> for Spectrogram<'
         _: &Rectangle,
     ) {
         let bounds = layout.bounds();
-        let (uv_y_range, piano_roll, bg, params);
+        let (uv_y_range, piano_roll, has_markers, has_tones, scale_bar, bg, panes, params, refined);
+        let (pitch, time_grid, calibrated, software, opacity);
+        let interaction = tree.state.downcast_ref::<InteractionState>();
         {
             let mut state = self.state.borrow_mut();
             let (bw, bh) = (
                 bounds.width.round().max(1.0) as u32,
                 bounds.height.round().max(1.0) as u32,
             );
+            state.drawn_bounds = Some(bounds);
             state.view_width = if matches!(state.rotation_index(), 1 | 3) {
                 bh
             } else {
                 bw
             };
+            if let Some(rate) = state.columns_per_second() {
+                let now = interaction.frame_at.unwrap_or_else(Instant::now);
+                state.scroll.advance(now, rate);
+            }
             uv_y_range = state.uv_y_range();
             piano_roll = state.settings.piano_roll_overlay;
-            bg = state.style.background;
-            params = state.visual_params(bounds, uv_y_range);
+            has_markers = !state.settings.markers.is_empty();
+            has_tones = !state.tones.tracker.tones().is_empty();
+            pitch = state.settings.pitch_readout.then(|| state.pitch_lines());
+            scale_bar = state.settings.scale_bar;
+            time_grid = state.settings.time_grid;
+            calibrated = state.calibrated;
+            opacity = state.opacity;
+            bg = faded(state.style.background, opacity);
+            panes = state.panes(bounds);
+            params = panes
+                .iter()
+                .enumerate()
+                .map(|(i, &pane)| state.channel_params(HistorySource::Live(i), pane, uv_y_range))
+                .collect::<Vec<_>>();
+            software = software_rendering().then(|| {
+                panes
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, &pane)| state.software_cells(i, pane, uv_y_range))
+                    .collect::<Vec<_>>()
+            });
+            refined = panes
+                .iter()
+                .enumerate()
+                .filter(|_| software.is_none())
+                .filter_map(|(i, &pane)| {
+                    let rect = state.refined_rect(pane)?;
+                    let params = state.channel_params(HistorySource::Refined(i), rect, uv_y_range)?;
+                    Some((pane, params))
+                })
+                .collect::<Vec<_>>();
         }
-        let interaction = tree.state.downcast_ref::<InteractionState>();
         fill_rect(renderer, bounds, bg);
-        if let Some(p) = params {
-            renderer.draw_primitive(bounds, SpectrogramPrimitive::new(p));
+        if let Some(cells) = software {
+            for (rect, color) in cells {
+                fill_rect(renderer, rect, faded(color, opacity));
+            }
+        } else {
+            for (&pane, p) in panes.iter().zip(params) {
+                if let Some(p) = p {
+                    draw_shader(renderer, theme, pane, SpectrogramPrimitive::new(p));
+                }
+            }
         }
-        if piano_roll != PianoRollOverlay::Off {
+        for (pane, p) in refined {
+            renderer.with_layer(pane, |r| {
+                draw_shader(r, theme, p.bounds, SpectrogramPrimitive::new(p));
+            });
+        }
+        for (i, &pane) in panes.iter().enumerate() {
+            let label = i + 1 == panes.len();
+            if time_grid || label {
+                renderer.with_layer(pane, |r| {
+                    self.draw_time_scale(r, theme, pane, time_grid, label);
+                });
+            }
+            if has_markers {
+                renderer.with_layer(pane, |r| self.draw_markers(r, theme, pane, uv_y_range));
+            }
+            if piano_roll != PianoRollOverlay::Off {
+                renderer.with_layer(pane, |r| {
+                    self.draw_piano_roll(r, theme, pane, piano_roll, uv_y_range);
+                });
+            }
+        }
+        // Tones come from the left (or only) channel.
+        if has_tones && let Some(&pane) = panes.first() {
+            renderer.with_layer(pane, |r| self.draw_tones(r, theme, pane, uv_y_range));
+        }
+        if let Some(lines) = &pitch && let Some(&pane) = panes.first() {
+            let inset = if piano_roll == PianoRollOverlay::Left { PIANO_ROLL_WIDTH } else { 0.0 };
+            renderer.with_layer(pane, |r| {
+                Self::draw_pitch(r, theme, pane, inset, lines, opacity);
+            });
+        }
+        if panes.len() > 1 {
+            renderer.with_layer(bounds, |r| Self::draw_pane_labels(r, theme, &panes, opacity));
+        }
+        if scale_bar && let Some(&pane) = panes.last() {
+            renderer.with_layer(pane, |r| self.draw_scale_bar(r, theme, pane));
+        }
+        if calibrated {
             renderer.with_layer(bounds, |r| {
-                self.draw_piano_roll(r, theme, bounds, piano_roll, uv_y_range);
+                draw_badge(r, theme, bounds, "Mic calibration", opacity);
             });
         }
         if interaction.left_held
             && let Some(c) = interaction.cursor
-            && bounds.contains(c)
+            && let Some(&pane) = panes.iter().find(|pane| pane.contains(c))
         {
             renderer.with_layer(bounds, |r| {
-                Self::draw_crosshair(r, theme, bounds, c);
-                self.draw_tooltip(r, theme, bounds, c, uv_y_range);
+                Self::draw_crosshair(r, theme, pane, c, opacity);
+                self.draw_tooltip(r, theme, pane, c, uv_y_range);
             });
         }
+        if let Some((from, to)) = interaction.selecting {
+            renderer.with_layer(bounds, |r| Self::draw_selection(r, theme, from, to, opacity));
+        }
+        if let Some(card) = &interaction.region {
+            renderer.with_layer(bounds, |r| Self::draw_region_card(r, theme, card, opacity));
+        }
     }
 
     fn mouse_interaction(
@@ -841,10 +2127,12 @@ mod tests {
             reset,
             points_per_column: 2,
             reassigned_power_scale: 1.0,
+            calibrated: false,
+            right: None,
             new_columns: values
                 .iter()
                 .map(|&v| {
-                    SpectrogramColumn::Classic(vec![super::super::processor::pack_classic_db(v); 2])
+                    SpectrogramColumn::Classic(vec![super::super::processor::pack_classic_power(v); 2])
                 })
                 .collect(),
         }
@@ -860,6 +2148,8 @@ mod tests {
             reset,
             points_per_column: 8,
             reassigned_power_scale: 0.25,
+            calibrated: false,
+            right: None,
             new_columns: counts
                 .iter()
                 .map(|&n| {
@@ -904,6 +2194,78 @@ mod tests {
         state
     }
 
+    #[test]
+    fn time_span_follows_history_and_pane_width() {
+        let mut state = SpectrogramState::new();
+        let mut update = classic_update(1_000, true, &[0.0]);
+        update.hop_size = 480;
+        state.apply_snapshot(update);
+        // 100 columns a second; the pane shows 400 of the 1000 held.
+        state.view_width = 400;
+        assert_eq!(state.visible_seconds(), Some(4.0));
+        state.view_width = 2_000;
+        assert_eq!(state.visible_seconds(), Some(10.0));
+        assert_eq!(time_grid_step(100.0), Some(1.0));
+        assert_eq!(time_grid_step(10.0), Some(5.0));
+        let line = state.time_line(Rectangle::new(Point::ORIGIN, Size::new(500.0, 80.0)), 100.0);
+        assert_eq!((line.x, line.height), (400.0, 80.0));
+    }
+
+    #[test]
+    fn time_zoom_keeps_the_cursor_column_and_pans_within_history() {
+        let mut state = SpectrogramState::new();
+        state.apply_snapshot(classic_update(1_000, true, &[0.0; 1_000]));
+        state.view_width = 400;
+        let pane = Rectangle::new(Point::ORIGIN, Size::new(400.0, 80.0));
+        let cursor = Point::new(300.0, 40.0);
+        assert_eq!(state.column_age(cursor, pane), 100.0);
+        state.time_zoom_at(cursor, pane, 4.0);
+        assert_eq!(state.visible_columns(), 100.0);
+        assert_eq!(state.column_age(cursor, pane), 100.0);
+        assert_eq!(state.time_pan, 75.0);
+        // Past the oldest held column the pan stops.
+        state.time_pan = 5_000.0;
+        state.clamp_time_pan();
+        assert_eq!(state.time_pan, 900.0);
+        let line = state.time_line(pane, 910.0);
+        assert_eq!(line.x, 360.0);
+    }
+
+    #[test]
+    fn popout_override_replaces_floor_and_contrast() {
+        let mut state = seeded_ring();
+        let docked = visual_params(&mut state);
+
+        state.set_display_override(Some(DisplayOverride {
+            floor_db: -60.0,
+            gamma: f32::NAN,
+        }));
+        let popped = visual_params(&mut state);
+        assert_eq!((popped.floor_db, popped.gamma), (-60.0, 1.0));
+
+        state.set_display_override(None);
+        let params = visual_params(&mut state);
+        assert_eq!((params.floor_db, params.gamma), (docked.floor_db, docked.gamma));
+    }
+
+    #[test]
+    fn inverted_axis_flips_texture_and_cursor_together() {
+        let mut state = seeded_ring();
+        let bounds = Rectangle::new(Point::new(0.0, 0.0), Size::new(10.0, 10.0));
+        let (top, bottom) = (Point::new(5.0, 1.0), Point::new(5.0, 9.0));
+        let at = |state: &SpectrogramState, point| {
+            state.frequency_at_cursor(point, bounds, [0.0, 1.0]).unwrap()
+        };
+        assert!(at(&state, top) > at(&state, bottom));
+        assert_eq!(visual_params(&mut state).uv_y_range, [0.0, 1.0]);
+
+        let mut settings = state.settings.clone();
+        settings.invert_frequency = true;
+        state.update_view_settings(&settings);
+        assert!(at(&state, top) < at(&state, bottom));
+        assert_eq!(visual_params(&mut state).uv_y_range, [1.0, 0.0]);
+    }
+
     #[test]
     fn resize_copy_plans_preserve_visible_columns() {
         let mut state = seeded_ring();
@@ -950,4 +2312,43 @@ mod tests {
             vec![0, 2, 1]
         );
     }
+
+    #[test]
+    fn busy_reassigned_columns_shrink_the_ring_within_the_cap() {
+        let mut history = SpectrogramHistory::default();
+        history.apply_update(reassigned_update(100, true, &[1]), 800);
+        // Estimated at half the bins per slot: 4 points and 8 bands, 80 bytes.
+        assert_eq!(history.ring_capacity, 10);
+
+        history.apply_update(reassigned_update(100, false, &[40; 3]), 800);
+        assert_eq!(history.reassigned_points_per_slot, 40);
+        assert_eq!(history.ring_capacity, 1);
+        assert!(u64::from(history.ring_capacity) * history.column_bytes() <= 800);
+    }
+
+    #[test]
+    fn column_bursts_slide_in_and_long_stalls_jump() {
+        let (mut lag, t0) = (ScrollLag::default(), Instant::now());
+        let at = |ms| t0 + Duration::from_millis(ms);
+        // 100 columns a second: the burst drains at 100 + 10 / 0.1 a second.
+        lag.push(10, 100.0, t0);
+        assert!((lag.advance(at(20), 100.0) - 6.0).abs() < 1e-3);
+        assert_eq!(lag.advance(at(70), 100.0), 0.0);
+        // Arrivals between frames don't move the clock: the next frame drains
+        // its whole 10 ms interval.
+        lag.push(5, 100.0, at(75));
+        assert!((lag.advance(at(80), 100.0) - 3.5).abs() < 1e-3);
+        // A quarter second's worth at once isn't worth catching up on.
+        lag.push(30, 100.0, at(80));
+        assert_eq!(lag.columns, 0.0);
+    }
+
+    #[test]
+    fn scale_ticks_land_on_round_levels() {
+        assert_eq!(scale_ticks(-96.0, 0.0), [-80.0, -60.0, -40.0, -20.0, 0.0]);
+        assert_eq!(scale_ticks(-30.0, 0.0), [-30.0, -24.0, -18.0, -12.0, -6.0, 0.0]);
+        assert_eq!(scale_ticks(-200.0, -1.0).first(), Some(&-200.0));
+        assert_eq!(scale_ticks(-200.0, -1.0).len(), 5);
+        assert!(scale_ticks(0.0, 0.0).is_empty());
+    }
 }
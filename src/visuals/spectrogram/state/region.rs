@@ -0,0 +1,355 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Coarse CPU copy of the spectrogram history for region statistics and the
+// software fallback; the full-resolution columns only live on the GPU. FFT
+// bins are summed into at most REGION_MAX_BANDS bands and packed like classic
+// columns. Each band also keeps where its loudest component sat, at full
+// resolution, so peak tracks aren't snapped to band centres.
+
+use super::super::processor::{
+    REGION_MAX_BANDS, SpectrogramColumn, pack_classic_power, unpack_classic_power,
+};
+use crate::util::audio::musical::{NoteInfo, Tuning};
+use crate::util::audio::{DB_FLOOR, NumberFormat, db_to_power, power_to_db};
+use std::collections::VecDeque;
+use std::ops::Range;
+
+#[derive(Debug, Default)]
+pub(super) struct ColumnStore {
+    bin_hz: f32,
+    bins_per_band: usize,
+    bands: usize,
+    capacity: usize,
+    reassigned_scale: f32,
+    // Newest last.
+    columns: VecDeque<Column>,
+    // Columns pushed since the layout last changed; the newest is `pushed - 1`.
+    pushed: u64,
+    power: Vec<f32>,
+    // Per band: (power, frequency) of the loudest component this push.
+    loudest: Vec<(f32, f32)>,
+}
+
+#[derive(Debug)]
+struct Column {
+    power: Box<[u16]>,
+    // Where the loudest component sat across its band, 0 at the low edge.
+    peak: Box<[u16]>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct RegionStats {
+    pub lo_hz: f32,
+    pub hi_hz: f32,
+    pub seconds: f32,
+    pub mean_db: f32,
+    pub max_db: f32,
+    pub energy_db: f32,
+    pub dominant_hz: f32,
+}
+
+impl RegionStats {
+    pub(super) fn lines(&self, tuning: Tuning, numbers: NumberFormat) -> [String; 4] {
+        let dominant = numbers.freq(self.dominant_hz);
+        [
+            format!(
+                "{} - {}   {}",
+                numbers.freq(self.lo_hz),
+                numbers.freq(self.hi_hz),
+                numbers.duration(self.seconds)
+            ),
+            numbers.localize(format!("mean {:.1} dB   max {:.1} dB", self.mean_db, self.max_db)),
+            numbers.localize(format!("energy {:.1} dB·s", self.energy_db)),
+            match NoteInfo::from_frequency(self.dominant_hz, tuning) {
+                Some(ni) => format!("dominant {dominant}   {}{:+}", ni.note, ni.cents),
+                None => format!("dominant {dominant}"),
+            },
+        ]
+    }
+}
+
+impl ColumnStore {
+    /// Drops everything when the bin layout changes; otherwise only trims to `capacity`.
+    pub(super) fn configure(
+        &mut self,
+        bins: usize,
+        bin_hz: f32,
+        reassigned_scale: f32,
+        capacity: usize,
+    ) {
+        let bins_per_band = bins.div_ceil(REGION_MAX_BANDS).max(1);
+        let bands = bins.div_ceil(bins_per_band);
+        if bands != self.bands || bins_per_band != self.bins_per_band || bin_hz != self.bin_hz {
+            *self = Self {
+                bin_hz,
+                bins_per_band,
+                bands,
+                ..Self::default()
+            };
+        }
+        self.reassigned_scale = reassigned_scale;
+        self.capacity = capacity;
+        while self.columns.len() > capacity {
+            self.columns.pop_front();
+        }
+    }
+
+    pub(super) fn push(&mut self, column: &SpectrogramColumn) {
+        if self.bands == 0 || self.capacity == 0 || self.bin_hz <= 0.0 {
+            return;
+        }
+        self.power.clear();
+        self.power.resize(self.bands, 0.0);
+        self.loudest.clear();
+        self.loudest.resize(self.bands, (0.0, 0.0));
+        let band = |bin: usize| bin / self.bins_per_band;
+        match column {
+            SpectrogramColumn::Classic(mags) => {
+                for (bin, &code) in mags.iter().enumerate() {
+                    let power = unpack_classic_power(code);
+                    if let Some(sum) = self.power.get_mut(band(bin)) {
+                        *sum += power;
+                    }
+                    if let Some(loudest) = self.loudest.get_mut(band(bin))
+                        && power > loudest.0
+                    {
+                        *loudest = (power, bin_peak_hz(mags, bin) * self.bin_hz);
+                    }
+                }
+            }
+            SpectrogramColumn::Reassigned(points) => {
+                for point in points {
+                    let bin = (point.freq_hz / self.bin_hz).round();
+                    if bin < 0.0 {
+                        continue;
+                    }
+                    let power = db_to_power(point.magnitude_db) * self.reassigned_scale;
+                    if let Some(sum) = self.power.get_mut(band(bin as usize)) {
+                        *sum += power;
+                    }
+                    if let Some(loudest) = self.loudest.get_mut(band(bin as usize))
+                        && power > loudest.0
+                    {
+                        *loudest = (power, point.freq_hz);
+                    }
+                }
+            }
+        }
+        let mut packed = if self.columns.len() >= self.capacity {
+            self.columns.pop_front()
+        } else {
+            None
+        }
+        .unwrap_or_else(|| Column {
+            power: vec![0; self.bands].into_boxed_slice(),
+            peak: vec![0; self.bands].into_boxed_slice(),
+        });
+        for (code, &power) in packed.power.iter_mut().zip(&self.power) {
+            *code = pack_classic_power(power);
+        }
+        for (band, (code, &(_, hz))) in packed.peak.iter_mut().zip(&self.loudest).enumerate() {
+            let across = (hz - self.band_low_hz(band)) / self.band_hz();
+            *code = (across.clamp(0.0, 1.0) * f32::from(u16::MAX)).round() as u16;
+        }
+        self.columns.push_back(packed);
+        self.pushed += 1;
+    }
+
+    fn band_hz(&self) -> f32 {
+        self.bin_hz * self.bins_per_band as f32
+    }
+
+    // A bin covers half a bin either side of its centre.
+    fn band_low_hz(&self, band: usize) -> f32 {
+        ((band * self.bins_per_band) as f32 - 0.5) * self.bin_hz
+    }
+
+    /// Columns `ages` back from the newest as lasting indices, which stay put
+    /// as later columns arrive.
+    pub(super) fn span(&self, ages: Range<usize>) -> Range<u64> {
+        let back = |age: usize| self.pushed.saturating_sub(age as u64);
+        back(ages.end)..back(ages.start)
+    }
+
+    /// The loudest band within `hz` for each retained column in `columns`,
+    /// oldest first, as (index, frequency, level in dB). The frequency is
+    /// where the band's loudest component sat, the level the band's total.
+    pub(super) fn peaks(&self, columns: Range<u64>, hz: Range<f32>) -> Vec<(u64, f32, f32)> {
+        if self.bin_hz <= 0.0 || self.bins_per_band == 0 {
+            return Vec::new();
+        }
+        let band_hz = self.band_hz();
+        let oldest = self.pushed - self.columns.len() as u64;
+        let mut peaks = Vec::new();
+        for (index, column) in (oldest..).zip(&self.columns) {
+            if !columns.contains(&index) {
+                continue;
+            }
+            let peak_hz = |band: usize| {
+                let across = f32::from(column.peak[band]) / f32::from(u16::MAX);
+                self.band_low_hz(band) + across * band_hz
+            };
+            let loudest = (0..self.bands)
+                .filter(|&band| hz.contains(&peak_hz(band)))
+                .map(|band| (band, unpack_classic_power(column.power[band])))
+                .filter(|&(_, power)| power > 0.0)
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+            if let Some((band, power)) = loudest {
+                peaks.push((index, peak_hz(band), power_to_db(power, DB_FLOOR)));
+            }
+        }
+        peaks
+    }
+
+    /// Level of the band holding `hz`, `age` columns back from the newest.
+    /// The band's total, so a tone reads at its own level.
+    pub(super) fn level_db(&self, age: usize, hz: f32) -> Option<f32> {
+        let column = self.columns.get(self.columns.len().checked_sub(age + 1)?)?;
+        let bin = (hz / self.bin_hz).round();
+        if bin.is_nan() || bin < 0.0 {
+            return None;
+        }
+        let code = *column.power.get(bin as usize / self.bins_per_band)?;
+        Some(power_to_db(unpack_classic_power(code), DB_FLOOR))
+    }
+
+    /// Statistics over columns `ages` back from the newest and frequencies in `hz`.
+    /// Mean and max are both of band totals, so a steady tone reads the same in each.
+    pub(super) fn stats(
+        &self,
+        ages: Range<usize>,
+        hz: Range<f32>,
+        column_seconds: f32,
+    ) -> Option<RegionStats> {
+        let len = self.columns.len();
+        let ages = ages.start.min(len)..ages.end.min(len);
+        let bins = (hz.start / self.bin_hz).ceil().max(0.0) as usize
+            ..(hz.end / self.bin_hz).floor().max(0.0) as usize + 1;
+        let bands = (bins.start / self.bins_per_band).min(self.bands)
+            ..bins.end.div_ceil(self.bins_per_band).min(self.bands);
+        if ages.is_empty() || bands.is_empty() {
+            return None;
+        }
+        let mut per_band = vec![0.0_f64; bands.len()];
+        let mut max = 0.0_f32;
+        for age in ages.clone() {
+            let column = &self.columns[len - 1 - age];
+            for (sum, &code) in per_band.iter_mut().zip(&column.power[bands.clone()]) {
+                let power = unpack_classic_power(code);
+                *sum += f64::from(power);
+                max = max.max(power);
+            }
+        }
+        let total: f64 = per_band.iter().sum();
+        let (dominant, _) = per_band
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))?;
+        let cells = (ages.len() * bands.len()) as f64;
+        let center_bin = (bands.start + dominant) * self.bins_per_band + self.bins_per_band / 2;
+        Some(RegionStats {
+            lo_hz: hz.start,
+            hi_hz: hz.end,
+            seconds: ages.len() as f32 * column_seconds,
+            mean_db: power_to_db((total / cells) as f32, DB_FLOOR),
+            max_db: power_to_db(max, DB_FLOOR),
+            energy_db: power_to_db((total * f64::from(column_seconds)) as f32, DB_FLOOR),
+            dominant_hz: center_bin as f32 * self.bin_hz,
+        })
+    }
+}
+
+// Fractional bin of the peak at `bin`, from a parabola through its
+// neighbours' levels.
+fn bin_peak_hz(mags: &[u16], bin: usize) -> f32 {
+    let db = |bin: usize| power_to_db(unpack_classic_power(mags[bin]), DB_FLOOR);
+    let offset = match bin.checked_sub(1).filter(|_| bin + 1 < mags.len()) {
+        Some(lo) => {
+            let (a, b, c) = (db(lo), db(bin), db(bin + 1));
+            let curve = a - 2.0 * b + c;
+            if curve < 0.0 { (0.5 * (a - c) / curve).clamp(-0.5, 0.5) } else { 0.0 }
+        }
+        None => 0.0,
+    };
+    bin as f32 + offset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::processor::SpectrogramPoint;
+    use super::*;
+
+    #[test]
+    fn stats_cover_only_the_selected_columns_and_bands() {
+        let mut store = ColumnStore::default();
+        store.configure(1025, 10.0, 1.0, 4);
+        assert_eq!((store.bins_per_band, store.bands), (3, 342));
+        let column = |tone_bin: usize, db: f32| {
+            let mut power = vec![0.0; 1025];
+            power[tone_bin] = db_to_power(db);
+            SpectrogramColumn::Classic(power.into_iter().map(pack_classic_power).collect())
+        };
+        // Oldest first; the capacity drops the first.
+        let tones = [
+            (10, 0.0),
+            (100, -20.0),
+            (100, -20.0),
+            (300, -10.0),
+            (100, -26.0),
+        ];
+        for (bin, db) in tones {
+            store.push(&column(bin, db));
+        }
+        assert_eq!(store.columns.len(), 4);
+
+        let stats = store.stats(1..4, 500.0..2000.0, 0.01).unwrap();
+        assert!((stats.max_db + 20.0).abs() < 0.1, "{stats:?}");
+        assert!((stats.dominant_hz - 1000.0).abs() <= 10.0, "{stats:?}");
+        // Two -20 dB columns over 0.02 s.
+        assert!((stats.energy_db - (-20.0 + 10.0 * 0.02_f32.log10())).abs() < 0.1);
+        assert!(stats.mean_db < stats.max_db);
+        // One band holding the same tone throughout.
+        let steady = store.stats(2..4, 990.0..1000.0, 0.01).unwrap();
+        assert!((steady.mean_db - steady.max_db).abs() < 0.1, "{steady:?}");
+
+        assert!(store.stats(0..4, 5000.0..6000.0, 0.01).unwrap().max_db <= DB_FLOOR + 1.0);
+        assert_eq!(store.stats(4..8, 500.0..2000.0, 0.01), None);
+
+        // The software fallback reads single cells back.
+        assert!((store.level_db(0, 1000.0).unwrap() + 26.0).abs() < 0.1);
+        assert!((store.level_db(1, 3000.0).unwrap() + 10.0).abs() < 0.1);
+        assert_eq!(store.level_db(4, 1000.0), None);
+
+        // The newest two columns, found again after another arrives.
+        let span = store.span(0..2);
+        store.push(&column(10, 0.0));
+        let peaks = store.peaks(span, 500.0..4000.0);
+        let summary: Vec<_> = peaks
+            .iter()
+            .map(|&(index, hz, db)| (index, hz.round(), db.round()))
+            .collect();
+        assert_eq!(summary, [(3, 3000.0, -10.0), (4, 1000.0, -26.0)]);
+    }
+
+    #[test]
+    fn peaks_keep_reassigned_frequencies_finer_than_a_band() {
+        let mut store = ColumnStore::default();
+        store.configure(8193, 2.0, 1.0, 4);
+        assert_eq!(store.bins_per_band, 17);
+        let point = |freq_hz, magnitude_db| SpectrogramPoint {
+            time_offset: 0.0,
+            freq_hz,
+            magnitude_db,
+        };
+        store.push(&SpectrogramColumn::Reassigned(
+            vec![point(1003.7, -12.0), point(1010.0, -30.0), point(4000.0, -40.0)],
+        ));
+        let [(_, hz, db)] = store.peaks(store.span(0..1), 900.0..1100.0)[..] else {
+            panic!("one column in the span");
+        };
+        assert!((hz - 1003.7).abs() < 0.05, "{hz}");
+        assert!(db > -12.0 && db < -11.0, "{db}");
+        assert!(store.peaks(store.span(0..1), 1100.0..3000.0).is_empty());
+    }
+}
@@ -1,15 +1,18 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // Copyright (C) 2026 Maika Namuo
 
-use crate::dsp::AudioBlock;
+use crate::dsp::calibration::{self, CalibrationCurve};
+use crate::dsp::{AudioBlock, noise_floor::NoiseFloor, power_spectrum};
 use crate::util::audio::{
-    Channel, DB_FLOOR, DEFAULT_SAMPLE_RATE, FrequencyScale, LN_TO_DB, WindowKind,
+    Channel, DB_FLOOR, DEFAULT_SAMPLE_RATE, FrequencyScale, LN_TO_DB, MixdownGains, WindowKind,
     compute_fft_bin_normalization, copy_dc_removed_windowed_from_deque, db_to_power,
     project_interleaved_channel_into, sanitize_negative_db, sanitize_sample_rate,
     window_coefficients,
 };
+use crate::util::lerp;
 use realfft::{RealFftPlanner, RealToComplex};
 use rustfft::num_complex::Complex32;
+use rustfft::{Fft, FftPlanner};
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::sync::Arc;
@@ -18,21 +21,46 @@ pub const MIN_SPECTRUM_EXP_FACTOR: f32 = 0.0;
 pub const MAX_SPECTRUM_EXP_FACTOR: f32 = 0.95;
 pub const MIN_SPECTRUM_PEAK_DECAY: f32 = 0.0;
 pub const MAX_SPECTRUM_PEAK_DECAY: f32 = 120.0;
+pub const MIN_SPECTRUM_ATTACK_MS: f32 = 0.0;
+pub const MAX_SPECTRUM_ATTACK_MS: f32 = 1_000.0;
+pub const MIN_SPECTRUM_RELEASE_MS: f32 = 10.0;
+pub const MAX_SPECTRUM_RELEASE_MS: f32 = 5_000.0;
 pub const MIN_SPECTRUM_DB_FLOOR: f32 = DB_FLOOR;
 pub const MAX_SPECTRUM_DB_FLOOR: f32 = -1.0;
 pub const DEFAULT_SPECTRUM_DB_FLOOR: f32 = -100.0;
+pub const MIN_SPECTRUM_CROSSOVER_HZ: f32 = 100.0;
+pub const MAX_SPECTRUM_CROSSOVER_HZ: f32 = 5_000.0;
+/// How many times shorter the treble transform of multi-rate mode is.
+pub const SHORT_FFT_DIVISOR: usize = 8;
+// Width of the blend from the long transform into the short one, above the crossover.
+const STITCH_FADE_OCTAVES: f32 = 0.5;
 
 const DEFAULT_SPECTRUM_HOP_DIVISOR: usize = 16;
 const DEFAULT_SPECTRUM_FFT_SIZE: usize = 16_384;
 const DEFAULT_SPECTRUM_EXP_FACTOR: f32 = 0.5;
 const DEFAULT_SPECTRUM_PEAK_DECAY: f32 = 12.0;
+const DEFAULT_SPECTRUM_ATTACK_MS: f32 = 10.0;
+const DEFAULT_SPECTRUM_RELEASE_MS: f32 = 300.0;
+const DEFAULT_SPECTRUM_CROSSOVER_HZ: f32 = 500.0;
+const MIN_SHORT_FFT_SIZE: usize = 64;
 const TRACE_COUNT: usize = 2;
+/// Capture channels that can be overlaid as traces of their own.
+pub const OVERLAY_CHANNELS: usize = 8;
+const SLOT_COUNT: usize = TRACE_COUNT + OVERLAY_CHANNELS;
 const WEIGHTING_COUNT: usize = 2;
 
-fn frequency_bins(sample_rate: f32, fft_size: usize) -> Vec<f32> {
-    let bins = fft_size / 2 + 1;
+// I/Q frames span -fs/2..fs/2 with zero in the middle, so a complex FFT has
+// every bin; a real one only the non-negative half.
+fn bin_count(fft_size: usize, iq: bool) -> usize {
+    if iq { fft_size } else { fft_size / 2 + 1 }
+}
+
+fn frequency_bins(sample_rate: f32, fft_size: usize, iq: bool) -> Vec<f32> {
     let bin_hz = sample_rate / fft_size as f32;
-    (0..bins).map(|i| i as f32 * bin_hz).collect()
+    let offset = if iq { fft_size / 2 } else { 0 };
+    (0..bin_count(fft_size, iq))
+        .map(|i| (i as f32 - offset as f32) * bin_hz)
+        .collect()
 }
 
 pub type SpectrumTraceSnapshot = [Vec<f32>; WEIGHTING_COUNT];
@@ -41,6 +69,19 @@ pub type SpectrumTraceSnapshot = [Vec<f32>; WEIGHTING_COUNT];
 pub struct SpectrumSnapshot {
     pub frequency_bins: Vec<f32>,
     pub traces: [SpectrumTraceSnapshot; TRACE_COUNT],
+    // Of the primary trace; empty unless `show_noise_floor` is set.
+    pub noise_floor: SpectrumTraceSnapshot,
+    // One per capture channel; empty unless that channel is overlaid and present.
+    pub overlays: [SpectrumTraceSnapshot; OVERLAY_CHANNELS],
+    /// A mic calibration curve is divided out of the levels.
+    pub calibrated: bool,
+    /// Bins are signed, from I/Q input; only the primary trace is filled.
+    pub iq: bool,
+}
+
+/// Whether `mask` (bit per capture channel) overlays `channel`.
+pub const fn overlays_channel(mask: u8, channel: usize) -> bool {
+    channel < OVERLAY_CHANNELS && mask & (1 << channel) != 0
 }
 
 crate::macros::default_struct! {
@@ -53,11 +94,20 @@ crate::macros::default_struct! {
         pub averaging: AveragingMode = AveragingMode::None,
         pub source: Channel = Channel::Mid,
         pub secondary_source: Channel = Channel::None,
+        pub overlay_channels: u8 = 0,
         pub frequency_scale: FrequencyScale = FrequencyScale::Logarithmic,
         pub reverse_frequency: bool = false,
         pub show_grid: bool = true,
         pub show_peak_label: bool = true,
+        pub show_noise_floor: bool = false,
         pub floor_db: f32 = DEFAULT_SPECTRUM_DB_FLOOR,
+        /// The first two channels are the real and imaginary parts of one
+        /// complex signal, such as SDR baseband.
+        pub iq_input: bool = false,
+        /// Above `crossover_hz` the levels come from a shorter FFT over the
+        /// newest samples, trading treble resolution for quicker response.
+        pub multi_rate: bool = false,
+        pub crossover_hz: f32 = DEFAULT_SPECTRUM_CROSSOVER_HZ,
     }
 }
 
@@ -69,6 +119,8 @@ impl SpectrumConfig {
             self.hop_size = (self.fft_size / DEFAULT_SPECTRUM_HOP_DIVISOR).max(1);
         }
         self.floor_db = sanitize_negative_db(self.floor_db, DEFAULT_SPECTRUM_DB_FLOOR);
+        self.crossover_hz = crate::util::finite_positive(self.crossover_hz)
+            .unwrap_or(DEFAULT_SPECTRUM_CROSSOVER_HZ);
     }
 }
 
@@ -78,6 +130,9 @@ pub enum AveragingMode {
     None,
     Exponential { factor: f32 },
     PeakHold { decay_per_second: f32 },
+    /// One-pole time constants for rising and falling power; no release holds
+    /// every bin at its maximum.
+    AttackRelease { attack_ms: f32, release_ms: Option<f32> },
 }
 
 impl AveragingMode {
@@ -88,6 +143,14 @@ impl AveragingMode {
     pub const fn default_peak_decay() -> f32 {
         DEFAULT_SPECTRUM_PEAK_DECAY
     }
+
+    pub const fn default_attack_ms() -> f32 {
+        DEFAULT_SPECTRUM_ATTACK_MS
+    }
+
+    pub const fn default_release_ms() -> f32 {
+        DEFAULT_SPECTRUM_RELEASE_MS
+    }
 }
 
 pub struct SpectrumProcessor {
@@ -99,11 +162,23 @@ pub struct SpectrumProcessor {
     real_buffer: Vec<f32>,
     spectrum_buffer: Vec<Complex32>,
     scratch_buffer: Vec<Complex32>,
+    window_normalization: Vec<f32>,
+    // The window's, with any mic calibration folded in.
     bin_normalization: Vec<f32>,
-    pcm_buffers: [VecDeque<f32>; TRACE_COUNT],
+    calibration: Option<Arc<CalibrationCurve>>,
+    // Complex FFT and its buffers, planned only while the input is I/Q.
+    iq_planner: FftPlanner<f32>,
+    iq_fft: Option<Arc<dyn Fft<f32>>>,
+    iq_buffer: Vec<Complex32>,
+    iq_scratch: Vec<Complex32>,
+    short: Option<ShortFft>,
+    pcm_buffers: [VecDeque<f32>; SLOT_COUNT],
     pending_skip_frames: usize,
     source_scratch: Vec<f32>,
-    levels: [SpectrumLevelBuffers; TRACE_COUNT],
+    mixdown: MixdownGains,
+    levels: [SpectrumLevelBuffers; SLOT_COUNT],
+    input_channels: usize,
+    noise_floor: NoiseFloor,
     a_weighting_db: Vec<f32>,
 }
 
@@ -122,11 +197,21 @@ impl SpectrumProcessor {
             real_buffer: Vec::new(),
             spectrum_buffer: Vec::new(),
             scratch_buffer: Vec::new(),
+            window_normalization: Vec::new(),
             bin_normalization: Vec::new(),
-            pcm_buffers: [VecDeque::new(), VecDeque::new()],
+            calibration: None,
+            iq_planner: FftPlanner::new(),
+            iq_fft: None,
+            iq_buffer: Vec::new(),
+            iq_scratch: Vec::new(),
+            short: None,
+            pcm_buffers: Default::default(),
             pending_skip_frames: 0,
             source_scratch: Vec::new(),
+            mixdown: MixdownGains::default(),
             levels: Default::default(),
+            input_channels: 0,
+            noise_floor: NoiseFloor::default(),
             a_weighting_db: Vec::new(),
         };
         processor.rebuild_fft();
@@ -137,6 +222,11 @@ impl SpectrumProcessor {
         self.config
     }
 
+    // Each frame is centred half an FFT behind the newest sample.
+    pub fn latency_frames(&self) -> usize {
+        self.config.fft_size / 2
+    }
+
     fn rebuild_fft(&mut self) {
         self.config.normalize();
         let fft_size = self.config.fft_size;
@@ -145,47 +235,130 @@ impl SpectrumProcessor {
         self.real_buffer.resize(fft_size, 0.0);
         self.spectrum_buffer = self.fft.make_output_vec();
         self.scratch_buffer = self.fft.make_scratch_vec();
-        self.bin_normalization = compute_fft_bin_normalization(&self.window, fft_size);
         self.reset_buffers();
     }
 
+    // Two channels at least, or there is no imaginary part to pair with.
+    fn iq_mode(&self) -> bool {
+        self.config.iq_input && self.input_channels >= 2
+    }
+
+    fn bin_count(&self) -> usize {
+        bin_count(self.config.fft_size, self.iq_mode())
+    }
+
     fn reset_buffers(&mut self) {
-        self.snapshot.frequency_bins =
-            frequency_bins(self.config.sample_rate, self.config.fft_size);
+        let (fft_size, iq) = (self.config.fft_size, self.iq_mode());
+        self.snapshot.iq = iq;
+        self.snapshot.frequency_bins = frequency_bins(self.config.sample_rate, fft_size, iq);
         self.a_weighting_db = self
             .snapshot
             .frequency_bins
             .iter()
-            .map(|&f| a_weight(f))
+            .map(|&f| a_weight(f.abs()))
             .collect();
+        self.window_normalization = compute_fft_bin_normalization(&self.window, fft_size);
+        if iq {
+            // A complex tone lands in one bin at full power, so every bin
+            // takes the DC scale.
+            let dc_scale = self.window_normalization[0];
+            self.window_normalization = vec![dc_scale; fft_size];
+            let fft = self.iq_planner.plan_fft_forward(fft_size);
+            self.iq_buffer.resize(fft_size, Complex32::default());
+            self.iq_scratch.resize(fft.get_inplace_scratch_len(), Complex32::default());
+            self.iq_fft = Some(fft);
+        } else {
+            self.iq_fft = None;
+        }
+        let short_size = fft_size / SHORT_FFT_DIVISOR;
+        self.short = (self.config.multi_rate && !iq && short_size >= MIN_SHORT_FFT_SIZE)
+            .then(|| ShortFft::new(&mut self.planner, self.config.window, short_size));
+        self.apply_calibration();
         self.reset_level_buffers();
         self.pcm_buffers.iter_mut().for_each(VecDeque::clear);
         self.pending_skip_frames = 0;
     }
 
+    /// Per-channel weights the Mid source mixes with.
+    pub fn set_mixdown(&mut self, weights: &[f32], exclude_lfe: bool) {
+        self.mixdown.set_weights(weights, exclude_lfe);
+    }
+
+    /// Divides the mic's response out of the levels; `None` turns it off.
+    pub fn set_calibration(&mut self, curve: Option<Arc<CalibrationCurve>>) {
+        if self.calibration != curve {
+            self.calibration = curve;
+            self.apply_calibration();
+        }
+    }
+
+    fn apply_calibration(&mut self) {
+        // Baseband bins are offsets from a tuned frequency, not acoustic ones.
+        if self.snapshot.iq {
+            self.bin_normalization.clone_from(&self.window_normalization);
+            self.snapshot.calibrated = false;
+            return;
+        }
+        let bin_hz = self.config.sample_rate / self.config.fft_size.max(1) as f32;
+        let curve = self.calibration.as_deref();
+        self.snapshot.calibrated = calibration::apply_to_bins(
+            curve,
+            &self.window_normalization,
+            bin_hz,
+            &mut self.bin_normalization,
+        );
+        if let Some(short) = &mut self.short {
+            let bin_hz = self.config.sample_rate / short.real.len() as f32;
+            let base = &short.window_normalization;
+            calibration::apply_to_bins(curve, base, bin_hz, &mut short.normalization);
+        }
+    }
+
     fn reset_level_buffers(&mut self) {
-        let bins = self.config.fft_size / 2 + 1;
+        let bins = self.bin_count();
         let floor = self.config.floor_db;
         for trace in &mut self.snapshot.traces {
             for db in trace { reset_to_floor(db, bins, floor); }
         }
+        let active = self.active_traces();
+        let overlays = self.snapshot.overlays.iter_mut().zip(&active[TRACE_COUNT..]);
+        for (trace, &active) in overlays {
+            for db in trace {
+                if active { reset_to_floor(db, bins, floor) } else { db.clear() }
+            }
+        }
         let state_floor = smoothing_state_floor(&self.a_weighting_db, floor);
         for buffers in &mut self.levels { buffers.reset(bins, state_floor); }
+        self.noise_floor.reset();
+        self.snapshot.noise_floor.iter_mut().for_each(Vec::clear);
     }
 
     fn sources(&self) -> [Channel; TRACE_COUNT] {
         [self.config.source, self.config.secondary_source]
     }
 
-    fn active_traces(&self) -> [bool; TRACE_COUNT] {
+    // In I/Q mode the first two slots buffer the real and imaginary parts.
+    fn active_traces(&self) -> [bool; SLOT_COUNT] {
+        if self.iq_mode() {
+            return std::array::from_fn(|slot| slot < TRACE_COUNT);
+        }
         let [primary, secondary] = self.sources();
-        [primary != Channel::None, secondary != Channel::None && secondary != primary]
+        std::array::from_fn(|slot| match slot {
+            0 => primary != Channel::None,
+            1 => secondary != Channel::None && secondary != primary,
+            _ => {
+                let channel = slot - TRACE_COUNT;
+                channel < self.input_channels
+                    && overlays_channel(self.config.overlay_channels, channel)
+            }
+        })
     }
 
     fn process_ready_windows(&mut self) -> bool {
         let fft_size = self.config.fft_size;
         let hop = self.config.hop_size.max(1);
-        let bins = fft_size / 2 + 1;
+        let bins = self.bin_count();
+        let iq = self.iq_mode();
         let floor = self.config.floor_db;
         let dt_seconds = hop as f32 / self.config.sample_rate.max(f32::EPSILON);
         let active = self.active_traces();
@@ -194,11 +367,17 @@ impl SpectrumProcessor {
         debug_assert_eq!(self.a_weighting_db.len(), bins);
         if !active.iter().any(|&active| active) { return false; }
 
-        while (0..TRACE_COUNT).all(|trace| !active[trace] || self.pcm_buffers[trace].len() >= fft_size) {
-            for (trace, &active) in active.iter().enumerate() {
-                if active && !self.process_trace_window(trace, dt_seconds, floor) {
+        while (0..SLOT_COUNT).all(|trace| !active[trace] || self.pcm_buffers[trace].len() >= fft_size) {
+            if iq {
+                if !self.process_iq_window(dt_seconds, floor) {
                     return produced;
                 }
+            } else {
+                for (trace, &active) in active.iter().enumerate() {
+                    if active && !self.process_trace_window(trace, dt_seconds, floor) {
+                        return produced;
+                    }
+                }
             }
             let mut drained = hop;
             for (trace, &active) in active.iter().enumerate() {
@@ -225,29 +404,64 @@ impl SpectrumProcessor {
             &self.pcm_buffers[trace],
             &self.window,
         );
-        if self
-            .fft
-            .process_with_scratch(
-                &mut self.real_buffer,
-                &mut self.spectrum_buffer,
-                &mut self.scratch_buffer,
-            )
-            .is_err()
+        let level = &mut self.levels[trace];
+        if !power_spectrum(
+            &*self.fft,
+            &mut self.real_buffer,
+            &mut self.spectrum_buffer,
+            &mut self.scratch_buffer,
+            &mut level.scratch_power[..bins],
+        ) {
+            return false;
+        }
+        scale_powers(&mut level.scratch_power, &self.bin_normalization);
+        if let Some(short) = &mut self.short
+            && short.transform(&self.pcm_buffers[trace], self.config.fft_size)
         {
+            let fft_size = self.config.fft_size;
+            let bin_hz = self.config.sample_rate / fft_size as f32;
+            let from = (self.config.crossover_hz / bin_hz).ceil() as usize;
+            short.stitch(&mut level.scratch_power[..bins], fft_size, from);
+        }
+        self.publish_trace(trace, dt_seconds, floor);
+        true
+    }
+
+    fn process_iq_window(&mut self, dt_seconds: f32, floor: f32) -> bool {
+        let Some(fft) = self.iq_fft.as_ref() else {
             return false;
+        };
+        let n = self.config.fft_size;
+        for (part, pcm) in self.pcm_buffers[..TRACE_COUNT].iter().enumerate() {
+            copy_dc_removed_windowed_from_deque(&mut self.real_buffer, pcm, &self.window);
+            for (z, &x) in self.iq_buffer.iter_mut().zip(&self.real_buffer) {
+                if part == 0 { z.re = x } else { z.im = x }
+            }
         }
+        fft.process_with_scratch(&mut self.iq_buffer, &mut self.iq_scratch);
 
+        // Shifted so the most negative frequency comes first.
+        let half = n / 2;
+        let level = &mut self.levels[0];
+        for (i, power) in level.scratch_power[..n].iter_mut().enumerate() {
+            *power = self.iq_buffer[(i + n - half) % n].norm_sqr();
+        }
+        scale_powers(&mut level.scratch_power, &self.bin_normalization);
+        self.publish_trace(0, dt_seconds, floor);
+        true
+    }
+
+    // Folds the slot's fresh, normalized power spectrum into its levels.
+    fn publish_trace(&mut self, trace: usize, dt_seconds: f32, floor: f32) {
         let level = &mut self.levels[trace];
-        let snapshot = &mut self.snapshot.traces[trace];
-        for (idx, (complex, norm)) in self
-            .spectrum_buffer
-            .iter()
-            .zip(&self.bin_normalization)
-            .take(bins)
-            .enumerate()
-        {
-            level.scratch_power[idx] = complex.norm_sqr() * *norm;
+        if trace == 0 && self.config.show_noise_floor {
+            let noise = self.noise_floor.update(&level.scratch_power, dt_seconds);
+            write_levels(&mut self.snapshot.noise_floor, noise, &self.a_weighting_db, floor);
         }
+        let snapshot = match trace.checked_sub(TRACE_COUNT) {
+            Some(channel) => &mut self.snapshot.overlays[channel],
+            None => &mut self.snapshot.traces[trace],
+        };
         level.update_outputs(
             self.config.averaging,
             snapshot,
@@ -255,14 +469,14 @@ impl SpectrumProcessor {
             dt_seconds,
             floor,
         );
-        true
     }
 
     pub fn process_block(&mut self, block: &AudioBlock<'_>) -> Option<&SpectrumSnapshot> {
         if block.is_empty() { return None; }
 
-        if block.sample_rate != self.config.sample_rate {
+        if block.sample_rate != self.config.sample_rate || block.channels != self.input_channels {
             self.config.sample_rate = block.sample_rate;
+            self.input_channels = block.channels;
             self.reset_buffers();
         }
 
@@ -288,6 +502,13 @@ impl SpectrumProcessor {
         }
         let samples = &block.samples[skip * block.channels..];
 
+        if self.iq_mode() {
+            for (part, pcm) in self.pcm_buffers[..TRACE_COUNT].iter_mut().enumerate() {
+                let frames = samples.chunks_exact(block.channels).take(frames);
+                pcm.extend(frames.map(|frame| frame[part]));
+            }
+            return;
+        }
         let active = self.active_traces();
         for (idx, source) in self.sources().into_iter().enumerate().filter(|(idx, _)| active[*idx]) {
             if project_interleaved_channel_into(
@@ -296,10 +517,15 @@ impl SpectrumProcessor {
                 block.channels,
                 frames,
                 source,
+                &mut self.mixdown,
             ) {
                 self.pcm_buffers[idx].extend(&self.source_scratch);
             }
         }
+        for channel in (0..OVERLAY_CHANNELS).filter(|&channel| active[TRACE_COUNT + channel]) {
+            let frames = samples.chunks_exact(block.channels).take(frames);
+            self.pcm_buffers[TRACE_COUNT + channel].extend(frames.map(|frame| frame[channel]));
+        }
     }
 
     pub fn update_config(&mut self, mut config: SpectrumConfig) {
@@ -314,9 +540,13 @@ impl SpectrumProcessor {
             || old.hop_size != config.hop_size
             || old.source != config.source
             || old.secondary_source != config.secondary_source
+            || old.overlay_channels != config.overlay_channels
+            || old.iq_input != config.iq_input
+            || old.multi_rate != config.multi_rate
         {
             self.reset_buffers();
         } else if averaging_mode_changed
+            || old.show_noise_floor != config.show_noise_floor
             || (old.floor_db - config.floor_db).abs() > f32::EPSILON
         {
             self.reset_level_buffers();
@@ -324,6 +554,87 @@ impl SpectrumProcessor {
     }
 }
 
+// The treble transform of multi-rate mode, over the newest samples of each
+// long window. Its bins are spread over the long ones above the crossover.
+struct ShortFft {
+    fft: Arc<dyn RealToComplex<f32>>,
+    window: Arc<[f32]>,
+    window_normalization: Vec<f32>,
+    // The window's, with calibration and muted bands applied like the long one's.
+    normalization: Vec<f32>,
+    real: Vec<f32>,
+    spectrum: Vec<Complex32>,
+    scratch: Vec<Complex32>,
+    power: Vec<f32>,
+}
+
+impl ShortFft {
+    fn new(planner: &mut RealFftPlanner<f32>, window: WindowKind, size: usize) -> Self {
+        let fft = planner.plan_fft_forward(size);
+        let window = window_coefficients(window, size);
+        let window_normalization = compute_fft_bin_normalization(&window, size);
+        Self {
+            spectrum: fft.make_output_vec(),
+            scratch: fft.make_scratch_vec(),
+            fft,
+            window,
+            normalization: window_normalization.clone(),
+            window_normalization,
+            real: vec![0.0; size],
+            power: vec![0.0; size / 2 + 1],
+        }
+    }
+
+    // Transforms the last samples of the `len`-sample window at the front of `pcm`.
+    fn transform(&mut self, pcm: &VecDeque<f32>, len: usize) -> bool {
+        let Some(start) = len.checked_sub(self.real.len()) else {
+            return false;
+        };
+        for (dst, &sample) in self.real.iter_mut().zip(pcm.range(start..len)) {
+            *dst = sample;
+        }
+        let mean = self.real.iter().sum::<f32>() / self.real.len() as f32;
+        for (sample, &weight) in self.real.iter_mut().zip(self.window.iter()) {
+            *sample = (*sample - mean) * weight;
+        }
+        if !power_spectrum(
+            &*self.fft,
+            &mut self.real,
+            &mut self.spectrum,
+            &mut self.scratch,
+            &mut self.power,
+        ) {
+            return false;
+        }
+        scale_powers(&mut self.power, &self.normalization);
+        true
+    }
+
+    // Blends the bins of a `long_size` FFT from `from` up into the short
+    // ones, interpolating between short bins. A short bin is `1 / step` long
+    // bins wide and holds that much more noise, so its power is shared out
+    // over them; broadband levels then carry straight across the crossover.
+    fn stitch(&self, powers: &mut [f32], long_size: usize, from: usize) {
+        let step = self.real.len() as f32 / long_size as f32;
+        let last = self.power.len() - 1;
+        let fade = (from as f32 * (STITCH_FADE_OCTAVES.exp2() - 1.0)).ceil().max(1.0);
+        for (i, power) in powers.iter_mut().enumerate().skip(from) {
+            let pos = i as f32 * step;
+            let lo = (pos as usize).min(last);
+            let hi = (lo + 1).min(last);
+            let short = lerp(self.power[lo], self.power[hi], pos - lo as f32) * step;
+            let blend = ((i - from + 1) as f32 / fade).min(1.0);
+            *power = lerp(*power, short, blend);
+        }
+    }
+}
+
+fn scale_powers(powers: &mut [f32], normalization: &[f32]) {
+    for (power, norm) in powers.iter_mut().zip(normalization) {
+        *power *= *norm;
+    }
+}
+
 #[derive(Default)]
 struct SpectrumLevelBuffers {
     averaged_power: Vec<f32>,
@@ -354,13 +665,6 @@ impl SpectrumLevelBuffers {
         dt_seconds: f32,
         floor: f32,
     ) {
-        let bins = self.scratch_power.len();
-        debug_assert_eq!(weighting_db.len(), bins);
-        for output in outputs.iter_mut() {
-            if output.len() != bins {
-                output.resize(bins, floor);
-            }
-        }
         let powers = match mode {
             AveragingMode::None => &self.scratch_power,
             AveragingMode::Exponential { factor } => {
@@ -387,14 +691,51 @@ impl SpectrumLevelBuffers {
                 }
                 &self.peak_hold_power
             }
+            AveragingMode::AttackRelease { attack_ms, release_ms } => {
+                let attack = one_pole_step(attack_ms, dt_seconds);
+                let release = release_ms.map_or(0.0, |ms| one_pole_step(ms, dt_seconds));
+                for (avg, &power) in self.averaged_power.iter_mut().zip(&self.scratch_power) {
+                    let step = if power > *avg { attack } else { release };
+                    *avg += (power - *avg) * step;
+                    if *avg < self.state_floor {
+                        *avg = 0.0;
+                    }
+                }
+                &self.averaged_power
+            }
         };
-        let [weighted_out, raw_out] = outputs;
-        for i in 0..bins {
-            let db = powers[i].ln() * LN_TO_DB;
-            raw_out[i] = db.max(floor);
-            weighted_out[i] = (db + weighting_db[i]).max(floor);
+        write_levels(outputs, powers, weighting_db, floor);
+    }
+}
+
+// Fraction of the remaining distance a one-pole filter covers in `dt_seconds`.
+fn one_pole_step(time_constant_ms: f32, dt_seconds: f32) -> f32 {
+    if time_constant_ms <= 0.0 {
+        1.0
+    } else {
+        1.0 - (-dt_seconds * 1_000.0 / time_constant_ms).exp()
+    }
+}
+
+fn write_levels(
+    outputs: &mut [Vec<f32>; WEIGHTING_COUNT],
+    powers: &[f32],
+    weighting_db: &[f32],
+    floor: f32,
+) {
+    let bins = powers.len();
+    debug_assert_eq!(weighting_db.len(), bins);
+    for output in outputs.iter_mut() {
+        if output.len() != bins {
+            output.resize(bins, floor);
         }
     }
+    let [weighted_out, raw_out] = outputs;
+    for i in 0..bins {
+        let db = powers[i].ln() * LN_TO_DB;
+        raw_out[i] = db.max(floor);
+        weighted_out[i] = (db + weighting_db[i]).max(floor);
+    }
 }
 
 fn reset_to_floor(buf: &mut Vec<f32>, bins: usize, floor: f32) {
@@ -425,6 +766,7 @@ fn a_weight(freq_hz: f32) -> f32 {
 mod tests {
     use super::*;
     use crate::dsp::AudioBlock;
+    use crate::util::audio::power_to_db;
 
     #[test]
     fn normalization_bounds_runtime_values_without_enforcing_gui_ranges() {
@@ -490,6 +832,65 @@ mod tests {
         assert_eq!(p.pcm_buffers[1].iter().copied().collect::<Vec<_>>(), [0.5, -0.5]);
     }
 
+    #[test]
+    fn overlaid_channels_get_traces_of_their_own() {
+        let mut p = SpectrumProcessor::new(SpectrumConfig {
+            sample_rate: 64.0,
+            fft_size: 64,
+            hop_size: 64,
+            window: WindowKind::Rectangular,
+            overlay_channels: 0b1_0101,
+            ..Default::default()
+        });
+        // Three channels carrying tones at 4, 8 and 12 Hz; bit 4 has no channel.
+        let samples: Vec<f32> = (0..64)
+            .flat_map(|i| {
+                let tone = |hz: f32| (std::f32::consts::TAU * hz * i as f32 / 64.0).sin();
+                [tone(4.0), tone(8.0), tone(12.0)]
+            })
+            .collect();
+        let snap = p.process_block(&AudioBlock::new(&samples, 3, 64.0)).unwrap();
+
+        let loudest = |db: &[f32]| (0..db.len()).max_by(|&a, &b| db[a].total_cmp(&db[b]));
+        assert_eq!(loudest(&snap.overlays[0][1]), Some(4));
+        assert_eq!(loudest(&snap.overlays[2][1]), Some(12));
+        assert!(snap.overlays[1][1].is_empty());
+        assert!(snap.overlays[4][1].is_empty());
+    }
+
+    #[test]
+    fn iq_input_resolves_negative_frequencies() {
+        let mut p = SpectrumProcessor::new(SpectrumConfig {
+            sample_rate: 64.0,
+            fft_size: 64,
+            hop_size: 64,
+            window: WindowKind::Rectangular,
+            iq_input: true,
+            ..Default::default()
+        });
+        // e^(-j2π·8t): a tone 8 Hz below the centre that a real FFT would fold.
+        let samples: Vec<f32> = (0..64)
+            .flat_map(|i| {
+                let phase = std::f32::consts::TAU * -8.0 * i as f32 / 64.0;
+                [phase.cos(), phase.sin()]
+            })
+            .collect();
+        let snap = p.process_block(&AudioBlock::new(&samples, 2, 64.0)).unwrap();
+
+        assert!(snap.iq);
+        assert_eq!(snap.frequency_bins.len(), 64);
+        assert_eq!((snap.frequency_bins[0], snap.frequency_bins[63]), (-32.0, 31.0));
+        let raw = &snap.traces[0][1];
+        let loudest = (0..raw.len()).max_by(|&a, &b| raw[a].total_cmp(&raw[b])).unwrap();
+        assert_eq!(snap.frequency_bins[loudest], -8.0);
+        assert!(raw[loudest].abs() < 0.01, "full-scale tone read {} dB", raw[loudest]);
+        assert!(raw[32 + 8] < -60.0);
+
+        // Mono input has no imaginary part, so it falls back to a real spectrum.
+        let snap = p.process_block(&AudioBlock::new(&samples, 1, 64.0));
+        assert!(snap.is_some_and(|snap| !snap.iq && snap.frequency_bins.len() == 33));
+    }
+
     #[test]
     fn secondary_source_can_drive_processing_without_primary() {
         let mut p = SpectrumProcessor::new(SpectrumConfig {
@@ -560,6 +961,117 @@ mod tests {
         );
     }
 
+    #[test]
+    fn noise_floor_stays_under_intermittent_tones() {
+        const RATE: f32 = 48_000.0;
+        let mut p = SpectrumProcessor::new(SpectrumConfig {
+            sample_rate: RATE,
+            fft_size: 1024,
+            hop_size: 256,
+            show_noise_floor: true,
+            ..Default::default()
+        });
+        let mut seed = 1_u32;
+        // Half-second 1.5 kHz bursts every second over faint noise, ending mid-burst.
+        let samples: Vec<f32> = (0..(2.25 * RATE) as usize)
+            .map(|n| {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                let noise = (seed as f32 / u32::MAX as f32 - 0.5) * 0.01;
+                let t = n as f32 / RATE;
+                let on = if t.fract() < 0.5 { 0.5 } else { 0.0 };
+                noise + on * (std::f32::consts::TAU * 1500.0 * t).sin()
+            })
+            .collect();
+        let mut last = None;
+        for block in samples.chunks(1024) {
+            if let Some(snap) = p.process_block(&AudioBlock::new(block, 1, RATE)) {
+                last = Some(snap.clone());
+            }
+        }
+        let snap = last.expect("expected snapshot");
+        let bin = (1500.0 / RATE * 1024.0).round() as usize;
+        let [signal, noise] = [&snap.traces[0][1], &snap.noise_floor[1]];
+        assert_eq!(noise.len(), signal.len());
+        assert!(signal[bin] - noise[bin] > 30.0, "{} vs {}", signal[bin], noise[bin]);
+
+        let mut cfg = p.config();
+        cfg.show_noise_floor = false;
+        p.update_config(cfg);
+        assert!(p.snapshot.noise_floor.iter().all(Vec::is_empty));
+    }
+
+    #[test]
+    fn multi_rate_treble_follows_the_newest_samples() {
+        const RATE: f32 = 48_000.0;
+        // 6 kHz starts in the last eighth of the window, where a long Hann
+        // window barely looks.
+        let samples: Vec<f32> = (0..4096)
+            .map(|n| {
+                let t = n as f32 / RATE;
+                let treble = if n >= 3584 { 0.5 } else { 0.0 };
+                0.5 * (std::f32::consts::TAU * 100.0 * t).sin()
+                    + treble * (std::f32::consts::TAU * 6_000.0 * t).sin()
+            })
+            .collect();
+        let raw_db = |multi_rate| {
+            let mut p = SpectrumProcessor::new(SpectrumConfig {
+                sample_rate: RATE,
+                fft_size: 4096,
+                hop_size: 4096,
+                multi_rate,
+                crossover_hz: 1_000.0,
+                ..Default::default()
+            });
+            let snap = p.process_block(&AudioBlock::new(&samples, 1, RATE)).unwrap();
+            let db = |hz: f32| snap.traces[0][1][(hz / RATE * 4096.0).round() as usize];
+            (db(100.0), db(6_000.0))
+        };
+        let (long_bass, long_treble) = raw_db(false);
+        let (bass, treble) = raw_db(true);
+        assert!(long_treble < -30.0, "{long_treble}");
+        // The -6 dB tone, shared out over the eight long bins its short one spans.
+        assert!((treble + 6.0 + 9.0).abs() < 1.5, "{treble}");
+        assert_eq!(bass, long_bass);
+    }
+
+    #[test]
+    fn multi_rate_white_noise_has_no_step_at_the_crossover() {
+        const RATE: f32 = 48_000.0;
+        const SIZE: usize = 4096;
+        let mut seed = 0x2545_f491_u32;
+        let samples: Vec<f32> = (0..SIZE * 128)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (seed >> 8) as f32 / (1 << 24) as f32 - 0.5
+            })
+            .collect();
+        let mut p = SpectrumProcessor::new(SpectrumConfig {
+            sample_rate: RATE,
+            fft_size: SIZE,
+            hop_size: SIZE,
+            multi_rate: true,
+            crossover_hz: 2_000.0,
+            ..Default::default()
+        });
+        let mut sums = vec![0.0_f64; SIZE / 2 + 1];
+        for block in samples.chunks(SIZE) {
+            let snap = p.process_block(&AudioBlock::new(block, 1, RATE)).unwrap();
+            for (sum, &db) in sums.iter_mut().zip(&snap.traces[0][1]) {
+                *sum += f64::from(db_to_power(db));
+            }
+        }
+        let band_db = |lo: f32, hi: f32| {
+            let bins = (lo / RATE * SIZE as f32) as usize..(hi / RATE * SIZE as f32) as usize;
+            let mean = bins.clone().map(|i| sums[i]).sum::<f64>() / bins.len() as f64;
+            power_to_db(mean as f32)
+        };
+        let below = band_db(1_000.0, 2_000.0);
+        let across = band_db(2_000.0, 2_900.0);
+        let above = band_db(3_000.0, 6_000.0);
+        assert!((across - below).abs() < 1.0, "{below} vs {across}");
+        assert!((above - below).abs() < 1.0, "{below} vs {above}");
+    }
+
     #[test]
     fn changing_averaging_mode_clears_stale_state() {
         let mut processor = SpectrumProcessor::new(SpectrumConfig::default());
@@ -642,6 +1154,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn attack_release_follows_time_constants_and_can_hold() {
+        let burst = |release_ms| {
+            let mut buffers = SpectrumLevelBuffers::default();
+            buffers.reset(1, smoothing_state_floor(&[0.0], -100.0));
+            let mut outputs = [Vec::new(), Vec::new()];
+            let mode = AveragingMode::AttackRelease { attack_ms: 100.0, release_ms };
+            let mut step = |db: f32, seconds: f32| {
+                buffers.scratch_power[0] = db_to_power(db);
+                buffers.update_outputs(mode, &mut outputs, &[0.0], seconds, -100.0);
+                outputs[1][0]
+            };
+            // The first window lands in an empty buffer, which starts at zero power.
+            let attack = step(0.0, 0.1);
+            let held = step(-60.0, 1.0);
+            (attack, held)
+        };
+
+        let (attack, released) = burst(Some(250.0));
+        let expected = 10.0 * (1.0 - (-1.0_f32).exp()).log10();
+        assert!((attack - expected).abs() < 0.01, "attack reached {attack} dB");
+        assert!(released < attack - 10.0, "release only fell to {released} dB");
+
+        let (attack, held) = burst(None);
+        assert_eq!(held, attack);
+    }
+
     #[test]
     fn a_weight_matches_iec_reference_points() {
         let reference_points: &[(f32, f32)] = &[
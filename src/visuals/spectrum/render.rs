@@ -7,7 +7,7 @@ use std::sync::Arc;
 
 use crate::visuals::options::SpectrumDisplayMode;
 use crate::visuals::render::common::sdf_primitive;
-use crate::util::color::{rgba_with_alpha, sample_rgba_gradient};
+use crate::util::color::{ColorBlend, rgba_with_alpha, sample_rgba_gradient};
 use crate::util::lerp;
 use crate::visuals::render::common::{
     ClipTransform, GeometryScratch, SdfVertex, baseline_segment_vertices,
@@ -31,17 +31,28 @@ pub struct SpectrumParams {
     pub bounds: Rectangle,
     pub normalized_points: Arc<Vec<[f32; 2]>>,
     pub secondary_points: Arc<Vec<[f32; 2]>>,
+    pub noise_points: Arc<Vec<[f32; 2]>>,
+    pub noise_line_color: [f32; 4],
     pub key: u64,
     pub line_color: [f32; 4],
     pub line_width: f32,
     pub secondary_line_color: [f32; 4],
     pub secondary_line_width: f32,
+    /// Overlaid channel traces with their colours.
+    pub overlays: Vec<(Arc<Vec<[f32; 2]>>, [f32; 4])>,
     pub highlight_threshold: f32,
+    pub fill_alpha: f32,
     pub spectrum_palette: [[f32; 4]; 6],
+    pub palette_blend: ColorBlend,
     pub display_mode: SpectrumDisplayMode,
+    /// Lows at the center, reflected out to both edges.
+    pub mirrored: bool,
     pub bar_count: usize,
     pub bar_gap: f32,
     pub peak: Option<SpectrumPeakParams>,
+    pub opacity: f32,
+    /// Beat-pulse brightening, `0..=1`.
+    pub pulse: f32,
 }
 
 impl SpectrumPrimitive {
@@ -50,10 +61,25 @@ impl SpectrumPrimitive {
         let clip = ClipTransform::from_viewport(viewport);
 
         let has_primary = self.params.normalized_points.len() >= 2;
-        if !has_primary && self.params.secondary_points.len() < 2 {
+        if !has_primary && self.params.secondary_points.len() < 2 && self.params.overlays.is_empty()
+        {
             return;
         }
 
+        // Mirrored, the right half is built as a whole spectrum and then
+        // reflected onto the left.
+        let start = scratch.vertices.len();
+        let bounds = if self.params.mirrored {
+            Rectangle {
+                x: bounds.center_x(),
+                width: bounds.width * 0.5,
+                ..bounds
+            }
+        } else {
+            bounds
+        };
+        self.build_noise_vertices(scratch, clip, bounds);
+        self.build_overlay_vertices(scratch, clip, bounds);
         if has_primary && self.params.display_mode == SpectrumDisplayMode::Bar {
             self.build_bar_vertices(&mut scratch.vertices, clip, bounds);
         } else {
@@ -74,6 +100,52 @@ impl SpectrumPrimitive {
             let (x, y) = normalized_to_cartesian(bounds, peak.marker);
             vertices.extend(dot_vertices(x, y, 3.0, peak.marker_color, clip, false));
         }
+        if self.params.mirrored {
+            let [axis, _] = clip.to_clip(bounds.x, bounds.y);
+            let end = vertices.len();
+            vertices.extend_from_within(start..end);
+            for vertex in &mut vertices[end..] {
+                vertex.position[0] = 2.0 * axis - vertex.position[0];
+            }
+        }
+    }
+
+    // Drawn first so the spectrum sits on top of it.
+    fn build_noise_vertices(&self, scratch: &mut GeometryScratch, clip: ClipTransform, bounds: Rectangle) {
+        if self.params.noise_points.len() < 2 { return; }
+        let GeometryScratch { vertices, points2, .. } = scratch;
+        points2.extend(
+            self.params
+                .noise_points
+                .iter()
+                .map(|&p| normalized_to_cartesian(bounds, p)),
+        );
+        decimate_finite_ordered_line_in_place(points2, bounds.width.ceil().max(1.0) as usize * 2);
+        extend_aa_line_list(
+            vertices,
+            points2,
+            self.params.secondary_line_width,
+            self.params.noise_line_color,
+            clip,
+        );
+        points2.clear();
+    }
+
+    // Under the primary trace, like the secondary one.
+    fn build_overlay_vertices(
+        &self,
+        scratch: &mut GeometryScratch,
+        clip: ClipTransform,
+        bounds: Rectangle,
+    ) {
+        let pixel_budget = bounds.width.ceil().max(1.0) as usize * 2;
+        let GeometryScratch { vertices, points2, .. } = scratch;
+        for (points, color) in self.params.overlays.iter().filter(|(p, _)| p.len() >= 2) {
+            points2.extend(points.iter().map(|&p| normalized_to_cartesian(bounds, p)));
+            decimate_finite_ordered_line_in_place(points2, pixel_budget);
+            extend_aa_line_list(vertices, points2, self.params.line_width, *color, clip);
+            points2.clear();
+        }
     }
 
     fn build_line_vertices(&self, scratch: &mut GeometryScratch, clip: ClipTransform, bounds: Rectangle) {
@@ -88,17 +160,16 @@ impl SpectrumPrimitive {
         vertices.reserve((highlight_segments + line_segments + secondary_segments) * 6);
         let baseline = bounds.y + bounds.height;
 
+        let fill = self.params.display_mode == SpectrumDisplayMode::Fill;
         if has_primary {
             points.extend(normalized.iter().map(|&p| normalized_to_cartesian(bounds, p)));
-            push_highlight_columns(
-                vertices,
-                clip,
-                baseline,
-                points,
-                normalized,
-                &self.params.spectrum_palette,
-                self.params.highlight_threshold,
-            );
+            if !fill {
+                push_highlight_columns(vertices, clip, baseline, points, normalized, &self.params);
+            }
+            decimate_finite_ordered_line_in_place(points, pixel_budget);
+            if fill {
+                push_gradient_fill(vertices, clip, bounds, points, &self.params);
+            }
         }
 
         if has_secondary {
@@ -119,7 +190,6 @@ impl SpectrumPrimitive {
         }
 
         if has_primary {
-            decimate_finite_ordered_line_in_place(points, pixel_budget);
             extend_aa_line_list(
                 vertices,
                 points,
@@ -151,7 +221,7 @@ impl SpectrumPrimitive {
             let x1 = x0 + bar_w;
             if amp >= 1e-4 {
                 let y = y_at(amp);
-                let color = palette_color(&p.spectrum_palette, amp, p.highlight_threshold);
+                let color = p.highlight_color(amp);
                 verts.extend_from_slice(&gradient_quad_vertices(
                     x0,
                     y,
@@ -189,21 +259,108 @@ fn push_highlight_columns(
     baseline: f32,
     positions: &[(f32, f32)],
     normalized_points: &[[f32; 2]],
-    palette: &[[f32; 4]],
-    threshold: f32,
+    params: &SpectrumParams,
 ) {
     for (seg, pts) in positions.windows(2).zip(normalized_points.windows(2)) {
-        let c0 = palette_color(palette, pts[0][1], threshold);
-        let c1 = palette_color(palette, pts[1][1], threshold);
+        let c0 = params.highlight_color(pts[0][1]);
+        let c1 = params.highlight_color(pts[1][1]);
         if c0[3] > 0.0 || c1[3] > 0.0 {
             vertices.extend(baseline_segment_vertices(seg[0], seg[1], baseline, clip, [c0, c1]));
         }
     }
 }
 
-fn palette_color(palette: &[[f32; 4]], amp: f32, threshold: f32) -> [f32; 4] {
-    let intensity = (amp - threshold) / (1.0 - threshold).max(1e-6);
-    sample_rgba_gradient(palette, intensity)
+// Splits the area under the curve into horizontal bands at the gradient's
+// stops. The palette is then linear within each band, so the shader's vertex
+// interpolation paints the exact vertical gradient.
+fn push_gradient_fill(
+    vertices: &mut Vec<SdfVertex>,
+    clip: ClipTransform,
+    bounds: Rectangle,
+    points: &[(f32, f32)],
+    params: &SpectrumParams,
+) {
+    let levels = params.fill_levels();
+    let baseline = bounds.y + bounds.height;
+    let height = bounds.height.max(1e-6);
+    let level_at = |y: f32| ((baseline - y) / height).clamp(0.0, 1.0);
+    let alpha = params.fill_alpha.clamp(0.0, 1.0);
+    let vertex = |(x, level): (f32, f32)| {
+        let color = params.highlight_color(level);
+        SdfVertex::solid(
+            clip.to_clip(x, baseline - level * height),
+            rgba_with_alpha(color, color[3] * alpha),
+        )
+    };
+
+    for seg in points.windows(2) {
+        let [(x0, y0), (x1, y1)] = [seg[0], seg[1]];
+        let (h0, h1) = (level_at(y0), level_at(y1));
+        let column = [(x0, 0.0), (x1, 0.0), (x1, h1), (x0, h0)];
+        for band in levels.windows(2).take_while(|band| band[0] < h0.max(h1)) {
+            let mut above = [(0.0, 0.0); 8];
+            let mut inside = [(0.0, 0.0); 8];
+            let n = clip_polygon(&column, band[0], true, &mut above);
+            let n = clip_polygon(&above[..n], band[1], false, &mut inside);
+            if n < 3 {
+                continue;
+            }
+            for pair in inside[1..n].windows(2) {
+                vertices.extend([inside[0], pair[0], pair[1]].map(vertex));
+            }
+        }
+    }
+}
+
+// One Sutherland-Hodgman pass against a horizontal line, keeping the side
+// above (or below) `level`.
+fn clip_polygon(
+    poly: &[(f32, f32)],
+    level: f32,
+    keep_above: bool,
+    out: &mut [(f32, f32); 8],
+) -> usize {
+    let inside = |p: (f32, f32)| if keep_above { p.1 >= level } else { p.1 <= level };
+    let mut n = 0;
+    for (i, &cur) in poly.iter().enumerate() {
+        let prev = poly[(i + poly.len() - 1) % poly.len()];
+        if inside(cur) != inside(prev) {
+            let t = (level - prev.1) / (cur.1 - prev.1);
+            out[n] = (lerp(prev.0, cur.0, t), level);
+            n += 1;
+        }
+        if inside(cur) {
+            out[n] = cur;
+            n += 1;
+        }
+    }
+    n
+}
+
+impl SpectrumParams {
+    fn highlight_color(&self, amp: f32) -> [f32; 4] {
+        let threshold = self.highlight_threshold;
+        let intensity = (amp - threshold) / (1.0 - threshold).max(1e-6);
+        sample_rgba_gradient(&self.spectrum_palette, intensity, self.palette_blend)
+    }
+
+    // Heights where the fill gradient changes slope: the floor, every palette
+    // stop, and the top. Perceptual blends curve between stops, so those get
+    // extra subdivisions.
+    fn fill_levels(&self) -> Vec<f32> {
+        let threshold = self.highlight_threshold.clamp(0.0, 1.0);
+        let steps = match self.palette_blend {
+            ColorBlend::Srgb => 1,
+            ColorBlend::Oklab | ColorBlend::Lch => 4,
+        };
+        let count = (self.spectrum_palette.len() - 1) * steps;
+        let mut levels = vec![0.0];
+        levels.extend(
+            (0..=count).map(|i| threshold + (1.0 - threshold) * i as f32 / count as f32),
+        );
+        levels.dedup();
+        levels
+    }
 }
 
 fn sample_max(pts: &[[f32; 2]], t0: f32, t1: f32) -> f32 {
@@ -253,6 +410,30 @@ mod tests {
             assert_eq!(sample_max(&points, lo, hi), reference(lo, hi));
         }
     }
+
+    #[test]
+    fn gradient_bands_partition_the_area_under_a_segment() {
+        let area = |poly: &[(f32, f32)]| {
+            let twice: f32 = (0..poly.len())
+                .map(|i| {
+                    let (a, b) = (poly[i], poly[(i + 1) % poly.len()]);
+                    a.0 * b.1 - b.0 * a.1
+                })
+                .sum();
+            twice.abs() * 0.5
+        };
+        let column = [(0.0, 0.0), (2.0, 0.0), (2.0, 0.9), (0.0, 0.3)];
+        let levels = [0.0, 0.25, 0.5, 0.75, 1.0];
+        let mut total = 0.0;
+        for band in levels.windows(2) {
+            let (mut above, mut inside) = ([(0.0, 0.0); 8], [(0.0, 0.0); 8]);
+            let n = clip_polygon(&column, band[0], true, &mut above);
+            let n = clip_polygon(&above[..n], band[1], false, &mut inside);
+            assert!(inside[..n].iter().all(|p| (band[0]..=band[1]).contains(&p.1)));
+            total += area(&inside[..n]);
+        }
+        assert!((total - area(&column)).abs() < 1e-5);
+    }
 }
 
 sdf_primitive!(
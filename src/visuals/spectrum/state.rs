@@ -1,28 +1,51 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // Copyright (C) 2026 Maika Namuo
 
-use super::processor::{SpectrumSnapshot, SpectrumTraceSnapshot};
+mod peak_list;
+
+use super::processor::{
+    OVERLAY_CHANNELS, SpectrumSnapshot, SpectrumTraceSnapshot, overlays_channel,
+};
 use super::render::{SpectrumParams, SpectrumPeakParams, SpectrumPrimitive};
-use crate::persistence::settings::SpectrumSettings;
-use crate::visuals::options::{SpectrumDisplayMode, SpectrumWeightingMode};
-use crate::util::audio::musical::NoteInfo;
-use crate::util::audio::{Channel, FrequencyScale, fmt_freq};
-use crate::util::color::{color_to_rgba, with_alpha};
+use crate::persistence::settings::{FrequencyMarker, MemoryTrace, SpectrumSettings};
+use crate::visuals::options::{
+    SpectrumDisplayMode, SpectrumLevelGrid, SpectrumWeightingMode, TraceColor,
+};
+use crate::util::audio::musical::{MusicalNote, NoteInfo, Tuning, fmt_interval};
+use crate::util::audio::{
+    Channel, DbRange, FrequencyScale, LevelScale, NumberFormat, db_to_power,
+};
+use crate::util::color::{ColorBlend, color_to_rgba, with_alpha};
 use crate::util::lerp;
 use crate::visuals::palettes;
-use crate::visuals::render::common::{fill_rect, fill_snapped_bordered_rect, make_text, measure_text};
+use crate::visuals::render::common::{
+    draw_badge, draw_frequency_markers, draw_shader, faded, fill_rect, fill_snapped_bordered_rect,
+    make_text, measure_text,
+};
 use iced::advanced::Renderer as _;
 use iced::advanced::text::Renderer as _;
-use iced::{Color, Point, Rectangle, Size};
+use iced::advanced::mouse;
+use iced::{Color, Point, Rectangle, Size, keyboard};
+use peak_list::PeakList;
 use std::sync::{Arc, LazyLock};
+use std::time::Instant;
 
 const EPSILON: f32 = 1e-6;
 const MIN_FREQUENCY: f32 = 20.0;
-const MAX_DB: f32 = 0.0;
 const LINE_THICKNESS: f32 = 1.0;
 const SECONDARY_LINE_THICKNESS: f32 = 0.75;
 const GRID_LABEL_SIZE: f32 = 10.0;
 const GRID_LABEL_GAP: f32 = 6.0;
+// B10, past the top of any audio band.
+const NOTE_AXIS_TOP_MIDI: i32 = 143;
+const SEMITONE_MIN_PX: f32 = 6.0;
+const SNR_LABEL_SIZE: f32 = 10.0;
+const CURSOR_HIT_PX: f32 = 6.0;
+const CURSOR_LABEL_SIZE: f32 = 11.0;
+const LEGEND_LABEL_SIZE: f32 = 10.0;
+const LEGEND_SWATCH: f32 = 12.0;
+// Points kept per memory slot; finer spectra keep their loudest bin per band.
+const MEMORY_POINTS: usize = 1024;
 
 #[derive(Debug, Clone)]
 struct PeakLabel {
@@ -32,6 +55,13 @@ struct PeakLabel {
     opacity: f32,
 }
 
+// Level is read from the primary trace on each snapshot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct MeasureCursor {
+    hz: f32,
+    db: Option<f32>,
+}
+
 type PeakUpdate = ([String; 2], [f32; 2]);
 // Keep the Vec allocation when publishing freshly built points; Vec -> Arc<[T]> copies them.
 type SharedPoints = Arc<Vec<[f32; 2]>>;
@@ -48,28 +78,72 @@ fn share_points(points: Vec<[f32; 2]>) -> SharedPoints {
 #[derive(Debug, Clone)]
 pub(in crate::visuals) struct SpectrumState {
     style: SpectrumSettings,
+    // The shared spectrum range, and that range raised to this visual's floor.
+    level_range: DbRange,
+    levels: DbRange,
+    tuning: Tuning,
+    numbers: NumberFormat,
     pub(in crate::visuals) spectrum_palette: [Color; 6],
+    palette_blend: ColorBlend,
     primary: SharedPoints,
     secondary: SharedPoints,
+    // Overlaid capture channels and their points.
+    overlays: Vec<(usize, SharedPoints)>,
+    // The primary trace as `[hz, db]`, ready to be held in a memory slot.
+    latest: Vec<[f32; 2]>,
+    // Shown memory slots and their points.
+    memory: Vec<(usize, SharedPoints)>,
+    noise: SharedPoints,
+    snr: Option<String>,
     key: u64,
+    opacity: f32,
+    pulse: f32,
     peak: Option<PeakLabel>,
     effective_range: Option<(f32, f32)>,
-    x_cache_key: (usize, u32, FrequencyScale),
+    x_cache_key: (usize, u32, u32, FrequencyScale),
     x_cache: Vec<f32>,
+    peak_list: PeakList,
+    // Session-only markers pinned from the peak list.
+    pins: Vec<FrequencyMarker>,
+    cursors: Vec<MeasureCursor>,
+    modifiers: keyboard::Modifiers,
+    hovered: bool,
+    calibrated: bool,
+    // Signed bins from I/Q input, drawn from -fs/2 to fs/2.
+    iq: bool,
 }
 
 impl SpectrumState {
     pub fn new() -> Self {
         Self {
             style: SpectrumSettings::default(),
+            level_range: LevelScale::Spectrum.default_range(),
+            levels: LevelScale::Spectrum.default_range(),
+            tuning: Tuning::default(),
+            numbers: NumberFormat::default(),
             spectrum_palette: palettes::spectrum::COLORS,
+            palette_blend: ColorBlend::default(),
             primary: empty_points(),
             secondary: empty_points(),
+            overlays: Vec::new(),
+            latest: Vec::new(),
+            memory: Vec::new(),
+            noise: empty_points(),
+            snr: None,
             key: crate::visuals::next_key(),
+            opacity: 1.0,
+            pulse: 0.0,
             peak: None,
             effective_range: None,
-            x_cache_key: (0, 0, FrequencyScale::default()),
+            x_cache_key: (0, 0, 0, FrequencyScale::default()),
             x_cache: Vec::new(),
+            peak_list: PeakList::default(),
+            pins: Vec::new(),
+            cursors: Vec::new(),
+            modifiers: keyboard::Modifiers::default(),
+            hovered: false,
+            calibrated: false,
+            iq: false,
         }
     }
 
@@ -79,21 +153,103 @@ impl SpectrumState {
         if !settings.show_peak_label {
             self.peak = None;
         }
+        if settings.peak_list == 0 {
+            self.peak_list.clear();
+        }
+        self.rebuild_memory();
+    }
+
+    /// The marked bands while muting them is on, as (low, high) Hz.
+    pub fn muted_bands(&self) -> Vec<(f32, f32)> {
+        if !self.style.mute_marked_bands {
+            return Vec::new();
+        }
+        let bands = self.style.markers.iter().filter(|m| m.end_hz.is_some());
+        bands.map(|m| m.span()).collect()
+    }
+
+    /// The latest primary trace, for a memory slot.
+    pub fn memory_trace(&self) -> Option<MemoryTrace> {
+        (self.latest.len() >= 2).then(|| MemoryTrace {
+            visible: true,
+            points: decimate(&self.latest, MEMORY_POINTS),
+        })
+    }
+
+    fn rebuild_memory(&mut self) {
+        self.memory.clear();
+        let Some((min_f, max_f)) = self.effective_range else {
+            return;
+        };
+        let memory = &self.style.memory;
+        for (slot, trace) in memory.slots.iter().enumerate() {
+            let Some(trace) = trace.as_ref().filter(|trace| trace.visible) else {
+                continue;
+            };
+            let points = memory_points(&self.style, self.levels, min_f, max_f, &trace.points);
+            if points.len() >= 2 {
+                self.memory.push((slot, share_points(points)));
+            }
+        }
+    }
+
+    /// Held still under the pointer, so a transient can be read off.
+    pub fn hover_paused(&self) -> bool {
+        self.style.pause_on_hover && self.hovered
     }
 
     pub fn export_settings(&self) -> SpectrumSettings {
         self.style.clone()
     }
 
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity;
+    }
+
+    pub fn set_pulse(&mut self, pulse: f32) {
+        self.pulse = pulse;
+    }
+
     pub fn set_palette(&mut self, palette: &[Color; 6]) {
         self.spectrum_palette = *palette;
     }
 
+    pub fn palette_blend(&self) -> ColorBlend {
+        self.palette_blend
+    }
+
+    pub fn set_palette_blend(&mut self, blend: ColorBlend) {
+        self.palette_blend = blend;
+    }
+
+    pub fn set_level_range(&mut self, range: DbRange) {
+        self.level_range = range;
+    }
+
+    pub fn set_tuning(&mut self, tuning: Tuning) {
+        self.tuning = tuning;
+    }
+
+    pub fn set_number_format(&mut self, numbers: NumberFormat) {
+        self.numbers = numbers;
+    }
+
     pub fn apply_snapshot(&mut self, snap: &SpectrumSnapshot) {
+        self.levels = self.level_range.with_floor(self.style.floor_db);
+        self.calibrated = snap.calibrated;
+        self.iq = snap.iq;
         let bins = snap.frequency_bins.len();
-        let (primary, secondary) = (primary_trace(&self.style), secondary_trace(&self.style));
+        let (primary, secondary) = if snap.iq {
+            (Some(0), None)
+        } else {
+            (primary_trace(&self.style), secondary_trace(&self.style))
+        };
+        let overlays: Vec<usize> = (0..OVERLAY_CHANNELS)
+            .filter(|&channel| !snap.iq && overlays_channel(self.style.overlay_channels, channel))
+            .filter(|&channel| snap.overlays[channel].iter().all(|buf| buf.len() == bins))
+            .collect();
         if bins == 0
-            || (primary.is_none() && secondary.is_none())
+            || (primary.is_none() && secondary.is_none() && overlays.is_empty())
             || [primary, secondary]
                 .into_iter()
                 .flatten()
@@ -102,47 +258,156 @@ impl SpectrumState {
             self.clear_visuals();
             return;
         }
-        let min_f = MIN_FREQUENCY;
-        let max_f = snap.frequency_bins[bins - 1].max(min_f * 1.02);
+        let (min_f, max_f) = if snap.iq {
+            let min_f = snap.frequency_bins[0];
+            (min_f, snap.frequency_bins[bins - 1].max(min_f + 1.0))
+        } else {
+            let max_f = snap.frequency_bins[bins - 1].max(MIN_FREQUENCY * 1.02);
+            (MIN_FREQUENCY, max_f)
+        };
         let bins = snap.frequency_bins.as_slice();
         self.ensure_x_cache(min_f, max_f, bins);
-        let style = &self.style;
+        let (style, levels) = (&self.style, self.levels);
 
-        let points = |idx, mode| {
-            build_single_points(
-                style,
-                min_f,
-                max_f,
-                bins,
-                trace_db(&snap.traces[idx], mode),
-                &self.x_cache,
-            )
+        let points = |trace: &SpectrumTraceSnapshot, mode| {
+            let db = trace_db(trace, mode);
+            build_single_points(style, levels, min_f, max_f, bins, db, &self.x_cache)
         };
         let primary_points = primary
-            .map(|idx| points(idx, self.style.weighting_mode))
+            .map(|idx| points(&snap.traces[idx], self.style.weighting_mode))
             .unwrap_or_default();
         let secondary_points = secondary
-            .map(|idx| points(idx, self.style.secondary_weighting_mode))
+            .map(|idx| points(&snap.traces[idx], self.style.secondary_weighting_mode))
             .unwrap_or_default();
+        let overlay_points = overlays
+            .into_iter()
+            .map(|channel| {
+                let trace = points(&snap.overlays[channel], self.style.weighting_mode);
+                (channel, share_points(trace))
+            })
+            .collect();
+        let noise = primary
+            .filter(|_| self.style.show_noise_floor)
+            .map(|idx| (idx, trace_db(&snap.noise_floor, self.style.weighting_mode)))
+            .filter(|(_, noise)| noise.len() == bins.len());
+        let noise_points = noise
+            .map(|(_, db)| {
+                build_single_points(style, levels, min_f, max_f, bins, db, &self.x_cache)
+            })
+            .unwrap_or_default();
+        let snr = noise.and_then(|(idx, noise)| {
+            let signal = trace_db(&snap.traces[idx], self.style.weighting_mode);
+            snr_readout(bins, signal, noise, min_f, max_f).map(|text| self.numbers.localize(text))
+        });
         let pk = primary
             .filter(|_| self.style.show_peak_label)
             .and_then(|idx| self.build_peak(bins, trace_db(&snap.traces[idx], self.style.weighting_mode), min_f, max_f));
 
+        let mode = self.style.weighting_mode;
+        self.latest.clear();
+        if let Some(db) = primary.map(|idx| trace_db(&snap.traces[idx], mode)) {
+            for cursor in &mut self.cursors {
+                cursor.db = Some(value_at(bins, db, cursor.hz)).filter(|db| db.is_finite());
+            }
+            let finite = bins.iter().zip(db).filter(|(_, db)| db.is_finite());
+            self.latest.extend(finite.map(|(&hz, &db)| [hz, db]));
+        }
+
+        if let Some(idx) = primary.filter(|_| self.style.peak_list > 0) {
+            self.peak_list.update(
+                Instant::now(),
+                bins,
+                trace_db(&snap.traces[idx], self.style.weighting_mode),
+                (min_f, max_f),
+                self.levels.floor_db,
+                self.style.peak_list,
+            );
+        }
+
         self.primary = share_points(primary_points);
         self.secondary = share_points(secondary_points);
+        self.overlays = overlay_points;
+        self.noise = share_points(noise_points);
+        self.snr = snr;
         self.effective_range = Some((min_f, max_f));
+        self.rebuild_memory();
         self.fade_peak(pk);
     }
 
     fn clear_visuals(&mut self) {
         (self.primary, self.secondary) = (empty_points(), empty_points());
+        self.overlays.clear();
+        self.latest.clear();
+        self.memory.clear();
+        (self.noise, self.snr) = (empty_points(), None);
         self.effective_range = None;
         self.peak = None;
+        self.peak_list.clear();
+        for cursor in &mut self.cursors {
+            cursor.db = None;
+        }
+    }
+
+    fn is_pinned(&self, freq: f32) -> bool {
+        self.pins.iter().any(|pin| peak_list::same_partial(pin.hz, freq))
+    }
+
+    // Toggles a pin for the peak-list row under the cursor.
+    fn toggle_pin_at(&mut self, bounds: Rectangle, cursor: Point) -> bool {
+        let Some(freq) = peak_list::entry_at(bounds, &self.peak_list.entries, cursor) else {
+            return false;
+        };
+        if self.is_pinned(freq) {
+            self.pins.retain(|pin| !peak_list::same_partial(pin.hz, freq));
+        } else {
+            let label = NoteInfo::from_frequency(freq, self.tuning)
+                .map_or_else(|| self.numbers.freq(freq), |ni| ni.note.to_string());
+            self.pins.push(FrequencyMarker { hz: freq, end_hz: None, label });
+        }
+        true
+    }
+
+    fn freq_at_x(&self, bounds: Rectangle, x: f32) -> Option<f32> {
+        let (min_f, max_f) = self.effective_range?;
+        let t = ((x - bounds.x) / bounds.width.max(EPSILON)).clamp(0.0, 1.0);
+        let t = if self.style.reverse_frequency { 1.0 - t } else { t };
+        let f = self.style.frequency_scale.freq_at(min_f, max_f, t);
+        f.is_finite().then_some(f)
+    }
+
+    pub(in crate::visuals) fn x_of_freq(&self, bounds: Rectangle, f: f32) -> Option<f32> {
+        let (min_f, max_f) = self.effective_range?;
+        let t = self.style.frequency_scale.pos_of(min_f, max_f, f).clamp(0.0, 1.0);
+        let t = if self.style.reverse_frequency { 1.0 - t } else { t };
+        t.is_finite().then_some(bounds.x + bounds.width * t)
+    }
+
+    // Drops a measurement cursor, removes the one under the pointer, or moves
+    // the nearer of two.
+    fn place_cursor(&mut self, bounds: Rectangle, at: Point) -> bool {
+        let Some(hz) = self.freq_at_x(bounds, at.x) else {
+            return false;
+        };
+        let nearest = self
+            .cursors
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| Some((i, (self.x_of_freq(bounds, c.hz)? - at.x).abs())))
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        let placed = MeasureCursor { hz, db: None };
+        match nearest {
+            Some((i, distance)) if distance <= CURSOR_HIT_PX => {
+                self.cursors.remove(i);
+            }
+            Some((i, _)) if self.cursors.len() >= 2 => self.cursors[i] = placed,
+            _ => self.cursors.push(placed),
+        }
+        true
     }
 
     fn ensure_x_cache(&mut self, min_f: f32, max_f: f32, bins: &[f32]) {
         let scale = self.style.frequency_scale;
-        let key = (bins.len(), max_f.to_bits(), scale);
+        let key = (bins.len(), min_f.to_bits(), max_f.to_bits(), scale);
         if self.x_cache_key == key { return; }
 
         self.x_cache.clear();
@@ -169,17 +434,17 @@ impl SpectrumState {
         let t = self.style.frequency_scale.pos_of(min_f, max_f, f);
         if !t.is_finite() || !m.is_finite() { return None; }
         let x = if self.style.reverse_frequency { 1.0 - t } else { t }.clamp(0.0, 1.0);
-        let y = ((m - self.style.floor_db) / (MAX_DB - self.style.floor_db).max(EPSILON))
-            .clamp(0.0, 1.0);
+        let y = ((m - self.levels.floor_db) / self.levels.span().max(EPSILON)).clamp(0.0, 1.0);
         if y < 0.08 { return None; }
         let unit = match self.style.weighting_mode {
             SpectrumWeightingMode::AWeighted => "dBFS(A)",
             SpectrumWeightingMode::Raw => "dBFS",
         };
-        let freq = fmt_freq(f);
-        let text = match NoteInfo::from_frequency(f) {
-            Some(ni) => [ni.fmt_note_cents(), format!("{freq}   {m:.1} {unit}")],
-            None => [freq, format!("{m:.1} {unit}")],
+        let numbers = self.numbers;
+        let freq = numbers.freq(f);
+        let text = match NoteInfo::from_frequency(f, self.tuning) {
+            Some(ni) => [ni.fmt_note_cents(), numbers.localize(format!("{freq}   {m:.1} {unit}"))],
+            None => [freq, numbers.localize(format!("{m:.1} {unit}"))],
         };
         Some((text, [x, y]))
     }
@@ -212,19 +477,49 @@ impl SpectrumState {
 
     fn peak(&self) -> Option<&PeakLabel> {
         self.peak.as_ref().filter(|_| {
-            self.style.show_peak_label && self.style.source != Channel::None && self.primary.len() >= 2
+            self.style.show_peak_label
+                && !self.style.mirrored
+                && (self.iq || self.style.source != Channel::None)
+                && self.primary.len() >= 2
         })
     }
 
+    // Names and colours of the drawn traces, once channels or memory slots
+    // are overlaid.
+    fn legend(&self, theme: &iced::Theme) -> Vec<(String, Color)> {
+        if !self.style.trace_legend || (self.overlays.is_empty() && self.memory.is_empty()) {
+            return Vec::new();
+        }
+        let pal = theme.extended_palette();
+        let mut entries = Vec::new();
+        if self.style.source != Channel::None && self.primary.len() >= 2 {
+            entries.push((self.style.source.to_string(), pal.background.base.text));
+        }
+        if self.style.secondary_source != Channel::None && self.secondary.len() >= 2 {
+            entries.push((self.style.secondary_source.to_string(), pal.secondary.weak.text));
+        }
+        for &(channel, _) in &self.overlays {
+            let color = overlay_color(&self.style, channel);
+            entries.push((format!("Ch {}", channel + 1), color));
+        }
+        for &(slot, _) in &self.memory {
+            entries.push((format!("M{}", slot + 1), memory_color(slot)));
+        }
+        entries
+    }
+
     fn visual_params(
         &self,
         bounds: Rectangle,
         theme: &iced::Theme,
         peak_layout: Option<PeakLayout>,
     ) -> Option<SpectrumParams> {
-        let has_primary = self.style.source != Channel::None && self.primary.len() >= 2;
-        let has_secondary = self.style.secondary_source != Channel::None && self.secondary.len() >= 2;
-        if !has_primary && !has_secondary { return None; }
+        let has_primary =
+            (self.iq || self.style.source != Channel::None) && self.primary.len() >= 2;
+        let has_secondary = !self.iq
+            && self.style.secondary_source != Channel::None
+            && self.secondary.len() >= 2;
+        if !has_primary && !has_secondary && self.overlays.is_empty() { return None; }
         let pal = theme.extended_palette();
 
         let visible = |show: bool, points: &SharedPoints| {
@@ -244,14 +539,31 @@ impl SpectrumState {
             bounds,
             normalized_points: primary,
             secondary_points: secondary,
+            noise_points: visible(has_primary, &self.noise),
+            noise_line_color: color_to_rgba(with_alpha(pal.background.base.text, 0.3)),
             key: self.key,
             line_color: color_to_rgba(with_alpha(pal.background.base.text, 0.92)),
             line_width: LINE_THICKNESS,
             secondary_line_color: color_to_rgba(with_alpha(pal.secondary.weak.text, 0.32)),
             secondary_line_width: SECONDARY_LINE_THICKNESS,
+            overlays: self
+                .overlays
+                .iter()
+                .map(|(channel, points)| {
+                    let color = with_alpha(overlay_color(&self.style, *channel), 0.85);
+                    (Arc::clone(points), color_to_rgba(color))
+                })
+                .chain(self.memory.iter().map(|(slot, points)| {
+                    let color = with_alpha(memory_color(*slot), 0.85);
+                    (Arc::clone(points), color_to_rgba(color))
+                }))
+                .collect(),
             highlight_threshold: self.style.highlight_threshold,
+            fill_alpha: self.style.fill_alpha,
             spectrum_palette: self.spectrum_palette.map(color_to_rgba),
+            palette_blend: self.palette_blend,
             display_mode: self.style.display_mode,
+            mirrored: self.style.mirrored,
             bar_count: self.style.bar_count,
             bar_gap: self.style.bar_gap,
             peak: peak.map(|p| SpectrumPeakParams {
@@ -260,6 +572,8 @@ impl SpectrumState {
                 leader_anchor: peak_layout.map(|l| point_to_normalized(bounds, l.leader_anchor)),
                 leader_color: color_to_rgba(with_alpha(accent, p.opacity * 0.32)),
             }),
+            opacity: self.opacity,
+            pulse: self.pulse,
         })
     }
 }
@@ -269,19 +583,191 @@ crate::visuals::visualization_widget!(Spectrum, SpectrumState, |this, r, th, b|
     let peak = state.peak();
     let peak_layout = peak.and_then(|p| peak_label_layout(b, p));
     let Some(params) = state.visual_params(b, th, peak_layout) else {
-        fill_rect(r, b, th.extended_palette().background.base.color);
+        fill_rect(r, b, faded(th.extended_palette().background.base.color, state.opacity));
         return;
     };
-    if let Some((min_f, max_f)) = state.effective_range.filter(|_| state.style.show_grid) {
-        r.with_layer(b, |r| draw_grid(r, th, b, min_f, max_f, &state.style));
+    // Mirrored, every frequency sits at two places, so nothing is pinned to one.
+    let mirrored = state.style.mirrored;
+    let freq_range = state.effective_range.filter(|_| !mirrored);
+    if let Some((min_f, max_f)) = freq_range.filter(|_| state.style.show_grid) {
+        r.with_layer(b, |r| draw_grid(r, th, b, min_f, max_f, &state));
+    }
+    if state.effective_range.is_some() {
+        r.with_layer(b, |r| {
+            draw_level_grid(r, th, b, state.style.level_grid, state.levels, state.opacity);
+        });
+    }
+    if let Some((min_f, max_f)) = freq_range {
+        let style = &state.style;
+        let to_axis = |f| {
+            let pos = style.frequency_scale.pos_of(min_f, max_f, f);
+            b.x + b.width * if style.reverse_frequency { 1.0 - pos } else { pos }
+        };
+        r.with_layer(b, |r| {
+            draw_frequency_markers(r, th, b, &style.markers, false, state.opacity, to_axis);
+            draw_frequency_markers(r, th, b, &state.pins, false, state.opacity, to_axis);
+        });
     }
-    r.draw_primitive(b, SpectrumPrimitive::new(params));
+    draw_shader(r, th, b, SpectrumPrimitive::new(params));
     if let Some((pk, layout)) = peak.zip(peak_layout) {
         let accent = state.spectrum_palette[5];
-        r.with_layer(b, |r| draw_peak(r, th, pk, layout, accent));
+        r.with_layer(b, |r| draw_peak(r, th, pk, layout, accent, state.opacity));
+    }
+    if let Some(snr) = &state.snr {
+        r.with_layer(b, |r| draw_snr(r, th, b, snr, state.opacity));
+    }
+    if state.style.peak_list > 0 {
+        r.with_layer(b, |r| peak_list::draw(r, th, b, &state));
+    }
+    if !state.cursors.is_empty() && !mirrored {
+        r.with_layer(b, |r| draw_cursors(r, th, b, &state));
+    }
+    let legend = state.legend(th);
+    if !legend.is_empty() {
+        r.with_layer(b, |r| draw_legend(r, th, b, &legend, state.opacity));
+    }
+    let muted = !state.muted_bands().is_empty();
+    let badge = match (state.calibrated, muted) {
+        (true, true) => Some("Mic calibration, bands muted"),
+        (true, false) => Some("Mic calibration"),
+        (false, true) => Some("Bands muted"),
+        (false, false) => None,
+    };
+    if let Some(badge) = badge {
+        r.with_layer(b, |r| draw_badge(r, th, b, badge, state.opacity));
+    }
+}, update |this, event, cursor, b, shell| {
+    let mut state = this.state.borrow_mut();
+    match event {
+        iced::Event::Keyboard(keyboard::Event::ModifiersChanged(m)) => state.modifiers = *m,
+        iced::Event::Mouse(mouse::Event::CursorMoved { .. }) => state.hovered = cursor.is_over(b),
+        iced::Event::Mouse(mouse::Event::CursorLeft) => state.hovered = false,
+        iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+            let Some(pos) = cursor.position_over(b).filter(|_| !state.style.mirrored) else {
+                return;
+            };
+            let handled = if state.modifiers.control() {
+                state.place_cursor(b, pos)
+            } else {
+                state.toggle_pin_at(b, pos)
+            };
+            if handled {
+                shell.capture_event();
+                shell.request_redraw();
+            }
+        }
+        _ => {}
     }
 });
 
+// `"Δf 220.0Hz   Δ -6.0 dB   P5 +2 cents"`, measured upward from the lower cursor.
+fn cursor_readout(a: MeasureCursor, b: MeasureCursor, numbers: NumberFormat) -> String {
+    let (lo, hi) = if a.hz <= b.hz { (a, b) } else { (b, a) };
+    let mut text = format!("Δf {}", numbers.freq(hi.hz - lo.hz));
+    if let (Some(lo_db), Some(hi_db)) = (lo.db, hi.db) {
+        text += &numbers.localize(format!("   Δ {:+.1} dB", hi_db - lo_db));
+    }
+    if let Some(interval) = fmt_interval(hi.hz, lo.hz) {
+        text += &format!("   {interval}");
+    }
+    text
+}
+
+fn draw_cursors(r: &mut iced::Renderer, th: &iced::Theme, b: Rectangle, state: &SpectrumState) {
+    let pal = th.extended_palette();
+    let (accent, opacity) = (state.spectrum_palette[5], state.opacity);
+    for cursor in &state.cursors {
+        let Some(x) = state.x_of_freq(b, cursor.hz) else { continue };
+        let sx = (x - 0.5).clamp(b.x, (b.x + b.width - 1.0).max(b.x));
+        let line = Rectangle::new(Point::new(sx, b.y), Size::new(1.0, b.height));
+        fill_rect(r, line, with_alpha(accent, 0.8 * opacity));
+    }
+    let text = match state.cursors.as_slice() {
+        [first, second] => cursor_readout(*first, *second, state.numbers),
+        [only] => match only.db {
+            Some(db) => {
+                let freq = state.numbers.freq(only.hz);
+                state.numbers.localize(format!("{freq}   {db:.1} dB"))
+            }
+            None => state.numbers.freq(only.hz),
+        },
+        _ => return,
+    };
+    let size = measure_text(&text, CURSOR_LABEL_SIZE);
+    let pad = Size::new(6.0, 3.0);
+    let rect = Rectangle::new(
+        Point::new(
+            b.x + GRID_LABEL_GAP,
+            b.y + b.height - GRID_LABEL_GAP - size.height - 2.0 * pad.height,
+        ),
+        Size::new(size.width + 2.0 * pad.width, size.height + 2.0 * pad.height),
+    );
+    fill_snapped_bordered_rect(
+        r,
+        rect,
+        with_alpha(pal.background.strong.color, 0.9 * opacity),
+        iced::Border {
+            color: with_alpha(accent, 0.5 * opacity),
+            width: 1.0,
+            radius: 2.0.into(),
+        },
+    );
+    let at = Point::new(rect.x + pad.width, rect.y + pad.height);
+    let color = faded(pal.background.base.text, opacity);
+    r.fill_text(make_text(text, CURSOR_LABEL_SIZE, size), at, color, b);
+}
+
+// Bottom right, clear of the peak list above and the cursor readout on the left.
+fn draw_legend(
+    r: &mut iced::Renderer,
+    th: &iced::Theme,
+    b: Rectangle,
+    entries: &[(String, Color)],
+    opacity: f32,
+) {
+    let pal = th.extended_palette();
+    let sizes: Vec<Size> = entries
+        .iter()
+        .map(|(name, _)| measure_text(name, LEGEND_LABEL_SIZE))
+        .collect();
+    let row_height = sizes.iter().map(|size| size.height).fold(LEGEND_LABEL_SIZE, f32::max);
+    let text_width = sizes.iter().map(|size| size.width).fold(0.0, f32::max);
+    let pad = Size::new(6.0, 4.0);
+    let width = LEGEND_SWATCH + 5.0 + text_width + 2.0 * pad.width;
+    let height = row_height * entries.len() as f32 + 2.0 * pad.height;
+    if width > b.width || height > b.height {
+        return;
+    }
+    let rect = Rectangle::new(
+        Point::new(
+            b.x + b.width - GRID_LABEL_GAP - width,
+            b.y + b.height - GRID_LABEL_GAP - height,
+        ),
+        Size::new(width, height),
+    );
+    fill_snapped_bordered_rect(
+        r,
+        rect,
+        with_alpha(pal.background.strong.color, 0.85 * opacity),
+        iced::Border {
+            color: with_alpha(pal.background.base.text, 0.15 * opacity),
+            width: 1.0,
+            radius: 3.0.into(),
+        },
+    );
+    let text_color = faded(pal.background.base.text, opacity);
+    for (row, ((name, color), size)) in entries.iter().zip(&sizes).enumerate() {
+        let top = rect.y + pad.height + row as f32 * row_height;
+        let swatch = Rectangle::new(
+            Point::new(rect.x + pad.width, (top + row_height * 0.5 - 1.0).round()),
+            Size::new(LEGEND_SWATCH, 2.0),
+        );
+        fill_rect(r, swatch, faded(*color, opacity));
+        let at = Point::new(swatch.x + LEGEND_SWATCH + 5.0, top);
+        r.fill_text(make_text(name, LEGEND_LABEL_SIZE, *size), at, text_color, b);
+    }
+}
+
 fn value_at(bins: &[f32], mags: &[f32], f: f32) -> f32 {
     let i = bins.partition_point(|&bin| bin < f);
     if i == 0 { return mags[0]; }
@@ -293,6 +779,36 @@ fn value_at(bins: &[f32], mags: &[f32], f: f32) -> f32 {
     )
 }
 
+// Broadband SNR over the visible range, plus the margin at the loudest bin.
+fn snr_readout(
+    bins: &[f32],
+    signal: &[f32],
+    noise: &[f32],
+    min_f: f32,
+    max_f: f32,
+) -> Option<String> {
+    let (mut signal_power, mut noise_power) = (0.0, 0.0);
+    for ((&f, &s), &n) in bins.iter().zip(signal).zip(noise) {
+        if (min_f..=max_f).contains(&f) {
+            signal_power += db_to_power(s);
+            noise_power += db_to_power(n);
+        }
+    }
+    if noise_power <= 0.0 { return None; }
+    let broadband = 10.0 * (signal_power / noise_power).log10();
+    Some(match peak_bin(bins, signal, min_f, max_f) {
+        Some(i) => format!("SNR {broadband:.1} dB   peak {:.1} dB", signal[i] - noise[i]),
+        None => format!("SNR {broadband:.1} dB"),
+    })
+}
+
+fn draw_snr(r: &mut iced::Renderer, th: &iced::Theme, b: Rectangle, text: &str, opacity: f32) {
+    let size = measure_text(text, SNR_LABEL_SIZE);
+    let at = Point::new(b.x + GRID_LABEL_GAP, b.y + GRID_LABEL_GAP);
+    let color = with_alpha(th.extended_palette().secondary.weak.text, 0.84 * opacity);
+    r.fill_text(make_text(text, SNR_LABEL_SIZE, size), at, color, b);
+}
+
 fn peak_bin(bins: &[f32], db: &[f32], min_f: f32, max_f: f32) -> Option<usize> {
     (1..bins.len().saturating_sub(1))
         .filter(|&i| (min_f..=max_f).contains(&bins[i]) && db[i].is_finite())
@@ -327,7 +843,8 @@ fn interpolated_peak(bins: &[f32], db: &[f32], bin: usize) -> Option<(f32, f32)>
     } else {
         (center - 0.25 * (left - right) * offset).max(center)
     };
-    Some(((center_freq + offset * bin_hz).max(0.0), level))
+    // Real spectra stop at DC; signed I/Q bins run past it.
+    Some(((center_freq + offset * bin_hz).max(bins[0].min(0.0)), level))
 }
 
 #[cfg(test)]
@@ -344,6 +861,10 @@ mod tests {
         state.apply_snapshot(&SpectrumSnapshot {
             frequency_bins: vec![0.0, 20.0, 40.0],
             traces: [SpectrumTraceSnapshot::default(), trace],
+            noise_floor: SpectrumTraceSnapshot::default(),
+            overlays: Default::default(),
+            calibrated: false,
+            iq: false,
         });
 
         assert!(state.primary.is_empty());
@@ -351,10 +872,131 @@ mod tests {
         assert!(state.peak().is_none());
     }
 
+    #[test]
+    fn overlaid_channels_draw_with_their_own_colours_and_legend() {
+        let trace = || [vec![-20.0; 3], vec![-20.0; 3]];
+        let mut state = SpectrumState::new();
+        state.style.source = Channel::None;
+        state.style.overlay_channels = 0b111;
+        state.style.overlay_colors = vec![TraceColor::Auto, TraceColor::Blue];
+        let mut overlays: [SpectrumTraceSnapshot; OVERLAY_CHANNELS] = Default::default();
+        // Channel 3 wasn't captured, channel 4 isn't overlaid.
+        overlays[0] = trace();
+        overlays[1] = trace();
+        overlays[3] = trace();
+
+        state.apply_snapshot(&SpectrumSnapshot {
+            frequency_bins: vec![0.0, 20.0, 40.0],
+            traces: Default::default(),
+            noise_floor: SpectrumTraceSnapshot::default(),
+            overlays,
+            calibrated: false,
+            iq: false,
+        });
+
+        let channels: Vec<usize> = state.overlays.iter().map(|(channel, _)| *channel).collect();
+        assert_eq!(channels, [0, 1]);
+        assert!(state.overlays.iter().all(|(_, points)| points.len() >= 2));
+        let legend = state.legend(&iced::Theme::Dark);
+        assert_eq!(
+            legend,
+            [
+                ("Ch 1".to_owned(), palettes::spectrum::TRACES[0]),
+                ("Ch 2".to_owned(), palettes::spectrum::TRACES[5]),
+            ]
+        );
+        state.style.trace_legend = false;
+        assert!(state.legend(&iced::Theme::Dark).is_empty());
+    }
+
+    #[test]
+    fn cursors_measure_the_gap_and_interval_between_them() {
+        let mut state = SpectrumState::new();
+        state.effective_range = Some((20.0, 20_000.0));
+        let b = Rectangle::new(Point::ORIGIN, Size::new(1000.0, 200.0));
+        let x = |f| state.x_of_freq(b, f).unwrap();
+        let (at_220, at_330, at_400) = (x(220.0), x(330.0), x(400.0));
+        for x in [at_220, at_400, at_330] {
+            assert!(state.place_cursor(b, Point::new(x, 50.0)));
+        }
+        // The third click moves the nearer cursor rather than adding one.
+        assert_eq!(state.cursors.len(), 2);
+        assert!((state.cursors[0].hz - 220.0).abs() < 1.0);
+        assert!((state.cursors[1].hz - 330.0).abs() < 1.0);
+
+        state.cursors[0].db = Some(-12.0);
+        state.cursors[1].db = Some(-18.0);
+        let readout = cursor_readout(state.cursors[1], state.cursors[0], NumberFormat::default());
+        assert!(readout.starts_with("Δf 110."), "{readout}");
+        assert!(readout.contains("Δ -6.0 dB"), "{readout}");
+        assert!(readout.ends_with("P5 +2 cents"), "{readout}");
+
+        assert!(state.place_cursor(b, Point::new(at_220 + 2.0, 50.0)));
+        assert_eq!(state.cursors.len(), 1);
+    }
+
+    #[test]
+    fn snr_compares_the_visible_range_against_the_noise_estimate() {
+        let bins = [0.0, 20.0, 40.0, 60.0, 80.0];
+        let noise = [-60.0; 5];
+        let signal = [0.0, -40.0, -30.0, -40.0, -40.0];
+        let readout = snr_readout(&bins, &signal, &noise, 20.0, 80.0).unwrap();
+        // 10·log10((1e-3 + 3e-4) / 4e-6)
+        assert_eq!(readout, "SNR 25.1 dB   peak 30.0 dB");
+        assert!(snr_readout(&bins, &signal, &noise, 90.0, 100.0).is_none());
+    }
+
+    #[test]
+    fn level_grid_lines_stay_inside_the_range_and_labels_do_not_overlap() {
+        let lines = |floor, ceiling, step| {
+            level_lines(DbRange::new(floor, ceiling), step).collect::<Vec<_>>()
+        };
+        assert_eq!(lines(-48.0, 0.0, 12.0), [-12.0, -24.0, -36.0]);
+        assert!(lines(-5.0, 0.0, 6.0).is_empty());
+        // A raised ceiling keeps the lines on whole steps.
+        assert_eq!(lines(-20.0, 10.0, 6.0), [6.0, 0.0, -6.0, -12.0, -18.0]);
+
+        let mut labels = LabelRun::default();
+        assert!(labels.claim(10.0, 12.0));
+        assert!(!labels.claim(20.0, 12.0));
+        assert!(labels.claim(10.0 + 12.0 + GRID_LABEL_GAP, 12.0));
+    }
+
+    #[test]
+    fn memory_slots_hold_the_primary_trace_and_keep_its_peaks() {
+        let bins: Vec<f32> = (0..4096).map(|i| i as f32 * 5.0).collect();
+        let mut db = vec![-60.0; 4096];
+        db[2000] = -6.0;
+        let mut state = SpectrumState::new();
+        state.apply_snapshot(&SpectrumSnapshot {
+            frequency_bins: bins,
+            traces: [[db.clone(), db], SpectrumTraceSnapshot::default()],
+            noise_floor: SpectrumTraceSnapshot::default(),
+            overlays: Default::default(),
+            calibrated: false,
+            iq: false,
+        });
+
+        let trace = state.memory_trace().unwrap();
+        assert!(trace.points.len() <= MEMORY_POINTS + 1);
+        assert!(trace.points.windows(2).all(|pair| pair[0][0] < pair[1][0]));
+        assert!(trace.points.contains(&[10_000.0, -6.0]));
+
+        let mut style = state.export_settings();
+        style.memory.set(2, Some(trace));
+        state.update_view_settings(&style, style.floor_db);
+        assert_eq!(state.memory.len(), 1);
+        assert_eq!(state.legend(&iced::Theme::Dark)[1].0, "M3");
+        style.memory.slots[2].as_mut().unwrap().visible = false;
+        state.update_view_settings(&style, style.floor_db);
+        assert!(state.memory.is_empty());
+    }
+
     #[test]
     fn point_build_emits_only_finite_coordinates() {
         let points = build_single_points(
             &SpectrumSettings::default(),
+            LevelScale::Spectrum.default_range(),
             20.0,
             40.0,
             &[0.0, 20.0, 30.0, 40.0],
@@ -379,6 +1021,76 @@ fn secondary_trace(style: &SpectrumSettings) -> Option<usize> {
     }
 }
 
+fn overlay_color(style: &SpectrumSettings, channel: usize) -> Color {
+    let choice = style.overlay_colors.get(channel).copied().unwrap_or_default();
+    let slot = match choice {
+        TraceColor::Auto => channel,
+        TraceColor::Red => 0,
+        TraceColor::Orange => 1,
+        TraceColor::Yellow => 2,
+        TraceColor::Green => 3,
+        TraceColor::Cyan => 4,
+        TraceColor::Blue => 5,
+        TraceColor::Violet => 6,
+        TraceColor::Pink => 7,
+    };
+    palettes::spectrum::TRACES[slot % palettes::spectrum::TRACES.len()]
+}
+
+fn memory_color(slot: usize) -> Color {
+    palettes::spectrum::MEMORY[slot % palettes::spectrum::MEMORY.len()]
+}
+
+// Keeps the loudest point in each of `max_points` log-spaced bands, so peaks
+// survive and the low end, where bins are sparse, keeps every bin.
+fn decimate(points: &[[f32; 2]], max_points: usize) -> Vec<[f32; 2]> {
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return Vec::new();
+    };
+    if points.len() <= max_points {
+        return points.to_vec();
+    }
+    let low = first[0].max(MIN_FREQUENCY);
+    let ratio = (last[0] / low).max(1.0 + EPSILON).powf(1.0 / max_points as f32);
+    let mut out = Vec::with_capacity(max_points + 1);
+    let (mut rest, mut edge) = (points, low);
+    while !rest.is_empty() {
+        edge *= ratio;
+        let (band, tail) = rest.split_at(rest.partition_point(|p| p[0] < edge).max(1));
+        if let Some(&loudest) = band.iter().max_by(|a, b| a[1].total_cmp(&b[1])) {
+            out.push(loudest);
+        }
+        rest = tail;
+    }
+    out
+}
+
+// Memory points come from any FFT size, so x is placed per point rather than
+// read from the live trace's cache.
+fn memory_points(
+    style: &SpectrumSettings,
+    levels: DbRange,
+    min_f: f32,
+    max_f: f32,
+    points: &[[f32; 2]],
+) -> Vec<[f32; 2]> {
+    let dr = levels.span().max(EPSILON);
+    let mut out: Vec<[f32; 2]> = points
+        .iter()
+        .filter(|&&[hz, _]| (min_f..=max_f).contains(&hz))
+        .filter_map(|&[hz, db]| {
+            let x = style.frequency_scale.pos_of(min_f, max_f, hz).clamp(0.0, 1.0);
+            let y = ((db - levels.floor_db) / dr).clamp(0.0, 1.0);
+            let x = if style.reverse_frequency { 1.0 - x } else { x };
+            (x.is_finite() && y.is_finite()).then_some([x, y])
+        })
+        .collect();
+    if style.reverse_frequency {
+        out.reverse();
+    }
+    out
+}
+
 fn weighting_slot(mode: SpectrumWeightingMode) -> usize {
     match mode {
         SpectrumWeightingMode::AWeighted => 0,
@@ -392,14 +1104,15 @@ fn trace_db(trace: &SpectrumTraceSnapshot, mode: SpectrumWeightingMode) -> &[f32
 
 fn build_single_points(
     style: &SpectrumSettings,
+    levels: DbRange,
     min_f: f32,
     max_f: f32,
     bins: &[f32],
     db: &[f32],
     x_cache: &[f32],
 ) -> Vec<[f32; 2]> {
-    let dr = (MAX_DB - style.floor_db).max(EPSILON);
-    let y = |m: f32| ((m - style.floor_db) / dr).clamp(0.0, 1.0);
+    let dr = levels.span().max(EPSILON);
+    let y = |m: f32| ((m - levels.floor_db) / dr).clamp(0.0, 1.0);
     let mut out = Vec::with_capacity(x_cache.len());
     let mut xi = 0;
     let mut push = |m: f32| {
@@ -430,30 +1143,28 @@ fn draw_grid(
     b: Rectangle,
     min_f: f32,
     max_f: f32,
-    style: &SpectrumSettings,
+    state: &SpectrumState,
 ) {
     if b.width <= 0.0 || b.height <= 0.0 {
         return;
     }
-    let start_exp = min_f.max(1.0).log10().floor() as i32;
-    let end_exp = max_f.log10().ceil() as i32;
+    let (style, tuning, numbers) = (&state.style, state.tuning, state.numbers);
+    // Signed I/Q ranges repeat the ticks below zero, with zero itself major.
+    let signed = min_f < 0.0;
+    let lowest = if signed { MIN_FREQUENCY } else { min_f.max(1.0) };
+    let start_exp = lowest.log10().floor() as i32;
+    let end_exp = max_f.max(-min_f).log10().ceil() as i32;
     if end_exp < start_exp {
         return;
     }
+    let signs: &[f32] = if signed { &[-1.0, 1.0] } else { &[1.0] };
 
     let reverse = style.reverse_frequency;
     let pal = th.extended_palette();
-    let txt = pal.background.base.text;
-    let (major_lc, major_tc) = (with_alpha(txt, 0.25), with_alpha(txt, 0.75));
-    let (minor_lc, minor_tc) = (with_alpha(txt, 0.10), with_alpha(txt, 0.20));
+    let (txt, op) = (pal.background.base.text, state.opacity);
+    let (major_lc, major_tc) = (with_alpha(txt, 0.25 * op), with_alpha(txt, 0.75 * op));
+    let (minor_lc, minor_tc) = (with_alpha(txt, 0.10 * op), with_alpha(txt, 0.20 * op));
 
-    let exp_of = |di| {
-        if reverse {
-            end_exp - di
-        } else {
-            start_exp + di
-        }
-    };
     let tick_x = |f: f32| -> Option<f32> {
         if !(min_f..=max_f).contains(&f) { return None; }
         let pos = style
@@ -468,50 +1179,141 @@ fn draw_grid(
         fill_rect(r, Rectangle::new(Point::new(sx, top), Size::new(1.0, h)), c);
     };
 
-    for di in 0..=(end_exp - start_exp) {
-        let base = 10f32.powi(exp_of(di));
-        for &mult in &[3u32, 4, 6, 7, 8, 9] {
-            if let Some(x) = tick_x(base * mult as f32) {
+    let slot = Size::new(48.0_f32, 12.0);
+    let ty = b.y + GRID_LABEL_GAP;
+    let clamp_lo = b.x + GRID_LABEL_GAP;
+    let clamp_hi = (b.x + b.width - GRID_LABEL_GAP - slot.width).max(clamp_lo);
+    let mut labels = LabelRun::default();
+    let mut label = |r: &mut iced::Renderer, x: f32, s: String, c: Color| {
+        let tx = (x - slot.width * 0.5).clamp(clamp_lo, clamp_hi);
+        if !labels.claim(tx, slot.width) {
+            return;
+        }
+        let mut text = make_text(s, GRID_LABEL_SIZE, slot);
+        text.align_x = iced::alignment::Horizontal::Center.into();
+        let clip = Rectangle::new(Point::new(tx, ty), slot);
+        r.fill_text(text, Point::new(tx + slot.width * 0.5, ty), c, clip);
+    };
+
+    if style.note_axis && style.frequency_scale == FrequencyScale::Logarithmic && !signed {
+        // Every C is a major line; the other semitones only where they sit
+        // far enough apart to read.
+        let mut notes: Vec<(MusicalNote, f32)> = (0..=NOTE_AXIS_TOP_MIDI)
+            .map(MusicalNote::from_midi)
+            .filter_map(|note| Some((note, tick_x(note.to_frequency(tuning))?)))
+            .collect();
+        if reverse {
+            notes.reverse();
+        }
+        let mut last_x = f32::NEG_INFINITY;
+        for &(note, x) in &notes {
+            if note.name == "C" {
+                vline(r, x, b.y, b.height, major_lc);
+                label(r, x, note.to_string(), major_tc);
+            } else if x - last_x >= SEMITONE_MIN_PX {
                 vline(r, x, b.y, b.height, minor_lc);
             }
+            last_x = x;
         }
+        return;
     }
 
-    let slot = Size::new(48.0_f32, 12.0);
-    let ty = b.y + GRID_LABEL_GAP;
-    let clamp_lo = b.x + GRID_LABEL_GAP;
-    let clamp_hi = (b.x + b.width - GRID_LABEL_GAP - slot.width).max(clamp_lo);
-    let mults: [u32; 3] = if reverse { [5, 2, 1] } else { [1, 2, 5] };
-    let mut last_right = f32::NEG_INFINITY;
-
-    for di in 0..=(end_exp - start_exp) {
-        let base = 10f32.powi(exp_of(di));
-        for &mult in &mults {
-            let f = base * mult as f32;
-            let Some(x) = tick_x(f) else { continue };
-            let (lc, tc) = if mult == 1 {
-                (major_lc, major_tc)
-            } else {
-                (minor_lc, minor_tc)
-            };
+    let decade_ticks = |mults: &'static [u32]| {
+        signs.iter().flat_map(move |&sign| {
+            (start_exp..=end_exp).flat_map(move |exp| {
+                let base = sign * 10f32.powi(exp);
+                mults.iter().map(move |&mult| (base * mult as f32, mult == 1))
+            })
+        })
+    };
+    for (f, _) in decade_ticks(&[3, 4, 6, 7, 8, 9]) {
+        if let Some(x) = tick_x(f) {
+            vline(r, x, b.y, b.height, minor_lc);
+        }
+    }
 
-            vline(r, x, b.y, b.height, lc);
+    // Labelled left to right, so the run drops overlaps the same either way.
+    let zero = signed.then_some((0.0, true));
+    let mut ticks: Vec<(f32, f32, bool)> = decade_ticks(&[1, 2, 5])
+        .chain(zero)
+        .filter_map(|(f, major)| Some((tick_x(f)?, f, major)))
+        .collect();
+    ticks.sort_by(|a, b| a.0.total_cmp(&b.0));
+    for (x, f, major) in ticks {
+        let (lc, tc) = if major { (major_lc, major_tc) } else { (minor_lc, minor_tc) };
+        vline(r, x, b.y, b.height, lc);
+        label(r, x, numbers.freq(f), tc);
+    }
+}
 
-            let tx = (x - slot.width * 0.5).clamp(clamp_lo, clamp_hi);
-            if tx < last_right {
-                continue;
-            }
-            last_right = tx + slot.width + GRID_LABEL_GAP;
-
-            let mut text = make_text(fmt_freq(f), GRID_LABEL_SIZE, slot);
-            text.align_x = iced::alignment::Horizontal::Center.into();
-            r.fill_text(
-                text,
-                Point::new(tx + slot.width * 0.5, ty),
-                tc,
-                Rectangle::new(Point::new(tx, ty), slot),
-            );
+// Lines on whole `step`s strictly between the floor and the ceiling, loudest first.
+fn level_lines(levels: DbRange, step: f32) -> impl Iterator<Item = f32> {
+    let top = (levels.ceiling_db / step).ceil() - 1.0;
+    (0..)
+        .map(move |i| (top - i as f32) * step)
+        .take_while(move |&db| db > levels.floor_db + EPSILON)
+}
+
+fn level_step(grid: SpectrumLevelGrid) -> Option<f32> {
+    match grid {
+        SpectrumLevelGrid::Off => None,
+        SpectrumLevelGrid::Db6 => Some(6.0),
+        SpectrumLevelGrid::Db10 => Some(10.0),
+        SpectrumLevelGrid::Db12 => Some(12.0),
+    }
+}
+
+fn draw_level_grid(
+    r: &mut iced::Renderer,
+    th: &iced::Theme,
+    b: Rectangle,
+    grid: SpectrumLevelGrid,
+    levels: DbRange,
+    opacity: f32,
+) {
+    let Some(step) = level_step(grid) else { return };
+    let range = levels.span();
+    if b.width <= 0.0 || b.height <= 0.0 || range <= EPSILON {
+        return;
+    }
+    let txt = th.extended_palette().background.base.text;
+    let line_color = with_alpha(txt, 0.12 * opacity);
+    let text_color = with_alpha(txt, 0.6 * opacity);
+    // Keep clear of the frequency labels and SNR readout along the top edge.
+    let mut labels = LabelRun::after(b.y + GRID_LABEL_GAP + GRID_LABEL_SIZE * 1.2);
+
+    for db in level_lines(levels, step) {
+        let y = (b.y + b.height * (levels.ceiling_db - db) / range).round();
+        fill_rect(r, Rectangle::new(Point::new(b.x, y), Size::new(b.width, 1.0)), line_color);
+
+        let label = format!("{db:.0} dB");
+        let size = measure_text(&label, GRID_LABEL_SIZE);
+        let top = y - size.height - 1.0;
+        if !labels.claim(top, size.height) {
+            continue;
         }
+        let at = Point::new(b.x + GRID_LABEL_GAP, top);
+        r.fill_text(make_text(label, GRID_LABEL_SIZE, size), at, text_color, b);
+    }
+}
+
+// Places axis labels in order, dropping any that would overlap the last one kept.
+#[derive(Default)]
+struct LabelRun {
+    next_free: Option<f32>,
+}
+
+impl LabelRun {
+    fn after(position: f32) -> Self {
+        Self { next_free: Some(position) }
+    }
+
+    fn claim(&mut self, start: f32, extent: f32) -> bool {
+        if self.next_free.is_some_and(|free| start < free) {
+            return false;
+        }
+        self.next_free = Some(start + extent + GRID_LABEL_GAP);
+        true
     }
 }
 
@@ -556,14 +1358,16 @@ fn draw_peak(
     pk: &PeakLabel,
     layout: PeakLayout,
     accent: Color,
+    opacity: f32,
 ) {
     let pal = th.extended_palette();
+    let opacity = pk.opacity * opacity;
     fill_snapped_bordered_rect(
         r,
         layout.rect,
-        with_alpha(pal.background.strong.color, 0.90 * pk.opacity),
+        with_alpha(pal.background.strong.color, 0.90 * opacity),
         iced::Border {
-            color: with_alpha(accent, 0.50 * pk.opacity),
+            color: with_alpha(accent, 0.50 * opacity),
             width: 1.0,
             radius: 2.0.into(),
         },
@@ -571,14 +1375,14 @@ fn draw_peak(
     r.fill_text(
         make_text(&pk.text[0], 12.0, layout.title),
         layout.text,
-        with_alpha(pal.background.base.text, pk.opacity),
+        with_alpha(pal.background.base.text, opacity),
         Rectangle::new(layout.text, layout.title),
     );
     let pos = Point::new(layout.text.x, layout.text.y + layout.title.height + 2.0);
     r.fill_text(
         make_text(&pk.text[1], 10.0, layout.detail),
         pos,
-        with_alpha(pal.secondary.weak.text, 0.84 * pk.opacity),
+        with_alpha(pal.secondary.weak.text, 0.84 * opacity),
         Rectangle::new(pos, layout.detail),
     );
 }
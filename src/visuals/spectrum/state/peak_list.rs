@@ -0,0 +1,228 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+use super::{SpectrumState, interpolated_peak};
+use crate::util::audio::musical::NoteInfo;
+use crate::util::color::with_alpha;
+use crate::util::lerp;
+use crate::visuals::render::common::{faded, fill_bordered_rect, make_text, measure_text};
+use iced::advanced::text::Renderer as _;
+use iced::{Point, Rectangle, Size};
+use std::time::{Duration, Instant};
+
+const UPDATE_INTERVAL: Duration = Duration::from_millis(250);
+// Peaks closer than a semitone are treated as the same partial.
+const SEMITONE: f32 = 1.059_463_1;
+// Entries survive until they drop out of the top `count * KEEP_FACTOR` candidates.
+const KEEP_FACTOR: usize = 2;
+const DB_SMOOTHING: f32 = 0.5;
+const MIN_PROMINENCE_DB: f32 = 6.0;
+
+const ROW_HEIGHT: f32 = 15.0;
+const TABLE_WIDTH: f32 = 168.0;
+const TABLE_PAD: f32 = 6.0;
+const TABLE_MARGIN: f32 = 8.0;
+const TABLE_TOP: f32 = 24.0;
+const TEXT_SIZE: f32 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct PeakEntry {
+    pub freq: f32,
+    pub db: f32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct PeakList {
+    pub entries: Vec<PeakEntry>,
+    last_update: Option<Instant>,
+}
+
+pub(super) fn same_partial(a: f32, b: f32) -> bool {
+    a.max(b) < a.min(b) * SEMITONE
+}
+
+impl PeakList {
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.last_update = None;
+    }
+
+    pub fn update(
+        &mut self,
+        now: Instant,
+        bins: &[f32],
+        db: &[f32],
+        range: (f32, f32),
+        floor_db: f32,
+        count: usize,
+    ) {
+        if self
+            .last_update
+            .is_some_and(|last| now.duration_since(last) < UPDATE_INTERVAL)
+        {
+            return;
+        }
+        self.last_update = Some(now);
+        let candidates = candidates(bins, db, range, floor_db, count * KEEP_FACTOR);
+
+        let mut next: Vec<PeakEntry> = self
+            .entries
+            .iter()
+            .filter_map(|old| {
+                let new = candidates.iter().find(|c| same_partial(c.freq, old.freq))?;
+                Some(PeakEntry {
+                    freq: new.freq,
+                    db: lerp(old.db, new.db, DB_SMOOTHING),
+                })
+            })
+            .collect();
+        for candidate in &candidates {
+            if next.len() >= count {
+                break;
+            }
+            if !next.iter().any(|e| same_partial(e.freq, candidate.freq)) {
+                next.push(*candidate);
+            }
+        }
+        next.sort_by(|a, b| b.db.total_cmp(&a.db));
+        next.truncate(count);
+        self.entries = next;
+    }
+}
+
+// Strongest local maxima, loudest first, at most one per semitone.
+fn candidates(
+    bins: &[f32],
+    db: &[f32],
+    (min_f, max_f): (f32, f32),
+    floor_db: f32,
+    limit: usize,
+) -> Vec<PeakEntry> {
+    let mut maxima: Vec<PeakEntry> = (1..bins.len().min(db.len()).saturating_sub(1))
+        .filter(|&i| {
+            (min_f..=max_f).contains(&bins[i])
+                && db[i] > db[i - 1]
+                && db[i] >= db[i + 1]
+                && db[i] > floor_db + MIN_PROMINENCE_DB
+        })
+        .filter_map(|i| interpolated_peak(bins, db, i))
+        .map(|(freq, db)| PeakEntry { freq, db })
+        .collect();
+    maxima.sort_by(|a, b| b.db.total_cmp(&a.db));
+    let mut out: Vec<PeakEntry> = Vec::with_capacity(limit);
+    for peak in maxima {
+        if out.len() >= limit {
+            break;
+        }
+        if !out.iter().any(|p| same_partial(p.freq, peak.freq)) {
+            out.push(peak);
+        }
+    }
+    out
+}
+
+fn table_rect(bounds: Rectangle, rows: usize) -> Rectangle {
+    let width = TABLE_WIDTH.min(bounds.width - 2.0 * TABLE_MARGIN).max(0.0);
+    Rectangle::new(
+        Point::new(bounds.x + bounds.width - width - TABLE_MARGIN, bounds.y + TABLE_TOP),
+        Size::new(width, rows as f32 * ROW_HEIGHT + 2.0 * TABLE_PAD),
+    )
+}
+
+fn row_rect(table: Rectangle, row: usize) -> Rectangle {
+    Rectangle::new(
+        Point::new(table.x, table.y + TABLE_PAD + row as f32 * ROW_HEIGHT),
+        Size::new(table.width, ROW_HEIGHT),
+    )
+}
+
+pub(super) fn entry_at(bounds: Rectangle, entries: &[PeakEntry], cursor: Point) -> Option<f32> {
+    let table = table_rect(bounds, entries.len());
+    entries
+        .iter()
+        .enumerate()
+        .find(|&(row, _)| row_rect(table, row).contains(cursor))
+        .map(|(_, entry)| entry.freq)
+}
+
+pub(super) fn draw(
+    r: &mut iced::Renderer,
+    th: &iced::Theme,
+    bounds: Rectangle,
+    state: &SpectrumState,
+) {
+    let entries = &state.peak_list.entries;
+    if entries.is_empty() || bounds.width < TABLE_WIDTH * 0.5 {
+        return;
+    }
+    let (tuning, numbers, opacity) = (state.tuning, state.numbers, state.opacity);
+    let pal = th.extended_palette();
+    let table = table_rect(bounds, entries.len());
+    fill_bordered_rect(
+        r,
+        table,
+        with_alpha(pal.background.strong.color, 0.8 * opacity),
+        iced::Border {
+            color: with_alpha(pal.background.base.text, 0.15 * opacity),
+            width: 1.0,
+            radius: 3.0.into(),
+        },
+    );
+    let columns = [TABLE_PAD, TABLE_PAD + 52.0, TABLE_PAD + 112.0];
+    for (row, entry) in entries.iter().enumerate() {
+        let rect = row_rect(table, row);
+        let color = if state.is_pinned(entry.freq) {
+            pal.danger.base.color
+        } else {
+            pal.background.base.text
+        };
+        let color = faded(color, opacity);
+        let note = NoteInfo::from_frequency(entry.freq, tuning).map_or_else(String::new, |ni| {
+            format!("{}{:+}", ni.note, ni.cents)
+        });
+        let level = numbers.localize(format!("{:.1} dB", entry.db));
+        let cells = [note, numbers.freq(entry.freq), level];
+        for (text, offset) in cells.into_iter().zip(columns) {
+            let size = measure_text(&text, TEXT_SIZE);
+            let at = Point::new(rect.x + offset, rect.y + (ROW_HEIGHT - size.height) * 0.5);
+            r.fill_text(make_text(text, TEXT_SIZE, size), at, color, table);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spectrum(peaks: &[(usize, f32)]) -> (Vec<f32>, Vec<f32>) {
+        let bins: Vec<f32> = (0..512).map(|i| i as f32 * 20.0).collect();
+        let mut db = vec![-90.0; bins.len()];
+        for &(bin, level) in peaks {
+            db[bin] = level;
+            db[bin - 1] = level - 10.0;
+            db[bin + 1] = level - 10.0;
+        }
+        (bins, db)
+    }
+
+    #[test]
+    fn entries_hold_their_place_until_they_fall_out_of_the_keep_band() {
+        let mut list = PeakList::default();
+        let t0 = Instant::now();
+        let (bins, db) = spectrum(&[(50, -10.0), (100, -20.0), (200, -30.0)]);
+        list.update(t0, &bins, &db, (20.0, 10_000.0), -90.0, 2);
+        let freqs: Vec<f32> = list.entries.iter().map(|e| e.freq).collect();
+        assert_eq!(freqs, [1000.0, 2000.0]);
+
+        // A slightly louder third peak doesn't evict an entry inside the keep band.
+        let (bins, db) = spectrum(&[(50, -10.0), (100, -21.0), (200, -19.0)]);
+        list.update(t0 + UPDATE_INTERVAL, &bins, &db, (20.0, 10_000.0), -90.0, 2);
+        assert!(list.entries.iter().any(|e| e.freq == 2000.0));
+        assert_eq!(list.entries[1].db, -20.5);
+
+        // Updates are rate limited.
+        let (bins, db) = spectrum(&[(300, -5.0)]);
+        list.update(t0 + UPDATE_INTERVAL, &bins, &db, (20.0, 10_000.0), -90.0, 2);
+        assert_eq!(list.entries.len(), 2);
+    }
+}
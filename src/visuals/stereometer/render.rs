@@ -1,18 +1,20 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // Copyright (C) 2026 Maika Namuo
 
-use iced::Rectangle;
 use iced::advanced::graphics::Viewport;
+use iced::alignment::Horizontal;
+use iced::{Point, Rectangle};
 use std::sync::Arc;
 
 use crate::visuals::render::common::sdf_primitive;
 use crate::visuals::options::{
     CorrelationMeterMode, CorrelationMeterSide, StereometerMode, StereometerScale,
 };
+use crate::util::audio::db_to_gain;
 use crate::util::lerp;
 use crate::visuals::render::common::{
     ClipTransform, GeometryScratch, SdfVertex, dot_vertices, gradient_quad_vertices, line_vertices,
-    quad_vertices,
+    quad_vertices, rule_vertices,
 };
 
 // 0.66834.powf(0.3) and (1.0 / 0.66834).powi(2), respectively. Working
@@ -27,6 +29,12 @@ const GRID_LINE_WIDTH: f32 = 1.0;
 const GRID_CORNERS: [(f32, f32); 4] = [(1.0, 1.0), (-1.0, 1.0), (-1.0, -1.0), (1.0, -1.0)];
 const GRID_AXES: [((f32, f32), (f32, f32)); 2] =
     [((1.0, 1.0), (-1.0, -1.0)), ((1.0, -1.0), (-1.0, 1.0))];
+const LR_GUIDES: [((f32, f32), (f32, f32)); 2] = [((1.0, 0.0), (-1.0, 0.0)), ((0.0, 1.0), (0.0, -1.0))];
+// Peak level of a mono signal, on the scaled scale only.
+const RING_DB: [f32; 4] = [-24.0, -12.0, -6.0, 0.0];
+const RING_SEGMENTS: usize = 64;
+const LABEL_INSET: f32 = 8.0;
+const LABEL_MIN_GAP: f32 = 6.0;
 
 const CORR_W: f32 = 28.0;
 const CORR_PAD: f32 = 4.0;
@@ -65,10 +73,16 @@ pub struct StereometerParams {
     pub rotation: i8,
     pub flip: bool,
     pub unipolar: bool,
+    pub axis_lines: bool,
+    pub lr_guides: bool,
+    pub db_rings: bool,
     pub correlation_meter: CorrelationMeterMode,
     pub correlation_meter_side: CorrelationMeterSide,
     pub corr_trail: Vec<f32>,
     pub band_trail: [Vec<f32>; 3],
+    pub opacity: f32,
+    /// Beat-pulse brightening, `0..=1`.
+    pub pulse: f32,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -165,6 +179,12 @@ impl Projection {
     }
 }
 
+// Display radius, in unit space, of a mono signal peaking at `db`. Both
+// channels at `gain` put the point `gain * sqrt(2)` from the centre.
+fn ring_radius(db: f32) -> f32 {
+    scaled_point(db_to_gain(db) * std::f32::consts::SQRT_2, 0.0).0
+}
+
 fn clip_segment_to_visible_unipolar_half(
     mut a: (f32, f32),
     mut b: (f32, f32),
@@ -202,7 +222,7 @@ fn projected_line(
             (lerp(a.0, b.0, t0), lerp(a.1, b.1, t0)),
             (lerp(a.0, b.0, t1), lerp(a.1, b.1, t1)),
         ) {
-            out.extend(line_vertices(p0, p1, color, color, GRID_LINE_WIDTH, clip));
+            out.extend(rule_vertices(p0, p1, color, GRID_LINE_WIDTH, clip));
         }
     }
 }
@@ -237,14 +257,83 @@ impl StereometerPrimitive {
             }
         }
 
-        let axes = if self.params.mode == StereometerMode::Lissajous {
-            &GRID_AXES[..1]
-        } else {
-            &GRID_AXES[..]
+        let p = &self.params;
+        let axes = match (p.axis_lines, p.mode) {
+            (false, _) => &[][..],
+            (true, StereometerMode::Lissajous) => &GRID_AXES[..1],
+            (true, _) => &GRID_AXES[..],
         };
-        for &(start, end) in axes {
+        let guides = if p.lr_guides { &LR_GUIDES[..] } else { &[] };
+        for &(start, end) in axes.iter().chain(guides) {
             projected_line(vertices, projection, start, end, color, clip);
         }
+
+        if p.db_rings && projection.scale == StereometerScale::Scaled {
+            for db in RING_DB {
+                let radius = ring_radius(db);
+                let point = |i: usize| {
+                    let angle = i as f32 / RING_SEGMENTS as f32 * std::f32::consts::TAU;
+                    (radius * angle.cos(), radius * angle.sin())
+                };
+                for i in 0..RING_SEGMENTS {
+                    let (a, b) = (point(i), point(i + 1));
+                    let segment = if projection.unipolar {
+                        clip_segment_to_visible_unipolar_half(a, b)
+                    } else {
+                        Some((a, b))
+                    };
+                    if let Some((a, b)) = segment {
+                        let (a, b) = (projection.to_screen(a), projection.to_screen(b));
+                        vertices.extend(rule_vertices(a, b, color, GRID_LINE_WIDTH, clip));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Names for the shown axes and guides, near their ends, and levels for
+    /// the dB rings, as text, anchor and alignment.
+    pub(super) fn grid_labels(p: &StereometerParams) -> Vec<(String, Point, Horizontal)> {
+        let (vector, _) = Self::meter_layout(p);
+        let projection = Projection::from_params(p, vector);
+        let mut ends: Vec<(&str, (f32, f32))> = Vec::new();
+        if p.axis_lines {
+            ends.push(("M", (1.0, 1.0)));
+            if p.mode != StereometerMode::Lissajous {
+                ends.extend([("S", (1.0, -1.0)), ("S", (-1.0, 1.0))]);
+            }
+        }
+        if p.lr_guides {
+            ends.extend([("L", (1.0, 0.0)), ("R", (0.0, 1.0))]);
+        }
+        let mut labels: Vec<(String, Point, Horizontal)> = Vec::new();
+        let mut push = |text: String, at: Point, align| {
+            if labels.iter().all(|(_, other, _)| other.distance(at) >= LABEL_MIN_GAP) {
+                labels.push((text, at, align));
+            }
+        };
+        for (name, (l, r)) in ends {
+            let (x, y) = projection.project(l, r);
+            let (dx, dy) = (x - projection.cx, y - projection.cy);
+            let len = dx.hypot(dy);
+            if len > LABEL_INSET * 2.0 {
+                let at = Point::new(x - dx / len * LABEL_INSET, y - dy / len * LABEL_INSET);
+                push(name.to_owned(), at, Horizontal::Center);
+            }
+        }
+        if p.db_rings && projection.scale == StereometerScale::Scaled {
+            // Rotations step by 45 degrees, so halfway between two stays clear of the axes.
+            let (sin, cos) = std::f32::consts::FRAC_PI_8.sin_cos();
+            for db in RING_DB {
+                let radius = ring_radius(db) * projection.radius;
+                let at = Point::new(
+                    projection.cx + sin * radius + 2.0,
+                    projection.cy - cos * radius,
+                );
+                push(format!("{db:.0}"), at, Horizontal::Left);
+            }
+        }
+        labels
     }
 
     pub(super) fn correlation_y(bounds: Rectangle, value: f32) -> f32 {
@@ -523,6 +612,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rings_grow_with_level_and_labels_follow_the_toggles() {
+        assert!(RING_DB.windows(2).all(|w| ring_radius(w[0]) < ring_radius(w[1])));
+        assert!(ring_radius(0.0) <= 1.0);
+
+        let mut params = StereometerParams {
+            key: 0,
+            bounds: BOUNDS,
+            points: Arc::default(),
+            band_points: Default::default(),
+            palette: [[1.0; 4]; 9],
+            mode: StereometerMode::DotCloud,
+            scale: Scaled,
+            dot_radius: 1.5,
+            rotation: -1,
+            flip: true,
+            unipolar: false,
+            axis_lines: true,
+            lr_guides: false,
+            db_rings: false,
+            correlation_meter: CorrelationMeterMode::Off,
+            correlation_meter_side: CorrelationMeterSide::Right,
+            corr_trail: Vec::new(),
+            band_trail: Default::default(),
+            opacity: 1.0,
+            pulse: 0.0,
+        };
+        let names = |params: &StereometerParams| {
+            StereometerPrimitive::grid_labels(params)
+                .into_iter()
+                .map(|(text, at, _)| {
+                    assert_inside((at.x, at.y));
+                    text
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&params), ["M", "S", "S"]);
+        params.lr_guides = true;
+        params.db_rings = true;
+        assert_eq!(names(&params), ["M", "S", "S", "L", "R", "-24", "-12", "-6", "0"]);
+        params.scale = Linear;
+        params.axis_lines = false;
+        assert_eq!(names(&params), ["L", "R"]);
+    }
+
     #[test]
     fn unipolar_clip_rejects_hidden_segments_and_trims_crossings() {
         assert!(clip_segment_to_visible_unipolar_half((-1.0, 1.0), (1.0, 1.0)).is_none());
@@ -6,11 +6,11 @@ use super::render::{
     CORR_LABEL_GAP, CORR_LABEL_H, CORR_LABEL_W, StereometerParams, StereometerPrimitive,
 };
 use crate::persistence::settings::StereometerSettings;
-use crate::util::color::color_to_rgba;
+use crate::util::color::{color_to_rgba, with_alpha};
 use crate::visuals::{
     options::{CorrelationMeterMode, CorrelationMeterSide, StereometerMode},
     palettes,
-    render::common::{fill_rect, make_text},
+    render::common::{draw_shader, faded, fill_rect, make_text},
 };
 use iced::advanced::text;
 use iced::alignment::{Horizontal, Vertical};
@@ -19,6 +19,7 @@ use std::{collections::VecDeque, sync::Arc};
 
 const TRAIL_LEN: usize = 32;
 const CORR_LABEL_SIZE: f32 = 10.0;
+const GRID_LABEL_SIZE: f32 = 10.0;
 
 fn tracks_band_correlation(s: &StereometerSettings) -> bool {
     s.mode == StereometerMode::DotCloudBands
@@ -34,6 +35,8 @@ pub(in crate::visuals) struct StereometerState {
     pub(in crate::visuals) palette: [Color; 9],
     settings: StereometerSettings,
     key: u64,
+    opacity: f32,
+    pulse: f32,
 }
 
 impl StereometerState {
@@ -47,6 +50,8 @@ impl StereometerState {
             palette: palettes::stereometer::COLORS,
             settings: defaults,
             key: crate::visuals::next_key(),
+            opacity: 1.0,
+            pulse: 0.0,
         }
     }
 
@@ -67,6 +72,14 @@ impl StereometerState {
         };
     }
 
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity;
+    }
+
+    pub fn set_pulse(&mut self, pulse: f32) {
+        self.pulse = pulse;
+    }
+
     pub fn set_palette(&mut self, palette: &[Color; 9]) {
         self.palette = *palette;
     }
@@ -126,10 +139,15 @@ impl StereometerState {
             rotation: s.rotation,
             flip: s.flip,
             unipolar: s.unipolar && s.mode != StereometerMode::Lissajous,
+            axis_lines: s.axis_lines,
+            lr_guides: s.lr_guides,
+            db_rings: s.db_rings,
             correlation_meter: s.correlation_meter,
             correlation_meter_side: s.correlation_meter_side,
             corr_trail,
             band_trail,
+            opacity: self.opacity,
+            pulse: self.pulse,
         })
     }
 }
@@ -137,12 +155,29 @@ impl StereometerState {
 crate::visuals::visualization_widget!(Stereometer, StereometerState, |this, renderer, theme, bounds| {
     let state = this.state.borrow();
     let Some(params) = state.visual_params(bounds) else {
-        fill_rect(renderer, bounds, theme.extended_palette().background.base.color);
+        let background = theme.extended_palette().background.base.color;
+        fill_rect(renderer, bounds, faded(background, state.opacity));
         return;
     };
     let side = params.correlation_meter_side;
     let (_, meter) = StereometerPrimitive::meter_layout(&params);
-    renderer.draw_primitive(bounds, StereometerPrimitive::new(params));
+    let grid_labels = if state.settings.grid_labels {
+        StereometerPrimitive::grid_labels(&params)
+    } else {
+        Vec::new()
+    };
+    draw_shader(renderer, theme, bounds, StereometerPrimitive::new(params));
+
+    if !grid_labels.is_empty() {
+        let color = with_alpha(theme.extended_palette().background.base.text, 0.7 * state.opacity);
+        for (label, at, align) in grid_labels {
+            let size = Size::new(CORR_LABEL_W * 2.0, CORR_LABEL_H);
+            let mut text = make_text(label, GRID_LABEL_SIZE, size);
+            text.align_x = align.into();
+            text.align_y = Vertical::Center;
+            text::Renderer::fill_text(renderer, text, at, color, bounds);
+        }
+    }
 
     if let Some(meter) = meter.filter(|meter| meter.width > 0.0 && meter.height > 0.0) {
         let left = side == CorrelationMeterSide::Left;
@@ -152,7 +187,7 @@ crate::visuals::visualization_widget!(Stereometer, StereometerState, |this, rend
         } else {
             meter.x - CORR_LABEL_GAP
         };
-        let color = theme.extended_palette().background.base.text;
+        let color = faded(theme.extended_palette().background.base.text, state.opacity);
         for (label, value) in [("+1", 1.0), ("0", 0.0), ("-1", -1.0)] {
             let mut text = make_text(
                 label,
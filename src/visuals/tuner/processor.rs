@@ -0,0 +1,169 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+// Tuner readings: a small reassigned spectrogram feeds the pitch tracker, and
+// each pitch is read against the nearest note, or with a preset, against the
+// nearest open string. The state reads each pitch against its tuning reference.
+
+use crate::dsp::AudioBlock;
+use crate::dsp::pitch::PitchTracker;
+use crate::util::audio::musical::{MusicalNote, Tuning};
+use crate::util::audio::{DEFAULT_SAMPLE_RATE, db_to_power};
+use crate::visuals::options::TunerPreset;
+use crate::visuals::spectrogram::processor::{
+    SpectrogramColumn, SpectrogramConfig, SpectrogramProcessor,
+};
+
+// Fine enough to part the low B string's semitones, with about 47 frames a
+// second at 48 kHz.
+const FFT_SIZE: usize = 8192;
+const HOP_SIZE: usize = 1024;
+
+crate::macros::default_struct! {
+    #[derive(Debug, Clone, Copy)]
+    pub struct TunerConfig {
+        pub sample_rate: f32 = DEFAULT_SAMPLE_RATE,
+        pub preset: TunerPreset = TunerPreset::default(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TunerReading {
+    pub hz: f32,
+    pub target: MusicalNote,
+    pub target_hz: f32,
+    pub cents: f32,
+}
+
+impl TunerReading {
+    pub fn new(hz: f32, preset: TunerPreset, tuning: Tuning) -> Option<Self> {
+        let target = match preset.strings() {
+            [] => MusicalNote::from_frequency(hz, tuning)?,
+            strings => strings
+                .iter()
+                .map(|&midi| MusicalNote::from_midi(midi))
+                .min_by(|a, b| {
+                    let away = |note: &MusicalNote| (hz / note.to_frequency(tuning)).log2().abs();
+                    away(a).total_cmp(&away(b))
+                })?,
+        };
+        let target_hz = target.to_frequency(tuning);
+        (hz > 0.0 && target_hz > 0.0).then(|| Self {
+            hz,
+            target,
+            target_hz,
+            cents: 1200.0 * (hz / target_hz).log2(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TunerSnapshot {
+    /// `None` while nothing pitched is playing.
+    pub hz: Option<f32>,
+}
+
+pub struct TunerProcessor {
+    config: TunerConfig,
+    spectrogram: SpectrogramProcessor,
+    tracker: PitchTracker,
+}
+
+fn analysis_config(sample_rate: f32) -> SpectrogramConfig {
+    SpectrogramConfig {
+        sample_rate,
+        fft_size: FFT_SIZE,
+        hop_size: HOP_SIZE,
+        history_length: 1,
+        use_reassignment: true,
+        ..Default::default()
+    }
+}
+
+impl TunerProcessor {
+    pub fn new(config: TunerConfig) -> Self {
+        Self {
+            config,
+            spectrogram: SpectrogramProcessor::new(analysis_config(config.sample_rate)),
+            tracker: PitchTracker::default(),
+        }
+    }
+
+    pub fn config(&self) -> TunerConfig {
+        self.config
+    }
+
+    pub fn update_config(&mut self, config: TunerConfig) {
+        if config.sample_rate != self.config.sample_rate {
+            self.spectrogram
+                .update_config(analysis_config(config.sample_rate));
+            self.tracker.reset();
+        }
+        self.config = config;
+    }
+
+    pub fn process_block(&mut self, block: &AudioBlock<'_>) -> Option<TunerSnapshot> {
+        let update = self.spectrogram.process_block(block)?;
+        self.config.sample_rate = update.sample_rate;
+        if update.reset {
+            self.tracker.reset();
+        }
+        let mut pitch = None;
+        for column in &update.new_columns {
+            if let SpectrogramColumn::Reassigned(points) = column {
+                let peaks = points
+                    .iter()
+                    .map(|p| (p.freq_hz, db_to_power(p.magnitude_db)));
+                pitch = self.tracker.push(peaks);
+            }
+        }
+        if update.new_columns.is_empty() {
+            return None;
+        }
+        Some(TunerSnapshot {
+            hz: pitch.map(|pitch| pitch.hz),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::TAU;
+
+    #[test]
+    fn reads_a_sharp_a_string_against_the_preset() {
+        const RATE: f32 = 48_000.0;
+        // A2 a third of a semitone sharp, as a bright sawtooth-like tone.
+        let hz = 110.0 * (33.0_f32 / 1200.0).exp2();
+        let samples: Vec<f32> = (0..RATE as usize)
+            .map(|n| {
+                let t = n as f32 / RATE;
+                (1..=8)
+                    .map(|k| (TAU * hz * k as f32 * t).sin() * 0.3 / k as f32)
+                    .sum()
+            })
+            .collect();
+
+        let mut processor = TunerProcessor::new(TunerConfig {
+            sample_rate: RATE,
+            preset: TunerPreset::Guitar,
+        });
+        let pitch = samples
+            .chunks(4_800)
+            .filter_map(|chunk| processor.process_block(&AudioBlock::new(chunk, 1, RATE)))
+            .last()
+            .and_then(|snapshot| snapshot.hz)
+            .unwrap();
+        let tuning = Tuning::default();
+        let reading = TunerReading::new(pitch, TunerPreset::Guitar, tuning).unwrap();
+        assert_eq!(reading.target, MusicalNote::from_midi(45));
+        assert!((reading.cents - 33.0).abs() < 2.0, "{}", reading.cents);
+
+        // Halfway between strings, the closer one wins; chromatic takes any note.
+        let between = TunerReading::new(88.0, TunerPreset::Guitar, tuning).unwrap();
+        assert_eq!(between.target.to_string(), "E2");
+        let chromatic = TunerReading::new(88.0, TunerPreset::Chromatic, tuning).unwrap();
+        assert_eq!(chromatic.target.to_string(), "F2");
+    }
+}
@@ -0,0 +1,194 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+use super::processor::TunerReading;
+use crate::util::audio::NumberFormat;
+use crate::util::audio::musical::MusicalNote;
+use crate::util::color::with_alpha;
+use crate::visuals::palettes;
+use crate::visuals::render::common::{fill_rect, make_text, measure_text};
+use iced::advanced::text::Renderer as _;
+use iced::alignment::{Horizontal, Vertical};
+use iced::{Color, Point, Rectangle, Size};
+
+pub const TUNER_PALETTE_SIZE: usize = palettes::tuner::COLORS.len();
+
+const PAL_BACKGROUND: usize = 0;
+const PAL_TEXT: usize = 1;
+const PAL_SCALE: usize = 2;
+const PAL_IN_TUNE: usize = 3;
+const PAL_OFF: usize = 4;
+const PAL_STROBE: usize = 5;
+
+/// The needle's scale spans this many cents either side.
+pub const SCALE_CENTS: f32 = 50.0;
+const PADDING: f32 = 12.0;
+const ROW_GAP: f32 = 8.0;
+const STRING_ROW_HEIGHT: f32 = 22.0;
+const TICK_STEP_CENTS: usize = 10;
+const NEEDLE_WIDTH: f32 = 3.0;
+const STROBE_PERIOD: f32 = 24.0;
+const LABEL_SIZE: f32 = 11.0;
+const DETAIL_SIZE: f32 = 13.0;
+const IDLE_ALPHA: f32 = 0.25;
+
+#[derive(Debug, Clone)]
+pub struct TunerView {
+    pub palette: [Color; TUNER_PALETTE_SIZE],
+    /// `None` once the held reading has gone stale.
+    pub reading: Option<TunerReading>,
+    pub needle_cents: f32,
+    /// Strobe band offset in periods, `0..1`.
+    pub strobe_phase: f32,
+    pub in_tune_cents: f32,
+    pub strings: &'static [i32],
+    pub numbers: NumberFormat,
+}
+
+struct Layout {
+    note: Rectangle,
+    scale: Rectangle,
+    strobe: Rectangle,
+    strings: Rectangle,
+}
+
+impl Layout {
+    fn new(bounds: Rectangle, strings: bool) -> Self {
+        let inner = Rectangle::new(
+            Point::new(bounds.x + PADDING, bounds.y + PADDING),
+            Size::new(
+                (bounds.width - PADDING * 2.0).max(0.0),
+                (bounds.height - PADDING * 2.0).max(0.0),
+            ),
+        );
+        let strings_h = if strings { STRING_ROW_HEIGHT } else { 0.0 };
+        let strobe_h = (inner.height * 0.14).clamp(10.0, 36.0);
+        let scale_h = (inner.height * 0.2).clamp(24.0, 60.0);
+        let note_h = (inner.height - strings_h - strobe_h - scale_h - ROW_GAP * 3.0).max(0.0);
+        let mut y = inner.y;
+        let mut row = |height: f32| {
+            let rect = Rectangle::new(Point::new(inner.x, y), Size::new(inner.width, height));
+            y += height + ROW_GAP;
+            rect
+        };
+        Self {
+            note: row(note_h),
+            scale: row(scale_h),
+            strobe: row(strobe_h),
+            strings: row(strings_h),
+        }
+    }
+}
+
+fn cents_x(scale: Rectangle, cents: f32) -> f32 {
+    let t = (cents.clamp(-SCALE_CENTS, SCALE_CENTS) + SCALE_CENTS) / (SCALE_CENTS * 2.0);
+    scale.x + t * scale.width
+}
+
+fn label(renderer: &mut iced::Renderer, s: &str, px: f32, center: Point, color: Color) {
+    let size = measure_text(s, px);
+    let mut text = make_text(s, px, size);
+    text.align_x = Horizontal::Center.into();
+    text.align_y = Vertical::Center;
+    let clip = Rectangle::new(
+        Point::new(center.x - size.width / 2.0, center.y - size.height / 2.0),
+        size,
+    );
+    renderer.fill_text(text, center, color, clip);
+}
+
+pub fn draw(renderer: &mut iced::Renderer, bounds: Rectangle, view: &TunerView) {
+    let pal = &view.palette;
+    fill_rect(renderer, bounds, pal[PAL_BACKGROUND]);
+    let layout = Layout::new(bounds, !view.strings.is_empty());
+    let status = view.reading.map(|reading| {
+        if reading.cents.abs() <= view.in_tune_cents {
+            pal[PAL_IN_TUNE]
+        } else {
+            pal[PAL_OFF]
+        }
+    });
+
+    // Note name, with cents and frequency underneath.
+    let note = layout.note;
+    let detail_h = measure_text("0", DETAIL_SIZE).height;
+    let name_px = ((note.height - detail_h) * 0.8).clamp(16.0, 120.0);
+    let center = Point::new(note.center_x(), note.y + (note.height - detail_h) / 2.0);
+    match view.reading {
+        Some(reading) => {
+            let (name, color) = (reading.target.to_string(), status.unwrap_or(pal[PAL_TEXT]));
+            label(renderer, &name, name_px, center, color);
+            let detail = format!("{:+.1} cents  {:.2} Hz", reading.cents, reading.hz);
+            let detail = view.numbers.localize(detail);
+            let at = Point::new(note.center_x(), note.y + note.height - detail_h / 2.0);
+            label(renderer, &detail, DETAIL_SIZE, at, pal[PAL_TEXT]);
+        }
+        None => label(renderer, "--", name_px, center, pal[PAL_SCALE]),
+    }
+
+    // Cents scale, with the in-tune window shaded behind the needle.
+    let scale = layout.scale;
+    let tick_area = scale.height - LABEL_SIZE - 2.0;
+    let window_l = cents_x(scale, -view.in_tune_cents);
+    let window = Rectangle::new(
+        Point::new(window_l, scale.y),
+        Size::new(cents_x(scale, view.in_tune_cents) - window_l, tick_area),
+    );
+    fill_rect(renderer, window, with_alpha(pal[PAL_IN_TUNE], IDLE_ALPHA));
+    for cents in (-(SCALE_CENTS as i32)..=SCALE_CENTS as i32).step_by(TICK_STEP_CENTS) {
+        let major = cents == 0 || cents.unsigned_abs() as f32 == SCALE_CENTS;
+        let height = if major { tick_area } else { tick_area * 0.5 };
+        let x = cents_x(scale, cents as f32);
+        let tick = Rectangle::new(
+            Point::new(x - 0.5, scale.y + (tick_area - height) / 2.0),
+            Size::new(1.0, height),
+        );
+        fill_rect(renderer, tick, pal[PAL_SCALE]);
+        if major {
+            let at = Point::new(x, scale.y + scale.height - LABEL_SIZE / 2.0);
+            let text = format!("{cents:+}");
+            label(renderer, &text, LABEL_SIZE, at, pal[PAL_SCALE]);
+        }
+    }
+    if let Some(color) = status {
+        let x = cents_x(scale, view.needle_cents);
+        let needle = Rectangle::new(
+            Point::new(x - NEEDLE_WIDTH / 2.0, scale.y),
+            Size::new(NEEDLE_WIDTH, tick_area),
+        );
+        fill_rect(renderer, needle, color);
+    }
+
+    // Strobe band: drifts right when sharp, left when flat, stands when in tune.
+    let strobe = layout.strobe;
+    let stripe_color = match status {
+        Some(_) => pal[PAL_STROBE],
+        None => with_alpha(pal[PAL_STROBE], IDLE_ALPHA),
+    };
+    let mut x = strobe.x + (view.strobe_phase.rem_euclid(1.0) - 1.0) * STROBE_PERIOD;
+    while x < strobe.x + strobe.width {
+        let left = x.max(strobe.x);
+        let right = (x + STROBE_PERIOD / 2.0).min(strobe.x + strobe.width);
+        if right > left {
+            let stripe = Rectangle::new(
+                Point::new(left, strobe.y),
+                Size::new(right - left, strobe.height),
+            );
+            fill_rect(renderer, stripe, stripe_color);
+        }
+        x += STROBE_PERIOD;
+    }
+
+    // Open strings of the preset, the one being tuned highlighted.
+    let strings = layout.strings;
+    let slot = strings.width / view.strings.len().max(1) as f32;
+    for (index, &midi) in view.strings.iter().enumerate() {
+        let string = MusicalNote::from_midi(midi);
+        let color = match (view.reading, status) {
+            (Some(reading), Some(color)) if reading.target == string => color,
+            _ => pal[PAL_SCALE],
+        };
+        let at = Point::new(strings.x + slot * (index as f32 + 0.5), strings.center_y());
+        label(renderer, &string.to_string(), DETAIL_SIZE, at, color);
+    }
+}
@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 Maika Namuo
+
+use super::processor::{TunerReading, TunerSnapshot};
+use super::render::{self, SCALE_CENTS, TUNER_PALETTE_SIZE, TunerView};
+use crate::persistence::settings::TunerSettings;
+use crate::util::audio::NumberFormat;
+use crate::util::audio::musical::Tuning;
+use crate::visuals::palettes;
+use crate::visuals::render::common::faded;
+use iced::Color;
+use std::time::{Duration, Instant};
+
+// The last reading stays up this long after the note stops.
+const HOLD: Duration = Duration::from_millis(1500);
+const NEEDLE_SECONDS: f32 = 0.08;
+// Longer gaps between snapshots don't move the strobe any further.
+const MAX_STEP_SECONDS: f32 = 0.1;
+
+#[derive(Debug, Clone)]
+pub(in crate::visuals) struct TunerState {
+    settings: TunerSettings,
+    tuning: Tuning,
+    numbers: NumberFormat,
+    opacity: f32,
+    pub(in crate::visuals) palette: [Color; TUNER_PALETTE_SIZE],
+    held: Option<(TunerReading, Instant)>,
+    needle_cents: f32,
+    strobe_phase: f32,
+    last_snapshot: Option<Instant>,
+}
+
+impl TunerState {
+    pub fn new() -> Self {
+        Self {
+            settings: TunerSettings::default(),
+            tuning: Tuning::default(),
+            numbers: NumberFormat::default(),
+            opacity: 1.0,
+            palette: palettes::tuner::COLORS,
+            held: None,
+            needle_cents: 0.0,
+            strobe_phase: 0.0,
+            last_snapshot: None,
+        }
+    }
+
+    pub fn apply_snapshot(&mut self, snapshot: TunerSnapshot) {
+        let now = Instant::now();
+        let dt = self
+            .last_snapshot
+            .map_or(0.0, |last| now.duration_since(last).as_secs_f32())
+            .min(MAX_STEP_SECONDS);
+        self.last_snapshot = Some(now);
+        let reading = snapshot
+            .hz
+            .and_then(|hz| TunerReading::new(hz, self.settings.preset, self.tuning));
+        let Some(reading) = reading else {
+            return;
+        };
+        let cents = reading.cents.clamp(-SCALE_CENTS, SCALE_CENTS);
+        match self.held {
+            // A new note jumps the needle rather than sweeping across.
+            Some((held, _)) if held.target == reading.target => {
+                self.needle_cents +=
+                    (cents - self.needle_cents) * (1.0 - (-dt / NEEDLE_SECONDS).exp());
+            }
+            _ => self.needle_cents = cents,
+        }
+        // Like a strobe disc, the band moves at the beat between the note and its target.
+        self.strobe_phase =
+            (self.strobe_phase + (reading.hz - reading.target_hz) * dt).rem_euclid(1.0);
+        self.held = Some((reading, now));
+    }
+
+    pub fn update_view_settings(&mut self, settings: &TunerSettings) {
+        self.settings = settings.clone();
+        let strings = settings.preset.strings();
+        if let Some((reading, _)) = self.held
+            && !strings.is_empty()
+            && !strings.contains(&reading.target.midi_number)
+        {
+            self.held = None;
+        }
+    }
+
+    pub fn set_tuning(&mut self, tuning: Tuning) {
+        self.tuning = tuning;
+        self.held = None;
+    }
+
+    pub fn set_number_format(&mut self, numbers: NumberFormat) {
+        self.numbers = numbers;
+    }
+
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity;
+    }
+
+    pub fn export_settings(&self) -> TunerSettings {
+        self.settings.clone()
+    }
+
+    pub fn set_palette(&mut self, palette: &[Color; TUNER_PALETTE_SIZE]) {
+        self.palette = *palette;
+    }
+
+    fn view(&self) -> TunerView {
+        let reading = self
+            .held
+            .filter(|(_, at)| at.elapsed() < HOLD)
+            .map(|(reading, _)| reading);
+        TunerView {
+            palette: self.palette.map(|color| faded(color, self.opacity)),
+            reading,
+            needle_cents: self.needle_cents,
+            strobe_phase: self.strobe_phase,
+            in_tune_cents: self.settings.in_tune_cents,
+            strings: self.settings.preset.strings(),
+            numbers: self.numbers,
+        }
+    }
+}
+
+crate::visuals::visualization_widget!(Tuner, TunerState, |this, renderer, _theme, bounds| {
+    let view = this.state.borrow().view();
+    render::draw(renderer, bounds, &view);
+});
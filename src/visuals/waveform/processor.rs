@@ -1,9 +1,11 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // Copyright (C) 2026 Maika Namuo
 
+use crate::dsp::clicks::{ClickDetector, DEFAULT_CLICK_THRESHOLD};
 use crate::dsp::{AudioBlock, Biquad, ThreeBand, WindowedMeans};
 use crate::util::audio::{
-    BAND_SPLITS_HZ, Channel, DB_FLOOR, DEFAULT_SAMPLE_RATE, power_to_db, sanitize_sample_rate,
+    BAND_SPLITS_HZ, Channel, DB_FLOOR, DEFAULT_SAMPLE_RATE, MixdownGains, mix_frame, power_to_db,
+    sanitize_sample_rate,
 };
 
 pub const MIN_SCROLL_SPEED: f32 = 10.0;
@@ -35,6 +37,8 @@ crate::macros::default_struct! {
         pub max_columns: usize = MAX_COLUMN_CAPACITY,
         pub analyze_bands: bool = true,
         pub track_history: bool = false,
+        pub detect_clicks: bool = false,
+        pub click_threshold: f32 = DEFAULT_CLICK_THRESHOLD,
     }
 }
 
@@ -57,6 +61,63 @@ crate::macros::default_struct! {
         pub color_bands: [f32; NUM_BANDS] = [0.0; NUM_BANDS],
         pub rms_fast_db: [f32; NUM_BANDS] = [DB_FLOOR; NUM_BANDS],
         pub rms_slow_db: [f32; NUM_BANDS] = [DB_FLOOR; NUM_BANDS],
+        pub rms: f32 = 0.0,
+        // RMS frequency of the column in Hz; 0 when silent.
+        pub brightness_hz: f32 = 0.0,
+        pub click: bool = false,
+    }
+}
+
+// Energy of the signal and of its first difference over one column. For a
+// pure tone their ratio is 2·sin(π·f/fs), so it yields the RMS frequency, a
+// cheap stand-in for the spectral centroid.
+#[derive(Debug, Clone, Copy, Default)]
+struct ColumnEnergy {
+    power: f64,
+    samples: u32,
+    slope: f64,
+    steps: u32,
+    last: Option<f32>,
+}
+
+impl ColumnEnergy {
+    fn push(&mut self, sample: Option<f32>) {
+        let Some(sample) = sample else {
+            self.last = None;
+            return;
+        };
+        self.power += f64::from(sample) * f64::from(sample);
+        self.samples += 1;
+        if let Some(last) = self.last {
+            let step = f64::from(sample - last);
+            self.slope += step * step;
+            self.steps += 1;
+        }
+        self.last = Some(sample);
+    }
+
+    // Starts a new column; the last sample carries over so steps span the boundary.
+    fn restart(&mut self) {
+        *self = Self {
+            last: self.last,
+            ..Self::default()
+        };
+    }
+
+    fn rms(&self) -> f32 {
+        if self.samples == 0 {
+            return 0.0;
+        }
+        (self.power / f64::from(self.samples)).sqrt() as f32
+    }
+
+    fn brightness_hz(&self, sample_rate: f32) -> f32 {
+        if self.steps == 0 || self.rms() < WAVEFORM_SILENCE_AMPLITUDE {
+            return 0.0;
+        }
+        let ratio = (self.slope / f64::from(self.steps)).sqrt() / f64::from(self.rms());
+        let normalized = (0.5 * ratio).clamp(0.0, 1.0).asin() / std::f64::consts::PI;
+        (normalized * f64::from(sample_rate)) as f32
     }
 }
 
@@ -73,6 +134,13 @@ pub struct WaveformUpdate<'a> {
     pub reset: bool,
     pub columns: &'a [WaveFrame],
     pub preview: WaveformPreview,
+    /// The interleaved block the columns came from, kept for clip export.
+    pub samples: &'a [f32],
+    pub channels: usize,
+    pub sample_rate: f32,
+    pub scroll_speed: f32,
+    /// Clicks that started in this block.
+    pub clicks: usize,
 }
 
 fn window_len(samples_at_reference_rate: usize, sample_rate: f32) -> usize {
@@ -128,11 +196,11 @@ impl BandTracker {
     }
 }
 
-fn derived_frame(frame: &[f32]) -> [f32; DERIVED_CHANNELS] {
+fn derived_frame(frame: &[f32], mixdown: &[f32]) -> [f32; DERIVED_CHANNELS] {
     let left = frame[0];
     let right = frame.get(1).copied().unwrap_or(left);
     let side = if frame.len() > 1 { (left - right) * 0.5 } else { 0.0 };
-    [left, right, frame.iter().sum::<f32>() / frame.len() as f32, side]
+    [left, right, mix_frame(frame, mixdown), side]
 }
 
 fn process_bands(
@@ -151,9 +219,15 @@ pub struct WaveformProcessor {
     trackers: Option<[BandTracker; DERIVED_CHANNELS]>,
     column_phase: f64,
     current: [Option<(f32, f32, Option<f32>)>; DERIVED_CHANNELS],
+    energy: [ColumnEnergy; DERIVED_CHANNELS],
     last_sample: [Option<f32>; DERIVED_CHANNELS],
     pending_columns: Vec<WaveFrame>,
     reset_pending: bool,
+    // Left and right of the capture, when click detection is on.
+    click_detectors: Option<[ClickDetector; 2]>,
+    clicked: [bool; DERIVED_CHANNELS],
+    clicks: usize,
+    mixdown: MixdownGains,
 }
 
 impl WaveformProcessor {
@@ -165,9 +239,14 @@ impl WaveformProcessor {
             trackers: Self::trackers(config),
             column_phase: 0.0,
             current: [None; DERIVED_CHANNELS],
+            energy: [ColumnEnergy::default(); DERIVED_CHANNELS],
             last_sample: [None; DERIVED_CHANNELS],
             pending_columns: Vec::new(),
             reset_pending: true,
+            click_detectors: Self::click_detectors(config),
+            clicked: [false; DERIVED_CHANNELS],
+            clicks: 0,
+            mixdown: MixdownGains::default(),
         }
     }
 
@@ -175,15 +254,28 @@ impl WaveformProcessor {
         self.config
     }
 
+    /// Per-channel weights the mid row mixes with.
+    pub fn set_mixdown(&mut self, weights: &[f32], exclude_lfe: bool) {
+        self.mixdown.set_weights(weights, exclude_lfe);
+    }
+
     fn rebuild(&mut self) {
         self.column_phase = 0.0;
         self.last_sample = [None; DERIVED_CHANNELS];
+        self.energy = [ColumnEnergy::default(); DERIVED_CHANNELS];
         self.pending_columns.clear();
         self.reset_column();
         self.reset_trackers();
+        self.click_detectors = Self::click_detectors(self.config);
         self.reset_pending = true;
     }
 
+    fn click_detectors(config: WaveformConfig) -> Option<[ClickDetector; 2]> {
+        config.detect_clicks.then(|| {
+            std::array::from_fn(|_| ClickDetector::new(config.sample_rate, config.click_threshold))
+        })
+    }
+
     fn trackers(config: WaveformConfig) -> Option<[BandTracker; DERIVED_CHANNELS]> {
         config
             .analyze_bands
@@ -206,6 +298,8 @@ impl WaveformProcessor {
 
     fn reset_column(&mut self) {
         self.current = [None; DERIVED_CHANNELS];
+        self.clicked = [false; DERIVED_CHANNELS];
+        self.energy.iter_mut().for_each(ColumnEnergy::restart);
     }
 
     fn column_for(&self, channel: usize) -> WaveColumn {
@@ -216,9 +310,13 @@ impl WaveformProcessor {
             }
             (min, max)
         });
+        let energy = &self.energy[channel];
         let mut column = WaveColumn {
             min,
             max,
+            rms: energy.rms(),
+            brightness_hz: energy.brightness_hz(self.config.sample_rate),
+            click: self.clicked[channel],
             ..WaveColumn::default()
         };
         if let Some(trackers) = &self.trackers {
@@ -259,14 +357,27 @@ impl WaveformProcessor {
     fn ingest_samples(&mut self, samples: &[f32], channels: usize) {
         let step = (f64::from(self.config.scroll_speed) / f64::from(self.config.sample_rate))
             .clamp(0.0, 1.0);
+        let mut mixdown = std::mem::take(&mut self.mixdown);
+        let gains = mixdown.for_channels(channels);
         for frame in samples.chunks_exact(channels) {
-            let derived = derived_frame(frame);
+            let derived = derived_frame(frame, gains);
             let finite = derived.map(f32::is_finite);
             if let Some(trackers) = &mut self.trackers {
                 process_bands(trackers, derived, finite);
             }
+            if let Some(detectors) = &mut self.click_detectors {
+                let right = frame.get(1).copied().unwrap_or(frame[0]);
+                let [left, right] = [detectors[0].push(frame[0]), detectors[1].push(right)];
+                // Mid and side carry a click on either side.
+                let marks = [left, right, left || right, left || right];
+                for (clicked, mark) in self.clicked.iter_mut().zip(marks) {
+                    *clicked |= mark;
+                }
+                self.clicks += usize::from(left || right);
+            }
             self.ingest_derived(derived, finite, step);
         }
+        self.mixdown = mixdown;
     }
 
     fn ingest_derived(
@@ -276,6 +387,7 @@ impl WaveformProcessor {
         step: f64,
     ) {
         for channel in 0..DERIVED_CHANNELS {
+            self.energy[channel].push(finite[channel].then_some(derived[channel]));
             if finite[channel] {
                 let sample = derived[channel];
                 self.current[channel] = Some(match self.current[channel] {
@@ -311,12 +423,16 @@ impl WaveformProcessor {
         }
     }
 
-    pub fn process_block(&mut self, block: &AudioBlock<'_>) -> Option<WaveformUpdate<'_>> {
+    pub fn process_block<'a>(
+        &'a mut self,
+        block: &AudioBlock<'a>,
+    ) -> Option<WaveformUpdate<'a>> {
         if block.is_empty() {
             return None;
         }
 
         self.pending_columns.clear();
+        self.clicks = 0;
 
         let (channels, sample_rate) = (block.channels.max(1), block.sample_rate);
         if channels != self.source_channels || self.config.sample_rate != sample_rate {
@@ -340,6 +456,11 @@ impl WaveformProcessor {
             reset,
             columns: &self.pending_columns,
             preview,
+            samples: block.samples,
+            channels,
+            sample_rate: self.config.sample_rate,
+            scroll_speed: self.config.scroll_speed,
+            clicks: self.clicks,
         })
     }
 
@@ -348,6 +469,8 @@ impl WaveformProcessor {
         let rebuild = self.config.sample_rate != normalized.sample_rate;
         let reset_analysis = self.config.analyze_bands != normalized.analyze_bands
             || self.config.track_history != normalized.track_history;
+        let reset_clicks = self.config.detect_clicks != normalized.detect_clicks
+            || self.config.click_threshold != normalized.click_threshold;
         let resize_pending = self.config.max_columns != normalized.max_columns;
         self.config = normalized;
         if resize_pending {
@@ -355,8 +478,13 @@ impl WaveformProcessor {
         }
         if rebuild {
             self.rebuild();
-        } else if reset_analysis {
-            self.reset_trackers();
+        } else {
+            if reset_analysis {
+                self.reset_trackers();
+            }
+            if reset_clicks {
+                self.click_detectors = Self::click_detectors(self.config);
+            }
         }
     }
 }
@@ -364,7 +492,7 @@ impl WaveformProcessor {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::f32::consts::PI;
+    use std::f32::consts::{FRAC_1_SQRT_2, PI};
 
     const RATE: f32 = 48_000.0;
 
@@ -383,7 +511,7 @@ mod tests {
 
     fn process<'a>(
         processor: &'a mut WaveformProcessor,
-        samples: &[f32],
+        samples: &'a [f32],
         channels: usize,
     ) -> WaveformUpdate<'a> {
         processor
@@ -426,6 +554,31 @@ mod tests {
         assert_eq!((column(&update, 2, 0).min, column(&update, 2, 0).max), (0.25, 0.75));
     }
 
+    #[test]
+    fn clicks_mark_the_columns_of_the_side_they_hit() {
+        let mut processor = WaveformProcessor::new(WaveformConfig {
+            detect_clicks: true,
+            ..config(300.0, MAX_COLUMN_CAPACITY)
+        });
+        let tone = |n: usize| 0.5 * (2.0 * PI * 1_000.0 * n as f32 / RATE).sin();
+        // The left channel skips 20 samples half a second in.
+        let samples: Vec<f32> = (0..48_000)
+            .flat_map(|n| [tone(if n < 24_080 { n } else { n + 20 }), tone(n)])
+            .collect();
+        let update = process(&mut processor, &samples, 2);
+
+        assert_eq!(update.clicks, 1);
+        let marked = |channel| {
+            (0..update.columns.len())
+                .filter(|&col| column(&update, channel, col).click)
+                .collect::<Vec<_>>()
+        };
+        // 160 frames per column at 300 px/s.
+        assert_eq!(marked(0), [150]);
+        assert!(marked(1).is_empty());
+        assert_eq!(marked(2), [150]);
+    }
+
     #[test]
     fn previous_sample_continuity_catches_column_boundary_steps() {
         let mut processor = WaveformProcessor::new(config(RATE / 2.0, 8));
@@ -492,6 +645,30 @@ mod tests {
         assert!(high[2] > high[0] && high[2] > high[1], "high bands: {high:?}");
     }
 
+    #[test]
+    fn columns_report_rms_and_brightness() {
+        fn latest_for(freq: f32) -> WaveColumn {
+            let mut processor = WaveformProcessor::new(config(50.0, 512));
+            let samples: Vec<f32> = (0..RATE as usize)
+                .map(|n| (2.0 * PI * freq * n as f32 / RATE).sin())
+                .collect();
+            latest(&process(&mut processor, &samples, 1), 0)
+        }
+
+        for freq in [200.0, 1_000.0, 6_000.0] {
+            let column = latest_for(freq);
+            assert!((column.rms - FRAC_1_SQRT_2).abs() < 0.01, "rms {}", column.rms);
+            assert!(
+                (column.brightness_hz / freq - 1.0).abs() < 0.02,
+                "{freq} Hz read as {}",
+                column.brightness_hz
+            );
+        }
+        let mut processor = WaveformProcessor::new(config(50.0, 512));
+        let silent = latest(&process(&mut processor, &[0.0; 4_096], 1), 0);
+        assert_eq!((silent.rms, silent.brightness_hz), (0.0, 0.0));
+    }
+
     #[test]
     fn fast_rms_reacts_before_slow_rms() {
         let mut cfg = config(100.0, 512);
@@ -6,8 +6,8 @@ use iced::advanced::graphics::Viewport;
 use std::{collections::VecDeque, sync::Arc};
 
 use crate::util::{
-    audio::{DB_FLOOR, power_to_db, sanitize_negative_db},
-    color::{rgba_with_alpha, sample_rgba_gradient},
+    audio::{DB_FLOOR, DbRange, power_to_db},
+    color::{ColorBlend, rgba_with_alpha, sample_rgba_gradient},
 };
 use crate::visuals::options::{WaveformColorMode, WaveformHistoryMode};
 use crate::visuals::render::common::sdf_primitive;
@@ -15,14 +15,18 @@ use crate::visuals::render::common::{
     ChannelLayout, ClipTransform, GeometryScratch, extend_filled_line, quad_vertices,
 };
 use crate::visuals::waveform::processor::{
-    DEFAULT_BAND_DB_FLOOR, NUM_BANDS, WAVEFORM_SILENCE_AMPLITUDE, WaveColumn, WaveFrame,
-    WaveformPreview,
+    NUM_BANDS, WAVEFORM_SILENCE_AMPLITUDE, WaveColumn, WaveFrame, WaveformPreview,
 };
 
 const BAND_LINE_WIDTH: f32 = 1.5;
 const BAND_FILL_ALPHA: f32 = 0.15;
+// Opacity of the peak band when the RMS band sits inside it.
+const PEAK_LAYER_ALPHA: f32 = 0.45;
 const MIN_COLUMN_HEIGHT_PIXELS: f32 = 1.0;
 const LOUDNESS_QUIET_DB: f32 = -36.0;
+// Brightness gradient spans these RMS frequencies on a log scale.
+const BRIGHTNESS_DARK_HZ: f32 = 100.0;
+const BRIGHTNESS_BRIGHT_HZ: f32 = 8_000.0;
 
 #[derive(Debug)]
 pub struct WaveformParams {
@@ -35,13 +39,42 @@ pub struct WaveformParams {
     pub preview: WaveformPreview,
     pub color_mode: WaveformColorMode,
     pub history_mode: WaveformHistoryMode,
-    pub band_db_floor: f32,
+    /// Draws each column as a light peak band around a solid RMS band.
+    pub rms_layer: bool,
+    /// Range of the band history lines, already raised to the visual's floor.
+    pub band_levels: DbRange,
     pub palette: [[f32; 4]; NUM_BANDS],
+    pub palette_blend: ColorBlend,
     pub fill_alpha: f32,
     pub vertical_padding: f32,
     pub channel_gap: f32,
     pub amplitude_scale: f32,
     pub key: u64,
+    pub opacity: f32,
+    /// Beat-pulse brightening, `0..=1`.
+    pub pulse: f32,
+}
+
+// Horizontal placement shared by the sample columns and the history overlay.
+struct ColumnGeometry {
+    start: usize,
+    columns: usize,
+    width: f32,
+    preview_width: f32,
+    scroll_offset: f32,
+    right_edge: f32,
+}
+
+impl ColumnGeometry {
+    fn x(&self, i: usize) -> f32 {
+        let dist_steps = (self.columns - 1 - i) as f32;
+        (self.right_edge
+            - self.preview_width
+            - dist_steps * self.width
+            - self.scroll_offset
+            - self.width)
+            .floor()
+    }
 }
 
 impl WaveformParams {
@@ -49,22 +82,138 @@ impl WaveformParams {
         self.channels > 0 && self.preview.progress > 0.0 && self.preview.columns.is_some()
     }
 
+    fn layout(&self) -> ChannelLayout {
+        ChannelLayout::new(
+            self.bounds,
+            self.channels.max(1),
+            self.vertical_padding,
+            self.channel_gap,
+            self.amplitude_scale,
+        )
+    }
+
+    fn geometry(&self) -> Option<ColumnGeometry> {
+        let columns = self.columns.min(self.data.len());
+        let preview_active = self.preview_active();
+        if columns == 0 && !preview_active {
+            return None;
+        }
+        let width = self.column_width.max(0.5);
+        Some(ColumnGeometry {
+            start: self.data.len() - columns,
+            columns,
+            width,
+            preview_width: if preview_active { width } else { 0.0 },
+            scroll_offset: if preview_active {
+                self.preview.progress * width
+            } else {
+                0.0
+            },
+            right_edge: self.bounds.x + self.bounds.width,
+        })
+    }
+
+    /// Calls `emit` with a lane-high strip for every visible column holding a click.
+    pub(in crate::visuals) fn for_each_click(&self, mut emit: impl FnMut(Rectangle)) {
+        let Some(geometry) = self.geometry() else {
+            return;
+        };
+        let layout = self.layout();
+        for ch in 0..self.channels.max(1) {
+            let top = layout.center_y(ch) - layout.channel_height * 0.5;
+            let lane = self.lanes[ch];
+            let frames = self
+                .data
+                .range(geometry.start..geometry.start + geometry.columns);
+            for (i, _) in frames.enumerate().filter(|(_, frame)| frame[lane].click) {
+                emit(Rectangle::new(
+                    (geometry.x(i), top).into(),
+                    (geometry.width.max(1.0), layout.channel_height).into(),
+                ));
+            }
+        }
+    }
+
+    /// Calls `emit` with the rectangle and fill color of every visible sample
+    /// column, preview included; with the RMS layer on, each column's peak
+    /// band comes first and its RMS band follows.
+    pub(in crate::visuals) fn for_each_column(&self, mut emit: impl FnMut(Rectangle, [f32; 4])) {
+        let Some(geometry) = self.geometry() else {
+            return;
+        };
+        let layout = self.layout();
+        let static_color = (self.color_mode == WaveformColorMode::Static)
+            .then(|| with_fill_alpha(self.palette[0], self.fill_alpha));
+        let mut push = |center_y, x0: f32, x1: f32, column: WaveColumn| {
+            let Some((y0, y1)) =
+                sample_y_span(center_y, layout.amplitude_scale, column.min, column.max)
+            else {
+                return;
+            };
+            let color = static_color
+                .unwrap_or_else(|| with_fill_alpha(self.column_color(column), self.fill_alpha));
+            let rect = |y0: f32, y1: f32| Rectangle::new((x0, y0).into(), (x1 - x0, y1 - y0).into());
+            if !self.rms_layer {
+                emit(rect(y0, y1), color);
+                return;
+            }
+            emit(rect(y0, y1), with_fill_alpha(color, PEAK_LAYER_ALPHA));
+            // The RMS band stays within the peaks of a lopsided column.
+            if let Some((r0, r1)) =
+                sample_y_span(center_y, layout.amplitude_scale, -column.rms, column.rms)
+                && r1.min(y1) > r0.max(y0)
+            {
+                emit(rect(r0.max(y0), r1.min(y1)), color);
+            }
+        };
+
+        for ch in 0..self.channels.max(1) {
+            let center_y = layout.center_y(ch);
+            let lane = self.lanes[ch];
+            let frames = self
+                .data
+                .range(geometry.start..geometry.start + geometry.columns);
+            for (i, frame) in frames.enumerate() {
+                let x = geometry.x(i);
+                push(center_y, x, x + geometry.width, frame[lane]);
+            }
+            if let Some(preview) = self
+                .preview_active()
+                .then_some(self.preview.columns)
+                .flatten()
+            {
+                let start_x =
+                    (geometry.right_edge - geometry.preview_width - geometry.scroll_offset).floor();
+                push(center_y, start_x, geometry.right_edge, preview[lane]);
+            }
+        }
+    }
+
     fn column_color(&self, column: WaveColumn) -> [f32; 4] {
         match self.color_mode {
             WaveformColorMode::Frequency => self.band_mix_color(column.color_bands),
-            WaveformColorMode::Loudness => {
-                let peak = column.min.abs().max(column.max.abs());
-                let db = power_to_db(peak * peak, DB_FLOOR);
-                sample_rgba_gradient(&self.palette, if db.is_finite() {
-                    (db - LOUDNESS_QUIET_DB) / -LOUDNESS_QUIET_DB
-                } else {
-                    0.0
-                })
+            WaveformColorMode::Loudness => self.level_color(column.min.abs().max(column.max.abs())),
+            WaveformColorMode::Rms => self.level_color(column.rms),
+            WaveformColorMode::Brightness => {
+                let hz = column.brightness_hz.max(BRIGHTNESS_DARK_HZ);
+                let t = (hz / BRIGHTNESS_DARK_HZ).ln()
+                    / (BRIGHTNESS_BRIGHT_HZ / BRIGHTNESS_DARK_HZ).ln();
+                sample_rgba_gradient(&self.palette, t, self.palette_blend)
             }
             WaveformColorMode::Static => self.palette[0],
         }
     }
 
+    fn level_color(&self, amplitude: f32) -> [f32; 4] {
+        let db = power_to_db(amplitude * amplitude, DB_FLOOR);
+        let t = if db.is_finite() {
+            (db - LOUDNESS_QUIET_DB) / -LOUDNESS_QUIET_DB
+        } else {
+            0.0
+        };
+        sample_rgba_gradient(&self.palette, t, self.palette_blend)
+    }
+
     fn band_mix_color(&self, bands: [f32; NUM_BANDS]) -> [f32; 4] {
         let mut out = [0.0; 4];
         let mut total = 0.0;
@@ -118,112 +267,65 @@ fn with_fill_alpha(color: [f32; 4], alpha: f32) -> [f32; 4] {
 impl WaveformPrimitive {
     fn build_vertices(&self, viewport: &Viewport, scratch: &mut GeometryScratch) {
         let params = &self.params;
-        let data = &params.data;
-        let (channels, columns) = (params.channels.max(1), params.columns.min(data.len()));
-        let start = data.len().saturating_sub(columns);
-        let preview_active = params.preview_active();
-
-        if columns == 0 && !preview_active {
+        let Some(geometry) = params.geometry() else {
             return;
-        }
-
+        };
+        let (channels, columns, start) = (params.channels.max(1), geometry.columns, geometry.start);
         let clip = ClipTransform::from_viewport(viewport);
-        let col_width = params.column_width.max(0.5);
-        let preview_width = if preview_active { col_width } else { 0.0 };
-        let right_edge = params.bounds.x + params.bounds.width;
-
-        let layout = ChannelLayout::new(
-            params.bounds,
-            channels,
-            params.vertical_padding,
-            params.channel_gap,
-            params.amplitude_scale,
-        );
+        let layout = params.layout();
         let history: Option<fn(WaveColumn) -> [f32; NUM_BANDS]> = match params.history_mode {
             WaveformHistoryMode::Off => None,
             WaveformHistoryMode::RmsFast => Some(|column| column.rms_fast_db),
             WaveformHistoryMode::RmsSlow => Some(|column| column.rms_slow_db),
         };
         let history_active = history.is_some() && columns >= 2;
-        let floor = sanitize_negative_db(params.band_db_floor, DEFAULT_BAND_DB_FLOOR);
+        let DbRange { floor_db: floor, .. } = params.band_levels;
+        let span = params.band_levels.span();
 
         let vertices = &mut scratch.vertices;
         vertices.reserve(
-            channels * (columns + 1) * 6
+            channels * (columns + 1) * 6 * (1 + usize::from(params.rms_layer))
                 + usize::from(history_active) * channels * NUM_BANDS * columns * 12,
         );
 
-        let static_color = (params.color_mode == WaveformColorMode::Static)
-            .then(|| with_fill_alpha(params.palette[0], params.fill_alpha));
+        params.for_each_column(|rect, color| {
+            let (x1, y1) = (rect.x + rect.width, rect.y + rect.height);
+            vertices.extend(quad_vertices(rect.x, rect.y, x1, y1, clip, color));
+        });
 
-        let preview_columns = preview_active.then_some(params.preview.columns).flatten();
-        let scroll_offset = if preview_columns.is_some() {
-            params.preview.progress * col_width
-        } else {
-            0.0
-        };
-
-        let column_x = |i: usize| -> f32 {
-            let dist_steps = (columns - 1 - i) as f32;
-            (right_edge - preview_width - dist_steps * col_width - scroll_offset - col_width)
-                .floor()
-        };
-        let push_column = |vertices: &mut Vec<_>, center_y, x0, x1, column: WaveColumn| {
-            if let Some((y0, y1)) =
-                sample_y_span(center_y, layout.amplitude_scale, column.min, column.max)
-            {
-                let color = static_color
-                    .unwrap_or_else(|| with_fill_alpha(params.column_color(column), params.fill_alpha));
-                vertices.extend(quad_vertices(x0, y0, x1, y1, clip, color));
-            }
+        let Some(history) = history.filter(|_| history_active) else {
+            return;
         };
-
         for ch in 0..channels {
             let center_y = layout.center_y(ch);
+            let baseline = center_y + layout.channel_height * 0.5;
+            let band_height = layout.channel_height;
+            let pts = &mut scratch.points;
+            for (band, &color) in params.palette.iter().enumerate() {
+                let fill_color = with_fill_alpha(color, BAND_FILL_ALPHA);
 
-            for (i, frame) in data.range(start..start + columns).enumerate() {
-                let column = frame[params.lanes[ch]];
-                let x = column_x(i);
-                push_column(vertices, center_y, x, x + col_width, column);
-            }
-
-            if let Some(preview_columns) = preview_columns {
-                let raw_last_x = right_edge - preview_width - scroll_offset;
-                let start_x = raw_last_x.floor();
-                let end_x = right_edge;
-
-                let ps = preview_columns[params.lanes[ch]];
-                push_column(vertices, center_y, start_x, end_x, ps);
-            }
-
-            if let Some(history) = history.filter(|_| history_active) {
-                let baseline = center_y + layout.channel_height * 0.5;
-                let band_height = layout.channel_height;
-                let pts = &mut scratch.points;
-                for (band, &color) in params.palette.iter().enumerate() {
-                    let fill_color = with_fill_alpha(color, BAND_FILL_ALPHA);
-
-                    pts.clear();
-                    pts.reserve(columns + 1);
-                    pts.extend(data.range(start..start + columns).enumerate().map(|(i, frame)| {
+                pts.clear();
+                pts.reserve(columns + 1);
+                pts.extend(params.data.range(start..start + columns).enumerate().map(
+                    |(i, frame)| {
                         let column = frame[params.lanes[ch]];
                         let db = history(column)[band].max(floor);
-                        let level = ((db - floor) / -floor).clamp(0.0, 1.0);
-                        (column_x(i), baseline - level * band_height)
-                    }));
-                    if let Some(&last) = pts.last() {
-                        pts.push((right_edge, last.1));
-                    }
-                    extend_filled_line(
-                        vertices,
-                        pts,
-                        baseline,
-                        BAND_LINE_WIDTH,
-                        color,
-                        fill_color,
-                        clip,
-                    );
+                        let level = ((db - floor) / span).clamp(0.0, 1.0);
+                        (geometry.x(i), baseline - level * band_height)
+                    },
+                ));
+                if let Some(&last) = pts.last() {
+                    pts.push((geometry.right_edge, last.1));
                 }
+                extend_filled_line(
+                    vertices,
+                    pts,
+                    baseline,
+                    BAND_LINE_WIDTH,
+                    color,
+                    fill_color,
+                    clip,
+                );
             }
         }
     }
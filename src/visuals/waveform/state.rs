@@ -2,27 +2,103 @@
 // Copyright (C) 2026 Maika Namuo
 
 use super::processor::{
-    MAX_COLUMN_CAPACITY, NUM_BANDS, WAVEFORM_CHANNELS, WaveFrame, WaveformPreview,
-    WaveformUpdate,
+    DEFAULT_BAND_DB_FLOOR, MAX_COLUMN_CAPACITY, NUM_BANDS, WAVEFORM_CHANNELS, WaveFrame,
+    WaveformPreview, WaveformUpdate,
 };
 use super::render::{WaveformParams, WaveformPrimitive};
 use crate::persistence::settings::WaveformSettings;
-use crate::util::color::color_to_rgba;
+use crate::util::audio::{DbRange, LevelScale, sanitize_negative_db};
+use crate::util::color::{ColorBlend, color_to_rgba, with_alpha};
+use crate::util::wav;
 use crate::visuals::palettes;
+use crate::visuals::render::common::{draw_shader, faded, fill_rect, software_rendering};
 use iced::Color;
+use iced::advanced::Renderer as _;
 use std::{cell::Cell, collections::VecDeque, sync::Arc};
 
 const COLUMN_WIDTH_PIXELS: f32 = 1.0;
 const INITIAL_VIEW_COLUMNS: usize = 512;
+// Bounds the retained audio when the view spans minutes at a slow scroll.
+const MAX_CLIP_SECONDS: f32 = 120.0;
+
+/// Raw input behind the columns on screen, for exporting what is visible.
+#[derive(Debug, Default)]
+struct SampleHistory {
+    samples: VecDeque<f32>,
+    channels: usize,
+    sample_rate: f32,
+    scroll_speed: f32,
+}
+
+impl SampleHistory {
+    fn frames_for(&self, columns: f32) -> usize {
+        if self.scroll_speed <= 0.0 {
+            return 0;
+        }
+        let seconds = (columns / self.scroll_speed).min(MAX_CLIP_SECONDS);
+        (seconds * self.sample_rate).round() as usize
+    }
+
+    fn push(&mut self, update: &WaveformUpdate<'_>, view_columns: usize) {
+        if update.reset || update.channels != self.channels {
+            self.samples.clear();
+        }
+        self.channels = update.channels.max(1);
+        self.sample_rate = update.sample_rate;
+        self.scroll_speed = update.scroll_speed;
+        self.samples.extend(update.samples);
+        // One spare column covers the partially drawn preview.
+        let keep = self.frames_for(view_columns as f32 + 1.0) * self.channels;
+        self.samples.drain(..self.samples.len().saturating_sub(keep));
+    }
+
+    /// The newest `columns` worth of audio, or whatever is retained if less.
+    fn clip(&self, columns: f32) -> Option<WaveformClip> {
+        let available = self.samples.len() / self.channels.max(1);
+        let frames = self.frames_for(columns).min(available);
+        (frames > 0).then(|| WaveformClip {
+            samples: self
+                .samples
+                .range(self.samples.len() - frames * self.channels..)
+                .copied()
+                .collect(),
+            channels: self.channels,
+            sample_rate: self.sample_rate,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WaveformClip {
+    pub samples: Vec<f32>,
+    pub channels: usize,
+    pub sample_rate: f32,
+}
+
+impl WaveformClip {
+    pub fn seconds(&self) -> f32 {
+        (self.samples.len() / self.channels.max(1)) as f32 / self.sample_rate
+    }
+
+    pub fn to_wav(&self) -> Option<Vec<u8>> {
+        let channels = u16::try_from(self.channels).ok()?;
+        wav::encode_f32(&self.samples, channels, self.sample_rate.round() as u32)
+    }
+}
 
 #[derive(Debug)]
 pub(in crate::visuals) struct WaveformState {
     data: Arc<VecDeque<WaveFrame>>,
     preview: WaveformPreview,
     view_columns: Cell<usize>,
+    history: SampleHistory,
     pub(in crate::visuals) style: WaveformStyle,
     settings: WaveformSettings,
+    // The shared waveform range, before the band floor.
+    level_range: DbRange,
     key: u64,
+    opacity: f32,
+    pulse: f32,
 }
 
 impl WaveformState {
@@ -31,13 +107,21 @@ impl WaveformState {
             data: Arc::new(VecDeque::with_capacity(INITIAL_VIEW_COLUMNS)),
             preview: WaveformPreview::default(),
             view_columns: Cell::new(INITIAL_VIEW_COLUMNS),
+            history: SampleHistory::default(),
             style: WaveformStyle::default(),
             settings: WaveformSettings::default(),
+            level_range: LevelScale::Waveform.default_range(),
             key: crate::visuals::next_key(),
+            opacity: 1.0,
+            pulse: 0.0,
         }
     }
 
     pub fn apply_snapshot(&mut self, update: WaveformUpdate<'_>) {
+        self.history.push(&update, self.view_columns.get());
+        if update.clicks > 0 {
+            tracing::info!("[waveform] {} click(s) in the capture", update.clicks);
+        }
         self.preview = update.preview;
         if !update.reset && update.columns.is_empty() {
             return;
@@ -54,6 +138,12 @@ impl WaveformState {
         self.view_columns.get()
     }
 
+    /// Audio for the columns currently on screen, newest last.
+    pub fn visible_clip(&self) -> Option<WaveformClip> {
+        let drawn = self.data.len() as f32 + self.preview.progress;
+        self.history.clip(drawn.min(self.view_columns.get() as f32))
+    }
+
     pub fn update_view_settings(&mut self, settings: &WaveformSettings) {
         self.settings = settings.clone();
     }
@@ -62,10 +152,26 @@ impl WaveformState {
         self.settings.clone()
     }
 
+    pub fn set_level_range(&mut self, range: DbRange) {
+        self.level_range = range;
+    }
+
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity;
+    }
+
+    pub fn set_pulse(&mut self, pulse: f32) {
+        self.pulse = pulse;
+    }
+
     pub fn set_palette(&mut self, palette: &[Color; NUM_BANDS]) {
         self.style.palette = *palette;
     }
 
+    pub fn set_palette_blend(&mut self, blend: ColorBlend) {
+        self.style.palette_blend = blend;
+    }
+
     pub fn visual_params(&self, bounds: iced::Rectangle) -> Option<WaveformParams> {
         let needed = ((bounds.width / COLUMN_WIDTH_PIXELS).ceil() as usize)
             .clamp(1, MAX_COLUMN_CAPACITY);
@@ -83,6 +189,7 @@ impl WaveformState {
         }
 
         let lanes = &lanes[..selected_channels];
+        let band_floor = sanitize_negative_db(self.settings.band_db_floor, DEFAULT_BAND_DB_FLOOR);
 
         Some(WaveformParams {
             bounds,
@@ -94,13 +201,17 @@ impl WaveformState {
             preview: self.preview,
             color_mode: self.settings.color_mode,
             history_mode: self.settings.history_mode,
-            band_db_floor: self.settings.band_db_floor,
+            rms_layer: self.settings.rms_layer,
+            band_levels: self.level_range.with_floor(band_floor),
             palette: self.style.palette.map(color_to_rgba),
+            palette_blend: self.style.palette_blend,
             fill_alpha: self.style.fill_alpha,
             vertical_padding: self.style.vertical_padding,
             channel_gap: self.style.channel_gap,
             amplitude_scale: self.style.amplitude_scale,
             key: self.key,
+            opacity: self.opacity,
+            pulse: self.pulse,
         })
     }
 
@@ -145,7 +256,71 @@ crate::macros::default_struct! {
         pub channel_gap: f32 = 12.0,
         pub amplitude_scale: f32 = 1.0,
         pub(in crate::visuals) palette: [Color; NUM_BANDS] = palettes::waveform::COLORS,
+        pub(in crate::visuals) palette_blend: ColorBlend = ColorBlend::Srgb,
     }
 }
 
-crate::visuals::visualization_widget!(Waveform, WaveformState, WaveformPrimitive);
+crate::visuals::visualization_widget!(Waveform, WaveformState, |this, renderer, theme, bounds| {
+    let state = this.state.borrow();
+    let Some(params) = state.visual_params(bounds) else {
+        let background = theme.extended_palette().background.base.color;
+        fill_rect(renderer, bounds, faded(background, state.opacity));
+        return;
+    };
+    let mut clicks = Vec::new();
+    params.for_each_click(|rect| clicks.push(rect));
+    if software_rendering() {
+        params.for_each_column(|rect, color| {
+            fill_rect(renderer, rect, faded(Color::from(color), state.opacity))
+        });
+    } else {
+        draw_shader(renderer, theme, bounds, WaveformPrimitive::new(params));
+    }
+    if !clicks.is_empty() {
+        let color = with_alpha(theme.extended_palette().danger.base.color, 0.8 * state.opacity);
+        renderer.with_layer(bounds, |renderer| {
+            for rect in clicks {
+                fill_rect(renderer, rect, color);
+            }
+        });
+    }
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(samples: &[f32], channels: usize, reset: bool) -> WaveformUpdate<'_> {
+        WaveformUpdate {
+            reset,
+            columns: &[],
+            preview: WaveformPreview::default(),
+            samples,
+            channels,
+            sample_rate: 1_000.0,
+            scroll_speed: 100.0,
+            clicks: 0,
+        }
+    }
+
+    #[test]
+    fn history_keeps_the_visible_window_and_clips_its_newest_frames() {
+        let mut history = SampleHistory::default();
+        let ramp: Vec<f32> = (0..400).map(|n| n as f32).collect();
+        // 10 columns at 100 px/s is 0.1 s: 100 frames, plus one spare column.
+        history.push(&update(&ramp, 2, true), 10);
+        assert_eq!(history.samples.len(), 220);
+
+        let clip = history.clip(5.0).unwrap();
+        assert_eq!((clip.channels, clip.samples.len()), (2, 100));
+        assert_eq!(clip.samples.first(), Some(&300.0));
+        assert_eq!(clip.samples.last(), Some(&399.0));
+        assert!((clip.seconds() - 0.05).abs() < 1e-6);
+        assert_eq!(history.clip(50.0).unwrap().samples.len(), 220);
+
+        // A new channel layout drops audio that no longer matches.
+        history.push(&update(&[0.5; 3], 1, false), 10);
+        assert_eq!(history.clip(10.0).unwrap().samples, [0.5; 3]);
+        assert!(history.clip(0.0).is_none());
+    }
+}